@@ -0,0 +1,206 @@
+//! Criterion benchmarks for the search hot paths
+//!
+//! Every benchmark runs on the same canonical positions (opening, midgame duel, crowded
+//! four-snake board), so a performance refactor can be compared against a saved baseline:
+//!
+//!   cargo bench --bench hot_paths -- --save-baseline before
+//!   (change)
+//!   cargo bench --bench hot_paths -- --baseline before
+//!
+//! Evaluation runs with the eval cache disabled so the distance field is measured on every
+//! call, and the search runs at a fixed depth so its node count does not depend on the host.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use starter_snake_rust::bot::{Bot, HistoryTable, SearchLimits, SharedSearchState};
+use starter_snake_rust::config::Config;
+use starter_snake_rust::distance_field::DistanceField;
+use starter_snake_rust::types::{Battlesnake, Board, Coord, Direction};
+use std::sync::Arc;
+
+fn snake(id: &str, health: i32, body: &[(i32, i32)]) -> Battlesnake {
+    let body: Vec<Coord> = body.iter().map(|&(x, y)| Coord { x, y }).collect();
+    Battlesnake {
+        id: id.to_string(),
+        name: id.to_string(),
+        health,
+        head: body[0],
+        length: body.len() as i32,
+        body,
+        latency: "0".to_string(),
+        shout: None,
+        squad: None,
+    }
+}
+
+fn board(snakes: Vec<Battlesnake>, food: &[(i32, i32)]) -> Board {
+    Board {
+        height: 11,
+        width: 11,
+        food: food.iter().map(|&(x, y)| Coord { x, y }).collect(),
+        snakes,
+        hazards: vec![],
+        occupancy: None,
+    }
+}
+
+/// Canonical positions; the first snake of each is "us"
+fn positions() -> Vec<(&'static str, Board)> {
+    let opening = board(
+        vec![
+            snake("us", 100, &[(1, 1), (1, 1), (1, 1)]),
+            snake("b", 100, &[(9, 1), (9, 1), (9, 1)]),
+            snake("c", 100, &[(1, 9), (1, 9), (1, 9)]),
+            snake("d", 100, &[(9, 9), (9, 9), (9, 9)]),
+        ],
+        &[(2, 2), (8, 2), (2, 8), (8, 8), (5, 5)],
+    );
+    let duel = board(
+        vec![
+            snake("us", 70, &[(5, 5), (5, 4), (5, 3), (4, 3), (3, 3), (3, 4), (3, 5), (3, 6)]),
+            snake("them", 55, &[(7, 7), (7, 8), (8, 8), (9, 8), (9, 7), (9, 6), (9, 5)]),
+        ],
+        &[(1, 1), (9, 1), (5, 9)],
+    );
+    let crowded = board(
+        vec![
+            snake("us", 80, &[(2, 2), (2, 3), (2, 4), (3, 4), (4, 4)]),
+            snake("b", 60, &[(8, 2), (9, 2), (9, 3), (9, 4)]),
+            snake("c", 90, &[(2, 8), (2, 9), (3, 9), (4, 9), (5, 9), (6, 9)]),
+            snake("d", 45, &[(8, 8), (7, 8), (7, 7), (7, 6), (8, 6)]),
+        ],
+        &[(5, 5), (0, 10), (10, 0), (6, 3)],
+    );
+    vec![("opening", opening), ("duel", duel), ("crowded", crowded)]
+}
+
+fn config() -> Config {
+    let mut config = Config::default_hardcoded();
+    config.eval_cache.enabled = false;
+    config
+}
+
+fn bench_move_generation(c: &mut Criterion) {
+    let config = config();
+    let mut group = c.benchmark_group("generate_legal_moves");
+    for (name, board) in positions() {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for snake in &board.snakes {
+                    black_box(Bot::generate_legal_moves(black_box(&board), snake, &config));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_flood_fill(c: &mut Criterion) {
+    let mut group = c.benchmark_group("flood_fill_bfs");
+    for (name, board) in positions() {
+        let head = board.snakes[0].head;
+        group.bench_function(name, |b| b.iter(|| Bot::flood_fill_bfs(black_box(&board), head, 0, None, None)));
+    }
+    group.finish();
+}
+
+fn bench_adversarial_flood_fill(c: &mut Criterion) {
+    let mut group = c.benchmark_group("adversarial_flood_fill");
+    for (name, board) in positions() {
+        let sources: Vec<usize> = (0..board.snakes.len()).collect();
+        group.bench_function(name, |b| {
+            b.iter(|| DistanceField::build(black_box(&board), &sources, None).control_summary(board.snakes.len()))
+        });
+    }
+    group.finish();
+}
+
+fn bench_evaluate_state(c: &mut Criterion) {
+    let config = config();
+    let mut group = c.benchmark_group("evaluate_state");
+    for (name, board) in positions() {
+        group.bench_function(name, |b| b.iter(|| Bot::evaluate_all(black_box(&board), "us", &config)));
+    }
+    group.finish();
+}
+
+fn bench_apply_move(c: &mut Criterion) {
+    let config = config();
+    let mut group = c.benchmark_group("apply_move");
+    for (name, board) in positions() {
+        let moves: Vec<Direction> = board
+            .snakes
+            .iter()
+            .map(|s| Bot::generate_legal_moves(&board, s, &config).first().copied().unwrap_or(Direction::Up))
+            .collect();
+        group.bench_function(name, |b| {
+            b.iter_batched_ref(
+                || board.clone(),
+                |board| {
+                    for (idx, &dir) in moves.iter().enumerate() {
+                        Bot::apply_move(board, idx, dir, &config);
+                    }
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn bench_search(c: &mut Criterion) {
+    let config = config();
+    let mut group = c.benchmark_group("fixed_depth_search");
+    group.sample_size(10);
+    // Duels branch less, so they are searched deeper in comparable time
+    for ((name, board), depth) in positions().into_iter().zip([3u8, 5, 3]) {
+        let you = board.snakes[0].clone();
+        group.bench_function(format!("{}_depth{}", name, depth), |b| {
+            b.iter(|| {
+                let shared = Arc::new(SharedSearchState::new());
+                let mut history = HistoryTable::new(board.width as u32, board.height);
+                let limits = SearchLimits::from_config(&config).with_fixed_depth(Some(depth));
+                Bot::compute_best_move_internal(&board, &you, 0, shared.clone(), &config, &mut history, limits);
+                shared.get_best()
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Parallel search with and without the second-ply split (strategy.split_second_ply)
+/// The split only applies with more rayon threads than root moves, so on hosts with few cores
+/// both variants measure the same one-task-per-root-move search
+fn bench_second_ply_split(c: &mut Criterion) {
+    let mut group = c.benchmark_group("second_ply_split");
+    group.sample_size(10);
+    for ((name, board), depth) in positions().into_iter().zip([3u8, 5, 3]).skip(1) {
+        let you = board.snakes[0].clone();
+        for split in [false, true] {
+            let mut config = config();
+            config.strategy.split_second_ply = split;
+            let variant = if split { "split" } else { "root_only" };
+            group.bench_function(format!("{}_depth{}_{}", name, depth, variant), |b| {
+                b.iter(|| {
+                    let shared = Arc::new(SharedSearchState::new());
+                    let mut history = HistoryTable::new(board.width as u32, board.height);
+                    let limits = SearchLimits::from_config(&config).with_fixed_depth(Some(depth));
+                    Bot::compute_best_move_internal(&board, &you, 0, shared.clone(), &config, &mut history, limits);
+                    shared.get_best()
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_move_generation,
+    bench_flood_fill,
+    bench_adversarial_flood_fill,
+    bench_evaluate_state,
+    bench_apply_move,
+    bench_search,
+    bench_second_ply_split
+);
+criterion_main!(benches);
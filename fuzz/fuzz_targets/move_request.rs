@@ -0,0 +1,33 @@
+//! Fuzz target: /move request bodies
+//!
+//! Feeds arbitrary bytes through the same steps as the /move handler before the search:
+//! JSON deserialization into a GameState (RawGameState) and request_validation::sanitize.
+//! Neither may panic, and a state sanitize accepts must hold what the Bot relies on.
+//!
+//! Usage:
+//!   cargo +nightly fuzz run move_request
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use starter_snake_rust::config::Config;
+use starter_snake_rust::request_validation;
+use starter_snake_rust::types::GameState;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(raw) = std::str::from_utf8(data) else { return };
+    let Ok(mut state) = serde_json::from_str::<GameState>(raw) else { return };
+    let config = Config::default_hardcoded();
+    if request_validation::sanitize(&mut state, &config).is_err() {
+        return;
+    }
+
+    let board = &state.board;
+    let in_bounds = |c: &starter_snake_rust::types::Coord| c.x >= 0 && c.y >= 0 && c.x < board.width && c.y < board.height as i32;
+    assert!(board.width > 0 && board.height > 0);
+    assert!(board.food.iter().all(in_bounds) && board.hazards.iter().all(in_bounds));
+    assert!(board.snakes.iter().all(|s| !s.body.is_empty() && s.head == s.body[0] && s.length == s.body.len() as i32));
+    let you = board.snakes.iter().find(|s| s.id == state.you.id).expect("'you' on the board");
+    assert!(you.body.iter().all(in_bounds));
+    assert_eq!(you.body, state.you.body);
+});
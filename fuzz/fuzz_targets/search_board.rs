@@ -0,0 +1,132 @@
+//! Fuzz target: adversarial boards into the search
+//!
+//! Builds structurally valid positions from the fuzzer's bytes: any board size up to
+//! request_validation.max_board_dimension, bodies walked over adjacent cells that may cross
+//! themselves, other snakes, food and hazards or stack in place, health from 0 to 100 and any
+//! game mode. Each position goes through request_validation::sanitize like a /move request and
+//! then into Bot::compute_best_move_internal with a node budget of at most 64, stopped like a
+//! /move search once BUDGET_MS is spent. The search must not panic.
+//!
+//! Usage:
+//!   cargo +nightly fuzz run search_board
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use starter_snake_rust::bot::{Bot, HistoryTable, SearchLimits, SharedSearchState};
+use starter_snake_rust::config::Config;
+use starter_snake_rust::game_mode::GameMode;
+use starter_snake_rust::hazard_walls::HazardWalls;
+use starter_snake_rust::request_validation;
+use starter_snake_rust::squad::SquadRules;
+use starter_snake_rust::types::{Battlesnake, Board, Coord, Direction, Game, GameState, Ruleset};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Arbitrary)]
+struct FuzzSnake {
+    start: (u8, u8),
+    /// One step per byte: 0-3 move in Direction::ALL order (staying put at the edge), others stack
+    path: Vec<u8>,
+    health: u8,
+    squad: bool,
+}
+
+#[derive(Debug, Arbitrary)]
+struct FuzzPosition {
+    width: u8,
+    height: u8,
+    turn: u16,
+    mode: u8,
+    snakes: Vec<FuzzSnake>,
+    food: Vec<(u8, u8)>,
+    hazards: Vec<(u8, u8)>,
+    max_nodes: u8,
+}
+
+/// Search budget per input, as a tight /move would have
+const BUDGET_MS: u64 = 50;
+
+const MODES: [GameMode; 8] = [
+    GameMode::Standard,
+    GameMode::Solo,
+    GameMode::Royale,
+    GameMode::Constrictor,
+    GameMode::Wrapped,
+    GameMode::Squad(SquadRules { allow_body_collisions: true, shared_elimination: true, shared_health: true, shared_length: true }),
+    GameMode::Maze,
+    GameMode::Snail,
+];
+
+fn position(input: &FuzzPosition, max_dimension: i32) -> Option<GameState> {
+    let (width, height) = (1 + input.width as i32 % max_dimension, 1 + input.height as i32 % max_dimension);
+    let cell = |(x, y): (u8, u8)| Coord { x: x as i32 % width, y: y as i32 % height };
+    let snakes: Vec<Battlesnake> = input
+        .snakes
+        .iter()
+        .take(8)
+        .enumerate()
+        .map(|(i, s)| {
+            let mut body = vec![cell(s.start)];
+            for &step in s.path.iter().take(255) {
+                let last = body[body.len() - 1];
+                let next = Direction::ALL.get(step as usize).map(|d| d.apply(&last)).unwrap_or(last);
+                let inside = next.x >= 0 && next.y >= 0 && next.x < width && next.y < height;
+                body.push(if inside { next } else { last });
+            }
+            Battlesnake {
+                id: format!("s{}", i),
+                name: format!("s{}", i),
+                health: s.health as i32 % 101,
+                head: body[0],
+                length: body.len() as i32,
+                body,
+                latency: "0".to_string(),
+                shout: None,
+                squad: Some(if s.squad { "red" } else { "blue" }.to_string()),
+            }
+        })
+        .collect();
+    let you = snakes.first()?.clone();
+    Some(GameState {
+        game: Game { id: "fuzz".to_string(), ruleset: Ruleset::default(), timeout: 500, map: String::new() },
+        turn: input.turn as i32,
+        board: Board {
+            height: height as u32,
+            width,
+            food: input.food.iter().take(64).map(|&c| cell(c)).collect(),
+            snakes,
+            hazards: input.hazards.iter().take(256).map(|&c| cell(c)).collect(),
+            occupancy: None,
+        },
+        you,
+    })
+}
+
+fuzz_target!(|input: FuzzPosition| {
+    let base = Config::default_hardcoded();
+    let Some(mut state) = position(&input, base.request_validation.max_board_dimension) else { return };
+    if request_validation::sanitize(&mut state, &base).is_err() {
+        return;
+    }
+
+    // The mode reaches the search through the config, as in Bot::get_move
+    let mode = MODES[input.mode as usize % MODES.len()];
+    let hazard_walls = (mode == GameMode::Maze && !state.board.hazards.is_empty()).then(|| Arc::new(HazardWalls::new(&state.board)));
+    let config = Config { game_mode: mode, hazard_walls, ..base };
+
+    // Searched like a /move: on its own thread, stopped once the budget is spent
+    let shared = Arc::new(SharedSearchState::new());
+    let mut history = HistoryTable::new(state.board.width as u32, state.board.height);
+    let limits = SearchLimits::default().with_max_nodes(Some(1 + input.max_nodes as u64 % 64)).with_budget_ms(Some(BUDGET_MS));
+    let start = Instant::now();
+    std::thread::scope(|scope| {
+        let search = scope.spawn(|| Bot::compute_best_move_internal(&state.board, &state.you, state.turn, shared.clone(), start, &config, &mut history, limits));
+        while !search.is_finished() && start.elapsed() < Duration::from_millis(BUDGET_MS) {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        shared.request_stop();
+        search.join().expect("search panicked");
+    });
+});
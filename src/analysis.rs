@@ -0,0 +1,313 @@
+// Death analysis of logged games
+//
+// analyze_deaths used to classify deaths from raw JSON, checking the head of the last logged
+// board against walls and bodies. That board is the position *before* our last move, so
+// almost everything came out as "Trapped". Here the last logged move is played forward with
+// rules::resolve_turn against every combination of opponent replies: the cause is the
+// elimination seen in most replies that kill us, and the position is measured by the open
+// cells connected to our head (space) and the open neighbouring cells (escape routes). Our snake is the
+// first one on the board, as in ReplayEngine::replay_entry. Shared by the analyze_deaths
+// binary and the replay report. At /end the final board is known too, so
+// analyze_death_observed fixes the surviving opponents to the moves they played (see
+// src/post_mortem.rs).
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::config::Config;
+use crate::replay::{EntryFilter, LogEntry, ReplayEngine};
+use crate::rules::{self, EliminationCause, RulesSettings};
+use crate::types::{Board, Coord, Direction};
+
+/// Opponents whose replies are enumerated (4^n boards); the rest continue straight
+const MAX_ENUMERATED_OPPONENTS: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DeathCause {
+    /// Health reached 0
+    Starvation,
+    WallCollision,
+    SelfCollision,
+    /// Ran into the body of the snake with this id
+    OpponentCollision(String),
+    /// Lost a head-to-head against the snake with this id
+    HeadToHead(String),
+    /// Fell with the squad-mate with this id
+    SquadMate(String),
+    /// Every neighbouring cell was blocked
+    Trapped,
+    /// The last move does not lose in any opponent reply (e.g. a truncated log)
+    Unknown,
+}
+
+impl DeathCause {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeathCause::Starvation => "Starvation",
+            DeathCause::WallCollision => "Wall Collision",
+            DeathCause::SelfCollision => "Self Collision",
+            DeathCause::OpponentCollision(_) => "Opponent Collision",
+            DeathCause::HeadToHead(_) => "Head-to-Head Loss",
+            DeathCause::SquadMate(_) => "Squad-Mate Eliminated",
+            DeathCause::Trapped => "Trapped (No Legal Moves)",
+            DeathCause::Unknown => "Unknown",
+        }
+    }
+
+    fn from_elimination(cause: &EliminationCause) -> Self {
+        match cause {
+            EliminationCause::OutOfHealth => DeathCause::Starvation,
+            EliminationCause::OutOfBounds => DeathCause::WallCollision,
+            EliminationCause::SelfCollision => DeathCause::SelfCollision,
+            EliminationCause::Collision(id) => DeathCause::OpponentCollision(id.clone()),
+            EliminationCause::HeadToHead(id) => DeathCause::HeadToHead(id.clone()),
+            EliminationCause::BySquad(id) => DeathCause::SquadMate(id.clone()),
+        }
+    }
+}
+
+/// How and where our snake died
+#[derive(Debug, Clone)]
+pub struct DeathAnalysis {
+    /// Last logged turn (we died resolving it)
+    pub turn: i32,
+    pub final_move: Direction,
+    pub cause: DeathCause,
+    /// Share of opponent replies in which the final move kills us
+    pub fatal_replies: f32,
+    pub health: i32,
+    pub length: i32,
+    /// Open cells connected to our head on the last logged board
+    pub space: usize,
+    /// Neighbouring cells we could still move into (in bounds, no body but a moving tail)
+    pub escape_routes: usize,
+    pub food_count: usize,
+    /// Length of the longest opponent on the last logged board
+    pub longest_opponent: i32,
+}
+
+impl DeathAnalysis {
+    /// One-line summary, e.g. "turn 41: Head-to-Head Loss vs b (moved left, health 54, ...)"
+    pub fn describe(&self) -> String {
+        let against = match &self.cause {
+            DeathCause::OpponentCollision(id) | DeathCause::HeadToHead(id) => format!(" vs {}", id),
+            DeathCause::SquadMate(id) => format!(" ({})", id),
+            _ => String::new(),
+        };
+        format!(
+            "turn {}: {}{} (moved {} into {:.0}% fatal replies; health {}, length {}, space {}, {} escape route(s))",
+            self.turn,
+            self.cause.as_str(),
+            against,
+            self.final_move.as_str(),
+            self.fatal_replies * 100.0,
+            self.health,
+            self.length,
+            self.space,
+            self.escape_routes
+        )
+    }
+}
+
+/// In bounds and not covered by a body segment that stays put this turn
+fn is_open(board: &Board, c: Coord) -> bool {
+    c.x >= 0
+        && c.y >= 0
+        && c.x < board.width
+        && c.y < board.height as i32
+        && board.snakes.iter().all(|s| {
+            // A tail moves away unless the snake is stacked on it (just ate)
+            let tail_moves = s.body.len() >= 2 && s.body[s.body.len() - 1] != s.body[s.body.len() - 2];
+            let solid = if tail_moves { &s.body[..s.body.len() - 1] } else { &s.body[..] };
+            !solid.contains(&c)
+        })
+}
+
+/// Neighbouring cells of `head` a snake could enter this turn
+fn escape_routes(board: &Board, head: Coord) -> usize {
+    Direction::ALL.iter().filter(|dir| is_open(board, dir.apply(&head))).count()
+}
+
+/// Open cells connected to `head` on the board as it stands (bodies do not move away, unlike
+/// the search's time-aware Bot::flood_fill_bfs, which sees most of an open board)
+fn reachable_space(board: &Board, head: Coord) -> usize {
+    let mut seen = vec![head];
+    let mut next_idx = 0;
+    while next_idx < seen.len() {
+        let pos = seen[next_idx];
+        next_idx += 1;
+        for next in pos.neighbors() {
+            if is_open(board, next) && !seen.contains(&next) {
+                seen.push(next);
+            }
+        }
+    }
+    seen.len() - 1
+}
+
+/// Every combination of moves for the first MAX_ENUMERATED_OPPONENTS opponents
+fn reply_combinations(opponents: &[String]) -> Vec<HashMap<String, Direction>> {
+    let mut combinations = vec![HashMap::new()];
+    for id in opponents.iter().take(MAX_ENUMERATED_OPPONENTS) {
+        let mut extended = Vec::with_capacity(combinations.len() * 4);
+        for moves in &combinations {
+            for &dir in Direction::ALL.iter() {
+                let mut moves: HashMap<String, Direction> = moves.clone();
+                moves.insert(id.clone(), dir);
+                extended.push(moves);
+            }
+        }
+        combinations = extended;
+    }
+    combinations
+}
+
+/// Why our snake (the first on `board`) dies playing `final_move`
+///
+/// Returns None if the board has no snakes.
+pub fn analyze_death(board: &Board, turn: i32, final_move: Direction, config: &Config) -> Option<DeathAnalysis> {
+    classify(board, turn, final_move, &HashMap::new(), config)
+}
+
+/// Like analyze_death, with `next` the board after the fatal turn (the final board at /end)
+///
+/// Opponents still on `next` played the move their head shows; only the replies of those
+/// that died with us are enumerated. Falls back to analyze_death if the observed moves do
+/// not kill us (a board that does not follow from `board`).
+pub fn analyze_death_observed(board: &Board, next: &Board, turn: i32, final_move: Direction, config: &Config) -> Option<DeathAnalysis> {
+    let observed: HashMap<String, Direction> = board
+        .snakes
+        .iter()
+        .skip(1)
+        .filter_map(|snake| {
+            let after = next.our_snake(&snake.id)?;
+            Some((snake.id.clone(), rules::direction_between(snake.head, after.head)?))
+        })
+        .collect();
+    classify(board, turn, final_move, &observed, config)
+        .filter(|death| death.cause != DeathCause::Unknown)
+        .or_else(|| analyze_death(board, turn, final_move, config))
+}
+
+/// analyze_death with the opponents in `known` fixed to their move
+fn classify(board: &Board, turn: i32, final_move: Direction, known: &HashMap<String, Direction>, config: &Config) -> Option<DeathAnalysis> {
+    let us = board.snakes.first()?;
+    let head = us.body.first().copied()?;
+    let settings = RulesSettings::from_config(config);
+    let opponents: Vec<String> = board.snakes.iter().skip(1).map(|s| s.id.clone()).filter(|id| !known.contains_key(id)).collect();
+
+    // Tally our elimination cause over all opponent replies
+    let combinations = reply_combinations(&opponents);
+    let mut causes: Vec<(DeathCause, usize)> = Vec::new();
+    for mut moves in combinations.iter().cloned() {
+        moves.extend(known.iter().map(|(id, &dir)| (id.clone(), dir)));
+        moves.insert(us.id.clone(), final_move);
+        let mut next = board.clone();
+        let eliminations = rules::resolve_turn(&mut next, &moves, &settings);
+        if let Some(ours) = eliminations.iter().find(|e| e.snake_id == us.id) {
+            let cause = DeathCause::from_elimination(&ours.cause);
+            match causes.iter_mut().find(|(c, _)| *c == cause) {
+                Some((_, count)) => *count += 1,
+                None => causes.push((cause, 1)),
+            }
+        }
+    }
+    let fatal: usize = causes.iter().map(|(_, count)| count).sum();
+
+    let routes = escape_routes(board, head);
+    let cause = if routes == 0 {
+        DeathCause::Trapped
+    } else {
+        // Most frequent cause, first seen on ties
+        causes
+            .iter()
+            .fold(None::<&(DeathCause, usize)>, |best, c| match best {
+                Some(b) if b.1 >= c.1 => Some(b),
+                _ => Some(c),
+            })
+            .map_or(DeathCause::Unknown, |(cause, _)| cause.clone())
+    };
+
+    Some(DeathAnalysis {
+        turn,
+        final_move,
+        cause,
+        fatal_replies: fatal as f32 / combinations.len() as f32,
+        health: us.health,
+        length: us.length,
+        space: reachable_space(board, head),
+        escape_routes: routes,
+        food_count: board.food.len(),
+        longest_opponent: board.snakes.iter().skip(1).map(|s| s.length).max().unwrap_or(0),
+    })
+}
+
+/// Death analysis of a logged game; None if we won (see EntryFilter::death_turn)
+pub fn analyze_game(entries: &[LogEntry], config: &Config) -> Result<Option<DeathAnalysis>, String> {
+    let death_turn = match EntryFilter::death_turn(entries) {
+        Some(turn) => turn,
+        None => return Ok(None),
+    };
+    let last = entries
+        .iter()
+        .find(|e| e.turn == death_turn)
+        .ok_or_else(|| format!("Turn {} not found in log", death_turn))?;
+    let final_move = ReplayEngine::parse_direction(&last.chosen_move)?;
+    Ok(analyze_death(&last.board, last.turn, final_move, config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board_builder::parse_board;
+
+    fn analyze(ascii: &str, final_move: Direction) -> DeathAnalysis {
+        let board = parse_board(ascii).unwrap();
+        analyze_death(&board, 10, final_move, &Config::default_hardcoded()).unwrap()
+    }
+
+    #[test]
+    fn test_causes_come_from_playing_the_last_move() {
+        let open = "
+            . . . . .
+            . . . . .
+            a> a a . .
+            . . . . b>
+            . . . . b
+        ";
+        let wall = analyze(open, Direction::Left);
+        assert_eq!(wall.cause, DeathCause::WallCollision);
+        assert_eq!(wall.fatal_replies, 1.0);
+        assert_eq!(wall.escape_routes, 2);
+        assert_eq!(wall.space, 25 - 3, "every cell but the heads and our middle segment");
+
+        let duel = "
+            . . . . .
+            . . . b> b
+            . . a> . b
+            . . a . .
+            . . a . .
+        ";
+        let head_on = analyze(duel, Direction::Up);
+        assert_eq!(head_on.cause, DeathCause::HeadToHead("b".to_string()));
+        assert_eq!(head_on.fatal_replies, 0.25);
+
+        assert!(head_on.describe().starts_with("turn 10: Head-to-Head Loss vs b (moved up into 25% fatal replies"));
+
+        let reverse = analyze(duel, Direction::Down);
+        assert_eq!(reverse.cause, DeathCause::SelfCollision);
+        assert_eq!(reverse.fatal_replies, 1.0);
+    }
+
+    #[test]
+    fn test_boxed_in_snake_is_trapped() {
+        let boxed = "
+            b  b  b
+            b  a> b>
+            b  a  a
+        ";
+        let analysis = analyze(boxed, Direction::Up);
+        assert_eq!(analysis.cause, DeathCause::Trapped);
+        assert_eq!(analysis.escape_routes, 0);
+    }
+}
@@ -0,0 +1,280 @@
+// Local self-play games between configs
+//
+// Tuning score weights against recorded games only measures agreement with past decisions.
+// The arena plays whole games instead: every snake is driven by its own Config through the
+// same search and veto layers as a live /move (via ReplayEngine, at a fixed depth so a game
+// depends only on the configs and the seed), and turns are resolved by rules::resolve_turn.
+// Starting positions and food follow the standard ruleset; food spawns are seeded. A config
+// can also play scripted opponents (see opponents/) instead of other configs.
+
+use rand::rngs::StdRng;
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::SeedableRng;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+use crate::config::{ArenaConfig, Config, RatingConfig};
+use crate::opponents::Opponent;
+use crate::rating::{MatchResult, Sprt, SprtDecision, SprtStatus};
+use crate::replay::ReplayEngine;
+use crate::rules::{self, Elimination, RulesSettings};
+use crate::types::{Battlesnake, Board, Coord, Direction};
+
+/// Result of one arena game
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameRecord {
+    /// Turns played
+    pub turns: i32,
+    /// Index of the sole survivor; None if everyone died or max_turns was reached
+    pub winner: Option<usize>,
+    /// (player index, turn, elimination) in the order they happened
+    pub eliminations: Vec<(usize, i32, Elimination)>,
+}
+
+fn snake_id(player: usize) -> String {
+    format!("player-{}", player)
+}
+
+/// Standard starting squares: corners first, then edge midpoints, each group shuffled
+fn starting_positions(size: i32, rng: &mut StdRng) -> Vec<Coord> {
+    let (low, mid, high) = (1, size / 2, size - 2);
+    let mut corners = vec![
+        Coord { x: low, y: low },
+        Coord { x: low, y: high },
+        Coord { x: high, y: low },
+        Coord { x: high, y: high },
+    ];
+    let mut edges = vec![
+        Coord { x: low, y: mid },
+        Coord { x: mid, y: low },
+        Coord { x: high, y: mid },
+        Coord { x: mid, y: high },
+    ];
+    corners.shuffle(rng);
+    edges.shuffle(rng);
+    corners.extend(edges);
+    corners
+}
+
+/// Board for `players` snakes: length 3 stacked on their start, one food diagonally toward
+/// the centre of each, and one food in the centre
+pub fn initial_board(players: usize, arena: &ArenaConfig, rng: &mut StdRng) -> Result<Board, String> {
+    let size = arena.board_size;
+    let starts = starting_positions(size, rng);
+    if players > starts.len() {
+        return Err(format!("at most {} snakes fit the standard starting positions", starts.len()));
+    }
+
+    let centre = Coord { x: size / 2, y: size / 2 };
+    let mut food = Vec::new();
+    let mut snakes = Vec::new();
+    for (player, &start) in starts.iter().take(players).enumerate() {
+        let toward = |from: i32, to: i32, rng: &mut StdRng| match to.cmp(&from) {
+            std::cmp::Ordering::Greater => 1,
+            std::cmp::Ordering::Less => -1,
+            std::cmp::Ordering::Equal => *[-1, 1].choose(rng).unwrap_or(&1),
+        };
+        let dx = toward(start.x, centre.x, rng);
+        let dy = toward(start.y, centre.y, rng);
+        food.push(Coord { x: start.x + dx, y: start.y + dy });
+
+        snakes.push(Battlesnake {
+            id: snake_id(player),
+            name: snake_id(player),
+            health: 100,
+            body: vec![start; 3],
+            head: start,
+            length: 3,
+            latency: "0".to_string(),
+            shout: None,
+            squad: None,
+        });
+    }
+    if !snakes.iter().any(|s| s.head == centre) {
+        food.push(centre);
+    }
+
+    Ok(Board { height: size as u32, width: size, food, snakes, hazards: vec![], occupancy: None })
+}
+
+/// Plays one game from the standard setup; `choose(player, board, snake, turn)` gives the
+/// move of every alive snake
+fn play(
+    players: usize,
+    arena: &ArenaConfig,
+    settings: &RulesSettings,
+    seed: u64,
+    mut choose: impl FnMut(usize, &Board, &Battlesnake, i32) -> Result<Direction, String>,
+) -> Result<GameRecord, String> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut board = initial_board(players, arena, &mut rng)?;
+
+    let mut eliminations = Vec::new();
+    let mut turn = 0;
+    while turn < arena.max_turns && board.snakes.len() > usize::from(players > 1) {
+        let mut moves: HashMap<String, Direction> = HashMap::new();
+        for player in 0..players {
+            let id = snake_id(player);
+            if let Some(snake) = board.our_snake(&id) {
+                let dir = choose(player, &board, snake, turn)?;
+                moves.insert(id, dir);
+            }
+        }
+
+        for elimination in rules::resolve_turn(&mut board, &moves, settings) {
+            let player = elimination.snake_id.trim_start_matches("player-").parse().unwrap_or(0);
+            eliminations.push((player, turn, elimination));
+        }
+        rules::spawn_food(&mut board, arena, &mut rng);
+        turn += 1;
+    }
+
+    let winner = match board.snakes.as_slice() {
+        [survivor] if players > 1 => survivor.id.trim_start_matches("player-").parse().ok(),
+        _ => None,
+    };
+    Ok(GameRecord { turns: turn, winner, eliminations })
+}
+
+/// Plays one game; `players[i]` drives the snake "player-i"
+pub fn play_game(players: &[&Config], arena: &ArenaConfig, seed: u64) -> Result<GameRecord, String> {
+    let engines: Vec<ReplayEngine> = players
+        .iter()
+        .map(|config| ReplayEngine::new((*config).clone(), false).with_fixed_depth(Some(arena.search_depth)))
+        .collect();
+    // All snakes play under the same rules; take them from the first config
+    let settings = RulesSettings::from_config(players[0]);
+    play(players.len(), arena, &settings, seed, |player, board, snake, turn| {
+        engines[player].replay_turn(board, &snake.id, turn).map(|(dir, _, _, _)| dir).map_err(String::from)
+    })
+}
+
+/// Plays one game of `config` (as "player-0") against scripted opponents ("player-1", ...)
+pub fn play_vs_opponents(
+    config: &Config,
+    opponents: &mut [Box<dyn Opponent>],
+    arena: &ArenaConfig,
+    seed: u64,
+) -> Result<GameRecord, String> {
+    let engine = ReplayEngine::new(config.clone(), false).with_fixed_depth(Some(arena.search_depth));
+    let settings = RulesSettings::from_config(config);
+    play(opponents.len() + 1, arena, &settings, seed, |player, board, snake, turn| match player {
+        0 => engine.replay_turn(board, &snake.id, turn).map(|(dir, _, _, _)| dir).map_err(String::from),
+        _ => Ok(opponents[player - 1].choose(board, snake)),
+    })
+}
+
+/// Plays `games` duels between `a` and `b` in parallel and scores them for `a`
+/// Seats alternate and each pair of games shares a seed, so both configs get the same starts
+pub fn play_match(a: &Config, b: &Config, arena: &ArenaConfig, games: u32, seed: u64) -> Result<MatchResult, String> {
+    let records: Vec<(bool, GameRecord)> = (0..games)
+        .into_par_iter()
+        .map(|game| {
+            let a_first = game % 2 == 0;
+            let players = if a_first { [a, b] } else { [b, a] };
+            play_game(&players, arena, seed.wrapping_add(u64::from(game / 2))).map(|record| (a_first, record))
+        })
+        .collect::<Result<_, String>>()?;
+
+    let mut result = MatchResult::default();
+    for (a_first, record) in records {
+        let a_seat = if a_first { 0 } else { 1 };
+        match record.winner {
+            Some(winner) if winner == a_seat => result.wins += 1,
+            Some(_) => result.losses += 1,
+            None => result.draws += 1,
+        }
+    }
+    Ok(result)
+}
+
+/// Plays `candidate` against `baseline` in batches of rating.batch_games until the SPRT
+/// decides or rating.max_games is reached; `on_batch` sees the running total after each batch
+///
+/// # Returns
+/// * The total result (for the candidate) and the final SPRT status
+pub fn play_sprt(
+    candidate: &Config,
+    baseline: &Config,
+    arena: &ArenaConfig,
+    rating: &RatingConfig,
+    seed: u64,
+    mut on_batch: impl FnMut(&MatchResult, &SprtStatus),
+) -> Result<(MatchResult, SprtStatus), String> {
+    let sprt = Sprt::new(rating);
+    let mut total = MatchResult::default();
+    let mut status = sprt.status(&total);
+
+    while status.decision == SprtDecision::Continue && total.games() < rating.max_games {
+        let games = rating.batch_games.min(rating.max_games - total.games());
+        // Each batch continues the seed sequence so no starting position repeats
+        let batch_seed = seed.wrapping_add(u64::from(total.games() / 2));
+        total += play_match(candidate, baseline, arena, games, batch_seed)?;
+        status = sprt.status(&total);
+        on_batch(&total, &status);
+    }
+    Ok((total, status))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opponents;
+
+    fn arena() -> ArenaConfig {
+        ArenaConfig { max_turns: 40, search_depth: 1, ..Config::default_hardcoded().arena }
+    }
+
+    #[test]
+    fn test_initial_board_follows_standard_setup() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let board = initial_board(4, &arena(), &mut rng).unwrap();
+
+        assert_eq!(board.snakes.len(), 4);
+        assert_eq!(board.food.len(), 5);
+        for snake in &board.snakes {
+            assert_eq!(snake.body, vec![snake.head; 3]);
+            assert!([1, 9].contains(&snake.head.x) && [1, 9].contains(&snake.head.y), "corners first");
+        }
+        assert!(board.food.contains(&Coord { x: 5, y: 5 }));
+        assert!(initial_board(9, &arena(), &mut rng).is_err());
+    }
+
+    #[test]
+    fn test_games_are_reproducible_from_the_seed() {
+        let config = Config::default_hardcoded();
+        let first = play_game(&[&config, &config], &arena(), 3).unwrap();
+        let again = play_game(&[&config, &config], &arena(), 3).unwrap();
+
+        assert_eq!(first, again);
+        assert!(first.turns <= 40);
+        if let Some(winner) = first.winner {
+            assert!(winner < 2);
+            assert_eq!(first.eliminations.last().map(|(player, _, _)| 1 - player), Some(winner));
+        }
+    }
+
+    #[test]
+    fn test_scripted_opponents_take_the_other_seats() {
+        let config = Config::default_hardcoded();
+        let opponents = || vec![opponents::by_name("greedy", 5).unwrap(), opponents::by_name("random", 5).unwrap()];
+        let record = play_vs_opponents(&config, &mut opponents(), &arena(), 5).unwrap();
+
+        assert_eq!(record, play_vs_opponents(&config, &mut opponents(), &arena(), 5).unwrap());
+        assert!(record.winner.is_none_or(|winner| winner < 3));
+        assert!(record.eliminations.iter().all(|(player, _, _)| *player < 3));
+    }
+
+    #[test]
+    fn test_sprt_stops_at_max_games_without_a_decision() {
+        let config = Config::default_hardcoded();
+        let arena = ArenaConfig { max_turns: 20, ..arena() };
+        let rating = RatingConfig { batch_games: 2, max_games: 4, ..config.rating.clone() };
+        let mut batches = Vec::new();
+
+        let (total, status) = play_sprt(&config, &config, &arena, &rating, 11, |result, _| batches.push(result.games())).unwrap();
+        assert_eq!(batches, vec![2, 4]);
+        assert_eq!(total.games(), 4);
+        assert_eq!(status.decision, SprtDecision::Continue);
+    }
+}
@@ -31,7 +31,7 @@ fn main() {
     println!("═══════════════════════════════════════════════════════════");
     println!();
     println!("Game file: {}", game_file);
-    println!("IDAPOS head distance multiplier: {}", config.idapos.head_distance_multiplier);
+    println!("IDAPOS head distance multiplier: {}", config.idapos.late_game_head_distance_multiplier);
     println!();
 
     // Load and analyze each turn
@@ -138,8 +138,8 @@ fn determine_active_snakes(
     let our_head = snakes[our_idx].head;
 
     // Calculate locality threshold with maximum cap
-    let base_threshold = config.idapos.head_distance_multiplier * remaining_depth;
-    let locality_threshold = std::cmp::min(base_threshold, config.idapos.max_locality_distance);
+    let base_threshold = config.idapos.late_game_head_distance_multiplier * remaining_depth;
+    let locality_threshold = std::cmp::min(base_threshold, config.idapos.late_game_max_locality_distance);
 
     for (idx, snake) in snakes.iter().enumerate() {
         if idx == our_idx || snake.body.is_empty() {
@@ -5,68 +5,50 @@
 //!
 //! Usage:
 //!   cargo run --release --bin analyze_deaths -- <log_directory>
+//!   cargo run --release --bin analyze_deaths -- --post-mortems <post_mortems.jsonl>
 //!
 //! Output:
-//!   - Death cause categorization (starvation, collision, trapped)
-//!   - Final board states for each death
+//!   - Death cause categorization (starvation, collision, trapped), from replaying the
+//!     last logged move against every opponent reply (see src/analysis.rs), or as the bot
+//!     recorded it at /end with --post-mortems (see src/post_mortem.rs)
+//!   - With --post-mortems, the candidate moves of each fatal decision
+//!   - Space and escape routes at each death
 //!   - Common patterns and preventable mistakes
 //!   - Strategic recommendations
 
-use serde_json::Value;
 use std::collections::HashMap;
 use std::env;
-use std::fs::{self, File};
-use std::io::{BufRead, BufReader};
+use std::fs;
 use std::path::Path;
 
-#[derive(Debug, Clone)]
-struct DeathAnalysis {
+use starter_snake_rust::analysis::{self, DeathAnalysis};
+use starter_snake_rust::config::Config;
+use starter_snake_rust::post_mortem::PostMortem;
+use starter_snake_rust::replay::ReplayEngine;
+
+/// Outcome of one logged game
+struct GameOutcome {
     game_name: String,
     total_turns: usize,
-    winner_id: String,
-    winner_length: usize,
-    loser_id: String,
-    death_cause: DeathCause,
-    final_health: i64,
-    final_length: usize,
-    available_space: Option<usize>,
-    food_count: usize,
-}
-
-#[derive(Debug, Clone, PartialEq)]
-enum DeathCause {
-    Starvation,           // Health reached 0
-    WallCollision,        // Hit boundary
-    SelfCollision,        // Hit own body
-    OpponentCollision,    // Hit opponent's body
-    HeadToHead,           // Head-to-head with equal/longer opponent
-    Trapped,              // No legal moves available
-    Unknown,
-}
-
-impl DeathCause {
-    fn as_str(&self) -> &str {
-        match self {
-            DeathCause::Starvation => "Starvation",
-            DeathCause::WallCollision => "Wall Collision",
-            DeathCause::SelfCollision => "Self Collision",
-            DeathCause::OpponentCollision => "Opponent Collision",
-            DeathCause::HeadToHead => "Head-to-Head Loss",
-            DeathCause::Trapped => "Trapped (No Legal Moves)",
-            DeathCause::Unknown => "Unknown",
-        }
-    }
+    /// None if we won
+    death: Option<DeathAnalysis>,
 }
 
 fn main() {
     let args: Vec<String> = env::args().collect();
 
-    if args.len() < 2 {
+    if args.len() < 2 || (args[1] == "--post-mortems" && args.len() < 3) {
         eprintln!("Usage: {} <log_directory>", args[0]);
+        eprintln!("       {} --post-mortems <post_mortems.jsonl>", args[0]);
         eprintln!("Example: {} tests/fixtures/1v1_self/", args[0]);
         std::process::exit(1);
     }
 
+    if args[1] == "--post-mortems" {
+        report_post_mortems(Path::new(&args[2]));
+        return;
+    }
+
     let log_dir = &args[1];
 
     println!("============================================================");
@@ -77,24 +59,27 @@ fn main() {
     println!();
 
     // Get all JSONL files
-    let paths: Vec<_> = fs::read_dir(log_dir)
+    let mut paths: Vec<_> = fs::read_dir(log_dir)
         .expect("Failed to read log directory")
         .filter_map(Result::ok)
         .map(|entry| entry.path())
         .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("jsonl"))
         .collect();
+    paths.sort();
 
     if paths.is_empty() {
         eprintln!("No .jsonl files found in: {}", log_dir);
         std::process::exit(1);
     }
 
-    let mut all_deaths: Vec<DeathAnalysis> = Vec::new();
+    let config = Config::load_or_default();
+    let engine = ReplayEngine::new(config.clone(), false);
+    let mut games: Vec<GameOutcome> = Vec::new();
 
     for path in &paths {
-        match analyze_game_death(&path) {
-            Ok(analysis) => {
-                all_deaths.push(analysis);
+        match analyze_game_death(&engine, path, &config) {
+            Ok(outcome) => {
+                games.push(outcome);
             }
             Err(e) => {
                 eprintln!("Error analyzing {}: {}", path.display(), e);
@@ -102,179 +87,79 @@ fn main() {
         }
     }
 
-    print_death_report(&all_deaths);
+    print_death_report(&games);
 }
 
-fn analyze_game_death(path: &Path) -> Result<DeathAnalysis, String> {
-    let file = File::open(path)
-        .map_err(|e| format!("Failed to open file: {}", e))?;
-
-    let reader = BufReader::new(file);
-    let mut turns: HashMap<u64, Vec<Value>> = HashMap::new();
-
-    // Group entries by turn
-    for line in reader.lines() {
-        let line = line.map_err(|e| format!("Failed to read line: {}", e))?;
-
-        if line.trim().is_empty() {
-            continue;
-        }
-
-        let entry: Value = serde_json::from_str(&line)
-            .map_err(|e| format!("Failed to parse JSON: {}", e))?;
+fn analyze_game_death(engine: &ReplayEngine, path: &Path, config: &Config) -> Result<GameOutcome, String> {
+    let entries = engine.load_log_file(path)?;
+    let mut turns: Vec<i32> = entries.iter().map(|e| e.turn).collect();
+    turns.dedup();
 
-        let turn = entry["turn"].as_u64().unwrap_or(0);
-        turns.entry(turn).or_insert_with(Vec::new).push(entry);
-    }
-
-    let total_turns = turns.len();
-
-    // Get the final turn to analyze death
-    let max_turn = turns.keys().max().copied().unwrap_or(0);
-    let final_entries = turns.get(&max_turn).ok_or("No final turn found")?;
-
-    // Get one entry to analyze the final board state
-    let final_entry = final_entries.first().ok_or("No entries in final turn")?;
-    let board = &final_entry["board"];
-
-    let snakes = board["snakes"].as_array().ok_or("No snakes array")?;
-
-    // Find winner (alive) and loser (dead or lower health)
-    let (winner, loser) = identify_winner_loser(snakes)?;
-
-    let winner_id = winner["id"].as_str().unwrap_or("unknown").to_string();
-    let winner_length = winner["length"].as_u64().unwrap_or(0) as usize;
-
-    let loser_id = loser["id"].as_str().unwrap_or("unknown").to_string();
-    let final_health = loser["health"].as_i64().unwrap_or(0);
-    let final_length = loser["length"].as_u64().unwrap_or(0) as usize;
-
-    // Determine death cause
-    let death_cause = determine_death_cause(&loser, snakes, board);
-
-    let food_count = board["food"].as_array().map(|f| f.len()).unwrap_or(0);
-
-    Ok(DeathAnalysis {
-        game_name: path.file_name().unwrap().to_str().unwrap().to_string(),
-        total_turns,
-        winner_id,
-        winner_length,
-        loser_id,
-        death_cause,
-        final_health,
-        final_length,
-        available_space: None, // Could calculate with flood fill
-        food_count,
+    Ok(GameOutcome {
+        game_name: path.file_name().unwrap().to_string_lossy().into_owned(),
+        total_turns: turns.len(),
+        death: analysis::analyze_game(&entries, config)?,
     })
 }
 
-fn identify_winner_loser(snakes: &[Value]) -> Result<(Value, Value), String> {
-    if snakes.is_empty() {
-        return Err("No snakes found".to_string());
-    }
-
-    // Find our snake (Rusty) - this is the one we're analyzing
-    let our_snake = snakes
-        .iter()
-        .find(|s| {
-            s["name"].as_str() == Some("Rusty") ||
-            s["id"].as_str().map(|id| id.contains("Rusty")).unwrap_or(false)
-        })
-        .ok_or("Could not find Rusty snake in game")?;
-
-    // Find the winner - the snake with the highest health, or longest if tied
-    let winner = snakes
-        .iter()
-        .filter(|s| s["id"] != our_snake["id"]) // Not us
-        .max_by_key(|s| {
-            let health = s["health"].as_i64().unwrap_or(0);
-            let length = s["length"].as_u64().unwrap_or(0);
-            (health, length)
-        })
-        .unwrap_or(snakes.first().unwrap()); // Fallback to first snake if all are us (shouldn't happen)
-
-    Ok((winner.clone(), our_snake.clone()))
-}
-
-fn determine_death_cause(loser: &Value, snakes: &[Value], board: &Value) -> DeathCause {
-    let health = loser["health"].as_i64().unwrap_or(0);
-
-    // Check for starvation
-    if health == 0 {
-        return DeathCause::Starvation;
-    }
-
-    // Check if snake has body (if not, something went wrong)
-    let body = match loser["body"].as_array() {
-        Some(b) if !b.is_empty() => b,
-        _ => return DeathCause::Unknown,
-    };
-
-    let head = &body[0];
-    let head_x = head["x"].as_i64().unwrap_or(0);
-    let head_y = head["y"].as_i64().unwrap_or(0);
-
-    // Check for wall collision
-    let width = board["width"].as_i64().unwrap_or(11);
-    let height = board["height"].as_i64().unwrap_or(11);
-
-    if head_x < 0 || head_x >= width || head_y < 0 || head_y >= height {
-        return DeathCause::WallCollision;
-    }
+/// Report of the deaths the bot recorded at /end (only lost games have a post-mortem)
+fn report_post_mortems(path: &Path) {
+    println!("============================================================");
+    println!("Death Pattern Analysis (post-mortems)");
+    println!("============================================================");
+    println!();
+    println!("Reading: {}", path.display());
+    println!();
 
-    // Check for self collision (head overlaps with body segment beyond neck)
-    for segment in body.iter().skip(1) {
-        if segment["x"] == head["x"] && segment["y"] == head["y"] {
-            return DeathCause::SelfCollision;
+    let post_mortems = PostMortem::load(path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    });
+    let mut games = Vec::new();
+    for post_mortem in &post_mortems {
+        match post_mortem.death() {
+            Ok(death) => games.push(GameOutcome {
+                game_name: post_mortem.game_id.clone(),
+                total_turns: post_mortem.turn as usize + 1,
+                death: Some(death),
+            }),
+            Err(e) => eprintln!("Error reading post-mortem of {}: {}", post_mortem.game_id, e),
         }
     }
+    print_death_report(&games);
 
-    // Check for opponent collision
-    for snake in snakes {
-        let snake_id = snake["id"].as_str().unwrap_or("");
-        let loser_id = loser["id"].as_str().unwrap_or("");
-
-        if snake_id == loser_id {
-            continue; // Skip self
-        }
-
-        if let Some(opponent_body) = snake["body"].as_array() {
-            // Check head-to-head
-            if !opponent_body.is_empty() {
-                let opp_head = &opponent_body[0];
-                if opp_head["x"] == head["x"] && opp_head["y"] == head["y"] {
-                    return DeathCause::HeadToHead;
-                }
-            }
-
-            // Check collision with opponent body
-            for segment in opponent_body {
-                if segment["x"] == head["x"] && segment["y"] == head["y"] {
-                    return DeathCause::OpponentCollision;
-                }
-            }
+    println!();
+    println!("============================================================");
+    println!("FATAL DECISIONS");
+    println!("============================================================");
+    for post_mortem in &post_mortems {
+        println!("{}: {}", post_mortem.game_id, post_mortem.description);
+        for result in &post_mortem.last_turns {
+            // Deepest iteration of the candidate table
+            let scores = result
+                .candidates
+                .as_ref()
+                .and_then(|iterations| iterations.last())
+                .map(|iteration| iteration.scores.iter().map(|c| format!("{} {}", c.candidate_move, c.score)).collect::<Vec<_>>().join(", "))
+                .unwrap_or_default();
+            println!("  turn {}: {} (score {}, depth {}) [{}]", result.turn, result.chosen_move, result.score, result.depth, scores);
         }
     }
-
-    // If alive but game ended, likely trapped
-    if health > 0 {
-        return DeathCause::Trapped;
-    }
-
-    DeathCause::Unknown
 }
 
-fn print_death_report(deaths: &[DeathAnalysis]) {
-    println!("Analyzed {} games", deaths.len());
+fn print_death_report(games: &[GameOutcome]) {
+    let deaths: Vec<(&GameOutcome, &DeathAnalysis)> =
+        games.iter().filter_map(|g| g.death.as_ref().map(|d| (g, d))).collect();
+    println!("Analyzed {} games ({} won, {} lost)", games.len(), games.len() - deaths.len(), deaths.len());
     println!();
+    if deaths.is_empty() {
+        return;
+    }
 
     // Categorize by death cause
-    let mut by_cause: HashMap<String, Vec<&DeathAnalysis>> = HashMap::new();
-    for death in deaths {
-        by_cause
-            .entry(death.death_cause.as_str().to_string())
-            .or_insert_with(Vec::new)
-            .push(death);
+    let mut by_cause: HashMap<&str, Vec<&DeathAnalysis>> = HashMap::new();
+    for (_, death) in &deaths {
+        by_cause.entry(death.cause.as_str()).or_default().push(death);
     }
 
     println!("============================================================");
@@ -285,53 +170,42 @@ fn print_death_report(deaths: &[DeathAnalysis]) {
     causes.sort_by_key(|(_, v)| std::cmp::Reverse(v.len()));
 
     for (cause, games) in &causes {
-        println!("{}: {} games ({:.1}%)",
+        let mean_space = games.iter().map(|d| d.space).sum::<usize>() as f64 / games.len() as f64;
+        println!("{}: {} games ({:.1}%), mean space at death {:.1}",
             cause,
             games.len(),
-            100.0 * games.len() as f64 / deaths.len() as f64
+            100.0 * games.len() as f64 / deaths.len() as f64,
+            mean_space
         );
     }
     println!();
 
     // Quick games analysis (< 100 turns)
-    let quick_deaths: Vec<_> = deaths.iter().filter(|d| d.total_turns < 100).collect();
+    let quick_deaths: Vec<_> = deaths.iter().filter(|(g, _)| g.total_turns < 100).collect();
 
     if !quick_deaths.is_empty() {
         println!("============================================================");
         println!("QUICK GAMES (<100 turns) - {} games", quick_deaths.len());
         println!("============================================================");
 
-        for death in &quick_deaths {
-            println!("{} (turn {}): {}",
-                death.game_name,
-                death.total_turns,
-                death.death_cause.as_str()
-            );
-            println!("  Loser: health={}, length={}, food_available={}",
-                death.final_health,
-                death.final_length,
-                death.food_count
-            );
+        for (game, death) in &quick_deaths {
+            println!("{}: {}", game.game_name, death.describe());
+            println!("  food_available={}", death.food_count);
         }
         println!();
     }
 
     // Epic games analysis (> 300 turns)
-    let epic_games: Vec<_> = deaths.iter().filter(|d| d.total_turns > 300).collect();
+    let epic_games: Vec<_> = deaths.iter().filter(|(g, _)| g.total_turns > 300).collect();
 
     if !epic_games.is_empty() {
         println!("============================================================");
         println!("EPIC GAMES (>300 turns) - {} games", epic_games.len());
         println!("============================================================");
 
-        for death in &epic_games {
-            println!("{} (turn {}): {}",
-                death.game_name,
-                death.total_turns,
-                death.death_cause.as_str()
-            );
-            println!("  Winner: length={}", death.winner_length);
-            println!("  Loser: health={}, length={}", death.final_health, death.final_length);
+        for (game, death) in &epic_games {
+            println!("{}: {}", game.game_name, death.describe());
+            println!("  Longest opponent: length={}", death.longest_opponent);
         }
         println!();
     }
@@ -341,10 +215,11 @@ fn print_death_report(deaths: &[DeathAnalysis]) {
     println!("============================================================");
 
     // Count preventable deaths
-    let starvation_count = by_cause.get("Starvation").map(|v| v.len()).unwrap_or(0);
-    let trapped_count = by_cause.get("Trapped (No Legal Moves)").map(|v| v.len()).unwrap_or(0);
-    let collision_count = by_cause.get("Wall Collision").map(|v| v.len()).unwrap_or(0)
-        + by_cause.get("Self Collision").map(|v| v.len()).unwrap_or(0);
+    let count = |cause: &str| by_cause.get(cause).map(|v| v.len()).unwrap_or(0);
+    let starvation_count = count("Starvation");
+    let trapped_count = count("Trapped (No Legal Moves)");
+    let collision_count = count("Wall Collision") + count("Self Collision");
+    let head_to_head_count = count("Head-to-Head Loss");
 
     if starvation_count > 0 {
         println!("• Starvation ({} games): Improve food-seeking behavior", starvation_count);
@@ -364,5 +239,10 @@ fn print_death_report(deaths: &[DeathAnalysis]) {
         println!("  - Consider penalizing risky positions more heavily");
     }
 
+    if head_to_head_count > 0 {
+        println!("• Head-to-head losses ({} games): Review head-to-head avoidance", head_to_head_count);
+        println!("  - Deaths with spare escape routes were avoidable moves");
+    }
+
     println!("============================================================");
 }
@@ -0,0 +1,179 @@
+//! Analyzes where 1v1 alpha-beta and MaxN disagree on the same position
+//!
+//! For sampled turns where IDAPOS reduces the game to two active snakes (true duels and
+//! multiplayer locality boundary cases), both strategies score every root move at a fixed
+//! depth. Reports which positions pick different moves and how far apart the scores are,
+//! to guide score normalization and the mode-switching logic.
+//!
+//! Usage:
+//!   cargo run --release --bin analyze_strategy_disagreement -- <game.jsonl|dir> [--depth N] [--sample K]
+
+use starter_snake_rust::bot::{Bot, StrategyComparison};
+use starter_snake_rust::config::Config;
+use starter_snake_rust::replay::ReplayEngine;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+struct Disagreement {
+    file: String,
+    turn: i32,
+    comparison: StrategyComparison,
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 2 {
+        eprintln!("Usage: {} <game_file.jsonl|directory> [--depth N] [--sample K]", args[0]);
+        eprintln!("Compares 1v1 alpha-beta and MaxN root scores on IDAPOS boundary positions");
+        eprintln!("Options:");
+        eprintln!("  --depth N  : Fixed search depth for both strategies (default: 3)");
+        eprintln!("  --sample K : Analyze every K-th turn (default: 1)");
+        std::process::exit(1);
+    }
+
+    let input = &args[1];
+    let mut depth: u8 = 3;
+    let mut sample: usize = 1;
+
+    let mut i = 2;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--depth" if i + 1 < args.len() => {
+                depth = args[i + 1].parse().unwrap_or(3);
+                i += 2;
+            }
+            "--sample" if i + 1 < args.len() => {
+                sample = args[i + 1].parse::<usize>().unwrap_or(1).max(1);
+                i += 2;
+            }
+            _ => {
+                eprintln!("Unknown option: {}", args[i]);
+                i += 1;
+            }
+        }
+    }
+
+    let config = Config::load_or_default();
+
+    println!("============================================================");
+    println!("Strategy Disagreement Analysis (alpha-beta vs MaxN)");
+    println!("============================================================\n");
+    println!("Input: {}", input);
+    println!("Depth: {}, sampling every {} turn(s)\n", depth, sample);
+
+    let files = match collect_files(Path::new(input)) {
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let engine = ReplayEngine::new(config.clone(), false);
+    let mut sampled = 0usize;
+    let mut eligible = 0usize;
+    let mut duel_positions = 0usize;
+    let mut gaps: Vec<i64> = Vec::new();
+    let mut disagreements: Vec<Disagreement> = Vec::new();
+
+    for path in &files {
+        let entries = match engine.load_log_file(path) {
+            Ok(entries) => entries,
+            Err(e) => {
+                eprintln!("Skipping {}: {}", path.display(), e);
+                continue;
+            }
+        };
+
+        for entry in entries.iter().step_by(sample) {
+            sampled += 1;
+
+            // Replay convention: the first snake in a log entry is ours
+            let our_id = match entry.board.snakes.first() {
+                Some(snake) => snake.id.clone(),
+                None => continue,
+            };
+
+            let comparison = match Bot::compare_root_strategies(&entry.board, &our_id, entry.turn, depth, &config) {
+                Some(comparison) => comparison,
+                None => continue,
+            };
+
+            eligible += 1;
+            if comparison.alive_snakes == 2 {
+                duel_positions += 1;
+            }
+            gaps.push(comparison.max_score_gap());
+
+            if comparison.disagrees() {
+                disagreements.push(Disagreement {
+                    file: path.file_name().and_then(|n| n.to_str()).unwrap_or("?").to_string(),
+                    turn: entry.turn,
+                    comparison,
+                });
+            }
+        }
+    }
+
+    disagreements.sort_by_key(|d| -d.comparison.alpha_beta_regret());
+
+    println!("Disagreements (sorted by alpha-beta regret):");
+    println!("{:<28} {:>5} {:>6} {:>6} {:>6} {:>12} {:>12}", "file", "turn", "alive", "AB", "MaxN", "AB regret", "max gap");
+    for d in &disagreements {
+        println!(
+            "{:<28} {:>5} {:>6} {:>6} {:>6} {:>12} {:>12}",
+            d.file,
+            d.turn,
+            d.comparison.alive_snakes,
+            d.comparison.alpha_beta_best.as_str(),
+            d.comparison.maxn_best.as_str(),
+            d.comparison.alpha_beta_regret(),
+            d.comparison.max_score_gap()
+        );
+        for m in &d.comparison.moves {
+            println!(
+                "      {:<6} alpha-beta={:>12} maxn={:>12}",
+                m.direction.as_str(),
+                m.alpha_beta_score,
+                m.maxn_score
+            );
+        }
+    }
+
+    println!("\n============================================================");
+    println!("Summary");
+    println!("============================================================");
+    println!("Sampled positions:        {}", sampled);
+    println!("Two-active-snake positions: {} ({} duels, {} IDAPOS-reduced)", eligible, duel_positions, eligible - duel_positions);
+    if eligible > 0 {
+        println!(
+            "Disagreements:            {} ({:.1}%)",
+            disagreements.len(),
+            disagreements.len() as f64 * 100.0 / eligible as f64
+        );
+        gaps.sort_unstable();
+        let mean_gap = gaps.iter().sum::<i64>() as f64 / gaps.len() as f64;
+        println!("Score gap (|AB - MaxN|):  mean {:.0}, median {}, max {}", mean_gap, gaps[gaps.len() / 2], gaps[gaps.len() - 1]);
+    }
+}
+
+/// Collects a single .jsonl file, or all .jsonl files in a directory
+fn collect_files(input: &Path) -> Result<Vec<PathBuf>, String> {
+    if input.is_file() {
+        return Ok(vec![input.to_path_buf()]);
+    }
+
+    let mut files: Vec<PathBuf> = fs::read_dir(input)
+        .map_err(|e| format!("Failed to read {}: {}", input.display(), e))?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("jsonl"))
+        .collect();
+    files.sort();
+
+    if files.is_empty() {
+        return Err(format!("No .jsonl files found in {}", input.display()));
+    }
+    Ok(files)
+}
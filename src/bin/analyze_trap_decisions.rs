@@ -168,7 +168,7 @@ fn analyze_game(
 
         for dir in &all_directions {
             let score = if is_move_legal(board, &our_snake_id, *dir) {
-                Some(Bot::evaluate_move_detailed(board, &our_snake_id, *dir, config))
+                Bot::evaluate_move_detailed(board, &our_snake_id, *dir, config).ok()
             } else {
                 None
             };
@@ -0,0 +1,120 @@
+//! Win Probability Calibration
+//!
+//! Fits the [win_probability] logistic curve (root score -> probability of winning) to the
+//! outcomes of logged self-play games (see src/win_probability.rs). Turns logged without a
+//! search result are searched again at --depth plies to get their root score.
+//!
+//! Usage:
+//!   cargo run --release --bin calibrate_win_probability -- <log file or directory>... [--depth N]
+//!
+//! Directories are scanned for .jsonl and .jsonl.gz files (not recursively).
+//!
+//! Output:
+//!   - Games read (won / lost / undecided) and the samples fit
+//!   - The fitted slope and intercept as a [win_probability] snippet for Snake.toml
+//!   - The score at a few win probabilities, to check the curve against the thresholds
+
+use starter_snake_rust::config::Config;
+use starter_snake_rust::dataset;
+use starter_snake_rust::replay::ReplayEngine;
+use starter_snake_rust::win_probability;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+
+struct Args {
+    inputs: Vec<String>,
+    depth: u8,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args { inputs: Vec::new(), depth: 4 };
+    let mut iter = env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--depth" => {
+                let value = iter.next().ok_or("--depth needs a value")?;
+                args.depth = value.parse().map_err(|e| format!("--depth: {}", e))?;
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown flag {}", flag)),
+            path => args.inputs.push(path.to_string()),
+        }
+    }
+    if args.inputs.is_empty() {
+        return Err("Usage: calibrate_win_probability <log file or directory>... [--depth N]".to_string());
+    }
+    Ok(args)
+}
+
+/// Log files named by `inputs` (directories expanded to their .jsonl and .jsonl.gz files)
+fn log_files(inputs: &[String]) -> Result<Vec<PathBuf>, String> {
+    let is_log = |path: &Path| {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        name.ends_with(".jsonl") || name.ends_with(".jsonl.gz")
+    };
+    let mut files = Vec::new();
+    for input in inputs {
+        let path = Path::new(input);
+        if path.is_dir() {
+            let mut found: Vec<PathBuf> = fs::read_dir(path)
+                .map_err(|e| format!("Failed to read {}: {}", input, e))?
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| is_log(path))
+                .collect();
+            found.sort();
+            files.extend(found);
+        } else {
+            files.push(path.to_path_buf());
+        }
+    }
+    Ok(files)
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let files = log_files(&args.inputs)?;
+    let config = Config::load_or_default();
+    let engine = ReplayEngine::new(config.clone(), false).with_fixed_depth(Some(args.depth));
+
+    let (mut games, mut decided) = (0usize, 0usize);
+    let mut samples = Vec::new();
+    for path in &files {
+        let entries = match engine.load_log_file(path) {
+            Ok(entries) => entries,
+            Err(e) => {
+                eprintln!("Skipping {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        for game in dataset::split_games(&entries) {
+            games += 1;
+            let game_samples = dataset::calibration_samples(&game, &engine, &config);
+            if !game_samples.is_empty() {
+                decided += 1;
+            }
+            samples.extend(game_samples);
+        }
+    }
+    println!("Read {} game(s) from {} file(s), {} with a known winner", games, files.len(), decided);
+
+    let calibration = win_probability::fit(&samples)?;
+    println!("Fit {} sample(s), mean log loss {:.4}\n", calibration.samples, calibration.log_loss);
+    println!("[win_probability]");
+    println!("slope = {:e}", calibration.slope);
+    println!("intercept = {:.4}\n", calibration.intercept);
+    for p in [0.005f64, 0.2, 0.5, 0.8, 0.995] {
+        let score = ((p / (1.0 - p)).ln() - calibration.intercept) / calibration.slope;
+        println!("p(win) {:>5.3} at score {:.0}", p, score);
+    }
+    Ok(())
+}
+
+fn main() {
+    env_logger::init();
+    let result = parse_args().and_then(run);
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,151 @@
+//! Genetic Weight Evolution
+//!
+//! Evolves the [scores] fields listed in [tuning.parameters] with a genetic algorithm: every
+//! generation each individual plays arena games against the starting config, and the fittest
+//! breed the next generation (see src/evolution.rs). A checkpoint is written after every
+//! generation; running again resumes from it unless --fresh is given.
+//!
+//! Usage:
+//!   cargo run --release --bin evolve -- [config.toml] [--generations N] [--population N]
+//!       [--games N] [--checkpoint PATH] [--fresh] [--output PATH]
+//!
+//! Output:
+//!   - One line per generation: best and mean fitness and the best values so far
+//!   - The checkpoint (default: evolution.checkpoint_path)
+//!   - The evolved config (default: evolution.output_path)
+
+use starter_snake_rust::config::Config;
+use starter_snake_rust::evolution::{Checkpoint, Evolution};
+use starter_snake_rust::tuning;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process;
+use std::time::Instant;
+
+struct Args {
+    config_path: String,
+    generations: Option<u32>,
+    population: Option<usize>,
+    games: Option<u32>,
+    checkpoint: Option<String>,
+    fresh: bool,
+    output: Option<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        config_path: "Snake.toml".to_string(),
+        generations: None,
+        population: None,
+        games: None,
+        checkpoint: None,
+        fresh: false,
+        output: None,
+    };
+    let mut iter = env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| iter.next().ok_or_else(|| format!("{} needs a value", flag));
+        match arg.as_str() {
+            "--generations" => args.generations = Some(value("--generations")?.parse().map_err(|e| format!("--generations: {}", e))?),
+            "--population" => args.population = Some(value("--population")?.parse().map_err(|e| format!("--population: {}", e))?),
+            "--games" => args.games = Some(value("--games")?.parse().map_err(|e| format!("--games: {}", e))?),
+            "--checkpoint" => args.checkpoint = Some(value("--checkpoint")?),
+            "--fresh" => args.fresh = true,
+            "--output" => args.output = Some(value("--output")?),
+            flag if flag.starts_with("--") => return Err(format!("Unknown flag {}", flag)),
+            path => args.config_path = path.to_string(),
+        }
+    }
+    Ok(args)
+}
+
+fn format_values(values: &[(String, f64)]) -> String {
+    values
+        .iter()
+        .map(|(name, value)| format!("{}={:.2}", name, value))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let mut config = Config::from_file(&args.config_path)?;
+    if let Some(generations) = args.generations {
+        config.evolution.generations = generations;
+    }
+    if let Some(population) = args.population {
+        config.evolution.population_size = population;
+    }
+    if let Some(games) = args.games {
+        config.evolution.games_per_individual = games;
+    }
+    let errors = config.validation_errors();
+    if let Some(first) = errors.first() {
+        return Err(format!("{} has {} config error(s), first: {}", args.config_path, errors.len(), first));
+    }
+    let checkpoint_path = args.checkpoint.clone().unwrap_or_else(|| config.evolution.checkpoint_path.clone());
+    let output = args.output.clone().unwrap_or_else(|| config.evolution.output_path.clone());
+
+    let mut evolution = if !args.fresh && Path::new(&checkpoint_path).exists() {
+        Evolution::resume(&config, Checkpoint::load(&checkpoint_path)?)?
+    } else {
+        Evolution::new(&config)?
+    };
+    let e = &config.evolution;
+    println!("════════════════════════════════════════════════════════");
+    println!("                 GENETIC WEIGHT EVOLUTION");
+    println!("════════════════════════════════════════════════════════");
+    println!(
+        "{} generations x {} individuals x {} games, board {}x{}, depth {}, max {} turns",
+        e.generations,
+        e.population_size,
+        e.games_per_individual,
+        config.arena.board_size,
+        config.arena.board_size,
+        config.arena.search_depth,
+        config.arena.max_turns
+    );
+    if evolution.generation() > 0 {
+        println!("Resuming from {} at generation {}", checkpoint_path, evolution.generation() + 1);
+    }
+    println!("Start: {}", format_values(&evolution.best_values()));
+    println!();
+
+    let started = Instant::now();
+    while evolution.generation() < e.generations {
+        let generation = evolution.generation();
+        let fitness = evolution.evaluate()?;
+        let best = fitness.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let mean = fitness.iter().sum::<f64>() / fitness.len() as f64;
+        evolution.advance(&fitness);
+        evolution.checkpoint().save(&checkpoint_path)?;
+
+        println!(
+            "[{:>3}] best {:.2} mean {:.2} | best so far {:.2}: {}",
+            generation + 1,
+            best,
+            mean,
+            evolution.best().map_or(best, |individual| individual.fitness),
+            format_values(&evolution.best_values())
+        );
+    }
+
+    let template = fs::read_to_string(&args.config_path).map_err(|e| format!("Failed to read {}: {}", args.config_path, e))?;
+    let evolved = tuning::rewrite_scores(&template, &config.scores, &evolution.best_values())?;
+    fs::write(&output, evolved).map_err(|e| format!("Failed to write {}: {}", output, e))?;
+
+    println!();
+    println!("Done in {:.1}s", started.elapsed().as_secs_f64());
+    println!("Evolved: {}", format_values(&evolution.best_values()));
+    println!("Written to {} (checkpoint: {})", output, checkpoint_path);
+    Ok(())
+}
+
+fn main() {
+    env_logger::init();
+    let result = parse_args().and_then(run);
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,143 @@
+//! Training Dataset Exporter
+//!
+//! Converts JSONL game logs into (state tensor, chosen move, game outcome) records for
+//! training a policy/value network offline (format documented in src/dataset.rs). Every
+//! logged position is written once per board symmetry (8 by default) unless --no-augment.
+//!
+//! Usage:
+//!   cargo run --release --bin export_dataset -- <log file or directory>... [--output PATH]
+//!       [--size N] [--no-augment]
+//!
+//! Directories are scanned for .jsonl and .jsonl.gz files (not recursively).
+//!
+//! Output:
+//!   - The dataset file (default: dataset.bsds)
+//!   - Games read (won / lost / undecided), positions and records written, and positions
+//!     skipped because the board is larger than --size (default 11)
+
+use starter_snake_rust::config::Config;
+use starter_snake_rust::dataset::{self, DatasetWriter, Symmetry};
+use starter_snake_rust::replay::ReplayEngine;
+use std::env;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::process;
+
+struct Args {
+    inputs: Vec<String>,
+    output: String,
+    size: usize,
+    augment: bool,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args { inputs: Vec::new(), output: "dataset.bsds".to_string(), size: 11, augment: true };
+    let mut iter = env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| iter.next().ok_or_else(|| format!("{} needs a value", flag));
+        match arg.as_str() {
+            "--output" => args.output = value("--output")?,
+            "--size" => args.size = value("--size")?.parse().map_err(|e| format!("--size: {}", e))?,
+            "--no-augment" => args.augment = false,
+            flag if flag.starts_with("--") => return Err(format!("Unknown flag {}", flag)),
+            path => args.inputs.push(path.to_string()),
+        }
+    }
+    if args.inputs.is_empty() {
+        return Err("Usage: export_dataset <log file or directory>... [--output PATH] [--size N] [--no-augment]".to_string());
+    }
+    Ok(args)
+}
+
+/// Log files named by `inputs` (directories expanded to their .jsonl and .jsonl.gz files)
+fn log_files(inputs: &[String]) -> Result<Vec<PathBuf>, String> {
+    let is_log = |path: &Path| {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        name.ends_with(".jsonl") || name.ends_with(".jsonl.gz")
+    };
+    let mut files = Vec::new();
+    for input in inputs {
+        let path = Path::new(input);
+        if path.is_dir() {
+            let mut found: Vec<PathBuf> = fs::read_dir(path)
+                .map_err(|e| format!("Failed to read {}: {}", input, e))?
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| is_log(path))
+                .collect();
+            found.sort();
+            files.extend(found);
+        } else {
+            files.push(path.to_path_buf());
+        }
+    }
+    Ok(files)
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let files = log_files(&args.inputs)?;
+    let config = Config::load_or_default();
+    let engine = ReplayEngine::new(config.clone(), false);
+    let symmetries: &[Symmetry] = if args.augment { &Symmetry::ALL } else { &[Symmetry::Identity] };
+    let out = File::create(&args.output).map_err(|e| format!("Failed to create {}: {}", args.output, e))?;
+    let mut writer = DatasetWriter::new(BufWriter::new(out), args.size)?;
+
+    let (mut games, mut positions, mut skipped) = (0usize, 0usize, 0usize);
+    let (mut wins, mut losses) = (0usize, 0usize);
+    for path in &files {
+        let entries = match engine.load_log_file(path) {
+            Ok(entries) => entries,
+            Err(e) => {
+                eprintln!("Skipping {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        for game in dataset::split_games(&entries) {
+            let records = dataset::game_records(&game, &config, args.size, symmetries);
+            let written = records.len() / symmetries.len();
+            skipped += game.len() - written;
+            positions += written;
+            games += 1;
+            match dataset::game_outcome(&game, &config) {
+                1 => wins += 1,
+                -1 => losses += 1,
+                _ => {}
+            }
+            for record in &records {
+                writer.write(record)?;
+            }
+        }
+    }
+    let (records, _) = writer.finish()?;
+
+    println!(
+        "Read {} game(s) from {} file(s): {} won, {} lost, {} undecided",
+        games,
+        files.len(),
+        wins,
+        losses,
+        games - wins - losses
+    );
+    println!(
+        "Wrote {} record(s) ({} position(s) x {} symmetr{}) to {}",
+        records,
+        positions,
+        symmetries.len(),
+        if symmetries.len() == 1 { "y" } else { "ies" },
+        args.output
+    );
+    if skipped > 0 {
+        println!("Skipped {} position(s) larger than {}x{} or with an unreadable move", skipped, args.size, args.size);
+    }
+    Ok(())
+}
+
+fn main() {
+    env_logger::init();
+    let result = parse_args().and_then(run);
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
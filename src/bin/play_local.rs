@@ -0,0 +1,195 @@
+//! Local Game Runner
+//!
+//! Plays whole games in-process between our Bot and scripted opponents, resolving turns with
+//! the official rules (src/rules.rs) and the arena's standard setup and food spawning. Our
+//! snake goes through the production /start, /move and /end code paths with per-game debug
+//! logging forced on, so every game leaves a JSONL log in the production format (ready for
+//! replay, the analysis tools or a fixture directory).
+//!
+//! Usage:
+//!   cargo run --release --bin play_local -- [--opponents greedy,random,wall] [--games N]
+//!       [--seed S] [--timeout-ms MS] [--max-turns N] [--log-dir DIR] [--config PATH]
+//!
+//! Opponents (src/opponents/): greedy, cautious, head-hunter, random, wall
+//!
+//! Output:
+//!   - One line per game: turns, winner and how every snake died
+//!   - <log-dir>/<game id>.jsonl per game (default log-dir: local_games)
+//!   - Our W/D/L over all games
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use starter_snake_rust::arena;
+use starter_snake_rust::bot::Bot;
+use starter_snake_rust::config::Config;
+use starter_snake_rust::opponents::{self, Opponent};
+use starter_snake_rust::rules::{self, RulesSettings};
+use starter_snake_rust::types::{Direction, Game, GameState, Ruleset};
+use std::collections::HashMap;
+use std::env;
+use std::process;
+use std::time::Instant;
+
+/// Id of the snake our Bot plays
+const OUR_ID: &str = "player-0";
+
+struct Args {
+    opponents: Vec<String>,
+    games: u32,
+    seed: u64,
+    timeout_ms: u32,
+    max_turns: Option<i32>,
+    log_dir: String,
+    config_path: String,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        opponents: ["greedy", "random", "wall"].iter().map(|name| name.to_string()).collect(),
+        games: 1,
+        seed: 1,
+        timeout_ms: 500,
+        max_turns: None,
+        log_dir: "local_games".to_string(),
+        config_path: "Snake.toml".to_string(),
+    };
+    let mut iter = env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| iter.next().ok_or_else(|| format!("{} needs a value", flag));
+        match arg.as_str() {
+            "--opponents" => args.opponents = value("--opponents")?.split(',').map(|name| name.to_string()).collect(),
+            "--games" => args.games = value("--games")?.parse().map_err(|e| format!("--games: {}", e))?,
+            "--seed" => args.seed = value("--seed")?.parse().map_err(|e| format!("--seed: {}", e))?,
+            "--timeout-ms" => args.timeout_ms = value("--timeout-ms")?.parse().map_err(|e| format!("--timeout-ms: {}", e))?,
+            "--max-turns" => args.max_turns = Some(value("--max-turns")?.parse().map_err(|e| format!("--max-turns: {}", e))?),
+            "--log-dir" => args.log_dir = value("--log-dir")?,
+            "--config" => args.config_path = value("--config")?,
+            other => return Err(format!("Unknown argument {}", other)),
+        }
+    }
+    // Unknown names fail before any game is played
+    for name in &args.opponents {
+        opponents::by_name(name, 0)?;
+    }
+    Ok(args)
+}
+
+/// Outcome of one game for our snake
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Outcome {
+    Win,
+    Draw,
+    Loss,
+}
+
+async fn play_game(bot: &Bot, config: &Config, args: &Args, game_idx: u32) -> Result<Outcome, String> {
+    let seed = args.seed.wrapping_add(u64::from(game_idx));
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut scripted: Vec<Box<dyn Opponent>> =
+        args.opponents.iter().map(|name| opponents::by_name(name, seed)).collect::<Result<_, _>>()?;
+    let mut board = arena::initial_board(scripted.len() + 1, &config.arena, &mut rng)?;
+    for (snake, opponent) in board.snakes.iter_mut().skip(1).zip(&scripted) {
+        snake.name = format!("{} ({})", snake.id, opponent.name());
+    }
+    let game = Game {
+        id: format!("local-{}-{}", args.seed, game_idx),
+        ruleset: Ruleset { name: "standard".to_string(), version: "local".to_string(), ..Ruleset::default() },
+        timeout: args.timeout_ms,
+        map: "standard".to_string(),
+    };
+    let settings = RulesSettings::from_config(config);
+    let max_turns = args.max_turns.unwrap_or(config.arena.max_turns);
+
+    let mut you = board.snakes[0].clone();
+    bot.start(None, &game, &0, &board, &you)?;
+    let mut deaths = Vec::new();
+    let mut turn = 0;
+    while turn < max_turns && board.snakes.len() > 1 {
+        you = match board.our_snake(OUR_ID) {
+            Some(snake) => snake.clone(),
+            None => break,
+        };
+        let state = GameState { game: game.clone(), turn, board: board.clone(), you: you.clone() };
+        let our_move = bot.try_get_move(None, state).await.map_err(|e| format!("Turn {}: {}", turn, e))?;
+        bot.flush_debug_log().await;
+
+        let mut moves: HashMap<String, Direction> = HashMap::new();
+        moves.insert(OUR_ID.to_string(), our_move);
+        for (player, opponent) in scripted.iter_mut().enumerate() {
+            let id = format!("player-{}", player + 1);
+            if let Some(snake) = board.our_snake(&id) {
+                let dir = opponent.choose(&board, snake);
+                moves.insert(id, dir);
+            }
+        }
+        for elimination in rules::resolve_turn(&mut board, &moves, &settings) {
+            deaths.push(format!("{} turn {} ({:?})", elimination.snake_id, turn, elimination.cause));
+        }
+        rules::spawn_food(&mut board, &config.arena, &mut rng);
+        turn += 1;
+    }
+    bot.end(None, &game, &turn, &board, &you)?;
+    bot.flush_debug_log().await;
+
+    let outcome = match board.snakes.as_slice() {
+        [survivor] if survivor.id == OUR_ID => Outcome::Win,
+        _ if board.snake_index(OUR_ID).is_none() => Outcome::Loss,
+        _ => Outcome::Draw,
+    };
+    println!(
+        "{}: {:?} after {} turns{}{}",
+        game.id,
+        outcome,
+        turn,
+        if deaths.is_empty() { "" } else { " | " },
+        deaths.join(", ")
+    );
+    Ok(outcome)
+}
+
+async fn run(args: Args) -> Result<(), String> {
+    let mut config = Config::from_file(&args.config_path)?;
+    config.debug.enabled = true;
+    config.debug.per_game_files = true;
+    config.debug.log_directory = args.log_dir.clone();
+    config.debug.compress = false;
+    let bot = Bot::new(config.clone());
+
+    println!(
+        "Playing {} game(s) against {} (seed {}, timeout {}ms)",
+        args.games,
+        args.opponents.join(", "),
+        args.seed,
+        args.timeout_ms
+    );
+    let started = Instant::now();
+    let mut tally: HashMap<Outcome, u32> = HashMap::new();
+    for game_idx in 0..args.games {
+        let outcome = play_game(&bot, &config, &args, game_idx).await?;
+        *tally.entry(outcome).or_default() += 1;
+    }
+    let count = |outcome| tally.get(&outcome).copied().unwrap_or(0);
+    println!();
+    println!(
+        "{}W {}D {}L in {:.1}s; logs in {}/",
+        count(Outcome::Win),
+        count(Outcome::Draw),
+        count(Outcome::Loss),
+        started.elapsed().as_secs_f64(),
+        args.log_dir
+    );
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
+    let result = match parse_args() {
+        Ok(args) => run(args).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,100 @@
+//! Tactical Puzzle Runner
+//!
+//! Solves every puzzle in a directory (default: puzzles.directory) with a fixed-depth search
+//! and reports which ones pass, plus the pass rate per tag (see src/puzzles.rs).
+//!
+//! Usage:
+//!   cargo run --release --bin puzzles -- [puzzle_dir] [--depth N] [--tag TAG] [--config PATH]
+//!
+//! Output:
+//!   - PASS/FAIL per puzzle with the chosen and the acceptable moves
+//!   - Pass rate per tag and overall
+//!   - Exit code 1 if any puzzle fails (known failures, marked "known_failure", excepted)
+
+use starter_snake_rust::config::Config;
+use starter_snake_rust::puzzles::{self, PuzzleOutcome};
+use std::env;
+use std::process;
+
+struct Args {
+    dir: Option<String>,
+    depth: Option<u8>,
+    tag: Option<String>,
+    config_path: String,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args { dir: None, depth: None, tag: None, config_path: "Snake.toml".to_string() };
+    let mut iter = env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| iter.next().ok_or_else(|| format!("{} needs a value", flag));
+        match arg.as_str() {
+            "--depth" => args.depth = Some(value("--depth")?.parse().map_err(|e| format!("--depth: {}", e))?),
+            "--tag" => args.tag = Some(value("--tag")?),
+            "--config" => args.config_path = value("--config")?,
+            flag if flag.starts_with("--") => return Err(format!("Unknown flag {}", flag)),
+            dir => args.dir = Some(dir.to_string()),
+        }
+    }
+    Ok(args)
+}
+
+fn moves(outcome: &PuzzleOutcome) -> String {
+    outcome.acceptable.iter().map(|d| d.as_str()).collect::<Vec<_>>().join("/")
+}
+
+fn run(args: Args) -> Result<bool, String> {
+    let config = Config::from_file(&args.config_path)?;
+    let dir = args.dir.unwrap_or_else(|| config.puzzles.directory.clone());
+    let default_depth = args.depth.unwrap_or(config.puzzles.default_depth);
+
+    let mut outcomes = Vec::new();
+    for (path, mut puzzle) in puzzles::load_dir(&dir)? {
+        if args.tag.as_ref().is_some_and(|tag| tag != &puzzle.tag) {
+            continue;
+        }
+        if args.depth.is_some() {
+            // An explicit --depth overrides per-puzzle depths
+            puzzle.depth = None;
+        }
+        let outcome = puzzle.solve(&config, default_depth)?;
+        println!(
+            "{:<5} {:<14} {:<40} chose {:<5} (acceptable {}, depth {}) {}",
+            match (outcome.passed, outcome.known_failure) {
+                (true, _) => "PASS",
+                (false, true) => "KNOWN",
+                (false, false) => "FAIL",
+            },
+            outcome.tag,
+            outcome.name,
+            outcome.chosen.as_str(),
+            moves(&outcome),
+            outcome.depth,
+            path.file_name().and_then(|n| n.to_str()).unwrap_or("")
+        );
+        outcomes.push(outcome);
+    }
+
+    println!();
+    println!("{:<16} {:>6} {:>6} {:>8}", "Tag", "Passed", "Total", "Rate");
+    println!("{}", "-".repeat(40));
+    for (tag, summary) in puzzles::summarize(&outcomes) {
+        println!("{:<16} {:>6} {:>6} {:>7.1}%", tag, summary.passed, summary.total, summary.pass_rate());
+    }
+    let passed = outcomes.iter().filter(|o| o.passed).count();
+    println!("{}", "-".repeat(40));
+    println!("{:<16} {:>6} {:>6}", "Overall", passed, outcomes.len());
+    Ok(outcomes.iter().all(|o| o.passed || o.known_failure))
+}
+
+fn main() {
+    env_logger::init();
+    match parse_args().and_then(run) {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(2);
+        }
+    }
+}
@@ -129,7 +129,7 @@ fn regenerate_game_log(
     let mut total_turns = 0;
     let mut moves_corrected = 0;
 
-    for (_turn_num, entries) in sorted_turns {
+    for (turn_num, entries) in sorted_turns {
         // Process entries in file order - entry i corresponds to snake i
         for (snake_idx, (_line_num, mut entry)) in entries.into_iter().enumerate() {
             total_turns += 1;
@@ -146,7 +146,7 @@ fn regenerate_game_log(
                 .ok_or_else(|| format!("Snake {} not found (only {} snakes)", snake_idx, board.snakes.len()))?;
 
             // Replay this turn with fixed code
-            match replay_engine.replay_turn(&board, &our_snake.id) {
+            match replay_engine.replay_turn(&board, &our_snake.id, turn_num as i32) {
                 Ok((replayed_direction, _score, _depth, _time)) => {
                     let original_move = entry["chosen_move"].as_str().unwrap_or("");
                     let replayed_move = replayed_direction.as_str();
@@ -2,20 +2,40 @@
 //
 // Usage:
 //   cargo run --bin replay -- <log_file> [options]
+//   cargo run --bin replay -- <log_dir> --all [--jobs N]   (every *.jsonl / *.jsonl.gz below log_dir)
 //
 // Options:
 //   --all                  Replay all turns
-//   --turns <turn1,turn2>  Replay specific turns (comma-separated)
+//   --turns <turn1,turn2>  Replay specific turns (comma-separated, ranges as 30..60)
+//   --min-snakes <N>       Only turns with at least N snakes on the board
+//   --near-death <K>       Only the last K turns before our death
+//   --low-health <H>       Only turns where our health is at most H
 //   --validate             Run validation mode with expected moves
 //   --verbose              Show detailed output for each turn
 //   --config <path>        Path to Snake.toml (default: Snake.toml)
+//   --depth <N>            Deterministic mode: search exactly N plies, no clock
+//   --deterministic-parallel  Search root moves in parallel, reproducibly (strategy.deterministic_parallel)
+//   --json                 Emit SearchResultV1 JSON lines instead of the report
+//   --export-html <path>   Also write a self-contained HTML replay of the replayed turns
+//   --compare <other.toml> Replay under --config and other.toml and report where they differ
+//   --what-if <turn:move>  Force a move on a turn and play both it and the bot's move forward
+//   --jobs <N>             Replay games and turns on N threads (combine with --depth)
+//   --snake-id <id>        Replay this snake instead of the one the log recorded
+//   --snake-name <name>    Replay the snake with this name when no id matches (imported games)
+//   --as <snake-id>        Replay another snake's decisions (e.g. an opponent's) from its perspective
+//   --export-puzzles <dir> Write each mismatched turn as a puzzle candidate (JSON) into dir
 
 use std::env;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 use std::process;
 
 // Import from the main crate
 use starter_snake_rust::config::Config;
-use starter_snake_rust::replay::ReplayEngine;
+use starter_snake_rust::html_report;
+use starter_snake_rust::puzzles::Puzzle;
+use starter_snake_rust::replay::{EntryFilter, ReplayEngine, ReplayResult, WhatIfLine, WhatIfReport};
 use starter_snake_rust::types::Direction;
 
 fn print_usage() {
@@ -23,13 +43,28 @@ fn print_usage() {
     eprintln!();
     eprintln!("USAGE:");
     eprintln!("  replay <log_file> [OPTIONS]");
+    eprintln!("  replay <log_dir> --all [OPTIONS]   Replay every *.jsonl(.gz) below log_dir, report per game");
     eprintln!();
     eprintln!("OPTIONS:");
     eprintln!("  --all                   Replay all turns in the log");
-    eprintln!("  --turns <T1,T2,...>     Replay specific turns (comma-separated)");
+    eprintln!("  --turns <T1,T2,...>     Replay specific turns (comma-separated, ranges as 30..60)");
+    eprintln!("  --min-snakes <N>        Only turns with at least N snakes on the board");
+    eprintln!("  --near-death <K>        Only the last K turns before our death");
+    eprintln!("  --low-health <H>        Only turns where our health is at most H");
     eprintln!("  --validate <T:M,...>    Validate expected moves (format: turn:move,...)");
+    eprintln!("  --what-if <T:M>         Force move M on turn T, roll both lines forward ([what_if] horizon)");
     eprintln!("  --verbose               Show detailed output for each turn");
     eprintln!("  --config <path>         Path to Snake.toml (default: Snake.toml)");
+    eprintln!("  --depth <N>             Search exactly N plies with no clock (reproducible)");
+    eprintln!("  --deterministic-parallel  Search root moves in parallel with reproducible results (with --depth)");
+    eprintln!("  --json                  Print one SearchResultV1 JSON object per turn (stable schema)");
+    eprintln!("  --export-html <path>    Write an animated HTML replay (with --all or --turns)");
+    eprintln!("  --compare <other.toml>  Report turns where other.toml picks a different move than --config");
+    eprintln!("  --jobs <N>              Replay games and turns on N threads (use with --depth)");
+    eprintln!("  --snake-id <id>         Replay the snake with this id (default: the logged one, else the first)");
+    eprintln!("  --snake-name <name>     Replay the snake with this name when no id matches");
+    eprintln!("  --as <snake-id>         Replay that snake's decisions from its perspective (with --all or --turns)");
+    eprintln!("  --export-puzzles <dir>  Write every mismatched turn as a puzzle candidate into dir");
     eprintln!("  --help                  Show this help message");
     eprintln!();
     eprintln!("EXAMPLES:");
@@ -44,16 +79,42 @@ fn print_usage() {
     eprintln!();
     eprintln!("  # Verbose replay of all turns");
     eprintln!("  replay battlesnake_debug.jsonl --all --verbose");
+    eprintln!();
+    eprintln!("  # Reproducible replay at a fixed depth");
+    eprintln!("  replay battlesnake_debug.jsonl --all --depth 6");
+    eprintln!();
+    eprintln!("  # Where would the tuned config have played differently?");
+    eprintln!("  replay battlesnake_debug.jsonl --all --depth 4 --compare Snake.tuned.toml");
+    eprintln!();
+    eprintln!("  # Should it have gone left on turn 42?");
+    eprintln!("  replay battlesnake_debug.jsonl --what-if 42:left --depth 3");
+    eprintln!();
+    eprintln!("  # The approach to our death, in four-snake positions from turn 30 on");
+    eprintln!("  replay battlesnake_debug.jsonl --turns 30..500 --min-snakes 4 --near-death 10 --depth 4");
+    eprintln!();
+    eprintln!("  # Match rate over a directory of games, 4 at a time");
+    eprintln!("  replay tests/fixtures/1v1_self --all --depth 4 --jobs 4");
+    eprintln!();
+    eprintln!("  # Where did the opponent go wrong? Its disagreements with our search, as puzzles");
+    eprintln!("  replay battlesnake_debug.jsonl --all --as gs_opponent --depth 6 --export-puzzles puzzles/candidates");
+    eprintln!();
+    eprintln!("  # Shareable HTML replay of a lost game");
+    eprintln!("  replay battlesnake_debug.jsonl --all --export-html game.html");
 }
 
-fn parse_turns(s: &str) -> Result<Vec<i32>, String> {
-    s.split(',')
-        .map(|t| {
-            t.trim()
-                .parse::<i32>()
-                .map_err(|e| format!("Invalid turn number '{}': {}", t, e))
-        })
-        .collect()
+/// Every *.jsonl file below `dir`, sorted by path
+fn collect_logs(dir: &Path, logs: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries = fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    for entry in entries {
+        let path = entry.map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?.path();
+        if path.is_dir() {
+            collect_logs(&path, logs)?;
+        } else if path.to_str().is_some_and(|name| name.ends_with(".jsonl") || name.ends_with(".jsonl.gz")) {
+            logs.push(path);
+        }
+    }
+    logs.sort();
+    Ok(())
 }
 
 fn parse_direction(s: &str) -> Result<Direction, String> {
@@ -89,6 +150,99 @@ fn parse_expected_moves(s: &str) -> Result<Vec<(i32, Vec<Direction>)>, String> {
         .collect()
 }
 
+/// Parses "turn:move" for --what-if
+fn parse_what_if(s: &str) -> Result<(i32, Direction), String> {
+    let (turn, dir) = s.split_once(':').ok_or_else(|| format!("Expected turn:move, got '{}'", s))?;
+    let turn = turn.trim().parse::<i32>().map_err(|_| format!("Invalid turn number: {}", turn))?;
+    Ok((turn, parse_direction(dir.trim())?))
+}
+
+fn print_what_if(report: &WhatIfReport, logged: &str, horizon: i32) {
+    let moves = |line: &WhatIfLine| line.moves.iter().map(|d| d.as_str()).collect::<Vec<_>>().join(" ");
+    println!("═══════════════════════════════════════════════════════════");
+    println!("                  WHAT-IF TURN {} (logged: {})", report.turn, logged);
+    println!("═══════════════════════════════════════════════════════════");
+    for (label, line) in [("Forced", &report.forced), ("Bot", &report.baseline)] {
+        let first = line.first_move().map_or("-", |d| d.as_str());
+        println!("{:<7} {:<6} {}", label, first, line.describe());
+        println!("        moves: {}", moves(line));
+    }
+    println!();
+    let gain = report.survival_gain(horizon);
+    if report.forced.first_move() == report.baseline.first_move() {
+        println!("The bot already plays the forced move.");
+    } else if gain > 0 {
+        println!("Verdict: the forced move survives {} turn(s) longer.", gain);
+    } else if gain < 0 {
+        println!("Verdict: the bot's move survives {} turn(s) longer.", -gain);
+    } else {
+        println!("Verdict: no difference within {} turns.", horizon);
+    }
+}
+
+/// Progress/status output; goes to stderr in --json mode so stdout stays valid JSON lines
+fn status(json: bool, message: &str) {
+    if json {
+        eprintln!("{}", message);
+    } else {
+        println!("{}", message);
+    }
+}
+
+/// Writes the --export-html page for the replayed turns
+fn export_html(path: &str, log_file: &str, results: &[ReplayResult], config: &Config, json: bool) {
+    let written = html_report::render(&format!("Replay of {}", log_file), results, config)
+        .and_then(|html| fs::write(path, html).map_err(|e| format!("Failed to write {}: {}", path, e)));
+    match written {
+        Ok(()) => status(json, &format!("HTML replay written to {}", path)),
+        Err(e) => {
+            eprintln!("Error exporting HTML: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Writes --export-puzzles candidates: one puzzle per mismatched turn, named after the log,
+/// the turn and the snake
+fn export_puzzles(dir: &str, log_file: &str, results: &[ReplayResult], opponent: bool, json: bool) {
+    if let Err(e) = fs::create_dir_all(dir) {
+        eprintln!("Error: Could not create {}: {}", dir, e);
+        process::exit(1);
+    }
+    let game = Path::new(log_file).file_name().and_then(|name| name.to_str()).unwrap_or("game");
+    let game = game.trim_end_matches(".gz").trim_end_matches(".jsonl");
+    let tag = if opponent { "opponent-mistake" } else { "replay-mismatch" };
+    let mismatches: Vec<&ReplayResult> = results.iter().filter(|r| !r.matches).collect();
+    for result in &mismatches {
+        let name = format!("{} turn {} ({})", game, result.turn, result.our_id);
+        let puzzle = Puzzle::from_replay(result, name, tag);
+        let path = Path::new(dir).join(format!("{}_turn{:03}_{}.json", game, result.turn, result.our_id));
+        let written = serde_json::to_string_pretty(&puzzle).map_err(|e| e.to_string()).and_then(|body| fs::write(&path, body).map_err(|e| e.to_string()));
+        if let Err(e) = written {
+            eprintln!("Error: Could not write {}: {}", path.display(), e);
+            process::exit(1);
+        }
+    }
+    status(json, &format!("Wrote {} puzzle candidate(s) to {} (review them before moving any into tests/puzzles)", mismatches.len(), dir));
+}
+
+/// Prints the human-readable report, or one SearchResultV1 per line with --json
+fn emit_results(engine: &ReplayEngine, results: &[ReplayResult], json: bool) {
+    if !json {
+        // Colors only on a terminal; piped reports get the plain (pasteable) grid
+        let colored = std::io::stdout().is_terminal();
+        engine.print_report(results, |board| if colored { board.render() } else { board.render_ascii() });
+        return;
+    }
+
+    for result in results {
+        match serde_json::to_string(&result.to_schema()) {
+            Ok(line) => println!("{}", line),
+            Err(e) => eprintln!("Failed to serialize turn {}: {}", result.turn, e),
+        }
+    }
+}
+
 fn main() {
     // Initialize logger
     env_logger::init();
@@ -107,6 +261,15 @@ fn main() {
     let log_file = &args[1];
     let mut config_path = "Snake.toml".to_string();
     let mut verbose = false;
+    let mut fixed_depth: Option<u8> = None;
+    let mut deterministic_parallel = false;
+    let mut json = false;
+    let mut html_path: Option<String> = None;
+    let mut compare_path: Option<String> = None;
+    let mut jobs: Option<usize> = None;
+    let mut perspective: Option<String> = None;
+    let mut puzzle_dir: Option<String> = None;
+    let mut filter = EntryFilter::default();
     let mut mode = None;
 
     // Parse arguments
@@ -121,6 +284,10 @@ fn main() {
                     eprintln!("Error: --turns requires an argument");
                     process::exit(1);
                 }
+                filter.turns = EntryFilter::parse_turns(&args[i + 1]).unwrap_or_else(|e| {
+                    eprintln!("Error parsing turns: {}", e);
+                    process::exit(1);
+                });
                 mode = Some("turns");
                 i += 1;
             }
@@ -132,6 +299,14 @@ fn main() {
                 mode = Some("validate");
                 i += 1;
             }
+            "--what-if" => {
+                if i + 1 >= args.len() {
+                    eprintln!("Error: --what-if requires an argument");
+                    process::exit(1);
+                }
+                mode = Some("what-if");
+                i += 1;
+            }
             "--config" => {
                 if i + 1 >= args.len() {
                     eprintln!("Error: --config requires an argument");
@@ -140,9 +315,96 @@ fn main() {
                 config_path = args[i + 1].clone();
                 i += 1;
             }
+            "--depth" => {
+                if i + 1 >= args.len() {
+                    eprintln!("Error: --depth requires an argument");
+                    process::exit(1);
+                }
+                fixed_depth = match args[i + 1].parse::<u8>() {
+                    Ok(depth) if depth > 0 => Some(depth),
+                    _ => {
+                        eprintln!("Error: Invalid depth '{}'", args[i + 1]);
+                        process::exit(1);
+                    }
+                };
+                i += 1;
+            }
+            "--deterministic-parallel" => {
+                deterministic_parallel = true;
+            }
             "--verbose" => {
                 verbose = true;
             }
+            "--json" => {
+                json = true;
+            }
+            "--export-html" => {
+                if i + 1 >= args.len() {
+                    eprintln!("Error: --export-html requires an argument");
+                    process::exit(1);
+                }
+                html_path = Some(args[i + 1].clone());
+                i += 1;
+            }
+            "--compare" => {
+                if i + 1 >= args.len() {
+                    eprintln!("Error: --compare requires an argument");
+                    process::exit(1);
+                }
+                compare_path = Some(args[i + 1].clone());
+                i += 1;
+            }
+            "--min-snakes" | "--near-death" | "--low-health" => {
+                let value = args.get(i + 1).and_then(|v| v.parse::<i32>().ok()).filter(|&v| v >= 0);
+                let value = value.unwrap_or_else(|| {
+                    eprintln!("Error: {} requires a non-negative number", args[i]);
+                    process::exit(1);
+                });
+                match args[i].as_str() {
+                    "--min-snakes" => filter.min_snakes = Some(value as usize),
+                    "--near-death" => filter.near_death = Some(value),
+                    _ => filter.low_health = Some(value),
+                }
+                i += 1;
+            }
+            "--snake-id" | "--snake-name" => {
+                let value = args.get(i + 1).cloned().unwrap_or_else(|| {
+                    eprintln!("Error: {} requires an argument", args[i]);
+                    process::exit(1);
+                });
+                if args[i] == "--snake-id" {
+                    filter.snake.id = Some(value);
+                } else {
+                    filter.snake.name = Some(value);
+                }
+                i += 1;
+            }
+            "--as" | "--export-puzzles" => {
+                let value = args.get(i + 1).cloned().unwrap_or_else(|| {
+                    eprintln!("Error: {} requires an argument", args[i]);
+                    process::exit(1);
+                });
+                if args[i] == "--as" {
+                    perspective = Some(value);
+                } else {
+                    puzzle_dir = Some(value);
+                }
+                i += 1;
+            }
+            "--jobs" => {
+                if i + 1 >= args.len() {
+                    eprintln!("Error: --jobs requires an argument");
+                    process::exit(1);
+                }
+                jobs = match args[i + 1].parse::<usize>() {
+                    Ok(n) if n > 0 => Some(n),
+                    _ => {
+                        eprintln!("Error: Invalid job count '{}'", args[i + 1]);
+                        process::exit(1);
+                    }
+                };
+                i += 1;
+            }
             _ => {
                 eprintln!("Error: Unknown option '{}'", args[i]);
                 print_usage();
@@ -153,24 +415,105 @@ fn main() {
     }
 
     if mode.is_none() {
-        eprintln!("Error: Must specify --all, --turns, or --validate");
+        eprintln!("Error: Must specify --all, --turns, --validate or --what-if");
         print_usage();
         process::exit(1);
     }
 
     // Load configuration
-    let config = Config::from_file(&config_path).unwrap_or_else(|e| {
+    let mut config = Config::from_file(&config_path).unwrap_or_else(|e| {
         eprintln!("Warning: Could not load config from '{}': {}", config_path, e);
         eprintln!("Using default configuration");
         Config::default_hardcoded()
     });
+    config.strategy.deterministic_parallel |= deterministic_parallel;
 
-    println!("Loaded configuration from: {}", config_path);
-    println!("Replay log file: {}", log_file);
-    println!();
+    status(json, &format!("Loaded configuration from: {}", config_path));
+    status(json, &format!("Replay log file: {}", log_file));
+    if let Some(depth) = fixed_depth {
+        let search = if config.strategy.deterministic_parallel { "parallel" } else { "sequential" };
+        status(json, &format!("Deterministic mode: fixed depth {} ({} search)", depth, search));
+    }
+    status(json, "");
+
+    if (html_path.is_some() || compare_path.is_some() || json) && mode == Some("what-if") {
+        eprintln!("Error: --what-if does not combine with --export-html, --compare or --json");
+        process::exit(1);
+    }
+    if html_path.is_some() && mode == Some("validate") {
+        eprintln!("Error: --export-html needs --all or --turns");
+        process::exit(1);
+    }
+    if !filter.is_empty() && matches!(mode, Some("validate") | Some("what-if")) {
+        eprintln!("Error: turn filters need --all or --turns");
+        process::exit(1);
+    }
+    if compare_path.is_some() && (mode == Some("validate") || json || html_path.is_some()) {
+        eprintln!("Error: --compare needs --all or --turns and does not combine with --json or --export-html");
+        process::exit(1);
+    }
+
+    if perspective.is_some() && (!matches!(mode, Some("all") | Some("turns")) || compare_path.is_some() || jobs.is_some()) {
+        eprintln!("Error: --as needs --all or --turns and does not combine with --compare or --jobs");
+        process::exit(1);
+    }
+    if perspective.is_some() && (filter.snake.id.is_some() || filter.snake.name.is_some()) {
+        eprintln!("Error: --as names the replayed snake itself; drop --snake-id / --snake-name");
+        process::exit(1);
+    }
+    if puzzle_dir.is_some() && !matches!(mode, Some("all") | Some("turns")) {
+        eprintln!("Error: --export-puzzles needs --all or --turns");
+        process::exit(1);
+    }
+    // --low-health looks at the replayed snake
+    if let Some(id) = &perspective {
+        filter.snake.id = Some(id.clone());
+    }
+
+    // Create replay engine (the HTML export evaluates components with the same config)
+    let engine = ReplayEngine::new(config.clone(), verbose).with_fixed_depth(fixed_depth).with_snake(filter.snake.clone());
 
-    // Create replay engine
-    let engine = ReplayEngine::new(config, verbose);
+    // Replays fan out on their own pool: each search runs on the global pool, and a job
+    // waiting for its search must not hold a thread that search needs
+    let pool = jobs.map(|n| {
+        rayon::ThreadPoolBuilder::new().num_threads(n).build().unwrap_or_else(|e| {
+            eprintln!("Error: Could not start {} replay threads: {}", n, e);
+            process::exit(1);
+        })
+    });
+    if jobs.is_some() && fixed_depth.is_none() {
+        eprintln!("Warning: --jobs without --depth shares the CPU between timed searches, so they reach lower depths than in the logged game");
+    }
+
+    if Path::new(log_file).is_dir() {
+        if mode != Some("all") || json || html_path.is_some() || compare_path.is_some() || perspective.is_some() || puzzle_dir.is_some() {
+            eprintln!("Error: a log directory needs --all and does not combine with --json, --export-html, --compare, --as or --export-puzzles");
+            process::exit(1);
+        }
+        let mut logs = Vec::new();
+        if let Err(e) = collect_logs(Path::new(log_file), &mut logs) {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+        if logs.is_empty() {
+            eprintln!("Error: No .jsonl logs found in {}", log_file);
+            process::exit(1);
+        }
+
+        println!("Replaying {} game(s) on {} thread(s)...", logs.len(), jobs.unwrap_or(1));
+        let games = match &pool {
+            Some(pool) => pool.install(|| engine.replay_files(&logs, &filter)),
+            None => logs
+                .iter()
+                .map(|path| {
+                    let results = engine.load_log_file(path).and_then(|entries| engine.replay_all(&filter.select(&entries)));
+                    (path.clone(), results)
+                })
+                .collect(),
+        };
+        engine.print_batch_report(&games);
+        return;
+    }
 
     // Load log file
     let entries = match engine.load_log_file(log_file) {
@@ -186,15 +529,70 @@ fn main() {
         process::exit(1);
     }
 
-    println!("Loaded {} log entries\n", entries.len());
+    status(json, &format!("Loaded {} log entries\n", entries.len()));
+
+    // Turn selection for --all / --turns and --compare; single turns must exist
+    if let Some((turn, _)) = filter.turns.iter().find(|&&(from, to)| from == to && !entries.iter().any(|e| e.turn == from)) {
+        eprintln!("Error: Turn {} not found in log file", turn);
+        process::exit(1);
+    }
+    let selected = filter.select(&entries);
+    if selected.is_empty() && matches!(mode, Some("all") | Some("turns")) {
+        eprintln!("Error: No log entries match the turn filters");
+        process::exit(1);
+    }
+
+    if let Some(other_path) = &compare_path {
+        let mut other = match Config::from_file(other_path) {
+            Ok(other) => other,
+            Err(e) => {
+                eprintln!("Error loading {}: {}", other_path, e);
+                process::exit(1);
+            }
+        };
+        other.strategy.deterministic_parallel |= deterministic_parallel;
+        println!("Comparing {} turn(s) against {}...", selected.len(), other_path);
+        match engine.compare(&selected, &config, &other) {
+            Ok(comparison) => engine.print_comparison(&comparison, &config_path, other_path),
+            Err(e) => {
+                eprintln!("Error during comparison: {}", e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
 
     // Execute based on mode
     match mode.as_deref() {
-        Some("all") => {
-            println!("Replaying all {} turns...\n", entries.len());
-            match engine.replay_all(&entries) {
+        Some("all") | Some("turns") => {
+            if filter.is_empty() {
+                status(json, &format!("Replaying all {} turns...\n", selected.len()));
+            } else if mode == Some("turns") && filter.turns.iter().all(|(from, to)| from == to) {
+                status(json, &format!("Replaying {} specific turn(s)...\n", selected.len()));
+            } else {
+                status(json, &format!("Replaying {} of {} turns...\n", selected.len(), entries.len()));
+            }
+            let replayed = match (&perspective, &pool) {
+                // The snake's move on a turn is read off the next turn's board, so it replays
+                // from every entry and keeps the selected turns
+                (Some(id), _) => engine
+                    .replay_as(&entries, id)
+                    .map(|results| results.into_iter().filter(|r| selected.iter().any(|e| e.turn == r.turn)).collect()),
+                (None, Some(pool)) => Ok(pool.install(|| engine.replay_all_parallel(&selected))),
+                (None, None) => engine.replay_all(&selected),
+            };
+            match replayed {
                 Ok(results) => {
-                    engine.print_report(&results);
+                    emit_results(&engine, &results, json);
+                    if !json && perspective.is_none() {
+                        engine.print_death_summary(&entries);
+                    }
+                    if let Some(path) = &html_path {
+                        export_html(path, log_file, &results, &config, json);
+                    }
+                    if let Some(dir) = &puzzle_dir {
+                        export_puzzles(dir, log_file, &results, perspective.is_some(), json);
+                    }
                 }
                 Err(e) => {
                     eprintln!("Error during replay: {}", e);
@@ -202,23 +600,29 @@ fn main() {
                 }
             }
         }
-        Some("turns") => {
-            let turn_arg = &args[args.iter().position(|a| a == "--turns").unwrap() + 1];
-            let turns = match parse_turns(turn_arg) {
-                Ok(t) => t,
+        Some("what-if") => {
+            let arg = &args[args.iter().position(|a| a == "--what-if").unwrap() + 1];
+            let (turn, forced) = match parse_what_if(arg) {
+                Ok(parsed) => parsed,
                 Err(e) => {
-                    eprintln!("Error parsing turns: {}", e);
+                    eprintln!("Error parsing --what-if: {}", e);
                     process::exit(1);
                 }
             };
-
-            println!("Replaying {} specific turn(s)...\n", turns.len());
-            match engine.replay_turns(&entries, &turns) {
-                Ok(results) => {
-                    engine.print_report(&results);
+            let entry = match entries.iter().find(|e| e.turn == turn) {
+                Some(entry) => entry,
+                None => {
+                    eprintln!("Error: Turn {} not found in log", turn);
+                    process::exit(1);
                 }
+            };
+
+            let horizon = config.what_if.horizon;
+            println!("What-if on turn {}: forcing {} (horizon {} turns)...\n", turn, forced.as_str(), horizon);
+            match engine.what_if(entry, forced) {
+                Ok(report) => print_what_if(&report, entry.chosen_move.as_str(), horizon),
                 Err(e) => {
-                    eprintln!("Error during replay: {}", e);
+                    eprintln!("Error during what-if: {}", e);
                     process::exit(1);
                 }
             }
@@ -0,0 +1,122 @@
+//! SPRT Match Runner
+//!
+//! Plays a candidate config against a baseline in the self-play arena until the sequential
+//! probability ratio test from [rating] accepts or rejects the candidate (see src/rating.rs).
+//!
+//! Usage:
+//!   cargo run --release --bin sprt_match -- <candidate.toml> [baseline.toml] [--elo0 E] [--elo1 E] [--max-games N]
+//!
+//! Output:
+//!   - One line per batch: W/D/L, Elo with 95% interval, LLR against its bounds
+//!   - Final verdict: ACCEPT (candidate is stronger), REJECT, or UNDECIDED at max_games
+//!   - Exit code 0 on ACCEPT, 1 on REJECT/UNDECIDED, 2 on errors
+
+use starter_snake_rust::arena;
+use starter_snake_rust::config::Config;
+use starter_snake_rust::rating::{self, SprtDecision};
+use std::env;
+use std::process;
+use std::time::Instant;
+
+struct Args {
+    candidate: String,
+    baseline: String,
+    elo0: Option<f64>,
+    elo1: Option<f64>,
+    max_games: Option<u32>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut paths = Vec::new();
+    let (mut elo0, mut elo1, mut max_games) = (None, None, None);
+    let mut iter = env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| iter.next().ok_or_else(|| format!("{} needs a value", flag));
+        match arg.as_str() {
+            "--elo0" => elo0 = Some(value("--elo0")?.parse().map_err(|e| format!("--elo0: {}", e))?),
+            "--elo1" => elo1 = Some(value("--elo1")?.parse().map_err(|e| format!("--elo1: {}", e))?),
+            "--max-games" => max_games = Some(value("--max-games")?.parse().map_err(|e| format!("--max-games: {}", e))?),
+            flag if flag.starts_with("--") => return Err(format!("Unknown flag {}", flag)),
+            path => paths.push(path.to_string()),
+        }
+    }
+    let mut paths = paths.into_iter();
+    let candidate = paths.next().ok_or("Usage: sprt_match <candidate.toml> [baseline.toml] [--elo0 E] [--elo1 E] [--max-games N]")?;
+    let baseline = paths.next().unwrap_or_else(|| "Snake.toml".to_string());
+    Ok(Args { candidate, baseline, elo0, elo1, max_games })
+}
+
+fn load(path: &str) -> Result<Config, String> {
+    let config = Config::from_file(path)?;
+    let errors = config.validation_errors();
+    match errors.first() {
+        Some(first) => Err(format!("{} has {} config error(s), first: {}", path, errors.len(), first)),
+        None => Ok(config),
+    }
+}
+
+fn run(args: Args) -> Result<SprtDecision, String> {
+    let candidate = load(&args.candidate)?;
+    let baseline = load(&args.baseline)?;
+    // Arena and test settings come from the baseline
+    let mut rating = baseline.rating.clone();
+    rating.elo0 = args.elo0.unwrap_or(rating.elo0);
+    rating.elo1 = args.elo1.unwrap_or(rating.elo1);
+    rating.max_games = args.max_games.unwrap_or(rating.max_games);
+    if rating.elo0 >= rating.elo1 {
+        return Err(format!("elo0 ({}) must be below elo1 ({})", rating.elo0, rating.elo1));
+    }
+
+    println!("════════════════════════════════════════════════════════");
+    println!("                     SPRT MATCH");
+    println!("════════════════════════════════════════════════════════");
+    println!("Candidate: {}", args.candidate);
+    println!("Baseline:  {}", args.baseline);
+    println!(
+        "H0: elo <= {}  H1: elo >= {}  (alpha {}, beta {}, max {} games)",
+        rating.elo0, rating.elo1, rating.alpha, rating.beta, rating.max_games
+    );
+    println!();
+
+    let started = Instant::now();
+    let (total, status) = arena::play_sprt(&candidate, &baseline, &baseline.arena, &rating, baseline.tuning.seed, |result, status| {
+        let elo = rating::elo(result).expect("a batch was played");
+        println!(
+            "{:>5} games | {:>4}W {:>4}D {:>4}L | Elo {:>+7.1} [{:+.1}, {:+.1}] | LLR {:>+6.2} ({:.2}, {:.2})",
+            result.games(),
+            result.wins,
+            result.draws,
+            result.losses,
+            elo.elo,
+            elo.lower,
+            elo.upper,
+            status.llr,
+            status.lower_bound,
+            status.upper_bound
+        );
+    })?;
+
+    let verdict = match status.decision {
+        SprtDecision::Accept => "ACCEPT: the candidate is stronger",
+        SprtDecision::Reject => "REJECT: the candidate is not stronger",
+        SprtDecision::Continue => "UNDECIDED: max_games reached",
+    };
+    println!();
+    println!("{} after {} games ({:.1}s)", verdict, total.games(), started.elapsed().as_secs_f64());
+    if let Some(elo) = rating::elo(&total) {
+        println!("Elo {:+.1} +/- {:.1}", elo.elo, elo.error());
+    }
+    Ok(status.decision)
+}
+
+fn main() {
+    env_logger::init();
+    match parse_args().and_then(run) {
+        Ok(SprtDecision::Accept) => {}
+        Ok(_) => process::exit(1),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,116 @@
+//! SPSA Parameter Tuner
+//!
+//! Tunes the [scores] fields listed in [tuning.parameters] by self-play: every iteration
+//! plays an arena match between two randomly perturbed configs and steps toward the winner
+//! (see src/tuning.rs). The result is a copy of the config file with [scores] updated.
+//!
+//! Usage:
+//!   cargo run --release --bin tune_spsa -- [config.toml] [--iterations N] [--games N] [--output PATH]
+//!
+//! Output:
+//!   - One line per iteration: match score of the plus config and the current values
+//!   - The tuned config (default: tuning.output_path)
+
+use starter_snake_rust::arena;
+use starter_snake_rust::config::Config;
+use starter_snake_rust::tuning::{self, Spsa};
+use std::env;
+use std::fs;
+use std::process;
+use std::time::Instant;
+
+struct Args {
+    config_path: String,
+    iterations: Option<u32>,
+    games: Option<u32>,
+    output: Option<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args { config_path: "Snake.toml".to_string(), iterations: None, games: None, output: None };
+    let mut iter = env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        let mut value = |flag: &str| iter.next().ok_or_else(|| format!("{} needs a value", flag));
+        match arg.as_str() {
+            "--iterations" => args.iterations = Some(value("--iterations")?.parse().map_err(|e| format!("--iterations: {}", e))?),
+            "--games" => args.games = Some(value("--games")?.parse().map_err(|e| format!("--games: {}", e))?),
+            "--output" => args.output = Some(value("--output")?),
+            flag if flag.starts_with("--") => return Err(format!("Unknown flag {}", flag)),
+            path => args.config_path = path.to_string(),
+        }
+    }
+    Ok(args)
+}
+
+fn format_values(values: &[(String, f64)]) -> String {
+    values
+        .iter()
+        .map(|(name, value)| format!("{}={:.2}", name, value))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let mut config = Config::from_file(&args.config_path)?;
+    if let Some(iterations) = args.iterations {
+        config.tuning.iterations = iterations;
+    }
+    if let Some(games) = args.games {
+        config.tuning.games_per_iteration = games;
+    }
+    let errors = config.validation_errors();
+    if let Some(first) = errors.first() {
+        return Err(format!("{} has {} config error(s), first: {}", args.config_path, errors.len(), first));
+    }
+    let output = args.output.clone().unwrap_or_else(|| config.tuning.output_path.clone());
+
+    let mut spsa = Spsa::new(&config)?;
+    let t = &config.tuning;
+    println!("════════════════════════════════════════════════════════");
+    println!("                 SPSA PARAMETER TUNING");
+    println!("════════════════════════════════════════════════════════");
+    println!(
+        "{} iterations x {} games, board {}x{}, depth {}, max {} turns",
+        t.iterations, t.games_per_iteration, config.arena.board_size, config.arena.board_size, config.arena.search_depth, config.arena.max_turns
+    );
+    println!("Start: {}", format_values(&spsa.values()));
+    println!();
+
+    let started = Instant::now();
+    for _ in 0..t.iterations {
+        let k = spsa.iteration();
+        let perturbation = spsa.perturb()?;
+        let seed = t.seed.wrapping_add(u64::from(k) * u64::from(t.games_per_iteration));
+        let result = arena::play_match(&perturbation.plus, &perturbation.minus, &config.arena, t.games_per_iteration, seed)?;
+        spsa.update(&perturbation, result.score());
+
+        println!(
+            "[{:>3}] plus {:>2}W {:>2}D {:>2}L ({:.2}) | {}",
+            k + 1,
+            result.wins,
+            result.draws,
+            result.losses,
+            result.score(),
+            format_values(&spsa.values())
+        );
+    }
+
+    let template = fs::read_to_string(&args.config_path).map_err(|e| format!("Failed to read {}: {}", args.config_path, e))?;
+    let tuned = tuning::rewrite_scores(&template, &config.scores, &spsa.values())?;
+    fs::write(&output, tuned).map_err(|e| format!("Failed to write {}: {}", output, e))?;
+
+    println!();
+    println!("Done in {:.1}s", started.elapsed().as_secs_f64());
+    println!("Tuned: {}", format_values(&spsa.values()));
+    println!("Written to {}", output);
+    Ok(())
+}
+
+fn main() {
+    env_logger::init();
+    let result = parse_args().and_then(run);
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
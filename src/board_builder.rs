@@ -0,0 +1,228 @@
+// Compact construction of test positions
+//
+// Positions in tests and puzzles used to be written out as full API JSON: every snake with
+// its id, name, latency, head and length, every coordinate as an object. BoardBuilder takes
+// bodies as (x, y) lists and fills in the rest; from_ascii reads a drawn grid instead:
+//
+//     . . * . .
+//     . a a a> .      a>  head of snake "a"     *  food
+//     . a . b> .      a   body segment of "a"   #  hazard (also as a suffix: a#, *#)
+//     . . . b .       .   empty
+//
+// The top row is the highest y, as on the Battlesnake board. Bodies are traced from the head
+// through adjacent segments of the same letter, so a drawing where a segment touches two
+// unvisited segments of its own snake is ambiguous and rejected; use BoardBuilder::snake for
+// coiled or stacked bodies.
+
+use std::collections::BTreeMap;
+
+use crate::types::{Battlesnake, Board, Coord};
+
+/// Builder for a Board (snakes get 100 health unless set)
+#[derive(Debug, Clone)]
+pub struct BoardBuilder {
+    width: i32,
+    height: i32,
+    food: Vec<Coord>,
+    hazards: Vec<Coord>,
+    snakes: Vec<Battlesnake>,
+}
+
+fn coords(cells: &[(i32, i32)]) -> Vec<Coord> {
+    cells.iter().map(|&(x, y)| Coord { x, y }).collect()
+}
+
+impl BoardBuilder {
+    pub fn new(width: i32, height: i32) -> Self {
+        BoardBuilder { width, height, food: Vec::new(), hazards: Vec::new(), snakes: Vec::new() }
+    }
+
+    /// Adds a snake with `body` listed head first (repeat a cell for stacked segments)
+    ///
+    /// # Panics
+    /// If `body` is empty
+    pub fn snake(mut self, id: &str, body: &[(i32, i32)]) -> Self {
+        assert!(!body.is_empty(), "snake '{}' needs at least one segment", id);
+        let body = coords(body);
+        self.snakes.push(Battlesnake {
+            id: id.to_string(),
+            name: id.to_string(),
+            health: 100,
+            head: body[0],
+            length: body.len() as i32,
+            body,
+            latency: "0".to_string(),
+            shout: None,
+            squad: None,
+        });
+        self
+    }
+
+    /// Sets the health of a snake added earlier
+    ///
+    /// # Panics
+    /// If no snake has this id
+    pub fn health(mut self, id: &str, health: i32) -> Self {
+        match self.snakes.iter_mut().find(|s| s.id == id) {
+            Some(snake) => snake.health = health,
+            None => panic!("no snake '{}' on the board", id),
+        }
+        self
+    }
+
+    pub fn food(mut self, cells: &[(i32, i32)]) -> Self {
+        self.food.extend(coords(cells));
+        self
+    }
+
+    pub fn hazards(mut self, cells: &[(i32, i32)]) -> Self {
+        self.hazards.extend(coords(cells));
+        self
+    }
+
+    /// Parses a drawn grid (see the module comment); snakes are named by their letter and
+    /// added in alphabetical order
+    pub fn from_ascii(ascii: &str) -> Result<Self, String> {
+        let rows: Vec<Vec<&str>> = ascii
+            .lines()
+            .map(|line| line.split_whitespace().collect::<Vec<_>>())
+            .filter(|row| !row.is_empty())
+            .collect();
+        let height = rows.len() as i32;
+        let width = rows.first().map_or(0, |row| row.len()) as i32;
+        if width == 0 {
+            return Err("empty board".to_string());
+        }
+
+        let mut builder = BoardBuilder::new(width, height);
+        let mut heads: BTreeMap<char, Coord> = BTreeMap::new();
+        let mut segments: BTreeMap<char, Vec<Coord>> = BTreeMap::new();
+        for (row_idx, row) in rows.iter().enumerate() {
+            if row.len() as i32 != width {
+                return Err(format!("row {} has {} cells, expected {}", row_idx + 1, row.len(), width));
+            }
+            let y = height - 1 - row_idx as i32;
+            for (x, &token) in row.iter().enumerate() {
+                let c = Coord { x: x as i32, y };
+                let (token, hazard) = match token.strip_suffix('#') {
+                    Some(rest) if !rest.is_empty() => (rest, true),
+                    _ => (token, false),
+                };
+                if hazard {
+                    builder.hazards.push(c);
+                }
+                let mut chars = token.chars();
+                match (chars.next(), chars.next(), chars.next()) {
+                    (Some('.'), None, _) => {}
+                    (Some('*'), None, _) => builder.food.push(c),
+                    (Some('#'), None, _) => builder.hazards.push(c),
+                    (Some(letter), None, _) if letter.is_ascii_lowercase() => segments.entry(letter).or_default().push(c),
+                    (Some(letter), Some('>'), None) if letter.is_ascii_lowercase() => {
+                        if heads.insert(letter, c).is_some() {
+                            return Err(format!("snake '{}' has two heads", letter));
+                        }
+                    }
+                    _ => return Err(format!("unknown cell '{}' at ({}, {})", token, c.x, y)),
+                }
+            }
+        }
+
+        if let Some(letter) = segments.keys().find(|letter| !heads.contains_key(letter)) {
+            return Err(format!("snake '{}' has no head (mark it '{}>')", letter, letter));
+        }
+        for (letter, head) in heads {
+            let body = trace_body(letter, head, segments.remove(&letter).unwrap_or_default())?;
+            let cells: Vec<(i32, i32)> = body.iter().map(|c| (c.x, c.y)).collect();
+            builder = builder.snake(&letter.to_string(), &cells);
+        }
+        Ok(builder)
+    }
+
+    pub fn build(self) -> Board {
+        Board { height: self.height as u32, width: self.width, food: self.food, snakes: self.snakes, hazards: self.hazards, occupancy: None }
+    }
+}
+
+/// Orders `segments` into a body by walking from the head to the tail
+fn trace_body(letter: char, head: Coord, mut segments: Vec<Coord>) -> Result<Vec<Coord>, String> {
+    let mut body = vec![head];
+    loop {
+        let last = body[body.len() - 1];
+        let adjacent: Vec<usize> = segments
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.manhattan(last) == 1)
+            .map(|(i, _)| i)
+            .collect();
+        match adjacent.as_slice() {
+            [] => break,
+            [next] => body.push(segments.swap_remove(*next)),
+            _ => {
+                return Err(format!(
+                    "snake '{}' is ambiguous at ({}, {}): {} adjacent segments",
+                    letter,
+                    last.x,
+                    last.y,
+                    adjacent.len()
+                ))
+            }
+        }
+    }
+    match segments.first() {
+        Some(c) => Err(format!("snake '{}' has a segment at ({}, {}) not connected to its body", letter, c.x, c.y)),
+        None => Ok(body),
+    }
+}
+
+/// Board from a drawn grid with default health (see BoardBuilder::from_ascii)
+pub fn parse_board(ascii: &str) -> Result<Board, String> {
+    BoardBuilder::from_ascii(ascii).map(BoardBuilder::build)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_matches_builder() {
+        let parsed = parse_board(
+            "
+            . . * . .
+            . a a a> .
+            . a . b> .
+            . . # b *#
+            ",
+        )
+        .unwrap();
+        let built = BoardBuilder::new(5, 4)
+            .snake("a", &[(3, 2), (2, 2), (1, 2), (1, 1)])
+            .snake("b", &[(3, 1), (3, 0)])
+            .food(&[(2, 3), (4, 0)])
+            .hazards(&[(2, 0), (4, 0)])
+            .build();
+
+        assert_eq!((parsed.width, parsed.height), (5, 4));
+        assert_eq!(parsed.food, built.food);
+        assert_eq!(parsed.hazards, built.hazards);
+        assert_eq!(parsed.snakes.len(), 2);
+        for (p, b) in parsed.snakes.iter().zip(&built.snakes) {
+            assert_eq!((&p.id, &p.body, p.head, p.length, p.health), (&b.id, &b.body, b.head, b.length, b.health));
+        }
+    }
+
+    #[test]
+    fn test_ascii_rejects_unreadable_drawings() {
+        assert!(parse_board(". a\n. .").unwrap_err().contains("no head"));
+        assert!(parse_board("a> a\na a").unwrap_err().contains("ambiguous"));
+        assert!(parse_board("a> . a\n. . .").unwrap_err().contains("not connected"));
+        assert!(parse_board(". .\n.").unwrap_err().contains("row 2"));
+        assert!(parse_board("A> .").unwrap_err().contains("unknown cell"));
+    }
+
+    #[test]
+    fn test_builder_sets_health_and_stacks() {
+        let board = BoardBuilder::new(11, 11).snake("us", &[(1, 1), (1, 1), (1, 1)]).health("us", 7).build();
+        assert_eq!(board.snakes[0].length, 3);
+        assert_eq!(board.snakes[0].health, 7);
+    }
+}
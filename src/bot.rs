@@ -10,25 +10,65 @@
 // To get you started we've included code to prevent your Battlesnake from moving backwards.
 // For more info see docs.battlesnake.com
 
-use log::{info, warn};
+use cpu_time::ThreadTime;
+use log::{debug, error, info, warn};
 use rayon::prelude::*;
+use serde::Serialize;
 use serde_json::{json, Value};
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::cmp::Reverse;
+use std::collections::{HashMap};
 use std::hash::{Hash, Hasher};
-use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU8, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicU32, AtomicU8, AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
 use std::time::{Duration, Instant};
+use tokio::sync::Notify;
 
+use crate::capacity::GameCapacity;
+use crate::contempt;
 use crate::config::Config;
-use crate::debug_logger::DebugLogger;
+use crate::debug_logger::{DebugLogger, TurnNotes};
+use crate::distance_field::{ControlSummary, DistanceField, FieldSummary, FoodPaths};
+use crate::endgame;
+use crate::error::BotError;
+use crate::eval_cache;
+use crate::evaluator::{self, EvalContext, Evaluator, HeuristicEvaluator};
+use crate::flood_grid::{with_flood_grid, FloodGrid};
+use crate::move_list::MoveList;
+use crate::food_route;
+use crate::game_summary::MoveSearch;
+use crate::search_scratch::{with_search_scratch, SearchScratch};
+use crate::opening::OpeningPlan;
+use crate::request_validation;
+use crate::schema::{ScoreBreakdownV1, SearchResultV1};
+use crate::post_mortem::PostMortem;
+use crate::score_trend;
+use crate::session::{GameSession, SessionStore};
+use crate::spectator::{SpectatorFrame, SpectatorHub, Subscription};
+use crate::rules;
+use crate::game_mode::{FoodModel, GameMode, HazardModel, SearchEngine};
+use crate::hazard_walls::HazardWalls;
+use crate::snail;
+use crate::squad;
 use crate::simple_profiler;
-use crate::types::{Battlesnake, Board, Coord, Direction, Game};
+use crate::time_manager;
+use crate::timing_history::{DepthTimings, TimingHistory};
+use crate::stall;
+use crate::stakes::Stakes;
+use crate::trap;
+use crate::turn_profile::TurnProfile;
+use crate::types::{Battlesnake, Board, Coord, Direction, Game, GameState, RulesetSettings};
+use crate::win_probability::{self, Decided};
 
 /// N-tuple score representation for MaxN algorithm
 /// Each component represents the utility score for one player
 #[derive(Debug, Clone)]
-struct ScoreTuple {
-    scores: Vec<i32>,
+pub struct ScoreTuple {
+    pub scores: Vec<i32>,
+    /// Explain mode (debug.explain): our evaluation breakdown at the leaf this tuple was
+    /// scored at, carried up the tree with the tuple that wins each node
+    pub leaf: Option<Arc<DetailedScore>>,
 }
 
 impl ScoreTuple {
@@ -36,11 +76,12 @@ impl ScoreTuple {
     fn new_with_value(num_players: usize, initial_value: i32) -> Self {
         ScoreTuple {
             scores: vec![initial_value; num_players],
+            leaf: None,
         }
     }
 
     /// Gets the score for a specific player
-    fn for_player(&self, player_idx: usize) -> i32 {
+    pub fn for_player(&self, player_idx: usize) -> i32 {
         self.scores.get(player_idx).copied().unwrap_or(i32::MIN)
     }
 }
@@ -70,6 +111,16 @@ struct TranspositionEntry {
     best_move: Option<Direction>,
     /// Age for LRU eviction (generation number)
     age: u32,
+    /// NodeKey::check of the node stored here; another node hashing to the same slot is a collision
+    check: u32,
+}
+
+/// Transposition key of a search node: the board, the snake to move and an independent
+/// check value that tells two nodes sharing a table slot apart
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodeKey {
+    hash: u64,
+    check: u32,
 }
 
 /// Transposition table for caching board state evaluations
@@ -81,6 +132,11 @@ pub struct TranspositionTable {
     max_size: usize,
     /// Current generation for LRU eviction
     current_age: AtomicU32,
+    /// Score probes (probe) and how many returned a cached score, for telemetry
+    probes: AtomicU64,
+    hits: AtomicU64,
+    /// Lookups that found a different node in the slot (same hash, different check)
+    collisions: AtomicU64,
 }
 
 impl TranspositionTable {
@@ -90,50 +146,90 @@ impl TranspositionTable {
             table: RwLock::new(HashMap::with_capacity(max_size)),
             max_size,
             current_age: AtomicU32::new(0),
+            probes: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            collisions: AtomicU64::new(0),
         }
     }
 
-    /// Hashes a board state for use as transposition table key
-    /// Includes all snake positions, healths, and food positions
+    /// Side to move at best-reply min plies, where every opponent replies at once
+    pub const OPPONENTS_TO_MOVE: usize = usize::MAX;
+
+    /// Hashes a board state (eval and IDAPOS cache key; node_key adds the side to move)
+    /// Includes every alive snake's index, health and body in order, food and hazards
     pub fn hash_board(board: &Board) -> u64 {
         use std::collections::hash_map::DefaultHasher;
 
         let mut hasher = DefaultHasher::new();
 
-        // Hash snakes (position and health matter, not ID)
-        // Sort by position to ensure consistent hashing regardless of snake order
-        let mut snake_positions: Vec<_> = board.snakes.iter()
-            .filter(|s| s.health > 0)
-            .flat_map(|s| s.body.iter().map(move |coord| (coord.x, coord.y, s.health)))
-            .collect();
-        snake_positions.sort_unstable();
+        // Snakes keep their index for the whole search, and scores belong to indices: two
+        // snakes swapping places is a different position. Dead snakes are hashed as absent.
+        for (idx, snake) in board.snakes.iter().enumerate().filter(|(_, s)| s.health > 0) {
+            idx.hash(&mut hasher);
+            snake.health.hash(&mut hasher);
+            snake.body.len().hash(&mut hasher);
+            for coord in &snake.body {
+                (coord.x, coord.y).hash(&mut hasher);
+            }
+        }
 
-        for (x, y, health) in snake_positions {
-            x.hash(&mut hasher);
-            y.hash(&mut hasher);
-            health.hash(&mut hasher);
+        // Food and hazards as sets: the engine does not keep them in any order
+        for cells in [&board.food, &board.hazards] {
+            let mut positions: Vec<_> = cells.iter().map(|c| (c.x, c.y)).collect();
+            positions.sort_unstable();
+            positions.len().hash(&mut hasher);
+            for (x, y) in positions {
+                x.hash(&mut hasher);
+                y.hash(&mut hasher);
+            }
         }
 
-        // Hash food positions
-        let mut food_positions: Vec<_> = board.food.iter().map(|c| (c.x, c.y)).collect();
-        food_positions.sort_unstable();
+        hasher.finish()
+    }
 
-        for (x, y) in food_positions {
-            x.hash(&mut hasher);
-            y.hash(&mut hasher);
+    /// Key of the node on `board` (hashed by hash_board as `board_hash`) where snake
+    /// `to_move` moves next (OPPONENTS_TO_MOVE at best-reply min plies)
+    ///
+    /// The check is FNV-1a over the same features, independent of the SipHash slot hash,
+    /// so two nodes sharing a slot are told apart with probability 1 - 2^-32.
+    pub fn node_key(board_hash: u64, board: &Board, to_move: usize) -> NodeKey {
+        let hash = board_hash ^ (to_move as u64).wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+
+        let mut check: u32 = 0x811C_9DC5;
+        let mut mix = |value: i32| {
+            for byte in value.to_le_bytes() {
+                check = (check ^ byte as u32).wrapping_mul(0x0100_0193);
+            }
+        };
+        mix(to_move as i32);
+        for (idx, snake) in board.snakes.iter().enumerate().filter(|(_, s)| s.health > 0) {
+            mix(idx as i32);
+            mix(snake.health);
+            mix(snake.body.len() as i32);
+            for coord in &snake.body {
+                mix(coord.x);
+                mix(coord.y);
+            }
+        }
+        // Food and hazards summed, so their order does not matter
+        for cells in [&board.food, &board.hazards] {
+            mix(cells.len() as i32);
+            mix(cells.iter().fold(0i32, |sum, c| sum.wrapping_add(c.x.wrapping_mul(1021).wrapping_add(c.y))));
         }
 
-        hasher.finish()
+        NodeKey { hash, check }
     }
 
     /// Probes the transposition table for a cached evaluation
     /// Returns Some(score) if found and depth is sufficient, None otherwise
-    pub fn probe(&self, board_hash: u64, required_depth: u8) -> Option<i32> {
+    pub fn probe(&self, key: NodeKey, required_depth: u8) -> Option<i32> {
+        self.probes.fetch_add(1, Ordering::Relaxed);
         let table = self.table.read().ok()?;
 
-        if let Some(entry) = table.get(&board_hash) {
+        if let Some(entry) = self.entry(&table, key) {
             // Only use cached value if it was searched to at least the required depth
             if entry.depth >= required_depth {
+                self.hits.fetch_add(1, Ordering::Relaxed);
                 return Some(entry.score);
             }
         }
@@ -142,10 +238,10 @@ impl TranspositionTable {
     }
 
     /// Probes the transposition table and returns both score and best move
-    pub fn probe_with_move(&self, board_hash: u64, required_depth: u8) -> Option<(i32, Option<Direction>)> {
+    pub fn probe_with_move(&self, key: NodeKey, required_depth: u8) -> Option<(i32, Option<Direction>)> {
         let table = self.table.read().ok()?;
 
-        if let Some(entry) = table.get(&board_hash) {
+        if let Some(entry) = self.entry(&table, key) {
             // Only use cached value if it was searched to at least the required depth
             if entry.depth >= required_depth {
                 return Some((entry.score, entry.best_move));
@@ -155,9 +251,19 @@ impl TranspositionTable {
         None
     }
 
+    /// The entry of `key`'s node; a different node in its slot counts as a collision
+    fn entry<'a>(&self, table: &'a HashMap<u64, TranspositionEntry>, key: NodeKey) -> Option<&'a TranspositionEntry> {
+        let entry = table.get(&key.hash)?;
+        if entry.check != key.check {
+            self.collisions.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+        Some(entry)
+    }
+
     /// Stores an evaluation in the transposition table
     /// Performs LRU eviction if table is full
-    pub fn store(&self, board_hash: u64, score: i32, depth: u8, bound_type: BoundType, best_move: Option<Direction>) {
+    pub fn store(&self, key: NodeKey, score: i32, depth: u8, bound_type: BoundType, best_move: Option<Direction>) {
         let current_age = self.current_age.load(Ordering::Relaxed);
 
         if let Ok(mut table) = self.table.write() {
@@ -179,23 +285,25 @@ impl TranspositionTable {
             }
 
             // Store or update entry
-            match table.get_mut(&board_hash) {
-                Some(entry) if entry.depth < depth => {
-                    // Update if new depth is deeper
+            match table.get_mut(&key.hash) {
+                // Update if new depth is deeper, or replace another node that hashed here
+                Some(entry) if entry.depth < depth || entry.check != key.check => {
                     entry.score = score;
                     entry.depth = depth;
                     entry.bound_type = bound_type;
                     entry.best_move = best_move;
                     entry.age = current_age;
+                    entry.check = key.check;
                 }
                 None => {
                     // Insert new entry
-                    table.insert(board_hash, TranspositionEntry {
+                    table.insert(key.hash, TranspositionEntry {
                         score,
                         depth,
                         bound_type,
                         best_move,
                         age: current_age,
+                        check: key.check,
                     });
                 }
                 _ => {
@@ -210,6 +318,16 @@ impl TranspositionTable {
         self.current_age.fetch_add(1, Ordering::Relaxed);
     }
 
+    /// (probes, hits) of `probe` so far
+    pub fn hit_counts(&self) -> (u64, u64) {
+        (self.probes.load(Ordering::Relaxed), self.hits.load(Ordering::Relaxed))
+    }
+
+    /// Lookups so far that found another node in the slot (hash collisions)
+    pub fn collisions(&self) -> u64 {
+        self.collisions.load(Ordering::Relaxed)
+    }
+
     /// Returns statistics about the transposition table
     pub fn stats(&self) -> (usize, usize) {
         if let Ok(table) = self.table.read() {
@@ -231,6 +349,17 @@ enum ExecutionStrategy {
     ParallelMultiplayer,
 }
 
+/// Search run below each root move of a parallel search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RootEngine {
+    /// 1v1 alpha-beta: the opponent replies at the first ply
+    AlphaBeta,
+    /// Best-reply search: any active opponent replies at the first ply
+    BestReply,
+    /// MaxN: the first ply continues our cycle (current player `our_idx`)
+    MaxN,
+}
+
 /// Adaptive time estimation tracking empirical iteration times
 /// Uses exponential moving average to blend observed times with model predictions
 #[derive(Debug, Clone)]
@@ -245,6 +374,10 @@ struct AdaptiveTimeEstimator {
     /// Fallback configuration for exponential model
     base_time_ms: f64,
     branching_factor: f64,
+    /// Effective branching factor measured by this search (see effective_branching_factor)
+    observed_branching: Option<f64>,
+    /// Iteration times of earlier searches in the same bucket (see TimingHistory)
+    prior: Option<DepthTimings>,
 }
 
 impl AdaptiveTimeEstimator {
@@ -256,9 +389,24 @@ impl AdaptiveTimeEstimator {
             model_weight: model_weight.clamp(0.0, 1.0),
             base_time_ms,
             branching_factor,
+            observed_branching: None,
+            prior: None,
         }
     }
 
+    /// Seeds the estimator with the timings of earlier searches, which replace the model
+    /// wherever they cover a depth
+    fn with_prior(mut self, prior: Option<DepthTimings>) -> Self {
+        self.prior = prior;
+        self
+    }
+
+    /// Sets the measured effective branching factor, which replaces the model's ratio
+    /// between depths once known
+    fn record_branching(&mut self, effective_branching: Option<f64>) {
+        self.observed_branching = effective_branching;
+    }
+
     /// Records an observed iteration time at a specific depth
     fn record_observation(&mut self, depth: u8, elapsed_ms: f64) {
         let depth_idx = depth as usize;
@@ -298,9 +446,23 @@ impl AdaptiveTimeEstimator {
         // If we have observations for earlier depths, extrapolate using ratio
         if let Some(last_observed_depth) = self.find_last_observed_depth(depth) {
             let observed_time = self.depth_timings[last_observed_depth];
+            let depth_gap = depth - last_observed_depth as u8;
+
+            // Measured node growth per iteration beats the configured model: time per node is
+            // roughly constant, and node counts are exact where millisecond timings are not
+            if let Some(ebf) = self.observed_branching {
+                return (observed_time * ebf.powi(depth_gap as i32)).ceil() as u64;
+            }
+
+            // Before that, the growth earlier searches saw between the same two depths
+            if let Some(prior) = &self.prior {
+                let earlier = prior.mean_ms(last_observed_depth as u8).filter(|&ms| ms > 0.0);
+                if let (Some(earlier), Some(target)) = (earlier, prior.mean_ms(depth)) {
+                    return (observed_time * target / earlier).ceil() as u64;
+                }
+            }
 
             // Calculate expected ratio between depths using model
-            let depth_gap = depth - last_observed_depth as u8;
             let exponent_gap = (depth_gap as f64) * (num_snakes as f64);
             let ratio = self.branching_factor.powf(exponent_gap);
 
@@ -312,7 +474,11 @@ impl AdaptiveTimeEstimator {
             return blended.ceil() as u64;
         }
 
-        // No observations yet - fall back to pure model
+        // No observations yet - earlier searches' timing for this depth, else pure model
+        if let Some(prior_time) = self.prior.as_ref().and_then(|prior| prior.mean_ms(depth)) {
+            let blended = self.model_weight * model_estimate + (1.0 - self.model_weight) * prior_time;
+            return blended.ceil() as u64;
+        }
         model_estimate.ceil() as u64
     }
 
@@ -335,8 +501,87 @@ pub struct SharedSearchState {
     pub best_move_and_score: Arc<AtomicU64>,
     /// Flag indicating search completion
     pub search_complete: Arc<AtomicBool>,
+    /// Wakes the request waiting for the search once it completes (see mark_complete)
+    completion: Arc<Notify>,
     /// Current search depth being explored
     pub current_depth: Arc<AtomicU8>,
+    /// Per-direction bonus added to root move scores (e.g. opening plan guidance)
+    /// Set before the search is spawned, read-only afterwards
+    pub root_bias: Arc<[AtomicI32; 4]>,
+    /// Bit per move index of root moves pruned as provably losing (see Bot::prune_root_moves)
+    /// Set before the first iteration, read-only afterwards
+    pub root_excluded: Arc<AtomicU8>,
+    /// Nodes visited by maxn_search/alpha_beta_minimax across all search threads
+    pub nodes_searched: Arc<AtomicU64>,
+    /// Wall time (µs) of search tasks, summed over the threads that ran them
+    pub task_wall_us: Arc<AtomicU64>,
+    /// CPU time (µs) those same tasks actually consumed (per-thread CPU clock)
+    pub task_cpu_us: Arc<AtomicU64>,
+    /// Set when the time budget expires; the search tree unwinds as soon as it notices
+    pub stop: Arc<AtomicBool>,
+    /// Largest best-score change between consecutive completed iterations (search stability)
+    pub score_swing: Arc<AtomicU32>,
+    /// Panic message if the search thread panicked (its results must not be trusted)
+    pub panic_message: Arc<parking_lot::Mutex<Option<String>>>,
+    /// Transposition table (probes, hits) of the finished search, for telemetry
+    pub tt_probes: Arc<AtomicU64>,
+    pub tt_hits: Arc<AtomicU64>,
+    /// Transposition lookups that found another node in the slot (hash collisions)
+    pub tt_collisions: Arc<AtomicU64>,
+    /// Principal variation of the finished search (empty until it completes)
+    pub principal_variation: Arc<parking_lot::Mutex<Vec<Direction>>>,
+    /// (depth, move, score) of every root move whose subtree finished, in completion order
+    pub root_scores: Arc<parking_lot::Mutex<Vec<(u8, Direction, i32)>>>,
+    /// Explain mode: (depth, move, leaf breakdown) of root moves' winning lines
+    pub leaf_breakdowns: Arc<parking_lot::Mutex<Vec<(u8, Direction, DetailedScore)>>>,
+    /// Completed iterative-deepening iterations, shallowest first
+    pub iterations: Arc<parking_lot::Mutex<Vec<IterationStats>>>,
+    /// Best move of each completed iteration, shallowest first
+    pub checkpoints: Arc<parking_lot::Mutex<Vec<IterationCheckpoint>>>,
+    /// Iteration times of earlier searches, seeding the time estimator (set before the search)
+    pub timing_prior: Arc<parking_lot::Mutex<Option<DepthTimings>>>,
+    /// (depth, ms) as fed to the time estimator, for the timing history
+    pub depth_timings: Arc<parking_lot::Mutex<Vec<(u8, f64)>>>,
+}
+
+/// Cost of one completed iterative-deepening iteration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct IterationStats {
+    pub depth: u8,
+    pub time_ms: u64,
+    /// Nodes visited by this iteration alone
+    pub nodes: u64,
+}
+
+/// Best move and score as they stood when an iterative-deepening iteration completed
+/// The request answers with the newest one: root moves of the iteration in progress publish
+/// their scores as they finish, so its current favorite may not have met all its rivals yet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct IterationCheckpoint {
+    pub depth: u8,
+    pub move_idx: u8,
+    pub score: i32,
+    /// Milliseconds since the search started
+    pub elapsed_ms: u64,
+}
+
+impl IterationStats {
+    /// Nodes of this iteration over those of `previous` (None if `previous` visited none)
+    pub fn branching_over(&self, previous: &IterationStats) -> Option<f64> {
+        (previous.nodes > 0).then(|| self.nodes as f64 / previous.nodes as f64)
+    }
+}
+
+/// Effective branching factor after the last completed iteration: node growth per iteration,
+/// as the geometric mean over the last two iterations (alpha-beta alternates cheap and
+/// expensive depths, so a single ratio swings), and at least 1. None before two iterations.
+pub fn effective_branching_factor(iterations: &[IterationStats]) -> Option<f64> {
+    let growth = match iterations {
+        [.., before, _, last] => last.branching_over(before)?.sqrt(),
+        [previous, last] => last.branching_over(previous)?,
+        _ => return None,
+    };
+    Some(growth.max(1.0))
 }
 
 impl SharedSearchState {
@@ -347,10 +592,221 @@ impl SharedSearchState {
         SharedSearchState {
             best_move_and_score: Arc::new(AtomicU64::new(packed)),
             search_complete: Arc::new(AtomicBool::new(false)),
+            completion: Arc::new(Notify::new()),
             current_depth: Arc::new(AtomicU8::new(0)),
+            root_bias: Arc::new([AtomicI32::new(0), AtomicI32::new(0), AtomicI32::new(0), AtomicI32::new(0)]),
+            root_excluded: Arc::new(AtomicU8::new(0)),
+            nodes_searched: Arc::new(AtomicU64::new(0)),
+            task_wall_us: Arc::new(AtomicU64::new(0)),
+            task_cpu_us: Arc::new(AtomicU64::new(0)),
+            stop: Arc::new(AtomicBool::new(false)),
+            score_swing: Arc::new(AtomicU32::new(0)),
+            panic_message: Arc::new(parking_lot::Mutex::new(None)),
+            tt_probes: Arc::new(AtomicU64::new(0)),
+            tt_hits: Arc::new(AtomicU64::new(0)),
+            tt_collisions: Arc::new(AtomicU64::new(0)),
+            principal_variation: Arc::new(parking_lot::Mutex::new(Vec::new())),
+            root_scores: Arc::new(parking_lot::Mutex::new(Vec::new())),
+            leaf_breakdowns: Arc::new(parking_lot::Mutex::new(Vec::new())),
+            iterations: Arc::new(parking_lot::Mutex::new(Vec::new())),
+            checkpoints: Arc::new(parking_lot::Mutex::new(Vec::new())),
+            timing_prior: Arc::new(parking_lot::Mutex::new(None)),
+            depth_timings: Arc::new(parking_lot::Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Adds the probe counters of a transposition table used by this search
+    pub fn record_tt_stats(&self, tt: &TranspositionTable) {
+        let (probes, hits) = tt.hit_counts();
+        self.tt_probes.fetch_add(probes, Ordering::Relaxed);
+        self.tt_hits.fetch_add(hits, Ordering::Relaxed);
+        self.tt_collisions.fetch_add(tt.collisions(), Ordering::Relaxed);
+    }
+
+    /// Transposition table (probes, hits) recorded so far
+    pub fn tt_stats(&self) -> (u64, u64) {
+        (self.tt_probes.load(Ordering::Relaxed), self.tt_hits.load(Ordering::Relaxed))
+    }
+
+    /// Transposition hash collisions recorded so far
+    pub fn tt_collisions(&self) -> u64 {
+        self.tt_collisions.load(Ordering::Relaxed)
+    }
+
+    /// Principal variation recorded by the finished search (empty if none)
+    pub fn principal_variation(&self) -> Vec<Direction> {
+        self.principal_variation.lock().clone()
+    }
+
+    /// Records the final score of a root move searched to `depth` (root bias included)
+    pub fn record_root_score(&self, depth: u8, mv: Direction, score: i32) {
+        self.root_scores.lock().push((depth, mv, score));
+    }
+
+    /// Root move scores recorded so far, as (depth, move, score)
+    pub fn root_scores(&self) -> Vec<(u8, Direction, i32)> {
+        self.root_scores.lock().clone()
+    }
+
+    /// Records the leaf breakdown of the winning line under a root move searched to `depth`
+    pub fn record_leaf_breakdown(&self, depth: u8, mv: Direction, leaf: &DetailedScore) {
+        self.leaf_breakdowns.lock().push((depth, mv, leaf.clone()));
+    }
+
+    /// Leaf breakdown of the winning line under `mv` at `depth` (the last one recorded)
+    pub fn leaf_breakdown(&self, depth: u8, mv: Direction) -> Option<DetailedScore> {
+        self.leaf_breakdowns.lock().iter().rev().find(|&&(d, m, _)| d == depth && m == mv).map(|(_, _, leaf)| leaf.clone())
+    }
+
+    /// Records a completed iteration
+    pub fn record_iteration(&self, stats: IterationStats) {
+        self.iterations.lock().push(stats);
+    }
+
+    /// Completed iterations recorded so far
+    pub fn iterations(&self) -> Vec<IterationStats> {
+        self.iterations.lock().clone()
+    }
+
+    /// Checkpoints the best move and score once an iteration to `depth` has completed
+    pub fn record_checkpoint(&self, depth: u8, elapsed_ms: u64) {
+        let (move_idx, score) = self.get_best();
+        self.checkpoints.lock().push(IterationCheckpoint { depth, move_idx, score, elapsed_ms });
+    }
+
+    /// Checkpoint of the deepest completed iteration, None before the first completes
+    pub fn latest_checkpoint(&self) -> Option<IterationCheckpoint> {
+        self.checkpoints.lock().last().copied()
+    }
+
+    /// (move, score, depth) to answer with: the newest checkpoint, or the best move published
+    /// so far (with the depth in progress) when no iteration has completed
+    pub fn answer(&self) -> (u8, i32, u8) {
+        match self.latest_checkpoint() {
+            Some(checkpoint) => (checkpoint.move_idx, checkpoint.score, checkpoint.depth),
+            None => {
+                let (move_idx, score) = self.get_best();
+                (move_idx, score, self.current_depth.load(Ordering::Acquire))
+            }
+        }
+    }
+
+    /// Seeds the time estimator of the next search with earlier iteration times
+    pub fn set_timing_prior(&self, prior: Option<DepthTimings>) {
+        *self.timing_prior.lock() = prior;
+    }
+
+    /// Records an iteration time as observed by the time estimator
+    pub fn record_depth_timing(&self, depth: u8, observed_ms: f64) {
+        self.depth_timings.lock().push((depth, observed_ms));
+    }
+
+    /// (depth, ms) observations of the time estimator so far
+    pub fn depth_timings(&self) -> Vec<(u8, f64)> {
+        self.depth_timings.lock().clone()
+    }
+
+    /// Marks the search as crashed and complete so the request returns immediately
+    pub fn record_panic(&self, message: String) {
+        *self.panic_message.lock() = Some(message);
+        self.mark_complete();
+    }
+
+    /// Marks the search complete and wakes the request waiting for it
+    pub fn mark_complete(&self) {
+        self.search_complete.store(true, Ordering::Release);
+        // notify_one keeps a permit when nobody waits yet, so the wakeup is never lost
+        self.completion.notify_one();
+    }
+
+    /// Resolves as soon as the search is complete
+    pub async fn completed(&self) {
+        while !self.search_complete.load(Ordering::Acquire) {
+            self.completion.notified().await;
+        }
+    }
+
+    /// Panic message of a crashed search, None if it ran normally
+    pub fn panic_message(&self) -> Option<String> {
+        self.panic_message.lock().clone()
+    }
+
+    /// Records the score change between two consecutive completed iterations
+    pub fn record_score_swing(&self, previous: i32, current: i32) {
+        let swing = (current as i64 - previous as i64).unsigned_abs().min(u32::MAX as u64) as u32;
+        self.score_swing.fetch_max(swing, Ordering::Relaxed);
+    }
+
+    /// Largest score change seen between consecutive iterations of this search
+    pub fn score_swing(&self) -> u32 {
+        self.score_swing.load(Ordering::Relaxed)
+    }
+
+    /// Asks a running search to abort (results of the unfinished iteration are discarded)
+    pub fn request_stop(&self) {
+        self.stop.store(true, Ordering::Release);
+    }
+
+    /// True once a stop has been requested
+    #[inline]
+    pub fn is_stopped(&self) -> bool {
+        self.stop.load(Ordering::Acquire)
+    }
+
+    /// Adds the wall and CPU time of one finished search task
+    pub fn record_task_time(&self, wall: Duration, cpu: Duration) {
+        self.task_wall_us.fetch_add(wall.as_micros() as u64, Ordering::Relaxed);
+        self.task_cpu_us.fetch_add(cpu.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    /// Total (wall µs, CPU µs) of all search tasks recorded so far
+    pub fn task_times(&self) -> (u64, u64) {
+        (
+            self.task_wall_us.load(Ordering::Relaxed),
+            self.task_cpu_us.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Counts one search node and polls the stop flag every `check_interval` nodes
+    /// Returns true if the search should unwind immediately
+    #[inline]
+    pub fn count_node_and_check_stop(&self, check_interval: u64) -> bool {
+        let nodes = self.nodes_searched.fetch_add(1, Ordering::Relaxed) + 1;
+        nodes.is_multiple_of(check_interval.max(1)) && self.is_stopped()
+    }
+
+    /// Total nodes searched so far
+    pub fn nodes(&self) -> u64 {
+        self.nodes_searched.load(Ordering::Relaxed)
+    }
+
+    /// Sets the root score bonus for a move index
+    pub fn set_root_bias(&self, move_idx: u8, bonus: i32) {
+        if let Some(bias) = self.root_bias.get(move_idx as usize) {
+            bias.store(bonus, Ordering::Release);
+        }
+    }
+
+    /// Root score bonus for a move index (0 if none was set)
+    pub fn root_bias_for(&self, move_idx: u8) -> i32 {
+        self.root_bias
+            .get(move_idx as usize)
+            .map(|bias| bias.load(Ordering::Acquire))
+            .unwrap_or(0)
+    }
+
+    /// Excludes a root move index from every iteration of the search
+    pub fn exclude_root_move(&self, move_idx: u8) {
+        if move_idx < 8 {
+            self.root_excluded.fetch_or(1 << move_idx, Ordering::Release);
         }
     }
 
+    /// True if the root move index was pruned before the search
+    pub fn is_root_excluded(&self, move_idx: u8) -> bool {
+        move_idx < 8 && self.root_excluded.load(Ordering::Acquire) & (1 << move_idx) != 0
+    }
+
     /// Packs move (u8) and score (i32) into a u64
     /// Format: [score: i32 as u32 (bits 32-63)][unused: u24 (bits 8-31)][move: u8 (bits 0-7)]
     #[inline]
@@ -412,13 +868,158 @@ impl SharedSearchState {
 
 }
 
+/// Extracts a readable message from a caught panic payload
+fn panic_payload_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// Measures wall and per-thread CPU time of one unit of search work
+/// CPU time excludes time the thread spent descheduled, so wall ≫ CPU means a noisy host
+struct TaskClock {
+    wall: Instant,
+    cpu: Option<ThreadTime>,
+}
+
+impl TaskClock {
+    fn start() -> Self {
+        TaskClock {
+            wall: Instant::now(),
+            cpu: ThreadTime::try_now().ok(),
+        }
+    }
+
+    /// Records this task in the shared counters (no-op if the platform has no thread clock)
+    fn finish(self, shared: &SharedSearchState) {
+        if let Some(cpu) = self.cpu {
+            shared.record_task_time(self.wall.elapsed(), cpu.elapsed());
+        }
+    }
+}
+
+/// Optional search limits on top of the time budget
+/// Used for reproducible replays and benchmarks that must not depend on host speed
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SearchLimits {
+    /// Stop iterative deepening once this many nodes have been searched (None = unlimited)
+    /// Checked between iterations, so the last started depth always completes
+    pub max_nodes: Option<u64>,
+    /// Per-turn computation budget; None uses timing.effective_budget_ms()
+    pub budget_ms: Option<u64>,
+    /// Search exactly to this depth with no clock (deterministic replay mode)
+    /// Time checks are skipped and the sequential strategy is forced, so results
+    /// are identical across runs and machines
+    pub fixed_depth: Option<u8>,
+    /// When the budget's clock started (None = when the search starts), e.g. the request's arrival
+    pub started: Option<Instant>,
+}
+
+impl SearchLimits {
+    /// Limits from Snake.toml (timing.max_nodes = 0 means unlimited)
+    pub fn from_config(config: &Config) -> Self {
+        SearchLimits {
+            max_nodes: if config.timing.max_nodes > 0 {
+                Some(config.timing.max_nodes)
+            } else {
+                None
+            },
+            budget_ms: None,
+            fixed_depth: None,
+            started: None,
+        }
+    }
+
+    /// Overrides the per-turn budget (e.g. adapted to measured network latency)
+    pub fn with_budget_ms(mut self, budget_ms: Option<u64>) -> Self {
+        self.budget_ms = budget_ms;
+        self
+    }
+
+    /// Computation budget for this search
+    pub fn effective_budget_ms(&self, config: &Config) -> u64 {
+        self.budget_ms.unwrap_or_else(|| config.timing.effective_budget_ms())
+    }
+
+    /// Overrides the fixed search depth (None = time-bound iterative deepening)
+    pub fn with_fixed_depth(mut self, fixed_depth: Option<u8>) -> Self {
+        self.fixed_depth = fixed_depth;
+        self
+    }
+
+    /// Overrides the node budget (API parameter for tools)
+    pub fn with_max_nodes(mut self, max_nodes: Option<u64>) -> Self {
+        self.max_nodes = max_nodes;
+        self
+    }
+
+    /// Starts the budget's clock at `started` instead of when the search starts
+    pub fn started_at(mut self, started: Instant) -> Self {
+        self.started = Some(started);
+        self
+    }
+}
+
+/// What every node of one search shares: the searching snake, the tables and the configuration
+/// Parallel subtrees swap in their own tables (see Bot::subtree_tables); the per-thread
+/// SearchScratch is passed alongside, as it is borrowed mutably.
+#[derive(Clone, Copy)]
+struct SearchContext<'a> {
+    our_snake_id: &'a str,
+    /// Turn of the root position (IDAPOS scheduling)
+    turn: i32,
+    config: &'a Config,
+    tt: &'a Arc<TranspositionTable>,
+    killers: &'a KillerMoveTable,
+    history: &'a HistoryTable,
+    shared: &'a SharedSearchState,
+}
+
+/// Depth bookkeeping of a search node
+#[derive(Debug, Clone, Copy)]
+struct Ply {
+    /// Plies left to search
+    depth: u8,
+    /// Plies below the root
+    from_root: u8,
+    /// Quiescence extensions taken on this line
+    q_extensions: u8,
+}
+
+impl Ply {
+    /// Node `from_root` plies below the root with `depth` plies left and no extensions
+    fn new(depth: u8, from_root: u8) -> Self {
+        Ply { depth, from_root, q_extensions: 0 }
+    }
+
+    /// One ply deeper
+    fn next(self) -> Self {
+        Ply { depth: self.depth - 1, from_root: self.from_root + 1, ..self }
+    }
+
+    /// The same node searched to `depth`
+    fn with_depth(self, depth: u8) -> Self {
+        Ply { depth, ..self }
+    }
+}
+
 /// Killer Move Table for move ordering heuristic
 /// Tracks moves that caused alpha-beta cutoffs at each depth
 /// Used to improve move ordering and increase cutoff rate
+///
+/// Rows are atomic, so the tasks of a parallel search share one table and order their moves
+/// with each other's cutoffs.
 pub struct KillerMoveTable {
-    /// Two killer moves per depth (standard killer heuristic)
-    /// Array size is max_search_depth + 1 (config value: 20 + 1 = 21)
-    killers: Vec<Vec<Option<Direction>>>,
+    /// One row of killer slots per depth (max_search_depth + 1 rows), most recent first.
+    /// A row packs its slots into one atomic, a byte per slot (0 = empty, else direction + 1),
+    /// so a killer is recorded in a single compare-and-swap; only `per_depth` slots are used
+    killers: Vec<AtomicU32>,
+    /// Killer moves kept per depth (killer_moves_per_depth, at most one per direction)
+    per_depth: usize,
 }
 
 impl KillerMoveTable {
@@ -426,49 +1027,61 @@ impl KillerMoveTable {
     /// Size is determined by config.timing.max_search_depth
     pub fn new(config: &Config) -> Self {
         let max_depth = (config.timing.max_search_depth + 1) as usize;
-        let killer_count = config.move_ordering.killer_moves_per_depth;
 
         KillerMoveTable {
-            killers: vec![vec![None; killer_count]; max_depth],
+            killers: (0..max_depth).map(|_| AtomicU32::new(0)).collect(),
+            per_depth: config.move_ordering.killer_moves_per_depth.min(4),
         }
     }
 
     /// Records a killer move at a specific depth
     /// Shifts existing killer moves down (most recent first)
-    pub fn record_killer(&mut self, depth: u8, mv: Direction, config: &Config) {
-        if !config.move_ordering.enable_killer_heuristic {
-            return;
-        }
-
-        let depth_idx = depth as usize;
-        if depth_idx >= self.killers.len() {
+    pub fn record_killer(&self, depth: u8, mv: Direction, config: &Config) {
+        if !config.move_ordering.enable_killer_heuristic || self.per_depth == 0 {
             return;
         }
 
-        // Check if this move is already a killer at this depth
-        if self.killers[depth_idx].iter().any(|k| k == &Some(mv)) {
-            return;
-        }
+        let row = match self.killers.get(depth as usize) {
+            Some(row) => row,
+            None => return,
+        };
+        let slot = direction_to_index(mv) as u32 + 1;
+        let used_bits = (u64::from(u32::MAX) >> (32 - 8 * self.per_depth)) as u32;
 
         // Shift killers: [0] -> [1], [1] -> [2], etc.
-        // Insert new killer at position 0
-        self.killers[depth_idx].rotate_right(1);
-        self.killers[depth_idx][0] = Some(mv);
+        // Insert new killer at position 0, unless it is already a killer at this depth
+        let _ = row.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |packed| {
+            if (0..self.per_depth).any(|i| (packed >> (8 * i)) & 0xFF == slot) {
+                return None;
+            }
+            Some(((packed << 8) | slot) & used_bits)
+        });
     }
 
     /// Checks if a move is a killer move at a specific depth
     pub fn is_killer(&self, depth: u8, mv: Direction) -> bool {
-        let depth_idx = depth as usize;
-        if depth_idx >= self.killers.len() {
-            return false;
+        self.killer_flags(depth)[direction_to_index(mv)]
+    }
+
+    /// Killer moves at a specific depth, indexed by direction (Up, Down, Left, Right)
+    pub fn killer_flags(&self, depth: u8) -> [bool; 4] {
+        let mut flags = [false; 4];
+        if let Some(row) = self.killers.get(depth as usize) {
+            let packed = row.load(Ordering::Relaxed);
+            for i in 0..self.per_depth {
+                let slot = (packed >> (8 * i)) & 0xFF;
+                if slot > 0 {
+                    flags[slot as usize - 1] = true;
+                }
+            }
         }
-        self.killers[depth_idx].contains(&Some(mv))
+        flags
     }
 
     /// Clears all killer moves (called at start of new search iteration)
     pub fn clear(&mut self) {
-        for depth_killers in &mut self.killers {
-            depth_killers.fill(None);
+        for row in &mut self.killers {
+            *row.get_mut() = 0;
         }
     }
 
@@ -479,14 +1092,13 @@ impl KillerMoveTable {
         // Shift each depth's killers to the next depth
         // depth[0] <- depth[1], depth[1] <- depth[2], etc.
         // This effectively "ages" killers as we go deeper
-        if self.killers.len() > 1 {
-            for i in 0..self.killers.len() - 1 {
-                self.killers[i] = self.killers[i + 1].clone();
-            }
-            // Clear the deepest level (will be repopulated)
-            if let Some(last) = self.killers.last_mut() {
-                last.fill(None);
-            }
+        for depth in 1..self.killers.len() {
+            let deeper = *self.killers[depth].get_mut();
+            *self.killers[depth - 1].get_mut() = deeper;
+        }
+        // Clear the deepest level (will be repopulated)
+        if let Some(last) = self.killers.last_mut() {
+            *last.get_mut() = 0;
         }
     }
 }
@@ -494,40 +1106,75 @@ impl KillerMoveTable {
 /// History Heuristic Table for move ordering
 /// Tracks globally successful moves (not depth-specific like killers)
 /// Complements killer heuristic by learning which moves work well across all positions
+///
+/// Butterfly indexing: [mover side][from square][to direction], so our snake's
+/// successful moves never bias the ordering of opponent moves from the same square.
+/// Scores are halved periodically instead of cleared, and the table persists across
+/// turns through the per-game session. Cells are atomic, so the tasks of a parallel search
+/// update one shared table.
 pub struct HistoryTable {
-    /// Scores indexed by [position][direction]
-    /// Position is flattened: index = y * width + x
+    /// Scores indexed by [side][position][direction]
+    /// Flattened: index = side * (width * height) + y * width + x
+    /// Side 0 = our snake, side 1 = opponents
     /// Higher scores = more likely to cause cutoffs
-    scores: Vec<[i32; 4]>,  // 4 directions: Up, Down, Left, Right
+    scores: Vec<[AtomicI32; 4]>,  // 4 directions: Up, Down, Left, Right
     width: usize,
     height: usize,
 }
 
+impl Clone for HistoryTable {
+    fn clone(&self) -> Self {
+        HistoryTable {
+            scores: self.scores.iter().map(|cells| cells.each_ref().map(|cell| AtomicI32::new(cell.load(Ordering::Relaxed)))).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
 impl HistoryTable {
     /// Creates a new history table for the given board dimensions
     pub fn new(width: u32, height: u32) -> Self {
         let width = width as usize;
         let height = height as usize;
-        let size = width * height;
+        let size = width * height * 2;  // One butterfly board per side
 
         HistoryTable {
-            scores: vec![[0; 4]; size],
+            scores: (0..size).map(|_| Default::default()).collect(),
             width,
             height,
         }
     }
 
-    /// Updates history score for a move
-    /// Exponential bonus for cutoffs (2^depth), smaller penalty for non-cutoffs
-    pub fn update(&mut self, coord: &Coord, dir: Direction, depth: u8, caused_cutoff: bool) {
+    /// Returns true if the table was built for a board of these dimensions
+    pub fn matches_dimensions(&self, width: u32, height: u32) -> bool {
+        self.width == width as usize && self.height == height as usize
+    }
+
+    /// Maps (side, from square) to a flat index, or None if out of bounds
+    fn index(&self, is_our_move: bool, coord: &Coord) -> Option<usize> {
+        if coord.x < 0 || coord.y < 0 {
+            return None;
+        }
+
         let x = coord.x as usize;
         let y = coord.y as usize;
 
         if x >= self.width || y >= self.height {
-            return;  // Out of bounds
+            return None;  // Out of bounds
         }
 
-        let pos_idx = y * self.width + x;
+        let side_offset = if is_our_move { 0 } else { self.width * self.height };
+        Some(side_offset + y * self.width + x)
+    }
+
+    /// Updates history score for a move
+    /// Exponential bonus for cutoffs (2^depth), smaller penalty for non-cutoffs
+    pub fn update(&self, is_our_move: bool, coord: &Coord, dir: Direction, depth: u8, caused_cutoff: bool) {
+        let pos_idx = match self.index(is_our_move, coord) {
+            Some(idx) => idx,
+            None => return,
+        };
         let dir_idx = direction_to_index(dir);
 
         let bonus = if caused_cutoff {
@@ -539,49 +1186,44 @@ impl HistoryTable {
         };
 
         // Saturating add to prevent overflow
-        self.scores[pos_idx][dir_idx] = self.scores[pos_idx][dir_idx].saturating_add(bonus);
+        let _ = self.scores[pos_idx][dir_idx].fetch_update(Ordering::Relaxed, Ordering::Relaxed, |score| Some(score.saturating_add(bonus)));
     }
 
     /// Gets the history score for a move
     /// Higher scores indicate moves that historically cause more cutoffs
-    pub fn get_score(&self, coord: &Coord, dir: Direction) -> i32 {
-        let x = coord.x as usize;
-        let y = coord.y as usize;
-
-        if x >= self.width || y >= self.height {
-            return 0;  // Out of bounds
+    pub fn get_score(&self, is_our_move: bool, coord: &Coord, dir: Direction) -> i32 {
+        match self.index(is_our_move, coord) {
+            Some(pos_idx) => self.scores[pos_idx][direction_to_index(dir)].load(Ordering::Relaxed),
+            None => 0,
         }
-
-        let pos_idx = y * self.width + x;
-        let dir_idx = direction_to_index(dir);
-
-        self.scores[pos_idx][dir_idx]
     }
 
     /// Clears all history scores (called at start of new game or search tree)
     /// Note: Unlike killers, history often persists across iterations
     /// but we clear it per root position for freshness
     pub fn clear(&mut self) {
-        for scores in &mut self.scores {
-            scores.fill(0);
+        for score in self.scores.iter_mut().flat_map(|scores| scores.iter_mut()) {
+            *score.get_mut() = 0;
         }
     }
 
-    /// V11: Decay history scores instead of clearing
-    /// Multiplies all scores by decay_factor (e.g., 0.9 = keep 90% of previous knowledge)
-    /// This allows successful move patterns to persist while gradually forgetting old info
-    pub fn decay_history(&mut self, decay_factor: f32) {
-        for scores in &mut self.scores {
-            for score in scores.iter_mut() {
-                *score = (*score as f32 * decay_factor) as i32;
-            }
+    /// Halves all history scores instead of clearing them
+    /// Successful move patterns persist while old information fades geometrically
+    pub fn age(&mut self) {
+        for score in self.scores.iter_mut().flat_map(|scores| scores.iter_mut()) {
+            *score.get_mut() /= 2;
         }
     }
-}
 
-/// Calculates Manhattan distance between two coordinates
-fn manhattan_distance(a: Coord, b: Coord) -> i32 {
-    (a.x - b.x).abs() + (a.y - b.y).abs()
+    /// Largest absolute score in the table, used to trigger aging before saturation
+    pub fn max_magnitude(&self) -> i32 {
+        self.scores
+            .iter()
+            .flat_map(|scores| scores.iter())
+            .map(|score| score.load(Ordering::Relaxed).saturating_abs())
+            .max()
+            .unwrap_or(0)
+    }
 }
 
 /// Helper function to convert Direction to array index
@@ -594,6 +1236,51 @@ fn direction_to_index(dir: Direction) -> usize {
     }
 }
 
+/// Whether a depth-0 node `depth_from_root` plies from the root, on a line that already took
+/// `q_extensions` quiescence extensions, gets another one (unstable, within
+/// timing.max_quiescence_ply and timing.max_quiescence_extensions)
+fn extends_quiescence(board: &Board, our_snake_id: &str, depth_from_root: u8, q_extensions: u8, config: &Config) -> bool {
+    let within_caps = depth_from_root < config.timing.max_quiescence_ply && q_extensions < config.timing.max_quiescence_extensions;
+    if !within_caps {
+        // Unstable leaves cut off by a cap are only worth the instability check when profiling
+        if simple_profiler::is_profiling_enabled() && is_position_unstable(board, our_snake_id, config) {
+            simple_profiler::record_quiescence_extension(false);
+        }
+        return false;
+    }
+    let extends = is_position_unstable(board, our_snake_id, config);
+    if extends {
+        simple_profiler::record_quiescence_extension(true);
+    }
+    extends
+}
+
+/// Stack reserved per search frame (alpha_beta_minimax / maxn_search call), with room for
+/// unoptimized builds
+const SEARCH_FRAME_BYTES: usize = 8 * 1024;
+
+/// Stack a thread needs to run a search to the deepest line `config` allows
+///
+/// The recursion is bounded: depth_from_root never exceeds max(max_search_depth,
+/// max_quiescence_ply), and one ply stacks at most 2 frames per snake plus the node itself
+/// (request_validation.max_snakes caps the snakes). Every thread a search runs on is spawned
+/// with this stack rather than the 2 MiB default.
+pub fn search_stack_bytes(config: &Config) -> usize {
+    let plies = config.timing.max_search_depth.max(config.timing.max_quiescence_ply) as usize + 1;
+    let frames_per_ply = 2 * config.request_validation.max_snakes + 1;
+    plies * frames_per_ply * SEARCH_FRAME_BYTES + (1 << 20)
+}
+
+/// Builds the global rayon pool, whose workers run the root moves of searches outside a
+/// SearchSlot (replays, tools), with search_stack_bytes stacks
+///
+/// Only the first call before any rayon use takes effect; later calls keep the pool as is.
+pub fn init_global_search_pool(config: &Config) {
+    if let Err(e) = rayon::ThreadPoolBuilder::new().stack_size(search_stack_bytes(config)).build_global() {
+        debug!("Global search pool already running: {}", e);
+    }
+}
+
 /// Detects if a position is "unstable" and needs quiescence search extension
 /// Unstable positions in Battlesnake:
 /// 1. Head is adjacent to food (eating will change evaluation dramatically)
@@ -601,7 +1288,7 @@ fn direction_to_index(dir: Direction) -> usize {
 /// 3. Health is critically low (starvation imminent)
 /// 4. **NEW: Trap detection - reachable space is critically low (entrapment risk)**
 fn is_position_unstable(board: &Board, our_snake_id: &str, config: &Config) -> bool {
-    let our_snake = match board.snakes.iter().find(|s| &s.id == our_snake_id) {
+    let our_snake = match board.our_snake(our_snake_id) {
         Some(s) if s.health > 0 => s,
         _ => return false,
     };
@@ -610,7 +1297,7 @@ fn is_position_unstable(board: &Board, our_snake_id: &str, config: &Config) -> b
 
     // Check 1: Is head adjacent to food?
     for &food in &board.food {
-        if manhattan_distance(our_head, food) == 1 {
+        if our_head.manhattan(food) == 1 {
             return true; // About to eat food
         }
     }
@@ -622,7 +1309,7 @@ fn is_position_unstable(board: &Board, our_snake_id: &str, config: &Config) -> b
         }
 
         let opp_head = opponent.body[0];
-        let head_dist = manhattan_distance(our_head, opp_head);
+        let head_dist = our_head.manhattan(opp_head);
 
         // If heads are 1-2 moves apart, this is tactically critical
         if head_dist <= 2 {
@@ -633,7 +1320,7 @@ fn is_position_unstable(board: &Board, our_snake_id: &str, config: &Config) -> b
     // Check 3: Critical health and food is nearby?
     if our_snake.health <= 15 {
         for &food in &board.food {
-            if manhattan_distance(our_head, food) <= 3 {
+            if our_head.manhattan(food) <= 3 {
                 return true; // Starvation risk with nearby food
             }
         }
@@ -642,12 +1329,12 @@ fn is_position_unstable(board: &Board, our_snake_id: &str, config: &Config) -> b
     // Check 4: Trap detection - critically low reachable space
     // If we have very limited space, this is tactically critical (entrapment risk)
     // Use a quick flood fill to check available space
-    let our_idx = board.snakes.iter().position(|s| &s.id == our_snake_id).unwrap_or(0);
+    let our_idx = board.snake_index(our_snake_id).unwrap_or(0);
     let required_space = our_snake.length as usize + config.scores.space_safety_margin;
     let critical_space_threshold = required_space + (required_space / 2);
 
     // Use early exit optimization - stop counting once we know we have enough space
-    let reachable = Bot::flood_fill_bfs(board, our_head, our_idx, Some(critical_space_threshold + 1));
+    let reachable = Bot::flood_fill_bfs(board, our_head, our_idx, Some(critical_space_threshold + 1), config.hazard_walls.as_deref());
 
     // If we're within 50% of minimum required space, consider it unstable (trap forming)
     if reachable <= critical_space_threshold {
@@ -658,147 +1345,754 @@ fn is_position_unstable(board: &Board, our_snake_id: &str, config: &Config) -> b
 }
 
 /// Orders moves for better alpha-beta pruning
-/// Priority: PV move > killer moves > history scores > remaining moves
+/// Priority: PV move > killer moves > SEE / history scores > remaining moves
 /// This can improve alpha-beta efficiency by 50-80%
 fn order_moves(
-    moves: Vec<Direction>,
+    moves: &mut MoveList,
     pv_move: Option<Direction>,
     killers: &KillerMoveTable,
-    history: Option<(&HistoryTable, &Coord)>,  // (history_table, current_position)
+    history: Option<(&HistoryTable, &Coord, bool)>,  // (history_table, current_position, is_our_move)
+    see: Option<(&Board, usize)>,  // (board, moving snake index) for head-to-head exchange scores
     depth: u8,
     config: &Config,
-) -> Vec<Direction> {
-    let mut ordered = Vec::with_capacity(moves.len());
-
-    // Priority 1: PV (Principal Variation) move from previous iteration
-    if config.move_ordering.enable_pv_ordering {
-        if let Some(pv) = pv_move {
-            if moves.contains(&pv) {
-                ordered.push(pv);
-            }
+) {
+    // Sort keys per direction (Up, Down, Left, Right): (tier, SEE, lazy eval, history) with
+    // tier 0 = PV, 1 = killer, 2 = remaining. Keys live on the stack, computed once per move,
+    // and the move list is sorted in place (no allocation per node)
+    let mut keys = [(2u8, 0i32, 0i32, 0i32); 4];
+    let killer_flags = if config.move_ordering.enable_killer_heuristic { killers.killer_flags(depth) } else { [false; 4] };
+    let lazy = see.filter(|_| config.lazy_eval.enabled && depth >= config.lazy_eval.ordering_min_depth);
+    let see = see.filter(|_| config.move_ordering.enable_see_ordering);
+
+    for &mv in moves.iter() {
+        let key = &mut keys[direction_to_index(mv)];
+
+        // Priority 1: PV (Principal Variation) move from previous iteration
+        if config.move_ordering.enable_pv_ordering && pv_move == Some(mv) {
+            key.0 = 0;
+            continue;
         }
-    }
 
-    // Priority 2: Killer moves
-    if config.move_ordering.enable_killer_heuristic {
-        for &mv in &moves {
-            if !ordered.contains(&mv) && killers.is_killer(depth, mv) {
-                ordered.push(mv);
-            }
+        // Priority 2: Killer moves
+        if killer_flags[direction_to_index(mv)] {
+            key.0 = 1;
+            continue;
         }
+
+        // Priority 3: Static exchange value of the target square, then history score
+        // Winning head-to-heads are tried first, losing ones last
+        key.1 = see
+            .and_then(|(board, idx)| {
+                board.snakes.get(idx)
+                    .and_then(|snake| snake.body.first())
+                    .map(|head| Bot::static_exchange_eval(board, idx, mv.apply(head), config))
+            })
+            .unwrap_or(0);
+        // Interior nodes: the mover's lazy score after the move (material, health, danger)
+        key.2 = lazy
+            .map(|(board, idx)| {
+                let mut child = board.clone();
+                Bot::apply_move(&mut child, idx, mv, config);
+                evaluator::lazy_score(&child, idx, config, 0)
+            })
+            .unwrap_or(0);
+        key.3 = history.map(|(hist, pos, is_ours)| hist.get_score(is_ours, pos, mv)).unwrap_or(0);
     }
 
-    // Priority 3: History heuristic - sort remaining moves by history score
-    if let Some((hist, pos)) = history {
-        let mut remaining: Vec<_> = moves.iter()
-            .filter(|&&mv| !ordered.contains(&mv))
-            .map(|&mv| (mv, hist.get_score(pos, mv)))
-            .collect();
+    // Stable sort: tier ascending, then SEE, lazy eval and history descending
+    // Priority 4: moves without any score keep their generation order
+    moves.sort_by_key(|&mv| {
+        let (tier, see, lazy, history) = keys[direction_to_index(mv)];
+        (tier, Reverse(see), Reverse(lazy), Reverse(history))
+    });
+}
 
-        // Sort by history score (descending - higher scores first)
-        remaining.sort_by(|a, b| b.1.cmp(&a.1));
+/// Resolved configuration for the unprefixed routes and for each personality profile
+struct ProfileConfigs {
+    base: Arc<Config>,
+    profiles: HashMap<String, Arc<Config>>,
+}
 
-        for (mv, _score) in remaining {
-            ordered.push(mv);
-        }
-    } else {
-        // Priority 4: Remaining moves (if no history available)
-        for &mv in &moves {
-            if !ordered.contains(&mv) {
-                ordered.push(mv);
+impl ProfileConfigs {
+    /// Applies every profile's overrides to `base` (profiles that fail to resolve are skipped)
+    fn resolve(mut base: Config) -> Self {
+        evaluator::attach(&mut base);
+        let mut profiles = HashMap::new();
+        for name in base.profile_names() {
+            match base.for_profile(name) {
+                Ok(config) => {
+                    profiles.insert(name.to_string(), Arc::new(config));
+                }
+                Err(e) => warn!("Skipping profile: {}", e),
             }
         }
+        ProfileConfigs { base: Arc::new(base), profiles }
     }
-
-    ordered
 }
 
 /// Battlesnake Bot with OOP-style API
 /// Takes static configuration dependencies and exposes methods corresponding to API endpoints
 pub struct Bot {
-    config: Config,
+    /// Active configuration per profile; swapped as a whole when score weights are hot-reloaded
+    configs: parking_lot::RwLock<ProfileConfigs>,
+    /// Token required by the /admin endpoints (None disables them)
+    admin_token: Option<String>,
     debug_logger: Arc<tokio::sync::Mutex<Option<DebugLogger>>>,
+    sessions: SessionStore,
+    /// Search slots and per-search thread pools shared by concurrent games
+    capacity: Arc<GameCapacity>,
+    /// Iteration times of all games, seeding new sessions (time_estimation.history_file)
+    timings: Arc<parking_lot::Mutex<TimingHistory>>,
+    /// Viewers of /ws/games/<game_id> (spectator.enabled)
+    spectators: SpectatorHub,
 }
 
 impl Bot {
     /// Creates a new Bot instance with the given configuration
     ///
     /// # Arguments
-    /// * `config` - Static configuration that does not change during the bot's lifetime
+    /// * `config` - Startup configuration; only score weights can change later (/admin/config)
     pub fn new(config: Config) -> Self {
+        simple_profiler::set_enabled(config.profiling.enabled);
+        init_global_search_pool(&config);
+        let timings = Self::load_timing_history(&config);
         Bot {
-            config,
+            capacity: GameCapacity::new(&config),
+            sessions: SessionStore::new(Duration::from_millis(config.concurrency.session_idle_timeout_ms)),
+            spectators: SpectatorHub::new(config.spectator.channel_capacity),
+            configs: parking_lot::RwLock::new(ProfileConfigs::resolve(config)),
+            admin_token: None,
             debug_logger: Arc::new(tokio::sync::Mutex::new(None)),
+            timings: Arc::new(parking_lot::Mutex::new(timings)),
         }
     }
 
-    /// Ensures the debug logger is initialized (lazy initialization)
-    /// This is called on the first move to avoid blocking during startup
-    async fn ensure_debug_logger_initialized(&self) {
-        let mut logger_guard = self.debug_logger.lock().await;
-        if logger_guard.is_none() {
-            if self.config.debug.enabled {
-                *logger_guard = Some(
-                    DebugLogger::new(true, &self.config.debug.log_file_path).await
-                );
-            } else {
-                *logger_guard = Some(DebugLogger::disabled());
-            }
+    /// Timing history saved by an earlier run (empty if none is configured or readable)
+    fn load_timing_history(config: &Config) -> TimingHistory {
+        let path = Path::new(&config.time_estimation.history_file);
+        if !config.time_estimation.persist_timings || config.time_estimation.history_file.is_empty() || !path.exists() {
+            return TimingHistory::new();
         }
+        TimingHistory::load(path).unwrap_or_else(|e| {
+            warn!("Ignoring timing history: {}", e);
+            TimingHistory::new()
+        })
     }
 
-    /// Returns bot metadata and appearance
-    /// Corresponds to GET / endpoint
-    pub fn info(&self) -> Value {
-        info!("INFO");
-
-        json!({
-            "apiversion": "1",
-            "author": "ksiopiolosz-aterlo",
-            "color": "#00DEAD",
-            "head": "default",
-            "tail": "default",
-        })
+    /// Enables the /admin endpoints, guarded by `token` (empty or None keeps them disabled)
+    pub fn with_admin_token(mut self, token: Option<String>) -> Self {
+        self.admin_token = token.filter(|t| !t.is_empty());
+        self
     }
 
-    /// Called when a game starts
-    /// Corresponds to POST /start endpoint
-    pub fn start(&self, _game: &Game, _turn: &i32, _board: &Board, _you: &Battlesnake) {
-        info!("GAME START");
+    /// Search slots shared by all games (also taken by the /readyz self-test)
+    pub fn capacity(&self) -> &Arc<GameCapacity> {
+        &self.capacity
     }
 
-    /// Called when a game ends
-    /// Corresponds to POST /end endpoint
-    pub fn end(&self, _game: &Game, _turn: &i32, _board: &Board, _you: &Battlesnake) {
-        info!("GAME OVER");
+    /// Snapshot of the active configuration of the unprefixed routes
+    /// A move keeps the snapshot it started with, even if weights are swapped meanwhile
+    pub fn config(&self) -> Arc<Config> {
+        self.configs.read().base.clone()
+    }
+
+    /// Snapshot of the configuration for a personality profile (None: unprefixed routes)
+    ///
+    /// # Returns
+    /// * `Err` if no profile of that name is configured
+    pub fn config_for(&self, profile: Option<&str>) -> Result<Arc<Config>, String> {
+        let configs = self.configs.read();
+        match profile {
+            None => Ok(configs.base.clone()),
+            Some(name) => configs
+                .profiles
+                .get(name)
+                .cloned()
+                .ok_or_else(|| format!("Unknown profile '{}'", name)),
+        }
+    }
+
+    /// Session key: one session per snake, so several of our profiles can share a game
+    fn session_key(game: &Game, you: &Battlesnake) -> String {
+        format!("{}/{}", game.id, you.id)
+    }
+
+    /// New viewer of a game's per-turn results (None while spectator.enabled is off)
+    pub fn spectate(&self, game_id: &str) -> Option<Subscription> {
+        self.config().spectator.enabled.then(|| self.spectators.subscribe(game_id))
+    }
+
+    /// Whether the /admin endpoints are enabled
+    pub fn admin_enabled(&self) -> bool {
+        self.admin_token.is_some()
+    }
+
+    /// Checks a presented admin token (constant time in the token length)
+    pub fn admin_token_matches(&self, presented: &str) -> bool {
+        match &self.admin_token {
+            Some(token) => {
+                token.len() == presented.len()
+                    && token.bytes().zip(presented.bytes()).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+            }
+            None => false,
+        }
+    }
+
+    /// Active score weights as JSON
+    /// Corresponds to GET /admin/config
+    pub fn scores_json(&self) -> Value {
+        serde_json::to_value(&self.config().scores).unwrap_or(Value::Null)
+    }
+
+    /// Atomically swaps in score weights patched with `overrides` (partial ScoresConfig JSON)
+    /// Profiles are re-derived from the new weights, so their overrides stay on top.
+    /// Moves already in progress finish with the old weights. Returns the new active weights.
+    /// Patches that fail Config::validate are rejected and leave the active config untouched.
+    /// Corresponds to POST /admin/config
+    pub fn update_scores(&self, overrides: &Value) -> Result<Value, String> {
+        let mut active = self.configs.write();
+        let scores = active.base.scores.with_overrides(overrides)?;
+
+        let mut updated = Config::clone(&active.base);
+        updated.scores = scores;
+        let errors = updated.validation_errors();
+        if !errors.is_empty() {
+            let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+            return Err(errors.join("; "));
+        }
+        *active = ProfileConfigs::resolve(updated);
+
+        let changed: Vec<&str> = overrides
+            .as_object()
+            .map(|fields| fields.keys().map(String::as_str).collect())
+            .unwrap_or_default();
+        info!("ADMIN: score weights updated ({})", changed.join(", "));
+
+        serde_json::to_value(&active.base.scores).map_err(|e| format!("Failed to serialize scores: {}", e))
+    }
+
+    /// Ensures the debug logger is initialized (lazy initialization)
+    /// This is called on the first move to avoid blocking during startup
+    async fn ensure_debug_logger_initialized(&self) {
+        let config = self.config();
+        let mut logger_guard = self.debug_logger.lock().await;
+        if logger_guard.is_none() {
+            *logger_guard = Some(DebugLogger::from_config(&config.debug).await);
+        }
+    }
+
+    /// Waits for the debug log writes of the moves and game ends handled so far
+    pub async fn flush_debug_log(&self) {
+        let logger = self.debug_logger.lock().await.clone();
+        if let Some(logger) = logger {
+            logger.flush().await;
+        }
+    }
+
+    /// Returns bot metadata and appearance
+    /// Corresponds to GET / endpoint (GET /<profile> for a personality profile)
+    pub fn info(&self, profile: Option<&str>) -> Result<Value, String> {
+        let config = self.config_for(profile)?;
+        info!("INFO");
+
+        let appearance = &config.appearance;
+        Ok(json!({
+            "apiversion": "1",
+            "author": appearance.author,
+            "color": appearance.color,
+            "head": appearance.head,
+            "tail": appearance.tail,
+            "version": appearance.version(),
+        }))
+    }
+
+    /// Called when a game starts
+    /// Corresponds to POST /start endpoint (POST /<profile>/start for a personality profile)
+    pub fn start(&self, profile: Option<&str>, game: &Game, turn: &i32, board: &Board, you: &Battlesnake) -> Result<(), String> {
+        self.start_with_stakes(profile, game, turn, board, you, None)
+    }
+
+    /// start with the game's stakes from the /start query (None: stakes.default)
+    pub fn start_with_stakes(
+        &self,
+        profile: Option<&str>,
+        game: &Game,
+        _turn: &i32,
+        board: &Board,
+        you: &Battlesnake,
+        stakes: Option<Stakes>,
+    ) -> Result<(), String> {
+        let config = self.config_for(profile)?;
+        let mode = GameMode::from_game(game, &config);
+        let stakes = stakes.unwrap_or_else(|| Stakes::default_for(&config));
+        info!(
+            "GAME START{} (timeout {}ms, mode {}, ruleset {}, {} stakes)",
+            profile.map(|name| format!(" (profile {})", name)).unwrap_or_default(),
+            game.timeout,
+            mode.name(),
+            game.ruleset.describe(),
+            stakes.name()
+        );
+        let session = self.sessions.get_or_create(&Self::session_key(game, you), board);
+        let mut guard = session.lock();
+        guard.game_timeout_ms = game.timeout;
+        guard.mode = Some(mode);
+        guard.stakes = Some(stakes);
+        Self::init_opening_plan(&mut guard, board, you, &config);
+        Ok(())
+    }
+
+    /// Chooses the duel opening plan for a new session
+    fn init_opening_plan(session: &mut GameSession, board: &Board, you: &Battlesnake, config: &Config) {
+        session.opening = OpeningPlan::for_duel(board, you, config);
+        if let Some(plan) = &session.opening {
+            info!(
+                "Opening plan: {:?} spawn, {:?} food → waypoints {:?}",
+                plan.quadrant, plan.layout, plan.waypoints
+            );
+        }
+    }
+
+    /// Returns the move that follows the opening plan this turn, if it is still safe
+    /// Abandons the plan (normal search takes over) as soon as following it is unsafe
+    fn opening_plan_move(session: &mut GameSession, board: &Board, you: &Battlesnake, turn: i32, config: &Config) -> Option<Direction> {
+        let plan = session.opening.as_mut()?;
+        let target = plan.current_target(you.body[0], turn, config)?;
+
+        match Self::safe_step_toward(board, you, target, config) {
+            Some(dir) => Some(dir),
+            None => {
+                info!("Turn {}: Opening plan toward {:?} is unsafe, falling back to search", turn, target);
+                plan.abandon();
+                None
+            }
+        }
+    }
+
+    /// Picks the legal move that closes the distance to `target`, if that step is safe:
+    /// no losing head-to-head exchange and enough reachable space afterwards
+    fn safe_step_toward(board: &Board, you: &Battlesnake, target: Coord, config: &Config) -> Option<Direction> {
+        let our_idx = board.snake_index(&you.id)?;
+        let head = you.body[0];
+        let current_dist = head.manhattan(target);
+
+        let step = Self::generate_legal_moves(board, you, config)
+            .into_iter()
+            .filter(|dir| dir.apply(&head).manhattan(target) < current_dist)
+            .min_by_key(|dir| dir.apply(&head).manhattan(target))?;
+
+        let next = step.apply(&head);
+        if Self::static_exchange_eval(board, our_idx, next, config) < 0 {
+            return None;
+        }
+
+        let required_space = you.length as usize + config.scores.space_safety_margin;
+        let mut child_board = board.clone();
+        Self::apply_move(&mut child_board, our_idx, step, config);
+        if Self::flood_fill_bfs(&child_board, next, our_idx, Some(required_space), config.hazard_walls.as_deref()) < required_space {
+            return None;
+        }
+
+        Some(step)
+    }
+
+    /// Called when a game ends
+    /// Corresponds to POST /end endpoint (POST /<profile>/end for a personality profile)
+    pub fn end(&self, profile: Option<&str>, game: &Game, turn: &i32, board: &Board, you: &Battlesnake) -> Result<(), String> {
+        let config = self.config_for(profile)?;
+        info!("GAME OVER");
+        let session = self.sessions.remove(&Self::session_key(game, you));
+        if let Some(session) = session {
+            // The last move's post-mortem record is built after its response
+            let report = session.lock().pending_report.take();
+            if let Some(report) = report {
+                let _ = report.recv_timeout(Duration::from_secs(1));
+            }
+            let session = session.lock();
+            if !config.debug.summary_file_path.is_empty() {
+                let mode = session.mode.unwrap_or_else(|| GameMode::from_game(game, &config));
+                let summary = session.stats.clone().finish(&game.id, mode, *turn, board, &you.id, &config);
+                info!("Game {}: {:?} after {} turns, {} kill(s)", game.id, summary.outcome, summary.turns, summary.kills.len());
+                if let Err(e) = summary.append_to(Path::new(&config.debug.summary_file_path)) {
+                    warn!("{}", e);
+                }
+            }
+            if !config.debug.post_mortem_file_path.is_empty() {
+                if let Some(post_mortem) = PostMortem::build(&game.id, board, &you.id, &session.stats, &session.recent_searches, &config) {
+                    info!("Game {}: post-mortem {}", game.id, post_mortem.description);
+                    if let Err(e) = post_mortem.append_to(Path::new(&config.debug.post_mortem_file_path)) {
+                        warn!("{}", e);
+                    }
+                }
+            }
+        }
+        self.spectators.close(&game.id);
+        let estimation = &self.config().time_estimation;
+        if estimation.persist_timings && !estimation.history_file.is_empty() {
+            if let Err(e) = self.timings.lock().save(Path::new(&estimation.history_file)) {
+                warn!("{}", e);
+            }
+        }
+        // The logger only exists once a move was played; it is locked just to clone the sink
+        if let Ok(logger) = self.debug_logger.try_lock() {
+            if let Some(logger) = logger.as_ref() {
+                logger.finish_game(&game.id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Hard veto layer applied to the final chosen move
+    /// Independent of evaluation weights: if the move matches a banned pattern, the clean legal
+    /// move with the most reachable space is played instead, provided it is as safe as the
+    /// searched move: no worse static exchange (SEE) and no more fatal one-ply replies, without
+    /// dying to every reply. Every veto is logged for audit. Without such a move, the original
+    /// move stands.
+    pub fn apply_ban_list(
+        board: &Board,
+        you: &Battlesnake,
+        chosen: Direction,
+        legal_moves: &[Direction],
+        turn: i32,
+        config: &Config,
+    ) -> Direction {
+        if !config.ban_list.enabled {
+            return chosen;
+        }
+
+        let violations = Self::banned_patterns(board, you, chosen, config);
+        if violations.is_empty() {
+            return chosen;
+        }
+
+        let our_idx = match board.snake_index(&you.id) {
+            Some(idx) => idx,
+            None => return chosen,
+        };
+
+        // The searched move already passed SEE in the search; a replacement may not walk into an
+        // exchange it avoided, nor into replies it survives
+        let head = you.body[0];
+        let chosen_see = Self::static_exchange_eval(board, our_idx, chosen.apply(&head), config).min(0);
+        let (chosen_deaths, replies) = Self::one_ply_deaths(board, our_idx, chosen, config);
+        let as_safe = |mv: Direction| {
+            if Self::static_exchange_eval(board, our_idx, mv.apply(&head), config) < chosen_see {
+                return false;
+            }
+            let (deaths, _) = Self::one_ply_deaths(board, our_idx, mv, config);
+            deaths <= chosen_deaths && deaths < replies
+        };
+
+        let replacement = legal_moves
+            .iter()
+            .copied()
+            .filter(|&mv| mv != chosen && Self::banned_patterns(board, you, mv, config).is_empty() && as_safe(mv))
+            .max_by_key(|&mv| {
+                let mut child_board = board.clone();
+                Self::apply_move(&mut child_board, our_idx, mv, config);
+                Self::flood_fill_bfs(&child_board, mv.apply(&head), our_idx, None, config.hazard_walls.as_deref())
+            });
+
+        let names: Vec<&str> = violations.iter().map(|p| p.as_str()).collect();
+        match replacement {
+            Some(mv) => {
+                warn!(
+                    "Turn {}: VETO {} [{}] → playing {} instead",
+                    turn,
+                    chosen.as_str(),
+                    names.join(", "),
+                    mv.as_str()
+                );
+                mv
+            }
+            None => {
+                warn!(
+                    "Turn {}: VETO {} [{}] overridden - no other legal move is clean and as safe",
+                    turn,
+                    chosen.as_str(),
+                    names.join(", ")
+                );
+                chosen
+            }
+        }
+    }
+
+    /// Best move from a cheap one-ply ranking, used when the search result cannot be trusted
+    /// Ranks legal moves by: not losing a head-to-head, not matching a ban pattern, then reachable space
+    pub fn single_ply_fallback(board: &Board, you: &Battlesnake, legal_moves: &[Direction], config: &Config) -> Direction {
+        let head = match you.body.first() {
+            Some(&head) => head,
+            None => return Direction::Up,
+        };
+        let our_idx = board.snake_index(&you.id);
+
+        let ranked = legal_moves.iter().copied().max_by_key(|&mv| {
+            let next = mv.apply(&head);
+            let (see_safe, space) = match our_idx {
+                Some(idx) => {
+                    let mut child_board = board.clone();
+                    Self::apply_move(&mut child_board, idx, mv, config);
+                    (
+                        Self::static_exchange_eval(board, idx, next, config) >= 0,
+                        Self::flood_fill_bfs(&child_board, next, idx, None, config.hazard_walls.as_deref()),
+                    )
+                }
+                None => (true, 0),
+            };
+            let clean = Self::banned_patterns(board, you, mv, config).is_empty();
+            (see_safe, clean, space)
+        });
+
+        // No legal moves at all: at least stay on the board
+        ranked.unwrap_or_else(|| {
+            Direction::ALL
+                .iter()
+                .copied()
+                .find(|dir| !Self::is_out_of_bounds(&dir.apply(&head), board.width, board.height))
+                .unwrap_or(Direction::Up)
+        })
+    }
+
+    /// One-ply tactical check of the final move
+    /// Simulates our move against every combination of plausible replies of nearby opponents.
+    /// Returns an override if the chosen move dies against all of them while another legal move
+    /// does not; the replacement is the legal move with the fewest fatal replies (then most space).
+    pub fn verify_one_ply_safety(
+        board: &Board,
+        you: &Battlesnake,
+        chosen: Direction,
+        legal_moves: &[Direction],
+        config: &Config,
+    ) -> Option<SafetyOverride> {
+        if !config.safety_verifier.enabled || you.body.is_empty() {
+            return None;
+        }
+        let our_idx = board.snake_index(&you.id)?;
+
+        let (chosen_deaths, replies_checked) = Self::one_ply_deaths(board, our_idx, chosen, config);
+        if chosen_deaths < replies_checked {
+            return None;
+        }
+
+        let replacement = legal_moves
+            .iter()
+            .copied()
+            .filter(|&mv| mv != chosen)
+            .map(|mv| {
+                let (deaths, total) = Self::one_ply_deaths(board, our_idx, mv, config);
+                (mv, deaths, total)
+            })
+            .filter(|&(_, deaths, total)| deaths < total)
+            .min_by_key(|&(mv, deaths, total)| {
+                let mut child_board = board.clone();
+                Self::apply_move(&mut child_board, our_idx, mv, config);
+                let space = Self::flood_fill_bfs(&child_board, mv.apply(&you.body[0]), our_idx, None, config.hazard_walls.as_deref());
+                // Fewest fatal replies as a fraction of all replies, then most space
+                ((deaths * 1000) / total.max(1), std::cmp::Reverse(space))
+            })
+            .map(|(mv, _, _)| mv)?;
+
+        Some(SafetyOverride {
+            original: chosen,
+            replacement,
+            replies_checked,
+        })
+    }
+
+    /// Counts the opponent reply combinations after which `mv` leaves us dead or trapped
+    /// Returns (fatal combinations, total combinations). Only opponents within
+    /// safety_verifier.opponent_radius of our head are branched; others play their first legal move.
+    fn one_ply_deaths(board: &Board, our_idx: usize, mv: Direction, config: &Config) -> (usize, usize) {
+        let our_head = board.snakes[our_idx].body[0];
+
+        // Reply options per opponent (nearby ones branch, far ones are fixed)
+        let mut replies: Vec<(usize, MoveList)> = Vec::new();
+        for (idx, snake) in board.snakes.iter().enumerate() {
+            if idx == our_idx || snake.health <= 0 || snake.body.is_empty() {
+                continue;
+            }
+            let mut moves = Self::generate_legal_moves(board, snake, config);
+            if moves.is_empty() {
+                // Trapped opponent still has to move somewhere
+                moves.push(Direction::Up);
+            }
+            let distance = snake.body[0].manhattan(our_head);
+            if distance > config.safety_verifier.opponent_radius {
+                moves.truncate(1);
+            }
+            replies.push((idx, moves));
+        }
+
+        let total: usize = replies.iter().map(|(_, moves)| moves.len()).product();
+        let mut deaths = 0;
+        let mut choice = vec![0usize; replies.len()];
+
+        for _ in 0..total {
+            let mut child_board = board.clone();
+            let mut turn = vec![(our_idx, mv)];
+            turn.extend(replies.iter().enumerate().map(|(slot, (idx, moves))| (*idx, moves[choice[slot]])));
+            Self::apply_moves(&mut child_board, &turn, config);
+            Self::advance_full_turn(&mut child_board, config);
+
+            let us = &child_board.snakes[our_idx];
+            let dead = us.health <= 0
+                || Self::count_escape_squares(&child_board, us.body[0], us.body.get(1).copied(), None) == 0;
+            if dead {
+                deaths += 1;
+            }
+
+            // Advance the mixed-radix counter over reply combinations
+            for (slot, (_, moves)) in replies.iter().enumerate() {
+                choice[slot] += 1;
+                if choice[slot] < moves.len() {
+                    break;
+                }
+                choice[slot] = 0;
+            }
+        }
+
+        (deaths, total)
+    }
+
+    /// Returns the enabled ban-list patterns that a move matches
+    fn banned_patterns(board: &Board, you: &Battlesnake, mv: Direction, config: &Config) -> Vec<BanPattern> {
+        let mut violations = Vec::new();
+        let our_idx = match board.snake_index(&you.id) {
+            Some(idx) => idx,
+            None => return violations,
+        };
+        if you.body.is_empty() {
+            return violations;
+        }
+
+        let next = mv.apply(&you.body[0]);
+        let mut child_board = board.clone();
+        Self::apply_move(&mut child_board, our_idx, mv, config);
+
+        // Dead end: the region behind the move cannot hold our body
+        if config.ban_list.enter_dead_end {
+            let needed = you.length.max(1) as usize;
+            if Self::flood_fill_bfs(&child_board, next, our_idx, Some(needed), config.hazard_walls.as_deref()) < needed {
+                violations.push(BanPattern::EnterDeadEnd);
+            }
+        }
+
+        // Corner with an opponent head close enough to seal it
+        if config.ban_list.corner_with_opponent_adjacent {
+            let is_corner = (next.x == 0 || next.x == board.width - 1)
+                && (next.y == 0 || next.y == board.height as i32 - 1);
+            let opponent_near = board.snakes.iter().enumerate().any(|(idx, snake)| {
+                idx != our_idx
+                    && snake.health > 0
+                    && !snake.body.is_empty()
+                    && snake.body[0].manhattan(next) <= config.ban_list.corner_opponent_distance
+            });
+            if is_corner && opponent_near {
+                violations.push(BanPattern::CornerWithOpponentAdjacent);
+            }
+        }
+
+        // Single liberty: after the move only one free neighbour remains
+        if config.ban_list.leave_one_liberty {
+            let liberties = Self::count_escape_squares(&child_board, next, Some(you.body[0]), None);
+            if liberties <= 1 {
+                violations.push(BanPattern::LeaveOneLiberty);
+            }
+        }
+
+        violations
     }
 
     /// Computes and returns the next move using MaxN search with iterative deepening
-    /// Corresponds to POST /move endpoint
+    /// Corresponds to POST /move endpoint (POST /<profile>/move for a personality profile)
     ///
-    /// This method orchestrates the async polling and CPU-bound search computation:
+    /// This method orchestrates the async wait and CPU-bound search computation:
     /// 1. Spawns background search on rayon thread pool
-    /// 2. Polls for results with timeout management
+    /// 2. Waits for the search to complete or the time budget to expire, whichever is first
     /// 3. Returns best move found within time budget (anytime property)
     ///
     /// # Arguments
-    /// * `_game` - Current game metadata
+    /// * `profile` - Personality profile whose score weights to use (None: [scores] as-is)
+    /// * `game` - Current game metadata (game id and our snake id select the session)
     /// * `turn` - Current turn number
     /// * `board` - Current board state
     /// * `you` - Your snake's current state
     ///
     /// # Returns
     /// * `Value` - JSON response containing the chosen move direction
+    /// * `Err` if the profile is unknown
     pub async fn get_move(
         &self,
-        _game: &Game,
+        profile: Option<&str>,
+        game: &Game,
         turn: &i32,
         board: &Board,
         you: &Battlesnake,
-    ) -> Value {
+    ) -> Result<Value, String> {
+        self.get_move_with_request(profile, game, turn, board, you, None).await
+    }
+
+    /// get_move for library callers that may hold malformed states: `state` is validated
+    /// and repaired by request_validation::sanitize as the /move handler does, then searched
+    ///
+    /// # Returns
+    /// * `Direction` - the move get_move would answer
+    /// * `Err(BotError::InvalidState)` for states sanitize rejects (no snakes, empty bodies,
+    ///   `you` missing from the board, ...), `Err(BotError::UnknownProfile)` for unknown profiles
+    pub async fn try_get_move(&self, profile: Option<&str>, mut state: GameState) -> Result<Direction, BotError> {
+        let repairs = request_validation::sanitize(&mut state, &self.config()).map_err(BotError::InvalidState)?;
+        if !repairs.is_empty() {
+            warn!("Turn {}: repaired request ({})", state.turn, repairs.join("; "));
+        }
+        let response = self
+            .get_move(profile, &state.game, &state.turn, &state.board, &state.you)
+            .await
+            .map_err(BotError::UnknownProfile)?;
+        let name = response["move"].as_str().unwrap_or_default();
+        Direction::ALL.iter().copied().find(|dir| dir.as_str() == name).ok_or_else(|| BotError::InvalidDirection(name.to_string()))
+    }
+
+    /// get_move that also records the verbatim request body and response in the debug log
+    /// (the handlers pass the body when debug.log_requests is set)
+    pub async fn get_move_with_request(
+        &self,
+        profile: Option<&str>,
+        game: &Game,
+        turn: &i32,
+        board: &Board,
+        you: &Battlesnake,
+        request: Option<String>,
+    ) -> Result<Value, String> {
         let start_time = Instant::now();
+        let mut config = self.config_for(profile)?;
+
+        // Per-game session: mode from /start, history table carried over from the previous turn
+        let session = self.sessions.get_or_create(&Self::session_key(game, you), board);
+        let mode = *session.lock().mode.get_or_insert_with(|| GameMode::from_game(game, &config));
+        let strategy = mode.strategy();
+        // The mode's profile plays when the request names none (and it is configured)
+        if let (None, Some(mode_profile)) = (profile, strategy.profile) {
+            config = self.config_for(Some(mode_profile)).unwrap_or(config);
+        }
+        // The mode, a maze's walls and the ruleset settings reach the search through the config
+        let hazard_walls = match strategy.hazards {
+            HazardModel::Walls if !board.hazards.is_empty() => Some(Arc::new(HazardWalls::new(board))),
+            _ => None,
+        };
+        if mode != GameMode::Standard || game.ruleset.settings != RulesetSettings::default() {
+            let ruled = config.with_ruleset(&game.ruleset.settings);
+            config = Arc::new(Config { game_mode: mode, hazard_walls, ..ruled });
+        }
+        // Tournament stakes play the resulting config conservatively
+        let stakes = *session.lock().stakes.get_or_insert_with(|| Stakes::default_for(&config));
+        if let Some(conservative) = stakes.apply(&config) {
+            config = Arc::new(conservative);
+        }
 
         info!("Turn {}: Computing move", turn);
+        if config.debug.log_board && log::log_enabled!(log::Level::Debug) {
+            debug!("Turn {} board:\n{}", turn, board.render_ascii());
+        }
 
         // Ensure debug logger is initialized (lazy initialization on first call)
         self.ensure_debug_logger_initialized().await;
@@ -809,11 +2103,11 @@ impl Bot {
         // CRITICAL: Initialize shared state with first legal move BEFORE spawning search
         // Use force_initialize() to prevent race condition where search updates before init completes
         // ALSO: Keep legal_moves for later validation (must do this before cloning `you`)
-        let legal_moves = Self::generate_legal_moves(board, you, &self.config);
+        let legal_moves = Self::generate_legal_moves(board, you, &config);
         if !legal_moves.is_empty() {
             let first_legal_move = legal_moves[0];
             shared.force_initialize(
-                Self::direction_to_index(first_legal_move, &self.config),
+                Self::direction_to_index(first_legal_move, &config),
                 i32::MIN + 1, // Slightly better than initial i32::MIN
             );
         } else {
@@ -824,36 +2118,174 @@ impl Bot {
 
         let shared_clone = shared.clone();
 
-        // Clone data needed for the blocking task
-        let board_clone = board.clone();
-        let you = you.clone();
-        let config = self.config.clone();
+        // Clone data needed for the blocking task; the root board never changes during the
+        // search, so its occupancy is cached for the root-level checks
+        let mut board_clone = board.clone();
+        board_clone.cache_occupancy();
+        let you_clone = you.clone();
+        let search_config = Arc::clone(&config);
         let turn_number = *turn;
 
-        // Spawn CPU-bound computation on rayon thread pool
-        tokio::task::spawn_blocking(move || {
-            Bot::compute_best_move_internal(&board_clone, &you, turn_number, shared_clone, start_time, &config)
-        });
-
-        // Polling loop: check for results or timeout
-        let effective_budget = self.config.timing.effective_budget_ms();
-        let polling_interval = Duration::from_millis(self.config.timing.polling_interval_ms);
+        let (mut history, served_turn, budget_ms, overhead_ms) = {
+            let mut guard = session.lock();
+            if config.time_estimation.persist_timings {
+                // A new game starts from everything learned so far; later turns from its own session
+                if guard.turns_played == 0 {
+                    guard.timings = self.timings.lock().clone();
+                }
+                shared.set_timing_prior(guard.timings.prior(board));
+            }
+            if game.timeout > 0 {
+                guard.game_timeout_ms = game.timeout;
+            }
+            // Engine-reported latency of our previous move refines the overhead estimate
+            guard.latency.observe(&you.latency, &config);
+            let overhead_ms = guard.latency.overhead_estimate_ms(&config);
+            // Spend less on forced/simple turns and more on complex or unstable ones
+            let max_budget_ms = guard.latency.budget_ms(guard.game_timeout_ms, &config);
+            let allocation = time_manager::allocate(max_budget_ms, board, you, guard.last_score_swing, guard.last_score, &config);
+            // Critical turns also spend time banked by earlier turns
+            let last_score_swing = guard.last_score_swing;
+            let banked_ms = guard.time_bank.withdraw(allocation.budget_ms, max_budget_ms, last_score_swing, &config);
+            let budget_ms = allocation.budget_ms + banked_ms;
+            info!(
+                "Turn {}: budget {}ms (scale {:.2}, joint branching {}{}, banked +{}ms, balance {}ms)",
+                turn,
+                budget_ms,
+                allocation.scale,
+                allocation.branching,
+                if allocation.forced { ", forced move" } else { "" },
+                banked_ms,
+                guard.time_bank.balance_ms()
+            );
+            if guard.turns_played == 0 && guard.opening.is_none() && *turn == 0 {
+                // /start was missed for this game; pick the opening plan now
+                Self::init_opening_plan(&mut guard, board, you, &config);
+            }
+            if let Some(plan_move) = Self::opening_plan_move(&mut guard, board, you, *turn, &config) {
+                shared.set_root_bias(Self::direction_to_index(plan_move, &config), config.opening.follow_bonus);
+            }
+            (guard.history_for_next_turn(board, &config), guard.turns_played, budget_ms, overhead_ms)
+        };
 
-        loop {
-            tokio::time::sleep(polling_interval).await;
+        // Concurrent games share the cores: wait for a search slot (and its thread pool)
+        let slot = self
+            .capacity
+            .acquire(Duration::from_millis(config.concurrency.slot_wait_ms))
+            .await;
+        let searched = slot.is_some();
+        match slot {
+            Some(slot) => {
+                info!(
+                    "Turn {}: search slot with {} threads ({} searches running)",
+                    turn,
+                    slot.threads(),
+                    self.capacity.active()
+                );
 
-            let elapsed = start_time.elapsed().as_millis() as u64;
+                // Spawn CPU-bound computation on the slot's rayon thread pool
+                let search_session = session.clone();
+                let bot_timings = self.timings.clone();
+                let game_id = game.id.clone();
+                tokio::task::spawn_blocking(move || {
+                    let limits = SearchLimits::from_config(&search_config).with_budget_ms(Some(budget_ms)).started_at(start_time);
+                    let profile_start = simple_profiler::snapshot();
+                    // A panic must never take the move response down with it: contain it and let
+                    // get_move fall back to a one-ply move. The history table is discarded in that case.
+                    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+                        slot.install(|| {
+                            Bot::compute_best_move_internal(&board_clone, &you_clone, turn_number, shared_clone.clone(), &search_config, &mut history, limits);
+                        });
+                    }));
+                    match outcome {
+                        Ok(()) => {
+                            let mut guard = search_session.lock();
+                            guard.store_history(history, served_turn);
+                            let estimation = &search_config.time_estimation;
+                            if estimation.persist_timings {
+                                let timings = shared_clone.depth_timings();
+                                guard.timings.record(&board_clone, &timings, estimation.history_max_observations);
+                                bot_timings.lock().record(&board_clone, &timings, estimation.history_max_observations);
+                            }
+                        }
+                        Err(payload) => shared_clone.record_panic(panic_payload_message(payload.as_ref())),
+                    }
+                    let profiling = &search_config.profiling;
+                    if profiling.enabled && !profiling.json_directory.is_empty() {
+                        let profile = TurnProfile::from_search(
+                            &game_id,
+                            turn_number,
+                            budget_ms,
+                            start_time.elapsed().as_millis() as u64,
+                            &shared_clone,
+                            simple_profiler::snapshot().since(&profile_start),
+                        );
+                        if let Err(e) = profile.write(Path::new(&profiling.json_directory)) {
+                            warn!("Turn {}: {}", turn_number, e);
+                        }
+                    }
+                });
+            }
+            None => {
+                // Every slot is busy: answer with a one-ply move instead of queueing past the deadline
+                shared.mark_complete();
+            }
+        }
 
-            // Check if we've exceeded our time budget or search is complete
-            if elapsed >= effective_budget || shared.search_complete.load(Ordering::Acquire) {
-                break;
+        // Answer as soon as the search completes, or once the time budget is spent
+        // (nothing to wait for when no slot was available)
+        if searched {
+            let deadline = tokio::time::Instant::from_std(start_time + Duration::from_millis(budget_ms));
+            if tokio::time::timeout_at(deadline, shared.completed()).await.is_err() {
+                debug!("Turn {}: budget of {}ms spent before the search completed", turn, budget_ms);
             }
         }
 
-        // Extract results from shared state
-        let (best_move_idx, final_score) = shared.get_best();
-        let chosen_move = Self::index_to_direction(best_move_idx, &self.config);
-        let final_depth = shared.current_depth.load(Ordering::Acquire);
+        // Abort the blocking search so it stops burning CPU past the deadline
+        shared.request_stop();
+
+        // Extract results from shared state: the newest completed iteration, never the
+        // favorite of an iteration the deadline cut short
+        let (best_move_idx, final_score, final_depth) = shared.answer();
+        let (partial_move_idx, partial_score) = shared.get_best();
+        if partial_move_idx != best_move_idx {
+            info!(
+                "Turn {}: answering depth {} result {} ({}), not the unfinished iteration's {} ({})",
+                turn,
+                final_depth,
+                Self::index_to_direction(best_move_idx, &config).as_str(),
+                final_score,
+                Self::index_to_direction(partial_move_idx, &config).as_str(),
+                partial_score
+            );
+        }
+        let chosen_move = Self::index_to_direction(best_move_idx, &config);
+
+        // Crashed search: ignore whatever it published and rank moves one ply deep instead
+        let panic_message = shared.panic_message();
+        let chosen_move = match &panic_message {
+            None if !searched => {
+                let fallback = Self::single_ply_fallback(board, you, &legal_moves, &config);
+                warn!(
+                    "Turn {}: NO SEARCH SLOT ({} searches running) - playing one-ply move {}",
+                    turn,
+                    self.capacity.active(),
+                    fallback.as_str()
+                );
+                fallback
+            }
+            Some(message) => {
+                let fallback = Self::single_ply_fallback(board, you, &legal_moves, &config);
+                error!(
+                    "Turn {}: SEARCH PANICKED ({}) - falling back to one-ply move {}",
+                    turn,
+                    message,
+                    fallback.as_str()
+                );
+                fallback
+            }
+            None => chosen_move,
+        };
 
         // DEFENSIVE: Validate chosen move is actually legal (catches any remaining edge cases)
         let final_move = if legal_moves.contains(&chosen_move) {
@@ -866,34 +2298,236 @@ impl Bot {
             legal_moves.first().copied().unwrap_or(Direction::Up)
         };
 
+        // Hard safety net on top of the search result
+        let final_move = Self::apply_ban_list(board, you, final_move, &legal_moves, *turn, &config);
+
+        // Last line of defence: never play a move that dies to every opponent reply
+        // while another legal move survives at least one
+        let trusted_score = searched && panic_message.is_none() && final_depth > 0;
+        let mut notes = TurnNotes { panic: panic_message, you: Some(you.id.clone()), ..TurnNotes::default() };
+        let final_move = match Self::verify_one_ply_safety(board, you, final_move, &legal_moves, &config) {
+            Some(safety_override) => {
+                warn!("Turn {}: SAFETY OVERRIDE {}", turn, safety_override.describe());
+                notes.overrides.push(safety_override.describe());
+                safety_override.replacement
+            }
+            None => final_move,
+        };
+
+        let elapsed_ms = start_time.elapsed().as_millis() as u64;
+        let score_drop = {
+            let mut guard = session.lock();
+            guard.latency.record_handler_time(elapsed_ms);
+            guard.last_score_swing = Some(shared.score_swing());
+            guard.last_score = trusted_score.then_some(final_score);
+            if searched {
+                guard.time_bank.deposit(budget_ms, elapsed_ms, &config);
+            }
+            if !config.debug.summary_file_path.is_empty() || !config.debug.post_mortem_file_path.is_empty() {
+                let search = MoveSearch { depth: final_depth, nodes: shared.nodes(), margin_ms: game.timeout as i64 - elapsed_ms as i64 };
+                guard.stats.record_move(*turn, board, &you.id, final_move, search, &config);
+            }
+            if config.score_trend.enabled && trusted_score {
+                guard.score_trend.observe(*turn, final_score, &config.score_trend)
+            } else {
+                None
+            }
+        };
+        if let Some(drop) = score_drop {
+            warn!(
+                "Turn {}: SCORE DROP {} (candidates: {})\n{}",
+                turn,
+                drop.describe(),
+                score_trend::format_candidates(&shared.root_scores()),
+                board.render_ascii()
+            );
+            notes.score_drop = Some(drop.describe());
+        }
         info!(
-            "Turn {}: Chose {} (score: {}, depth: {}, time: {}ms)",
+            "Turn {}: Chose {} (score: {}, depth: {}, time: {}ms, budget: {}ms, latency est: {}ms)",
             turn,
             final_move.as_str(),
             final_score,
             final_depth,
-            start_time.elapsed().as_millis()
+            elapsed_ms,
+            budget_ms,
+            overhead_ms
         );
 
-        // Fire-and-forget debug logging (non-blocking)
-        if let Some(logger) = self.debug_logger.lock().await.as_ref() {
-            logger.log_move(*turn, board.clone(), final_move);
+        let response = json!({ "move": final_move.as_str() });
+
+        // Debug logging, the live stream of watched games and the last turns kept for a
+        // post-mortem run after the response: none of it (the detailed evaluation above all)
+        // is in the time budget
+        let logger = self.debug_logger.lock().await.clone();
+        let watched = self.spectators.is_watched(&game.id);
+        let post_mortem = !config.debug.post_mortem_file_path.is_empty() && config.debug.post_mortem_turns > 0;
+        if logger.is_some() || watched || post_mortem {
+            if let (Some(_), Some(request)) = (&logger, request) {
+                notes.request = Some(request);
+                notes.response = Some(response.to_string());
+            }
+            let pending = logger.as_ref().map(DebugLogger::pending_write);
+            let (done, report) = std::sync::mpsc::channel::<()>();
+            session.lock().pending_report = Some(report);
+            let spectators = self.spectators.clone();
+            let (game_id, you_id, turn, board) = (game.id.clone(), you.id.clone(), *turn, board.clone());
+            tokio::task::spawn_blocking(move || {
+                let (_pending, _done) = (pending, done);
+                let (tt_probes, tt_hits) = shared.tt_stats();
+                // The PV starts with the search's move; a changed final move invalidates it
+                let pv = shared.principal_variation();
+                let pv = if pv.first() == Some(&final_move) { pv } else { vec![final_move] };
+                let result = SearchResultV1::new(turn, final_move, final_score, final_depth, elapsed_ms)
+                    .with_nodes(shared.nodes())
+                    .with_tt_stats(tt_probes, tt_hits)
+                    .with_tt_collisions(shared.tt_collisions())
+                    .with_pv(&pv)
+                    .with_candidates(&shared.root_scores())
+                    .with_iterations(&shared.iterations());
+                let result = match Self::evaluate_move_detailed(&board, &you_id, final_move, &config) {
+                    Ok(breakdown) => result.with_breakdown(ScoreBreakdownV1::from(&breakdown)),
+                    Err(e) => {
+                        warn!("Turn {}: no score breakdown: {}", turn, e);
+                        result
+                    }
+                };
+                // Explain mode: where the winning line under the played move ends up
+                let result = match shared.leaf_breakdown(final_depth, final_move) {
+                    Some(leaf) => result.with_leaf_breakdown(ScoreBreakdownV1::from(&leaf)),
+                    None => result,
+                };
+                if post_mortem {
+                    let mut guard = session.lock();
+                    guard.recent_searches.push_back(result.clone());
+                    while guard.recent_searches.len() > config.debug.post_mortem_turns {
+                        guard.recent_searches.pop_front();
+                    }
+                }
+                if watched {
+                    spectators.publish(&SpectatorFrame {
+                        game_id: &game_id,
+                        you: &you_id,
+                        turn,
+                        board: &board,
+                        budget_ms,
+                        result: &result,
+                    });
+                }
+                if let Some(logger) = logger {
+                    logger.log_move(&game_id, turn, board, final_move, result, notes);
+                }
+            });
+        }
+
+        Ok(response)
+    }
+
+    /// Principal variation: `root_move`, then the best moves the transposition table holds
+    /// for the following plies, snakes taking turns in search order (us, then the next
+    /// alive snakes). Only alpha-beta nodes record a best move, so multiplayer (MaxN)
+    /// lines stop after the root move. At most `max_plies` moves; stops at a repetition.
+    pub fn principal_variation(
+        board: &Board,
+        you: &Battlesnake,
+        root_move: Direction,
+        tt: &TranspositionTable,
+        max_plies: usize,
+        config: &Config,
+    ) -> Vec<Direction> {
+        Self::principal_line(board, you, root_move, tt, max_plies, config).0
+    }
+
+    /// The principal variation and the position at its end
+    fn principal_line(
+        board: &Board,
+        you: &Battlesnake,
+        root_move: Direction,
+        tt: &TranspositionTable,
+        max_plies: usize,
+        config: &Config,
+    ) -> (Vec<Direction>, Board) {
+        let our_idx = match board.snake_index(&you.id) {
+            Some(idx) => idx,
+            None => return (vec![root_move], board.clone()),
+        };
+        let mut line = vec![root_move];
+        let mut position = board.clone();
+        // The root applies our move alone; deeper plies advance the game after every move
+        Self::apply_move(&mut position, our_idx, root_move, config);
+
+        let mut player = our_idx;
+        let mut seen = std::collections::HashSet::new();
+        while line.len() < max_plies {
+            let alive = (1..=position.snakes.len())
+                .map(|offset| (player + offset) % position.snakes.len())
+                .find(|&idx| position.snakes[idx].health > 0);
+            player = match alive {
+                Some(idx) => idx,
+                None => break,
+            };
+            let hash = TranspositionTable::hash_board(&position);
+            if !seen.insert(hash) {
+                break;
+            }
+            match tt.probe_with_move(TranspositionTable::node_key(hash, &position, player), 0) {
+                Some((_, Some(mv))) => {
+                    Self::apply_move(&mut position, player, mv, config);
+                    Self::advance_game_state(&mut position, config);
+                    line.push(mv);
+                }
+                _ => break,
+            }
         }
+        (line, position)
+    }
 
-        json!({ "move": final_move.as_str() })
+    /// Explain mode: records a leaf breakdown for every root move of the answer's iteration
+    /// that has none. MaxN carries its leaf up in the ScoreTuple; alpha-beta and best-reply
+    /// return bare scores (as do transposition hits and IDAPOS pairings), so their winning
+    /// line is recovered from the transposition table and evaluated at the end of the PV.
+    fn explain_root_moves(
+        board: &Board,
+        you: &Battlesnake,
+        tt: &TranspositionTable,
+        root_tts: &[Arc<TranspositionTable>],
+        shared: &SharedSearchState,
+        config: &Config,
+    ) {
+        let (_, _, depth) = shared.answer();
+        let max_plies = depth as usize * board.alive_snakes().count().max(1);
+        let moves: Vec<Direction> = shared.root_scores().iter().filter(|&&(d, _, _)| d == depth).map(|&(_, mv, _)| mv).collect();
+        for mv in moves {
+            if shared.leaf_breakdown(depth, mv).is_some() {
+                continue;
+            }
+            let line_tt = root_tts.get(Self::direction_to_index(mv, config) as usize).map_or(tt, |root_tt| root_tt.as_ref());
+            let (line, leaf) = Self::principal_line(board, you, mv, line_tt, max_plies, config);
+            if let Some(our_idx) = leaf.snake_index(&you.id) {
+                // Alpha-beta discounts its leaves per ply below the root
+                let plies = line.len().min(u8::MAX as usize) as u8;
+                shared.record_leaf_breakdown(depth, mv, &Self::detailed_score(&leaf, our_idx, config).discounted(plies, config));
+            }
+        }
     }
 
     /// Internal computation engine - runs on rayon thread pool
     /// Performs iterative deepening MaxN search with time management
+    ///
+    /// `history` is the per-game history table; it is updated in place so the
+    /// caller can persist it for the next turn. `limits` adds optional budgets
+    /// (e.g. max nodes) on top of the time budget, whose clock starts at `limits.started`.
     pub fn compute_best_move_internal(
         board: &Board,
         you: &Battlesnake,
         turn: i32,
         shared: Arc<SharedSearchState>,
-        start_time: Instant,
         config: &Config,
+        history: &mut HistoryTable,
+        limits: SearchLimits,
     ) {
         info!("Starting MaxN search computation");
+        let start_time = limits.started.unwrap_or_else(Instant::now);
         let init_start = Instant::now();
 
         // Create transposition table for this search
@@ -906,20 +2540,40 @@ impl Bot {
         let mut killers = KillerMoveTable::new(config);
         let mut pv_move: Option<Direction> = None;
 
-        // Create history table for move ordering
+        // History table for move ordering comes from the caller (persisted per game)
         // Tracks globally successful moves across all positions
-        let mut history = HistoryTable::new(board.width as u32, board.height as u32);
+        if !history.matches_dimensions(board.width as u32, board.height) {
+            *history = HistoryTable::new(board.width as u32, board.height);
+        }
+        let mut iterations_since_history_aging = 0u8;
 
         // Determine execution strategy
-        let num_alive_snakes = board.snakes.iter().filter(|s| s.health > 0).count();
+        let num_alive_snakes = board.alive_snakes().count();
         let num_cpus = rayon::current_num_threads();
 
-        let strategy = Self::determine_strategy(num_alive_snakes, num_cpus, config);
+        // Fixed-depth mode must not depend on thread scheduling (shared TT races in parallel search)
+        // unless the parallel search runs in deterministic mode
+        let strategy = if limits.fixed_depth.is_some() && !config.strategy.deterministic_parallel {
+            ExecutionStrategy::Sequential
+        } else {
+            Self::determine_strategy(config.game_mode, num_alive_snakes, num_cpus, config)
+        };
         info!(
-            "Selected strategy: {:?} (snakes={}, cpus={})",
-            strategy, num_alive_snakes, num_cpus
+            "Selected strategy: {:?} (mode={}, snakes={}, cpus={})",
+            strategy,
+            config.game_mode.name(),
+            num_alive_snakes,
+            num_cpus
         );
 
+        // Deterministic parallel mode: one TT per root move (by direction index), kept across
+        // iterations, so no subtree reads entries a sibling task stored
+        let root_tts: Vec<Arc<TranspositionTable>> = if config.strategy.deterministic_parallel && strategy != ExecutionStrategy::Sequential {
+            (0..Direction::ALL.len()).map(|_| Arc::new(TranspositionTable::new(100_000))).collect()
+        } else {
+            Vec::new()
+        };
+
         // Get appropriate time estimation parameters based on number of alive snakes
         let time_params = config.time_estimation.for_snake_count(num_alive_snakes);
 
@@ -929,7 +2583,8 @@ impl Bot {
             time_params.base_iteration_time_ms,
             time_params.branching_factor,
             config.time_estimation.model_weight,
-        );
+        )
+        .with_prior(shared.timing_prior.lock().clone());
 
         let init_elapsed = init_start.elapsed().as_micros();
         if simple_profiler::is_profiling_enabled() {
@@ -943,18 +2598,78 @@ impl Bot {
             info!("Found safe adjacent food at {:?}, taking immediate move: {}", food_pos, food_move.as_str());
             let food_move_idx = Self::direction_to_index(food_move, config);
             shared.force_initialize(food_move_idx, i32::MAX - 1000); // High score for immediate food
-            shared.search_complete.store(true, Ordering::Release);
+            shared.mark_complete();
             return; // Skip search entirely
         }
 
+        // PRE-SEARCH PRUNING: drop provably losing root moves from every iteration
+        // The budget then goes to the viable candidates; if all are losing, nothing is pruned
+        let root_legal = Self::generate_legal_moves(board, you, config);
+        let pruned = Self::prune_root_moves(board, you, &root_legal, config);
+        for (mv, reason) in &pruned {
+            info!("Turn {}: ROOT PRUNE {} - {}", turn, mv.as_str(), reason.describe());
+            shared.exclude_root_move(Self::direction_to_index(*mv, config));
+        }
+        // Trap-setting moves: the evaluation's trap term steers the search towards them
+        if let Some(our_idx) = board.snake_index(&you.id).filter(|_| config.scores.weight_trap_setting > 0.0) {
+            for (mv, cut) in trap::trap_moves(board, our_idx, config) {
+                for (opp, region) in cut {
+                    let opponent = &board.snakes[opp];
+                    info!("Turn {}: TRAP {} - cuts {} to {} cells (length {})", turn, mv.as_str(), opponent.name, region, opponent.length);
+                }
+            }
+        }
+        // Hungry: steer towards food we can reach, keep and leave again
+        if let Some(our_idx) = board.snake_index(&you.id).filter(|_| config.food_routing.enabled && you.health < config.food_routing.health_threshold) {
+            match food_route::plan(board, our_idx, config) {
+                Some(route) => {
+                    if let Some(mv) = route.first_move(you.body[0]) {
+                        info!("Turn {}: FOOD ROUTE {} - food at ({}, {}) in {} moves", turn, mv.as_str(), route.food.x, route.food.y, route.path.len());
+                        let move_idx = Self::direction_to_index(mv, config);
+                        shared.set_root_bias(move_idx, shared.root_bias_for(move_idx).max(config.food_routing.first_step_bonus));
+                    }
+                }
+                None => info!("Turn {}: NO SAFE FOOD - health {}, {} food on the board", turn, you.health, board.food.len()),
+            }
+        }
+        // Duel endgame without relevant food: prefer the moves that win the parity race
+        if let Some(our_idx) = board.snake_index(&you.id).filter(|_| config.endgame.enabled) {
+            if let Some((parity, margins)) = endgame::root_margins(board, our_idx, config) {
+                info!("Turn {}: ENDGAME parity - {} turns for us, {} for the opponent", turn, parity.our_turns, parity.their_turns);
+                let best = margins.iter().map(|&(_, margin)| margin).max();
+                if margins.iter().any(|&(_, margin)| Some(margin) != best) {
+                    for &(mv, _) in margins.iter().filter(|&&(_, margin)| Some(margin) == best) {
+                        let move_idx = Self::direction_to_index(mv, config);
+                        shared.set_root_bias(move_idx, shared.root_bias_for(move_idx).max(config.endgame.fill_bonus));
+                    }
+                }
+            }
+        }
+        // Clearly ahead: patrol a Hamiltonian cycle and give nothing away
+        if let Some(our_idx) = board.snake_index(&you.id).filter(|&idx| config.stall.enabled && stall::is_ahead(board, idx, config)) {
+            if let Some((mv, step)) = stall::stall_move(board, our_idx, config) {
+                info!("Turn {}: STALL {} - {:?}", turn, mv.as_str(), step);
+                let move_idx = Self::direction_to_index(mv, config);
+                shared.set_root_bias(move_idx, shared.root_bias_for(move_idx).max(config.stall.follow_bonus));
+            }
+        }
+
+        // The poller's fallback must not be a pruned move either
+        if shared.is_root_excluded(shared.get_best().0) {
+            if let Some(&viable) = root_legal.iter().find(|mv| !pruned.iter().any(|(p, _)| p == *mv)) {
+                shared.force_initialize(Self::direction_to_index(viable, config), i32::MIN + 1);
+            }
+        }
+
         // Iterative deepening loop
         let mut current_depth = config.timing.initial_depth;
-        let effective_budget = config.timing.effective_budget_ms();
+        let effective_budget = limits.effective_budget_ms(config);
         let mut previous_score: Option<i32> = None;  // Track previous iteration score for aspiration windows
 
         // V9: Track score improvement for early exit
-        let mut previous_best_score: Option<i32> = None;
-        let mut depth_since_improvement: u8 = 0;
+        let mut previous_best: Option<(u8, i32)> = None;
+        // Consecutive iterations that kept the best move with a score within stability_score_margin
+        let mut stable_iterations: u8 = 0;
 
         loop {
             let elapsed = start_time.elapsed().as_millis() as u64;
@@ -965,8 +2680,16 @@ impl Bot {
                          current_depth, elapsed, remaining);
             }
 
+            // Fixed-depth mode: no clock, stop exactly after the requested depth
+            if let Some(fixed_depth) = limits.fixed_depth {
+                if current_depth > fixed_depth {
+                    info!("Stopping search: reached fixed depth ({})", fixed_depth);
+                    break;
+                }
+            }
+
             // Check if we have enough time for another iteration
-            if remaining < config.timing.min_time_remaining_ms {
+            if limits.fixed_depth.is_none() && remaining < config.timing.min_time_remaining_ms {
                 info!(
                     "Stopping search: insufficient time remaining ({}ms)",
                     remaining
@@ -980,7 +2703,7 @@ impl Bot {
 
             // CRITICAL FIX: Use IDAPOS-filtered snake count for time estimation
             // Previously used num_alive_snakes (all snakes), causing massive overestimation
-            let active_snakes = Self::determine_active_snakes(board, &you.id, turn, current_depth, config);
+            let active_snakes = Self::determine_active_snakes(board, &you.id, turn, current_depth, None, config);
             let num_active_snakes = active_snakes.len();
 
             // Estimate time for next iteration using ADAPTIVE estimation
@@ -993,7 +2716,7 @@ impl Bot {
                          current_depth, num_alive_snakes, num_active_snakes, estimated_time);
             }
 
-            if estimated_time > remaining {
+            if limits.fixed_depth.is_none() && estimated_time > remaining {
                 info!("Stopping search: next iteration would exceed budget (estimated {}ms, remaining {}ms)",
                       estimated_time, remaining);
                 if simple_profiler::is_profiling_enabled() {
@@ -1015,26 +2738,48 @@ impl Bot {
             );
             shared.current_depth.store(current_depth, Ordering::Release);
 
-            // V11: Age killers and decay history instead of clearing
+            // V11: Age killers and halve history instead of clearing
             // This preserves valuable move ordering information across iterations
-            // Decay factor 0.9 = keep 90% of previous knowledge
+            // History is halved every history_aging_interval iterations, or early if it nears saturation
             killers.age_killers();
-            history.decay_history(0.9);
+            iterations_since_history_aging += 1;
+            if iterations_since_history_aging >= config.move_ordering.history_aging_interval
+                || history.max_magnitude() >= config.move_ordering.history_max_score
+            {
+                history.age();
+                iterations_since_history_aging = 0;
+            }
 
-            // Record iteration start time
+            // Record iteration start time (wall) and task CPU accounting baseline
             let iteration_start = Instant::now();
+            let nodes_before = shared.nodes();
+            let (task_wall_before, task_cpu_before) = shared.task_times();
+            let sequential_clock = if strategy == ExecutionStrategy::Sequential {
+                Some(TaskClock::start())
+            } else {
+                None
+            };
 
             // Determine if we should use aspiration windows
-            let use_aspiration_windows = config.aspiration_windows.enabled
-                && strategy == ExecutionStrategy::Sequential
-                && num_alive_snakes == config.strategy.min_snakes_for_1v1
-                && previous_score.is_some();
+            let aspiration_center = previous_score.filter(|_| {
+                config.aspiration_windows.enabled
+                    && strategy == ExecutionStrategy::Sequential
+                    && Self::pairs_players(num_alive_snakes, config.strategy.min_snakes_for_1v1, config)
+            });
 
             // Execute search with strategy-specific parameters
+            let ctx = SearchContext {
+                our_snake_id: &you.id,
+                turn,
+                config,
+                tt: &tt,
+                killers: &killers,
+                history,
+                shared: &shared,
+            };
             match strategy {
                 ExecutionStrategy::Sequential => {
-                    if use_aspiration_windows {
-                        let prev_score = previous_score.unwrap();
+                    if let Some(prev_score) = aspiration_center {
                         let window_size = config.aspiration_windows.initial_window_size;
                         let mut alpha = prev_score.saturating_sub(window_size);
                         let mut beta = prev_score.saturating_add(window_size);
@@ -1042,7 +2787,7 @@ impl Bot {
                         info!("Using aspiration window: [{}, {}] (previous score: {})", alpha, beta, prev_score);
 
                         // First search with narrow window
-                        Self::sequential_search(board, you, turn, current_depth, &shared, config, &tt, &mut killers, &mut history, pv_move, alpha, beta);
+                        Self::sequential_search(board, you, current_depth, pv_move, alpha, beta, &ctx);
 
                         // Check if we failed outside the window
                         let (_, result_score) = shared.get_best();
@@ -1051,101 +2796,188 @@ impl Bot {
                             // Fail-low: re-search with lower bound at -∞
                             info!("Aspiration window fail-low ({} <= {}), re-searching with wider window", result_score, alpha);
                             alpha = i32::MIN;
-                            Self::sequential_search(board, you, turn, current_depth, &shared, config, &tt, &mut killers, &mut history, pv_move, alpha, beta);
+                            Self::sequential_search(board, you, current_depth, pv_move, alpha, beta, &ctx);
 
                             let (_, retry_score) = shared.get_best();
                             if retry_score >= beta {
                                 // Also failed high on retry, do full window search
                                 info!("Retry also failed high ({} >= {}), searching with full window", retry_score, beta);
-                                Self::sequential_search(board, you, turn, current_depth, &shared, config, &tt, &mut killers, &mut history, pv_move, i32::MIN, i32::MAX);
+                                Self::sequential_search(board, you, current_depth, pv_move, i32::MIN, i32::MAX, &ctx);
                             }
                         } else if result_score >= beta {
                             // Fail-high: re-search with upper bound at +∞
                             info!("Aspiration window fail-high ({} >= {}), re-searching with wider window", result_score, beta);
                             beta = i32::MAX;
-                            Self::sequential_search(board, you, turn, current_depth, &shared, config, &tt, &mut killers, &mut history, pv_move, alpha, beta);
+                            Self::sequential_search(board, you, current_depth, pv_move, alpha, beta, &ctx);
 
                             let (_, retry_score) = shared.get_best();
                             if retry_score <= alpha {
                                 // Also failed low on retry, do full window search
                                 info!("Retry also failed low ({} <= {}), searching with full window", retry_score, alpha);
-                                Self::sequential_search(board, you, turn, current_depth, &shared, config, &tt, &mut killers, &mut history, pv_move, i32::MIN, i32::MAX);
+                                Self::sequential_search(board, you, current_depth, pv_move, i32::MIN, i32::MAX, &ctx);
                             }
                         }
                     } else {
                         // No aspiration windows, use full window
-                        Self::sequential_search(board, you, turn, current_depth, &shared, config, &tt, &mut killers, &mut history, pv_move, i32::MIN, i32::MAX);
+                        Self::sequential_search(board, you, current_depth, pv_move, i32::MIN, i32::MAX, &ctx);
                     }
                 }
                 ExecutionStrategy::Parallel1v1 => {
-                    Self::parallel_1v1_search(board, you, current_depth, &shared, config, &tt, &mut history, pv_move);
+                    Self::parallel_1v1_search(board, you, current_depth, &root_tts, pv_move, &ctx);
                 }
                 ExecutionStrategy::ParallelMultiplayer => {
-                    Self::parallel_multiplayer_search(board, you, turn, current_depth, &shared, config, &tt, &mut history, pv_move);
+                    Self::parallel_multiplayer_search(board, you, current_depth, &root_tts, pv_move, &ctx);
                 }
             }
 
+            // Budget expired during this iteration: its results were discarded, stop here
+            if shared.is_stopped() {
+                info!("Stopping search: aborted during depth {} (time budget expired)", current_depth);
+                break;
+            }
+
             // Record actual iteration time
             let iteration_elapsed = iteration_start.elapsed().as_millis() as u64;
+            if let Some(clock) = sequential_clock {
+                clock.finish(&shared);
+            }
+
+            // CPU vs wall time of the search tasks in this iteration
+            let (task_wall_after, task_cpu_after) = shared.task_times();
+            let task_wall_us = task_wall_after.saturating_sub(task_wall_before);
+            let task_cpu_us = task_cpu_after.saturating_sub(task_cpu_before);
+            let cpu_fraction = if task_wall_us > 0 && task_cpu_us > 0 {
+                (task_cpu_us as f64 / task_wall_us as f64).min(1.0)
+            } else {
+                1.0
+            };
+
+            if task_cpu_us > 0
+                && iteration_elapsed >= config.time_estimation.contention_min_iteration_ms
+                && task_wall_us as f64 >= task_cpu_us as f64 * config.time_estimation.contention_wall_cpu_ratio
+            {
+                warn!(
+                    "Host contention: depth {} search tasks took {:.1}ms wall but only {:.1}ms CPU (ratio {:.1})",
+                    current_depth,
+                    task_wall_us as f64 / 1000.0,
+                    task_cpu_us as f64 / 1000.0,
+                    task_wall_us as f64 / task_cpu_us as f64
+                );
+            }
 
             // Record observation for adaptive time estimation
             // This teaches the estimator about actual iteration times, making future estimates more accurate
-            time_estimator.record_observation(current_depth, iteration_elapsed as f64);
+            // With use_cpu_time, wall time is scaled by the fraction the workers were actually running,
+            // so scheduler noise from neighbours on a shared host does not inflate the model
+            let observed_ms = if config.time_estimation.use_cpu_time {
+                iteration_elapsed as f64 * cpu_fraction
+            } else {
+                iteration_elapsed as f64
+            };
+            time_estimator.record_observation(current_depth, observed_ms);
+            shared.record_depth_timing(current_depth, observed_ms);
+            let iteration_nodes = shared.nodes().saturating_sub(nodes_before);
+            shared.record_iteration(IterationStats {
+                depth: current_depth,
+                time_ms: iteration_elapsed,
+                nodes: iteration_nodes,
+            });
+            let ebf = effective_branching_factor(&shared.iterations());
+            time_estimator.record_branching(ebf);
 
             // Extract best move and score from this iteration
+            shared.record_checkpoint(current_depth, start_time.elapsed().as_millis() as u64);
             let (best_move_idx, best_score) = shared.get_best();
             pv_move = Some(Self::index_to_direction(best_move_idx, config));
             previous_score = Some(best_score);  // Store for next iteration's aspiration window
 
             info!(
-                "Completed depth {} in {}ms (estimated: {}ms, diff: {}ms)",
-                current_depth, iteration_elapsed, estimated_time, iteration_elapsed as i64 - estimated_time as i64
+                "Completed depth {} in {}ms (estimated: {}ms, diff: {}ms), {} nodes, EBF {}",
+                current_depth,
+                iteration_elapsed,
+                estimated_time,
+                iteration_elapsed as i64 - estimated_time as i64,
+                iteration_nodes,
+                ebf.map_or("-".to_string(), |ebf| format!("{:.2}", ebf))
             );
 
             // V9: Early exit conditions for decided positions
-            // Early exit condition 1: Certain win
-            if best_score >= config.timing.certain_win_threshold {
-                info!("Certain win detected (score: {}), stopping search at depth {}",
-                      best_score, current_depth);
-                break;
+            // Early exit conditions 1 and 2: certain win or forced loss (calibrated win
+            // probability, or the raw score thresholds with [win_probability] disabled)
+            match win_probability::decided(best_score, config) {
+                Some(Decided::Win) => {
+                    info!("Certain win detected (score: {}, p(win) {:.3}), stopping search at depth {}",
+                          best_score, win_probability::probability(best_score, config), current_depth);
+                    break;
+                }
+                Some(Decided::Loss) => {
+                    info!("Forced loss detected (score: {}, p(win) {:.3}), stopping search at depth {}",
+                          best_score, win_probability::probability(best_score, config), current_depth);
+                    break;
+                }
+                None => {}
             }
 
-            // Early exit condition 2: Forced loss
-            if best_score <= config.timing.certain_loss_threshold {
-                info!("Forced loss detected (score: {}), stopping search at depth {}",
-                      best_score, current_depth);
-                break;
+            // Track stability of the best move and score for the next condition
+            if let Some((prev_move_idx, prev_score)) = previous_best {
+                shared.record_score_swing(prev_score, best_score);
+                let score_change = (best_score as i64 - prev_score as i64).unsigned_abs();
+                if prev_move_idx == best_move_idx && score_change <= config.timing.stability_score_margin as u64 {
+                    stable_iterations = stable_iterations.saturating_add(1);
+                } else {
+                    stable_iterations = 0;
+                }
             }
-
-            // Early exit condition 3: No improvement in last N iterations with low time remaining
-            if depth_since_improvement >= config.timing.no_improvement_tolerance
-                && remaining < effective_budget / 3 {
-                info!("No score improvement for {} iterations, conserving time at depth {}",
-                      depth_since_improvement, current_depth);
-                break;
+            previous_best = Some((best_move_idx, best_score));
+
+            // Early exit condition 3: result stable for N iterations and the next one would eat
+            // most of what is left; the unspent time is banked for later turns
+            if limits.fixed_depth.is_none() {
+                let remaining = effective_budget.saturating_sub(start_time.elapsed().as_millis() as u64);
+                let next_estimate = time_estimator.estimate(current_depth + 1, num_active_snakes);
+                if Self::stable_enough_to_stop(stable_iterations, next_estimate, remaining, config) {
+                    info!(
+                        "Best move stable for {} iterations, stopping at depth {} (next ~{}ms, {}ms left unspent)",
+                        stable_iterations, current_depth, next_estimate, remaining
+                    );
+                    break;
+                }
             }
 
-            // Track improvement for next iteration
-            if let Some(prev_score) = previous_best_score {
-                if best_score > prev_score {
-                    depth_since_improvement = 0;
-                } else {
-                    depth_since_improvement += 1;
+            // Node budget: deterministic stop independent of host speed
+            if let Some(max_nodes) = limits.max_nodes {
+                let nodes = shared.nodes();
+                if nodes >= max_nodes {
+                    info!("Stopping search: node budget exhausted ({} >= {}) at depth {}",
+                          nodes, max_nodes, current_depth);
+                    break;
                 }
             }
-            previous_best_score = Some(best_score);
 
             current_depth += 1;
         }
 
-        shared.search_complete.store(true, Ordering::Release);
+        shared.record_tt_stats(&tt);
+        for root_tt in &root_tts {
+            shared.record_tt_stats(root_tt);
+        }
+        let (best_move_idx, _, _) = shared.answer();
+        let max_plies = shared.current_depth.load(Ordering::Acquire) as usize * num_alive_snakes.max(1);
+        let pv_tt = root_tts.get(best_move_idx as usize).unwrap_or(&tt);
+        *shared.principal_variation.lock() =
+            Self::principal_variation(board, you, Self::index_to_direction(best_move_idx, config), pv_tt, max_plies, config);
+        if config.debug.explain {
+            Self::explain_root_moves(board, you, &tt, &root_tts, &shared, config);
+        }
+
+        shared.mark_complete();
 
         // Merge profiling data from all threads
         if simple_profiler::is_profiling_enabled() {
             simple_profiler::merge_thread_local();
         }
 
-        let (best_move_idx, best_score) = shared.get_best();
+        let (best_move_idx, best_score, _) = shared.answer();
         let (tt_entries, tt_capacity) = tt.stats();
         info!(
             "Search complete. Best move: {:?}, Score: {}, TT: {}/{} entries ({:.1}% full)",
@@ -1157,16 +2989,27 @@ impl Bot {
         );
     }
 
-    /// Determines the execution strategy based on game state and hardware
+    /// True if iterative deepening should stop on a stable result: the best move has held (with
+    /// its score within timing.stability_score_margin) for timing.no_improvement_tolerance
+    /// iterations and the next iteration is projected to need more than the remaining time
+    /// divided by timing.stability_time_factor
+    fn stable_enough_to_stop(stable_iterations: u8, next_estimate_ms: u64, remaining_ms: u64, config: &Config) -> bool {
+        stable_iterations >= config.timing.no_improvement_tolerance
+            && (remaining_ms as f64) < next_estimate_ms as f64 * config.timing.stability_time_factor
+    }
+
+    /// Determines the execution strategy from the game mode's engine, the game state and the
+    /// hardware
     fn determine_strategy(
+        mode: GameMode,
         num_snakes: usize,
         num_cpus: usize,
         config: &Config,
     ) -> ExecutionStrategy {
+        let duels = mode.strategy().engine == SearchEngine::DuelOrMaxN;
         match (num_snakes, num_cpus) {
             (n, cpus)
-                if n == config.strategy.min_snakes_for_1v1
-                    && cpus >= config.strategy.min_cpus_for_parallel =>
+                if duels && n == config.strategy.min_snakes_for_1v1 && cpus >= config.strategy.min_cpus_for_parallel =>
             {
                 ExecutionStrategy::Parallel1v1
             }
@@ -1177,29 +3020,46 @@ impl Bot {
         }
     }
 
+    /// True if `players` snakes are searched as a duel (paired alpha-beta): exactly `duel_size`
+    /// of them, in a game mode whose engine allows duels
+    fn pairs_players(players: usize, duel_size: usize, config: &Config) -> bool {
+        players == duel_size && config.game_mode.strategy().engine == SearchEngine::DuelOrMaxN
+    }
+
+    /// True if `players` alive snakes that are not a duel are searched with best-reply search
+    /// instead of MaxN (strategy.multiplayer_search; never in squad games)
+    fn uses_best_reply(players: usize, config: &Config) -> bool {
+        if config.game_mode.strategy().engine != SearchEngine::DuelOrMaxN
+            || Self::pairs_players(players, config.strategy.min_snakes_for_1v1, config)
+        {
+            return false;
+        }
+        match config.strategy.multiplayer_search.as_str() {
+            "brs" => true,
+            "auto" => players >= config.strategy.brs_min_snakes,
+            _ => false,
+        }
+    }
+
     /// Sequential search implementation (works on any hardware)
     fn sequential_search(
         board: &Board,
         you: &Battlesnake,
-        turn: i32,
         depth: u8,
-        shared: &Arc<SharedSearchState>,
-        config: &Config,
-        tt: &Arc<TranspositionTable>,
-        killers: &mut KillerMoveTable,
-        history: &mut HistoryTable,
         pv_move: Option<Direction>,
         alpha: i32,
         beta: i32,
+        ctx: &SearchContext,
     ) {
+        let SearchContext { config, killers, history, shared, .. } = *ctx;
         // Generate legal moves for our snake
-        let mut legal_moves = Self::generate_legal_moves(board, you, config);
+        let mut legal_moves = Self::root_moves(board, you, shared, config);
 
         if legal_moves.is_empty() {
             info!("No legal moves available - choosing least-bad fallback");
             // When trapped, try to pick a move that's at least in-bounds
             // Priority: any in-bounds move > out-of-bounds move
-            let fallback_move = Direction::all()
+            let fallback_move = Direction::ALL
                 .iter()
                 .find(|&&dir| {
                     let next = dir.apply(&you.body[0]);
@@ -1216,21 +3076,19 @@ impl Bot {
         }
 
         // Order moves for better alpha-beta pruning
-        // Priority: PV move > killer moves > history heuristic > remaining moves
-        legal_moves = order_moves(legal_moves, pv_move, killers, Some((history, &you.body[0])), depth, config);
+        // Priority: PV move > killer moves > SEE / history heuristic > remaining moves
+        let see = board.snake_index(&you.id).map(|idx| (board, idx));
+        order_moves(&mut legal_moves, pv_move, killers, Some((history, &you.body[0], true)), see, depth, config);
 
         info!("Evaluating {} legal moves sequentially (ordered by PV + killers)", legal_moves.len());
 
         // Determine if we should use 1v1 alpha-beta or multiplayer MaxN
-        let num_alive = board.snakes.iter().filter(|s| s.health > 0).count();
-        let use_alpha_beta = num_alive == config.strategy.min_snakes_for_1v1;
+        let num_alive = board.alive_snakes().count();
+        let use_alpha_beta = Self::pairs_players(num_alive, config.strategy.min_snakes_for_1v1, config);
+        let use_best_reply = Self::uses_best_reply(num_alive, config);
 
         let our_snake_id = &you.id;
-        let our_idx = board
-            .snakes
-            .iter()
-            .position(|s| &s.id == our_snake_id)
-            .unwrap_or(0);
+        let our_idx = board.snake_index(our_snake_id).unwrap_or(0);
 
         let mut best_score = i32::MIN;
         let mut best_wall_distance = i32::MIN; // Track wall distance of best move
@@ -1239,37 +3097,29 @@ impl Bot {
             let mut child_board = board.clone();
             Self::apply_move(&mut child_board, our_idx, mv, config);
 
-            let score = if use_alpha_beta {
+            // One ply down from root after applying move
+            let ply = Ply::new(depth.saturating_sub(1), 1);
+            let score = with_search_scratch(|scratch| if use_alpha_beta {
                 // Use alpha-beta for 1v1 with aspiration window
-                Self::alpha_beta_minimax(
-                    &child_board,
-                    our_snake_id,
-                    depth.saturating_sub(1),
-                    1,  // One ply down from root after applying move
-                    alpha,
-                    beta,
-                    false,
-                    config,
-                    tt,
-                    killers,
-                    history,
-                )
+                Self::alpha_beta_minimax(&child_board, ply, alpha, beta, false, ctx, scratch)
+            } else if use_best_reply {
+                // Best-reply search for crowded multiplayer boards
+                Self::best_reply_search(&child_board, ply, alpha, beta, false, ctx, scratch)
             } else {
                 // Use MaxN for multiplayer
-                let tuple = Self::maxn_search(
-                    &child_board,
-                    our_snake_id,
-                    turn,
-                    depth.saturating_sub(1),
-                    1, // One ply down from root
-                    our_idx,
-                    config,
-                    tt,
-                    killers,
-                    history,
-                );
+                let tuple = Self::maxn_search(&child_board, ply, our_idx, ctx, scratch);
+                if let (Some(leaf), false) = (&tuple.leaf, shared.is_stopped()) {
+                    shared.record_leaf_breakdown(depth, mv, leaf);
+                }
                 tuple.for_player(our_idx)
-            };
+            });
+            if shared.is_stopped() {
+                // Budget expired mid-move: the partial score is meaningless
+                info!("Sequential search aborted at depth {}", depth);
+                return;
+            }
+            let score = score.saturating_add(shared.root_bias_for(Self::direction_to_index(mv, config)));
+            shared.record_root_score(depth, mv, score);
 
             // Calculate wall distance for corner avoidance tie-breaking
             let next_pos = mv.apply(&you.body[0]);
@@ -1279,10 +3129,12 @@ impl Bot {
             // If scores are very close (within 1000 points), prefer moves away from walls
             // This prevents getting trapped in corners when multiple moves seem equally good
             let score_threshold = 1000;
-            let should_update = if score > best_score + score_threshold {
+            // Saturating: best_score starts at i32::MIN and won lines score near i32::MAX
+            let (low, high) = (best_score.saturating_sub(score_threshold), best_score.saturating_add(score_threshold));
+            let should_update = if score > high {
                 // Clearly better score
                 true
-            } else if score >= best_score - score_threshold && score <= best_score + score_threshold {
+            } else if score >= low && score <= high {
                 // Scores are similar - use wall distance as tie-breaker
                 wall_distance > best_wall_distance
             } else {
@@ -1308,11 +3160,12 @@ impl Bot {
     /// - Doesn't collide with snake bodies (excluding tails which will move)
     /// - Doesn't reverse into the neck
     /// - Avoids head-to-head collisions with equal or longer snakes (unless no other option)
-    pub fn generate_legal_moves(board: &Board, snake: &Battlesnake, config: &Config) -> Vec<Direction> {
+    pub fn generate_legal_moves(board: &Board, snake: &Battlesnake, config: &Config) -> MoveList {
         let _prof = simple_profiler::ProfileGuard::new("move_gen");
+        let mut moves = MoveList::new();
 
         if snake.health <= 0 || snake.body.is_empty() {
-            return vec![];
+            return moves;
         }
 
         let head = snake.body[0];
@@ -1322,51 +3175,52 @@ impl Bot {
             None
         };
 
-        // First, generate all moves that pass basic collision checks
-        let basic_legal_moves: Vec<Direction> = Direction::all()
-            .iter()
-            .filter(|&&dir| {
-                let next = dir.apply(&head);
+        let passes_allies = config.game_mode.squad_rules().is_some_and(|rules| rules.allow_body_collisions);
 
-                // Can't reverse onto neck
-                if let Some(n) = neck {
-                    if next == n {
-                        return false;
-                    }
-                }
+        // First, generate all moves that pass basic collision checks (kept on the stack)
+        let mut basic_legal_moves = [Direction::Up; 4];
+        let mut basic_count = 0;
+        for &dir in Direction::ALL.iter() {
+            let next = dir.apply(&head);
 
-                // Must stay in bounds
-                if Self::is_out_of_bounds(&next, board.width, board.height) {
-                    return false;
-                }
+            // Can't reverse onto neck
+            if neck == Some(next) {
+                continue;
+            }
 
-                // Can't collide with bodies (excluding tails which will move)
-                if Self::is_collision(&next, board, config.move_generation.body_tail_offset) {
-                    return false;
-                }
+            // Must stay in bounds
+            if Self::is_out_of_bounds(&next, board.width, board.height) {
+                continue;
+            }
 
-                true
-            })
-            .copied()
-            .collect();
+            // Hazard walls are as final as the edge of the board
+            if config.hazard_walls.as_ref().is_some_and(|walls| walls.is_wall(next)) {
+                continue;
+            }
+
+            // Can't collide with bodies (excluding tails which will move), but may pass
+            // through squad-mates where the squad rules allow it
+            if Self::is_collision(&next, board, config.move_generation.body_tail_offset, passes_allies.then_some(snake)) {
+                continue;
+            }
+
+            basic_legal_moves[basic_count] = dir;
+            basic_count += 1;
+        }
+        let basic_legal_moves = &basic_legal_moves[..basic_count];
 
         // Now filter out dangerous head-to-head positions
-        let safe_moves: Vec<Direction> = basic_legal_moves
-            .iter()
-            .filter(|&&dir| {
-                let next = dir.apply(&head);
-                !Self::is_dangerous_head_to_head(&next, snake, board)
-            })
-            .copied()
-            .collect();
+        moves.extend(basic_legal_moves.iter().copied().filter(|dir| {
+            let next = dir.apply(&head);
+            !Self::is_dangerous_head_to_head(&next, snake, board, passes_allies)
+        }));
 
         // If we have safe moves, use them. Otherwise, fall back to basic legal moves
         // (better to risk a head-to-head than to definitely die)
-        if !safe_moves.is_empty() {
-            safe_moves
-        } else {
-            basic_legal_moves
+        if moves.is_empty() {
+            moves.extend(basic_legal_moves.iter().copied());
         }
+        moves
     }
 
     /// Checks if a coordinate is out of bounds
@@ -1375,18 +3229,9 @@ impl Bot {
     }
 
     /// Checks if a coordinate collides with any snake body
-    fn is_collision(coord: &Coord, board: &Board, body_tail_offset: usize) -> bool {
-        for snake in &board.snakes {
-            if snake.health <= 0 {
-                continue;
-            }
-
-            let body_check_len = snake.body.len().saturating_sub(body_tail_offset);
-            if snake.body[..body_check_len].contains(coord) {
-                return true;
-            }
-        }
-        false
+    /// Bodies of `mover`'s squad-mates are ignored when a mover is given
+    fn is_collision(coord: &Coord, board: &Board, body_tail_offset: usize, mover: Option<&Battlesnake>) -> bool {
+        board.blocked(*coord, body_tail_offset, |idx| mover.is_some_and(|mover| mover.is_ally_of(&board.snakes[idx])))
     }
 
     /// Checks if moving to a position could result in a dangerous head-to-head collision
@@ -1396,10 +3241,16 @@ impl Bot {
     /// This handles two scenarios:
     /// 1. Direct collision: both snakes move to the exact same cell (e.g., converging on food)
     /// 2. Adjacent threat: opponent head is adjacent to our target position and could move there
-    fn is_dangerous_head_to_head(position: &Coord, our_snake: &Battlesnake, board: &Board) -> bool {
+    ///
+    /// Squad-mates are ignored when `passes_allies` (they cannot collide with us)
+    fn is_dangerous_head_to_head(position: &Coord, our_snake: &Battlesnake, board: &Board, passes_allies: bool) -> bool {
         for opponent in &board.snakes {
-            // Skip ourselves and dead snakes
-            if opponent.id == our_snake.id || opponent.health <= 0 || opponent.body.is_empty() {
+            // Skip ourselves, passable squad-mates and dead snakes
+            if opponent.id == our_snake.id
+                || (passes_allies && opponent.is_ally_of(our_snake))
+                || opponent.health <= 0
+                || opponent.body.is_empty()
+            {
                 continue;
             }
 
@@ -1414,7 +3265,7 @@ impl Bot {
 
             // Check if opponent could also move to the exact same target position
             // This is the key check for converging collisions (e.g., both going for food)
-            for dir in Direction::all() {
+            for dir in Direction::ALL {
                 let opp_next = dir.apply(&opp_head);
 
                 // Skip if opponent would be reversing onto their neck
@@ -1475,10 +3326,10 @@ impl Bot {
             .food
             .iter()
             .filter_map(|&food_pos| {
-                let dist = manhattan_distance(head, food_pos);
+                let dist = head.manhattan(food_pos);
                 if dist == 1 {
                     // Find which direction leads to this food
-                    for dir in Direction::all() {
+                    for dir in Direction::ALL {
                         if dir.apply(&head) == food_pos {
                             return Some((food_pos, dir));
                         }
@@ -1494,13 +3345,10 @@ impl Bot {
 
         // Check each adjacent food for safety
         // Prioritize by: (1) safety, (2) escape routes after eating
-        let our_idx = board
-            .snakes
-            .iter()
-            .position(|s| s.id == you.id)
-            .unwrap_or(0);
+        let our_idx = board.snake_index(&you.id).unwrap_or(0);
 
-        let active_snakes = Self::determine_active_snakes(board, &you.id, turn, 2, config);
+        let active_snakes = Self::determine_active_snakes(board, &you.id, turn, 2, None, config);
+        let paths = Self::bounded_food_paths(board, &active_snakes, config);
 
         for (food_pos, dir) in adjacent_food {
             // Check if this direction is legal
@@ -1510,7 +3358,7 @@ impl Bot {
             }
 
             // Check if food is actually safe (not a trap)
-            if !Self::is_food_actually_safe(board, food_pos, our_idx, &active_snakes, config) {
+            if !Self::is_food_actually_safe(board, food_pos, our_idx, &active_snakes, paths.as_ref(), config) {
                 continue;
             }
 
@@ -1553,13 +3401,16 @@ impl Bot {
 
     /// Applies a move to a specific snake in the game state
     /// Updates snake position, handles food consumption, and decreases health
-    fn apply_move(board: &mut Board, snake_idx: usize, dir: Direction, config: &Config) {
+    /// Growth follows the official rules: the tail always moves, and eating then duplicates
+    /// the new tail segment, which stays stacked (and blocks its cell) for one more turn
+    pub fn apply_move(board: &mut Board, snake_idx: usize, dir: Direction, config: &Config) {
         let _prof = simple_profiler::ProfileGuard::new("apply_move");
 
         if snake_idx >= board.snakes.len() {
             return;
         }
 
+        board.clear_occupancy();
         let snake = &mut board.snakes[snake_idx];
         if snake.health <= 0 || snake.body.is_empty() {
             return;
@@ -1568,24 +3419,33 @@ impl Bot {
         // Calculate new head position
         let new_head = dir.apply(&snake.body[0]);
 
-        // Move head to new position
+        // Move head to new position; the tail moves too, even on a turn the snake grows
         snake.body.insert(0, new_head);
         snake.head = new_head;
+        let tail = snake.body.pop();
 
         // Check if food was eaten
         let ate_food = board.food.contains(&new_head);
-        if ate_food {
+        let strategy = config.game_mode.strategy();
+        if strategy.food == FoodModel::Constrictor {
+            // Constrictor: no food, every snake grows every turn and never starves
+            Self::stack_tail(snake);
+        } else if ate_food {
             // Remove food from board
             board.food.retain(|&f| f != new_head);
             // Restore health
             snake.health = config.game_rules.health_on_food as i32;
-            // Grow snake (don't remove tail)
-            snake.length += 1;
+            // Grow snake: the new tail segment is duplicated
+            Self::stack_tail(snake);
         } else {
-            // Remove tail (snake doesn't grow)
-            snake.body.pop();
             // Decrease health
             snake.health = snake.health.saturating_sub(config.game_rules.health_loss_per_turn as i32);
+            // Snail mode: the vacated cell gets a hazard stack as high as the snake, plus the
+            // one advance_game_state's decay takes this same turn
+            if let Some(tail) = tail.filter(|_| strategy.hazards == HazardModel::Trails) {
+                let stacks = snake.length as usize + 1;
+                snail::lay_trail(&mut board.hazards, tail, stacks);
+            }
         }
 
         // Mark snake as dead if health reaches zero
@@ -1594,96 +3454,125 @@ impl Bot {
         }
     }
 
-    /// Advances the game state by one turn after all snakes have moved
-    /// Handles head-to-head collisions and body collisions
-    fn advance_game_state(board: &mut Board) {
-        // Detect head-to-head collisions
-        let mut head_positions: HashMap<Coord, Vec<usize>> = HashMap::new();
-
-        for (idx, snake) in board.snakes.iter().enumerate() {
-            if snake.health > 0 && !snake.body.is_empty() {
-                head_positions
-                    .entry(snake.body[0])
-                    .or_insert_with(Vec::new)
-                    .push(idx);
-            }
+    /// Grows a snake by one segment the way the engine does: a copy of its tail
+    fn stack_tail(snake: &mut Battlesnake) {
+        if let Some(&tail) = snake.body.last() {
+            snake.body.push(tail);
         }
+        snake.length += 1;
+    }
 
-        // Process head-to-head collisions
-        for (_, indices) in head_positions.iter() {
-            if indices.len() > 1 {
-                // Multiple snakes at same position
-                let max_length = indices
-                    .iter()
-                    .map(|&i| board.snakes[i].length)
-                    .max()
-                    .unwrap_or(0);
+    /// Simulates a full turn with the search's own model (apply_moves, then
+    /// advance_full_turn). Snakes without an entry in `moves` continue straight.
+    /// Dead snakes stay on the board with health 0. Used by the rules oracle tests.
+    pub fn simulate_turn(board: &mut Board, moves: &HashMap<String, Direction>, config: &Config) {
+        let turn: Vec<(usize, Direction)> = board
+            .snakes
+            .iter()
+            .enumerate()
+            .map(|(idx, snake)| (idx, moves.get(&snake.id).copied().unwrap_or_else(|| rules::default_move(&snake.body))))
+            .collect();
+        Self::apply_moves(board, &turn, config);
+        Self::advance_full_turn(board, config);
+    }
 
-                // Count how many snakes have max length
-                let max_count = indices
-                    .iter()
-                    .filter(|&&i| board.snakes[i].length == max_length)
-                    .count();
-
-                // Kill snakes based on length comparison
-                for &idx in indices {
-                    if board.snakes[idx].length < max_length {
-                        // Shorter snake dies
-                        board.snakes[idx].health = 0;
-                    } else if max_count > 1 {
-                        // Equal length: all die
-                        board.snakes[idx].health = 0;
-                    }
-                }
-            }
-        }
+    /// Applies the moves of one turn as if they happened at once: every snake whose head
+    /// lands on a food eats it, even when another snake got there first (a head-to-head on
+    /// food compares the grown lengths, as in the engine)
+    fn apply_moves(board: &mut Board, moves: &[(usize, Direction)], config: &Config) {
+        let food = board.food.clone();
+        for &(idx, dir) in moves {
+            board.food.clone_from(&food);
+            Self::apply_move(board, idx, dir, config);
+        }
+        let heads: Vec<Coord> = moves.iter().filter_map(|&(idx, _)| board.snakes.get(idx)?.body.first().copied()).collect();
+        board.food = food;
+        board.food.retain(|f| !heads.contains(f));
+    }
 
-        // Check for body collisions (snake head hitting any body segment)
-        let mut collision_snakes = Vec::new();
-        for (idx, snake) in board.snakes.iter().enumerate() {
-            if snake.health <= 0 {
-                continue;
-            }
+    /// Advances the game state after one snake's move (an alpha-beta ply)
+    /// The snakes still to move will pull their tails away, so the last segment of every
+    /// body does not block
+    fn advance_game_state(board: &mut Board, config: &Config) {
+        Self::resolve_eliminations(board, config, false);
+    }
 
-            let head = snake.body[0];
+    /// Advances the game state after every snake has moved (simulate_turn, max-n and the
+    /// one-ply safety check): whole bodies block, tails included, as in the engine
+    fn advance_full_turn(board: &mut Board, config: &Config) {
+        Self::resolve_eliminations(board, config, true);
+    }
 
-            // Check collision with all snake bodies (including own)
-            for (other_idx, other_snake) in board.snakes.iter().enumerate() {
-                if other_snake.health <= 0 {
-                    continue;
-                }
+    /// Eliminations in the engine's order: out of health and out of bounds first, then self,
+    /// body and head-to-head collisions among the survivors, all decided against the same
+    /// board and applied together (and the squad rules, if any)
+    fn resolve_eliminations(board: &mut Board, config: &Config, tails_block: bool) {
+        board.clear_occupancy();
+        if config.game_mode.strategy().hazards == HazardModel::Trails {
+            snail::decay_trails(&mut board.hazards);
+        }
 
-                // Check against body segments (excluding the tail which just moved)
-                let check_len = if idx == other_idx {
-                    // Own body: check all except head and tail
-                    if other_snake.body.len() > 2 {
-                        other_snake.body.len() - 1
-                    } else {
-                        0
-                    }
-                } else {
-                    // Other snake: check all except tail
-                    other_snake.body.len().saturating_sub(1)
-                };
+        let squad_rules = config.game_mode.squad_rules();
+        let alive_before: Vec<bool> = match squad_rules {
+            Some(_) => board.snakes.iter().map(|s| s.health > 0).collect(),
+            None => Vec::new(),
+        };
 
-                if other_snake.body[1..check_len.min(other_snake.body.len())]
-                    .contains(&head)
-                {
-                    collision_snakes.push(idx);
-                    break;
+        // Snakes that left the board are eliminated before any collision is considered
+        let (width, height) = (board.width, board.height as i32);
+        for snake in board.snakes.iter_mut() {
+            if let Some(head) = snake.body.first() {
+                if head.x < 0 || head.y < 0 || head.x >= width || head.y >= height {
+                    snake.health = 0;
                 }
             }
         }
 
-        // Mark collided snakes as dead
-        for idx in collision_snakes {
+        // Collisions: a snake dies on any segment behind a head (its own included) and on a
+        // rival head at least as long (squad-mates pass each other when the squad rules allow
+        // it). Snakes that die here still block the others.
+        let passes_allies = squad_rules.is_some_and(|rules| rules.allow_body_collisions);
+        let alive: Vec<usize> = (0..board.snakes.len()).filter(|&idx| board.snakes[idx].health > 0 && !board.snakes[idx].body.is_empty()).collect();
+        let collided: Vec<usize> = alive
+            .iter()
+            .copied()
+            .filter(|&idx| {
+                let snake = &board.snakes[idx];
+                let head = snake.body[0];
+                alive.iter().any(|&other_idx| {
+                    let other = &board.snakes[other_idx];
+                    if other_idx != idx && passes_allies && snake.is_ally_of(other) {
+                        return false;
+                    }
+                    let end = if tails_block { other.body.len() } else { other.body.len() - 1 };
+                    // get(): bodies shorter than 2 segments have nothing behind the head (and must not panic)
+                    let body_hit = other.body.get(1..end.max(1)).is_some_and(|segments| segments.contains(&head));
+                    body_hit || (other_idx != idx && other.body[0] == head && other.length >= snake.length)
+                })
+            })
+            .collect();
+        for idx in collided {
             board.snakes[idx].health = 0;
         }
+
+        if let Some(rules) = squad_rules {
+            if rules.shared_elimination {
+                let fallen: Vec<usize> =
+                    (0..board.snakes.len()).filter(|&idx| alive_before[idx] && board.snakes[idx].health <= 0).collect();
+                for (idx, _) in squad::shared_eliminations(board, &fallen) {
+                    board.snakes[idx].health = 0;
+                }
+            }
+            squad::share_attributes(board, &rules);
+        }
+
+        // Eliminated bodies leave the board (the snakes keep their index)
+        board.remove_eliminated();
     }
 
     /// Checks if the game state is terminal (game over)
     fn is_terminal(board: &Board, our_snake_id: &str, config: &Config) -> bool {
-        let alive_count = board.snakes.iter().filter(|s| s.health > 0).count();
+        let alive_count = board.alive_snakes().count();
 
         // Terminal if only one or zero snakes alive
         if alive_count <= config.game_rules.terminal_state_threshold {
@@ -1691,7 +3580,7 @@ impl Bot {
         }
 
         // Terminal if our snake is dead
-        if let Some(our_snake) = board.snakes.iter().find(|s| s.id == our_snake_id) {
+        if let Some(our_snake) = board.our_snake(our_snake_id) {
             if our_snake.health <= 0 {
                 return true;
             }
@@ -1703,289 +3592,149 @@ impl Bot {
     /// Flood fill BFS to count reachable cells from a starting position
     /// Accounts for snake bodies that will move over time
     /// Returns the number of cells reachable
-    ///
-    /// # Performance Optimization
-    /// If `early_exit_threshold` is provided, the search terminates early once
-    /// that many cells are found. This is useful when we only need to know if
-    /// "enough" space exists (e.g., checking if opponent is trapped).
-    fn flood_fill_bfs(
+    /// Root moves that lose whatever the search finds, with the reason for each
+    /// A move is provably losing if it dies against every combination of opponent replies
+    /// (see one_ply_deaths) or if the region it enters cannot hold our body. Returns nothing
+    /// if pruning is disabled or if every legal move is losing (then the search decides).
+    pub fn prune_root_moves(
         board: &Board,
-        start: Coord,
-        _snake_idx: usize,
-        early_exit_threshold: Option<usize>,
-    ) -> usize {
-        let _prof = simple_profiler::ProfileGuard::new("flood_fill");
-
-        // Pre-build obstacle map for O(1) lookups (huge performance improvement)
-        // Maps each occupied cell to the number of turns until it becomes free
-        let mut obstacles: HashMap<Coord, usize> = HashMap::new();
-        for snake in &board.snakes {
-            if snake.health <= 0 {
-                continue;
-            }
-            for (seg_idx, &segment) in snake.body.iter().enumerate() {
-                let segments_from_tail = snake.body.len() - seg_idx;
-                obstacles.insert(segment, segments_from_tail);
-            }
+        you: &Battlesnake,
+        legal_moves: &[Direction],
+        config: &Config,
+    ) -> Vec<(Direction, RootExclusion)> {
+        if !config.root_pruning.enabled || you.body.is_empty() {
+            return Vec::new();
         }
+        let Some(our_idx) = board.snake_index(&you.id) else { return Vec::new() };
+        let needed = you.length.max(1) as usize;
 
-        let mut visited = HashSet::new();
-        let mut queue = VecDeque::new();
-
-        queue.push_back((start, 0)); // (position, turns_elapsed)
-        visited.insert(start);
-
-        while let Some((pos, turns)) = queue.pop_front() {
-            // Early exit optimization: if we've found enough space, stop searching
-            if let Some(threshold) = early_exit_threshold {
-                if visited.len() >= threshold {
-                    return visited.len();
-                }
-            }
-
-            for dir in Direction::all().iter() {
-                let next = dir.apply(&pos);
-
-                // Check bounds
-                if next.x < 0
-                    || next.x >= board.width
-                    || next.y < 0
-                    || next.y >= board.height as i32
-                {
-                    continue;
-                }
-
-                if visited.contains(&next) {
-                    continue;
-                }
-
-                // Check if blocked using pre-built obstacle map (O(1) instead of O(snakes × length))
-                if let Some(&segments_from_tail) = obstacles.get(&next) {
-                    if segments_from_tail > turns {
-                        continue; // Still blocked
-                    }
+        let excluded: Vec<(Direction, RootExclusion)> = legal_moves
+            .iter()
+            .filter_map(|&mv| {
+                let (deaths, replies) = Self::one_ply_deaths(board, our_idx, mv, config);
+                if replies > 0 && deaths == replies {
+                    return Some((mv, RootExclusion::ForcedDeath { replies }));
                 }
+                let mut child_board = board.clone();
+                Self::apply_move(&mut child_board, our_idx, mv, config);
+                let next = mv.apply(&you.body[0]);
+                let space = Self::flood_fill_bfs(&child_board, next, our_idx, Some(needed), config.hazard_walls.as_deref());
+                (space < needed).then_some((mv, RootExclusion::NoRoom { space, needed }))
+            })
+            .collect();
 
-                visited.insert(next);
-                queue.push_back((next, turns + 1));
-            }
+        if excluded.len() == legal_moves.len() {
+            return Vec::new();
         }
+        excluded
+    }
 
-        visited.len()
+    /// Legal root moves minus the ones pruned before the search
+    fn root_moves(board: &Board, you: &Battlesnake, shared: &SharedSearchState, config: &Config) -> MoveList {
+        let mut moves = Self::generate_legal_moves(board, you, config);
+        moves.retain(|&mv| !shared.is_root_excluded(Self::direction_to_index(mv, config)));
+        moves
     }
 
-    /// Enhanced flood fill that returns distance information for entrapment detection
-    /// Returns (total_cells, distance_map) where distance_map tracks turns to reach each cell
-    fn flood_fill_with_distances(
+    ///
+    /// # Performance Optimization
+    /// If `early_exit_threshold` is provided, the search terminates early once
+    /// that many cells are found. This is useful when we only need to know if
+    /// "enough" space exists (e.g., checking if opponent is trapped).
+    ///
+    /// Hazard walls (if any) block their cells for good.
+    pub fn flood_fill_bfs(
         board: &Board,
         start: Coord,
         _snake_idx: usize,
-    ) -> (usize, HashMap<Coord, usize>) {
-        let _prof = simple_profiler::ProfileGuard::new("flood_fill_with_distances");
-
-        // Pre-build obstacle map for O(1) lookups
-        let mut obstacles: HashMap<Coord, usize> = HashMap::new();
-        for snake in &board.snakes {
-            if snake.health <= 0 {
-                continue;
-            }
-            for (seg_idx, &segment) in snake.body.iter().enumerate() {
-                let segments_from_tail = snake.body.len() - seg_idx;
-                obstacles.insert(segment, segments_from_tail);
-            }
-        }
-
-        let mut distance_map = HashMap::new();
-        let mut queue = VecDeque::new();
-
-        queue.push_back((start, 0)); // (position, turns_elapsed)
-        distance_map.insert(start, 0);
-
-        while let Some((pos, turns)) = queue.pop_front() {
-            for dir in Direction::all().iter() {
-                let next = dir.apply(&pos);
-
-                // Check bounds
-                if next.x < 0
-                    || next.x >= board.width
-                    || next.y < 0
-                    || next.y >= board.height as i32
-                {
-                    continue;
-                }
-
-                if distance_map.contains_key(&next) {
-                    continue;
-                }
-
-                // Check if blocked using pre-built obstacle map
-                if let Some(&segments_from_tail) = obstacles.get(&next) {
-                    if segments_from_tail > turns {
-                        continue; // Still blocked
-                    }
-                }
-
-                distance_map.insert(next, turns + 1);
-                queue.push_back((next, turns + 1));
-            }
-        }
-
-        let total = distance_map.len();
-        (total, distance_map)
-    }
-
-    /// Checks if a position will be blocked at a future turn
-    /// Accounts for snake body segments moving away over time
-    fn is_position_blocked_at_time(
-        board: &Board,
-        pos: Coord,
-        turns_future: usize,
-        _checking_snake: usize,
-    ) -> bool {
-        for snake in &board.snakes {
-            if snake.health <= 0 {
-                continue;
-            }
+        early_exit_threshold: Option<usize>,
+        walls: Option<&HazardWalls>,
+    ) -> usize {
+        let _prof = simple_profiler::ProfileGuard::new("flood_fill");
 
-            for (seg_idx, &segment) in snake.body.iter().enumerate() {
-                if segment == pos {
-                    // Will this segment have moved away?
-                    let segments_from_tail = snake.body.len() - seg_idx;
-                    if segments_from_tail > turns_future {
-                        return true; // Still occupied
-                    }
-                }
+        with_flood_grid(board.width, board.height as i32, |grid| {
+            if let Some(walls) = walls {
+                walls.block(grid);
             }
-        }
-        false
+            let queue = Self::time_aware_fill(grid, board, start, early_exit_threshold);
+            let reachable = queue.len();
+            grid.restore_queue(queue);
+            reachable
+        })
     }
 
-    /// Adversarial flood fill - simultaneous BFS from all snake heads
-    /// Returns map of which snake controls each cell (longer snakes win ties)
-    ///
-    /// If active_snakes is empty, processes all snakes.
-    /// Otherwise, only processes snakes in the provided list (IDAPOS optimization).
-    fn adversarial_flood_fill(board: &Board, active_snakes: &[usize]) -> Vec<Option<usize>> {
-        let _prof = simple_profiler::ProfileGuard::new("adversarial_flood_fill");
-
-        let size = (board.width * board.height as i32) as usize;
-        let mut control_map: Vec<Option<usize>> = vec![None; size];
-        let mut distance_map: Vec<Option<usize>> = vec![None; size];
-
-        // Determine which snakes to process
-        let process_all = active_snakes.is_empty();
-
-        // Helper to convert Coord to flat array index
-        let coord_to_idx = |c: &Coord| (c.y * board.width + c.x) as usize;
-
-        // Mark snake bodies as obstacles controlled by their owner
-        if process_all {
-            for (idx, snake) in board.snakes.iter().enumerate() {
-                if snake.health <= 0 {
-                    continue;
-                }
-                for &seg in &snake.body {
-                    control_map[coord_to_idx(&seg)] = Some(idx);
-                }
-            }
-        } else {
-            for &idx in active_snakes {
-                if idx >= board.snakes.len() {
-                    continue;
-                }
-                let snake = &board.snakes[idx];
-                if snake.health <= 0 {
-                    continue;
-                }
-                for &seg in &snake.body {
-                    control_map[coord_to_idx(&seg)] = Some(idx);
-                }
+    /// Time-aware BFS shared by the flood fills, on this thread's reusable FloodGrid
+    /// Body segments block a cell until they have moved away (turns >= segments from tail).
+    /// Returns the BFS queue, which holds every reached cell with the turn it was reached;
+    /// hand it back with grid.restore_queue() to keep its allocation.
+    fn time_aware_fill(
+        grid: &mut FloodGrid,
+        board: &Board,
+        start: Coord,
+        early_exit_threshold: Option<usize>,
+    ) -> Vec<(Coord, u16)> {
+        // Obstacle grid: each occupied cell with the number of turns until it becomes free
+        for snake in &board.snakes {
+            if snake.health <= 0 {
+                continue;
             }
-        }
-
-        // Sort snakes by length (longer snakes win ties)
-        let mut snakes_sorted: Vec<(usize, &Battlesnake)> = if process_all {
-            board.snakes.iter().enumerate().collect()
-        } else {
-            active_snakes
-                .iter()
-                .filter_map(|&idx| {
-                    if idx < board.snakes.len() {
-                        Some((idx, &board.snakes[idx]))
-                    } else {
-                        None
-                    }
-                })
-                .collect()
-        };
-        snakes_sorted.sort_by_key(|(_, s)| std::cmp::Reverse(s.length));
-
-        // Simultaneous BFS from all heads
-        let mut queue = VecDeque::new();
-        for (idx, snake) in snakes_sorted.iter() {
-            if snake.health > 0 && !snake.body.is_empty() {
-                let head_idx = coord_to_idx(&snake.body[0]);
-                queue.push_back((snake.body[0], *idx, 0));
-                distance_map[head_idx] = Some(0);
+            // Tail first, so a stacked tail keeps the turns of its head-most copy
+            for (seg_idx, &segment) in snake.body.iter().enumerate().rev() {
+                grid.block(segment, (snake.body.len() - seg_idx) as u16);
             }
         }
 
-        while let Some((pos, owner, dist)) = queue.pop_front() {
-            let pos_idx = coord_to_idx(&pos);
+        let mut queue = grid.take_queue();
+        queue.push((start, 0)); // (position, turns_elapsed)
+        grid.reach(start, 0);
 
-            // Skip if already claimed by another snake at same or closer distance
-            if let Some(existing_dist) = distance_map[pos_idx] {
-                if existing_dist < dist {
-                    continue;
+        let mut next_idx = 0;
+        while next_idx < queue.len() {
+            // Early exit optimization: if we've found enough space, stop searching
+            if let Some(threshold) = early_exit_threshold {
+                if queue.len() >= threshold {
+                    break;
                 }
             }
 
-            // Claim cell if not already controlled
-            if control_map[pos_idx].is_none() {
-                control_map[pos_idx] = Some(owner);
-            }
+            let (pos, turns) = queue[next_idx];
+            next_idx += 1;
 
-            for dir in Direction::all().iter() {
-                let next = dir.apply(&pos);
+            for next in pos.neighbors() {
 
-                if next.x < 0
-                    || next.x >= board.width
-                    || next.y < 0
-                    || next.y >= board.height as i32
-                {
+                // Out of bounds, already reached, or still blocked by a body segment
+                if grid.is_reached(next) || grid.blocked_at(next, turns) {
                     continue;
                 }
 
-                let next_idx = coord_to_idx(&next);
-                let next_dist = dist + 1;
-
-                // Only explore if we can reach it faster (not equal distance - prevents re-exploration)
-                let should_explore = match distance_map[next_idx] {
-                    Some(existing_dist) => next_dist < existing_dist,
-                    None => true,
-                };
-
-                if should_explore && control_map[next_idx].is_none() {
-                    distance_map[next_idx] = Some(next_dist);
-                    queue.push_back((next, owner, next_dist));
-                }
+                grid.reach(next, turns + 1);
+                queue.push((next, turns + 1));
             }
         }
 
-        control_map
+        queue
     }
 
-    /// Helper to compute control score from pre-computed map
-    fn compute_control_score_from_map(
-        control_map: &[Option<usize>],
+    /// Checks if a position will be blocked at a future turn
+    /// Accounts for snake body segments moving away over time
+    fn is_position_blocked_at_time(
+        board: &Board,
+        pos: Coord,
+        turns_future: usize,
+        _checking_snake: usize,
+    ) -> bool {
+        // Occupied unless the segment on `pos` is among the last `turns_future` (moved away)
+        board.blocked(pos, turns_future, |_| false)
+    }
+
+    /// Helper to compute control score from a pre-computed (possibly cached) territory summary
+    pub(crate) fn compute_control_score_from_summary(
+        summary: &ControlSummary,
         snake_idx: usize,
         config: &Config,
     ) -> i32 {
-        let our_cells = control_map
-            .iter()
-            .filter(|cell| cell.map_or(false, |owner| owner == snake_idx))
-            .count();
-        let total_free = control_map.iter().filter(|cell| cell.is_some()).count();
+        let our_cells = summary.owned.get(snake_idx).copied().unwrap_or(0);
+        let total_free = summary.claimed;
 
         if total_free == 0 {
             return 0;
@@ -1994,13 +3743,52 @@ impl Bot {
         ((our_cells as f32 / total_free as f32) * config.scores.territory_scale_factor) as i32
     }
 
+    /// Territory of a snake's squad-mates, counted for it at squad.ally_territory_weight
+    /// (0 outside squad games)
+    pub(crate) fn compute_ally_control_score(board: &Board, summary: &ControlSummary, snake_idx: usize, config: &Config) -> i32 {
+        if config.game_mode.squad_rules().is_none() || summary.claimed == 0 {
+            return 0;
+        }
+        let snake = &board.snakes[snake_idx];
+        let ally_cells: usize = board
+            .snakes
+            .iter()
+            .enumerate()
+            .filter(|(_, other)| other.is_ally_of(snake))
+            .map(|(idx, _)| summary.owned.get(idx).copied().unwrap_or(0))
+            .sum();
+        let share = ally_cells as f32 / summary.claimed as f32;
+        (share * config.scores.territory_scale_factor * config.squad.ally_territory_weight) as i32
+    }
+
+    /// Distance from snake `snake_idx`'s head to a food: the path distance when `paths` has one
+    /// (food_paths.enabled), else through the corridors on a hazard-wall map, else Manhattan
+    fn food_distance(board: &Board, snake_idx: usize, food: Coord, paths: Option<&FoodPaths>, config: &Config) -> i32 {
+        let from = board.snakes[snake_idx].body[0];
+        paths
+            .filter(|_| config.food_paths.enabled)
+            .and_then(|paths| paths.distance(snake_idx, food))
+            .or_else(|| config.hazard_walls.as_ref().and_then(|walls| walls.food_distance(from, food)))
+            .unwrap_or_else(|| from.manhattan(food))
+    }
+
+    /// Food paths for checks that run outside the evaluation: a field bounded to
+    /// food_paths.max_bfs_depth turns (None if food paths are disabled)
+    fn bounded_food_paths(board: &Board, sources: &[usize], config: &Config) -> Option<FoodPaths> {
+        config.food_paths.enabled.then(|| {
+            DistanceField::build_within(board, sources, config.hazard_walls.as_deref(), Some(config.food_paths.max_bfs_depth))
+                .food_paths(board)
+        })
+    }
+
     /// Computes health and food score for a snake
     /// Returns higher score for closer food when health is low
     /// Adds extra urgency when in health disadvantage vs opponents
-    fn compute_health_score(
+    pub(crate) fn compute_health_score(
         board: &Board,
         snake_idx: usize,
         active_snakes: &[usize],
+        paths: Option<&FoodPaths>,
         config: &Config,
     ) -> i32 {
         if snake_idx >= board.snakes.len() {
@@ -2024,7 +3812,7 @@ impl Bot {
         let nearest_food_dist = board
             .food
             .iter()
-            .map(|&food| manhattan_distance(head, food))
+            .map(|&food| Self::food_distance(board, snake_idx, food, paths, config))
             .min()
             .unwrap_or(config.scores.default_food_distance);
 
@@ -2043,12 +3831,12 @@ impl Bot {
             let nearest_food = board
                 .food
                 .iter()
-                .min_by_key(|&food| manhattan_distance(head, *food))
+                .min_by_key(|&food| Self::food_distance(board, snake_idx, *food, paths, config))
                 .copied();
 
             // V8: Use smarter food safety check that predicts post-eating traps
             let is_food_safe = if let Some(food_pos) = nearest_food {
-                Self::is_food_actually_safe(board, food_pos, snake_idx, active_snakes, config)
+                Self::is_food_actually_safe(board, food_pos, snake_idx, active_snakes, paths, config)
             } else {
                 false
             };
@@ -2108,7 +3896,7 @@ impl Bot {
                         if opp.health <= 0 || opp.body.is_empty() {
                             return None;
                         }
-                        nearest_food.map(|f| manhattan_distance(opp.body[0], f))
+                        nearest_food.map(|f| Self::food_distance(board, opp_idx, f, paths, config))
                     })
                     .min()
                     .unwrap_or(999);
@@ -2156,7 +3944,7 @@ impl Bot {
                         if opp.health <= 0 || opp.body.is_empty() {
                             return None;
                         }
-                        nearest_food.map(|f| manhattan_distance(opp.body[0], f))
+                        nearest_food.map(|f| Self::food_distance(board, opp_idx, f, paths, config))
                     })
                     .min()
                     .unwrap_or(999);
@@ -2213,7 +4001,7 @@ impl Bot {
                     let center_x = (board.width / 2) as i32;
                     let center_y = (board.height / 2) as i32;
                     let center = Coord { x: center_x, y: center_y };
-                    let dist_from_center = manhattan_distance(food_pos, center);
+                    let dist_from_center = food_pos.manhattan(center);
 
                     let safe_food_bonus = if dist_from_center <= config.scores.safe_food_center_threshold {
                         config.scores.safe_food_bonus
@@ -2264,7 +4052,7 @@ impl Bot {
                     return None;
                 }
                 // Only consider opponents within threat range
-                let dist = manhattan_distance(head, s.body[0]);
+                let dist = head.manhattan(s.body[0]);
                 if dist <= config.scores.health_threat_distance {
                     Some(s.health)
                 } else {
@@ -2294,7 +4082,7 @@ impl Bot {
     fn is_opponent_threatening(
         opponent_idx: usize,
         our_head: Coord,
-        nearest_food: Option<Coord>,
+        nearest_food: Option<(Coord, i32)>,
         board: &Board,
         config: &Config,
     ) -> bool {
@@ -2308,11 +4096,10 @@ impl Bot {
         }
 
         let opp_head = opponent.body[0];
-        let head_distance = manhattan_distance(our_head, opp_head);
+        let head_distance = our_head.manhattan(opp_head);
 
         // Opponent is threatening if head is close enough to actively trap us
-        let threat_distance = if let Some(food) = nearest_food {
-            let food_dist = manhattan_distance(our_head, food);
+        let threat_distance = if let Some((_, food_dist)) = nearest_food {
             // Opponent threatening if they can reach food area before/with us
             // Use adversarial_body_threat_buffer from config
             (food_dist + config.scores.adversarial_body_threat_buffer).min(6)  // Cap at 6 to avoid very distant snakes
@@ -2326,9 +4113,10 @@ impl Bot {
     /// Computes space control score - how many cells are reachable
     /// Penalizes cramped positions that could lead to being trapped
     /// Uses IDAPOS-filtered active_snakes list for adversarial entrapment detection
-    fn compute_space_score(
+    pub(crate) fn compute_space_score(
         board: &Board,
         snake_idx: usize,
+        field: &FieldSummary,
         active_snakes: &[usize],
         config: &Config,
     ) -> i32 {
@@ -2343,8 +4131,9 @@ impl Bot {
                 * config.scores.space_shortage_penalty;
         }
 
-        // Get reachable cells with distance information
-        let (reachable, distance_map) = Self::flood_fill_with_distances(board, snake.body[0], snake_idx);
+        // Reachable cells and cells within the nearby radius, both from the distance field
+        let reachable = field.reachable.get(snake_idx).copied().flatten().unwrap_or(0);
+        let nearby_cells = field.nearby.get(snake_idx).copied().unwrap_or(0);
         let required = snake.length as usize + config.scores.space_safety_margin;
 
         if reachable < required {
@@ -2353,8 +4142,6 @@ impl Bot {
 
         // Detect tight spaces / narrow corridors (entrapment risk)
         // If most cells are far away, we're in a narrow corridor that could trap us
-        let nearby_threshold = (snake.length.min(config.scores.entrapment_nearby_threshold as i32)) as usize;
-        let nearby_cells = distance_map.iter().filter(|(_, &dist)| dist <= nearby_threshold).count();
         let compactness_ratio = nearby_cells as f32 / reachable as f32;
 
         // Penalty for narrow spaces based on compactness ratio thresholds
@@ -2375,6 +4162,7 @@ impl Bot {
             snake_idx,
             reachable,
             active_snakes,
+            &field.food,
             config
         );
 
@@ -2389,6 +4177,7 @@ impl Bot {
         our_idx: usize,
         _our_current_space: usize,
         active_snakes: &[usize],
+        paths: &FoodPaths,
         config: &Config,
     ) -> i32 {
         if our_idx >= board.snakes.len() {
@@ -2403,13 +4192,9 @@ impl Bot {
         let our_head = our_snake.body[0];
 
         // V10: Find nearest food for threat assessment
-        let nearest_food = if !board.food.is_empty() {
-            board.food.iter()
-                .min_by_key(|&&food| manhattan_distance(our_head, food))
-                .copied()
-        } else {
-            None
-        };
+        let nearest_food = board.food.iter()
+            .map(|&food| (food, Self::food_distance(board, our_idx, food, Some(paths), config)))
+            .min_by_key(|&(_, dist)| dist);
 
         let locality_threshold = config.scores.adversarial_entrapment_distance;
         let mut max_penalty = 0;
@@ -2432,7 +4217,7 @@ impl Bot {
             }
 
             // Check if opponent is within entrapment distance
-            let distance = manhattan_distance(our_head, opponent.body[0]);
+            let distance = our_head.manhattan(opponent.body[0]);
             if distance > locality_threshold {
                 continue; // Snake too far away to pose entrapment threat
             }
@@ -2456,25 +4241,14 @@ impl Bot {
         max_penalty
     }
 
-    /// Computes territory control score - percentage of free cells controlled
-    /// Uses adversarial flood fill to determine territory ownership
-    fn compute_control_score(board: &Board, snake_idx: usize, config: &Config) -> i32 {
-        if snake_idx >= board.snakes.len() {
-            return 0;
-        }
-
-        let control_map = Self::adversarial_flood_fill(board, &[]);
-        Self::compute_control_score_from_map(&control_map, snake_idx, config)
-    }
-
     /// Computes attack potential score
     /// Awards points for length advantage near opponents and trapping opponents
     /// Uses cached flood fill results if available (P2: caching optimization)
-    fn compute_attack_score(
+    pub(crate) fn compute_attack_score(
         board: &Board,
         snake_idx: usize,
         config: &Config,
-        space_cache: &HashMap<usize, usize>,
+        space_by_snake: &[Option<usize>],
     ) -> i32 {
         if snake_idx >= board.snakes.len() {
             return 0;
@@ -2495,20 +4269,21 @@ impl Bot {
 
             // Head-to-head advantage if longer
             if our_snake.length > opponent.length {
-                let dist = manhattan_distance(our_head, opponent.body[0]);
+                let dist = our_head.manhattan(opponent.body[0]);
                 if dist <= config.scores.attack_head_to_head_distance {
                     attack += config.scores.attack_head_to_head_bonus;
                 }
             }
 
-            // Trap potential - opponent has limited space (use cache if available)
+            // Trap potential - opponent has limited space (from the distance field if available)
             // Early exit threshold: if opponent has enough space, we don't need exact count
             let trap_threshold = opponent.length as usize + config.scores.attack_trap_margin;
-            let opp_space = space_cache
-                .get(&idx)
+            let opp_space = space_by_snake
+                .get(idx)
                 .copied()
+                .flatten()
                 .unwrap_or_else(|| {
-                    Self::flood_fill_bfs(board, opponent.body[0], idx, Some(trap_threshold + 1))
+                    Self::flood_fill_bfs(board, opponent.body[0], idx, Some(trap_threshold + 1), config.hazard_walls.as_deref())
                 });
             if opp_space < trap_threshold {
                 attack += config.scores.attack_trap_bonus;
@@ -2518,13 +4293,35 @@ impl Bot {
         attack
     }
 
-    /// Checks if a position could result in a head-to-head collision with equal/longer opponents
-    /// Returns a penalty if the position is dangerous (could lose head-to-head)
-    fn check_head_collision_danger(
+    /// Checks if a position could result in a head-to-head collision
+    /// Delegates to static exchange evaluation so the result is signed:
+    /// negative when the exchange loses or ties, positive when we would win it
+    pub(crate) fn check_head_collision_danger(
         board: &Board,
         snake_idx: usize,
         position: Coord,
         config: &Config,
+    ) -> i32 {
+        Self::static_exchange_eval(board, snake_idx, position, config)
+    }
+
+    /// Static Exchange Evaluation (SEE) for a potential head-to-head square
+    /// Resolves the duel on `target` without searching, by comparing:
+    /// 1. Lengths of every opponent that can step onto the square (longer wins, equal = both die)
+    /// 2. Escape squares: a shorter contender with few alternatives is forced into our kill,
+    ///    and winning is worth less if the square itself is a dead end for us
+    /// 3. Backup threats: extra contenders and equal/longer heads within reach of the
+    ///    square's neighbours can punish us on the following turn even after a won exchange
+    ///
+    /// Returns 0 when the square is uncontested, otherwise a signed value:
+    /// losing exchange → head_collision_penalty, tie → penalty × see_tie_penalty_factor (or
+    /// the contempt against the other duelist, see contempt::tie_penalty),
+    /// winning exchange → up to see_kill_bonus
+    fn static_exchange_eval(
+        board: &Board,
+        snake_idx: usize,
+        target: Coord,
+        config: &Config,
     ) -> i32 {
         if snake_idx >= board.snakes.len() {
             return 0;
@@ -2535,44 +4332,91 @@ impl Bot {
             return 0;
         }
 
-        // Check each opponent
+        let mut contenders = 0i32;
+        let mut losing = false;
+        let mut tying = false;
+        let mut kill_gain = 0i32;
+        let mut backup_threats = 0i32;
+        // Squad-mates that cannot collide with us neither contest the square nor threaten it
+        let passes_allies = config.game_mode.squad_rules().is_some_and(|rules| rules.allow_body_collisions);
+
         for (idx, opponent) in board.snakes.iter().enumerate() {
-            if idx == snake_idx || opponent.health <= 0 || opponent.body.is_empty() {
+            if idx == snake_idx || opponent.health <= 0 || opponent.body.is_empty() || (passes_allies && opponent.is_ally_of(our_snake)) {
                 continue;
             }
 
             let opp_head = opponent.body[0];
+            let opp_neck = opponent.body.get(1).copied();
 
-            // Get opponent's neck to avoid considering reverse moves
-            let opp_neck = if opponent.body.len() > 1 {
-                Some(opponent.body[1])
-            } else {
-                None
-            };
-
-            // For each possible opponent move, check if they could reach our position
-            for dir in Direction::all() {
-                let opp_next_pos = dir.apply(&opp_head);
+            // Opponent contests the square if it can step onto it without reversing
+            let contests = opp_head.manhattan(target) == 1 && opp_neck != Some(target);
 
-                // Skip if opponent would be reversing onto their neck
-                if let Some(neck) = opp_neck {
-                    if opp_next_pos == neck {
-                        continue;
-                    }
+            if !contests {
+                // Backup threat: equal/longer head that can meet us on our next step
+                if opp_head.manhattan(target) == 2 && opponent.length >= our_snake.length {
+                    backup_threats += 1;
                 }
+                continue;
+            }
 
-                // If opponent could move to the same position as us
-                if opp_next_pos == position {
-                    // Check if we would lose (equal or shorter length)
-                    if our_snake.length <= opponent.length {
-                        // This is a dangerous position - we would lose or tie
-                        return config.scores.head_collision_penalty;
-                    }
-                }
+            contenders += 1;
+            if opponent.length > our_snake.length {
+                losing = true;
+            } else if opponent.length == our_snake.length {
+                tying = true;
+            } else {
+                // Shorter contender: the fewer alternatives it has, the more likely it walks into us
+                let opp_escapes = Self::count_escape_squares(board, opp_head, opp_neck, Some(target));
+                kill_gain += config.scores.see_kill_bonus / (opp_escapes + 1);
             }
         }
 
-        0
+        if contenders == 0 {
+            return 0;
+        }
+
+        // Every contender beyond the first is another head we must survive
+        backup_threats += contenders - 1;
+        let backup_penalty = backup_threats * config.scores.see_backup_threat_penalty;
+
+        if losing {
+            return config.scores.head_collision_penalty + backup_penalty;
+        }
+
+        if tying {
+            // Both die: in duels contempt scores it below surviving behind (see contempt.rs)
+            let flat = (config.scores.head_collision_penalty as f32 * config.scores.see_tie_penalty_factor) as i32;
+            return contempt::tie_penalty(board, snake_idx, flat, config) + backup_penalty;
+        }
+
+        // Won exchange: discount the kill if the square leaves us nowhere to go afterwards
+        let our_neck = if our_snake.body[0] == target {
+            our_snake.body.get(1).copied()
+        } else {
+            Some(our_snake.body[0])
+        };
+        let our_escapes = Self::count_escape_squares(board, target, our_neck, None);
+        kill_gain * our_escapes.min(2) / 2 + backup_penalty
+    }
+
+    /// Counts free neighbouring squares of `from` for SEE
+    /// Excludes the snake's own neck, an optional contested square and non-tail body segments
+    fn count_escape_squares(
+        board: &Board,
+        from: Coord,
+        neck: Option<Coord>,
+        excluded: Option<Coord>,
+    ) -> i32 {
+        from.neighbors()
+            .iter()
+            .copied()
+            .filter(|next| {
+                Some(*next) != neck
+                    && Some(*next) != excluded
+                    && !Self::is_out_of_bounds(next, board.width, board.height)
+                    && !Self::is_collision(next, board, 1, None)
+            })
+            .count() as i32
     }
 
     /// Computes wall proximity penalty to discourage moves toward boundaries
@@ -2580,7 +4424,7 @@ impl Bot {
     /// Health-aware: scales penalty down when health is low to allow edge food acquisition
     /// Examples (at full health): distance=0 → -500, distance=1 → -250, distance=2 → -167
     /// Caps at distance >= 3 (safe distance)
-    fn compute_wall_penalty(pos: Coord, width: i32, height: i32, health: i32, config: &Config) -> i32 {
+    pub(crate) fn compute_wall_penalty(pos: Coord, width: i32, height: i32, health: i32, config: &Config) -> i32 {
         let dist_to_wall = [
             pos.x,                  // distance to left wall
             width - 1 - pos.x,      // distance to right wall
@@ -2612,7 +4456,7 @@ impl Bot {
 
     /// Computes center bias to encourage staying in central board positions
     /// Central positions provide more escape routes and avoid dead ends
-    fn compute_center_bias(pos: Coord, width: i32, height: i32, config: &Config) -> i32 {
+    pub(crate) fn compute_center_bias(pos: Coord, width: i32, height: i32, config: &Config) -> i32 {
         let center_x = width / 2;
         let center_y = height / 2;
         let dist_from_center = (pos.x - center_x).abs() + (pos.y - center_y).abs();
@@ -2625,7 +4469,7 @@ impl Bot {
     /// Computes corner danger penalty with health-aware scaling
     /// V5 fix: Game 03 died at (10,10) after eating corner food - need to avoid corners
     /// V10: At critical health, accept corner risk if necessary for food
-    fn compute_corner_danger(pos: Coord, width: i32, height: i32, health: i32, config: &Config) -> i32 {
+    pub(crate) fn compute_corner_danger(pos: Coord, width: i32, height: i32, health: i32, config: &Config) -> i32 {
         // Distance to nearest corner
         let corners = [
             (0, 0),
@@ -2681,34 +4525,8 @@ impl Bot {
         // Body grows when eating food (don't remove tail)
 
         // Count legal moves from the new position
-        let directions = [
-            Direction::Up,
-            Direction::Down,
-            Direction::Left,
-            Direction::Right,
-        ];
-
         let mut legal_moves = 0;
-        for dir in &directions {
-            let next_pos = match dir {
-                Direction::Up => Coord {
-                    x: new_head.x,
-                    y: new_head.y + 1,
-                },
-                Direction::Down => Coord {
-                    x: new_head.x,
-                    y: new_head.y - 1,
-                },
-                Direction::Left => Coord {
-                    x: new_head.x - 1,
-                    y: new_head.y,
-                },
-                Direction::Right => Coord {
-                    x: new_head.x + 1,
-                    y: new_head.y,
-                },
-            };
-
+        for next_pos in new_head.neighbors() {
             // Check bounds
             if next_pos.x < 0
                 || next_pos.x >= board.width as i32
@@ -2729,18 +4547,7 @@ impl Bot {
             }
 
             // Check if we'd hit other snakes (excluding their tails)
-            let mut other_collision = false;
-            for (idx, other_snake) in board.snakes.iter().enumerate() {
-                if idx == snake_idx || other_snake.health == 0 {
-                    continue;
-                }
-
-                let other_body_check = &other_snake.body[..other_snake.body.len().saturating_sub(1)];
-                if other_body_check.contains(&next_pos) {
-                    other_collision = true;
-                    break;
-                }
-            }
+            let other_collision = board.blocked(next_pos, 1, |idx| idx == snake_idx);
 
             if other_collision {
                 continue;
@@ -2767,11 +4574,12 @@ impl Bot {
     /// - Old code: marked "SAFE" (opponent not within distance 2 of food)
     /// - Reality: Opponent traps us against top wall after we eat
     /// - V8 fix: Checks escape routes POST-eating with opponent in pursuit
-    fn is_food_actually_safe(
+    pub(crate) fn is_food_actually_safe(
         board: &Board,
         food_pos: Coord,
         snake_idx: usize,
         active_snakes: &[usize],
+        paths: Option<&FoodPaths>,
         config: &Config,
     ) -> bool {
         if snake_idx >= board.snakes.len() {
@@ -2783,8 +4591,7 @@ impl Bot {
             return false;
         }
 
-        let our_head = our_snake.body[0];
-        let our_dist = manhattan_distance(our_head, food_pos);
+        let our_dist = Self::food_distance(board, snake_idx, food_pos, paths, config);
 
         // Check each ACTIVE opponent (IDAPOS-filtered)
         for &opp_idx in active_snakes {
@@ -2797,8 +4604,7 @@ impl Bot {
                 continue;
             }
 
-            let opp_head = opp.body[0];
-            let opp_dist = manhattan_distance(opp_head, food_pos);
+            let opp_dist = Self::food_distance(board, opp_idx, food_pos, paths, config);
 
             // Check 1: Can they arrive first or simultaneously?
             if opp_dist <= our_dist {
@@ -2842,7 +4648,7 @@ impl Bot {
 
     /// Computes length advantage bonus to encourage growth
     /// V5 fix: Bot stayed small (length 6) while opponents grew (length 19)
-    fn compute_length_advantage(board: &Board, snake_idx: usize, config: &Config) -> i32 {
+    pub(crate) fn compute_length_advantage(board: &Board, snake_idx: usize, config: &Config) -> i32 {
         let our_length = board.snakes[snake_idx].length;
 
         // Get opponent lengths (alive snakes only, excluding ourselves)
@@ -2876,7 +4682,7 @@ impl Bot {
     /// Strongly incentivizes growth when significantly shorter than opponents
     /// Example failure (V7.2 Turn 36): Our length 3 vs opponent length 7 = 4 unit gap
     /// With growth_urgency_per_length=500, this generates +2000 bonus to close gap
-    fn compute_growth_urgency(
+    pub(crate) fn compute_growth_urgency(
         board: &Board,
         snake_idx: usize,
         active_snakes: &[usize],
@@ -2925,7 +4731,7 @@ impl Bot {
     /// NUANCED: Only applies penalty when opponents are nearby (indicating active trap risk)
     /// Prevents self-trapping but allows tail-chasing as valid survival tactic when isolated
     /// Uses IDAPOS-filtered active_snakes to check for nearby opponents
-    fn compute_tail_chasing_penalty(
+    pub(crate) fn compute_tail_chasing_penalty(
         board: &Board,
         snake_idx: usize,
         active_snakes: &[usize],
@@ -2953,7 +4759,7 @@ impl Bot {
                     return false;
                 }
                 let opp_head = opponent.body[0];
-                manhattan_distance(head, opp_head) <= config.scores.tail_chasing_opponent_distance
+                head.manhattan(opp_head) <= config.scores.tail_chasing_opponent_distance
             });
 
         // If no opponents nearby, tail-chasing is safe (no penalty)
@@ -2965,7 +4771,7 @@ impl Bot {
         let nearby_segments = snake.body[2..]
             .iter()
             .filter(|&&seg| {
-                manhattan_distance(head, seg) <= config.scores.tail_chasing_detection_distance
+                head.manhattan(seg) <= config.scores.tail_chasing_detection_distance
             })
             .count();
 
@@ -2981,63 +4787,54 @@ impl Bot {
         -(penalty as i32)
     }
 
-    /// Helper: Flood fill that returns HashSet of reachable positions
+    /// Helper: Flood fill that marks reachable positions in the grid and returns their count
     /// Uses IDAPOS-filtered active snakes for collision detection (consistent with space control)
     fn flood_fill_for_articulation(
         board: &Board,
         start: Coord,
-        snake_idx: usize,
+        _snake_idx: usize,
         active_snakes: &[usize],
-    ) -> HashSet<Coord> {
-        let mut visited = HashSet::new();
-        let mut queue = VecDeque::new();
-
-        queue.push_back(start);
-        visited.insert(start);
-
-        while let Some(pos) = queue.pop_front() {
-            for &dir in &[Direction::Up, Direction::Down, Direction::Left, Direction::Right] {
-                let next = match dir {
-                    Direction::Up => Coord { x: pos.x, y: pos.y + 1 },
-                    Direction::Down => Coord { x: pos.x, y: pos.y - 1 },
-                    Direction::Left => Coord { x: pos.x - 1, y: pos.y },
-                    Direction::Right => Coord { x: pos.x + 1, y: pos.y },
-                };
-
-                // Check bounds
-                if next.x < 0 || next.x >= board.width as i32 ||
-                   next.y < 0 || next.y >= board.height as i32 {
-                    continue;
+        grid: &mut FloodGrid,
+    ) -> usize {
+        // IDAPOS: Only active (nearby) snakes block; their whole body counts as static
+        for &idx in active_snakes {
+            if let Some(snake) = board.snakes.get(idx) {
+                if snake.health > 0 {
+                    for &segment in &snake.body {
+                        grid.block(segment, u16::MAX);
+                    }
                 }
+            }
+        }
 
-                if visited.contains(&next) {
-                    continue;
-                }
+        let mut queue = grid.take_queue();
+        queue.push((start, 0));
+        grid.reach(start, 0);
 
-                // IDAPOS: Only check collision with active (nearby) snakes
-                let blocked = active_snakes.iter().any(|&idx| {
-                    if idx >= board.snakes.len() {
-                        return false;
-                    }
-                    let snake = &board.snakes[idx];
-                    snake.health > 0 && snake.body.contains(&next)
-                });
+        let mut next_idx = 0;
+        while next_idx < queue.len() {
+            let (pos, _) = queue[next_idx];
+            next_idx += 1;
 
-                if !blocked {
-                    visited.insert(next);
-                    queue.push_back(next);
+            for next in pos.neighbors() {
+                if grid.is_reached(next) || grid.blocked_at(next, 0) {
+                    continue;
                 }
+                grid.reach(next, 0);
+                queue.push((next, 0));
             }
         }
 
-        visited
+        let reachable = queue.len();
+        grid.restore_queue(queue);
+        reachable
     }
 
     /// V7: Detects articulation points in reachable space
     /// Articulation points are positions whose removal would disconnect the space
     /// These are narrow passages that create high trap risk
     /// Uses IDAPOS-filtered active_snakes for efficient collision detection
-    fn compute_articulation_point_penalty(
+    pub(crate) fn compute_articulation_point_penalty(
         board: &Board,
         snake_idx: usize,
         active_snakes: &[usize],
@@ -3058,52 +4855,40 @@ impl Bot {
 
         let head = snake.body[0];
 
-        // Flood fill to get reachable space (returns HashSet)
-        // Uses IDAPOS-filtered snakes for collision checks
-        let reachable = Self::flood_fill_for_articulation(board, head, snake_idx, active_snakes);
-
-        if reachable.len() < 4 {
-            return 0; // Too small to have meaningful articulation points
-        }
+        with_flood_grid(board.width, board.height as i32, |grid| {
+            if let Some(walls) = config.hazard_walls.as_deref() {
+                walls.block(grid);
+            }
+            // Flood fill to get reachable space (marked as reached in the grid)
+            // Uses IDAPOS-filtered snakes for collision checks
+            let reachable = Self::flood_fill_for_articulation(board, head, snake_idx, active_snakes, grid);
 
-        // Check if current head position is an articulation point
-        // Method: Remove head from reachable set and check connectivity
-        let is_articulation = Self::is_articulation_point(head, &reachable);
+            if reachable < 4 {
+                return 0; // Too small to have meaningful articulation points
+            }
 
-        if is_articulation {
-            config.scores.articulation_point_penalty
-        } else {
-            0
-        }
+            // Check if current head position is an articulation point
+            // Method: Remove head from reachable set and check connectivity
+            if Self::is_articulation_point(head, grid) {
+                config.scores.articulation_point_penalty
+            } else {
+                0
+            }
+        })
     }
 
     /// Helper: Check if a position is an articulation point
     fn is_articulation_point(
         pos: Coord,
-        reachable: &HashSet<Coord>,
+        grid: &mut FloodGrid,
     ) -> bool {
-        // Get neighbors that are in reachable set
-        let neighbors: Vec<Coord> = [
-            Direction::Up,
-            Direction::Down,
-            Direction::Left,
-            Direction::Right,
-        ]
-        .iter()
-        .filter_map(|&dir| {
-            let next = match dir {
-                Direction::Up => Coord { x: pos.x, y: pos.y + 1 },
-                Direction::Down => Coord { x: pos.x, y: pos.y - 1 },
-                Direction::Left => Coord { x: pos.x - 1, y: pos.y },
-                Direction::Right => Coord { x: pos.x + 1, y: pos.y },
-            };
-            if reachable.contains(&next) && next != pos {
-                Some(next)
-            } else {
-                None
-            }
-        })
-        .collect();
+        // Get neighbors that are in the reachable set
+        let neighbors: Vec<Coord> = pos
+            .neighbors()
+            .iter()
+            .copied()
+            .filter(|&next| next != pos && grid.is_reached(next))
+            .collect();
 
         if neighbors.len() < 2 {
             return false; // Not enough neighbors to be articulation point
@@ -3111,37 +4896,36 @@ impl Bot {
 
         // Check if removing this position disconnects the neighbors
         // Do BFS from first neighbor without going through pos
-        let mut visited = HashSet::new();
-        let mut queue = VecDeque::new();
-        queue.push_back(neighbors[0]);
-        visited.insert(neighbors[0]);
-        visited.insert(pos); // Block the articulation point candidate
-
-        while let Some(current) = queue.pop_front() {
-            for &dir in &[
-                Direction::Up,
-                Direction::Down,
-                Direction::Left,
-                Direction::Right,
-            ] {
-                let next = match dir {
-                    Direction::Up => Coord { x: current.x, y: current.y + 1 },
-                    Direction::Down => Coord { x: current.x, y: current.y - 1 },
-                    Direction::Left => Coord { x: current.x - 1, y: current.y },
-                    Direction::Right => Coord { x: current.x + 1, y: current.y },
-                };
-                if reachable.contains(&next) && !visited.contains(&next) {
-                    visited.insert(next);
-                    queue.push_back(next);
+        let mut queue = grid.take_queue();
+        grid.mark(pos); // Block the articulation point candidate
+        grid.mark(neighbors[0]);
+        queue.push((neighbors[0], 0));
+
+        let mut next_idx = 0;
+        while next_idx < queue.len() {
+            let (current, _) = queue[next_idx];
+            next_idx += 1;
+
+            for next in current.neighbors() {
+                if grid.is_reached(next) && grid.mark(next) {
+                    queue.push((next, 0));
                 }
             }
         }
+        grid.restore_queue(queue);
 
         // If not all neighbors are reachable, pos is an articulation point
-        neighbors.iter().any(|n| !visited.contains(n))
+        neighbors.iter().any(|&n| !grid.is_marked(n))
+    }
+
+    /// Leaf evaluation of every snake (by board index), with all snakes active
+    /// Public entry point for benchmarks; the search calls evaluate_state directly
+    pub fn evaluate_all(board: &Board, our_snake_id: &str, config: &Config) -> Vec<i32> {
+        Self::evaluate_state(board, our_snake_id, config, None, 0).scores
     }
 
-    /// Evaluates the current game state for all snakes
+    /// Evaluates the current game state for all snakes with the configured evaluator
+    /// (config.evaluator, see evaluator.rs; the handcrafted HeuristicEvaluator by default)
     /// Returns an N-tuple of scores (one per snake)
     ///
     /// # Parameters
@@ -3154,187 +4938,34 @@ impl Bot {
         active_snakes: Option<&[usize]>,
         depth_from_root: u8,
     ) -> ScoreTuple {
-        let _prof = simple_profiler::ProfileGuard::new("eval");
-
-        let num_snakes = board.snakes.len();
-        let mut scores = vec![0i32; num_snakes];
-
-        // Pre-compute ALL flood fills once per evaluation (P2: caching optimization)
-        // This eliminates redundant computation in space + attack scores
-        let mut space_cache: HashMap<usize, usize> = HashMap::new();
-        for (idx, snake) in board.snakes.iter().enumerate() {
-            if snake.health > 0 && !snake.body.is_empty() {
-                // Only compute for active snakes (IDAPOS optimization)
-                let is_active = active_snakes.map_or(true, |active| active.contains(&idx));
-                if is_active {
-                    // No early exit threshold - cache needs exact count for multiple components
-                    space_cache.insert(idx, Self::flood_fill_bfs(board, snake.body[0], idx, None));
-                }
-            }
-        }
-
-        // Compute territory control ONCE for active snakes only (major optimization!)
-        // If active_snakes is empty, processes all snakes. Otherwise, only processes filtered snakes.
-        let control_map = if let Some(active) = active_snakes {
-            if active.is_empty() {
-                None
-            } else {
-                Some(Self::adversarial_flood_fill(board, active))
-            }
-        } else {
-            Some(Self::adversarial_flood_fill(board, &[]))
+        let ctx = EvalContext { our_snake_id, config, active_snakes, depth_from_root };
+        let mut tuple = match &config.evaluator {
+            Some(evaluator) => evaluator.evaluate(board, &ctx),
+            None => HeuristicEvaluator.evaluate(board, &ctx),
         };
-
-        for (idx, snake) in board.snakes.iter().enumerate() {
-            if snake.health <= 0 {
-                scores[idx] = config.scores.score_dead_snake;
-                continue;
-            }
-
-            // Check if this snake is active (needs full evaluation)
-            let is_active = active_snakes.map_or(true, |active| active.contains(&idx));
-
-            // Multi-component evaluation
-            let survival = 0; // Alive = 0 penalty
-            let active_list = active_snakes.unwrap_or(&[]);
-            let health = Self::compute_health_score(board, idx, active_list, config);
-
-            // Compute space score with entrapment detection
-            // Uses IDAPOS-filtered active snakes for adversarial entrapment detection
-            let space = if is_active {
-                let active_list = active_snakes.unwrap_or(&[]);
-                Self::compute_space_score(board, idx, active_list, config)
-            } else {
-                0
-            };
-
-            // Only compute expensive control and attack for active snakes
-            let control = if is_active {
-                if let Some(ref map) = control_map {
-                    Self::compute_control_score_from_map(map, idx, config)
-                } else {
-                    0
-                }
-            } else {
-                0  // Skip expensive territory control for non-active snakes
-            };
-
-            let length = snake.length * config.scores.weight_length;
-
-            let attack = if is_active {
-                Self::compute_attack_score(board, idx, config, &space_cache)
-            } else {
-                0  // Skip expensive attack calculation for non-active snakes
-            };
-
-            // Check for head-to-head collision danger
-            let head_collision_danger = if !snake.body.is_empty() {
-                Self::check_head_collision_danger(board, idx, snake.body[0], config)
-            } else {
-                0
-            };
-
-            // Wall proximity penalty, center bias, and corner danger
-            let (wall_penalty, center_bias, corner_danger) = if !snake.body.is_empty() {
-                let head = snake.body[0];
-                (
-                    Self::compute_wall_penalty(head, board.width as i32, board.height as i32, snake.health, config),
-                    Self::compute_center_bias(head, board.width as i32, board.height as i32, config),
-                    Self::compute_corner_danger(head, board.width as i32, board.height as i32, snake.health, config),  // V10: Added health parameter
-                )
-            } else {
-                (0, 0, 0)
-            };
-
-            // Length advantage bonus
-            let length_advantage = Self::compute_length_advantage(board, idx, config);
-
-            // V8: Growth urgency - incentivize growth when shorter than opponents
-            // Uses IDAPOS-filtered active snakes to compare lengths efficiently
-            let growth_urgency = if is_active {
-                let active_list = active_snakes.unwrap_or(&[]);
-                Self::compute_growth_urgency(board, idx, active_list, config)
-            } else {
-                0  // Skip for non-active snakes
-            };
-
-            // V7: Tail-chasing detection (nuanced - only when opponents nearby)
-            // Uses IDAPOS-filtered active snakes to check for nearby opponents
-            let tail_chasing_penalty = if is_active {
-                let active_list = active_snakes.unwrap_or(&[]);
-                Self::compute_tail_chasing_penalty(board, idx, active_list, config)
-            } else {
-                0  // Skip tail-chasing check for non-active snakes
-            };
-
-            // V7: Articulation point detection (narrow passage risk)
-            // Uses IDAPOS-filtered active snakes for efficient collision detection
-            let articulation_penalty = if is_active {
-                let active_list = active_snakes.unwrap_or(&[]);
-                Self::compute_articulation_point_penalty(board, idx, active_list, config)
-            } else {
-                0  // Skip expensive articulation check for non-active snakes
-            };
-
-            // Weighted combination
-            scores[idx] = survival
-                + (config.scores.score_survival_weight * survival as f32) as i32
-                + (config.scores.weight_space * space as f32) as i32
-                + (config.scores.weight_health * health as f32) as i32
-                + (config.scores.weight_control * control as f32) as i32
-                + (config.scores.weight_attack * attack as f32) as i32
-                + length
-                + head_collision_danger
-                + wall_penalty
-                + center_bias
-                + corner_danger
-                + length_advantage + growth_urgency
-                + tail_chasing_penalty
-                + articulation_penalty;
-        }
-
-        // Apply survival penalty if our snake is dead
-        if let Some(our_idx) = board.snakes.iter().position(|s| s.id == our_snake_id) {
-            if board.snakes[our_idx].health <= 0 {
-                scores[our_idx] = config.scores.score_survival_penalty;
-            }
-        }
-
-        // V7.2: Apply temporal discounting - future scores less confident, weighted lower
-        // discount = (0.95 ^ depth): depth 0 = 1.0, depth 5 = 0.77, depth 10 = 0.60
-        if depth_from_root > 0 {
-            let discount = config.scores.temporal_discount_factor.powi(depth_from_root as i32);
-            for score in &mut scores {
-                *score = (*score as f32 * discount) as i32;
-            }
+        if config.debug.explain {
+            tuple.leaf = evaluator::breakdown(board, &ctx).map(Arc::new);
         }
-
-        ScoreTuple { scores }
+        tuple
     }
 
     /// Determines which snakes are active (local) for IDAPOS optimization
     /// Returns indices of snakes within locality distance
     /// V11.3: Uses turn-adaptive thresholds for awareness vs performance balance
+    /// Search nodes pass their transposition hash so the set is reused (eval_cache::active_set)
     fn determine_active_snakes(
         board: &Board,
         our_snake_id: &str,
         turn: i32,
         remaining_depth: u8,
+        board_hash: Option<u64>,
         config: &Config,
     ) -> Vec<usize> {
-        let our_idx = match board.snakes.iter().position(|s| s.id == our_snake_id) {
+        let our_idx = match board.snake_index(our_snake_id) {
             Some(idx) => idx,
             None => return vec![],
         };
 
-        let mut active = vec![our_idx];
-
-        if board.snakes[our_idx].health <= 0 || board.snakes[our_idx].body.is_empty() {
-            return active;
-        }
-
-        let our_head = board.snakes[our_idx].body[0];
-
         // V11.3: Turn-adaptive IDAPOS thresholds
         // Early game: wider awareness to avoid long-term entrapment
         // Late game: tighter filtering for performance
@@ -3355,13 +4986,33 @@ impl Bot {
         let base_threshold = multiplier * remaining_depth as i32;
         let locality_threshold = std::cmp::min(base_threshold, max_distance);
 
+        match board_hash {
+            Some(hash) => eval_cache::active_set(hash, locality_threshold, our_idx, config, || {
+                Self::compute_active_snakes(board, our_idx, locality_threshold)
+            }),
+            None => Self::compute_active_snakes(board, our_idx, locality_threshold),
+        }
+    }
+
+    /// Our snake plus every alive snake with its head or a body segment within
+    /// `locality_threshold` of our head
+    fn compute_active_snakes(board: &Board, our_idx: usize, locality_threshold: i32) -> Vec<usize> {
+        let _prof = simple_profiler::ProfileGuard::new("idapos");
+        let mut active = vec![our_idx];
+
+        if board.snakes[our_idx].health <= 0 || board.snakes[our_idx].body.is_empty() {
+            return active;
+        }
+
+        let our_head = board.snakes[our_idx].body[0];
+
         for (idx, snake) in board.snakes.iter().enumerate() {
-            if idx == our_idx || snake.health <= 0 {
+            if idx == our_idx || snake.health <= 0 || snake.body.is_empty() {
                 continue;
             }
 
             // Check head distance
-            let head_dist = manhattan_distance(our_head, snake.body[0]);
+            let head_dist = our_head.manhattan(snake.body[0]);
             if head_dist <= locality_threshold {
                 active.push(idx);
                 continue;
@@ -3369,7 +5020,7 @@ impl Bot {
 
             // Check any body segment distance (using same capped threshold)
             for &segment in &snake.body {
-                if manhattan_distance(our_head, segment) <= locality_threshold {
+                if our_head.manhattan(segment) <= locality_threshold {
                     active.push(idx);
                     break;
                 }
@@ -3386,13 +5037,14 @@ impl Bot {
         b: &ScoreTuple,
         our_idx: usize,
     ) -> ScoreTuple {
+        // Summed as i64: several dead opponents (score_dead_snake) overflow i32
         let opponent_sum = |t: &ScoreTuple| {
             t.scores
                 .iter()
                 .enumerate()
                 .filter(|(i, _)| *i != our_idx)
-                .map(|(_, &s)| s)
-                .sum::<i32>()
+                .map(|(_, &s)| i64::from(s))
+                .sum::<i64>()
         };
 
         if opponent_sum(a) < opponent_sum(b) {
@@ -3406,16 +5058,14 @@ impl Bot {
     /// Used by IDAPOS when locality masking reduces game to 2 active snakes
     fn alpha_beta_for_two_snakes(
         board: &Board,
-        our_snake_id: &str,
-        depth: u8,
-        depth_from_root: u8,
+        ply: Ply,
         our_idx: usize,
         opponent_idx: usize,
-        config: &Config,
-        tt: &Arc<TranspositionTable>,
+        ctx: &SearchContext,
+        scratch: &mut SearchScratch,
     ) -> ScoreTuple {
         // Create a simplified 2-player board with only the active snakes
-        let mut simplified_board = board.clone();
+        let mut simplified_board = scratch.child_board(board);
 
         // Mark all non-active snakes as dead
         for (idx, snake) in simplified_board.snakes.iter_mut().enumerate() {
@@ -3425,23 +5075,13 @@ impl Bot {
         }
 
         // Create local killer table and history table for this search
-        let mut killers = KillerMoveTable::new(config);
-        let mut history = HistoryTable::new(board.width as u32, board.height as u32);
+        let killers = KillerMoveTable::new(ctx.config);
+        let history = HistoryTable::new(board.width as u32, board.height);
+        let ctx = SearchContext { killers: &killers, history: &history, ..*ctx };
 
         // Use alpha-beta to get our score
-        let our_score = Self::alpha_beta_minimax(
-            &simplified_board,
-            our_snake_id,
-            depth,
-            depth_from_root,
-            i32::MIN,
-            i32::MAX,
-            true,
-            config,
-            tt,
-            &mut killers,
-            &mut history,
-        );
+        let our_score = Self::alpha_beta_minimax(&simplified_board, ply, i32::MIN, i32::MAX, true, &ctx, scratch);
+        scratch.recycle_board(simplified_board);
 
         // Create score tuple with our score and opponent's inverse
         // In zero-sum approximation, opponent's score is -our_score
@@ -3449,77 +5089,65 @@ impl Bot {
         scores[our_idx] = our_score;
         scores[opponent_idx] = -our_score;
 
-        ScoreTuple { scores }
+        ScoreTuple { scores, leaf: None }
     }
 
     /// MaxN recursive search for multiplayer games
     /// Each player maximizes their own score component
     fn maxn_search(
         board: &Board,
-        our_snake_id: &str,
-        turn: i32,
-        depth: u8,
-        depth_from_root: u8,
+        ply: Ply,
         current_player_idx: usize,
-        config: &Config,
-        tt: &Arc<TranspositionTable>,
-        killers: &mut KillerMoveTable,
-        history: &mut HistoryTable,
+        ctx: &SearchContext,
+        scratch: &mut SearchScratch,
     ) -> ScoreTuple {
+        let SearchContext { our_snake_id, turn, config, tt, killers, history, shared } = *ctx;
+        let Ply { depth, from_root: depth_from_root, q_extensions } = ply;
         let _prof = simple_profiler::ProfileGuard::new("maxn");
+        if shared.count_node_and_check_stop(config.timing.stop_check_interval_nodes) {
+            // Aborted: value is discarded by the root, never stored in the TT
+            return ScoreTuple::new_with_value(board.snakes.len(), 0);
+        }
 
         // Probe transposition table
         let board_hash = TranspositionTable::hash_board(board);
-        if let Some(cached_score) = tt.probe(board_hash, depth) {
+        let node_key = TranspositionTable::node_key(board_hash, board, current_player_idx);
+        if let Some(cached_score) = tt.probe(node_key, depth) {
             simple_profiler::record_tt_lookup(true);
             return ScoreTuple::new_with_value(board.snakes.len(), cached_score);
         }
         simple_profiler::record_tt_lookup(false);
 
-        let our_idx = board
-            .snakes
-            .iter()
-            .position(|s| &s.id == our_snake_id)
-            .unwrap_or(0);
+        let our_idx = board.snake_index(our_snake_id).unwrap_or(0);
 
         // IDAPOS: Determine active (local) snakes to reduce branching
         // Do this BEFORE terminal evaluation so we can optimize evaluation too
-        let active_snakes = Self::determine_active_snakes(board, our_snake_id, turn, depth, config);
+        let active_snakes = Self::determine_active_snakes(board, our_snake_id, turn, depth, Some(board_hash), config);
 
         // Check for terminal state first
         if Self::is_terminal(board, our_snake_id, config) {
             let eval = Self::evaluate_state(board, our_snake_id, config, Some(&active_snakes), depth_from_root);
-            tt.store(board_hash, eval.for_player(our_idx), depth, BoundType::Exact, None);
+            tt.store(node_key, eval.for_player(our_idx), depth, BoundType::Exact, None);
             return eval;
         }
 
         // At depth 0, check if position is unstable (quiescence extension)
         if depth == 0 {
-            if is_position_unstable(board, our_snake_id, config) {
+            if extends_quiescence(board, our_snake_id, depth_from_root, q_extensions, config) {
                 // Extend search by 1 ply for tactically critical positions
                 // Recompute active snakes for extended depth
-                return Self::maxn_search(
-                    board,
-                    our_snake_id,
-                    turn,
-                    1, // Extended depth
-                    depth_from_root + 1, // Going one ply deeper
-                    current_player_idx,
-                    config,
-                    tt,
-                    killers,
-                    history,
-                );
+                let extended = Ply { depth: 1, from_root: depth_from_root + 1, q_extensions: q_extensions + 1 };
+                return Self::maxn_search(board, extended, current_player_idx, ctx, scratch);
             }
 
             // Stable position at depth 0, evaluate normally
             let eval = Self::evaluate_state(board, our_snake_id, config, Some(&active_snakes), depth_from_root);
-            tt.store(board_hash, eval.for_player(our_idx), depth, BoundType::Exact, None);
+            tt.store(node_key, eval.for_player(our_idx), depth, BoundType::Exact, None);
             return eval;
         }
 
         // If only 2 snakes are active and we're one of them, use alpha-beta
-        if active_snakes.len() == config.idapos.min_snakes_for_alpha_beta
+        if Self::pairs_players(active_snakes.len(), config.idapos.min_snakes_for_alpha_beta, config)
             && active_snakes.contains(&our_idx)
         {
             // Switch to alpha-beta for efficiency
@@ -3529,16 +5157,7 @@ impl Bot {
                 .copied()
                 .unwrap_or(0);
 
-            return Self::alpha_beta_for_two_snakes(
-                board,
-                our_snake_id,
-                depth,
-                depth_from_root,
-                our_idx,
-                opponent_idx,
-                config,
-                tt,
-            );
+            return Self::alpha_beta_for_two_snakes(board, ply, our_idx, opponent_idx, ctx, scratch);
         }
 
         // Check if current player is alive and active
@@ -3553,12 +5172,14 @@ impl Bot {
             if next == our_idx {
                 // All active snakes have moved, inactive snakes passed
                 // Advance game state and reduce depth
-                let mut advanced_board = board.clone();
-                Self::advance_game_state(&mut advanced_board);
-                return Self::maxn_search(&advanced_board, our_snake_id, turn, depth - 1, depth_from_root + 1, our_idx, config, tt, killers, history);
+                let mut advanced_board = scratch.child_board(board);
+                Self::advance_full_turn(&mut advanced_board, config);
+                let tuple = Self::maxn_search(&advanced_board, ply.next(), our_idx, ctx, scratch);
+                scratch.recycle_board(advanced_board);
+                return tuple;
             } else {
                 // Continue with next player at same depth
-                return Self::maxn_search(board, our_snake_id, turn, depth, depth_from_root, next, config, tt, killers, history);
+                return Self::maxn_search(board, ply, next, ctx, scratch);
             }
         }
 
@@ -3567,24 +5188,26 @@ impl Bot {
 
         if moves.is_empty() {
             // No legal moves - mark snake as dead and continue
-            let mut dead_board = board.clone();
+            let mut dead_board = scratch.child_board(board);
             dead_board.snakes[current_player_idx].health = 0;
             let next = (current_player_idx + 1) % board.snakes.len();
-            return Self::maxn_search(&dead_board, our_snake_id, turn, depth, depth_from_root, next, config, tt, killers, history);
+            let tuple = Self::maxn_search(&dead_board, ply, next, ctx, scratch);
+            scratch.recycle_board(dead_board);
+            return tuple;
         }
 
         // Try to get best move from transposition table for move ordering
-        let tt_best_move = tt.probe_with_move(board_hash, depth).and_then(|(_, mv)| mv);
+        let tt_best_move = tt.probe_with_move(node_key, depth).and_then(|(_, mv)| mv);
 
-        // Order moves using TT move > killers > history heuristic
+        // Order moves using TT move > killers > SEE / history heuristic
         let current_pos = &board.snakes[current_player_idx].body[0];
-        moves = order_moves(moves, tt_best_move, killers, Some((history, current_pos)), depth, config);
+        order_moves(&mut moves, tt_best_move, killers, Some((history, current_pos, current_player_idx == our_idx)), Some((board, current_player_idx)), depth, config);
 
         let mut best_tuple =
             ScoreTuple::new_with_value(board.snakes.len(), i32::MIN);
 
-        for mv in moves {
-            let mut child_board = board.clone();
+        for &mv in moves.iter() {
+            let mut child_board = scratch.child_board(board);
             Self::apply_move(&mut child_board, current_player_idx, mv, config);
 
             let next = (current_player_idx + 1) % board.snakes.len();
@@ -3592,19 +5215,24 @@ impl Bot {
 
             let child_tuple = if all_moved {
                 // All snakes have moved - advance game state and reduce depth
-                Self::advance_game_state(&mut child_board);
-                Self::maxn_search(&child_board, our_snake_id, turn, depth - 1, depth_from_root + 1, our_idx, config, tt, killers, history)
+                Self::advance_full_turn(&mut child_board, config);
+                Self::maxn_search(&child_board, ply.next(), our_idx, ctx, scratch)
             } else {
                 // Continue with next player at same depth
-                Self::maxn_search(&child_board, our_snake_id, turn, depth, depth_from_root, next, config, tt, killers, history)
+                Self::maxn_search(&child_board, ply, next, ctx, scratch)
             };
+            scratch.recycle_board(child_board);
+
+            if shared.is_stopped() {
+                return child_tuple;
+            }
 
             // Update if current player improves their score
             if child_tuple.for_player(current_player_idx)
                 > best_tuple.for_player(current_player_idx)
             {
                 // Update history for this good move
-                history.update(current_pos, mv, depth, false);
+                history.update(current_player_idx == our_idx, current_pos, mv, depth, false);
                 best_tuple = child_tuple;
             } else if child_tuple.for_player(current_player_idx)
                 == best_tuple.for_player(current_player_idx)
@@ -3615,30 +5243,97 @@ impl Bot {
         }
 
         // Store result in transposition table before returning
-        tt.store(board_hash, best_tuple.for_player(our_idx), depth, BoundType::Exact, None);
+        tt.store(node_key, best_tuple.for_player(our_idx), depth, BoundType::Exact, None);
         best_tuple
     }
 
+    /// Lazy-eval pruning of an alpha-beta node (our score; `is_max` when we move)
+    /// `bound` is the side of the window the node must clear: alpha when we move, else beta.
+    /// Futility at the frontier: a lazy score futility_margin outside the window fails without
+    /// a search. Razoring up to lazy_eval.razor_depth: a lazy score razor_margin outside the
+    /// window is confirmed with `leaf` (the node searched at depth 0) before failing.
+    /// Tactical positions (is_position_unstable) are never pruned.
+    ///
+    /// # Returns
+    /// * `Some(score)` - the node fails low (max) or high (min) with this score
+    /// * `None` - the node must be searched
+    fn lazy_cutoff(
+        board: &Board,
+        our_idx: usize,
+        ply: Ply,
+        bound: i32,
+        is_max: bool,
+        ctx: &SearchContext,
+        leaf: impl FnOnce() -> i32,
+    ) -> Option<i32> {
+        let SearchContext { our_snake_id, config, .. } = *ctx;
+        let Ply { depth, from_root: depth_from_root, .. } = ply;
+        let lazy = &config.lazy_eval;
+        if !lazy.enabled || depth == 0 || depth > lazy.razor_depth.max(1) {
+            return None;
+        }
+        let outside = |score: i32, margin: i32| {
+            if is_max {
+                score.saturating_add(margin) <= bound
+            } else {
+                score.saturating_sub(margin) >= bound
+            }
+        };
+        let estimate = evaluator::lazy_score(board, our_idx, config, depth_from_root);
+        let margin = if depth == 1 { lazy.futility_margin } else { lazy.razor_margin };
+        if !outside(estimate, margin) || is_position_unstable(board, our_snake_id, config) {
+            return None;
+        }
+        if depth == 1 {
+            return Some(estimate);
+        }
+        let verified = leaf();
+        if outside(verified, 0) {
+            Some(verified)
+        } else {
+            None
+        }
+    }
+
     /// Alpha-beta minimax for 2-player zero-sum games (1v1)
     /// More efficient than MaxN when only two snakes remain
     fn alpha_beta_minimax(
         board: &Board,
-        our_snake_id: &str,
-        depth: u8,
-        depth_from_root: u8,
+        ply: Ply,
         mut alpha: i32,
         mut beta: i32,
         is_max: bool,
-        config: &Config,
-        tt: &Arc<TranspositionTable>,
-        killers: &mut KillerMoveTable,
-        history: &mut HistoryTable,
+        ctx: &SearchContext,
+        scratch: &mut SearchScratch,
     ) -> i32 {
+        let SearchContext { our_snake_id, config, tt, killers, history, shared, .. } = *ctx;
+        let Ply { depth, from_root: depth_from_root, q_extensions } = ply;
         let _prof = simple_profiler::ProfileGuard::new("alpha_beta");
+        if shared.count_node_and_check_stop(config.timing.stop_check_interval_nodes) {
+            // Aborted: value is discarded by the root, never stored in the TT
+            return 0;
+        }
+
+        let our_idx = board.snake_index(our_snake_id).unwrap_or(0);
+
+        // Determine which player moves
+        let player_idx = if is_max {
+            our_idx
+        } else {
+            // Find opponent (first alive snake that isn't us)
+            board
+                .snakes
+                .iter()
+                .enumerate()
+                .find(|(i, s)| *i != our_idx && s.health > 0)
+                .map(|(i, _)| i)
+                .unwrap_or(our_idx)
+        };
 
         // Probe transposition table
         let board_hash = TranspositionTable::hash_board(board);
-        if let Some(cached_score) = tt.probe(board_hash, depth) {
+        let node_key = TranspositionTable::node_key(board_hash, board, player_idx);
+        if let Some(cached_score) = tt.probe(node_key, depth) {
             simple_profiler::record_tt_lookup(true);
             return cached_score;
         }
@@ -3647,124 +5342,92 @@ impl Bot {
         // Check for terminal state first
         if Self::is_terminal(board, our_snake_id, config) {
             let scores = Self::evaluate_state(board, our_snake_id, config, None, depth_from_root);
-            let our_idx = board
-                .snakes
-                .iter()
-                .position(|s| &s.id == our_snake_id)
-                .unwrap_or(0);
             let score = scores.for_player(our_idx);
-            tt.store(board_hash, score, depth, BoundType::Exact, None);
+            tt.store(node_key, score, depth, BoundType::Exact, None);
             return score;
         }
 
         // At depth 0, check if position is unstable (quiescence extension)
         if depth == 0 {
-            if is_position_unstable(board, our_snake_id, config) {
+            if extends_quiescence(board, our_snake_id, depth_from_root, q_extensions, config) {
                 // Extend search by 1 ply for tactically critical positions
                 // This helps avoid horizon effect on food eating and collisions
-                return Self::alpha_beta_minimax(
-                    board,
-                    our_snake_id,
-                    1, // Extended depth
-                    depth_from_root + 1,  // Extending search, increment depth from root
-                    alpha,
-                    beta,
-                    is_max,
-                    config,
-                    tt,
-                    killers,
-                    history,
-                );
+                let extended = Ply { depth: 1, from_root: depth_from_root + 1, q_extensions: q_extensions + 1 };
+                return Self::alpha_beta_minimax(board, extended, alpha, beta, is_max, ctx, scratch);
             }
 
             // Stable position at depth 0, evaluate normally
             let scores = Self::evaluate_state(board, our_snake_id, config, None, depth_from_root);
-            let our_idx = board
-                .snakes
-                .iter()
-                .position(|s| &s.id == our_snake_id)
-                .unwrap_or(0);
+            let our_idx = board.snake_index(our_snake_id).unwrap_or(0);
             let score = scores.for_player(our_idx);
-            tt.store(board_hash, score, depth, BoundType::Exact, None);
+            tt.store(node_key, score, depth, BoundType::Exact, None);
             return score;
         }
 
-        let our_idx = board
-            .snakes
-            .iter()
-            .position(|s| &s.id == our_snake_id)
-            .unwrap_or(0);
-
-        // Determine which player moves
-        let player_idx = if is_max {
-            our_idx
-        } else {
-            // Find opponent (first alive snake that isn't us)
-            board
-                .snakes
-                .iter()
-                .enumerate()
-                .find(|(i, s)| *i != our_idx && s.health > 0)
-                .map(|(i, _)| i)
-                .unwrap_or(our_idx)
-        };
-
         if player_idx >= board.snakes.len() || board.snakes[player_idx].health <= 0 {
             // Player is dead, return evaluation
             let scores = Self::evaluate_state(board, our_snake_id, config, None, depth_from_root);
             return scores.for_player(our_idx);
         }
 
+        let bound = if is_max { alpha } else { beta };
+        let cutoff = Self::lazy_cutoff(board, our_idx, ply, bound, is_max, ctx, || {
+            Self::alpha_beta_minimax(board, ply.with_depth(0), alpha, beta, is_max, ctx, scratch)
+        });
+        if let Some(score) = cutoff {
+            return score;
+        }
+
         let mut moves = Self::generate_legal_moves(board, &board.snakes[player_idx], config);
 
         if moves.is_empty() {
-            let mut dead_board = board.clone();
+            let mut dead_board = scratch.child_board(board);
             dead_board.snakes[player_idx].health = 0;
-            return Self::alpha_beta_minimax(
-                &dead_board,
-                our_snake_id,
-                depth,
-                depth_from_root,  // Same depth, no state change
-                alpha,
-                beta,
-                !is_max,
-                config,
-                tt,
-                killers,
-                history,
-            );
+            // Same depth, no state change
+            let score = Self::alpha_beta_minimax(&dead_board, ply, alpha, beta, !is_max, ctx, scratch);
+            scratch.recycle_board(dead_board);
+            return score;
+        }
+
+        // Frontier node: a batching evaluator (the value network) sees all the leaves below at once
+        if depth == 1 {
+            if let Some(evaluator) = config.evaluator.as_ref().filter(|evaluator| evaluator.batches_leaves()) {
+                let children: Vec<Board> = moves
+                    .iter()
+                    .map(|&mv| {
+                        let mut child = board.clone();
+                        Self::apply_move(&mut child, player_idx, mv, config);
+                        Self::advance_game_state(&mut child, config);
+                        child
+                    })
+                    .collect();
+                evaluator.prefetch(&children, config);
+            }
         }
 
         // Try to get best move from transposition table for move ordering
-        let tt_best_move = tt.probe_with_move(board_hash, depth).and_then(|(_, mv)| mv);
+        let tt_best_move = tt.probe_with_move(node_key, depth).and_then(|(_, mv)| mv);
 
-        // Order moves using TT move > killers > history heuristic
+        // Order moves using TT move > killers > SEE / history heuristic
         let current_pos = &board.snakes[player_idx].body[0];
-        moves = order_moves(moves, tt_best_move, killers, Some((history, current_pos)), depth, config);
+        order_moves(&mut moves, tt_best_move, killers, Some((history, current_pos, is_max)), Some((board, player_idx)), depth, config);
 
         if is_max {
             let mut max_eval = i32::MIN;
             let mut best_move: Option<Direction> = None;
             let mut had_cutoff = false;
 
-            for mv in moves {
-                let mut child_board = board.clone();
+            for &mv in moves.iter() {
+                let mut child_board = scratch.child_board(board);
                 Self::apply_move(&mut child_board, player_idx, mv, config);
-                Self::advance_game_state(&mut child_board);
-
-                let eval = Self::alpha_beta_minimax(
-                    &child_board,
-                    our_snake_id,
-                    depth - 1,
-                    depth_from_root + 1,  // One ply deeper
-                    alpha,
-                    beta,
-                    false,
-                    config,
-                    tt,
-                    killers,
-                    history,
-                );
+                Self::advance_game_state(&mut child_board, config);
+
+                let eval = Self::alpha_beta_minimax(&child_board, ply.next(), alpha, beta, false, ctx, scratch);
+                scratch.recycle_board(child_board);
+
+                if shared.is_stopped() {
+                    return eval;
+                }
 
                 if eval > max_eval {
                     max_eval = eval;
@@ -3775,7 +5438,7 @@ impl Bot {
                 if beta <= alpha {
                     // Beta cutoff: record this move as a killer and update history
                     killers.record_killer(depth, mv, config);
-                    history.update(current_pos, mv, depth, true);
+                    history.update(is_max, current_pos, mv, depth, true);
                     simple_profiler::record_alpha_beta_cutoff();
                     had_cutoff = true;
                     break;
@@ -3788,31 +5451,24 @@ impl Bot {
             } else {
                 BoundType::Exact  // All moves explored: exact score
             };
-            tt.store(board_hash, max_eval, depth, bound_type, best_move);
+            tt.store(node_key, max_eval, depth, bound_type, best_move);
             max_eval
         } else {
             let mut min_eval = i32::MAX;
             let mut best_move: Option<Direction> = None;
             let mut had_cutoff = false;
 
-            for mv in moves {
-                let mut child_board = board.clone();
+            for &mv in moves.iter() {
+                let mut child_board = scratch.child_board(board);
                 Self::apply_move(&mut child_board, player_idx, mv, config);
-                Self::advance_game_state(&mut child_board);
-
-                let eval = Self::alpha_beta_minimax(
-                    &child_board,
-                    our_snake_id,
-                    depth - 1,
-                    depth_from_root + 1,  // One ply deeper
-                    alpha,
-                    beta,
-                    true,
-                    config,
-                    tt,
-                    killers,
-                    history,
-                );
+                Self::advance_game_state(&mut child_board, config);
+
+                let eval = Self::alpha_beta_minimax(&child_board, ply.next(), alpha, beta, true, ctx, scratch);
+                scratch.recycle_board(child_board);
+
+                if shared.is_stopped() {
+                    return eval;
+                }
 
                 if eval < min_eval {
                     min_eval = eval;
@@ -3823,7 +5479,7 @@ impl Bot {
                 if beta <= alpha {
                     // Alpha cutoff: record this move as a killer and update history
                     killers.record_killer(depth, mv, config);
-                    history.update(current_pos, mv, depth, true);
+                    history.update(is_max, current_pos, mv, depth, true);
                     simple_profiler::record_alpha_beta_cutoff();
                     had_cutoff = true;
                     break;
@@ -3836,37 +5492,176 @@ impl Bot {
             } else {
                 BoundType::Exact  // All moves explored: exact score
             };
-            tt.store(board_hash, min_eval, depth, bound_type, best_move);
+            tt.store(node_key, min_eval, depth, bound_type, best_move);
             min_eval
         }
     }
 
+    /// Best-reply search (Schadd & Winands) for multiplayer games
+    /// Plies alternate between us and the opponents as in a duel, but an opponent ply plays only
+    /// the single strongest reply of any one active opponent (IDAPOS) while the others pass.
+    /// The tree grows like alpha-beta's instead of MaxN's product of every snake's moves, so
+    /// it reaches far deeper on crowded boards, at the cost of assuming the opponents cooperate
+    /// against us. Returns our score; alpha-beta pruning applies.
+    fn best_reply_search(
+        board: &Board,
+        ply: Ply,
+        mut alpha: i32,
+        mut beta: i32,
+        is_max: bool,
+        ctx: &SearchContext,
+        scratch: &mut SearchScratch,
+    ) -> i32 {
+        let SearchContext { our_snake_id, turn, config, tt, killers, history, shared } = *ctx;
+        let Ply { depth, from_root: depth_from_root, q_extensions } = ply;
+        // Profiled (time, nodes and cutoffs) as alpha-beta, which it is apart from the min plies
+        let _prof = simple_profiler::ProfileGuard::new("alpha_beta");
+        if shared.count_node_and_check_stop(config.timing.stop_check_interval_nodes) {
+            // Aborted: value is discarded by the root, never stored in the TT
+            return 0;
+        }
+
+        let our_idx = board.snake_index(our_snake_id).unwrap_or(0);
+        let board_hash = TranspositionTable::hash_board(board);
+        let node_key = TranspositionTable::node_key(board_hash, board, if is_max { our_idx } else { TranspositionTable::OPPONENTS_TO_MOVE });
+        if let Some(cached_score) = tt.probe(node_key, depth) {
+            simple_profiler::record_tt_lookup(true);
+            return cached_score;
+        }
+        simple_profiler::record_tt_lookup(false);
+
+        if Self::is_terminal(board, our_snake_id, config) || (depth == 0 && !extends_quiescence(board, our_snake_id, depth_from_root, q_extensions, config)) {
+            let score = Self::evaluate_state(board, our_snake_id, config, None, depth_from_root).for_player(our_idx);
+            tt.store(node_key, score, depth, BoundType::Exact, None);
+            return score;
+        }
+        // Unstable position at depth 0: extend by one ply (quiescence)
+        let ply = if depth == 0 { Ply { depth: 1, q_extensions: q_extensions + 1, ..ply } } else { ply };
+        let depth = ply.depth;
+
+        let bound = if is_max { alpha } else { beta };
+        let cutoff = Self::lazy_cutoff(board, our_idx, ply, bound, is_max, ctx, || {
+            Self::best_reply_search(board, ply.with_depth(0), alpha, beta, is_max, ctx, scratch)
+        });
+        if let Some(score) = cutoff {
+            return score;
+        }
+
+        // (player, move) pairs of this ply: our moves, or every reply of every active opponent
+        let mut replies: Vec<(usize, Direction)> = Vec::new();
+        let mut child_base: Option<Board> = None;
+        if is_max {
+            let mut moves = Self::generate_legal_moves(board, &board.snakes[our_idx], config);
+            let current_pos = &board.snakes[our_idx].body[0];
+            let tt_best_move = tt.probe_with_move(node_key, depth).and_then(|(_, mv)| mv);
+            order_moves(&mut moves, tt_best_move, killers, Some((history, current_pos, true)), Some((board, our_idx)), depth, config);
+            replies.extend(moves.iter().map(|&mv| (our_idx, mv)));
+        } else {
+            let active = Self::determine_active_snakes(board, our_snake_id, turn, depth, Some(board_hash), config);
+            for idx in active.into_iter().filter(|&idx| idx != our_idx && board.snakes[idx].health > 0) {
+                let mut moves = Self::generate_legal_moves(board, &board.snakes[idx], config);
+                if moves.is_empty() {
+                    // Trapped opponents die whoever replies
+                    child_base.get_or_insert_with(|| scratch.child_board(board)).snakes[idx].health = 0;
+                    continue;
+                }
+                let current_pos = &board.snakes[idx].body[0];
+                order_moves(&mut moves, None, killers, Some((history, current_pos, false)), Some((board, idx)), depth, config);
+                replies.extend(moves.iter().map(|&mv| (idx, mv)));
+            }
+        }
+        let base = child_base.as_ref().unwrap_or(board);
+
+        if replies.is_empty() {
+            // Nobody can move: we are trapped (max) or every opponent is (min)
+            let score = if is_max {
+                let mut dead_board = scratch.child_board(base);
+                dead_board.snakes[our_idx].health = 0;
+                let score = Self::evaluate_state(&dead_board, our_snake_id, config, None, depth_from_root).for_player(our_idx);
+                scratch.recycle_board(dead_board);
+                score
+            } else {
+                Self::best_reply_search(base, ply.next(), alpha, beta, true, ctx, scratch)
+            };
+            if let Some(child_base) = child_base {
+                scratch.recycle_board(child_base);
+            }
+            return score;
+        }
+
+        let mut best = if is_max { i32::MIN } else { i32::MAX };
+        let mut best_move: Option<Direction> = None;
+        let mut had_cutoff = false;
+        for &(player_idx, mv) in &replies {
+            let mut child_board = scratch.child_board(base);
+            Self::apply_move(&mut child_board, player_idx, mv, config);
+            Self::advance_game_state(&mut child_board, config);
+            let eval = Self::best_reply_search(&child_board, ply.next(), alpha, beta, !is_max, ctx, scratch);
+            scratch.recycle_board(child_board);
+
+            if shared.is_stopped() {
+                if let Some(child_base) = child_base {
+                    scratch.recycle_board(child_base);
+                }
+                return eval;
+            }
+
+            if (is_max && eval > best) || (!is_max && eval < best) {
+                best = eval;
+                best_move = Some(mv);
+            }
+            if is_max {
+                alpha = alpha.max(eval);
+            } else {
+                beta = beta.min(eval);
+            }
+            if beta <= alpha {
+                killers.record_killer(depth, mv, config);
+                history.update(is_max, &board.snakes[player_idx].body[0], mv, depth, true);
+                simple_profiler::record_alpha_beta_cutoff();
+                had_cutoff = true;
+                break;
+            }
+        }
+        if let Some(child_base) = child_base {
+            scratch.recycle_board(child_base);
+        }
+
+        let bound_type = match (had_cutoff, is_max) {
+            (false, _) => BoundType::Exact,
+            (true, true) => BoundType::Lower,
+            (true, false) => BoundType::Upper,
+        };
+        // Only our own moves are meaningful as the TT move (it orders our moves above)
+        tt.store(node_key, best, depth, bound_type, best_move.filter(|_| is_max));
+        best
+    }
+
     /// Parallel multiplayer MaxN search using rayon
     /// Evaluates root moves in parallel, then uses sequential MaxN for subtrees
     fn parallel_multiplayer_search(
         board: &Board,
         you: &Battlesnake,
-        turn: i32,
         depth: u8,
-        shared: &Arc<SharedSearchState>,
-        config: &Config,
-        tt: &Arc<TranspositionTable>,
-        _history: &mut HistoryTable,  // Unused in parallel search (each thread has its own)
+        root_tts: &[Arc<TranspositionTable>],
         pv_move: Option<Direction>,
+        ctx: &SearchContext,
     ) {
+        let SearchContext { config, tt, killers, history, shared, .. } = *ctx;
         // Order moves using PV move from previous iteration
-        let mut legal_moves = Self::generate_legal_moves(board, you, config);
+        let mut legal_moves = Self::root_moves(board, you, shared, config);
 
         if !legal_moves.is_empty() {
-            // Order root moves by PV only (no killers/history at root for parallel search)
-            legal_moves = order_moves(legal_moves, pv_move, &KillerMoveTable::new(config), None, depth, config);
+            // Order root moves by PV and SEE only (no killers/history at root for parallel search)
+            let see = board.snake_index(&you.id).map(|idx| (board, idx));
+            order_moves(&mut legal_moves, pv_move, &KillerMoveTable::new(config), None, see, depth, config);
         }
 
         if legal_moves.is_empty() {
             info!("No legal moves available - choosing least-bad fallback");
             // When trapped, try to pick a move that's at least in-bounds
             // Priority: any in-bounds move > out-of-bounds move
-            let fallback_move = Direction::all()
+            let fallback_move = Direction::ALL
                 .iter()
                 .find(|&&dir| {
                     let next = dir.apply(&you.body[0]);
@@ -3882,44 +5677,55 @@ impl Bot {
             return;
         }
 
+        let use_best_reply = Self::uses_best_reply(board.alive_snakes().count(), config);
         info!(
-            "Evaluating {} legal moves in parallel (multiplayer MaxN)",
-            legal_moves.len()
+            "Evaluating {} legal moves in parallel (multiplayer {})",
+            legal_moves.len(),
+            if use_best_reply { "BRS" } else { "MaxN" }
         );
 
         let our_snake_id = &you.id;
-        let our_idx = board
-            .snakes
-            .iter()
-            .position(|s| &s.id == our_snake_id)
-            .unwrap_or(0);
+        let our_idx = board.snake_index(our_snake_id).unwrap_or(0);
+        let engine = if use_best_reply { RootEngine::BestReply } else { RootEngine::MaxN };
+        let split = Self::splits_second_ply(legal_moves.len(), config);
 
         // Parallel evaluation of root moves
-        legal_moves.par_iter().enumerate().for_each(|(_idx, &mv)| {
-            // Each thread needs its own killers and history tables (can't share mutable refs across threads)
-            let mut local_killers = KillerMoveTable::new(config);
-            let mut local_history = HistoryTable::new(board.width as u32, board.height as u32);
+        let scores: Vec<(Direction, i32)> = legal_moves.par_iter().map(|&mv| {
+            let clock = TaskClock::start();
+            let move_idx = Self::direction_to_index(mv, config);
+            let (tt, private_tables) = Self::subtree_tables(move_idx, tt, root_tts, history, config);
+            let (killers, history) = private_tables.as_ref().map_or((killers, history), |(killers, history)| (killers, history));
+            let ctx = &SearchContext { tt, killers, history, ..*ctx };
 
             let mut child_board = board.clone();
             Self::apply_move(&mut child_board, our_idx, mv, config);
 
-            let tuple = Self::maxn_search(
-                &child_board,
-                our_snake_id,
-                turn,
-                depth.saturating_sub(1),
-                1, // One ply down from root
-                our_idx,
-                config,
-                tt,
-                &mut local_killers,
-                &mut local_history,
-            );
-            let our_score = tuple.for_player(our_idx);
+            let second_ply = if split { Self::search_second_ply(&child_board, our_idx, depth, engine, ctx) } else { None };
+            // One ply down from root
+            let ply = Ply::new(depth.saturating_sub(1), 1);
+            let (score, leaf) = second_ply.unwrap_or_else(|| with_search_scratch(|scratch| if use_best_reply {
+                (Self::best_reply_search(&child_board, ply, i32::MIN, i32::MAX, false, ctx, scratch), None)
+            } else {
+                let tuple = Self::maxn_search(&child_board, ply, our_idx, ctx, scratch);
+                (tuple.for_player(our_idx), tuple.leaf)
+            }));
+            if let (Some(leaf), false) = (leaf, shared.is_stopped()) {
+                shared.record_leaf_breakdown(depth, mv, &leaf);
+            }
+            let our_score = score.saturating_add(shared.root_bias_for(move_idx));
 
             // Atomic update of best move and score together (prevents race conditions)
-            shared.try_update_best(Self::direction_to_index(mv, config), our_score);
-        });
+            // Skipped if the budget expired while this subtree was searched
+            if !config.strategy.deterministic_parallel && !shared.is_stopped() {
+                shared.try_update_best(move_idx, our_score);
+                shared.record_root_score(depth, mv, our_score);
+            }
+            clock.finish(shared);
+            (mv, our_score)
+        }).collect();
+        if config.strategy.deterministic_parallel {
+            Self::publish_root_scores(scores, depth, shared, config);
+        }
 
         let (_, final_score) = shared.get_best();
         info!(
@@ -3934,25 +5740,25 @@ impl Bot {
         board: &Board,
         you: &Battlesnake,
         depth: u8,
-        shared: &Arc<SharedSearchState>,
-        config: &Config,
-        tt: &Arc<TranspositionTable>,
-        _history: &mut HistoryTable,  // Unused in parallel search (each thread has its own)
+        root_tts: &[Arc<TranspositionTable>],
         pv_move: Option<Direction>,
+        ctx: &SearchContext,
     ) {
+        let SearchContext { config, tt, killers, history, shared, .. } = *ctx;
         // Order moves using PV move from previous iteration
-        let mut legal_moves = Self::generate_legal_moves(board, you, config);
+        let mut legal_moves = Self::root_moves(board, you, shared, config);
 
         if !legal_moves.is_empty() {
-            // Order root moves by PV only (no killers/history at root for parallel search)
-            legal_moves = order_moves(legal_moves, pv_move, &KillerMoveTable::new(config), None, depth, config);
+            // Order root moves by PV and SEE only (no killers/history at root for parallel search)
+            let see = board.snake_index(&you.id).map(|idx| (board, idx));
+            order_moves(&mut legal_moves, pv_move, &KillerMoveTable::new(config), None, see, depth, config);
         }
 
         if legal_moves.is_empty() {
             info!("No legal moves available - choosing least-bad fallback");
             // When trapped, try to pick a move that's at least in-bounds
             // Priority: any in-bounds move > out-of-bounds move
-            let fallback_move = Direction::all()
+            let fallback_move = Direction::ALL
                 .iter()
                 .find(|&&dir| {
                     let next = dir.apply(&you.body[0]);
@@ -3974,67 +5780,305 @@ impl Bot {
         );
 
         let our_snake_id = &you.id;
-        let our_idx = board
-            .snakes
-            .iter()
-            .position(|s| &s.id == our_snake_id)
-            .unwrap_or(0);
+        let our_idx = board.snake_index(our_snake_id).unwrap_or(0);
+        let split = Self::splits_second_ply(legal_moves.len(), config);
 
         // Parallel evaluation of root moves
-        legal_moves.par_iter().enumerate().for_each(|(_idx, &mv)| {
-            // Create local killer table and history table for this subtree (each thread gets its own)
-            let mut local_killers = KillerMoveTable::new(config);
-            let mut local_history = HistoryTable::new(board.width as u32, board.height as u32);
+        let scores: Vec<(Direction, i32)> = legal_moves.par_iter().map(|&mv| {
+            let clock = TaskClock::start();
+            let move_idx = Self::direction_to_index(mv, config);
+            let (tt, private_tables) = Self::subtree_tables(move_idx, tt, root_tts, history, config);
+            let (killers, history) = private_tables.as_ref().map_or((killers, history), |(killers, history)| (killers, history));
+            let ctx = &SearchContext { tt, killers, history, ..*ctx };
 
             let mut child_board = board.clone();
             Self::apply_move(&mut child_board, our_idx, mv, config);
 
-            let score = Self::alpha_beta_minimax(
-                &child_board,
-                our_snake_id,
-                depth.saturating_sub(1),
-                1,  // One ply down from root after applying move
-                i32::MIN,
-                i32::MAX,
-                false,
-                config,
-                tt,
-                &mut local_killers,
-                &mut local_history,
-            );
+            let second_ply = if split { Self::search_second_ply(&child_board, our_idx, depth, RootEngine::AlphaBeta, ctx).map(|(score, _)| score) } else { None };
+            // One ply down from root after applying move
+            let ply = Ply::new(depth.saturating_sub(1), 1);
+            let score = second_ply.unwrap_or_else(|| with_search_scratch(|scratch| {
+                Self::alpha_beta_minimax(&child_board, ply, i32::MIN, i32::MAX, false, ctx, scratch)
+            }));
+            let score = score.saturating_add(shared.root_bias_for(move_idx));
+
+            // Atomic update of best move and score together (prevents race conditions)
+            // Skipped if the budget expired while this subtree was searched
+            if !config.strategy.deterministic_parallel && !shared.is_stopped() {
+                shared.try_update_best(move_idx, score);
+                shared.record_root_score(depth, mv, score);
+            }
+            clock.finish(shared);
+            (mv, score)
+        }).collect();
+        if config.strategy.deterministic_parallel {
+            Self::publish_root_scores(scores, depth, shared, config);
+        }
+
+        let (_, final_score) = shared.get_best();
+        info!("Parallel 1v1 search complete: best score = {}", final_score);
+    }
+
+    /// Whether parallel root searches split each root move's subtree at the second ply:
+    /// strategy.split_second_ply, and more pool threads than root moves to keep busy
+    /// Never in deterministic mode, where replies racing for the shared bound would make
+    /// the searched tree depend on thread timing
+    fn splits_second_ply(root_moves: usize, config: &Config) -> bool {
+        config.strategy.split_second_ply && !config.strategy.deterministic_parallel && rayon::current_num_threads() > root_moves
+    }
+
+    /// Tables the subtree of a parallel root move is searched with
+    /// Shared ones (the search's TT, killers and history) unless `root_tts` holds per-root-move
+    /// TTs (deterministic mode): then the move's own TT, fresh killers and a private copy of
+    /// the history, so no sibling's timing can change what this subtree reads
+    fn subtree_tables<'a>(
+        move_idx: u8,
+        tt: &'a Arc<TranspositionTable>,
+        root_tts: &'a [Arc<TranspositionTable>],
+        history: &HistoryTable,
+        config: &Config,
+    ) -> (&'a Arc<TranspositionTable>, Option<(KillerMoveTable, HistoryTable)>) {
+        match root_tts.get(move_idx as usize) {
+            Some(root_tt) => (root_tt, Some((KillerMoveTable::new(config), history.clone()))),
+            None => (tt, None),
+        }
+    }
+
+    /// Deterministic mode: publishes the root scores of a finished parallel iteration in move
+    /// index order, so the best move is the lowest-indexed of the top-scoring moves whichever
+    /// task finished first. Nothing is published if the iteration was stopped.
+    fn publish_root_scores(mut scores: Vec<(Direction, i32)>, depth: u8, shared: &SharedSearchState, config: &Config) {
+        if shared.is_stopped() {
+            return;
+        }
+        scores.sort_by_key(|&(mv, _)| Self::direction_to_index(mv, config));
+        for (mv, score) in scores {
+            shared.try_update_best(Self::direction_to_index(mv, config), score);
+            shared.record_root_score(depth, mv, score);
+        }
+    }
+
+    /// Our score for the root move that led to `child`, with the node below the root expanded
+    /// here and each of its moves (root move × first reply) searched as its own rayon task
+    ///
+    /// Replicates that node of `engine`: alpha-beta and best-reply take the lowest reply, and
+    /// the best reply so far bounds its siblings (beta), so they fail high instead of being
+    /// searched exactly; MaxN takes our best continuation with pessimistic tie-breaking.
+    /// None when the node is not worth splitting (its replies would be frontier nodes), does
+    /// not expand moves here (terminal, IDAPOS switching to alpha-beta, a trapped or passing
+    /// mover) or has fewer than two; the caller then searches the root move in one task.
+    /// MaxN also returns the leaf breakdown of the winning line (explain mode, see ScoreTuple).
+    fn search_second_ply(
+        child: &Board,
+        our_idx: usize,
+        depth: u8,
+        engine: RootEngine,
+        ctx: &SearchContext,
+    ) -> Option<(i32, Option<Arc<DetailedScore>>)> {
+        let SearchContext { our_snake_id, turn, config, tt, shared, .. } = *ctx;
+        if depth < 3 || child.snakes.len() < 2 || Self::is_terminal(child, our_snake_id, config) {
+            return None;
+        }
+        let child_depth = depth - 1;
+        let child_hash = TranspositionTable::hash_board(child);
+
+        // (player, move) pairs of the node, as the engine generates them
+        let mut replies: Vec<(usize, Direction)> = Vec::new();
+        let mover = match engine {
+            RootEngine::AlphaBeta => {
+                let opponent = child.snakes.iter().enumerate().find(|(idx, s)| *idx != our_idx && s.health > 0)?.0;
+                replies.extend(Self::generate_legal_moves(child, &child.snakes[opponent], config).iter().map(|&mv| (opponent, mv)));
+                opponent
+            }
+            RootEngine::BestReply => {
+                let active = Self::determine_active_snakes(child, our_snake_id, turn, child_depth, Some(child_hash), config);
+                for idx in active.into_iter().filter(|&idx| idx != our_idx && child.snakes[idx].health > 0) {
+                    let moves = Self::generate_legal_moves(child, &child.snakes[idx], config);
+                    if moves.is_empty() {
+                        return None;
+                    }
+                    replies.extend(moves.iter().map(|&mv| (idx, mv)));
+                }
+                TranspositionTable::OPPONENTS_TO_MOVE
+            }
+            RootEngine::MaxN => {
+                let active = Self::determine_active_snakes(child, our_snake_id, turn, child_depth, Some(child_hash), config);
+                let pairs = Self::pairs_players(active.len(), config.idapos.min_snakes_for_alpha_beta, config);
+                if !active.contains(&our_idx) || pairs {
+                    return None;
+                }
+                replies.extend(Self::generate_legal_moves(child, &child.snakes[our_idx], config).iter().map(|&mv| (our_idx, mv)));
+                our_idx
+            }
+        };
+        if replies.len() < 2 {
+            return None;
+        }
+        let node_key = TranspositionTable::node_key(child_hash, child, mover);
+        if let Some(cached_score) = tt.probe(node_key, child_depth) {
+            return Some((cached_score, None));
+        }
+        // The expanded node counts like any other
+        shared.count_node_and_check_stop(config.timing.stop_check_interval_nodes);
+
+        // Lowest reply so far (alpha-beta and best-reply): the siblings' beta
+        let bound = AtomicI32::new(i32::MAX);
+        let results: Vec<ScoreTuple> = replies
+            .par_iter()
+            .map(|&(player, mv)| {
+                let mut grandchild = child.clone();
+                Self::apply_move(&mut grandchild, player, mv, config);
+                with_search_scratch(|scratch| match engine {
+                    RootEngine::AlphaBeta | RootEngine::BestReply => {
+                        Self::advance_game_state(&mut grandchild, config);
+                        let beta = bound.load(Ordering::Acquire);
+                        let ply = Ply::new(depth - 2, 2);
+                        let score = if engine == RootEngine::AlphaBeta {
+                            Self::alpha_beta_minimax(&grandchild, ply, i32::MIN, beta, true, ctx, scratch)
+                        } else {
+                            Self::best_reply_search(&grandchild, ply, i32::MIN, beta, true, ctx, scratch)
+                        };
+                        bound.fetch_min(score, Ordering::AcqRel);
+                        ScoreTuple { scores: vec![score], leaf: None }
+                    }
+                    RootEngine::MaxN => {
+                        let next = (our_idx + 1) % grandchild.snakes.len();
+                        Self::maxn_search(&grandchild, Ply::new(child_depth, 1), next, ctx, scratch)
+                    }
+                })
+            })
+            .collect();
+
+        // Aborted replies are discarded by the root, never stored in the TT
+        let stopped = shared.is_stopped();
+        let result = if engine == RootEngine::MaxN {
+            let mut best = ScoreTuple::new_with_value(child.snakes.len(), i32::MIN);
+            for tuple in results {
+                if tuple.for_player(our_idx) > best.for_player(our_idx) {
+                    best = tuple;
+                } else if tuple.for_player(our_idx) == best.for_player(our_idx) {
+                    best = Self::pessimistic_tie_break(&best, &tuple, our_idx);
+                }
+            }
+            if !stopped {
+                tt.store(node_key, best.for_player(our_idx), child_depth, BoundType::Exact, None);
+            }
+            (best.for_player(our_idx), best.leaf)
+        } else {
+            let (best_idx, score) = results.iter().map(|tuple| tuple.scores[0]).enumerate().min_by_key(|&(_, score)| score)?;
+            // The opponent's refutation orders the next iteration and extends the PV
+            let best_move = (engine == RootEngine::AlphaBeta).then_some(replies[best_idx].1);
+            if !stopped {
+                tt.store(node_key, score, child_depth, BoundType::Exact, best_move);
+            }
+            (score, None)
+        };
+        Some(result)
+    }
+
+    /// Offline analysis: scores every root move with both 1v1 alpha-beta and MaxN at a fixed depth
+    /// Only applies to true duels and to multiplayer positions where IDAPOS leaves two active
+    /// snakes (locality boundary cases), i.e. exactly where the search could pick either strategy.
+    /// Returns None when the position is not such a boundary case.
+    pub fn compare_root_strategies(
+        board: &Board,
+        our_snake_id: &str,
+        turn: i32,
+        depth: u8,
+        config: &Config,
+    ) -> Option<StrategyComparison> {
+        let our_idx = board.snake_index(our_snake_id)?;
+        let alive_snakes = board.alive_snakes().count();
+        let active_snakes = Self::determine_active_snakes(board, our_snake_id, turn, depth, None, config);
+
+        // Duels always qualify (root strategy is chosen by alive count); larger games only
+        // when locality masking leaves exactly one opponent in range
+        let opponent_idx = if alive_snakes == config.strategy.min_snakes_for_1v1 {
+            board.snakes.iter().enumerate().find(|(idx, s)| *idx != our_idx && s.health > 0)?.0
+        } else if active_snakes.len() == config.idapos.min_snakes_for_alpha_beta {
+            active_snakes.iter().copied().find(|&idx| idx != our_idx)?
+        } else {
+            return None;
+        };
+
+        let you = &board.snakes[our_idx];
+        let legal_moves = Self::generate_legal_moves(board, you, config);
+        if legal_moves.is_empty() {
+            return None;
+        }
+
+        // Alpha-beta sees only the two active snakes, exactly like alpha_beta_for_two_snakes
+        let mut duel_board = board.clone();
+        for (idx, snake) in duel_board.snakes.iter_mut().enumerate() {
+            if idx != our_idx && idx != opponent_idx {
+                snake.health = 0;
+            }
+        }
+
+        // MaxN must not shortcut into alpha-beta, otherwise both columns would be identical
+        let mut maxn_config = config.clone();
+        maxn_config.idapos.min_snakes_for_alpha_beta = 0;
+
+        // Fresh search state: no clock, no root bias, nodes counted but unlimited
+        let shared = SharedSearchState::new();
+
+        let mut moves = Vec::with_capacity(legal_moves.len());
+        for &mv in &legal_moves {
+            let ab_tt = Arc::new(TranspositionTable::new(100_000));
+            let killers = KillerMoveTable::new(config);
+            let history = HistoryTable::new(board.width as u32, board.height);
+            let mut ab_child = duel_board.clone();
+            Self::apply_move(&mut ab_child, our_idx, mv, config);
+            let ctx = SearchContext { our_snake_id, turn, config, tt: &ab_tt, killers: &killers, history: &history, shared: &shared };
+            let ply = Ply::new(depth.saturating_sub(1), 1);
+            let alpha_beta_score =
+                with_search_scratch(|scratch| Self::alpha_beta_minimax(&ab_child, ply, i32::MIN, i32::MAX, false, &ctx, scratch));
+
+            let maxn_tt = Arc::new(TranspositionTable::new(100_000));
+            let killers = KillerMoveTable::new(&maxn_config);
+            let history = HistoryTable::new(board.width as u32, board.height);
+            let mut maxn_child = board.clone();
+            Self::apply_move(&mut maxn_child, our_idx, mv, &maxn_config);
+            let ctx = SearchContext { config: &maxn_config, tt: &maxn_tt, killers: &killers, history: &history, ..ctx };
+            let maxn_score = with_search_scratch(|scratch| Self::maxn_search(&maxn_child, ply, our_idx, &ctx, scratch)).for_player(our_idx);
 
-            // Atomic update of best move and score together (prevents race conditions)
-            shared.try_update_best(Self::direction_to_index(mv, config), score);
-        });
+            moves.push(StrategyMoveScores { direction: mv, alpha_beta_score, maxn_score });
+        }
 
-        let (_, final_score) = shared.get_best();
-        info!("Parallel 1v1 search complete: best score = {}", final_score);
+        let alpha_beta_best = moves.iter().max_by_key(|m| m.alpha_beta_score).map(|m| m.direction)?;
+        let maxn_best = moves.iter().max_by_key(|m| m.maxn_score).map(|m| m.direction)?;
+
+        Some(StrategyComparison {
+            alive_snakes,
+            active_snakes,
+            opponent_idx,
+            moves,
+            alpha_beta_best,
+            maxn_best,
+        })
     }
 
     /// Public evaluation for analysis tools - provides detailed score breakdown
+    /// Errors when `our_snake_id` is not on the board or has no body
     pub fn evaluate_move_detailed(
         board: &Board,
         our_snake_id: &str,
         test_move: Direction,
         config: &Config,
-    ) -> DetailedScore {
+    ) -> Result<DetailedScore, BotError> {
         // Apply the move to get resulting board state
         let mut test_board = board.clone();
-        let our_idx = test_board.snakes.iter().position(|s| s.id == our_snake_id)
-            .expect("Our snake not found");
+        let our_idx = test_board.snake_index(our_snake_id).ok_or_else(|| BotError::SnakeNotFound(our_snake_id.to_string()))?;
 
         let snake = &test_board.snakes[our_idx];
-        let head = snake.body[0];
-        let new_head = match test_move {
-            Direction::Up => Coord { x: head.x, y: head.y + 1 },
-            Direction::Down => Coord { x: head.x, y: head.y - 1 },
-            Direction::Left => Coord { x: head.x - 1, y: head.y },
-            Direction::Right => Coord { x: head.x + 1, y: head.y },
-        };
+        let head = *snake.body.first().ok_or_else(|| BotError::EmptyBody(our_snake_id.to_string()))?;
+        let new_head = test_move.apply(&head);
 
-        // Apply move
+        // Apply move (leaving the board is a death, as in the rules)
         test_board.snakes[our_idx].body.insert(0, new_head);
-        if test_board.food.contains(&new_head) {
+        if Self::is_out_of_bounds(&new_head, test_board.width, test_board.height) {
+            test_board.snakes[our_idx].health = 0;
+        } else if test_board.is_food(new_head) {
             test_board.food.retain(|f| *f != new_head);
             test_board.snakes[our_idx].health = config.game_rules.health_on_food as i32;
             test_board.snakes[our_idx].length += 1;
@@ -4043,62 +6087,83 @@ impl Bot {
             test_board.snakes[our_idx].health = test_board.snakes[our_idx].health.saturating_sub(config.game_rules.health_loss_per_turn as i32);
         }
 
-        // Compute individual score components
-        let health = Self::compute_health_score(&test_board, our_idx, &[], config);
-        let space = Self::compute_space_score(&test_board, our_idx, &[], config);
-        let control = Self::compute_control_score(&test_board, our_idx, config);
-        let length = test_board.snakes[our_idx].length * config.scores.weight_length;
+        Ok(Self::detailed_score(&test_board, our_idx, config))
+    }
 
-        let space_cache: HashMap<usize, usize> = HashMap::new();
-        let attack = Self::compute_attack_score(&test_board, our_idx, config, &space_cache);
+    /// Evaluation breakdown of `board` for the snake at `our_idx`, as the heuristic evaluation
+    /// scores it with every snake evaluated fully (undiscounted)
+    pub fn detailed_score(board: &Board, our_idx: usize, config: &Config) -> DetailedScore {
+        let field_sources: Vec<usize> = (0..board.snakes.len()).collect();
+        let field = DistanceField::build(board, &field_sources, config.hazard_walls.as_deref()).summarize(board, config);
+        let ctx = EvalContext { our_snake_id: &board.snakes[our_idx].id, config, active_snakes: None, depth_from_root: 0 };
+        evaluator::components(board, our_idx, &field, &field_sources, &ctx)
+    }
+}
 
-        let head_collision = if !test_board.snakes[our_idx].body.is_empty() {
-            Self::check_head_collision_danger(&test_board, our_idx, test_board.snakes[our_idx].body[0], config)
-        } else {
-            0
-        };
+/// Built-in move patterns for the hard ban list (see Bot::apply_ban_list)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BanPattern {
+    /// Region entered is smaller than our body
+    EnterDeadEnd,
+    /// Board corner with an opponent head nearby
+    CornerWithOpponentAdjacent,
+    /// At most one free neighbour after the move
+    LeaveOneLiberty,
+}
 
-        let (wall_penalty, center_bias) = if !test_board.snakes[our_idx].body.is_empty() {
-            let h = test_board.snakes[our_idx].body[0];
-            (
-                Self::compute_wall_penalty(h, test_board.width as i32, test_board.height as i32, test_board.snakes[our_idx].health, config),
-                Self::compute_center_bias(h, test_board.width as i32, test_board.height as i32, config),
-            )
-        } else {
-            (0, 0)
-        };
+impl BanPattern {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BanPattern::EnterDeadEnd => "enter-dead-end",
+            BanPattern::CornerWithOpponentAdjacent => "corner-with-opponent-adjacent",
+            BanPattern::LeaveOneLiberty => "leave-only-one-liberty",
+        }
+    }
+}
+
+/// A final move replaced by the one-ply safety verifier (see Bot::verify_one_ply_safety)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SafetyOverride {
+    pub original: Direction,
+    pub replacement: Direction,
+    /// Opponent reply combinations simulated for the original move (all of them fatal)
+    pub replies_checked: usize,
+}
+
+impl SafetyOverride {
+    pub fn describe(&self) -> String {
+        format!(
+            "safety-verifier: {} dies against all {} opponent replies, played {}",
+            self.original.as_str(),
+            self.replies_checked,
+            self.replacement.as_str()
+        )
+    }
+}
+
+/// Why a root move was pruned before the search (see Bot::prune_root_moves)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RootExclusion {
+    /// Dies (or is left without a free neighbour) against every opponent reply combination
+    ForcedDeath { replies: usize },
+    /// The region entered holds fewer cells than our body
+    NoRoom { space: usize, needed: usize },
+}
 
-        let survival = if test_board.snakes[our_idx].health > 0 { 0 } else { config.scores.score_survival_penalty };
-
-        // Weighted total
-        let total = survival
-            + (config.scores.score_survival_weight * survival as f32) as i32
-            + (config.scores.weight_space * space as f32) as i32
-            + (config.scores.weight_health * health as f32) as i32
-            + (config.scores.weight_control * control as f32) as i32
-            + (config.scores.weight_attack * attack as f32) as i32
-            + length
-            + head_collision
-            + wall_penalty
-            + center_bias;
-
-        DetailedScore {
-            total,
-            survival,
-            health,
-            space,
-            control,
-            attack,
-            length,
-            head_collision,
-            wall_penalty,
-            center_bias,
+impl RootExclusion {
+    pub fn describe(&self) -> String {
+        match self {
+            RootExclusion::ForcedDeath { replies } => format!("forced death against all {} opponent replies", replies),
+            RootExclusion::NoRoom { space, needed } => format!("only {} reachable cells for a body of {}", space, needed),
         }
     }
 }
 
-/// Detailed score breakdown for analysis
-#[derive(Debug, Clone)]
+/// Detailed score breakdown for analysis: the terms of the heuristic evaluation, computed by
+/// the same code as the search's leaf scores (see evaluator::components)
+/// space, health, control, attack and trap_setting are unweighted; `total` applies
+/// [scores] weights and includes `temporal_discount` (the change the discount makes)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct DetailedScore {
     pub total: i32,
     pub survival: i32,
@@ -4106,16 +6171,252 @@ pub struct DetailedScore {
     pub space: i32,
     pub control: i32,
     pub attack: i32,
+    pub trap_setting: i32,
     pub length: i32,
+    pub endgame_parity: i32,
     pub head_collision: i32,
     pub wall_penalty: i32,
     pub center_bias: i32,
+    pub corner_danger: i32,
+    pub length_advantage: i32,
+    pub growth_urgency: i32,
+    pub tail_chasing: i32,
+    pub articulation: i32,
+    pub trail_penalty: i32,
+    pub temporal_discount: i32,
+}
+
+impl DetailedScore {
+    /// Named components in a fixed order (total excluded)
+    pub fn components(&self) -> [(&'static str, i32); 18] {
+        [
+            ("survival", self.survival),
+            ("health", self.health),
+            ("space", self.space),
+            ("control", self.control),
+            ("attack", self.attack),
+            ("trap_setting", self.trap_setting),
+            ("length", self.length),
+            ("endgame_parity", self.endgame_parity),
+            ("head_collision", self.head_collision),
+            ("wall_penalty", self.wall_penalty),
+            ("center_bias", self.center_bias),
+            ("corner_danger", self.corner_danger),
+            ("length_advantage", self.length_advantage),
+            ("growth_urgency", self.growth_urgency),
+            ("tail_chasing", self.tail_chasing),
+            ("articulation", self.articulation),
+            ("trail_penalty", self.trail_penalty),
+            ("temporal_discount", self.temporal_discount),
+        ]
+    }
+
+    /// The breakdown of a leaf `depth_from_root` plies below the root, discounted like the
+    /// search discounts its score there
+    pub fn discounted(mut self, depth_from_root: u8, config: &Config) -> Self {
+        let total = evaluator::discounted(self.total, depth_from_root, config);
+        self.temporal_discount = total - self.total;
+        self.total = total;
+        self
+    }
+
+    /// Components that differ from `other`, as (name, self - other), largest difference first
+    pub fn differences(&self, other: &DetailedScore) -> Vec<(&'static str, i64)> {
+        let mut diffs: Vec<(&'static str, i64)> = self
+            .components()
+            .iter()
+            .zip(other.components().iter())
+            .map(|(&(name, a), &(_, b))| (name, i64::from(a) - i64::from(b)))
+            .filter(|&(_, diff)| diff != 0)
+            .collect();
+        diffs.sort_by_key(|&(_, diff)| std::cmp::Reverse(diff.abs()));
+        diffs
+    }
+}
+
+/// Root scores for one move under both search strategies
+#[derive(Debug, Clone)]
+pub struct StrategyMoveScores {
+    pub direction: Direction,
+    pub alpha_beta_score: i32,
+    pub maxn_score: i32,
+}
+
+/// Alpha-beta vs MaxN comparison for a single position (see compare_root_strategies)
+#[derive(Debug, Clone)]
+pub struct StrategyComparison {
+    pub alive_snakes: usize,
+    pub active_snakes: Vec<usize>,
+    pub opponent_idx: usize,
+    pub moves: Vec<StrategyMoveScores>,
+    pub alpha_beta_best: Direction,
+    pub maxn_best: Direction,
+}
+
+impl StrategyComparison {
+    /// True if the two strategies would play different moves
+    pub fn disagrees(&self) -> bool {
+        self.alpha_beta_best != self.maxn_best
+    }
+
+    /// How much alpha-beta thinks it loses by playing MaxN's choice
+    pub fn alpha_beta_regret(&self) -> i64 {
+        let score_of = |dir: Direction| {
+            self.moves.iter().find(|m| m.direction == dir).map(|m| m.alpha_beta_score as i64).unwrap_or(0)
+        };
+        score_of(self.alpha_beta_best) - score_of(self.maxn_best)
+    }
+
+    /// Largest per-move gap between the two strategies' scores (score normalization signal)
+    pub fn max_score_gap(&self) -> i64 {
+        self.moves
+            .iter()
+            .map(|m| (m.alpha_beta_score as i64 - m.maxn_score as i64).abs())
+            .max()
+            .unwrap_or(0)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_effective_branching_factor_drives_extrapolation() {
+        let it = |depth, nodes| IterationStats { depth, time_ms: 0, nodes };
+        assert_eq!(effective_branching_factor(&[it(1, 10)]), None);
+        assert_eq!(effective_branching_factor(&[it(1, 10), it(2, 40)]), Some(4.0));
+        // Odd/even swings are averaged over the last two iterations
+        assert_eq!(effective_branching_factor(&[it(1, 10), it(2, 80), it(3, 90)]), Some(3.0));
+        // TT hits can shrink an iteration, but the next one is never assumed cheaper
+        assert_eq!(effective_branching_factor(&[it(1, 50), it(2, 20)]), Some(1.0));
+
+        let mut estimator = AdaptiveTimeEstimator::new(0.01, 3.5, 0.1);
+        estimator.record_observation(3, 20.0);
+        let model = estimator.estimate(4, 2);
+        estimator.record_branching(Some(2.0));
+        assert_eq!(estimator.estimate(4, 2), 40);
+        assert_eq!(estimator.estimate(5, 2), 80);
+        assert_ne!(model, 40);
+    }
+
+    #[test]
+    fn test_timing_prior_replaces_the_model() {
+        let mut prior = DepthTimings::default();
+        prior.observe(2, 2.0, 10);
+        prior.observe(3, 10.0, 10);
+        let mut estimator = AdaptiveTimeEstimator::new(0.01, 3.5, 0.0).with_prior(Some(prior));
+        assert_eq!(estimator.estimate(2, 2), 2, "unobserved depth comes from earlier searches");
+        assert_eq!(estimator.estimate(5, 2), AdaptiveTimeEstimator::new(0.01, 3.5, 0.0).estimate(5, 2));
+
+        // A slower position keeps the growth earlier searches saw between the two depths
+        estimator.record_observation(2, 4.0);
+        assert_eq!(estimator.estimate(3, 2), 20);
+    }
+
+    #[test]
+    fn test_eating_stacks_the_tail_like_the_engine() {
+        let config = Config::default_hardcoded();
+        let mut board = crate::board_builder::BoardBuilder::new(7, 7)
+            .snake("a", &[(1, 1), (1, 0), (0, 0)])
+            .snake("b", &[(3, 1), (4, 1), (5, 1)])
+            .food(&[(1, 2), (1, 3)])
+            .build();
+        let c = |x, y| Coord { x, y };
+
+        // The tail leaves (0, 0) and the new tail at (1, 0) is stacked
+        Bot::apply_move(&mut board, 0, Direction::Up, &config);
+        assert_eq!(board.snakes[0].body, vec![c(1, 2), c(1, 1), c(1, 0), c(1, 0)]);
+        assert_eq!(board.snakes[0].length, 4);
+        // Eating again on the next turn keeps exactly one stacked copy
+        Bot::apply_move(&mut board, 0, Direction::Up, &config);
+        assert_eq!(board.snakes[0].body, vec![c(1, 3), c(1, 2), c(1, 1), c(1, 0), c(1, 0)]);
+
+        // A stacked tail does not move next turn: moving into it is not legal
+        let mut b = board.snakes[1].clone();
+        b.body = vec![c(2, 0), c(3, 0), c(4, 0)];
+        board.snakes[1] = b.clone();
+        assert!(!Bot::generate_legal_moves(&board, &b, &config).contains(&Direction::Left));
+    }
+
+    #[test]
+    fn test_quiescence_stops_at_the_caps() {
+        let mut config = Config::default_hardcoded();
+        config.game_mode = crate::game_mode::GameMode::Squad(crate::squad::SquadRules::default());
+        // Two allied heads side by side are unstable
+        let board = crate::board_builder::BoardBuilder::new(11, 11).snake("a", &[(5, 5)]).snake("b", &[(6, 5)]).food(&[(0, 10)]).build();
+        let cap = config.timing.max_quiescence_ply;
+        assert!(extends_quiescence(&board, "a", cap - 1, 0, &config));
+        assert!(!extends_quiescence(&board, "a", cap, 0, &config));
+        // ...and once the line has taken max_quiescence_extensions, whatever its depth
+        let extensions = config.timing.max_quiescence_extensions;
+        assert!(extends_quiescence(&board, "a", 1, extensions - 1, &config));
+        assert!(!extends_quiescence(&board, "a", 1, extensions, &config));
+
+        // Found by fuzzing: allies that keep within 2 cells made quiescence recurse until
+        // depth_from_root overflowed
+        let mut board = crate::board_builder::BoardBuilder::new(11, 24)
+            .snake("a", &[(2, 8)])
+            .snake("b", &[(3, 10)])
+            .health("a", 10)
+            .health("b", 52)
+            .food(&[(7, 5)])
+            .build();
+        for snake in board.snakes.iter_mut() {
+            snake.squad = Some("blue".to_string());
+        }
+        let shared = Arc::new(SharedSearchState::new());
+        let limits = SearchLimits::default().with_fixed_depth(Some(2));
+        Bot::compute_best_move_internal(&board, &board.snakes[0], 1802, shared, &config, &mut HistoryTable::new(11, 24), limits);
+    }
+
+    fn turn_moves(list: &[(&str, Direction)]) -> HashMap<String, Direction> {
+        list.iter().map(|&(id, dir)| (id.to_string(), dir)).collect()
+    }
+
+    #[test]
+    fn test_three_way_head_to_head() {
+        let config = Config::default_hardcoded();
+        // Three snakes meet at (5, 5); only the strictly longest survives
+        let board = crate::board_builder::BoardBuilder::new(11, 11)
+            .snake("a", &[(4, 5), (3, 5), (2, 5), (1, 5), (0, 5)])
+            .snake("b", &[(6, 5), (7, 5), (8, 5), (9, 5)])
+            .snake("c", &[(5, 4), (5, 3), (5, 2), (5, 1)])
+            .food(&[(0, 10)])
+            .build();
+        let moves = turn_moves(&[("a", Direction::Right), ("b", Direction::Left), ("c", Direction::Up)]);
+        let mut next = board.clone();
+        Bot::simulate_turn(&mut next, &moves, &config);
+        let alive: Vec<&str> = next.alive_snakes().map(|s| s.id.as_str()).collect();
+        assert_eq!(alive, vec!["a"]);
+
+        // A tie for the longest kills everyone on the square
+        let mut tied = board.clone();
+        tied.snakes[1].body.push(Coord { x: 10, y: 5 });
+        tied.snakes[1].length = 5;
+        Bot::simulate_turn(&mut tied, &moves, &config);
+        assert_eq!(tied.alive_snakes().count(), 0);
+    }
+
+    #[test]
+    fn test_chained_collisions_resolve_on_one_board() {
+        let config = Config::default_hardcoded();
+        // a and b meet head-on on the food: both eat, stay equal and die. c runs into a's neck,
+        // which still blocks although a dies. d takes the cell a's tail left and survives.
+        let mut board = crate::board_builder::BoardBuilder::new(11, 11)
+            .snake("a", &[(3, 3), (2, 3), (1, 3)])
+            .snake("b", &[(5, 3), (6, 3), (7, 3)])
+            .snake("c", &[(3, 4), (3, 5), (3, 6)])
+            .snake("d", &[(0, 3), (0, 2), (0, 1)])
+            .food(&[(4, 3), (10, 10)])
+            .build();
+        let moves = turn_moves(&[("a", Direction::Right), ("b", Direction::Left), ("c", Direction::Down), ("d", Direction::Right)]);
+        Bot::simulate_turn(&mut board, &moves, &config);
+        let alive: Vec<&str> = board.alive_snakes().map(|s| s.id.as_str()).collect();
+        assert_eq!(alive, vec!["d"]);
+        assert_eq!(board.food, vec![Coord { x: 10, y: 10 }]);
+    }
+
     #[test]
     fn test_pack_unpack_positive_score() {
         let move_idx = 2u8; // Left
@@ -4252,4 +6553,915 @@ mod tests {
         assert_eq!(final_score, 9000, "Best score should be from highest update");
         assert_eq!(final_move, 1, "Best move should match the highest score (9 % 4 = 1)");
     }
+
+    fn test_snake(id: &str, body: Vec<Coord>) -> Battlesnake {
+        Battlesnake {
+            id: id.to_string(),
+            name: id.to_string(),
+            health: 100,
+            head: body[0],
+            length: body.len() as i32,
+            body,
+            latency: "0".to_string(),
+            shout: None,
+            squad: None,
+        }
+    }
+
+    fn test_board(snakes: Vec<Battlesnake>) -> Board {
+        Board { height: 11, width: 11, food: vec![], snakes, hazards: vec![], occupancy: None }
+    }
+
+    #[test]
+    fn test_see_uncontested_square_is_zero() {
+        let config = Config::default_hardcoded();
+        let board = test_board(vec![
+            test_snake("us", vec![Coord { x: 5, y: 5 }, Coord { x: 5, y: 4 }, Coord { x: 5, y: 3 }]),
+            test_snake("them", vec![Coord { x: 1, y: 1 }, Coord { x: 1, y: 0 }, Coord { x: 2, y: 0 }]),
+        ]);
+
+        assert_eq!(Bot::static_exchange_eval(&board, 0, Coord { x: 5, y: 6 }, &config), 0);
+    }
+
+    #[test]
+    fn test_see_losing_exchange_is_full_penalty() {
+        let config = Config::default_hardcoded();
+        let board = test_board(vec![
+            test_snake("us", vec![Coord { x: 5, y: 5 }, Coord { x: 5, y: 4 }, Coord { x: 5, y: 3 }]),
+            test_snake("them", vec![
+                Coord { x: 6, y: 6 }, Coord { x: 7, y: 6 }, Coord { x: 8, y: 6 }, Coord { x: 9, y: 6 },
+            ]),
+        ]);
+
+        let value = Bot::static_exchange_eval(&board, 0, Coord { x: 5, y: 6 }, &config);
+        assert_eq!(value, config.scores.head_collision_penalty);
+    }
+
+    #[test]
+    fn test_see_duel_tie_ranks_between_losing_and_surviving_behind() {
+        let config = Config::default_hardcoded();
+        let us = test_snake("us", vec![Coord { x: 5, y: 5 }, Coord { x: 5, y: 4 }, Coord { x: 5, y: 3 }]);
+        let target = Coord { x: 5, y: 6 };
+        // A longer and an equal head next to the target, and a longer snake far away
+        let see = |them: Vec<Coord>| Bot::static_exchange_eval(&test_board(vec![us.clone(), test_snake("them", them)]), 0, target, &config);
+        let longer = |head: Coord| (0..4).map(|i| Coord { x: head.x + i, y: head.y }).collect::<Vec<_>>();
+
+        let losing = see(longer(Coord { x: 6, y: 6 }));
+        let tie = see(vec![Coord { x: 6, y: 6 }, Coord { x: 7, y: 6 }, Coord { x: 8, y: 6 }]);
+        let survive_behind = see(longer(Coord { x: 1, y: 1 }));
+        assert!(losing < tie && tie < survive_behind, "losing {} < tie {} < behind {}", losing, tie, survive_behind);
+    }
+
+    #[test]
+    fn test_see_winning_exchange_is_positive_and_forced_kill_scores_higher() {
+        let config = Config::default_hardcoded();
+        let us = test_snake("us", vec![Coord { x: 5, y: 8 }, Coord { x: 5, y: 7 }, Coord { x: 5, y: 6 }]);
+        let them = test_snake("them", vec![Coord { x: 5, y: 10 }, Coord { x: 4, y: 10 }]);
+
+        // Shorter opponent on the top edge still has (6,10) to dodge into
+        let optional = test_board(vec![us.clone(), them.clone()]);
+        // Same position with (6,10) blocked: (5,9) is its only square
+        let forced = test_board(vec![
+            us,
+            them,
+            test_snake("blocker", vec![
+                Coord { x: 8, y: 10 }, Coord { x: 7, y: 10 }, Coord { x: 6, y: 10 }, Coord { x: 6, y: 9 },
+            ]),
+        ]);
+
+        let target = Coord { x: 5, y: 9 };
+        let optional_value = Bot::static_exchange_eval(&optional, 0, target, &config);
+        let forced_value = Bot::static_exchange_eval(&forced, 0, target, &config);
+
+        assert!(optional_value > 0, "winning exchange should be positive, got {}", optional_value);
+        assert!(forced_value > optional_value, "forced kill {} should beat optional kill {}", forced_value, optional_value);
+    }
+
+    #[test]
+    fn test_squad_mates_are_passable_and_not_head_to_head_threats() {
+        let mut config = Config::default_hardcoded();
+        let mut us = test_snake("us", vec![Coord { x: 5, y: 5 }, Coord { x: 5, y: 4 }, Coord { x: 5, y: 3 }]);
+        let mut mate = test_snake("mate", vec![
+            Coord { x: 6, y: 6 }, Coord { x: 6, y: 5 }, Coord { x: 6, y: 4 }, Coord { x: 7, y: 4 },
+        ]);
+        us.squad = Some("red".to_string());
+        mate.squad = Some("red".to_string());
+        let board = test_board(vec![us.clone(), mate]);
+
+        // Without squad rules the mate is an ordinary longer opponent
+        assert!(!Bot::generate_legal_moves(&board, &us, &config).contains(&Direction::Right));
+        assert_eq!(Bot::static_exchange_eval(&board, 0, Coord { x: 5, y: 6 }, &config), config.scores.head_collision_penalty);
+
+        config.game_mode = GameMode::Squad(crate::squad::SquadRules::default());
+        assert!(Bot::generate_legal_moves(&board, &us, &config).contains(&Direction::Right));
+        assert_eq!(Bot::static_exchange_eval(&board, 0, Coord { x: 5, y: 6 }, &config), 0);
+    }
+
+    #[test]
+    fn test_hazard_walls_block_moves_and_space() {
+        let board = crate::board_builder::parse_board(
+            "
+            . . # . .
+            . . # . .
+            a a> # . .
+            . . . . .
+            . . # . .
+        ",
+        )
+        .unwrap();
+        let you = board.snakes[0].clone();
+        let head = you.body[0];
+        let mut config = Config::default_hardcoded();
+        assert!(Bot::generate_legal_moves(&board, &you, &config).contains(&Direction::Right));
+
+        config.hazard_walls = Some(Arc::new(HazardWalls::new(&board)));
+        let walls = config.hazard_walls.as_deref();
+        assert!(!Bot::generate_legal_moves(&board, &you, &config).contains(&Direction::Right));
+        assert_eq!(Bot::flood_fill_bfs(&board, head, 0, None, None), 25);
+        assert_eq!(Bot::flood_fill_bfs(&board, head, 0, None, walls), 25 - 4);
+    }
+
+    #[test]
+    fn test_snail_mode_lays_and_decays_trails() {
+        let config = Config { game_mode: GameMode::Snail, ..Config::default_hardcoded() };
+        let mut board = test_board(vec![test_snake("us", vec![
+            Coord { x: 5, y: 5 }, Coord { x: 5, y: 4 }, Coord { x: 5, y: 3 },
+        ])]);
+        let up = HashMap::from([("us".to_string(), Direction::Up)]);
+
+        Bot::simulate_turn(&mut board, &up, &config);
+        assert_eq!(snail::trail_depth(&board.hazards, Coord { x: 5, y: 3 }), 3, "as high as the snake");
+        Bot::simulate_turn(&mut board, &up, &config);
+        assert_eq!(snail::trail_depth(&board.hazards, Coord { x: 5, y: 3 }), 2);
+        assert_eq!(snail::trail_depth(&board.hazards, Coord { x: 5, y: 4 }), 3);
+
+        // Standing in a trail costs one penalty per stack
+        board.snakes[0].body[0] = Coord { x: 5, y: 4 };
+        let in_trail = Bot::evaluate_state(&board, "us", &config, None, 0);
+        board.hazards.clear();
+        let clear = Bot::evaluate_state(&board, "us", &config, None, 0);
+        assert_eq!(in_trail.for_player(0) - clear.for_player(0), 3 * config.scores.trail_reentry_penalty);
+    }
+
+    #[test]
+    fn test_eliminated_snakes_keep_their_index_but_leave_the_board() {
+        let config = Config::default_hardcoded();
+        let mut board = test_board(vec![
+            test_snake("us", vec![Coord { x: 5, y: 5 }, Coord { x: 5, y: 4 }, Coord { x: 5, y: 3 }, Coord { x: 5, y: 2 }]),
+            test_snake("them", vec![Coord { x: 5, y: 7 }, Coord { x: 6, y: 7 }, Coord { x: 7, y: 7 }]),
+        ]);
+        // Heads meet on (5, 6) and the shorter snake loses
+        let moves = HashMap::from([("us".to_string(), Direction::Up), ("them".to_string(), Direction::Down)]);
+        Bot::simulate_turn(&mut board, &moves, &config);
+
+        assert_eq!(board.snakes.len(), 2);
+        assert_eq!(board.snake_index("them"), Some(1));
+        assert_eq!((board.snakes[1].health, board.snakes[1].body.len()), (0, 0));
+        assert!(!board.occupied(Coord { x: 6, y: 7 }), "the loser's body is gone");
+        assert_eq!(board.alive_snakes().count(), 1);
+    }
+
+    #[test]
+    fn test_order_moves_puts_losing_head_to_head_last() {
+        let config = Config::default_hardcoded();
+        let board = test_board(vec![
+            test_snake("us", vec![Coord { x: 5, y: 5 }, Coord { x: 5, y: 4 }, Coord { x: 5, y: 3 }]),
+            test_snake("them", vec![
+                Coord { x: 5, y: 7 }, Coord { x: 5, y: 8 }, Coord { x: 5, y: 9 }, Coord { x: 5, y: 10 },
+            ]),
+        ]);
+        let killers = KillerMoveTable::new(&config);
+
+        let mut ordered: MoveList = [Direction::Up, Direction::Left, Direction::Right].iter().copied().collect();
+        order_moves(
+            &mut ordered,
+            None,
+            &killers,
+            None,
+            Some((&board, 0)),
+            1,
+            &config,
+        );
+
+        assert_eq!(ordered.last(), Some(&Direction::Up), "losing head-to-head should be searched last");
+    }
+
+    #[test]
+    fn test_killer_table_keeps_recent_killers_per_depth() {
+        let config = Config::default_hardcoded();
+        let mut killers = KillerMoveTable::new(&config);
+        for mv in [Direction::Up, Direction::Left, Direction::Up, Direction::Right] {
+            killers.record_killer(3, mv, &config);
+        }
+
+        // Two slots per depth: Up was pushed out by Left and Right
+        assert_eq!(killers.killer_flags(3), [false, false, true, true]);
+        assert!(killers.is_killer(3, Direction::Right) && !killers.is_killer(2, Direction::Right));
+
+        killers.age_killers();
+        assert_eq!(killers.killer_flags(2), [false, false, true, true]);
+        assert_eq!(killers.killer_flags(3), [false; 4]);
+        assert_eq!(killers.killer_flags(200), [false; 4], "depths past the table have no killers");
+    }
+
+    #[test]
+    fn test_parallel_tasks_share_killers_and_history() {
+        let config = Config::default_hardcoded();
+        let killers = KillerMoveTable::new(&config);
+        let history = HistoryTable::new(11, 11);
+        let from = Coord { x: 5, y: 5 };
+
+        // Cutoffs found by one task order the moves of every other task
+        std::thread::scope(|scope| {
+            for mv in [Direction::Up, Direction::Left, Direction::Down, Direction::Right] {
+                let (killers, history, config) = (&killers, &history, &config);
+                scope.spawn(move || {
+                    killers.record_killer(2, mv, config);
+                    for _ in 0..100 {
+                        history.update(true, &from, Direction::Up, 3, true);
+                    }
+                });
+            }
+        });
+        let recorded = killers.killer_flags(2).iter().filter(|&&killer| killer).count();
+        assert_eq!(recorded, config.move_ordering.killer_moves_per_depth.min(4));
+        assert_eq!(history.get_score(true, &from, Direction::Up), 4 * 100 * 8, "no update is lost");
+        assert_eq!(history.clone().get_score(true, &from, Direction::Up), 3200);
+    }
+
+    #[test]
+    fn test_ban_list_vetoes_corner_next_to_opponent() {
+        let mut config = Config::default_hardcoded();
+        config.ban_list.corner_with_opponent_adjacent = true;
+        config.ban_list.leave_one_liberty = true;
+        let board = test_board(vec![
+            test_snake("us", vec![Coord { x: 1, y: 0 }, Coord { x: 2, y: 0 }, Coord { x: 3, y: 0 }]),
+            test_snake("them", vec![Coord { x: 0, y: 2 }, Coord { x: 0, y: 3 }, Coord { x: 0, y: 4 }]),
+        ]);
+        let you = board.snakes[0].clone();
+
+        let violations = Bot::banned_patterns(&board, &you, Direction::Left, &config);
+        assert!(violations.contains(&BanPattern::CornerWithOpponentAdjacent));
+        assert!(violations.contains(&BanPattern::LeaveOneLiberty));
+
+        let chosen = Bot::apply_ban_list(&board, &you, Direction::Left, &[Direction::Left, Direction::Up], 5, &config);
+        assert_eq!(chosen, Direction::Up);
+    }
+
+    #[test]
+    fn test_ban_list_keeps_move_when_everything_is_banned() {
+        let mut config = Config::default_hardcoded();
+        config.ban_list.corner_with_opponent_adjacent = true;
+        let board = test_board(vec![
+            test_snake("us", vec![Coord { x: 1, y: 0 }, Coord { x: 2, y: 0 }, Coord { x: 3, y: 0 }]),
+            test_snake("them", vec![Coord { x: 0, y: 2 }, Coord { x: 0, y: 3 }, Coord { x: 0, y: 4 }]),
+        ]);
+        let you = board.snakes[0].clone();
+
+        let chosen = Bot::apply_ban_list(&board, &you, Direction::Left, &[Direction::Left], 5, &config);
+        assert_eq!(chosen, Direction::Left);
+    }
+
+    #[test]
+    fn test_ban_list_replacement_must_be_as_safe_as_the_searched_move() {
+        let mut config = Config::default_hardcoded();
+        config.ban_list.enter_dead_end = true;
+        // Left is a one-cell pocket. Right opens the big bottom region but meets the head of a
+        // longer snake; up is a corridor into the small region between the wall rows. The wall
+        // ends in a stacked tail, so none of its cells clears within the flood fill's horizon
+        let mut wall = vec![
+            (0, 6), (2, 2), (1, 3), (2, 4), (4, 4), (1, 4), (0, 4), (5, 4), (6, 4),
+            (1, 6), (2, 6), (3, 6), (4, 6), (5, 6), (6, 6),
+        ];
+        wall.extend([(0, 5); 20]);
+        let board = crate::board_builder::BoardBuilder::new(7, 7)
+            .snake("us", &[(3, 3), (3, 2), (3, 1)])
+            .snake("big", &[(5, 3), (5, 2), (5, 1), (5, 0), (6, 0)])
+            .snake("wall", &wall)
+            .build();
+        let you = board.snakes[0].clone();
+        let legal = [Direction::Left, Direction::Up, Direction::Right];
+        assert_eq!(Bot::banned_patterns(&board, &you, Direction::Left, &config), vec![BanPattern::EnterDeadEnd]);
+
+        let space = |mv: Direction| {
+            let mut child = board.clone();
+            Bot::apply_move(&mut child, 0, mv, &config);
+            Bot::flood_fill_bfs(&child, mv.apply(&you.body[0]), 0, None, None)
+        };
+        assert!(space(Direction::Right) > space(Direction::Up));
+        assert!(Bot::static_exchange_eval(&board, 0, Direction::Right.apply(&you.body[0]), &config) < 0);
+
+        assert_eq!(Bot::apply_ban_list(&board, &you, Direction::Left, &legal, 5, &config), Direction::Up);
+        // Without the corridor the pocket stands rather than the losing exchange
+        assert_eq!(Bot::apply_ban_list(&board, &you, Direction::Left, &[Direction::Left, Direction::Right], 5, &config), Direction::Left);
+    }
+
+    #[test]
+    fn test_root_pruning_excludes_dead_end_unless_everything_loses() {
+        let config = Config::default_hardcoded();
+        let pocket_wall = [(0, 4), (0, 3), (0, 2), (0, 1), (1, 1), (1, 2), (1, 3), (1, 4)];
+        let board = test_board(vec![
+            test_snake("us", vec![Coord { x: 2, y: 0 }, Coord { x: 3, y: 0 }, Coord { x: 4, y: 0 }]),
+            test_snake("them", pocket_wall.iter().map(|&(x, y)| Coord { x, y }).collect()),
+        ]);
+        let you = board.snakes[0].clone();
+
+        // Left enters the two-cell pocket under their body; Up is open board
+        let pruned = Bot::prune_root_moves(&board, &you, &[Direction::Left, Direction::Up], &config);
+        assert_eq!(pruned, vec![(Direction::Left, RootExclusion::NoRoom { space: 2, needed: 3 })]);
+        assert!(Bot::prune_root_moves(&board, &you, &[Direction::Left], &config).is_empty());
+
+        let shared = SharedSearchState::new();
+        shared.exclude_root_move(Bot::direction_to_index(Direction::Left, &config));
+        assert_eq!(&Bot::root_moves(&board, &you, &shared, &config)[..], &[Direction::Up]);
+    }
+
+    #[test]
+    fn test_stable_result_stops_when_next_iteration_is_expensive() {
+        let config = Config::default_hardcoded();
+        let tolerance = config.timing.no_improvement_tolerance;
+        // Next iteration ~60ms: with 100ms left it would take over half of it
+        assert!(Bot::stable_enough_to_stop(tolerance, 60, 100, &config));
+        assert!(!Bot::stable_enough_to_stop(tolerance, 40, 100, &config), "cheap enough to keep going");
+        assert!(!Bot::stable_enough_to_stop(tolerance - 1, 60, 100, &config), "not stable for long enough");
+    }
+
+    #[test]
+    fn test_node_budget_stops_after_first_iteration() {
+        let config = Config::default_hardcoded();
+        let board = test_board(vec![
+            test_snake("us", vec![Coord { x: 2, y: 2 }, Coord { x: 2, y: 1 }, Coord { x: 2, y: 0 }]),
+            test_snake("them", vec![Coord { x: 8, y: 8 }, Coord { x: 8, y: 9 }, Coord { x: 8, y: 10 }]),
+        ]);
+        let you = board.snakes[0].clone();
+        let shared = Arc::new(SharedSearchState::new());
+        let mut history = HistoryTable::new(11, 11);
+
+        Bot::compute_best_move_internal(
+            &board,
+            &you,
+            3,
+            shared.clone(),
+            &config,
+            &mut history,
+            SearchLimits::default().with_max_nodes(Some(1)),
+        );
+
+        assert!(shared.nodes() > 0);
+        assert_eq!(shared.current_depth.load(Ordering::Acquire), config.timing.initial_depth);
+    }
+
+    #[test]
+    fn test_best_reply_search_replaces_maxn_on_crowded_boards() {
+        let mut config = Config::default_hardcoded();
+        assert!(!Bot::uses_best_reply(2, &config), "duels stay alpha-beta");
+        assert!(!Bot::uses_best_reply(3, &config));
+        assert!(Bot::uses_best_reply(4, &config), "auto switches at brs_min_snakes");
+        config.game_mode = GameMode::Squad(crate::squad::SquadRules::default());
+        assert!(!Bot::uses_best_reply(4, &config), "squad-mates are not opponents");
+
+        let mut board = test_board(vec![
+            test_snake("us", vec![Coord { x: 5, y: 5 }, Coord { x: 5, y: 4 }, Coord { x: 5, y: 3 }]),
+            test_snake("a", vec![Coord { x: 2, y: 6 }, Coord { x: 1, y: 6 }, Coord { x: 0, y: 6 }]),
+            test_snake("b", vec![Coord { x: 8, y: 6 }, Coord { x: 9, y: 6 }, Coord { x: 10, y: 6 }]),
+            test_snake("c", vec![Coord { x: 5, y: 8 }, Coord { x: 5, y: 9 }, Coord { x: 5, y: 10 }]),
+        ]);
+        board.food = vec![Coord { x: 3, y: 3 }, Coord { x: 7, y: 3 }];
+        let you = board.snakes[0].clone();
+        let search = |multiplayer_search: &str| {
+            let mut config = Config::default_hardcoded();
+            config.strategy.multiplayer_search = multiplayer_search.to_string();
+            let shared = Arc::new(SharedSearchState::new());
+            let mut history = HistoryTable::new(11, 11);
+            let limits = SearchLimits::default().with_fixed_depth(Some(2));
+            Bot::compute_best_move_internal(&board, &you, 3, shared.clone(), &config, &mut history, limits);
+            (Bot::index_to_direction(shared.get_best().0, &config), shared.iterations()[0].nodes)
+        };
+
+        // Same nominal depth, a fraction of the tree: only one opponent replies per ply
+        let (brs_move, brs_nodes) = search("brs");
+        let (_, maxn_nodes) = search("maxn");
+        assert!(Bot::generate_legal_moves(&board, &you, &Config::default_hardcoded()).contains(&brs_move));
+        assert!(brs_nodes * 2 < maxn_nodes, "BRS {} nodes vs MaxN {}", brs_nodes, maxn_nodes);
+    }
+
+    #[test]
+    fn test_duel_search_records_principal_variation_and_tt_stats() {
+        let config = Config::default_hardcoded();
+        let board = test_board(vec![
+            test_snake("us", vec![Coord { x: 2, y: 2 }, Coord { x: 2, y: 1 }, Coord { x: 2, y: 0 }]),
+            test_snake("them", vec![Coord { x: 8, y: 8 }, Coord { x: 8, y: 9 }, Coord { x: 8, y: 10 }]),
+        ]);
+        let you = board.snakes[0].clone();
+        let shared = Arc::new(SharedSearchState::new());
+        let mut history = HistoryTable::new(11, 11);
+
+        Bot::compute_best_move_internal(
+            &board,
+            &you,
+            3,
+            shared.clone(),
+            &config,
+            &mut history,
+            SearchLimits::default().with_fixed_depth(Some(5)),
+        );
+
+        let (best_idx, _) = shared.get_best();
+        let pv = shared.principal_variation();
+        assert_eq!(pv.first(), Some(&Bot::index_to_direction(best_idx, &config)));
+        assert!(pv.len() > 1 && pv.len() <= 4 * 2, "duel PV follows the TT: {:?}", pv);
+        let (probes, hits) = shared.tt_stats();
+        assert!(probes > 0 && hits <= probes);
+
+        // Every iteration scores each legal root move; the deepest one agrees with the choice
+        let table = SearchResultV1::new(3, pv[0], 0, 4, 0).with_candidates(&shared.root_scores()).candidates.unwrap();
+        let depths: Vec<u8> = table.iter().map(|iteration| iteration.depth).collect();
+        let last_depth = shared.current_depth.load(Ordering::Acquire);
+        assert_eq!(depths, (config.timing.initial_depth..=last_depth).collect::<Vec<_>>());
+        let deepest = table.last().unwrap();
+        let best = deepest.scores.iter().max_by_key(|c| c.score).unwrap();
+        assert_eq!(deepest.scores.len(), 3, "up, left and right are legal");
+        assert_eq!(best.score, shared.get_best().1);
+    }
+
+    #[test]
+    fn test_node_keys_separate_side_to_move_hazards_and_collisions() {
+        let board = test_board(vec![
+            test_snake("us", vec![Coord { x: 2, y: 2 }, Coord { x: 2, y: 1 }, Coord { x: 2, y: 0 }]),
+            test_snake("them", vec![Coord { x: 8, y: 8 }, Coord { x: 8, y: 9 }, Coord { x: 8, y: 10 }]),
+        ]);
+        let key = |board: &Board, to_move| TranspositionTable::node_key(TranspositionTable::hash_board(board), board, to_move);
+        let tt = TranspositionTable::new(16);
+        tt.store(key(&board, 0), 100, 3, BoundType::Exact, Some(Direction::Up));
+        assert_eq!(tt.probe(key(&board, 0), 3), Some(100));
+        assert_eq!(tt.probe(key(&board, 1), 0), None, "the other snake to move is another node");
+        assert_eq!(tt.probe(key(&board, TranspositionTable::OPPONENTS_TO_MOVE), 0), None);
+
+        let mut hazardous = board.clone();
+        hazardous.hazards.push(Coord { x: 5, y: 5 });
+        assert_eq!(tt.probe(key(&hazardous, 0), 0), None);
+
+        // Same slot, different node: a collision, never a hit, and the store takes the slot over
+        let stored = key(&board, 0);
+        let other = NodeKey { check: stored.check ^ 1, ..stored };
+        assert_eq!(tt.probe(other, 0), None);
+        assert_eq!(tt.collisions(), 1);
+        tt.store(other, -50, 1, BoundType::Exact, None);
+        assert_eq!(tt.probe(other, 1), Some(-50));
+        assert_eq!((tt.probe(stored, 0), tt.collisions()), (None, 2));
+    }
+
+    #[test]
+    fn test_second_ply_split_keeps_root_scores() {
+        let duel = test_board(vec![
+            test_snake("us", vec![Coord { x: 2, y: 2 }, Coord { x: 2, y: 1 }, Coord { x: 2, y: 0 }]),
+            test_snake("them", vec![Coord { x: 8, y: 8 }, Coord { x: 8, y: 9 }, Coord { x: 8, y: 10 }]),
+        ]);
+        let mut crowded = test_board(vec![
+            test_snake("us", vec![Coord { x: 5, y: 5 }, Coord { x: 5, y: 4 }, Coord { x: 5, y: 3 }]),
+            test_snake("a", vec![Coord { x: 2, y: 6 }, Coord { x: 1, y: 6 }, Coord { x: 0, y: 6 }]),
+            test_snake("b", vec![Coord { x: 8, y: 6 }, Coord { x: 9, y: 6 }, Coord { x: 10, y: 6 }]),
+        ]);
+        crowded.food = vec![Coord { x: 3, y: 3 }, Coord { x: 7, y: 3 }];
+
+        // More threads than root moves, so the split applies
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(8).build().unwrap();
+        let root_scores = |board: &Board, multiplayer_search: &str, split: bool| {
+            let mut config = Config::default_hardcoded();
+            config.strategy.multiplayer_search = multiplayer_search.to_string();
+            config.strategy.split_second_ply = split;
+            let shared = Arc::new(SharedSearchState::new());
+            let tt = Arc::new(TranspositionTable::new(100_000));
+            let killers = KillerMoveTable::new(&config);
+            let history = HistoryTable::new(11, 11);
+            let you = board.snakes[0].clone();
+            let ctx = SearchContext { our_snake_id: &you.id, turn: 3, config: &config, tt: &tt, killers: &killers, history: &history, shared: &shared };
+            pool.install(|| {
+                if board.snakes.len() == 2 {
+                    Bot::parallel_1v1_search(board, &you, 4, &[], None, &ctx);
+                } else {
+                    Bot::parallel_multiplayer_search(board, &you, 3, &[], None, &ctx);
+                }
+            });
+            let mut scores = shared.root_scores();
+            scores.sort_by_key(|&(_, mv, _)| Bot::direction_to_index(mv, &config));
+            scores
+        };
+
+        for (board, engine) in [(&duel, "auto"), (&crowded, "brs"), (&crowded, "maxn")] {
+            assert_eq!(root_scores(board, engine, true), root_scores(board, engine, false), "{} search", engine);
+        }
+    }
+
+    #[test]
+    fn test_deterministic_parallel_search_repeats_and_breaks_ties_by_index() {
+        let mut config = Config::default_hardcoded();
+        config.strategy.deterministic_parallel = true;
+
+        // Tied root scores: the lowest direction index wins whichever task finished first
+        let shared = SharedSearchState::new();
+        Bot::publish_root_scores(vec![(Direction::Right, 50), (Direction::Down, 50), (Direction::Left, 10)], 2, &shared, &config);
+        assert_eq!(shared.get_best(), (Bot::direction_to_index(Direction::Down, &config), 50));
+        let order: Vec<Direction> = shared.root_scores().iter().map(|&(_, mv, _)| mv).collect();
+        assert_eq!(order, vec![Direction::Down, Direction::Left, Direction::Right]);
+
+        // Fixed-depth searches stay parallel and repeat exactly
+        let mut board = test_board(vec![
+            test_snake("us", vec![Coord { x: 5, y: 5 }, Coord { x: 5, y: 4 }, Coord { x: 5, y: 3 }]),
+            test_snake("a", vec![Coord { x: 2, y: 6 }, Coord { x: 1, y: 6 }, Coord { x: 0, y: 6 }]),
+            test_snake("b", vec![Coord { x: 8, y: 6 }, Coord { x: 9, y: 6 }, Coord { x: 10, y: 6 }]),
+        ]);
+        board.food = vec![Coord { x: 3, y: 3 }, Coord { x: 7, y: 3 }];
+        let you = board.snakes[0].clone();
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(8).build().unwrap();
+        let search = || {
+            let shared = Arc::new(SharedSearchState::new());
+            let limits = SearchLimits::default().with_fixed_depth(Some(3));
+            pool.install(|| Bot::compute_best_move_internal(&board, &you, 3, shared.clone(), &config, &mut HistoryTable::new(11, 11), limits));
+            (shared.get_best(), shared.root_scores(), shared.principal_variation())
+        };
+        let first = search();
+        assert!(first.1.len() >= 3, "every iteration scores each root move: {:?}", first.1);
+        for _ in 0..3 {
+            assert_eq!(search(), first);
+        }
+    }
+
+    #[test]
+    fn test_answer_comes_from_the_newest_completed_iteration() {
+        let shared = SharedSearchState::new();
+        shared.force_initialize(1, i32::MIN + 1);
+        shared.current_depth.store(2, Ordering::Release);
+        assert_eq!(shared.answer(), (1, i32::MIN + 1, 2), "before any checkpoint: the fallback");
+
+        shared.try_update_best(2, 100);
+        shared.record_checkpoint(2, 40);
+        // Depth 3 in progress: one root move has finished and looks better than the rest
+        shared.current_depth.store(3, Ordering::Release);
+        shared.try_update_best(3, 900);
+        assert_eq!(shared.answer(), (2, 100, 2));
+
+        // Every iteration of a finished search is checkpointed
+        let board = test_board(vec![
+            test_snake("us", vec![Coord { x: 2, y: 2 }, Coord { x: 2, y: 1 }, Coord { x: 2, y: 0 }]),
+            test_snake("them", vec![Coord { x: 8, y: 8 }, Coord { x: 8, y: 9 }, Coord { x: 8, y: 10 }]),
+        ]);
+        let config = Config::default_hardcoded();
+        let shared = Arc::new(SharedSearchState::new());
+        let limits = SearchLimits::default().with_fixed_depth(Some(4));
+        Bot::compute_best_move_internal(&board, &board.snakes[0], 3, shared.clone(), &config, &mut HistoryTable::new(11, 11), limits);
+        let checkpoints = shared.checkpoints.lock().clone();
+        let depths: Vec<u8> = checkpoints.iter().map(|checkpoint| checkpoint.depth).collect();
+        assert_eq!(depths, shared.iterations().iter().map(|iteration| iteration.depth).collect::<Vec<_>>());
+        assert!(checkpoints.windows(2).all(|pair| pair[0].elapsed_ms <= pair[1].elapsed_ms));
+        let (move_idx, score) = shared.get_best();
+        assert_eq!(shared.answer(), (move_idx, score, *depths.last().unwrap()));
+    }
+
+    #[test]
+    fn test_explain_mode_carries_leaf_breakdowns_to_the_root() {
+        let mut config = Config::default_hardcoded();
+        config.debug.explain = true;
+
+        // MaxN: the tuple winning each node brings its leaf's breakdown up the tree
+        let crowded = crate::board_builder::BoardBuilder::new(11, 11)
+            .snake("s0", &[(5, 5), (5, 4), (5, 3)])
+            .snake("s1", &[(3, 5), (3, 4), (3, 3)])
+            .snake("s2", &[(7, 5), (7, 4), (7, 3)])
+            .build();
+        let (tt, shared) = (Arc::new(TranspositionTable::new(10_000)), SharedSearchState::new());
+        let (killers, history) = (KillerMoveTable::new(&config), HistoryTable::new(11, 11));
+        let ctx = SearchContext { our_snake_id: "s0", turn: 1, config: &config, tt: &tt, killers: &killers, history: &history, shared: &shared };
+        let tuple = with_search_scratch(|scratch| Bot::maxn_search(&crowded, Ply::new(2, 0), 0, &ctx, scratch));
+        assert!(tuple.leaf.is_some());
+
+        // Alpha-beta returns bare scores: each root move's PV is evaluated at its end instead
+        let duel = test_board(vec![
+            test_snake("us", vec![Coord { x: 2, y: 2 }, Coord { x: 2, y: 1 }, Coord { x: 2, y: 0 }]),
+            test_snake("them", vec![Coord { x: 8, y: 8 }, Coord { x: 8, y: 9 }, Coord { x: 8, y: 10 }]),
+        ]);
+        let search = |config: &Config| {
+            let shared = Arc::new(SharedSearchState::new());
+            let limits = SearchLimits::default().with_fixed_depth(Some(3));
+            Bot::compute_best_move_internal(&duel, &duel.snakes[0], 3, shared.clone(), config, &mut HistoryTable::new(11, 11), limits);
+            shared
+        };
+        let shared = search(&config);
+        let (_, _, depth) = shared.answer();
+        let moves: Vec<Direction> = shared.root_scores().iter().filter(|&&(d, _, _)| d == depth).map(|&(_, mv, _)| mv).collect();
+        assert!(!moves.is_empty());
+        assert!(moves.iter().all(|&mv| shared.leaf_breakdown(depth, mv).is_some()));
+
+        // Off by default: nothing is recorded
+        assert!(search(&Config::default_hardcoded()).leaf_breakdowns.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_completion_wakes_the_waiting_request() {
+        let shared = Arc::new(SharedSearchState::new());
+        let deadline = tokio::time::timeout(Duration::from_millis(20), shared.completed()).await;
+        assert!(deadline.is_err(), "the deadline ends the wait while the search runs");
+
+        // Woken by the search itself, not after a polling interval
+        let search = shared.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(5));
+            search.mark_complete();
+        });
+        let waited = Instant::now();
+        tokio::time::timeout(Duration::from_secs(5), shared.completed()).await.expect("completion wakes the request");
+        assert!(waited.elapsed() < Duration::from_secs(1));
+        shared.completed().await;
+    }
+
+    #[test]
+    fn test_task_clock_records_cpu_not_exceeding_wall() {
+        let shared = SharedSearchState::new();
+        let clock = TaskClock::start();
+        let mut acc = 0u64;
+        for i in 0..2_000_000u64 {
+            acc = acc.wrapping_mul(31).wrapping_add(i);
+        }
+        std::hint::black_box(acc);
+        clock.finish(&shared);
+
+        let (wall_us, cpu_us) = shared.task_times();
+        assert!(wall_us > 0, "busy task should take measurable wall time");
+        // Both clocks tick independently; allow a little slack for granularity
+        assert!(cpu_us <= wall_us + 1_000, "CPU time {}µs should not exceed wall time {}µs", cpu_us, wall_us);
+    }
+
+    #[test]
+    fn test_stop_flag_aborts_search_without_publishing_partial_results() {
+        let config = Config::default_hardcoded();
+        let board = test_board(vec![
+            test_snake("us", vec![Coord { x: 2, y: 2 }, Coord { x: 2, y: 1 }, Coord { x: 2, y: 0 }]),
+            test_snake("them", vec![Coord { x: 8, y: 8 }, Coord { x: 8, y: 9 }, Coord { x: 8, y: 10 }]),
+        ]);
+        let you = board.snakes[0].clone();
+        let shared = Arc::new(SharedSearchState::new());
+        shared.force_initialize(1, i32::MIN + 1);
+        shared.request_stop();
+        let mut history = HistoryTable::new(11, 11);
+
+        // Fixed depth 20 would take far too long if the stop flag were ignored
+        Bot::compute_best_move_internal(
+            &board,
+            &you,
+            3,
+            shared.clone(),
+            &config,
+            &mut history,
+            SearchLimits::default().with_fixed_depth(Some(20)),
+        );
+
+        assert_eq!(shared.get_best(), (1, i32::MIN + 1), "aborted iteration must not overwrite the best move");
+        assert!(shared.nodes() <= config.timing.stop_check_interval_nodes * 64);
+    }
+
+    #[test]
+    fn test_single_ply_fallback_avoids_losing_head_to_head() {
+        let config = Config::default_hardcoded();
+        let board = test_board(vec![
+            test_snake("us", vec![Coord { x: 5, y: 5 }, Coord { x: 5, y: 4 }, Coord { x: 5, y: 3 }]),
+            test_snake("them", vec![
+                Coord { x: 5, y: 7 }, Coord { x: 5, y: 8 }, Coord { x: 5, y: 9 }, Coord { x: 5, y: 10 },
+            ]),
+        ]);
+        let you = board.snakes[0].clone();
+
+        let fallback = Bot::single_ply_fallback(&board, &you, &[Direction::Up, Direction::Left, Direction::Right], &config);
+        assert_ne!(fallback, Direction::Up, "fallback must not walk into a losing head-to-head");
+    }
+
+    #[test]
+    fn test_food_safety_uses_path_distances() {
+        let config = Config::default_hardcoded();
+        // The hungry opponent is 2 cells from the food by Manhattan distance, but a long snake
+        // along y = 6 stands between them
+        let wall: Vec<(i32, i32)> = (1..=10).rev().map(|x| (x, 6)).collect();
+        let board = crate::board_builder::BoardBuilder::new(11, 11)
+            .snake("us", &[(5, 2), (5, 1), (5, 0)])
+            .snake("them", &[(5, 7), (5, 8), (5, 9), (5, 10)])
+            .snake("wall", &wall)
+            .health("them", 40)
+            .food(&[(5, 5)])
+            .build();
+        let (food, active) = (Coord { x: 5, y: 5 }, [0, 1, 2]);
+
+        assert!(!Bot::is_food_actually_safe(&board, food, 0, &active, None, &config), "Manhattan: contested");
+        let paths = DistanceField::build(&board, &active, None).food_paths(&board);
+        assert!(Bot::is_food_actually_safe(&board, food, 0, &active, Some(&paths), &config));
+        assert_eq!(Bot::food_distance(&board, 1, food, Some(&paths), &config), paths.distance(1, food).unwrap());
+        assert!(paths.distance(1, food).unwrap() > 3);
+    }
+
+    #[test]
+    fn test_lazy_cutoff_prunes_only_hopeless_quiet_nodes() {
+        let config = Config::default_hardcoded();
+        let board = crate::board_builder::BoardBuilder::new(11, 11)
+            .snake("us", &[(1, 1), (1, 2), (1, 3)])
+            .snake("them", &[(9, 9), (9, 8), (9, 7)])
+            .build();
+        let estimate = evaluator::lazy_score(&board, 0, &config, 1);
+        let hopeless = estimate + config.lazy_eval.razor_margin + 1;
+        let (tt, shared) = (Arc::new(TranspositionTable::new(1_000)), SharedSearchState::new());
+        let (killers, history) = (KillerMoveTable::new(&config), HistoryTable::new(11, 11));
+        let ctx = SearchContext { our_snake_id: "us", turn: 0, config: &config, tt: &tt, killers: &killers, history: &history, shared: &shared };
+        let cut = |depth: u8, alpha: i32, leaf: i32| Bot::lazy_cutoff(&board, 0, Ply::new(depth, 1), alpha, true, &ctx, || leaf);
+
+        assert_eq!(cut(1, hopeless, 0), Some(estimate), "futility: frontier fails with the lazy score");
+        assert_eq!(cut(1, estimate, 0), None, "inside the margin the frontier is searched");
+        assert_eq!(cut(2, hopeless, hopeless - 1), Some(hopeless - 1), "razoring: the leaf confirms the fail");
+        assert_eq!(cut(2, hopeless, hopeless + 1), None, "razoring: the leaf refutes the fail");
+        assert_eq!(cut(3, hopeless, 0), None, "beyond razor_depth nodes are searched");
+    }
+
+    #[test]
+    fn test_contained_panic_is_reported_to_poller() {
+        let shared = SharedSearchState::new();
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| panic!("search exploded")));
+
+        shared.record_panic(panic_payload_message(outcome.unwrap_err().as_ref()));
+        assert_eq!(shared.panic_message().as_deref(), Some("search exploded"));
+        assert!(shared.search_complete.load(Ordering::Acquire));
+    }
+
+    #[test]
+    fn test_safety_verifier_overrides_move_into_sealed_pocket() {
+        let config = Config::default_hardcoded();
+        let c = |x, y| Coord { x, y };
+        let board = test_board(vec![
+            test_snake("us", vec![c(1, 1), c(2, 1), c(3, 1)]),
+            // Stacked tails keep (0,2) and (0,0) occupied after the next move
+            test_snake("top", vec![c(3, 3), c(2, 3), c(1, 3), c(0, 3), c(0, 2), c(0, 2), c(0, 2)]),
+            test_snake("bottom", vec![c(4, 0), c(3, 0), c(2, 0), c(1, 0), c(0, 0), c(0, 0), c(0, 0)]),
+        ]);
+        let you = board.snakes[0].clone();
+        let legal = [Direction::Left, Direction::Up];
+
+        let safety_override = Bot::verify_one_ply_safety(&board, &you, Direction::Left, &legal, &config)
+            .expect("move into the sealed pocket must be overridden");
+        assert_eq!(safety_override.replacement, Direction::Up);
+        assert!(Bot::verify_one_ply_safety(&board, &you, Direction::Up, &legal, &config).is_none());
+    }
+
+    #[test]
+    fn test_score_hot_reload_swaps_weights_for_new_snapshots() {
+        let bot = Bot::new(Config::default_hardcoded()).with_admin_token(Some("secret".to_string()));
+        let before = bot.config();
+
+        let active = bot
+            .update_scores(&serde_json::json!({ "weight_space": 3.0 }))
+            .expect("valid override");
+        assert_eq!(active["weight_space"], serde_json::json!(3.0));
+        assert_eq!(bot.config().scores.weight_space, 3.0);
+        assert_eq!(before.scores.weight_space, Config::default_hardcoded().scores.weight_space,
+            "snapshots taken before the swap keep their weights");
+
+        assert!(bot.update_scores(&serde_json::json!({ "no_such_weight": 1 })).is_err());
+        assert!(bot.update_scores(&serde_json::json!({ "starvation_buffer_divisor": 0 })).is_err());
+        assert_eq!(bot.config().scores.weight_space, 3.0, "rejected patches change nothing");
+    }
+
+    #[test]
+    fn test_profiles_resolve_on_top_of_reloaded_scores() {
+        let bot = Bot::new(Config::default_hardcoded());
+        assert!(bot.config_for(Some("reckless")).is_err());
+
+        let aggressive = bot.config_for(Some("aggressive")).expect("configured profile");
+        assert_eq!(aggressive.scores.weight_attack, 16.0);
+        assert_eq!(bot.config_for(None).unwrap().scores.weight_attack, 8.0);
+
+        // Reloading base weights keeps each profile's overrides on top
+        bot.update_scores(&serde_json::json!({ "weight_health": 12.0, "weight_attack": 1.0 }))
+            .expect("valid override");
+        let aggressive = bot.config_for(Some("aggressive")).unwrap();
+        assert_eq!(aggressive.scores.weight_health, 12.0);
+        assert_eq!(aggressive.scores.weight_attack, 16.0);
+    }
+
+    #[test]
+    fn test_admin_token_check() {
+        assert!(!Bot::new(Config::default_hardcoded()).admin_enabled());
+        assert!(!Bot::new(Config::default_hardcoded()).with_admin_token(Some(String::new())).admin_enabled());
+
+        let bot = Bot::new(Config::default_hardcoded()).with_admin_token(Some("secret".to_string()));
+        assert!(bot.admin_token_matches("secret"));
+        assert!(!bot.admin_token_matches("secreT"));
+        assert!(!bot.admin_token_matches("secret2"));
+    }
+
+    #[test]
+    fn test_detailed_score_differences_are_ordered_by_magnitude() {
+        let base = DetailedScore {
+            total: 0,
+            survival: 0,
+            health: 500,
+            space: 100,
+            control: 10,
+            attack: 0,
+            length: 400,
+            head_collision: 0,
+            wall_penalty: 0,
+            center_bias: 0,
+            ..DetailedScore::default()
+        };
+        let other = DetailedScore { space: 1300, health: -400, control: 7, ..base.clone() };
+
+        assert_eq!(other.differences(&base), vec![("space", 1200), ("health", -900), ("control", -3)]);
+        assert!(base.differences(&base).is_empty());
+    }
+
+    #[test]
+    fn test_evaluate_move_detailed_reports_missing_snakes() {
+        let config = Config::default_hardcoded();
+        let mut board = crate::board_builder::BoardBuilder::new(7, 7).snake("us", &[(3, 3), (3, 2), (3, 1)]).snake("gone", &[(5, 5)]).build();
+        assert!(Bot::evaluate_move_detailed(&board, "us", Direction::Up, &config).is_ok());
+        assert!(matches!(Bot::evaluate_move_detailed(&board, "them", Direction::Up, &config), Err(BotError::SnakeNotFound(id)) if id == "them"));
+        board.snakes[1].body.clear();
+        assert!(matches!(Bot::evaluate_move_detailed(&board, "gone", Direction::Up, &config), Err(BotError::EmptyBody(_))));
+    }
+
+    #[test]
+    fn test_evaluate_move_detailed_scores_leaving_the_board_as_death() {
+        let config = Config::default_hardcoded();
+        let board = crate::board_builder::BoardBuilder::new(7, 7).snake("us", &[(0, 3), (1, 3), (2, 3)]).snake("them", &[(5, 5), (5, 4), (5, 3)]).build();
+        let score = Bot::evaluate_move_detailed(&board, "us", Direction::Left, &config).unwrap();
+        assert_eq!((score.total, score.survival), (config.scores.score_survival_penalty, config.scores.score_survival_penalty));
+    }
+
+    #[tokio::test]
+    async fn test_try_get_move_rejects_malformed_states() {
+        let bot = Bot::new(Config::default_hardcoded());
+        let board = crate::board_builder::BoardBuilder::new(7, 7).snake("us", &[(0, 3), (0, 2), (0, 1)]).snake("them", &[(5, 5), (5, 4), (5, 3)]).build();
+        let game = Game { id: "g".to_string(), ruleset: Default::default(), timeout: 500, map: String::new() };
+        let state = |you: Battlesnake, board: &Board| GameState { game: game.clone(), turn: 3, board: board.clone(), you };
+
+        let mut headless = board.snakes[0].clone();
+        headless.body.clear();
+        assert!(matches!(bot.try_get_move(None, state(headless, &board)).await, Err(BotError::InvalidState(_))));
+        let empty = Board { width: 0, ..board.clone() };
+        assert!(matches!(bot.try_get_move(None, state(board.snakes[0].clone(), &empty)).await, Err(BotError::InvalidState(_))));
+        assert!(matches!(bot.try_get_move(Some("reckless"), state(board.snakes[0].clone(), &board)).await, Err(BotError::UnknownProfile(_))));
+
+        let mv = bot.try_get_move(None, state(board.snakes[0].clone(), &board)).await.expect("valid state");
+        assert_ne!(mv, Direction::Left, "off the board");
+        assert_ne!(mv, Direction::Down, "into our neck");
+    }
+
+    #[tokio::test]
+    async fn test_debug_log_goes_to_the_configured_path() {
+        // Snake.toml's settings, logging to a temp file instead of its log_file_path
+        let mut config = Config::from_file("Snake.toml").expect("Snake.toml should be parseable");
+        let path = std::env::temp_dir().join(format!("bot_debug_log_{}.jsonl", std::process::id()));
+        config.debug.enabled = true;
+        config.debug.per_game_files = false;
+        config.debug.log_file_path = path.to_string_lossy().into_owned();
+        let bot = Bot::new(config);
+
+        let board = crate::board_builder::BoardBuilder::new(7, 7).snake("us", &[(3, 3), (3, 2), (3, 1)]).snake("them", &[(5, 5), (5, 4), (5, 3)]).build();
+        let game = Game { id: "g".to_string(), ruleset: Default::default(), timeout: 500, map: String::new() };
+        let state = GameState { game, turn: 3, board: board.clone(), you: board.snakes[0].clone() };
+        bot.try_get_move(None, state).await.expect("valid state");
+        bot.flush_debug_log().await;
+
+        let logged = std::fs::read_to_string(&path).expect("debug log written to the configured path");
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(logged.lines().count(), 1);
+    }
+
+    #[test]
+    fn test_deepest_line_fits_the_search_stack() {
+        // Snakes side by side in adjacent columns: every head is next to another, so every
+        // leaf is unstable and quiescence extends each line up to max_quiescence_ply
+        for snakes in [2, 4] {
+            let mut builder = crate::board_builder::BoardBuilder::new(snakes, 50);
+            for x in 0..snakes {
+                builder = builder.snake(&format!("s{}", x), &[(x, 2), (x, 1), (x, 0)]);
+            }
+            let board = builder.build();
+            let mut config = Config::default_hardcoded();
+            config.request_validation.max_snakes = snakes as usize;
+            config.timing.max_quiescence_extensions = config.timing.max_quiescence_ply;
+            let (depth, plies) = (config.timing.max_search_depth, config.timing.max_quiescence_ply);
+
+            let search = std::thread::Builder::new().stack_size(search_stack_bytes(&config)).spawn(move || {
+                let (tt, shared) = (Arc::new(TranspositionTable::new(10_000)), SharedSearchState::new());
+                let killers = KillerMoveTable::new(&config);
+                let history = HistoryTable::new(board.width as u32, board.height);
+                let ctx = SearchContext { our_snake_id: "s0", turn: 1, config: &config, tt: &tt, killers: &killers, history: &history, shared: &shared };
+                with_search_scratch(|scratch| {
+                    if board.snakes.len() == 2 {
+                        Bot::alpha_beta_minimax(&board, Ply::new(depth, 0), i32::MIN, i32::MAX, true, &ctx, scratch);
+                    } else {
+                        Bot::maxn_search(&board, Ply::new(depth, 0), 0, &ctx, scratch);
+                    }
+                });
+                shared.nodes()
+            });
+            let nodes = search.unwrap().join().expect("search overflowed its stack");
+            assert!(nodes > plies as u64, "{} snakes: the line ended before max_quiescence_ply", snakes);
+        }
+    }
 }
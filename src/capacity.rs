@@ -0,0 +1,230 @@
+// Fair sharing of CPU cores between concurrent games
+//
+// Every search used to run on the global rayon pool. With several games in flight, their
+// parallel root searches competed for the same workers and one deep search could starve the
+// others past their deadlines. A /move now takes a SearchSlot before searching:
+// - at most concurrency.max_concurrent_games searches run at once (later ones wait up to
+//   concurrency.slot_wait_ms, then answer with a one-ply move)
+// - each search gets its own rayon pool of (cores / running searches) threads, capped by
+//   concurrency.max_threads_per_game and by the cores the running searches leave free
+// A pool's size is fixed when its slot is taken, so a search cannot hand threads back when
+// another game arrives. Each slot therefore leaves one core for every slot still free: the
+// pools of all running searches never add up to more than the cores (with at least as many
+// cores as slots), and a lone game uses all but max_concurrent_games - 1 of them.
+// Idle pools are reused, most recent first, so worker threads (and their per-thread caches)
+// survive between turns; at most max_concurrent_games of them are kept. Their workers get
+// a stack deep enough for the longest line the configuration allows (bot::search_stack_bytes).
+
+use log::warn;
+use parking_lot::Mutex;
+use rayon::ThreadPool;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::bot;
+use crate::config::Config;
+
+/// Admission control and thread pools for concurrent searches
+pub struct GameCapacity {
+    permits: Arc<Semaphore>,
+    max_games: usize,
+    total_threads: usize,
+    /// 0 = no cap
+    max_threads_per_game: usize,
+    /// Stack of each pool worker (bot::search_stack_bytes)
+    stack_bytes: usize,
+    usage: Mutex<Usage>,
+}
+
+#[derive(Default)]
+struct Usage {
+    /// Searches currently holding a slot
+    active: usize,
+    /// Threads of their pools
+    threads: usize,
+    /// Idle pools with their thread counts, most recently returned last
+    idle_pools: VecDeque<(usize, Arc<ThreadPool>)>,
+}
+
+/// Permission to run one search, with the pool it must run on
+/// Dropping the slot returns the pool and frees the permit
+pub struct SearchSlot {
+    capacity: Arc<GameCapacity>,
+    pool: Option<Arc<ThreadPool>>,
+    threads: usize,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl GameCapacity {
+    pub fn new(config: &Config) -> Arc<Self> {
+        let total_threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        Self::with_threads(config, total_threads)
+    }
+
+    /// Capacity for an explicit number of cores (tests, tools)
+    pub fn with_threads(config: &Config, total_threads: usize) -> Arc<Self> {
+        let max_games = config.concurrency.max_concurrent_games.max(1);
+        Arc::new(GameCapacity {
+            permits: Arc::new(Semaphore::new(max_games)),
+            max_games,
+            total_threads: total_threads.max(1),
+            max_threads_per_game: config.concurrency.max_threads_per_game,
+            stack_bytes: bot::search_stack_bytes(config),
+            usage: Mutex::new(Usage::default()),
+        })
+    }
+
+    /// Searches currently running
+    pub fn active(&self) -> usize {
+        self.usage.lock().active
+    }
+
+    /// Thread share for a search when `active` searches run (including itself) and the
+    /// others hold `in_use` threads: cores / active, but no more than the free cores minus
+    /// one for each slot still free (at least one thread)
+    pub fn threads_for(&self, active: usize, in_use: usize) -> usize {
+        let mut share = self.total_threads / active.max(1);
+        if self.max_threads_per_game > 0 {
+            share = share.min(self.max_threads_per_game);
+        }
+        let free_slots = self.max_games.saturating_sub(active);
+        let available = self.total_threads.saturating_sub(in_use).saturating_sub(free_slots);
+        share.min(available).max(1)
+    }
+
+    /// Waits up to `wait` for a slot; None if every slot stayed busy
+    pub async fn acquire(self: &Arc<Self>, wait: Duration) -> Option<SearchSlot> {
+        let permit = tokio::time::timeout(wait, self.permits.clone().acquire_owned())
+            .await
+            .ok()?
+            .ok()?;
+
+        let (threads, idle) = {
+            let mut usage = self.usage.lock();
+            usage.active += 1;
+            let threads = self.threads_for(usage.active, usage.threads);
+            usage.threads += threads;
+            let idle = usage.idle_pools.iter().rposition(|(size, _)| *size == threads);
+            (threads, idle.and_then(|i| usage.idle_pools.remove(i)).map(|(_, pool)| pool))
+        };
+        Some(SearchSlot {
+            capacity: Arc::clone(self),
+            pool: idle.or_else(|| self.build_pool(threads)),
+            threads,
+            _permit: permit,
+        })
+    }
+
+    fn build_pool(&self, threads: usize) -> Option<Arc<ThreadPool>> {
+
+        match rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(move |i| format!("search-{}x-{}", threads, i))
+            .stack_size(self.stack_bytes)
+            .build()
+        {
+            Ok(pool) => Some(Arc::new(pool)),
+            Err(e) => {
+                warn!("Could not build a {}-thread search pool ({}), using the global pool", threads, e);
+                None
+            }
+        }
+    }
+}
+
+impl SearchSlot {
+    /// Worker threads this search may use
+    pub fn threads(&self) -> usize {
+        self.threads
+    }
+
+    /// Runs `f` inside this slot's pool (rayon calls in `f` stay on the pool)
+    pub fn install<R: Send>(&self, f: impl FnOnce() -> R + Send) -> R {
+        match &self.pool {
+            Some(pool) => pool.install(f),
+            None => f(),
+        }
+    }
+}
+
+impl Drop for SearchSlot {
+    fn drop(&mut self) {
+        let mut usage = self.capacity.usage.lock();
+        if let Some(pool) = self.pool.take() {
+            usage.idle_pools.push_back((self.threads, pool));
+            // The least recently used pools go (their workers exit once the pool is dropped)
+            while usage.idle_pools.len() > self.capacity.max_games {
+                usage.idle_pools.pop_front();
+            }
+        }
+        usage.active -= 1;
+        usage.threads -= self.threads;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capacity(max_games: usize, max_threads: usize, cores: usize) -> Arc<GameCapacity> {
+        let mut config = Config::default_hardcoded();
+        config.concurrency.max_concurrent_games = max_games;
+        config.concurrency.max_threads_per_game = max_threads;
+        GameCapacity::with_threads(&config, cores)
+    }
+
+    #[test]
+    fn test_cores_are_split_between_running_searches() {
+        let four_slots = capacity(4, 0, 8);
+        // A lone search leaves a core for each of the 3 other slots
+        assert_eq!(four_slots.threads_for(1, 0), 5);
+        assert_eq!(four_slots.threads_for(2, 0), 4);
+        // Whatever is left once the others hold their pools
+        assert_eq!(four_slots.threads_for(2, 5), 1);
+        assert_eq!(four_slots.threads_for(4, 6), 2);
+        assert_eq!(four_slots.threads_for(16, 8), 1);
+        assert_eq!(capacity(1, 0, 8).threads_for(1, 0), 8);
+        assert_eq!(capacity(1, 3, 8).threads_for(1, 0), 3);
+    }
+
+    #[tokio::test]
+    async fn test_slots_are_limited_and_returned() {
+        let capacity = capacity(2, 0, 4);
+        let wait = Duration::from_millis(20);
+
+        let first = capacity.acquire(wait).await.expect("free slot");
+        let second = capacity.acquire(wait).await.expect("free slot");
+        assert!(first.threads() + second.threads() <= 4, "{} + {} threads on 4 cores", first.threads(), second.threads());
+        assert_eq!(capacity.usage.lock().threads, first.threads() + second.threads());
+        assert_eq!(second.install(rayon::current_num_threads), second.threads());
+        assert!(capacity.acquire(wait).await.is_none(), "third search must wait");
+
+        drop(first);
+        let third = capacity.acquire(wait).await.expect("slot freed");
+        assert!(second.threads() + third.threads() <= 4);
+        assert_eq!(capacity.active(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_shares_never_exceed_the_cores_and_idle_pools_are_bounded() {
+        let capacity = capacity(3, 0, 6);
+        let wait = Duration::from_millis(20);
+
+        // Games arriving and leaving in every order keep the total within the cores
+        let mut slots = Vec::new();
+        for round in 0..12 {
+            if slots.len() == 3 || (round % 4 == 3 && !slots.is_empty()) {
+                slots.remove(round % slots.len());
+            }
+            slots.push(capacity.acquire(wait).await.expect("free slot"));
+            let total: usize = slots.iter().map(SearchSlot::threads).sum();
+            assert!(total <= 6, "round {}: {} threads on 6 cores", round, total);
+            assert_eq!(capacity.usage.lock().threads, total);
+        }
+        drop(slots);
+        assert_eq!(capacity.usage.lock().threads, 0);
+        assert!(capacity.usage.lock().idle_pools.len() <= 3);
+    }
+}
@@ -1,9 +1,17 @@
 // Configuration module for reading Snake.toml
 // This module provides OOP-style configuration management for the Battlesnake bot
 
-use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
+use std::sync::Arc;
+
+use crate::game_mode::GameMode;
+use crate::evaluator::Evaluator;
+use crate::hazard_walls::HazardWalls;
+use crate::types::RulesetSettings;
 
 /// Main configuration structure containing all tunable parameters
 #[derive(Debug, Deserialize, Clone)]
@@ -19,22 +27,75 @@ pub struct Config {
     pub player_indices: PlayerIndicesConfig,
     pub direction_encoding: DirectionEncodingConfig,
     pub game_rules: GameRulesConfig,
+    pub squad: SquadConfig,
+    pub opening: OpeningConfig,
+    pub ban_list: BanListConfig,
+    pub safety_verifier: SafetyVerifierConfig,
+    pub root_pruning: RootPruningConfig,
+    pub eval_cache: EvalCacheConfig,
+    pub lazy_eval: LazyEvalConfig,
+    pub food_paths: FoodPathsConfig,
+    pub food_routing: FoodRoutingConfig,
+    pub stall: StallConfig,
+    pub endgame: EndgameConfig,
+    pub contempt: ContemptConfig,
+    pub nn: NnConfig,
+    pub health: HealthConfig,
+    pub metrics: MetricsConfig,
+    pub request_validation: RequestValidationConfig,
+    pub concurrency: ConcurrencyConfig,
+    pub spectator: SpectatorConfig,
+    pub arena: ArenaConfig,
+    pub tuning: TuningConfig,
+    pub evolution: EvolutionConfig,
+    pub rating: RatingConfig,
+    pub puzzles: PuzzlesConfig,
+    pub what_if: WhatIfConfig,
+    pub score_trend: ScoreTrendConfig,
+    pub win_probability: WinProbabilityConfig,
+    pub stakes: StakesConfig,
     pub debug: DebugConfig,
     pub profiling: ProfilingConfig,
+    pub appearance: AppearanceConfig,
+    /// Named personality profiles: partial [scores] overrides (plus an optional appearance
+    /// table), served under /<name>/...
+    #[serde(default)]
+    pub profiles: BTreeMap<String, serde_json::Value>,
+    /// Mode of the game being searched (set per move from the game session, never from the
+    /// file)
+    #[serde(skip)]
+    pub game_mode: GameMode,
+    /// Impassable hazards of the game being searched (set per move in maze games)
+    #[serde(skip)]
+    pub hazard_walls: Option<Arc<HazardWalls>>,
+    /// Learned value network blended into the leaf evaluation (loaded from [nn] by
+    /// evaluator::attach, never from the file itself)
+    #[serde(skip)]
+    pub evaluator: Option<Arc<dyn Evaluator>>,
 }
 
 /// Timing and performance constants
 #[derive(Debug, Deserialize, Clone)]
 pub struct TimingConfig {
     pub response_time_budget_ms: u64,
+    pub use_game_timeout: bool,
     pub network_overhead_ms: u64,
-    pub polling_interval_ms: u64,
     pub initial_depth: u8,
     pub min_time_remaining_ms: u64,
     pub max_search_depth: u8,
+    pub max_quiescence_ply: u8,
+    /// Quiescence extensions one line may take, wherever they occur
+    pub max_quiescence_extensions: u8,
     pub certain_win_threshold: i32,
     pub certain_loss_threshold: i32,
     pub no_improvement_tolerance: u8,
+    pub stability_score_margin: u32,
+    pub stability_time_factor: f64,
+    pub max_nodes: u64,
+    pub stop_check_interval_nodes: u64,
+    pub latency: LatencyConfig,
+    pub allocation: AllocationConfig,
+    pub bank: TimeBankConfig,
 }
 
 impl TimingConfig {
@@ -44,10 +105,48 @@ impl TimingConfig {
     }
 }
 
+/// Game-phase-aware scaling of the per-turn budget (see time_manager.rs)
+#[derive(Debug, Deserialize, Clone)]
+pub struct AllocationConfig {
+    pub enabled: bool,
+    pub nominal_fraction: f64,
+    pub forced_move_fraction: f64,
+    pub min_scale: f64,
+    pub max_scale: f64,
+    pub reference_branching: f64,
+    pub branching_exponent: f64,
+    pub volatility_reference: u32,
+    pub volatility_exponent: f64,
+}
+
+/// Per-game bank of time saved by turns that finished early (see time_manager::TimeBank)
+#[derive(Debug, Deserialize, Clone)]
+pub struct TimeBankConfig {
+    pub enabled: bool,
+    pub max_balance_ms: u64,
+    pub critical_swing: u32,
+}
+
+/// Measured network latency feedback into the per-turn budget
+#[derive(Debug, Deserialize, Clone)]
+pub struct LatencyConfig {
+    pub enabled: bool,
+    pub smoothing: f64,
+    pub safety_margin_ms: u64,
+    pub min_overhead_ms: u64,
+    pub max_overhead_ms: u64,
+}
+
 /// Time estimation constants for iterative deepening
 #[derive(Debug, Deserialize, Clone)]
 pub struct TimeEstimationConfig {
     pub model_weight: f64,
+    pub use_cpu_time: bool,
+    pub contention_wall_cpu_ratio: f64,
+    pub contention_min_iteration_ms: u64,
+    pub persist_timings: bool,
+    pub history_max_observations: u32,
+    pub history_file: String,
     pub one_vs_one: GameModeTimeEstimation,
     pub multiplayer: GameModeTimeEstimation,
 }
@@ -81,10 +180,24 @@ impl TimeEstimationConfig {
 pub struct StrategyConfig {
     pub min_snakes_for_1v1: usize,
     pub min_cpus_for_parallel: usize,
+    /// Parallel root searches also split each root move at the second ply (root move × first
+    /// reply) when the pool has more threads than root moves
+    pub split_second_ply: bool,
+    /// Parallel root searches publish their results in move index order after every task has
+    /// finished, with per-root-move search tables, so the same input always yields the same
+    /// move; fixed-depth searches stay parallel instead of falling back to sequential
+    pub deterministic_parallel: bool,
+    /// Search for games that are not duels: "maxn", "brs" (best-reply search) or "auto"
+    pub multiplayer_search: String,
+    /// With "auto", best-reply search is used from this many alive snakes
+    pub brs_min_snakes: usize,
+    /// Leaf evaluation: "heuristic" or "nn" (heuristic blended with [nn]), see evaluator.rs
+    pub evaluator: String,
 }
 
 /// All evaluation and scoring constants
-#[derive(Debug, Deserialize, Clone)]
+/// Serializable so the active weights can be read and patched at runtime (/admin/config)
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct ScoresConfig {
     // Temporal discounting
     pub temporal_discount_factor: f32,
@@ -151,9 +264,23 @@ pub struct ScoresConfig {
     pub attack_trap_margin: usize,
     pub attack_trap_bonus: i32,
 
+    // Trap setting (trap.rs)
+    pub trap_setting_radius: i32,
+    pub trap_setting_bonus: i32,
+    pub trap_setting_deficit_bonus: i32,
+    pub weight_trap_setting: f32,
+
     // Head-to-head collision avoidance
     pub head_collision_penalty: i32,
 
+    // Static exchange evaluation (SEE) for contested head-to-head squares
+    pub see_kill_bonus: i32,
+    pub see_tie_penalty_factor: f32,
+    pub see_backup_threat_penalty: i32,
+
+    // Snail mode: penalty per hazard stack under the head (see snail.rs)
+    pub trail_reentry_penalty: i32,
+
     // Wall proximity penalty (mathematical formula)
     pub wall_penalty_base: i32,
     pub safe_distance_from_wall: i32,
@@ -201,6 +328,8 @@ pub struct IdaposConfig {
     pub late_game_max_locality_distance: i32,
 
     pub min_snakes_for_alpha_beta: usize,
+    /// Reuse active sets per (board hash, locality threshold) in the search (see eval_cache.rs)
+    pub cache_active_sets: bool,
 }
 
 /// Move ordering constants
@@ -209,6 +338,10 @@ pub struct MoveOrderingConfig {
     pub killer_moves_per_depth: usize,
     pub enable_pv_ordering: bool,
     pub enable_killer_heuristic: bool,
+    pub enable_see_ordering: bool,
+    pub history_aging_interval: u8,
+    pub history_max_score: i32,
+    pub history_persist_across_turns: bool,
 }
 
 /// Aspiration windows constants for 1v1 alpha-beta search
@@ -248,20 +381,366 @@ pub struct DirectionEncodingConfig {
 pub struct GameRulesConfig {
     pub health_on_food: u8,
     pub health_loss_per_turn: u8,
+    pub hazard_damage_per_turn: i32,
+    /// Hazard damage per turn from which hazard cells are walls (see hazard_walls.rs)
+    pub hazard_wall_damage: i32,
     pub terminal_state_threshold: usize,
 }
 
+/// Squad ruleset constants (see squad.rs)
+#[derive(Debug, Deserialize, Clone)]
+pub struct SquadConfig {
+    /// Weight of squad-mates' territory relative to our own in the control score
+    pub ally_territory_weight: f32,
+}
+
+/// Duel opening repertoire constants
+#[derive(Debug, Deserialize, Clone)]
+pub struct OpeningConfig {
+    pub enabled: bool,
+    pub max_turn: i32,
+    pub follow_bonus: i32,
+    pub near_food_distance: i32,
+}
+
+/// Hard ban list (veto layer on the final move)
+#[derive(Debug, Deserialize, Clone)]
+pub struct BanListConfig {
+    pub enabled: bool,
+    pub enter_dead_end: bool,
+    pub corner_with_opponent_adjacent: bool,
+    pub corner_opponent_distance: i32,
+    pub leave_one_liberty: bool,
+}
+
+/// One-ply tactical verification of the final move
+#[derive(Debug, Deserialize, Clone)]
+pub struct SafetyVerifierConfig {
+    pub enabled: bool,
+    pub opponent_radius: i32,
+}
+
+/// Pre-search pruning of provably losing root moves
+#[derive(Debug, Deserialize, Clone)]
+pub struct RootPruningConfig {
+    pub enabled: bool,
+}
+
+/// Per-thread cache of flood-fill and territory results keyed by board occupancy
+#[derive(Debug, Deserialize, Clone)]
+pub struct EvalCacheConfig {
+    pub enabled: bool,
+    pub max_entries: usize,
+}
+
+/// Lazy (material, health, head-to-head danger) evaluation at interior nodes
+#[derive(Debug, Deserialize, Clone)]
+pub struct LazyEvalConfig {
+    pub enabled: bool,
+    /// Frontier nodes (depth 1) whose lazy score misses the window by this much are not searched
+    pub futility_margin: i32,
+    /// Nodes up to this depth whose lazy score misses the window by razor_margin are verified
+    /// with a leaf evaluation first
+    pub razor_depth: u8,
+    pub razor_margin: i32,
+    /// Moves of nodes at least this deep are ordered by the lazy score of their child
+    pub ordering_min_depth: u8,
+}
+
+/// BFS path distances for food decisions (see distance_field.rs FoodPaths)
+#[derive(Debug, Deserialize, Clone)]
+pub struct FoodPathsConfig {
+    pub enabled: bool,
+    /// Depth of the bounded BFS run outside the evaluation (root food checks)
+    pub max_bfs_depth: u16,
+}
+
+/// Safe food routes when hungry (see food_route.rs)
+#[derive(Debug, Deserialize, Clone)]
+pub struct FoodRoutingConfig {
+    pub enabled: bool,
+    /// Plan a route when our health is below this
+    pub health_threshold: i32,
+    /// Root score bonus for the route's first move
+    pub first_step_bonus: i32,
+}
+
+/// Late-game Hamiltonian-cycle patrol when clearly ahead (see stall.rs)
+#[derive(Debug, Deserialize, Clone)]
+pub struct StallConfig {
+    pub enabled: bool,
+    /// Length lead over every opponent required to stall
+    pub min_length_lead: i32,
+    /// Lead of our evaluation over the best opponent's required to stall (with
+    /// [win_probability] disabled)
+    pub min_eval_lead: i32,
+    /// Calibrated win probability of our evaluation required to stall
+    pub min_win_probability: f64,
+    /// Root score bonus for the patrol move
+    pub follow_bonus: i32,
+}
+
+/// Duel endgame parity once no food is relevant (see endgame.rs)
+#[derive(Debug, Deserialize, Clone)]
+pub struct EndgameConfig {
+    pub enabled: bool,
+    /// Evaluation bonus for outlasting the opponent (penalty for being outlasted)
+    pub parity_bonus: i32,
+    /// Evaluation score per turn of survival margin
+    pub parity_turn_weight: i32,
+    /// Root score bonus for the moves with the best parity margin
+    pub fill_bonus: i32,
+}
+
+/// Duel draw avoidance: penalty of a tied head-to-head by opponent class (see contempt.rs)
+#[derive(Debug, Deserialize, Clone)]
+pub struct ContemptConfig {
+    pub enabled: bool,
+    /// Contempt against weak, even and strong opponents (score points below zero)
+    pub weak: i32,
+    pub even: i32,
+    pub strong: i32,
+    /// Opponent names (as sent by the engine) classed as weak or strong; others are even
+    pub weak_opponents: Vec<String>,
+    pub strong_opponents: Vec<String>,
+}
+
+/// Learned value network (see evaluator.rs; needs the `nn` cargo feature)
+#[derive(Debug, Deserialize, Clone)]
+pub struct NnConfig {
+    /// Model file (JSON weights, see nn.rs)
+    pub model_path: String,
+    /// Share of the network in the leaf score: 0 = handcrafted only, 1 = network only
+    pub blend: f32,
+    /// Score units of a network value of 1 (a certain win)
+    pub value_scale: f32,
+    /// Positions per network call when the leaves of a frontier node are evaluated together
+    pub batch_size: usize,
+}
+
+/// /readyz self-test limits
+#[derive(Debug, Deserialize, Clone)]
+pub struct HealthConfig {
+    pub self_test_max_ms: u64,
+    pub pool_timeout_ms: u64,
+}
+
+/// Handler latency histograms and slow-turn detection (see metrics.rs)
+#[derive(Debug, Deserialize, Clone)]
+pub struct MetricsConfig {
+    pub enabled: bool,
+    pub slow_turn_fraction: f64,
+}
+
+/// Snake metadata returned by GET / (a profile may override it in [profiles.<name>.appearance])
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct AppearanceConfig {
+    pub author: String,
+    /// "#RRGGBB"
+    pub color: String,
+    pub head: String,
+    pub tail: String,
+    /// Reported version; empty reports the crate version
+    pub version: String,
+}
+
+impl AppearanceConfig {
+    /// Version reported by GET /
+    pub fn version(&self) -> &str {
+        if self.version.is_empty() {
+            env!("CARGO_PKG_VERSION")
+        } else {
+            &self.version
+        }
+    }
+}
+
+/// Limits for incoming game states (see request_validation.rs)
+#[derive(Debug, Deserialize, Clone)]
+pub struct RequestValidationConfig {
+    pub max_board_dimension: i32,
+    /// Boards with more snakes are rejected (bounds the search's stack, see bot::search_stack_bytes)
+    pub max_snakes: usize,
+}
+
+/// Sharing cores between concurrent games (see capacity.rs)
+#[derive(Debug, Deserialize, Clone)]
+pub struct ConcurrencyConfig {
+    pub max_concurrent_games: usize,
+    pub max_threads_per_game: usize,
+    pub slot_wait_ms: u64,
+    /// Game sessions idle for longer are dropped (games whose /end never arrived)
+    pub session_idle_timeout_ms: u64,
+}
+
+/// Live per-turn search results over WebSocket (see spectator.rs)
+#[derive(Debug, Deserialize, Clone)]
+pub struct SpectatorConfig {
+    pub enabled: bool,
+    pub channel_capacity: usize,
+}
+
+/// Self-play games between configs (see arena.rs)
+#[derive(Debug, Deserialize, Clone)]
+pub struct ArenaConfig {
+    pub board_size: i32,
+    pub max_turns: i32,
+    /// Every move is a fixed-depth search (no clock), so games are reproducible
+    pub search_depth: u8,
+    pub minimum_food: usize,
+    /// Percent chance per turn to spawn one food once minimum_food is on the board
+    pub food_spawn_chance: u32,
+}
+
+/// SPSA tuning of [scores] fields (see tuning.rs and bin/tune_spsa.rs)
+#[derive(Debug, Deserialize, Clone)]
+pub struct TuningConfig {
+    pub iterations: u32,
+    /// Self-play games between the +/- perturbed configs per iteration
+    pub games_per_iteration: u32,
+    /// SPSA step size a (parameters are normalized to [0, 1] over their bounds)
+    pub learning_rate: f64,
+    /// SPSA perturbation size c, as a fraction of each parameter's range
+    pub perturbation: f64,
+    /// SPSA stability constant A
+    pub stability_constant: f64,
+    /// Decay exponents: a_k = a / (k + 1 + A)^alpha, c_k = c / (k + 1)^gamma
+    pub learning_rate_decay: f64,
+    pub perturbation_decay: f64,
+    pub seed: u64,
+    pub output_path: String,
+    /// [scores] field name -> [min, max]
+    pub parameters: BTreeMap<String, [f64; 2]>,
+}
+
+/// Genetic search over the [tuning.parameters] (see evolution.rs)
+#[derive(Debug, Deserialize, Clone)]
+pub struct EvolutionConfig {
+    pub population_size: usize,
+    pub generations: u32,
+    /// Arena duels of each individual against the starting config per generation
+    pub games_per_individual: u32,
+    /// Best individuals copied unchanged into the next generation
+    pub elite: usize,
+    /// Individuals drawn per parent selection (the fittest of them is the parent)
+    pub tournament_size: usize,
+    /// Chance that a child mixes two parents (otherwise it copies one)
+    pub crossover_rate: f64,
+    /// Chance that each gene is mutated
+    pub mutation_rate: f64,
+    /// Standard deviation of a mutation, as a fraction of the parameter's range
+    pub mutation_sigma: f64,
+    pub seed: u64,
+    /// Written after every generation; a run resumes from it
+    pub checkpoint_path: String,
+    pub output_path: String,
+}
+
+/// Elo estimates and SPRT for arena matches (see rating.rs)
+#[derive(Debug, Deserialize, Clone)]
+pub struct RatingConfig {
+    /// H0: the candidate is elo0 stronger; H1: it is elo1 stronger
+    pub elo0: f64,
+    pub elo1: f64,
+    /// False positive / false negative rates
+    pub alpha: f64,
+    pub beta: f64,
+    /// Games played between SPRT checks
+    pub batch_games: u32,
+    /// Give up without a decision after this many games
+    pub max_games: u32,
+}
+
+/// Tactical puzzle runs (see puzzles.rs)
+#[derive(Debug, Deserialize, Clone)]
+pub struct PuzzlesConfig {
+    pub directory: String,
+    /// Search depth for puzzles that do not set their own
+    pub default_depth: u8,
+}
+
+/// What-if rollouts in the replay tool (replay --what-if)
+#[derive(Debug, Deserialize, Clone)]
+pub struct WhatIfConfig {
+    /// Turns simulated after the forced move
+    pub horizon: i32,
+    /// Seed for food spawns during the rollout (both lines see the same spawns)
+    pub seed: u64,
+}
+
+/// Root score drop detection across turns (see score_trend.rs)
+#[derive(Debug, Deserialize, Clone)]
+pub struct ScoreTrendConfig {
+    pub enabled: bool,
+    /// Searched turns whose mean root score is the baseline
+    pub window: usize,
+    /// Fall below the baseline that counts as a drop
+    pub drop_threshold: i64,
+}
+
+/// Logistic calibration of root scores to win probability (see win_probability.rs)
+#[derive(Debug, Deserialize, Clone)]
+pub struct WinProbabilityConfig {
+    pub enabled: bool,
+    pub slope: f64,
+    pub intercept: f64,
+    /// Win probability of the best move above which the search stops deepening
+    pub certain_win: f64,
+    /// Win probability of the best move below which the search stops deepening
+    pub certain_loss: f64,
+    /// Smallest time allocation factor of a decided position
+    pub decided_min_scale: f64,
+}
+
+/// Ladder or tournament play per game (see stakes.rs)
+#[derive(Debug, Deserialize, Clone)]
+pub struct StakesConfig {
+    /// Stakes of games whose /start names none: "ladder" or "tournament"
+    pub default: String,
+    /// Added to scores.space_safety_margin at tournament stakes
+    pub tournament_extra_space_margin: usize,
+    /// Added to the network overhead allowance (static and measured) at tournament stakes
+    pub tournament_extra_overhead_ms: u64,
+    /// Best-reply search on every board that is not a duel at tournament stakes
+    pub tournament_paranoid_search: bool,
+}
+
 /// Debug configuration
 #[derive(Debug, Deserialize, Clone)]
 pub struct DebugConfig {
     pub enabled: bool,
     pub log_file_path: String,
+    /// Log the board as an ASCII grid at debug level every turn
+    pub log_board: bool,
+    /// Keep the verbatim /move request and response bodies in each log entry
+    pub log_requests: bool,
+    /// Write one file per game (<log_directory>/<game id>.jsonl) instead of log_file_path
+    pub per_game_files: bool,
+    pub log_directory: String,
+    /// Gzip a game's file when the game ends (<game id>.jsonl.gz)
+    pub compress: bool,
+    /// Game files kept in log_directory, newest first (0 = no limit)
+    pub max_files: usize,
+    /// Game files older than this are deleted (0 = no limit)
+    pub max_age_hours: u64,
+    /// One GameSummary JSON line per game is appended here at /end, also when debug logging
+    /// is disabled (empty: none)
+    pub summary_file_path: String,
+    /// One PostMortem JSON line per lost game is appended here at /end (empty: none)
+    pub post_mortem_file_path: String,
+    /// Moves whose search results (with candidate tables) a post-mortem keeps
+    pub post_mortem_turns: usize,
+    /// Carry the evaluation breakdown at the end of each root move's winning line back to
+    /// the root (debug entries' leaf_breakdown, replay mismatches)
+    pub explain: bool,
 }
 
 /// Performance profiling configuration
 #[derive(Debug, Deserialize, Clone)]
 pub struct ProfilingConfig {
     pub enabled: bool,
+    /// Directory for one JSON profile per search (empty: none)
+    pub json_directory: String,
     pub log_to_stderr: bool,
     pub track_move_generation: bool,
     pub track_evaluation: bool,
@@ -296,17 +775,52 @@ impl Config {
         Config {
             timing: TimingConfig {
                 response_time_budget_ms: 400,
+                use_game_timeout: true,
                 network_overhead_ms: 50,
-                polling_interval_ms: 50,
                 initial_depth: 2,
                 min_time_remaining_ms: 20,
                 max_search_depth: 20,
+                max_quiescence_ply: 32,
+                max_quiescence_extensions: 8,
                 certain_win_threshold: 1000000,
                 certain_loss_threshold: -1000000,
                 no_improvement_tolerance: 2,
+                stability_score_margin: 2000,
+                stability_time_factor: 2.0,
+                max_nodes: 0,
+                stop_check_interval_nodes: 64,
+                latency: LatencyConfig {
+                    enabled: true,
+                    smoothing: 0.3,
+                    safety_margin_ms: 10,
+                    min_overhead_ms: 15,
+                    max_overhead_ms: 200,
+                },
+                allocation: AllocationConfig {
+                    enabled: true,
+                    nominal_fraction: 0.8,
+                    forced_move_fraction: 0.1,
+                    min_scale: 0.5,
+                    max_scale: 1.25,
+                    reference_branching: 9.0,
+                    branching_exponent: 0.25,
+                    volatility_reference: 5000,
+                    volatility_exponent: 0.2,
+                },
+                bank: TimeBankConfig {
+                    enabled: true,
+                    max_balance_ms: 1000,
+                    critical_swing: 20000,
+                },
             },
             time_estimation: TimeEstimationConfig {
                 model_weight: 0.1,  // Reduced from 0.4 - favor empirical observations
+                use_cpu_time: true,
+                contention_wall_cpu_ratio: 2.0,
+                contention_min_iteration_ms: 5,
+                persist_timings: true,
+                history_max_observations: 50,
+                history_file: String::new(),
                 one_vs_one: GameModeTimeEstimation {
                     base_iteration_time_ms: 0.01,
                     branching_factor: 2.2,  // Initial model (will adapt via AdaptiveTimeEstimator)
@@ -319,6 +833,11 @@ impl Config {
             strategy: StrategyConfig {
                 min_snakes_for_1v1: 2,
                 min_cpus_for_parallel: 2,
+                split_second_ply: true,
+                deterministic_parallel: false,
+                multiplayer_search: "auto".to_string(),
+                brs_min_snakes: 4,
+                evaluator: "heuristic".to_string(),
             },
             scores: ScoresConfig {
                 temporal_discount_factor: 0.95,
@@ -329,7 +848,7 @@ impl Config {
                 score_dead_snake: i32::MIN + 1000,
                 score_survival_penalty: -1_000_000,
                 score_survival_weight: 1000.0,
-                weight_space: 15.0,  // V11: Reduced from 25.0 for balanced play
+                weight_space: 20.0,
                 weight_health: 40.0,  // V11: Reduced from 75.0 to match lower food bonuses
                 weight_control: 5.0,  // V11: Increased from 3.0 for strategic positioning
                 weight_attack: 8.0,  // V11: Reduced from 10.0 for selective aggression
@@ -365,7 +884,15 @@ impl Config {
                 attack_head_to_head_bonus: 200,  // Increased from 50 for aggressive kills
                 attack_trap_margin: 3,
                 attack_trap_bonus: 300,  // Increased from 100 to reward trapping
+                trap_setting_radius: 3,
+                trap_setting_bonus: 400,
+                trap_setting_deficit_bonus: 50,
+                weight_trap_setting: 8.0,
                 head_collision_penalty: -50_000,
+                see_kill_bonus: 3000,
+                see_tie_penalty_factor: 1.0,
+                see_backup_threat_penalty: -2000,
+                trail_reentry_penalty: -400,
                 wall_penalty_base: 500,  // Reduced from 1000 to allow edge food acquisition
                 safe_distance_from_wall: 3,
                 center_bias_multiplier: 50,  // Increased from 10 to prevent wall-hugging
@@ -392,11 +919,16 @@ impl Config {
                 late_game_head_distance_multiplier: 1,
                 late_game_max_locality_distance: 5,
                 min_snakes_for_alpha_beta: 2,
+                cache_active_sets: true,
             },
             move_ordering: MoveOrderingConfig {
                 killer_moves_per_depth: 2,
                 enable_pv_ordering: true,
                 enable_killer_heuristic: true,
+                enable_see_ordering: true,
+                history_aging_interval: 2,
+                history_max_score: 1 << 20,
+                history_persist_across_turns: true,
             },
             aspiration_windows: AspirationWindowsConfig {
                 enabled: true,
@@ -421,21 +953,281 @@ impl Config {
             game_rules: GameRulesConfig {
                 health_on_food: 100,
                 health_loss_per_turn: 1,
+                hazard_damage_per_turn: 14,
+                hazard_wall_damage: 100,
                 terminal_state_threshold: 1,
             },
+            squad: SquadConfig {
+                ally_territory_weight: 1.0,
+            },
+            opening: OpeningConfig {
+                enabled: true,
+                max_turn: 10,
+                follow_bonus: 300,
+                near_food_distance: 3,
+            },
+            ban_list: BanListConfig {
+                enabled: true,
+                enter_dead_end: false,
+                corner_with_opponent_adjacent: false,
+                corner_opponent_distance: 2,
+                leave_one_liberty: false,
+            },
+            safety_verifier: SafetyVerifierConfig {
+                enabled: true,
+                opponent_radius: 3,
+            },
+            root_pruning: RootPruningConfig { enabled: true },
+            eval_cache: EvalCacheConfig {
+                enabled: true,
+                max_entries: 65536,
+            },
+            lazy_eval: LazyEvalConfig {
+                enabled: true,
+                futility_margin: 60000,
+                razor_depth: 2,
+                razor_margin: 90000,
+                ordering_min_depth: 3,
+            },
+            food_paths: FoodPathsConfig {
+                enabled: true,
+                max_bfs_depth: 20,
+            },
+            food_routing: FoodRoutingConfig {
+                enabled: true,
+                health_threshold: 35,
+                first_step_bonus: 5000,
+            },
+            stall: StallConfig {
+                enabled: true,
+                min_length_lead: 2,
+                min_eval_lead: 5000,
+                min_win_probability: 0.8,
+                follow_bonus: 1500,
+            },
+            endgame: EndgameConfig {
+                enabled: true,
+                parity_bonus: 50000,
+                parity_turn_weight: 100,
+                fill_bonus: 2000,
+            },
+            contempt: ContemptConfig {
+                enabled: true,
+                weak: 45000,
+                even: 40000,
+                strong: 30000,
+                weak_opponents: Vec::new(),
+                strong_opponents: Vec::new(),
+            },
+            nn: NnConfig {
+                model_path: "models/value_net.json".to_string(),
+                blend: 0.3,
+                value_scale: 2000.0,
+                batch_size: 16,
+            },
+            health: HealthConfig {
+                self_test_max_ms: 10,
+                pool_timeout_ms: 250,
+            },
+            metrics: MetricsConfig {
+                enabled: true,
+                slow_turn_fraction: 0.9,
+            },
+            request_validation: RequestValidationConfig {
+                max_board_dimension: 50,
+                max_snakes: 16,
+            },
+            concurrency: ConcurrencyConfig {
+                max_concurrent_games: 4,
+                max_threads_per_game: 0,
+                slot_wait_ms: 100,
+                session_idle_timeout_ms: 300_000,
+            },
+            spectator: SpectatorConfig {
+                enabled: false,
+                channel_capacity: 64,
+            },
+            arena: ArenaConfig {
+                board_size: 11,
+                max_turns: 300,
+                search_depth: 2,
+                minimum_food: 1,
+                food_spawn_chance: 15,
+            },
+            tuning: TuningConfig {
+                iterations: 50,
+                games_per_iteration: 8,
+                learning_rate: 0.05,
+                perturbation: 0.1,
+                stability_constant: 5.0,
+                learning_rate_decay: 0.602,
+                perturbation_decay: 0.101,
+                seed: 1,
+                output_path: "Snake.tuned.toml".to_string(),
+                parameters: BTreeMap::from([
+                    ("weight_space".to_string(), [5.0, 40.0]),
+                    ("weight_health".to_string(), [10.0, 80.0]),
+                    ("weight_control".to_string(), [0.0, 20.0]),
+                    ("weight_attack".to_string(), [0.0, 20.0]),
+                    ("attack_head_to_head_bonus".to_string(), [0.0, 600.0]),
+                ]),
+            },
+            evolution: EvolutionConfig {
+                population_size: 12,
+                generations: 20,
+                games_per_individual: 4,
+                elite: 2,
+                tournament_size: 3,
+                crossover_rate: 0.7,
+                mutation_rate: 0.2,
+                mutation_sigma: 0.1,
+                seed: 1,
+                checkpoint_path: "evolution_checkpoint.json".to_string(),
+                output_path: "Snake.evolved.toml".to_string(),
+            },
+            rating: RatingConfig {
+                elo0: 0.0,
+                elo1: 10.0,
+                alpha: 0.05,
+                beta: 0.05,
+                batch_games: 16,
+                max_games: 4000,
+            },
+            puzzles: PuzzlesConfig {
+                directory: "tests/puzzles".to_string(),
+                default_depth: 4,
+            },
+            what_if: WhatIfConfig {
+                horizon: 60,
+                seed: 1,
+            },
+            score_trend: ScoreTrendConfig {
+                enabled: true,
+                window: 5,
+                drop_threshold: 20000,
+            },
+            win_probability: WinProbabilityConfig {
+                enabled: true,
+                slope: 1.02e-5,
+                intercept: -0.85,
+                certain_win: 0.995,
+                certain_loss: 0.005,
+                decided_min_scale: 0.5,
+            },
+            stakes: StakesConfig {
+                default: "ladder".to_string(),
+                tournament_extra_space_margin: 2,
+                tournament_extra_overhead_ms: 40,
+                tournament_paranoid_search: true,
+            },
             debug: DebugConfig {
                 enabled: false,
                 log_file_path: "battlesnake_debug.jsonl".to_string(),
+                log_board: false,
+                log_requests: false,
+                per_game_files: false,
+                log_directory: "game_logs".to_string(),
+                compress: false,
+                max_files: 500,
+                max_age_hours: 0,
+                summary_file_path: String::new(),
+                post_mortem_file_path: String::new(),
+                post_mortem_turns: 5,
+                explain: false,
             },
             profiling: ProfilingConfig {
                 enabled: false,
+                json_directory: String::new(),
                 log_to_stderr: true,
                 track_move_generation: true,
                 track_evaluation: true,
                 track_search: true,
                 track_transposition_table: true,
             },
+            appearance: AppearanceConfig {
+                author: "ksiopiolosz-aterlo".to_string(),
+                color: "#00DEAD".to_string(),
+                head: "default".to_string(),
+                tail: "default".to_string(),
+                version: String::new(),
+            },
+            profiles: BTreeMap::from([
+                (
+                    "aggressive".to_string(),
+                    serde_json::json!({
+                        "weight_attack": 16.0,
+                        "weight_control": 10.0,
+                        "attack_head_to_head_bonus": 400,
+                        "attack_trap_bonus": 600,
+                    }),
+                ),
+                (
+                    "defensive".to_string(),
+                    serde_json::json!({
+                        "weight_space": 30.0,
+                        "weight_attack": 2.0,
+                        "head_collision_penalty": -80000,
+                    }),
+                ),
+                (
+                    "snail".to_string(),
+                    serde_json::json!({
+                        "trail_reentry_penalty": -1500,
+                        "weight_space": 30.0,
+                    }),
+                ),
+            ]),
+            game_mode: GameMode::Standard,
+            hazard_walls: None,
+            evaluator: None,
+        }
+    }
+
+    /// Names of the configured personality profiles
+    pub fn profile_names(&self) -> impl Iterator<Item = &str> {
+        self.profiles.keys().map(String::as_str)
+    }
+
+    /// Copy of this config with the named profile's score overrides applied
+    ///
+    /// # Returns
+    /// * `Err` if the profile does not exist or its overrides do not fit ScoresConfig
+    pub fn for_profile(&self, name: &str) -> Result<Config, String> {
+        let overrides = self
+            .profiles
+            .get(name)
+            .ok_or_else(|| format!("Unknown profile '{}'", name))?;
+
+        // The appearance table is not a score field
+        let mut overrides = overrides.clone();
+        let appearance = overrides.as_object_mut().and_then(|fields| fields.remove("appearance"));
+
+        let mut config = self.clone();
+        config.scores = self
+            .scores
+            .with_overrides(&overrides)
+            .map_err(|e| format!("Profile '{}': {}", name, e))?;
+        if let Some(appearance) = appearance {
+            config.appearance = with_overrides(&self.appearance, &appearance, "appearance")
+                .map_err(|e| format!("Profile '{}': {}", name, e))?;
         }
+        Ok(config)
+    }
+
+    /// Copy of this config playing under a game's ruleset settings: the settings the engine
+    /// sent replace the [game_rules] and [arena] (food spawning) values they correspond to
+    pub fn with_ruleset(&self, settings: &RulesetSettings) -> Config {
+        let mut config = self.clone();
+        if let Some(damage) = settings.hazard_damage_per_turn {
+            config.game_rules.hazard_damage_per_turn = damage;
+        }
+        if let Some(minimum_food) = settings.minimum_food {
+            config.arena.minimum_food = minimum_food;
+        }
+        if let Some(chance) = settings.food_spawn_chance {
+            config.arena.food_spawn_chance = chance;
+        }
+        config
     }
 
     /// Attempts to load from file, falls back to hardcoded defaults on error
@@ -448,6 +1240,37 @@ impl Config {
     }
 }
 
+/// Copy of a config section with the fields in `overrides` (a partial JSON object) replaced
+/// Unknown field names and values of the wrong type are rejected; nothing is applied then
+fn with_overrides<T: Serialize + DeserializeOwned>(section: &T, overrides: &serde_json::Value, kind: &str) -> Result<T, String> {
+    let overrides = overrides
+        .as_object()
+        .ok_or_else(|| format!("Expected a JSON object of {} fields", kind))?;
+
+    let mut merged = serde_json::to_value(section)
+        .map_err(|e| format!("Failed to serialize {}: {}", kind, e))?;
+    let fields = merged
+        .as_object_mut()
+        .ok_or_else(|| format!("{} did not serialize to an object", kind))?;
+
+    for (name, value) in overrides {
+        if !fields.contains_key(name) {
+            return Err(format!("Unknown {} field '{}'", kind, name));
+        }
+        fields.insert(name.clone(), value.clone());
+    }
+
+    serde_json::from_value(merged).map_err(|e| format!("Invalid {} value: {}", kind, e))
+}
+
+impl ScoresConfig {
+    /// Returns a copy with the fields in `overrides` (a partial JSON object) replaced
+    /// Unknown field names and values of the wrong type are rejected; nothing is applied then
+    pub fn with_overrides(&self, overrides: &serde_json::Value) -> Result<ScoresConfig, String> {
+        with_overrides(self, overrides, "score")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -484,7 +1307,6 @@ mod tests {
         // Test timing config
         assert!(config.timing.response_time_budget_ms > 0);
         assert!(config.timing.network_overhead_ms > 0);
-        assert!(config.timing.polling_interval_ms > 0);
         assert!(config.timing.initial_depth > 0);
         assert!(config.timing.min_time_remaining_ms > 0);
         assert!(config.timing.max_search_depth > 0);
@@ -577,8 +1399,8 @@ mod tests {
 
         // IDAPOS
         assert_eq!(
-            file_config.idapos.head_distance_multiplier,
-            hardcoded_config.idapos.head_distance_multiplier
+            file_config.idapos.early_game_head_distance_multiplier,
+            hardcoded_config.idapos.early_game_head_distance_multiplier
         );
         assert_eq!(
             file_config.idapos.min_snakes_for_alpha_beta,
@@ -607,6 +1429,84 @@ mod tests {
         assert_eq!(config.scores.health_threat_distance, 3);
     }
 
+    #[test]
+    fn test_score_overrides_replace_only_given_fields() {
+        let scores = Config::default_hardcoded().scores;
+        let patched = scores
+            .with_overrides(&serde_json::json!({ "weight_space": 7.5, "attack_trap_bonus": 12 }))
+            .expect("valid overrides");
+
+        assert_eq!(patched.weight_space, 7.5);
+        assert_eq!(patched.attack_trap_bonus, 12);
+        assert_eq!(patched.weight_health, scores.weight_health);
+    }
+
+    #[test]
+    fn test_ruleset_settings_replace_rule_constants() {
+        let ruleset: crate::types::Ruleset = serde_json::from_value(serde_json::json!({
+            "name": "royale",
+            "version": "v1.2.3",
+            "settings": {
+                "foodSpawnChance": 25,
+                "minimumFood": 2,
+                "hazardDamagePerTurn": 20,
+                "hazardMap": "",
+                "royale": { "shrinkEveryNTurns": 10 },
+                "squad": { "allowBodyCollisions": false }
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            ruleset.describe(),
+            "royale v1.2.3 (hazard damage 20, minimum food 2, food spawn 25%, shrink every 10 turns)"
+        );
+
+        let config = Config::default_hardcoded();
+        let ruled = config.with_ruleset(&ruleset.settings);
+        assert_eq!(ruled.game_rules.hazard_damage_per_turn, 20);
+        assert_eq!((ruled.arena.minimum_food, ruled.arena.food_spawn_chance), (2, 25));
+        let unset = config.with_ruleset(&Default::default());
+        assert_eq!(unset.game_rules.hazard_damage_per_turn, config.game_rules.hazard_damage_per_turn);
+    }
+
+    #[test]
+    fn test_score_overrides_reject_unknown_fields_and_bad_types() {
+        let scores = Config::default_hardcoded().scores;
+        assert!(scores.with_overrides(&serde_json::json!({ "weight_spaec": 1.0 })).is_err());
+        assert!(scores.with_overrides(&serde_json::json!({ "attack_trap_bonus": "high" })).is_err());
+        assert!(scores.with_overrides(&serde_json::json!([1, 2])).is_err());
+    }
+
+    #[test]
+    fn test_profiles_match_hardcoded_and_apply_on_top_of_scores() {
+        let file_config = Config::from_file("Snake.toml")
+            .expect("Snake.toml should be parseable");
+        let hardcoded_config = Config::default_hardcoded();
+        assert_eq!(file_config.profiles, hardcoded_config.profiles);
+        assert_eq!(file_config.appearance, hardcoded_config.appearance);
+
+        let aggressive = hardcoded_config.for_profile("aggressive").expect("profile resolves");
+        assert_eq!(aggressive.scores.weight_attack, 16.0);
+        assert_eq!(aggressive.scores.weight_health, hardcoded_config.scores.weight_health);
+        assert!(hardcoded_config.for_profile("reckless").is_err());
+    }
+
+    #[test]
+    fn test_profile_appearance_overrides_only_its_fields() {
+        let mut config = Config::default_hardcoded();
+        config.profiles.insert(
+            "red".to_string(),
+            serde_json::json!({ "weight_attack": 5.0, "appearance": { "color": "#FF0000" } }),
+        );
+        let red = config.for_profile("red").expect("profile resolves");
+        assert_eq!(red.appearance.color, "#FF0000");
+        assert_eq!(red.appearance.author, config.appearance.author);
+        assert_eq!(red.scores.weight_attack, 5.0);
+
+        config.profiles.insert("typo".to_string(), serde_json::json!({ "appearance": { "colour": "#FF0000" } }));
+        assert!(config.for_profile("typo").unwrap_err().contains("Unknown appearance field 'colour'"));
+    }
+
     #[test]
     fn test_invalid_toml_returns_error() {
         // Test with a non-existent file
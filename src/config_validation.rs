@@ -0,0 +1,697 @@
+// Range and consistency checks for Config
+//
+// Config::from_file only checks that Snake.toml parses. Values that parse but make no sense
+// (a zero budget, an inverted min/max pair, a divisor of 0) used to surface as panics or
+// silently broken search much later. validate() lists every problem with the field path,
+// the offending value and the expected range. Errors are values the bot cannot run with;
+// warnings are legal but almost certainly unintended (e.g. a component weight of 0).
+
+use std::fmt;
+
+use crate::config::Config;
+use crate::stakes::Stakes;
+
+/// First path segments of built-in routes; a profile with one of these names would be shadowed
+const RESERVED_PROFILE_NAMES: [&str; 4] = ["admin", "healthz", "metrics", "readyz"];
+
+/// "#RRGGBB" as the Battlesnake API expects for a snake color
+fn is_hex_color(color: &str) -> bool {
+    color.len() == 7 && color.starts_with('#') && color[1..].chars().all(|ch| ch.is_ascii_hexdigit())
+}
+
+/// How serious a config issue is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// One problem found by Config::validate
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigIssue {
+    pub severity: Severity,
+    /// Dotted path as written in Snake.toml (e.g. "timing.latency.smoothing")
+    pub field: String,
+    pub value: String,
+    pub expected: String,
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, "{}: {} = {} (expected {})", label, self.field, self.value, self.expected)
+    }
+}
+
+/// Collects issues while walking the config
+struct Checker {
+    issues: Vec<ConfigIssue>,
+}
+
+impl Checker {
+    fn push(&mut self, severity: Severity, field: &str, value: impl fmt::Display, expected: &str) {
+        self.issues.push(ConfigIssue {
+            severity,
+            field: field.to_string(),
+            value: value.to_string(),
+            expected: expected.to_string(),
+        });
+    }
+
+    /// Records an error unless `ok`
+    fn require(&mut self, ok: bool, field: &str, value: impl fmt::Display, expected: &str) {
+        if !ok {
+            self.push(Severity::Error, field, value, expected);
+        }
+    }
+
+    /// Records a warning unless `ok`
+    fn recommend(&mut self, ok: bool, field: &str, value: impl fmt::Display, expected: &str) {
+        if !ok {
+            self.push(Severity::Warning, field, value, expected);
+        }
+    }
+
+    /// Component weights: negative inverts the component (error), zero disables it (warning)
+    fn weight(&mut self, field: &str, value: f64) {
+        self.require(value >= 0.0, field, value, ">= 0");
+        self.recommend(value != 0.0, field, value, "> 0; 0 disables this component");
+    }
+
+    fn fraction(&mut self, field: &str, value: f64) {
+        self.require(value > 0.0 && value <= 1.0, field, value, "in (0, 1]");
+    }
+}
+
+impl Config {
+    /// Checks value ranges and cross-field consistency
+    ///
+    /// # Returns
+    /// Every error and warning found (empty if the config is sane)
+    pub fn validate(&self) -> Vec<ConfigIssue> {
+        let mut c = Checker { issues: Vec::new() };
+        self.validate_timing(&mut c);
+        self.validate_search(&mut c);
+        self.validate_scores(&mut c);
+        self.validate_rules(&mut c);
+        self.validate_profiles(&mut c);
+        self.validate_tuning(&mut c);
+        c.issues
+    }
+
+    /// Only the issues with Severity::Error
+    pub fn validation_errors(&self) -> Vec<ConfigIssue> {
+        self.validate()
+            .into_iter()
+            .filter(|issue| issue.severity == Severity::Error)
+            .collect()
+    }
+
+    fn validate_timing(&self, c: &mut Checker) {
+        let t = &self.timing;
+        c.require(t.response_time_budget_ms > 0, "timing.response_time_budget_ms", t.response_time_budget_ms, "> 0");
+        c.require(
+            t.network_overhead_ms < t.response_time_budget_ms,
+            "timing.network_overhead_ms",
+            t.network_overhead_ms,
+            &format!("< response_time_budget_ms ({})", t.response_time_budget_ms),
+        );
+        c.recommend(
+            t.min_time_remaining_ms < t.effective_budget_ms(),
+            "timing.min_time_remaining_ms",
+            t.min_time_remaining_ms,
+            &format!("< effective budget ({} ms)", t.effective_budget_ms()),
+        );
+        c.require(t.initial_depth >= 1, "timing.initial_depth", t.initial_depth, ">= 1");
+        c.require(
+            t.initial_depth <= t.max_search_depth,
+            "timing.initial_depth",
+            t.initial_depth,
+            &format!("<= max_search_depth ({})", t.max_search_depth),
+        );
+        c.require(
+            (t.max_search_depth..=250).contains(&t.max_quiescence_ply),
+            "timing.max_quiescence_ply",
+            t.max_quiescence_ply,
+            &format!("in [max_search_depth ({}), 250]", t.max_search_depth),
+        );
+        c.recommend(
+            t.max_quiescence_extensions <= t.max_quiescence_ply,
+            "timing.max_quiescence_extensions",
+            t.max_quiescence_extensions,
+            &format!("<= max_quiescence_ply ({})", t.max_quiescence_ply),
+        );
+        c.require(t.certain_win_threshold > 0, "timing.certain_win_threshold", t.certain_win_threshold, "> 0");
+        c.require(t.certain_loss_threshold < 0, "timing.certain_loss_threshold", t.certain_loss_threshold, "< 0");
+        c.require(t.stability_time_factor >= 1.0, "timing.stability_time_factor", t.stability_time_factor, ">= 1");
+        c.require(
+            t.stop_check_interval_nodes >= 1,
+            "timing.stop_check_interval_nodes",
+            t.stop_check_interval_nodes,
+            ">= 1",
+        );
+
+        let l = &t.latency;
+        c.fraction("timing.latency.smoothing", l.smoothing);
+        c.require(
+            l.min_overhead_ms <= l.max_overhead_ms,
+            "timing.latency.min_overhead_ms",
+            l.min_overhead_ms,
+            &format!("<= max_overhead_ms ({})", l.max_overhead_ms),
+        );
+        c.recommend(
+            l.max_overhead_ms < t.response_time_budget_ms,
+            "timing.latency.max_overhead_ms",
+            l.max_overhead_ms,
+            &format!("< response_time_budget_ms ({})", t.response_time_budget_ms),
+        );
+
+        let a = &t.allocation;
+        c.fraction("timing.allocation.nominal_fraction", a.nominal_fraction);
+        c.fraction("timing.allocation.forced_move_fraction", a.forced_move_fraction);
+        c.require(a.min_scale > 0.0, "timing.allocation.min_scale", a.min_scale, "> 0");
+        c.require(
+            a.min_scale <= a.max_scale,
+            "timing.allocation.min_scale",
+            a.min_scale,
+            &format!("<= max_scale ({})", a.max_scale),
+        );
+        c.recommend(
+            a.nominal_fraction * a.max_scale <= 1.0,
+            "timing.allocation.max_scale",
+            a.max_scale,
+            &format!("<= 1 / nominal_fraction ({:.2}) so scaled budgets stay within the deadline", 1.0 / a.nominal_fraction),
+        );
+        c.require(a.reference_branching > 0.0, "timing.allocation.reference_branching", a.reference_branching, "> 0");
+        c.require(a.branching_exponent >= 0.0, "timing.allocation.branching_exponent", a.branching_exponent, ">= 0");
+        c.require(a.volatility_reference > 0, "timing.allocation.volatility_reference", a.volatility_reference, "> 0");
+        c.require(a.volatility_exponent >= 0.0, "timing.allocation.volatility_exponent", a.volatility_exponent, ">= 0");
+        c.require(t.bank.critical_swing > 0, "timing.bank.critical_swing", t.bank.critical_swing, "> 0");
+
+        let e = &self.time_estimation;
+        c.require(
+            (0.0..=1.0).contains(&e.model_weight),
+            "time_estimation.model_weight",
+            e.model_weight,
+            "in [0, 1]",
+        );
+        c.require(
+            e.contention_wall_cpu_ratio >= 1.0,
+            "time_estimation.contention_wall_cpu_ratio",
+            e.contention_wall_cpu_ratio,
+            ">= 1",
+        );
+        c.require(
+            e.history_max_observations >= 1,
+            "time_estimation.history_max_observations",
+            e.history_max_observations,
+            ">= 1",
+        );
+        for (mode, est) in [("one_vs_one", &e.one_vs_one), ("multiplayer", &e.multiplayer)] {
+            let base = format!("time_estimation.{}.base_iteration_time_ms", mode);
+            c.require(est.base_iteration_time_ms > 0.0, &base, est.base_iteration_time_ms, "> 0");
+            let branching = format!("time_estimation.{}.branching_factor", mode);
+            c.require(est.branching_factor > 0.0, &branching, est.branching_factor, "> 0");
+            c.recommend(
+                est.branching_factor >= 1.0,
+                &branching,
+                est.branching_factor,
+                ">= 1; below 1 deeper iterations are predicted to be faster",
+            );
+        }
+    }
+
+    fn validate_search(&self, c: &mut Checker) {
+        let s = &self.strategy;
+        c.require(s.min_snakes_for_1v1 >= 2, "strategy.min_snakes_for_1v1", s.min_snakes_for_1v1, ">= 2");
+        c.require(s.min_cpus_for_parallel >= 1, "strategy.min_cpus_for_parallel", s.min_cpus_for_parallel, ">= 1");
+        c.require(
+            ["maxn", "brs", "auto"].contains(&s.multiplayer_search.as_str()),
+            "strategy.multiplayer_search",
+            &s.multiplayer_search,
+            "\"maxn\", \"brs\" or \"auto\"",
+        );
+        c.require(s.brs_min_snakes >= 3, "strategy.brs_min_snakes", s.brs_min_snakes, ">= 3");
+        c.require(
+            crate::evaluator::NAMES.contains(&s.evaluator.as_str()),
+            "strategy.evaluator",
+            &s.evaluator,
+            "\"heuristic\" or \"nn\"",
+        );
+
+        let i = &self.idapos;
+        for (field, value) in [
+            ("idapos.early_game_head_distance_multiplier", i.early_game_head_distance_multiplier),
+            ("idapos.early_game_max_locality_distance", i.early_game_max_locality_distance),
+            ("idapos.late_game_head_distance_multiplier", i.late_game_head_distance_multiplier),
+            ("idapos.late_game_max_locality_distance", i.late_game_max_locality_distance),
+        ] {
+            c.require(value >= 1, field, value, ">= 1");
+        }
+        c.require(i.early_game_turn_threshold >= 0, "idapos.early_game_turn_threshold", i.early_game_turn_threshold, ">= 0");
+
+        let m = &self.move_ordering;
+        c.require(m.history_max_score > 0, "move_ordering.history_max_score", m.history_max_score, "> 0");
+        c.recommend(
+            !m.enable_killer_heuristic || m.killer_moves_per_depth >= 1,
+            "move_ordering.killer_moves_per_depth",
+            m.killer_moves_per_depth,
+            ">= 1 while enable_killer_heuristic is on",
+        );
+        c.recommend(
+            m.killer_moves_per_depth <= 4,
+            "move_ordering.killer_moves_per_depth",
+            m.killer_moves_per_depth,
+            "<= 4 (one per direction; more slots are ignored)",
+        );
+
+        let w = &self.aspiration_windows;
+        if w.enabled {
+            c.require(w.initial_window_size > 0, "aspiration_windows.initial_window_size", w.initial_window_size, "> 0");
+            c.require(
+                w.window_expansion_multiplier >= 2,
+                "aspiration_windows.window_expansion_multiplier",
+                w.window_expansion_multiplier,
+                ">= 2 so failed windows widen",
+            );
+        }
+
+        let cache = &self.eval_cache;
+        c.recommend(
+            !cache.enabled || cache.max_entries >= 1,
+            "eval_cache.max_entries",
+            cache.max_entries,
+            ">= 1 while eval_cache is enabled",
+        );
+        let lazy = &self.lazy_eval;
+        c.require(lazy.futility_margin >= 0, "lazy_eval.futility_margin", lazy.futility_margin, ">= 0");
+        c.require(lazy.razor_margin >= 0, "lazy_eval.razor_margin", lazy.razor_margin, ">= 0");
+        c.recommend(
+            lazy.razor_margin >= lazy.futility_margin,
+            "lazy_eval.razor_margin",
+            lazy.razor_margin,
+            ">= lazy_eval.futility_margin (deeper nodes need wider margins)",
+        );
+        c.require(self.food_paths.max_bfs_depth >= 1, "food_paths.max_bfs_depth", self.food_paths.max_bfs_depth, ">= 1");
+        let routing = &self.food_routing;
+        c.require((1..=100).contains(&routing.health_threshold), "food_routing.health_threshold", routing.health_threshold, "in [1, 100]");
+        c.require(routing.first_step_bonus >= 0, "food_routing.first_step_bonus", routing.first_step_bonus, ">= 0");
+        let stall = &self.stall;
+        c.require(stall.min_length_lead >= 1, "stall.min_length_lead", stall.min_length_lead, ">= 1 (we must be longer)");
+        c.require(stall.follow_bonus >= 0, "stall.follow_bonus", stall.follow_bonus, ">= 0");
+        c.require((0.5..1.0).contains(&stall.min_win_probability), "stall.min_win_probability", stall.min_win_probability, "in [0.5, 1)");
+
+        let contempt = &self.contempt;
+        for (field, value) in [("contempt.weak", contempt.weak), ("contempt.even", contempt.even), ("contempt.strong", contempt.strong)] {
+            c.require(value >= 0, field, value, ">= 0");
+        }
+        c.recommend(
+            contempt.strong <= contempt.even && contempt.even <= contempt.weak,
+            "contempt.even",
+            contempt.even,
+            "between contempt.strong and contempt.weak (weaker opponents make a draw costlier)",
+        );
+        c.recommend(
+            contempt.strong_opponents.iter().all(|name| !contempt.weak_opponents.contains(name)),
+            "contempt.strong_opponents",
+            contempt.strong_opponents.join(", "),
+            "no name also in contempt.weak_opponents (strong wins)",
+        );
+        c.recommend(
+            contempt.weak < -self.scores.head_collision_penalty,
+            "contempt.weak",
+            contempt.weak,
+            "below -scores.head_collision_penalty (ties are kept above a losing exchange)",
+        );
+        let endgame = &self.endgame;
+        c.require(endgame.parity_bonus >= 0, "endgame.parity_bonus", endgame.parity_bonus, ">= 0");
+        c.require(endgame.parity_turn_weight >= 0, "endgame.parity_turn_weight", endgame.parity_turn_weight, ">= 0");
+        c.require(endgame.fill_bonus >= 0, "endgame.fill_bonus", endgame.fill_bonus, ">= 0");
+        let nn = &self.nn;
+        c.require((0.0..=1.0).contains(&nn.blend), "nn.blend", nn.blend, "in [0, 1]");
+        c.require(nn.value_scale > 0.0, "nn.value_scale", nn.value_scale, "> 0");
+        c.require(nn.batch_size >= 1, "nn.batch_size", nn.batch_size, ">= 1");
+        let uses_nn = self.strategy.evaluator == "nn";
+        c.require(!uses_nn || !nn.model_path.is_empty(), "nn.model_path", &nn.model_path, "a model file while strategy.evaluator is \"nn\"");
+        c.recommend(
+            !uses_nn || cfg!(feature = "nn"),
+            "strategy.evaluator",
+            &self.strategy.evaluator,
+            "\"heuristic\" unless built with --features nn (the heuristic is used)",
+        );
+        c.require(
+            self.safety_verifier.opponent_radius >= 1,
+            "safety_verifier.opponent_radius",
+            self.safety_verifier.opponent_radius,
+            ">= 1",
+        );
+        c.require(self.health.self_test_max_ms >= 1, "health.self_test_max_ms", self.health.self_test_max_ms, ">= 1");
+        c.require(self.health.pool_timeout_ms >= 1, "health.pool_timeout_ms", self.health.pool_timeout_ms, ">= 1");
+        c.require(is_hex_color(&self.appearance.color), "appearance.color", &self.appearance.color, "\"#RRGGBB\"");
+        let fraction = self.metrics.slow_turn_fraction;
+        c.require(fraction > 0.0 && fraction <= 1.0, "metrics.slow_turn_fraction", fraction, "in (0, 1]");
+        c.require(
+            self.request_validation.max_board_dimension >= 1,
+            "request_validation.max_board_dimension",
+            self.request_validation.max_board_dimension,
+            ">= 1",
+        );
+        c.require(self.request_validation.max_snakes >= 1, "request_validation.max_snakes", self.request_validation.max_snakes, ">= 1");
+        let con = &self.concurrency;
+        c.require(con.max_concurrent_games >= 1, "concurrency.max_concurrent_games", con.max_concurrent_games, ">= 1");
+        c.recommend(
+            con.session_idle_timeout_ms >= 60_000,
+            "concurrency.session_idle_timeout_ms",
+            con.session_idle_timeout_ms,
+            ">= 60000 (a slow game must not lose its session between moves)",
+        );
+        c.recommend(
+            con.slot_wait_ms < self.timing.effective_budget_ms() / 2,
+            "concurrency.slot_wait_ms",
+            con.slot_wait_ms,
+            &format!("< half the effective budget ({} ms) to leave time to search", self.timing.effective_budget_ms() / 2),
+        );
+        c.require(
+            self.spectator.channel_capacity >= 1,
+            "spectator.channel_capacity",
+            self.spectator.channel_capacity,
+            ">= 1",
+        );
+        c.require(
+            !self.debug.enabled || !self.debug.log_file_path.is_empty(),
+            "debug.log_file_path",
+            "\"\"",
+            "a file path while debug is enabled",
+        );
+        c.require(
+            !self.debug.per_game_files || !self.debug.log_directory.is_empty(),
+            "debug.log_directory",
+            "\"\"",
+            "a directory while per_game_files is enabled",
+        );
+        c.require(self.score_trend.window >= 2, "score_trend.window", self.score_trend.window, ">= 2");
+        c.require(self.score_trend.drop_threshold > 0, "score_trend.drop_threshold", self.score_trend.drop_threshold, "> 0");
+
+        let wp = &self.win_probability;
+        c.require(wp.slope > 0.0 && wp.slope.is_finite(), "win_probability.slope", wp.slope, "> 0 (higher scores must win more often)");
+        c.require(wp.intercept.is_finite(), "win_probability.intercept", wp.intercept, "a finite number");
+        c.require(wp.certain_win > 0.5 && wp.certain_win < 1.0, "win_probability.certain_win", wp.certain_win, "in (0.5, 1)");
+        c.require(wp.certain_loss > 0.0 && wp.certain_loss < 0.5, "win_probability.certain_loss", wp.certain_loss, "in (0, 0.5)");
+        c.require((0.0..=1.0).contains(&wp.decided_min_scale), "win_probability.decided_min_scale", wp.decided_min_scale, "in [0, 1]");
+
+        let stakes = &self.stakes;
+        c.require(Stakes::parse(&stakes.default).is_ok(), "stakes.default", &stakes.default, "\"ladder\" or \"tournament\"");
+        c.recommend(
+            self.timing.network_overhead_ms + stakes.tournament_extra_overhead_ms < self.timing.response_time_budget_ms,
+            "stakes.tournament_extra_overhead_ms",
+            stakes.tournament_extra_overhead_ms,
+            &format!("< response_time_budget_ms - network_overhead_ms ({})", self.timing.response_time_budget_ms.saturating_sub(self.timing.network_overhead_ms)),
+        );
+    }
+
+    fn validate_scores(&self, c: &mut Checker) {
+        let s = &self.scores;
+        c.fraction("scores.temporal_discount_factor", s.temporal_discount_factor as f64);
+        c.require(s.survival_max_multiplier > 0.0, "scores.survival_max_multiplier", s.survival_max_multiplier, "> 0");
+        c.require(s.survival_health_threshold <= 100, "scores.survival_health_threshold", s.survival_health_threshold, "<= 100");
+
+        c.require(s.score_dead_snake < 0, "scores.score_dead_snake", s.score_dead_snake, "< 0");
+        c.require(s.score_survival_penalty < 0, "scores.score_survival_penalty", s.score_survival_penalty, "< 0");
+        c.require(s.score_survival_weight > 0.0, "scores.score_survival_weight", s.score_survival_weight, "> 0");
+        c.require(s.score_zero_health < 0, "scores.score_zero_health", s.score_zero_health, "< 0");
+
+        c.weight("scores.weight_space", s.weight_space as f64);
+        c.weight("scores.weight_health", s.weight_health as f64);
+        c.weight("scores.weight_control", s.weight_control as f64);
+        c.weight("scores.weight_attack", s.weight_attack as f64);
+        c.weight("scores.weight_trap_setting", s.weight_trap_setting as f64);
+        c.require(s.trap_setting_radius >= 1, "scores.trap_setting_radius", s.trap_setting_radius, ">= 1");
+        c.weight("scores.weight_length", s.weight_length as f64);
+
+        c.require(s.health_max > 0.0, "scores.health_max", s.health_max, "> 0");
+        c.require(s.default_food_distance > 0, "scores.default_food_distance", s.default_food_distance, "> 0");
+        c.require(
+            s.starvation_buffer_divisor != 0,
+            "scores.starvation_buffer_divisor",
+            s.starvation_buffer_divisor,
+            "!= 0 (used as a divisor)",
+        );
+        c.require(
+            s.health_urgency_min_multiplier <= s.health_urgency_max_multiplier,
+            "scores.health_urgency_min_multiplier",
+            s.health_urgency_min_multiplier,
+            &format!("<= health_urgency_max_multiplier ({})", s.health_urgency_max_multiplier),
+        );
+
+        c.require(
+            (0.0..=1.0).contains(&s.entrapment_severe_threshold),
+            "scores.entrapment_severe_threshold",
+            s.entrapment_severe_threshold,
+            "in [0, 1]",
+        );
+        c.require(
+            (0.0..=1.0).contains(&s.entrapment_moderate_threshold),
+            "scores.entrapment_moderate_threshold",
+            s.entrapment_moderate_threshold,
+            "in [0, 1]",
+        );
+        c.require(
+            s.entrapment_severe_threshold <= s.entrapment_moderate_threshold,
+            "scores.entrapment_severe_threshold",
+            s.entrapment_severe_threshold,
+            &format!("<= entrapment_moderate_threshold ({})", s.entrapment_moderate_threshold),
+        );
+        c.require(s.entrapment_nearby_threshold >= 1, "scores.entrapment_nearby_threshold", s.entrapment_nearby_threshold, ">= 1");
+
+        c.require(s.territory_scale_factor > 0.0, "scores.territory_scale_factor", s.territory_scale_factor, "> 0");
+        c.require(
+            (0.0..=1.0).contains(&s.see_tie_penalty_factor),
+            "scores.see_tie_penalty_factor",
+            s.see_tie_penalty_factor,
+            "in [0, 1]",
+        );
+        c.require(
+            s.tail_chasing_penalty_exponent > 0.0,
+            "scores.tail_chasing_penalty_exponent",
+            s.tail_chasing_penalty_exponent,
+            "> 0",
+        );
+        c.recommend(s.head_collision_penalty < 0, "scores.head_collision_penalty", s.head_collision_penalty, "< 0");
+    }
+
+    fn validate_rules(&self, c: &mut Checker) {
+        let p = &self.player_indices;
+        c.require(
+            p.player_max_index != p.player_min_index,
+            "player_indices.player_min_index",
+            p.player_min_index,
+            &format!("!= player_max_index ({})", p.player_max_index),
+        );
+
+        let d = &self.direction_encoding;
+        let mut indices = [d.direction_up_index, d.direction_down_index, d.direction_left_index, d.direction_right_index];
+        indices.sort_unstable();
+        c.require(
+            indices == [0, 1, 2, 3],
+            "direction_encoding",
+            format!("{:?}", [d.direction_up_index, d.direction_down_index, d.direction_left_index, d.direction_right_index]),
+            "a permutation of 0, 1, 2, 3",
+        );
+
+        let g = &self.game_rules;
+        c.require(
+            (1..=100).contains(&g.health_on_food),
+            "game_rules.health_on_food",
+            g.health_on_food,
+            "in [1, 100]",
+        );
+        c.require(g.health_loss_per_turn >= 1, "game_rules.health_loss_per_turn", g.health_loss_per_turn, ">= 1");
+        c.require(g.hazard_damage_per_turn >= 0, "game_rules.hazard_damage_per_turn", g.hazard_damage_per_turn, ">= 0");
+        c.require(g.hazard_wall_damage >= 1, "game_rules.hazard_wall_damage", g.hazard_wall_damage, ">= 1");
+        c.require(
+            g.terminal_state_threshold >= 1,
+            "game_rules.terminal_state_threshold",
+            g.terminal_state_threshold,
+            ">= 1",
+        );
+
+        let weight = self.squad.ally_territory_weight;
+        c.require(weight >= 0.0, "squad.ally_territory_weight", weight, ">= 0");
+
+        c.require(self.opening.max_turn >= 0, "opening.max_turn", self.opening.max_turn, ">= 0");
+    }
+
+    fn validate_tuning(&self, c: &mut Checker) {
+        let a = &self.arena;
+        c.require(a.board_size >= 5, "arena.board_size", a.board_size, ">= 5");
+        c.require(a.max_turns >= 1, "arena.max_turns", a.max_turns, ">= 1");
+        c.require(a.search_depth >= 1, "arena.search_depth", a.search_depth, ">= 1");
+        c.require(a.food_spawn_chance <= 100, "arena.food_spawn_chance", a.food_spawn_chance, "<= 100 (percent)");
+
+        let t = &self.tuning;
+        c.require(t.iterations >= 1, "tuning.iterations", t.iterations, ">= 1");
+        c.require(t.games_per_iteration >= 1, "tuning.games_per_iteration", t.games_per_iteration, ">= 1");
+        c.recommend(
+            t.games_per_iteration.is_multiple_of(2),
+            "tuning.games_per_iteration",
+            t.games_per_iteration,
+            "an even number so both configs play each seat equally",
+        );
+        c.require(t.learning_rate > 0.0, "tuning.learning_rate", t.learning_rate, "> 0");
+        c.require(
+            t.perturbation > 0.0 && t.perturbation <= 0.5,
+            "tuning.perturbation",
+            t.perturbation,
+            "in (0, 0.5] (fraction of each parameter's range)",
+        );
+        c.require(t.stability_constant >= 0.0, "tuning.stability_constant", t.stability_constant, ">= 0");
+
+        let e = &self.evolution;
+        c.require(e.population_size >= 2, "evolution.population_size", e.population_size, ">= 2");
+        c.require(e.generations >= 1, "evolution.generations", e.generations, ">= 1");
+        c.require(e.games_per_individual >= 1, "evolution.games_per_individual", e.games_per_individual, ">= 1");
+        c.require(
+            e.elite < e.population_size,
+            "evolution.elite",
+            e.elite,
+            &format!("< population_size ({})", e.population_size),
+        );
+        c.require(
+            e.tournament_size >= 1 && e.tournament_size <= e.population_size,
+            "evolution.tournament_size",
+            e.tournament_size,
+            &format!("in [1, population_size ({})]", e.population_size),
+        );
+        c.require((0.0..=1.0).contains(&e.crossover_rate), "evolution.crossover_rate", e.crossover_rate, "in [0, 1]");
+        c.require((0.0..=1.0).contains(&e.mutation_rate), "evolution.mutation_rate", e.mutation_rate, "in [0, 1]");
+        c.require(
+            e.mutation_sigma > 0.0 && e.mutation_sigma <= 0.5,
+            "evolution.mutation_sigma",
+            e.mutation_sigma,
+            "in (0, 0.5] (fraction of each parameter's range)",
+        );
+
+        let r = &self.rating;
+        c.require(r.elo0 < r.elo1, "rating.elo0", r.elo0, &format!("< elo1 ({})", r.elo1));
+        c.require(r.alpha > 0.0 && r.alpha < 0.5, "rating.alpha", r.alpha, "in (0, 0.5)");
+        c.require(r.beta > 0.0 && r.beta < 0.5, "rating.beta", r.beta, "in (0, 0.5)");
+        c.require(r.batch_games >= 1, "rating.batch_games", r.batch_games, ">= 1");
+        c.require(
+            r.max_games >= r.batch_games,
+            "rating.max_games",
+            r.max_games,
+            &format!(">= batch_games ({})", r.batch_games),
+        );
+
+        c.require(self.puzzles.default_depth >= 1, "puzzles.default_depth", self.puzzles.default_depth, ">= 1");
+        c.require(self.what_if.horizon >= 1, "what_if.horizon", self.what_if.horizon, ">= 1");
+
+        let scores = serde_json::to_value(&self.scores).unwrap_or_default();
+        for (name, &[min, max]) in &t.parameters {
+            let field = format!("tuning.parameters.{}", name);
+            let current = scores.get(name).and_then(serde_json::Value::as_f64);
+            c.require(current.is_some(), &field, name, "a numeric [scores] field");
+            c.require(min < max, &field, format!("[{}, {}]", min, max), "min < max");
+            if let Some(value) = current {
+                c.recommend(
+                    (min..=max).contains(&value),
+                    &field,
+                    format!("[{}, {}]", min, max),
+                    &format!("bounds containing the current value ({})", value),
+                );
+            }
+        }
+    }
+
+    /// Each profile must resolve and its patched scores must pass the [scores] checks
+    /// Only issues the profile introduces are reported; inherited ones are already under scores.*
+    fn validate_profiles(&self, c: &mut Checker) {
+        let mut inherited = Checker { issues: Vec::new() };
+        self.validate_scores(&mut inherited);
+
+        for name in self.profile_names() {
+            let field = format!("profiles.{}", name);
+            if RESERVED_PROFILE_NAMES.contains(&name) {
+                c.push(Severity::Error, &field, name, "a name not used by another route");
+            }
+            let profile = match self.for_profile(name) {
+                Ok(profile) => profile,
+                Err(e) => {
+                    c.push(Severity::Error, &field, self.profiles[name].to_string(), &format!("valid [scores] overrides ({})", e));
+                    continue;
+                }
+            };
+
+            if profile.appearance.color != self.appearance.color {
+                let color = &profile.appearance.color;
+                c.require(is_hex_color(color), &format!("{}.appearance.color", field), color, "\"#RRGGBB\"");
+            }
+
+            let mut scores = Checker { issues: Vec::new() };
+            profile.validate_scores(&mut scores);
+            for mut issue in scores.issues.into_iter().filter(|issue| !inherited.issues.contains(issue)) {
+                issue.field = issue.field.replacen("scores", &field, 1);
+                c.issues.push(issue);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shipped_config_has_no_errors() {
+        let config = Config::from_file("Snake.toml").expect("Snake.toml should be parseable");
+        assert_eq!(config.validation_errors(), Vec::new());
+        assert_eq!(Config::default_hardcoded().validation_errors(), Vec::new());
+    }
+
+    #[test]
+    fn test_issues_name_field_value_and_range() {
+        let mut config = Config::default_hardcoded();
+        config.timing.network_overhead_ms = 500;
+        config.scores.starvation_buffer_divisor = 0;
+        config.scores.weight_attack = 0.0;
+
+        let issues = config.validate();
+        let overhead = issues
+            .iter()
+            .find(|i| i.field == "timing.network_overhead_ms")
+            .expect("overhead above budget is reported");
+        assert_eq!(overhead.severity, Severity::Error);
+        assert_eq!(overhead.to_string(), "error: timing.network_overhead_ms = 500 (expected < response_time_budget_ms (400))");
+
+        assert!(issues.iter().any(|i| i.field == "scores.starvation_buffer_divisor" && i.severity == Severity::Error));
+        assert!(issues.iter().any(|i| i.field == "scores.weight_attack" && i.severity == Severity::Warning));
+        assert_eq!(config.validation_errors().len(), 2);
+    }
+
+    #[test]
+    fn test_profile_issues_are_reported_under_the_profile() {
+        let mut config = Config::default_hardcoded();
+        config.profiles.insert("broken".to_string(), serde_json::json!({ "temporal_discount_factor": 0.0 }));
+        config.profiles.insert("typo".to_string(), serde_json::json!({ "weight_atack": 1.0 }));
+        config.profiles.insert("healthz".to_string(), serde_json::json!({}));
+
+        let fields: Vec<String> = config.validation_errors().into_iter().map(|i| i.field).collect();
+        assert_eq!(
+            fields,
+            vec!["profiles.broken.temporal_discount_factor", "profiles.healthz", "profiles.typo"]
+        );
+    }
+
+    #[test]
+    fn test_tuning_parameters_must_name_score_fields_with_bounds() {
+        let mut config = Config::default_hardcoded();
+        config.tuning.parameters.insert("weight_atack".to_string(), [0.0, 1.0]);
+        config.tuning.parameters.insert("weight_length".to_string(), [200.0, 50.0]);
+
+        let fields: Vec<String> = config.validation_errors().into_iter().map(|i| i.field).collect();
+        assert_eq!(fields, vec!["tuning.parameters.weight_atack", "tuning.parameters.weight_length"]);
+    }
+}
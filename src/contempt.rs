@@ -0,0 +1,93 @@
+// Duel contempt
+//
+// A head-to-head between equal lengths kills both snakes. The rules call that a draw, but
+// ladders rank it as a loss for both, so in an even duel drifting into a tied exchange is
+// no better than being beaten. The flat SEE tie penalty (head_collision_penalty times
+// see_tie_penalty_factor) can score above a position where we survive but trail, so in
+// duels contempt replaces it with a penalty below the survive-but-behind range, though
+// never at or below a losing exchange: a draw still beats certain death. How much depends
+// on the opponent: against a weak one a draw throws away a likely win, while against a
+// strong one it is closer to what the game was worth. Opponents are classed by
+// the name the engine sends; unlisted names are even.
+
+use crate::config::Config;
+use crate::types::Board;
+
+/// Opponent class selecting the contempt value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpponentStrength {
+    Weak,
+    Even,
+    Strong,
+}
+
+/// Class of an opponent by its name (contempt.weak_opponents / contempt.strong_opponents)
+pub fn strength(name: &str, config: &Config) -> OpponentStrength {
+    let contempt = &config.contempt;
+    if contempt.strong_opponents.iter().any(|listed| listed == name) {
+        OpponentStrength::Strong
+    } else if contempt.weak_opponents.iter().any(|listed| listed == name) {
+        OpponentStrength::Weak
+    } else {
+        OpponentStrength::Even
+    }
+}
+
+/// Contempt against an opponent of this strength (score points, >= 0)
+pub fn value(strength: OpponentStrength, config: &Config) -> i32 {
+    match strength {
+        OpponentStrength::Weak => config.contempt.weak,
+        OpponentStrength::Even => config.contempt.even,
+        OpponentStrength::Strong => config.contempt.strong,
+    }
+}
+
+/// Value of a tied exchange for snake `idx`, given the flat SEE tie penalty `flat`
+/// In a duel (exactly two snakes alive) with contempt enabled, the tie scores at least the
+/// contempt against the other duelist below zero, but stays above a losing exchange
+/// (head_collision_penalty); otherwise `flat` stands
+pub fn tie_penalty(board: &Board, idx: usize, flat: i32, config: &Config) -> i32 {
+    if !config.contempt.enabled {
+        return flat;
+    }
+    let mut alive = board.snakes.iter().enumerate().filter(|(_, s)| s.health > 0);
+    let (Some(a), Some(b), None) = (alive.next(), alive.next(), alive.next()) else { return flat };
+    let opponent = if a.0 == idx { b.1 } else if b.0 == idx { a.1 } else { return flat };
+    let losing = config.scores.head_collision_penalty;
+    flat.min(-value(strength(&opponent.name, config), config)).max(losing.saturating_add(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board_builder::BoardBuilder;
+
+    #[test]
+    fn test_duel_ties_score_the_contempt_of_the_opponent_class() {
+        let mut config = Config::default_hardcoded();
+        config.contempt.strong_opponents = vec!["them".to_string()];
+        let duel = BoardBuilder::new(11, 11).snake("us", &[(5, 5), (5, 4), (5, 3)]).snake("them", &[(7, 5), (7, 4), (7, 3)]).build();
+
+        let flat = -20_000;
+        assert_eq!(strength("them", &config), OpponentStrength::Strong);
+        assert_eq!(tie_penalty(&duel, 0, flat, &config), -config.contempt.strong);
+        config.contempt.strong_opponents.clear();
+        assert_eq!(tie_penalty(&duel, 0, flat, &config), -config.contempt.even);
+
+        // However large the contempt, a tie stays above a losing exchange
+        config.contempt.even = 10_000_000;
+        assert_eq!(tie_penalty(&duel, 0, flat, &config), config.scores.head_collision_penalty + 1);
+        config.contempt.even = Config::default_hardcoded().contempt.even;
+
+        // Three snakes alive: not a duel
+        let three = BoardBuilder::new(11, 11)
+            .snake("us", &[(5, 5), (5, 4), (5, 3)])
+            .snake("them", &[(7, 5), (7, 4), (7, 3)])
+            .snake("other", &[(1, 1), (1, 2), (1, 3)])
+            .build();
+        assert_eq!(tie_penalty(&three, 0, flat, &config), flat);
+
+        config.contempt.enabled = false;
+        assert_eq!(tie_penalty(&duel, 0, flat, &config), flat);
+    }
+}
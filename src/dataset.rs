@@ -0,0 +1,401 @@
+// Training data export
+//
+// Turns logged games into (state tensor, chosen move, game outcome) records for training a
+// policy/value network offline. Every logged position is encoded from the point of view of the
+// snake that moved (the first snake of the logged board, as in replay), and each position can
+// be multiplied by the 8 symmetries of the board (rotations and reflections, with the chosen
+// move transformed alike) since the rules do not care about orientation.
+//
+// File format (little-endian), written by DatasetWriter and read back by read_dataset:
+//
+//   header   magic "BSDS" | version u32 | channels u32 | size u32 | records u64   (24 bytes)
+//   record   state f32[channels][size][size] | move u8 | outcome i8
+//
+// The state is indexed [channel][y][x] with y = 0 the bottom row (Battlesnake coordinates);
+// boards smaller than `size` are padded with zeros (see the mask channel). `move` indexes
+// Direction::ALL (up, down, left, right) and `outcome` is 1 if the snake won, -1 if it lost
+// and 0 if the log does not tell (see game_outcome). In numpy:
+//
+//   dtype = np.dtype([("state", "<f4", (channels, size, size)), ("move", "u1"), ("outcome", "i1")])
+//   records = np.fromfile(path, dtype=dtype, offset=24)
+
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use crate::analysis;
+use crate::config::Config;
+use crate::replay::{LogEntry, ReplayEngine};
+use crate::types::{Battlesnake, Board, Coord, Direction};
+
+pub const MAGIC: [u8; 4] = *b"BSDS";
+pub const VERSION: u32 = 1;
+const HEADER_BYTES: u64 = 24;
+
+/// Planes of the state tensor
+pub const CHANNELS: usize = 9;
+/// Our head (1)
+pub const CH_OUR_HEAD: usize = 0;
+/// Our body: (length - segment index) / length, so the tail is the smallest value
+pub const CH_OUR_BODY: usize = 1;
+/// Our health / 100 on every board cell
+pub const CH_OUR_HEALTH: usize = 2;
+/// Opponent heads (1)
+pub const CH_OPPONENT_HEADS: usize = 3;
+/// Opponent bodies, encoded like our body
+pub const CH_OPPONENT_BODIES: usize = 4;
+/// Heads of opponents at least as long as us (a head-to-head there does not win)
+pub const CH_DANGEROUS_HEADS: usize = 5;
+pub const CH_FOOD: usize = 6;
+pub const CH_HAZARDS: usize = 7;
+/// 1 on the board, 0 on padding
+pub const CH_MASK: usize = 8;
+
+/// One of the 8 rotations and reflections of a board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symmetry {
+    Identity,
+    /// Quarter turn counter-clockwise
+    Rotate90,
+    Rotate180,
+    /// Quarter turn clockwise
+    Rotate270,
+    /// Mirror left-right
+    FlipX,
+    /// Mirror top-bottom
+    FlipY,
+    /// Mirror across the x = y diagonal
+    Transpose,
+    /// Mirror across the other diagonal
+    AntiTranspose,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::Rotate90,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::FlipX,
+        Symmetry::FlipY,
+        Symmetry::Transpose,
+        Symmetry::AntiTranspose,
+    ];
+
+    /// True if the transformed board is `height` wide and `width` high
+    pub fn swaps_axes(self) -> bool {
+        matches!(self, Symmetry::Rotate90 | Symmetry::Rotate270 | Symmetry::Transpose | Symmetry::AntiTranspose)
+    }
+
+    /// Image of `c` on a `width` x `height` board
+    pub fn coord(self, c: Coord, width: i32, height: i32) -> Coord {
+        let (n, m) = (width - 1, height - 1);
+        let (x, y) = match self {
+            Symmetry::Identity => (c.x, c.y),
+            Symmetry::Rotate90 => (m - c.y, c.x),
+            Symmetry::Rotate180 => (n - c.x, m - c.y),
+            Symmetry::Rotate270 => (c.y, n - c.x),
+            Symmetry::FlipX => (n - c.x, c.y),
+            Symmetry::FlipY => (c.x, m - c.y),
+            Symmetry::Transpose => (c.y, c.x),
+            Symmetry::AntiTranspose => (m - c.y, n - c.x),
+        };
+        Coord { x, y }
+    }
+
+    /// Image of a move: the step between the images of a cell and its neighbour
+    pub fn direction(self, dir: Direction) -> Direction {
+        let origin = Coord { x: 1, y: 1 };
+        let delta = self.coord(dir.apply(&origin), 3, 3) - self.coord(origin, 3, 3);
+        Direction::from_delta(delta).unwrap_or(dir)
+    }
+
+    /// `board` with every coordinate transformed (snake order and attributes unchanged)
+    pub fn board(self, board: &Board) -> Board {
+        let (width, height) = (board.width, board.height as i32);
+        let map = |cells: &[Coord]| cells.iter().map(|&c| self.coord(c, width, height)).collect::<Vec<_>>();
+        let (new_width, new_height) = if self.swaps_axes() { (height, width) } else { (width, height) };
+        Board {
+            width: new_width,
+            height: new_height as u32,
+            food: map(&board.food),
+            hazards: map(&board.hazards),
+            snakes: board
+                .snakes
+                .iter()
+                .map(|snake| Battlesnake {
+                    body: map(&snake.body),
+                    head: self.coord(snake.head, width, height),
+                    ..snake.clone()
+                })
+                .collect(),
+            occupancy: None,
+        }
+    }
+}
+
+/// One training example
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    /// CHANNELS x size x size values, [channel][y][x]
+    pub state: Vec<f32>,
+    pub chosen: Direction,
+    pub outcome: i8,
+}
+
+/// State tensor of `board` seen by its first snake, padded to `size` x `size`
+/// None if the board has no snakes or does not fit
+pub fn encode(board: &Board, size: usize) -> Option<Vec<f32>> {
+    let us = board.snakes.first()?;
+    if board.width as usize > size || board.height as usize > size {
+        return None;
+    }
+    let mut state = vec![0.0f32; CHANNELS * size * size];
+    let mut set = |channel: usize, c: Coord, value: f32| {
+        if c.x >= 0 && c.y >= 0 && (c.x as usize) < size && (c.y as usize) < size {
+            let idx = (channel * size + c.y as usize) * size + c.x as usize;
+            state[idx] = state[idx].max(value);
+        }
+    };
+
+    for y in 0..board.height as i32 {
+        for x in 0..board.width {
+            set(CH_MASK, Coord { x, y }, 1.0);
+            set(CH_OUR_HEALTH, Coord { x, y }, us.health.clamp(0, 100) as f32 / 100.0);
+        }
+    }
+    for (idx, snake) in board.snakes.iter().enumerate() {
+        let Some(&head) = snake.body.first() else { continue };
+        let (head_channel, body_channel) = if idx == 0 { (CH_OUR_HEAD, CH_OUR_BODY) } else { (CH_OPPONENT_HEADS, CH_OPPONENT_BODIES) };
+        set(head_channel, head, 1.0);
+        if idx > 0 && snake.length >= us.length {
+            set(CH_DANGEROUS_HEADS, head, 1.0);
+        }
+        let len = snake.body.len();
+        for (i, &segment) in snake.body.iter().enumerate().skip(1) {
+            set(body_channel, segment, (len - i) as f32 / len as f32);
+        }
+    }
+    for &food in &board.food {
+        set(CH_FOOD, food, 1.0);
+    }
+    for &hazard in &board.hazards {
+        set(CH_HAZARDS, hazard, 1.0);
+    }
+    Some(state)
+}
+
+/// Splits log entries into games: one per snake that made the moves (logs hold the first
+/// snake's moves, so self-play files interleave two games), in order of first appearance
+pub fn split_games(entries: &[LogEntry]) -> Vec<Vec<LogEntry>> {
+    let mut games: Vec<(String, Vec<LogEntry>)> = Vec::new();
+    for entry in entries {
+        let Ok(us) = entry.our_snake() else { continue };
+        match games.iter_mut().find(|(id, _)| *id == us.id) {
+            Some((_, game)) => game.push(entry.clone()),
+            None => games.push((us.id.clone(), vec![entry.clone()])),
+        }
+    }
+    games.into_iter().map(|(_, game)| game).collect()
+}
+
+/// Outcome of one logged game for the snake that moved
+///
+/// Logs end with our last /move, not with the final board, so the result is read from that
+/// move (see analysis::analyze_death): 1 if we were the last snake left or survive every
+/// opponent reply (the game ended with us alive), -1 if every reply kills us, and 0 if it
+/// depends on the replies or the game was solo.
+pub fn game_outcome(entries: &[LogEntry], config: &Config) -> i8 {
+    let (Some(first), Some(last)) = (entries.iter().min_by_key(|e| e.turn), entries.iter().max_by_key(|e| e.turn)) else {
+        return 0;
+    };
+    if first.board.snakes.len() < 2 {
+        return 0;
+    }
+    if last.board.snakes.len() < 2 {
+        return 1;
+    }
+    let Ok(final_move) = ReplayEngine::parse_direction(&last.chosen_move) else { return 0 };
+    match analysis::analyze_death(&last.board, last.turn, final_move, config) {
+        Some(death) if death.fatal_replies >= 1.0 => -1,
+        Some(death) if death.fatal_replies == 0.0 => 1,
+        _ => 0,
+    }
+}
+
+/// (root score, won) samples of one logged game for win_probability::fit, empty if the log does not tell who won
+/// Turns without a logged search result are searched again by `engine`; scores beyond the
+/// raw certain win / loss thresholds are proven outcomes and left out of the fit
+pub fn calibration_samples(entries: &[LogEntry], engine: &ReplayEngine, config: &Config) -> Vec<(i32, bool)> {
+    let won = match game_outcome(entries, config) {
+        1 => true,
+        -1 => false,
+        _ => return Vec::new(),
+    };
+    entries
+        .iter()
+        .filter_map(|entry| match &entry.result {
+            Some(result) => Some(result.score),
+            None => engine.replay_entry(entry).ok().map(|replayed| replayed.replayed_score),
+        })
+        .filter(|&score| score < config.timing.certain_win_threshold && score > config.timing.certain_loss_threshold)
+        .map(|score| (score, won))
+        .collect()
+}
+
+/// Records of one game; every position is written once per symmetry in `symmetries`
+/// Entries with an unreadable move or a board larger than `size` are skipped
+pub fn game_records(entries: &[LogEntry], config: &Config, size: usize, symmetries: &[Symmetry]) -> Vec<Record> {
+    let outcome = game_outcome(entries, config);
+    let mut records = Vec::new();
+    for entry in entries {
+        let Ok(chosen) = ReplayEngine::parse_direction(&entry.chosen_move) else { continue };
+        for &symmetry in symmetries {
+            let board = symmetry.board(&entry.board);
+            if let Some(state) = encode(&board, size) {
+                records.push(Record { state, chosen: symmetry.direction(chosen), outcome });
+            }
+        }
+    }
+    records
+}
+
+fn io_error(e: std::io::Error) -> String {
+    format!("Failed to write dataset: {}", e)
+}
+
+/// Streams records into the binary format above
+pub struct DatasetWriter<W: Write + Seek> {
+    out: W,
+    size: usize,
+    records: u64,
+}
+
+impl<W: Write + Seek> DatasetWriter<W> {
+    /// Writes the header (with a record count of 0 until finish)
+    pub fn new(mut out: W, size: usize) -> Result<Self, String> {
+        out.write_all(&MAGIC).map_err(io_error)?;
+        for value in [VERSION, CHANNELS as u32, size as u32] {
+            out.write_all(&value.to_le_bytes()).map_err(io_error)?;
+        }
+        out.write_all(&0u64.to_le_bytes()).map_err(io_error)?;
+        Ok(DatasetWriter { out, size, records: 0 })
+    }
+
+    pub fn write(&mut self, record: &Record) -> Result<(), String> {
+        if record.state.len() != CHANNELS * self.size * self.size {
+            return Err(format!("Record has {} values, expected {}", record.state.len(), CHANNELS * self.size * self.size));
+        }
+        let mut bytes = Vec::with_capacity(record.state.len() * 4 + 2);
+        for value in &record.state {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        let move_idx = Direction::ALL.iter().position(|&dir| dir == record.chosen).unwrap_or(0);
+        bytes.push(move_idx as u8);
+        bytes.push(record.outcome as u8);
+        self.out.write_all(&bytes).map_err(io_error)?;
+        self.records += 1;
+        Ok(())
+    }
+
+    /// Writes the record count into the header; returns it and the output
+    pub fn finish(mut self) -> Result<(u64, W), String> {
+        self.out.seek(SeekFrom::Start(HEADER_BYTES - 8)).map_err(io_error)?;
+        self.out.write_all(&self.records.to_le_bytes()).map_err(io_error)?;
+        self.out.seek(SeekFrom::End(0)).map_err(io_error)?;
+        self.out.flush().map_err(io_error)?;
+        Ok((self.records, self.out))
+    }
+}
+
+/// Reads a whole dataset: (size, records)
+pub fn read_dataset<R: Read>(mut input: R) -> Result<(usize, Vec<Record>), String> {
+    let read_err = |e: std::io::Error| format!("Failed to read dataset: {}", e);
+    let mut header = [0u8; HEADER_BYTES as usize];
+    input.read_exact(&mut header).map_err(read_err)?;
+    let u32_at = |at: usize| u32::from_le_bytes([header[at], header[at + 1], header[at + 2], header[at + 3]]);
+    if header[..4] != MAGIC {
+        return Err("Not a dataset file (bad magic)".to_string());
+    }
+    if u32_at(4) != VERSION || u32_at(8) as usize != CHANNELS {
+        return Err(format!("Unsupported dataset version {} with {} channels", u32_at(4), u32_at(8)));
+    }
+    let size = u32_at(12) as usize;
+    let mut count_bytes = [0u8; 8];
+    count_bytes.copy_from_slice(&header[16..24]);
+    let count = u64::from_le_bytes(count_bytes);
+
+    let values = CHANNELS * size * size;
+    let mut buf = vec![0u8; values * 4 + 2];
+    let mut records = Vec::new();
+    for _ in 0..count {
+        input.read_exact(&mut buf).map_err(read_err)?;
+        let state = buf[..values * 4].chunks_exact(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect();
+        let chosen = *Direction::ALL.get(buf[values * 4] as usize).ok_or("Move index out of range")?;
+        records.push(Record { state, chosen, outcome: buf[values * 4 + 1] as i8 });
+    }
+    Ok((size, records))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board_builder::BoardBuilder;
+    use std::io::Cursor;
+
+    fn entry(turn: i32, board: Board, chosen: &str) -> LogEntry {
+        serde_json::from_value(serde_json::json!({
+            "turn": turn,
+            "chosen_move": chosen,
+            "board": board,
+            "timestamp": "2025-01-01T00:00:00Z",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_symmetries_move_snakes_and_moves_together() {
+        let board = BoardBuilder::new(5, 3).snake("a", &[(1, 0), (0, 0)]).food(&[(4, 2)]).build();
+        for symmetry in Symmetry::ALL {
+            let image = symmetry.board(&board);
+            assert_eq!((image.width as u32, image.height), if symmetry.swaps_axes() { (3, 5) } else { (5, 3) });
+            assert!(image.cell_index(image.food[0]).is_some(), "{:?} keeps food on the board", symmetry);
+            for dir in Direction::ALL {
+                let moved = symmetry.coord(dir.apply(&board.snakes[0].body[0]), 5, 3);
+                assert_eq!(symmetry.direction(dir).apply(&image.snakes[0].body[0]), moved, "{:?} {:?}", symmetry, dir);
+            }
+        }
+        assert_eq!(Symmetry::Rotate90.direction(Direction::Right), Direction::Up);
+        assert_eq!(Symmetry::FlipX.direction(Direction::Up), Direction::Up);
+    }
+
+    #[test]
+    fn test_games_export_and_read_back() {
+        let duel = BoardBuilder::new(7, 7).snake("us", &[(1, 1), (1, 0)]).snake("them", &[(5, 5), (5, 6)]).build();
+        let won = BoardBuilder::new(7, 7).snake("us", &[(1, 2), (1, 1)]).build();
+        let entries = vec![entry(0, duel.clone(), "up"), entry(1, won, "right")];
+        let config = Config::default_hardcoded();
+        assert_eq!(game_outcome(&entries, &config), 1);
+        assert_eq!(game_outcome(&entries[..1], &config), 1, "the log ends with us surviving every reply");
+        let walled = BoardBuilder::new(7, 7).snake("us", &[(1, 0), (1, 1)]).snake("them", &[(5, 5), (5, 6)]).build();
+        assert_eq!(game_outcome(&[entry(0, walled, "down")], &config), -1, "the last move leaves the board");
+        assert_eq!(split_games(&entries).len(), 1);
+
+        let records = game_records(&entries, &config, 11, &Symmetry::ALL);
+        assert_eq!(records.len(), 16);
+        let state = &records[0].state;
+        let at = |channel: usize, x: usize, y: usize| state[(channel * 11 + y) * 11 + x];
+        assert_eq!((at(CH_OUR_HEAD, 1, 1), at(CH_OUR_BODY, 1, 0), at(CH_OPPONENT_HEADS, 5, 5)), (1.0, 0.5, 1.0));
+        assert_eq!(at(CH_DANGEROUS_HEADS, 5, 5), 1.0, "an equal-length head is dangerous");
+        assert_eq!((at(CH_MASK, 6, 6), at(CH_MASK, 7, 0)), (1.0, 0.0));
+        assert!(game_records(&entries, &config, 5, &[Symmetry::Identity]).is_empty(), "7x7 does not fit in 5x5");
+
+        let mut writer = DatasetWriter::new(Cursor::new(Vec::new()), 11).unwrap();
+        for record in &records {
+            writer.write(record).unwrap();
+        }
+        let (count, out) = writer.finish().unwrap();
+        assert_eq!(count, 16);
+        let bytes = out.into_inner();
+        assert_eq!(bytes.len() as u64, HEADER_BYTES + 16 * (CHANNELS as u64 * 121 * 4 + 2));
+        assert_eq!(read_dataset(Cursor::new(bytes)).unwrap(), (11, records));
+    }
+}
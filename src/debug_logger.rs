@@ -2,16 +2,53 @@
 //
 // This module provides fire-and-forget async logging to avoid blocking
 // the main request/response cycle. Each turn's state is written to a JSONL file.
+//
+// By default every game goes to one file (debug.log_file_path), which grows until restart and
+// interleaves concurrent games. With debug.per_game_files each game gets its own
+// <log_directory>/<game id>.jsonl instead, gzipped at /end if debug.compress is set. The
+// directory is pruned by count and age (debug.max_files, debug.max_age_hours) whenever a
+// new game file is opened.
 
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use log::error;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 use tokio::fs::{File, OpenOptions};
 use tokio::io::AsyncWriteExt;
 use tokio::sync::Mutex;
 
+use crate::config::DebugConfig;
+use crate::schema::SearchResultV1;
 use crate::types::{Board, Direction};
 
+/// Out-of-band data of a turn, kept in the debug log for replay analysis
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TurnNotes {
+    /// Id of the snake the bot played (absent in older logs, whose first snake is ours)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub you: Option<String>,
+    /// Panic message if the search crashed and a fallback move was played
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub panic: Option<String>,
+    /// Root score fell sharply against the previous turns (see score_trend.rs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score_drop: Option<String>,
+    /// Final-move overrides applied after the search (one description each)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<String>,
+    /// /move request body exactly as received (debug.log_requests)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request: Option<String>,
+    /// Response body sent for it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<String>,
+}
+
 /// Represents a single debug log entry
 #[derive(Debug, Serialize)]
 struct DebugLogEntry {
@@ -19,25 +56,132 @@ struct DebugLogEntry {
     chosen_move: String,
     board: Board,
     timestamp: String,
+    /// Versioned search result (see schema.rs)
+    result: SearchResultV1,
+    #[serde(flatten)]
+    notes: TurnNotes,
+}
+
+/// Which game files to keep in the per-game log directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Retention {
+    /// Newest files kept (0 = no limit)
+    pub max_files: usize,
+    /// Files last written longer ago are deleted
+    pub max_age: Option<Duration>,
+}
+
+impl Retention {
+    pub fn from_config(config: &DebugConfig) -> Self {
+        Retention {
+            max_files: config.max_files,
+            max_age: (config.max_age_hours > 0).then(|| Duration::from_secs(config.max_age_hours * 3600)),
+        }
+    }
+}
+
+/// Open files of the games in progress, by game id
+struct PerGameFiles {
+    directory: PathBuf,
+    compress: bool,
+    retention: Retention,
+    open: Mutex<HashMap<String, File>>,
+}
+
+/// Where entries are written
+#[derive(Clone)]
+enum Sink {
+    /// Every game in one file
+    Single(Arc<Mutex<Option<File>>>),
+    /// One file per game
+    PerGame(Arc<PerGameFiles>),
 }
 
 /// Shared debug logger state
-/// Uses Arc<Mutex<File>> to allow concurrent async writes from multiple tasks
+/// Files sit behind async mutexes to allow concurrent writes from multiple tasks
 #[derive(Clone)]
 pub struct DebugLogger {
-    file: Arc<Mutex<Option<File>>>,
+    sink: Sink,
     enabled: bool,
+    /// Write tasks spawned and not finished yet (see flush)
+    pending: Arc<AtomicUsize>,
+}
+
+/// Keeps DebugLogger::flush waiting until dropped (see DebugLogger::pending_write)
+pub struct PendingWrite(Arc<AtomicUsize>);
+
+impl Drop for PendingWrite {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// `id` usable as a file name: characters other than letters, digits, '-' and '_' become '_'
+pub fn file_safe_id(id: &str) -> String {
+    id.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' }).collect()
+}
+
+/// Log file of a game: `<directory>/<game id>.jsonl` (see file_safe_id)
+pub fn game_log_path(directory: &Path, game_id: &str) -> PathBuf {
+    directory.join(format!("{}.jsonl", file_safe_id(game_id)))
+}
+
+/// True for the files this logger writes (`.jsonl`, `.jsonl.gz`)
+fn is_game_log(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.ends_with(".jsonl") || name.ends_with(".jsonl.gz"))
+}
+
+/// Gzips `path` into `<path>.gz` and removes the original; returns the compressed path
+pub fn compress_log(path: &Path) -> Result<PathBuf, String> {
+    let mut gz_name = path.as_os_str().to_owned();
+    gz_name.push(".gz");
+    let gz_path = PathBuf::from(gz_name);
+
+    let mut input = std::fs::File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    let output =
+        std::fs::File::create(&gz_path).map_err(|e| format!("Failed to create {}: {}", gz_path.display(), e))?;
+    let mut encoder = GzEncoder::new(output, Compression::default());
+    std::io::copy(&mut input, &mut encoder).map_err(|e| format!("Failed to compress {}: {}", path.display(), e))?;
+    encoder.finish().map_err(|e| format!("Failed to compress {}: {}", path.display(), e))?;
+    std::fs::remove_file(path).map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+    Ok(gz_path)
+}
+
+/// Applies `retention` to the game logs in `directory`, never touching `keep` (files still
+/// being written); returns the deleted files
+pub fn prune_logs(directory: &Path, retention: &Retention, keep: &[PathBuf]) -> Result<Vec<PathBuf>, String> {
+    let entries =
+        std::fs::read_dir(directory).map_err(|e| format!("Failed to read {}: {}", directory.display(), e))?;
+    let mut logs: Vec<(SystemTime, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| is_game_log(path) && !keep.contains(path))
+        .filter_map(|path| Some((std::fs::metadata(&path).ok()?.modified().ok()?, path)))
+        .collect();
+    // Newest first
+    logs.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+
+    let now = SystemTime::now();
+    let kept_slots = if retention.max_files == 0 { usize::MAX } else { retention.max_files.saturating_sub(keep.len()) };
+    let mut removed = Vec::new();
+    for (idx, (modified, path)) in logs.into_iter().enumerate() {
+        let expired = retention.max_age.is_some_and(|max_age| now.duration_since(modified).unwrap_or_default() > max_age);
+        if expired || idx >= kept_slots {
+            std::fs::remove_file(&path).map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+            removed.push(path);
+        }
+    }
+    Ok(removed)
 }
 
 impl DebugLogger {
-    /// Creates a new debug logger
+    /// Creates a new debug logger writing every game to one file
     /// If enabled is true, initializes the log file (truncating if it exists)
     pub async fn new(enabled: bool, log_file_path: &str) -> Self {
         if !enabled {
-            return DebugLogger {
-                file: Arc::new(Mutex::new(None)),
-                enabled: false,
-            };
+            return Self::disabled();
         }
 
         // Initialize the log file
@@ -51,77 +195,264 @@ impl DebugLogger {
             Ok(file) => {
                 log::info!("Debug logging enabled: {}", log_file_path);
                 DebugLogger {
-                    file: Arc::new(Mutex::new(Some(file))),
+                    sink: Sink::Single(Arc::new(Mutex::new(Some(file)))),
                     enabled: true,
+                    pending: Arc::default(),
                 }
             }
             Err(e) => {
                 error!("Failed to create debug log file '{}': {}", log_file_path, e);
-                DebugLogger {
-                    file: Arc::new(Mutex::new(None)),
-                    enabled: false,
-                }
+                Self::disabled()
             }
         }
     }
 
+    /// Creates a debug logger writing one file per game into `directory`
+    pub async fn per_game(directory: &str, compress: bool, retention: Retention) -> Self {
+        if let Err(e) = tokio::fs::create_dir_all(directory).await {
+            error!("Failed to create debug log directory '{}': {}", directory, e);
+            return Self::disabled();
+        }
+        log::info!("Debug logging enabled: one file per game in {}", directory);
+        DebugLogger {
+            sink: Sink::PerGame(Arc::new(PerGameFiles {
+                directory: PathBuf::from(directory),
+                compress,
+                retention,
+                open: Mutex::new(HashMap::new()),
+            })),
+            enabled: true,
+            pending: Arc::default(),
+        }
+    }
+
+    /// Debug logger for `[debug]` (disabled, single file or per game)
+    pub async fn from_config(config: &DebugConfig) -> Self {
+        if !config.enabled {
+            Self::disabled()
+        } else if config.per_game_files {
+            Self::per_game(&config.log_directory, config.compress, Retention::from_config(config)).await
+        } else {
+            Self::new(true, &config.log_file_path).await
+        }
+    }
+
     /// Creates a disabled debug logger (no-op)
     pub fn disabled() -> Self {
         DebugLogger {
-            file: Arc::new(Mutex::new(None)),
+            sink: Sink::Single(Arc::new(Mutex::new(None))),
             enabled: false,
+            pending: Arc::default(),
         }
     }
 
     /// Logs a move decision asynchronously (fire-and-forget)
     /// This spawns a tokio task that writes to the file without blocking
-    pub fn log_move(&self, turn: i32, board: Board, chosen_move: Direction) {
+    pub fn log_move(
+        &self,
+        game_id: &str,
+        turn: i32,
+        board: Board,
+        chosen_move: Direction,
+        result: SearchResultV1,
+        notes: TurnNotes,
+    ) {
         if !self.enabled {
             return;
         }
 
-        let file_handle = self.file.clone();
-        let chosen_move_str = chosen_move.as_str().to_string();
+        let sink = self.sink.clone();
+        let game_id = game_id.to_string();
+        let entry = DebugLogEntry {
+            turn,
+            chosen_move: chosen_move.as_str().to_string(),
+            board,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            result,
+            notes,
+        };
 
         // Spawn fire-and-forget task
+        let pending = self.pending.clone();
+        pending.fetch_add(1, Ordering::AcqRel);
+        tokio::spawn(async move {
+            match sink {
+                Sink::Single(file_handle) => {
+                    if let Some(file) = file_handle.lock().await.as_mut() {
+                        Self::write_entry(file, &entry).await;
+                    }
+                }
+                Sink::PerGame(games) => Self::log_game_entry(&games, &game_id, &entry).await,
+            }
+            pending.fetch_sub(1, Ordering::AcqRel);
+        });
+    }
+
+    /// Counts an entry still being prepared elsewhere as pending until the guard is dropped,
+    /// so a flush in the meantime waits for it (log_move must be called before the drop)
+    pub fn pending_write(&self) -> PendingWrite {
+        self.pending.fetch_add(1, Ordering::AcqRel);
+        PendingWrite(self.pending.clone())
+    }
+
+    /// Closes the file of a finished game, compressing it if configured (fire-and-forget;
+    /// no-op for a single log file)
+    pub fn finish_game(&self, game_id: &str) {
+        let games = match &self.sink {
+            Sink::PerGame(games) if self.enabled => games.clone(),
+            _ => return,
+        };
+        let game_id = game_id.to_string();
+        let pending = self.pending.clone();
+        pending.fetch_add(1, Ordering::AcqRel);
+
         tokio::spawn(async move {
-            Self::log_move_internal(file_handle, turn, board, chosen_move_str).await;
+            Self::close_game_file(&games, &game_id).await;
+            pending.fetch_sub(1, Ordering::AcqRel);
         });
     }
 
-    /// Internal async function that performs the actual file write
-    async fn log_move_internal(
-        file_handle: Arc<Mutex<Option<File>>>,
-        turn: i32,
-        board: Board,
-        chosen_move: String,
-    ) {
-        let mut file_guard = file_handle.lock().await;
-
-        if let Some(file) = file_guard.as_mut() {
-            let entry = DebugLogEntry {
-                turn,
-                chosen_move,
-                board,
-                timestamp: chrono::Utc::now().to_rfc3339(),
+    /// Waits until every entry logged so far is written and every finished game is closed
+    /// (the server never waits; batch tools do before the next game or before exiting)
+    pub async fn flush(&self) {
+        while self.pending.load(Ordering::Acquire) > 0 {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+    }
+
+    /// Flushes, drops and (if configured) compresses the file of a finished game
+    async fn close_game_file(games: &PerGameFiles, game_id: &str) {
+        let mut file = match games.open.lock().await.remove(game_id) {
+            Some(file) => file,
+            None => return,
+        };
+        if let Err(e) = file.flush().await {
+            error!("Failed to flush debug log: {}", e);
+        }
+        drop(file);
+
+        if games.compress {
+            let path = game_log_path(&games.directory, game_id);
+            match tokio::task::spawn_blocking(move || compress_log(&path)).await {
+                Ok(Err(e)) => error!("Failed to compress debug log: {}", e),
+                Err(e) => error!("Debug log compression task failed: {}", e),
+                Ok(Ok(_)) => {}
+            }
+        }
+    }
+
+    /// Appends to the game's file, opening it (and pruning the directory) on its first turn
+    async fn log_game_entry(games: &PerGameFiles, game_id: &str, entry: &DebugLogEntry) {
+        let mut open = games.open.lock().await;
+        if !open.contains_key(game_id) {
+            let path = game_log_path(&games.directory, game_id);
+            let file = match OpenOptions::new().create(true).append(true).open(&path).await {
+                Ok(file) => file,
+                Err(e) => {
+                    error!("Failed to create debug log file '{}': {}", path.display(), e);
+                    return;
+                }
             };
+            open.insert(game_id.to_string(), file);
 
-            match serde_json::to_string(&entry) {
-                Ok(json_line) => {
-                    let line_with_newline = format!("{}\n", json_line);
-                    if let Err(e) = file.write_all(line_with_newline.as_bytes()).await {
-                        error!("Failed to write debug log entry: {}", e);
-                    } else {
-                        // Flush to ensure data is written to disk
-                        if let Err(e) = file.flush().await {
-                            error!("Failed to flush debug log: {}", e);
-                        }
+            let keep: Vec<PathBuf> = open.keys().map(|id| game_log_path(&games.directory, id)).collect();
+            let (directory, retention) = (games.directory.clone(), games.retention);
+            tokio::task::spawn_blocking(move || match prune_logs(&directory, &retention, &keep) {
+                Ok(removed) if !removed.is_empty() => log::info!("Pruned {} old debug log(s)", removed.len()),
+                Ok(_) => {}
+                Err(e) => error!("Failed to prune debug logs: {}", e),
+            });
+        }
+        if let Some(file) = open.get_mut(game_id) {
+            Self::write_entry(file, entry).await;
+        }
+    }
+
+    /// Writes one JSONL line and flushes it
+    async fn write_entry(file: &mut File, entry: &DebugLogEntry) {
+        match serde_json::to_string(entry) {
+            Ok(json_line) => {
+                let line_with_newline = format!("{}\n", json_line);
+                if let Err(e) = file.write_all(line_with_newline.as_bytes()).await {
+                    error!("Failed to write debug log entry: {}", e);
+                } else {
+                    // Flush to ensure data is written to disk
+                    if let Err(e) = file.flush().await {
+                        error!("Failed to flush debug log: {}", e);
                     }
                 }
-                Err(e) => {
-                    error!("Failed to serialize debug log entry: {}", e);
-                }
+            }
+            Err(e) => {
+                error!("Failed to serialize debug log entry: {}", e);
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("debug_logger_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_game_logs_compress_and_prune() {
+        let dir = scratch_dir("retention");
+        assert_eq!(game_log_path(&dir, "abc-1/../x"), dir.join("abc-1____x.jsonl"));
+
+        let path = game_log_path(&dir, "game");
+        std::fs::write(&path, "{\"turn\":0}\n").unwrap();
+        let gz = compress_log(&path).unwrap();
+        assert_eq!(gz, dir.join("game.jsonl.gz"));
+        assert!(!path.exists());
+        let mut text = String::new();
+        flate2::read::GzDecoder::new(std::fs::File::open(&gz).unwrap()).read_to_string(&mut text).unwrap();
+        assert_eq!(text, "{\"turn\":0}\n");
+
+        // Five games written an hour apart (oldest first), plus an unrelated file
+        let now = SystemTime::now();
+        let games: Vec<PathBuf> = (0..5).map(|i| game_log_path(&dir, &format!("g{}", i))).collect();
+        for (i, game) in games.iter().enumerate() {
+            let file = std::fs::File::create(game).unwrap();
+            file.set_modified(now - Duration::from_secs(3600 * (5 - i as u64))).unwrap();
+        }
+        std::fs::remove_file(&gz).unwrap();
+        std::fs::write(dir.join("notes.txt"), "").unwrap();
+
+        // Under 2.5 hours old: g3 and g4
+        let by_age = Retention { max_files: 0, max_age: Some(Duration::from_secs(9000)) };
+        let removed = prune_logs(&dir, &by_age, &[]).unwrap();
+        assert_eq!(removed.len(), 3);
+        assert!(games[3].exists() && games[4].exists() && dir.join("notes.txt").exists());
+
+        // One slot: the open g3 takes it, so the closed g4 goes even though it is newer
+        let by_count = Retention { max_files: 1, max_age: None };
+        assert_eq!(prune_logs(&dir, &by_count, &[games[3].clone()]).unwrap(), vec![games[4].clone()]);
+        assert!(games[3].exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_flush_waits_for_every_entry() {
+        let dir = scratch_dir("flush");
+        let logger = DebugLogger::per_game(dir.to_str().unwrap(), false, Retention { max_files: 0, max_age: None }).await;
+        let board = Board { height: 3, width: 3, food: vec![], snakes: vec![], hazards: vec![], occupancy: None };
+        for turn in 0..20 {
+            let result = SearchResultV1::new(turn, Direction::Up, 0, 1, 0);
+            logger.log_move("g", turn, board.clone(), Direction::Up, result, TurnNotes::default());
+        }
+        logger.finish_game("g");
+        logger.flush().await;
+
+        let text = std::fs::read_to_string(game_log_path(&dir, "g")).unwrap();
+        assert_eq!(text.lines().count(), 20);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
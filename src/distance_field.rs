@@ -0,0 +1,340 @@
+// Single-pass distance and ownership field for evaluation
+//
+// Evaluation used to run its own BFS per component: a flood fill for the attack cache, a
+// second identical fill with distances for the space score, and an adversarial flood fill
+// for territory. The field builds the obstacle timing grid once, runs one time-aware BFS per
+// evaluated snake into a flat distance array, and derives ownership (closest head wins,
+// longer snake on ties) from those distances. Every component reads from the result.
+
+use crate::config::Config;
+use crate::hazard_walls::HazardWalls;
+use crate::simple_profiler;
+use crate::types::{Board, Coord};
+
+/// Distance value for cells a snake cannot reach
+pub const UNREACHED: u16 = u16::MAX;
+
+/// Territory control result: cells owned per snake index and cells claimed by anyone
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ControlSummary {
+    pub owned: Vec<usize>,
+    pub claimed: usize,
+}
+
+/// Per-snake numbers evaluation needs from the field (small enough to cache and clone)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSummary {
+    /// Reachable cells per snake index (None for snakes not in the field)
+    pub reachable: Vec<Option<usize>>,
+    /// Cells reachable within min(length, entrapment_nearby_threshold) turns, per snake index
+    pub nearby: Vec<usize>,
+    pub control: ControlSummary,
+    pub food: FoodPaths,
+}
+
+/// Path distances from the field's heads to every food on the board
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FoodPaths {
+    food: Vec<Coord>,
+    /// Per snake index: head and turns to each food in `food` order (None if not in the field)
+    turns: Vec<Option<(Coord, Vec<u16>)>>,
+    /// BFS depth of a bounded field (None: complete)
+    limit: Option<u16>,
+    /// Distance of food no path reaches (the number of cells, farther than any reachable food)
+    unreachable: i32,
+}
+
+impl FoodPaths {
+    /// Turns for snake `snake_idx` to reach `food`
+    /// Food a bounded field did not reach counts as limit + 1, or its Manhattan distance if larger
+    /// None if the snake is not in the field or `food` was not on its board
+    pub fn distance(&self, snake_idx: usize, food: Coord) -> Option<i32> {
+        let food_idx = self.food.iter().position(|&f| f == food)?;
+        let (head, turns) = self.turns.get(snake_idx)?.as_ref()?;
+        Some(match (turns[food_idx], self.limit) {
+            (UNREACHED, Some(limit)) => (limit as i32 + 1).max(head.manhattan(food)),
+            (UNREACHED, None) => self.unreachable,
+            (turns, _) => turns as i32,
+        })
+    }
+
+    /// Whether some path takes snake `snake_idx` to `food` (within the limit of a bounded field)
+    pub fn reaches(&self, snake_idx: usize, food: Coord) -> bool {
+        let Some(food_idx) = self.food.iter().position(|&f| f == food) else { return false };
+        self.turns.get(snake_idx).and_then(Option::as_ref).is_some_and(|(_, turns)| turns[food_idx] != UNREACHED)
+    }
+}
+
+/// Time-aware BFS distances from each evaluated head, plus cell ownership
+pub struct DistanceField {
+    width: i32,
+    height: i32,
+    /// Snake indices with a distance layer, in the order they were given
+    sources: Vec<usize>,
+    /// sources.len() layers of width * height distances
+    distances: Vec<u16>,
+    /// Owning snake per cell: body owner, else closest head (None if unclaimed)
+    owner: Vec<Option<usize>>,
+    /// BFS depth limit of build_within (None: complete field)
+    limit: Option<u16>,
+}
+
+impl DistanceField {
+    /// Builds the field for the given snakes (dead or bodiless snakes are skipped)
+    /// Every alive body blocks its cells until it has moved away, as in Bot::flood_fill_bfs;
+    /// hazard walls (if any) block theirs for good
+    pub fn build(board: &Board, sources: &[usize], walls: Option<&HazardWalls>) -> Self {
+        Self::build_within(board, sources, walls, None)
+    }
+
+    /// Like build, with the BFS stopped `limit` turns from each head (cells beyond count as
+    /// unreached, and the ownership they would get is lost)
+    pub fn build_within(board: &Board, sources: &[usize], walls: Option<&HazardWalls>, limit: Option<u16>) -> Self {
+        let _prof = simple_profiler::ProfileGuard::new("distance_field");
+
+        let width = board.width;
+        let height = board.height as i32;
+        let cells = (width.max(0) * height.max(0)) as usize;
+        let index = |c: Coord| -> Option<usize> {
+            if c.x < 0 || c.y < 0 || c.x >= width || c.y >= height {
+                None
+            } else {
+                Some((c.y * width + c.x) as usize)
+            }
+        };
+
+        // Turns until each cell is free (0 = free); segments are written tail first, so a
+        // stacked tail keeps the turns of its head-most copy
+        let mut blocked_for = vec![0u16; cells];
+        for &wall in walls.map_or(&[][..], HazardWalls::cells) {
+            if let Some(i) = index(wall) {
+                blocked_for[i] = u16::MAX;
+            }
+        }
+        for snake in board.snakes.iter().filter(|s| s.health > 0) {
+            let len = snake.body.len();
+            for (seg_idx, &segment) in snake.body.iter().enumerate().rev() {
+                if let Some(i) = index(segment) {
+                    blocked_for[i] = (len - seg_idx) as u16;
+                }
+            }
+        }
+
+        let sources: Vec<usize> = sources
+            .iter()
+            .copied()
+            .filter(|&idx| board.snakes.get(idx).is_some_and(|s| s.health > 0 && !s.body.is_empty()))
+            .collect();
+
+        let mut distances = vec![UNREACHED; sources.len() * cells];
+        let mut queue: Vec<(Coord, u16)> = Vec::with_capacity(cells);
+        for (layer, &idx) in sources.iter().enumerate() {
+            let layer = &mut distances[layer * cells..(layer + 1) * cells];
+            let start = board.snakes[idx].body[0];
+            let Some(start_i) = index(start) else { continue };
+
+            queue.clear();
+            queue.push((start, 0));
+            layer[start_i] = 0;
+
+            let mut next_idx = 0;
+            while next_idx < queue.len() {
+                let (pos, turns) = queue[next_idx];
+                next_idx += 1;
+                if limit.is_some_and(|limit| turns >= limit) {
+                    continue;
+                }
+
+                for next in pos.neighbors() {
+                    let Some(i) = index(next) else { continue };
+                    if layer[i] != UNREACHED || blocked_for[i] > turns {
+                        continue;
+                    }
+                    layer[i] = turns + 1;
+                    queue.push((next, turns + 1));
+                }
+            }
+        }
+
+        // Ownership: evaluated bodies belong to their snake, other cells to the closest head
+        // Ties go to the longer snake, then to the earlier source
+        let mut owner: Vec<Option<usize>> = vec![None; cells];
+        let mut by_length: Vec<usize> = (0..sources.len()).collect();
+        by_length.sort_by_key(|&layer| std::cmp::Reverse(board.snakes[sources[layer]].length));
+
+        for cell in 0..cells {
+            let mut best: Option<(u16, usize)> = None;
+            for &layer in &by_length {
+                let dist = distances[layer * cells + cell];
+                if dist != UNREACHED && best.is_none_or(|(best_dist, _)| dist < best_dist) {
+                    best = Some((dist, sources[layer]));
+                }
+            }
+            owner[cell] = best.map(|(_, idx)| idx);
+        }
+        for &idx in &sources {
+            for &segment in &board.snakes[idx].body {
+                if let Some(i) = index(segment) {
+                    owner[i] = Some(idx);
+                }
+            }
+        }
+
+        DistanceField { width, height, sources, distances, owner, limit }
+    }
+
+    fn layer(&self, snake_idx: usize) -> Option<&[u16]> {
+        let cells = self.owner.len();
+        self.sources
+            .iter()
+            .position(|&idx| idx == snake_idx)
+            .map(|layer| &self.distances[layer * cells..(layer + 1) * cells])
+    }
+
+    /// Turns for the snake's head to reach `c` (None if unreachable or not in the field)
+    pub fn distance(&self, snake_idx: usize, c: Coord) -> Option<u16> {
+        if c.x < 0 || c.y < 0 || c.x >= self.width || c.y >= self.height {
+            return None;
+        }
+        let dist = self.layer(snake_idx)?[(c.y * self.width + c.x) as usize];
+        (dist != UNREACHED).then_some(dist)
+    }
+
+    /// Cells the snake can reach (including its head), None if the snake is not in the field
+    pub fn reachable(&self, snake_idx: usize) -> Option<usize> {
+        self.layer(snake_idx).map(|layer| layer.iter().filter(|&&d| d != UNREACHED).count())
+    }
+
+    /// Cells the snake can reach within `turns` turns
+    pub fn reachable_within(&self, snake_idx: usize, turns: usize) -> usize {
+        self.layer(snake_idx)
+            .map(|layer| layer.iter().filter(|&&d| d != UNREACHED && (d as usize) <= turns).count())
+            .unwrap_or(0)
+    }
+
+    /// Territory counts for `num_snakes` snake indices
+    pub fn control_summary(&self, num_snakes: usize) -> ControlSummary {
+        let mut owned = vec![0; num_snakes];
+        let mut claimed = 0;
+        for owner in self.owner.iter().flatten() {
+            if let Some(count) = owned.get_mut(*owner) {
+                *count += 1;
+            }
+            claimed += 1;
+        }
+        ControlSummary { owned, claimed }
+    }
+
+    /// Path distances from the field's heads to the food on `board`
+    pub fn food_paths(&self, board: &Board) -> FoodPaths {
+        let turns = (0..board.snakes.len())
+            .map(|idx| {
+                let head = *board.snakes[idx].body.first()?;
+                self.layer(idx)?;
+                Some((head, board.food.iter().map(|&food| self.distance(idx, food).unwrap_or(UNREACHED)).collect()))
+            })
+            .collect();
+        let unreachable = self.width.max(0) * self.height.max(0);
+        FoodPaths { food: board.food.clone(), turns, limit: self.limit, unreachable }
+    }
+
+    /// Everything evaluate_state reads from the field
+    pub fn summarize(&self, board: &Board, config: &Config) -> FieldSummary {
+        let num_snakes = board.snakes.len();
+        let reachable = (0..num_snakes).map(|idx| self.reachable(idx)).collect();
+        let nearby = (0..num_snakes)
+            .map(|idx| {
+                let length = board.snakes[idx].length.min(config.scores.entrapment_nearby_threshold);
+                self.reachable_within(idx, length.max(0) as usize)
+            })
+            .collect();
+
+        FieldSummary { reachable, nearby, control: self.control_summary(num_snakes), food: self.food_paths(board) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Battlesnake;
+
+    fn snake(id: &str, body: Vec<Coord>) -> Battlesnake {
+        Battlesnake {
+            id: id.to_string(),
+            name: id.to_string(),
+            health: 100,
+            head: body[0],
+            length: body.len() as i32,
+            body,
+            latency: "0".to_string(),
+            shout: None,
+            squad: None,
+        }
+    }
+
+    #[test]
+    fn test_tail_cells_open_up_over_time() {
+        // 3x1 corridor: head at x=0 with its own body behind it to the right
+        let board = Board {
+            height: 1,
+            width: 4,
+            food: vec![],
+            snakes: vec![snake("a", vec![Coord { x: 1, y: 0 }, Coord { x: 2, y: 0 }, Coord { x: 3, y: 0 }])],
+            hazards: vec![],
+            occupancy: None,
+        };
+        let field = DistanceField::build(&board, &[0], None);
+
+        assert_eq!(field.reachable(0), Some(2), "head plus the free cell to the left");
+        assert_eq!(field.distance(0, Coord { x: 0, y: 0 }), Some(1));
+        assert_eq!(field.distance(0, Coord { x: 2, y: 0 }), None, "neck never frees up behind the head");
+    }
+
+    #[test]
+    fn test_closest_head_owns_and_longer_wins_ties() {
+        let board = Board {
+            height: 1,
+            width: 7,
+            food: vec![],
+            snakes: vec![
+                snake("short", vec![Coord { x: 1, y: 0 }, Coord { x: 0, y: 0 }]),
+                snake("long", vec![Coord { x: 5, y: 0 }, Coord { x: 6, y: 0 }, Coord { x: 6, y: 0 }]),
+            ],
+            hazards: vec![],
+            occupancy: None,
+        };
+        let field = DistanceField::build(&board, &[0, 1], None);
+        let summary = field.control_summary(2);
+
+        // x=3 is equidistant (2 turns each): the longer snake takes it
+        assert_eq!(summary.owned, vec![3, 4]);
+        assert_eq!(summary.claimed, 7);
+    }
+
+    #[test]
+    fn test_food_paths_go_around_bodies() {
+        // A wall snake along y = 1 with a tail stacked at (1, 1) leaves a gap at x = 0: food
+        // straight above is 2 cells away by Manhattan distance but 6 turns by path
+        let board = Board {
+            height: 3,
+            width: 5,
+            food: vec![Coord { x: 2, y: 2 }],
+            snakes: vec![
+                snake("a", vec![Coord { x: 2, y: 0 }, Coord { x: 3, y: 0 }]),
+                snake(
+                    "wall",
+                    [(4, 1), (4, 1), (3, 1), (2, 1), (1, 1), (1, 1), (1, 1), (1, 1)].iter().map(|&(x, y)| Coord { x, y }).collect(),
+                ),
+            ],
+            hazards: vec![],
+            occupancy: None,
+        };
+        let food = board.food[0];
+        let paths = DistanceField::build(&board, &[0], None).food_paths(&board);
+        assert_eq!(paths.distance(0, food), Some(6));
+        assert_eq!(paths.distance(1, food), None, "not in the field");
+
+        let bounded = DistanceField::build_within(&board, &[0], None, Some(2)).food_paths(&board);
+        assert_eq!(bounded.distance(0, food), Some(3), "beyond the BFS depth: depth + 1");
+    }
+}
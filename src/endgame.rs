@@ -0,0 +1,133 @@
+// Duel endgame parity
+//
+// Late in a duel, once no food matters, the game is decided by who runs out of moves first,
+// usually far beyond the search horizon. Parity estimates it from the evaluation's distance
+// field. Each snake's Voronoi region holds the cells it reaches first (time-aware, so the tail
+// schedule is accounted for) plus its own body. With at least as many free cells there as it
+// has body cells it can chase its tail until it starves; otherwise it has about one move per
+// free cell left. Food is relevant when either snake has a path to it shorter than its health.
+//
+// The evaluation adds endgame.parity_bonus for winning the race (minus for losing it, zero
+// for dying together) plus parity_turn_weight per turn of margin, so the search seals off
+// regions when the count favours us and avoids it otherwise. At the root, the dedicated
+// endgame mode compares the parity after each of our moves and gives fill_bonus to the moves
+// with the best margin.
+
+use crate::bot::Bot;
+use crate::config::Config;
+use crate::distance_field::{DistanceField, FieldSummary};
+use crate::types::{Board, Direction};
+
+/// Turns each duelist can survive without food
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parity {
+    pub our_turns: i32,
+    pub their_turns: i32,
+}
+
+impl Parity {
+    pub fn margin(&self) -> i32 {
+        self.our_turns - self.their_turns
+    }
+}
+
+/// Parity for snake `idx` in a duel without relevant food, None otherwise (or if either
+/// snake is not in the field)
+pub fn parity(board: &Board, idx: usize, field: &FieldSummary, config: &Config) -> Option<Parity> {
+    let alive: Vec<usize> = (0..board.snakes.len()).filter(|&i| board.snakes[i].health > 0).collect();
+    let &[a, b] = alive.as_slice() else { return None };
+    let opp = if idx == a { b } else if idx == b { a } else { return None };
+    if field.reachable.get(idx)?.is_none() || field.reachable.get(opp)?.is_none() {
+        return None;
+    }
+    let food_matters = board.food.iter().any(|&food| {
+        !config.food_paths.enabled
+            || [idx, opp].iter().any(|&i| field.food.reaches(i, food) && field.food.distance(i, food).is_some_and(|d| d < board.snakes[i].health))
+    });
+    if food_matters {
+        return None;
+    }
+    let turns = |i: usize| {
+        let snake = &board.snakes[i];
+        let free = field.control.owned.get(i).copied().unwrap_or(0) as i32 - snake.length;
+        if free >= snake.length {
+            snake.health
+        } else {
+            snake.health.min(free.max(0))
+        }
+    };
+    Some(Parity { our_turns: turns(idx), their_turns: turns(opp) })
+}
+
+/// Endgame term of snake `idx` (0 outside duel endgames)
+pub fn parity_score(board: &Board, idx: usize, field: &FieldSummary, config: &Config) -> i32 {
+    let Some(parity) = parity(board, idx, field, config) else { return 0 };
+    let margin = parity.margin();
+    margin.signum() * config.endgame.parity_bonus + margin * config.endgame.parity_turn_weight
+}
+
+/// Root endgame mode: the parity now and the margin after each of our legal moves (before
+/// the opponent replies), None outside duel endgames
+pub fn root_margins(board: &Board, our_idx: usize, config: &Config) -> Option<(Parity, Vec<(Direction, i32)>)> {
+    let summary = |board: &Board| {
+        let sources: Vec<usize> = (0..board.snakes.len()).filter(|&i| board.snakes[i].health > 0).collect();
+        DistanceField::build(board, &sources, config.hazard_walls.as_deref()).summarize(board, config)
+    };
+    let now = parity(board, our_idx, &summary(board), config)?;
+    let margins = Bot::generate_legal_moves(board, &board.snakes[our_idx], config)
+        .into_iter()
+        .filter_map(|mv| {
+            let mut child = board.clone();
+            Bot::apply_move(&mut child, our_idx, mv, config);
+            parity(&child, our_idx, &summary(&child), config).map(|p| (mv, p.margin()))
+        })
+        .collect();
+    Some((now, margins))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board_builder::BoardBuilder;
+
+    /// Duel without food: the opponent's head is sealed into the corner by our body
+    fn boxed_in() -> Board {
+        BoardBuilder::new(11, 11)
+            .snake("us", &[(2, 1), (2, 2), (1, 2), (0, 2), (0, 3), (0, 4), (0, 5)])
+            .snake("them", &[(0, 0), (1, 0), (2, 0), (3, 0), (4, 0)])
+            .health("them", 60)
+            .build()
+    }
+
+    fn summary(board: &Board, config: &Config) -> FieldSummary {
+        DistanceField::build(board, &[0, 1], None).summarize(board, config)
+    }
+
+    #[test]
+    fn test_parity_counts_regions_and_health() {
+        let config = Config::default_hardcoded();
+        let board = boxed_in();
+        let field = summary(&board, &config);
+        let parity = parity(&board, 0, &field, &config).expect("duel without food");
+        assert_eq!(parity.our_turns, 100, "room to chase our tail until we starve");
+        assert!(parity.their_turns < 60, "boxed in: {:?}", parity);
+        assert_eq!(parity_score(&board, 1, &field, &config), -parity_score(&board, 0, &field, &config));
+
+        // Reachable food the opponent can live on switches the mode off
+        let mut fed = board.clone();
+        fed.food.push(crate::types::Coord { x: 0, y: 0 });
+        assert_eq!(super::parity(&fed, 0, &summary(&fed, &config), &config), None);
+    }
+
+    #[test]
+    fn test_root_margins_avoid_sealing_ourselves_in() {
+        let config = Config::default_hardcoded();
+        let board = boxed_in();
+        let (now, margins) = root_margins(&board, 0, &config).unwrap();
+        assert!(now.margin() > 0);
+        // Left walks into the corner pocket with the opponent: the race is no longer won
+        let margin = |dir| margins.iter().find(|&&(mv, _)| mv == dir).map(|&(_, m)| m).unwrap();
+        assert!(margin(Direction::Right) > 90, "{:?}", margins);
+        assert!(margin(Direction::Left) < 10, "{:?}", margins);
+    }
+}
@@ -0,0 +1,50 @@
+// Typed errors of the library API
+//
+// Inside the crate errors are Strings: a log line or a 400 body is all they ever become.
+// The entry points that tools and tests build on return BotError instead, so a caller can
+// tell a bad position from an unreadable log or a crashed search and degrade accordingly:
+// Bot::try_get_move, Bot::evaluate_move_detailed, LogEntry::our_snake and the ReplayEngine
+// methods. BotError converts into String, so `?` keeps working in String-returning code.
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum BotError {
+    /// The game state cannot be played (request_validation::sanitize rejected it)
+    #[error("Invalid game state: {0}")]
+    InvalidState(String),
+    #[error("Snake with id '{0}' not found in board state")]
+    SnakeNotFound(String),
+    #[error("Snake '{0}' has no body")]
+    EmptyBody(String),
+    #[error("No snakes found in board state")]
+    NoSnakes,
+    #[error("{0}")]
+    UnknownProfile(String),
+    #[error("Invalid direction: {0}")]
+    InvalidDirection(String),
+    #[error("Failed to open log file: {0}")]
+    LogOpen(#[source] std::io::Error),
+    #[error("Failed to read line {line}: {source}")]
+    LogRead { line: usize, source: std::io::Error },
+    #[error("Failed to parse JSON on line {line}: {source}")]
+    LogParse { line: usize, source: serde_json::Error },
+    /// A /move request logged with debug.log_requests that does not parse or validate
+    #[error("Turn {turn}: {reason}")]
+    LoggedRequest { turn: i32, reason: String },
+    #[error("Turn {0} not found in log file")]
+    TurnNotFound(i32),
+    #[error("Failed to start search thread: {0}")]
+    SearchThread(#[source] std::io::Error),
+    #[error("Search thread panicked on turn {0}")]
+    SearchPanicked(i32),
+    /// Expected-move validation failures (ReplayEngine::validate_expected_moves)
+    #[error("{0}")]
+    UnexpectedMoves(String),
+}
+
+impl From<BotError> for String {
+    fn from(e: BotError) -> Self {
+        e.to_string()
+    }
+}
@@ -0,0 +1,227 @@
+// Per-thread cache of the expensive evaluation components
+//
+// The distance field (space, entrapment and territory) dominates evaluation time, but it
+// only depends on the board layout: which cells each snake occupies and how many turns
+// until each segment moves away. Moves that only change health, and transpositions the TT
+// misses because of it, leave that layout untouched. Field summaries are stored under a
+// region hash of the layout (plus the food cells when they carry food path distances, see
+// food_paths) and reused until a move changes it. IDAPOS active sets
+// (idapos.cache_active_sets) are kept the same way under the node's board hash and the
+// locality threshold, so transpositions and the re-searches of iterative deepening skip the
+// body scans. Each table is cleared once it reaches eval_cache.max_entries.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::config::Config;
+use crate::distance_field::FieldSummary;
+use crate::simple_profiler;
+use crate::types::{Board, Coord};
+
+#[derive(Default)]
+struct EvalCache {
+    /// field layout -> summary of the distance field
+    fields: HashMap<u64, FieldSummary>,
+    /// (board hash, locality threshold, our index) -> IDAPOS active set
+    active_sets: HashMap<(u64, i32, usize), Vec<usize>>,
+}
+
+thread_local! {
+    static EVAL_CACHE: RefCell<EvalCache> = RefCell::new(EvalCache::default());
+}
+
+/// Looks `key` up in one of this thread's tables, computing and storing it on a miss
+/// `compute` runs outside the cache borrow, so it may use other thread-local pools
+/// `record` counts the lookup for the profiler
+fn lookup_or_compute<K, V>(
+    table: fn(&mut EvalCache) -> &mut HashMap<K, V>,
+    key: K,
+    config: &Config,
+    record: fn(bool),
+    compute: impl FnOnce() -> V,
+) -> V
+where
+    K: std::hash::Hash + Eq,
+    V: Clone,
+{
+    let cached = EVAL_CACHE.with(|cache| table(&mut cache.borrow_mut()).get(&key).cloned());
+    record(cached.is_some());
+    if let Some(value) = cached {
+        return value;
+    }
+
+    let value = compute();
+    EVAL_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let table = table(&mut cache);
+        if table.len() >= config.eval_cache.max_entries {
+            table.clear();
+        }
+        let capacity = table.capacity();
+        table.insert(key, value.clone());
+        simple_profiler::record_hash_growth(capacity, table.capacity());
+    });
+    value
+}
+
+/// Cached distance field summary for the evaluated snakes `sources`
+pub fn field_summary(
+    board: &Board,
+    sources: &[usize],
+    config: &Config,
+    compute: impl FnOnce() -> FieldSummary,
+) -> FieldSummary {
+    if !config.eval_cache.enabled {
+        return compute();
+    }
+    let key = field_hash(board, sources, config);
+    lookup_or_compute(|cache| &mut cache.fields, key, config, simple_profiler::record_eval_cache_lookup, compute)
+}
+
+/// Cached IDAPOS active set of a search node (`board_hash` is its transposition hash)
+pub fn active_set(
+    board_hash: u64,
+    locality_threshold: i32,
+    our_idx: usize,
+    config: &Config,
+    compute: impl FnOnce() -> Vec<usize>,
+) -> Vec<usize> {
+    if !config.idapos.cache_active_sets {
+        return compute();
+    }
+    let key = (board_hash, locality_threshold, our_idx);
+    lookup_or_compute(|cache| &mut cache.active_sets, key, config, simple_profiler::record_idapos_cache_lookup, compute)
+}
+
+#[inline]
+fn mix(hash: u64, value: u64) -> u64 {
+    // splitmix64 finalizer over the running hash
+    let mut z = (hash ^ value).wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[inline]
+fn coord_value(c: Coord, extra: u64) -> u64 {
+    (c.x as u16 as u64) | ((c.y as u16 as u64) << 16) | (extra << 32)
+}
+
+/// Region hash for flood fills: board size plus every alive segment and its turns until free
+pub fn occupancy_hash(board: &Board) -> u64 {
+    let mut hash = mix(board.width as u64, board.height as u64);
+    for snake in board.snakes.iter().filter(|s| s.health > 0) {
+        let len = snake.body.len();
+        for (seg_idx, &segment) in snake.body.iter().enumerate() {
+            hash = mix(hash, coord_value(segment, (len - seg_idx) as u64));
+        }
+    }
+    hash
+}
+
+/// Region hash for a distance field: the layout plus each source's index and length
+/// (length breaks ownership ties and caps the nearby radius together with the config), and
+/// the food cells when the summary carries food paths
+fn field_hash(board: &Board, sources: &[usize], config: &Config) -> u64 {
+    let mut hash = mix(occupancy_hash(board), config.scores.entrapment_nearby_threshold as u64);
+    for &idx in sources.iter().filter(|&&idx| idx < board.snakes.len()) {
+        hash = mix(hash, ((idx as u64) << 32) | board.snakes[idx].length as u32 as u64);
+    }
+    if config.food_paths.enabled {
+        for &food in &board.food {
+            hash = mix(hash, coord_value(food, 1));
+        }
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distance_field::{ControlSummary, DistanceField, FoodPaths};
+    use crate::types::Battlesnake;
+
+    fn board() -> Board {
+        let body = vec![Coord { x: 2, y: 2 }, Coord { x: 2, y: 1 }, Coord { x: 2, y: 0 }];
+        Board {
+            height: 7,
+            width: 7,
+            food: vec![Coord { x: 5, y: 5 }],
+            snakes: vec![Battlesnake {
+                id: "a".to_string(),
+                name: "a".to_string(),
+                health: 80,
+                head: body[0],
+                length: body.len() as i32,
+                body,
+                latency: "0".to_string(),
+                shout: None,
+                squad: None,
+            }],
+            hazards: vec![],
+            occupancy: None,
+        }
+    }
+
+    #[test]
+    fn test_layout_hash_ignores_food_and_health() {
+        let a = board();
+        let mut b = board();
+        b.food.clear();
+        b.snakes[0].health = 12;
+        assert_eq!(occupancy_hash(&a), occupancy_hash(&b));
+
+        // Moving the snake changes the layout
+        b.snakes[0].body = vec![Coord { x: 2, y: 3 }, Coord { x: 2, y: 2 }, Coord { x: 2, y: 1 }];
+        assert_ne!(occupancy_hash(&a), occupancy_hash(&b));
+    }
+
+    #[test]
+    fn test_field_is_computed_once_per_layout() {
+        let mut config = Config::default_hardcoded();
+        config.food_paths.enabled = false;
+        let a = board();
+        let mut b = board();
+        b.food.clear();
+        let summary = FieldSummary {
+            reachable: vec![Some(42)],
+            nearby: vec![3],
+            control: ControlSummary { owned: vec![42], claimed: 42 },
+            food: FoodPaths::default(),
+        };
+
+        let mut computed = 0;
+        let first = field_summary(&a, &[0], &config, || {
+            computed += 1;
+            summary.clone()
+        });
+        let second = field_summary(&b, &[0], &config, || {
+            computed += 1;
+            DistanceField::build(&b, &[0], None).summarize(&b, &config)
+        });
+        assert_eq!(computed, 1);
+        assert_eq!(first, second);
+
+        // Food path distances make the food part of the key
+        config.food_paths.enabled = true;
+        assert_ne!(field_hash(&a, &[0], &config), field_hash(&b, &[0], &config));
+    }
+
+    #[test]
+    fn test_active_sets_are_reused_per_hash_and_threshold() {
+        let mut config = Config::default_hardcoded();
+        let mut computed = 0;
+        let mut lookup = |config: &Config, hash: u64, threshold: i32| {
+            active_set(hash, threshold, 0, config, || {
+                computed += 1;
+                vec![0, 2]
+            })
+        };
+        assert_eq!(lookup(&config, 7, 3), vec![0, 2]);
+        assert_eq!(lookup(&config, 7, 3), vec![0, 2]);
+        lookup(&config, 7, 4);
+        config.idapos.cache_active_sets = false;
+        lookup(&config, 7, 3);
+        assert_eq!(computed, 3, "hit only for the same hash and threshold while enabled");
+    }
+}
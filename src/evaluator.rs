@@ -0,0 +1,609 @@
+// Leaf evaluation
+//
+// The search scores its leaves through an Evaluator: evaluate(board, ctx) -> ScoreTuple, one
+// score per snake. HeuristicEvaluator is the handcrafted evaluation (space, health, control,
+// attack and the positional terms, weighted by [scores]); other evaluators are attached to
+// the config by name (strategy.evaluator) and fall back to the heuristic when they cannot be
+// built, so experiments swap the evaluation without touching the search. The heuristic sums
+// the terms of components(), which also fill the DetailedScore breakdowns analysis reports.
+//
+// "nn" blends a learned value model into the heuristic: (1 - nn.blend) * heuristic + nn.blend
+// * value * nn.value_scale, per snake (blend 1 replaces the heuristic). A ValueModel sees a
+// position from one snake's side, listed first on the board as in the training records of
+// dataset.rs; the network backend lives in nn.rs behind the `nn` cargo feature. Values are
+// cached per search thread under the board hash and the snake's head. Alpha-beta nodes one
+// ply above the leaves (the frontier) hand all their children to a batching evaluator before
+// searching them, in batches of nn.batch_size; the leaves then find their values in the cache.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use log::warn;
+
+use crate::bot::{Bot, DetailedScore, ScoreTuple, TranspositionTable};
+use crate::config::Config;
+use crate::distance_field::{DistanceField, FieldSummary};
+use crate::eval_cache;
+use crate::endgame;
+use crate::game_mode::HazardModel;
+use crate::simple_profiler::{self, ComponentTimer, EvalComponent};
+use crate::snail;
+use crate::squad;
+use crate::trap;
+use crate::types::{Board, Coord};
+
+/// What an evaluation may depend on besides the board
+#[derive(Debug, Clone, Copy)]
+pub struct EvalContext<'a> {
+    pub our_snake_id: &'a str,
+    pub config: &'a Config,
+    /// Snakes to evaluate in detail (IDAPOS); None evaluates every snake fully
+    pub active_snakes: Option<&'a [usize]>,
+    /// Plies below the root (scores are discounted with depth)
+    pub depth_from_root: u8,
+}
+
+/// A leaf evaluation
+pub trait Evaluator: Send + Sync + fmt::Debug {
+    fn name(&self) -> &str;
+
+    /// Score of every snake on `board` (by board index)
+    fn evaluate(&self, board: &Board, ctx: &EvalContext) -> ScoreTuple;
+
+    /// True if the search should hand over the children of frontier nodes (see prefetch)
+    fn batches_leaves(&self) -> bool {
+        false
+    }
+
+    /// Prepares the evaluation of `children`, the next leaves to be evaluated
+    fn prefetch(&self, _children: &[Board], _config: &Config) {}
+}
+
+/// Names accepted by strategy.evaluator
+pub const NAMES: [&str; 2] = ["heuristic", "nn"];
+
+/// Attaches the evaluator named by strategy.evaluator to `config` (None: the heuristic)
+/// An evaluator that cannot be built is logged and leaves the heuristic in charge
+pub fn attach(config: &mut Config) {
+    if config.evaluator.is_some() {
+        return;
+    }
+    config.evaluator = match config.strategy.evaluator.as_str() {
+        "nn" => load_value_model(config).map(|model| Arc::new(NnEvaluator::new(model)) as Arc<dyn Evaluator>),
+        _ => None,
+    };
+}
+
+#[cfg(feature = "nn")]
+fn load_value_model(config: &Config) -> Option<Arc<dyn ValueModel>> {
+    match crate::nn::ValueNetwork::load(&config.nn.model_path) {
+        Ok(model) => Some(Arc::new(model)),
+        Err(e) => {
+            warn!("{}; falling back to the heuristic evaluation", e);
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "nn"))]
+fn load_value_model(_config: &Config) -> Option<Arc<dyn ValueModel>> {
+    warn!("strategy.evaluator is \"nn\" but this binary was built without the nn feature; using the heuristic evaluation");
+    None
+}
+
+/// The handcrafted evaluation
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeuristicEvaluator;
+
+impl Evaluator for HeuristicEvaluator {
+    fn name(&self) -> &str {
+        "heuristic"
+    }
+
+    fn evaluate(&self, board: &Board, ctx: &EvalContext) -> ScoreTuple {
+        let EvalContext { config, depth_from_root, .. } = *ctx;
+        let _prof = simple_profiler::ProfileGuard::new("eval");
+
+        let (field_sources, field) = leaf_field(board, ctx);
+        let mut scores: Vec<i32> = (0..board.snakes.len()).map(|idx| components(board, idx, &field, &field_sources, ctx).total).collect();
+
+        // Squads play for the team: MaxN compares squad utilities, not individual scores
+        if config.game_mode.squad_rules().is_some() {
+            squad::team_utilities(board, &mut scores);
+        }
+
+        for score in &mut scores {
+            *score = discounted(*score, depth_from_root, config);
+        }
+
+        ScoreTuple { scores, leaf: None }
+    }
+}
+
+/// V7.2: Temporal discounting - future scores less confident, weighted lower
+/// discount = (0.95 ^ depth): depth 0 = 1.0, depth 5 = 0.77, depth 10 = 0.60
+pub fn discounted(score: i32, depth_from_root: u8, config: &Config) -> i32 {
+    if depth_from_root == 0 {
+        return score;
+    }
+    let discount = config.scores.temporal_discount_factor.powi(depth_from_root as i32);
+    (score as f32 * discount) as i32
+}
+
+/// One distance/ownership field per evaluation for the active snakes (IDAPOS), with those
+/// snakes. Space, entrapment, territory control and attack all read from it
+fn leaf_field(board: &Board, ctx: &EvalContext) -> (Vec<usize>, FieldSummary) {
+    let EvalContext { config, active_snakes, .. } = *ctx;
+    let field_sources: Vec<usize> = match active_snakes {
+        Some(active) => active.to_vec(),
+        None => (0..board.snakes.len()).collect(),
+    };
+    let field = eval_cache::field_summary(board, &field_sources, config, || {
+        DistanceField::build(board, &field_sources, config.hazard_walls.as_deref()).summarize(board, config)
+    });
+    (field_sources, field)
+}
+
+/// Our breakdown of the heuristic evaluation of `board` under `ctx` (squad utilities aside),
+/// discounted: its total is our score in HeuristicEvaluator::evaluate. None if we are not
+/// on the board
+pub fn breakdown(board: &Board, ctx: &EvalContext) -> Option<DetailedScore> {
+    let our_idx = board.snake_index(ctx.our_snake_id)?;
+    let (field_sources, field) = leaf_field(board, ctx);
+    Some(components(board, our_idx, &field, &field_sources, ctx).discounted(ctx.depth_from_root, ctx.config))
+}
+
+/// The heuristic's terms for the snake at `idx`, undiscounted: HeuristicEvaluator scores a
+/// snake with their total, and Bot::detailed_score reports them, so analysis sees exactly
+/// what the search saw. `field` covers the snakes in `field_sources` (IDAPOS); snakes outside
+/// ctx.active_snakes skip the expensive terms. Dead snakes score only `survival`
+/// (score_survival_penalty for us, score_dead_snake for the others).
+pub fn components(board: &Board, idx: usize, field: &FieldSummary, field_sources: &[usize], ctx: &EvalContext) -> DetailedScore {
+    let EvalContext { our_snake_id, config, active_snakes, .. } = *ctx;
+    let snake = &board.snakes[idx];
+    if snake.health <= 0 {
+        let survival = if snake.id == our_snake_id { config.scores.score_survival_penalty } else { config.scores.score_dead_snake };
+        return DetailedScore { total: survival, survival, ..DetailedScore::default() };
+    }
+
+    // Check if this snake is active (needs full evaluation)
+    let is_active = active_snakes.is_none_or(|active| active.contains(&idx));
+    let active_list = active_snakes.unwrap_or(&[]);
+    let mut timer = ComponentTimer::start();
+
+    // Multi-component evaluation
+    let survival = 0; // Alive = 0 penalty
+    let health = timer.time(EvalComponent::Health, || Bot::compute_health_score(board, idx, active_list, Some(&field.food), config));
+
+    // Compute space score with entrapment detection
+    // Uses IDAPOS-filtered active snakes for adversarial entrapment detection
+    let space = if is_active {
+        timer.time(EvalComponent::Space, || Bot::compute_space_score(board, idx, field, active_list, config))
+    } else {
+        0
+    };
+
+    // Only compute expensive control and attack for active snakes
+    let control = if is_active {
+        timer.time(EvalComponent::Control, || {
+            Bot::compute_control_score_from_summary(&field.control, idx, config)
+                + Bot::compute_ally_control_score(board, &field.control, idx, config)
+        })
+    } else {
+        0  // Skip expensive territory control for non-active snakes
+    };
+
+    let length = snake.length * config.scores.weight_length;
+
+    let attack = if is_active {
+        timer.time(EvalComponent::Attack, || Bot::compute_attack_score(board, idx, config, &field.reachable))
+    } else {
+        0  // Skip expensive attack calculation for non-active snakes
+    };
+
+    // Trap setting: shorter opponents nearby cut off from enough room
+    let trap_setting = if is_active {
+        timer.time(EvalComponent::TrapSetting, || trap::trap_score(board, idx, &field.control, field_sources, config))
+    } else {
+        0
+    };
+
+    // Duel endgame: who runs out of moves first once no food matters
+    let endgame_parity = if is_active && config.endgame.enabled {
+        timer.time(EvalComponent::EndgameParity, || endgame::parity_score(board, idx, field, config))
+    } else {
+        0
+    };
+
+    // Check for head-to-head collision danger
+    let head_collision = if !snake.body.is_empty() {
+        timer.time(EvalComponent::HeadCollision, || Bot::check_head_collision_danger(board, idx, snake.body[0], config))
+    } else {
+        0
+    };
+
+    // Wall proximity penalty, center bias, and corner danger
+    let (wall_penalty, center_bias, corner_danger) = if !snake.body.is_empty() {
+        let head = snake.body[0];
+        timer.time(EvalComponent::Positional, || {
+            (
+                Bot::compute_wall_penalty(head, board.width, board.height as i32, snake.health, config),
+                Bot::compute_center_bias(head, board.width, board.height as i32, config),
+                Bot::compute_corner_danger(head, board.width, board.height as i32, snake.health, config),  // V10: Added health parameter
+            )
+        })
+    } else {
+        (0, 0, 0)
+    };
+
+    // Length advantage bonus
+    let length_advantage = timer.time(EvalComponent::LengthAdvantage, || Bot::compute_length_advantage(board, idx, config));
+
+    // Snail mode: standing in a trail (the fresher, the higher the stack)
+    let trail_penalty = match snake.body.first() {
+        Some(&head) if config.game_mode.strategy().hazards == HazardModel::Trails => {
+            timer.time(EvalComponent::TrailPenalty, || snail::trail_depth(&board.hazards, head) as i32 * config.scores.trail_reentry_penalty)
+        }
+        _ => 0,
+    };
+
+    // V8: Growth urgency - incentivize growth when shorter than opponents
+    // Uses IDAPOS-filtered active snakes to compare lengths efficiently
+    let growth_urgency = if is_active {
+        timer.time(EvalComponent::GrowthUrgency, || Bot::compute_growth_urgency(board, idx, active_list, config))
+    } else {
+        0  // Skip for non-active snakes
+    };
+
+    // V7: Tail-chasing detection (nuanced - only when opponents nearby)
+    // Uses IDAPOS-filtered active snakes to check for nearby opponents
+    let tail_chasing = if is_active {
+        timer.time(EvalComponent::TailChasing, || Bot::compute_tail_chasing_penalty(board, idx, active_list, config))
+    } else {
+        0  // Skip tail-chasing check for non-active snakes
+    };
+
+    // V7: Articulation point detection (narrow passage risk)
+    // Uses IDAPOS-filtered active snakes for efficient collision detection
+    let articulation = if is_active {
+        timer.time(EvalComponent::Articulation, || Bot::compute_articulation_point_penalty(board, idx, active_list, config))
+    } else {
+        0  // Skip expensive articulation check for non-active snakes
+    };
+
+    // Weighted combination
+    let total = survival
+        + (config.scores.score_survival_weight * survival as f32) as i32
+        + (config.scores.weight_space * space as f32) as i32
+        + (config.scores.weight_health * health as f32) as i32
+        + (config.scores.weight_control * control as f32) as i32
+        + (config.scores.weight_attack * attack as f32) as i32
+        + (config.scores.weight_trap_setting * trap_setting as f32) as i32
+        + length
+        + endgame_parity
+        + head_collision
+        + wall_penalty
+        + center_bias
+        + corner_danger
+        + length_advantage + growth_urgency
+        + tail_chasing
+        + articulation
+        + trail_penalty;
+
+    DetailedScore {
+        total,
+        survival,
+        health,
+        space,
+        control,
+        attack,
+        trap_setting,
+        length,
+        endgame_parity,
+        head_collision,
+        wall_penalty,
+        center_bias,
+        corner_danger,
+        length_advantage,
+        growth_urgency,
+        tail_chasing,
+        articulation,
+        trail_penalty,
+        temporal_discount: 0,
+    }
+}
+
+/// Lazy evaluation of snake `idx`: material, health and immediate head-to-head danger, with
+/// the weights and depth discount of the heuristic but none of its flood fills. Interior nodes
+/// use it for futility pruning, razoring and move ordering; leaves get the full evaluation.
+pub fn lazy_score(board: &Board, idx: usize, config: &Config, depth_from_root: u8) -> i32 {
+    let snake = match board.snakes.get(idx) {
+        Some(snake) if snake.health > 0 && !snake.body.is_empty() => snake,
+        Some(_) => return config.scores.score_survival_penalty,
+        None => return 0,
+    };
+    let material = snake.length * config.scores.weight_length + Bot::compute_length_advantage(board, idx, config);
+    let health = (config.scores.weight_health * Bot::compute_health_score(board, idx, &[], None, config) as f32) as i32;
+    let danger = Bot::check_head_collision_danger(board, idx, snake.body[0], config);
+    let score = material.saturating_add(health).saturating_add(danger);
+    if depth_from_root > 0 {
+        (score as f32 * config.scores.temporal_discount_factor.powi(depth_from_root as i32)) as i32
+    } else {
+        score
+    }
+}
+
+/// A learned position value
+pub trait ValueModel: Send + Sync + fmt::Debug {
+    /// Value of each position for the snake listed first on it, in [-1, 1] (1: certain win)
+    /// None for positions the model cannot read (e.g. a board larger than it was trained on)
+    fn values(&self, positions: &[Board]) -> Vec<Option<f32>>;
+}
+
+/// The heuristic blended with a value model ("nn")
+#[derive(Debug, Clone)]
+pub struct NnEvaluator {
+    model: Arc<dyn ValueModel>,
+}
+
+impl NnEvaluator {
+    pub fn new(model: Arc<dyn ValueModel>) -> Self {
+        NnEvaluator { model }
+    }
+}
+
+impl Evaluator for NnEvaluator {
+    fn name(&self) -> &str {
+        "nn"
+    }
+
+    /// The heuristic scores, blended with the model's values of the alive snakes (the value
+    /// is not discounted with depth: it already estimates the final outcome)
+    fn evaluate(&self, board: &Board, ctx: &EvalContext) -> ScoreTuple {
+        let mut tuple = HeuristicEvaluator.evaluate(board, ctx);
+        let values = snake_values(self.model.as_ref(), board, ctx.config);
+        blend(&mut tuple.scores, &values, ctx.config);
+        tuple
+    }
+
+    fn batches_leaves(&self) -> bool {
+        true
+    }
+
+    fn prefetch(&self, children: &[Board], config: &Config) {
+        let wanted: Vec<(&Board, u64, usize)> = children
+            .iter()
+            .flat_map(|board| {
+                let hash = TranspositionTable::hash_board(board);
+                (0..board.snakes.len()).map(move |idx| (board, hash, idx))
+            })
+            .collect();
+        fill(self.model.as_ref(), &wanted, config);
+    }
+}
+
+/// `board` with snake `idx` moved to the front (the side a model evaluates)
+pub fn perspective(board: &Board, idx: usize) -> Board {
+    let mut view = board.clone();
+    view.occupancy = None;
+    if idx < view.snakes.len() {
+        let snake = view.snakes.remove(idx);
+        view.snakes.insert(0, snake);
+    }
+    view
+}
+
+thread_local! {
+    /// (board hash, head of the evaluated snake) -> value (None: unreadable)
+    static VALUES: RefCell<HashMap<(u64, Coord), Option<f32>>> = RefCell::new(HashMap::new());
+}
+
+fn cache_key(board_hash: u64, board: &Board, idx: usize) -> Option<(u64, Coord)> {
+    let snake = board.snakes.get(idx).filter(|snake| snake.health > 0)?;
+    Some((board_hash, *snake.body.first()?))
+}
+
+/// Evaluates the uncached (board, snake) pairs of `wanted` in batches and caches the values
+fn fill(model: &dyn ValueModel, wanted: &[(&Board, u64, usize)], config: &Config) {
+    let missing: Vec<((u64, Coord), Board)> = VALUES.with(|cache| {
+        let cache = cache.borrow();
+        let mut seen = Vec::new();
+        wanted
+            .iter()
+            .filter_map(|&(board, hash, idx)| {
+                let key = cache_key(hash, board, idx)?;
+                if cache.contains_key(&key) || seen.contains(&key) {
+                    return None;
+                }
+                seen.push(key);
+                Some((key, perspective(board, idx)))
+            })
+            .collect()
+    });
+    for chunk in missing.chunks(config.nn.batch_size.max(1)) {
+        let positions: Vec<Board> = chunk.iter().map(|(_, board)| board.clone()).collect();
+        let values = model.values(&positions);
+        VALUES.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            if cache.len() + chunk.len() > config.eval_cache.max_entries {
+                cache.clear();
+            }
+            let capacity = cache.capacity();
+            for ((key, _), value) in chunk.iter().zip(values) {
+                cache.insert(*key, value);
+            }
+            simple_profiler::record_hash_growth(capacity, cache.capacity());
+        });
+    }
+}
+
+/// Model values of the alive snakes of `board`, by board index (None: dead or unreadable)
+fn snake_values(model: &dyn ValueModel, board: &Board, config: &Config) -> Vec<Option<f32>> {
+    let hash = TranspositionTable::hash_board(board);
+    let wanted: Vec<(&Board, u64, usize)> = (0..board.snakes.len()).map(|idx| (board, hash, idx)).collect();
+    fill(model, &wanted, config);
+    VALUES.with(|cache| {
+        let cache = cache.borrow();
+        (0..board.snakes.len())
+            .map(|idx| cache_key(hash, board, idx).and_then(|key| cache.get(&key).copied().flatten()))
+            .collect()
+    })
+}
+
+/// Blends model values into heuristic `scores` (alive snakes with a value only)
+fn blend(scores: &mut [i32], values: &[Option<f32>], config: &Config) {
+    let share = config.nn.blend;
+    for (score, value) in scores.iter_mut().zip(values) {
+        if let Some(value) = value {
+            let learned = value.clamp(-1.0, 1.0) * config.nn.value_scale;
+            *score = ((1.0 - share) * *score as f32 + share * learned) as i32;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board_builder::BoardBuilder;
+    use crate::bot::Bot;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Values every position by the length difference of its first snake; counts calls
+    #[derive(Debug, Default)]
+    struct LengthModel {
+        calls: AtomicUsize,
+        positions: AtomicUsize,
+    }
+
+    impl ValueModel for LengthModel {
+        fn values(&self, positions: &[Board]) -> Vec<Option<f32>> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            self.positions.fetch_add(positions.len(), Ordering::Relaxed);
+            positions
+                .iter()
+                .map(|board| {
+                    let longest_other = board.snakes.iter().skip(1).map(|s| s.length).max().unwrap_or(0);
+                    Some(((board.snakes[0].length - longest_other) as f32 / 4.0).clamp(-1.0, 1.0))
+                })
+                .collect()
+        }
+    }
+
+    fn duel(our_len: usize) -> Board {
+        let body: Vec<(i32, i32)> = (0..our_len as i32).map(|y| (1, y)).collect();
+        BoardBuilder::new(11, 11).snake("us", &body).snake("them", &[(8, 8), (8, 7), (8, 6)]).build()
+    }
+
+    #[test]
+    fn test_config_selects_the_evaluator() {
+        let mut config = Config::default_hardcoded();
+        attach(&mut config);
+        assert!(config.evaluator.is_none(), "\"heuristic\" needs nothing attached");
+        let board = duel(5);
+        let ctx = EvalContext { our_snake_id: "us", config: &config, active_snakes: None, depth_from_root: 0 };
+        assert_eq!(HeuristicEvaluator.evaluate(&board, &ctx).scores, Bot::evaluate_all(&board, "us", &config));
+
+        config.strategy.evaluator = "nn".to_string();
+        config.nn.model_path = "no/such/model.json".to_string();
+        attach(&mut config);
+        assert!(config.evaluator.is_none(), "an evaluator that cannot be built leaves the heuristic");
+    }
+
+    #[test]
+    fn test_breakdown_totals_match_the_leaf_scores() {
+        let config = Config::default_hardcoded();
+        let crowded = BoardBuilder::new(11, 11)
+            .snake("us", &[(5, 5), (5, 4), (5, 3)])
+            .snake("near", &[(3, 5), (3, 4), (3, 3), (3, 2)])
+            .snake("far", &[(9, 9), (9, 10)])
+            .food(&[(6, 6)])
+            .build();
+        let mut dead = duel(4);
+        dead.snakes[0].health = 0;
+        let active = [0, 1];
+        for board in [duel(5), crowded, dead] {
+            for (active_snakes, depth_from_root) in [(None, 0), (None, 4), (Some(&active[..]), 2)] {
+                let ctx = EvalContext { our_snake_id: "us", config: &config, active_snakes, depth_from_root };
+                let breakdown = breakdown(&board, &ctx).unwrap();
+                assert_eq!(breakdown.total, HeuristicEvaluator.evaluate(&board, &ctx).for_player(0));
+                let undiscounted = Bot::detailed_score(&board, 0, &config).total;
+                if active_snakes.is_none() {
+                    assert_eq!(breakdown.total, undiscounted + breakdown.temporal_discount);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_lazy_score_tracks_material_health_and_danger() {
+        let config = Config::default_hardcoded();
+        let board = duel(5);
+        let longer = lazy_score(&board, 0, &config, 0);
+        assert!(longer > lazy_score(&board, 1, &config, 0), "the longer snake scores higher");
+        assert!(lazy_score(&board, 0, &config, 3).abs() < longer.abs(), "discounted with depth like the full evaluation");
+
+        let mut fed = board.clone();
+        fed.food = vec![Coord { x: 5, y: 5 }];
+        let mut hungry = fed.clone();
+        hungry.snakes[0].health = 10;
+        assert!(lazy_score(&hungry, 0, &config, 0) < lazy_score(&fed, 0, &config, 0));
+
+        // Head next to a longer snake: losing head-to-head
+        let contested = BoardBuilder::new(11, 11)
+            .snake("us", &[(5, 5), (5, 4), (5, 3)])
+            .snake("them", &[(5, 6), (5, 7), (5, 8), (5, 9)])
+            .build();
+        let mut safe = contested.clone();
+        safe.snakes[1].body = vec![Coord { x: 9, y: 7 }, Coord { x: 9, y: 8 }, Coord { x: 9, y: 9 }, Coord { x: 9, y: 10 }];
+        assert!(lazy_score(&contested, 0, &config, 0) < lazy_score(&safe, 0, &config, 0));
+    }
+
+    #[test]
+    fn test_network_value_blends_into_the_leaf_score() {
+        let mut config = Config::default_hardcoded();
+        config.eval_cache.enabled = false;
+        let board = duel(5);
+        let handcrafted = Bot::evaluate_all(&board, "us", &config);
+
+        config.nn.blend = 1.0;
+        config.evaluator = Some(Arc::new(NnEvaluator::new(Arc::new(LengthModel::default()))));
+        let learned = Bot::evaluate_all(&board, "us", &config);
+        assert_eq!(learned, vec![1000, -1000], "blend 1 replaces the score by value * value_scale");
+
+        config.nn.blend = 0.5;
+        let mixed = Bot::evaluate_all(&board, "us", &config);
+        assert_eq!(mixed[0], ((handcrafted[0] as f32 + 1000.0) * 0.5) as i32);
+
+        attach(&mut config);
+        assert_eq!(config.evaluator.as_ref().map(|e| e.name()), Some("nn"), "attach keeps an attached evaluator");
+    }
+
+    #[test]
+    fn test_prefetch_batches_the_frontier() {
+        let mut config = Config::default_hardcoded();
+        config.nn.batch_size = 4;
+        let model = Arc::new(LengthModel::default());
+        let children: Vec<Board> = (3..6).map(duel).collect();
+        NnEvaluator::new(model.clone()).prefetch(&children, &config);
+        assert_eq!(model.positions.load(Ordering::Relaxed), 6, "two snakes on each of three children");
+        assert_eq!(model.calls.load(Ordering::Relaxed), 2, "six positions in batches of four");
+
+        let values = snake_values(model.as_ref(), &children[2], &config);
+        assert_eq!(values, vec![Some(0.5), Some(-0.5)]);
+        assert_eq!(model.calls.load(Ordering::Relaxed), 2, "leaves are served from the cache");
+
+        // A fixed-depth search reaches the network through frontier batches
+        use crate::bot::{HistoryTable, SearchLimits, SharedSearchState};
+        let model = Arc::new(LengthModel::default());
+        config.evaluator = Some(Arc::new(NnEvaluator::new(model.clone())));
+        let board = duel(4);
+        let you = board.snakes[0].clone();
+        let shared = Arc::new(SharedSearchState::new());
+        let limits = SearchLimits::default().with_fixed_depth(Some(3));
+        Bot::compute_best_move_internal(&board, &you, 5, shared, &config, &mut HistoryTable::new(11, 11), limits);
+        let (calls, positions) = (model.calls.load(Ordering::Relaxed), model.positions.load(Ordering::Relaxed));
+        assert!(calls > 0 && positions > calls, "{} positions in {} calls", positions, calls);
+    }
+}
@@ -0,0 +1,255 @@
+// Genetic weight evolution
+//
+// A population of genomes searches the [scores] fields listed in [tuning.parameters], using the
+// same normalized [0, 1] encoding as SPSA (tuning.rs). Every generation each individual plays
+// an arena match against the starting config (the same seeds for everyone, so individuals are
+// compared on the same games) and its match score is its fitness. The next generation keeps
+// the `elite` fittest unchanged and breeds the rest: tournament selection, uniform crossover
+// and Gaussian mutation. The randomness of a generation comes from the seed and the generation
+// number alone, so a run resumed from a checkpoint continues like an uninterrupted one (up to
+// the last digit the JSON checkpoint keeps of each gene).
+// Driven by src/bin/evolve.rs.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use crate::arena;
+use crate::config::{Config, EvolutionConfig};
+use crate::tuning::{self, Parameter};
+
+/// A genome and the match score it earned
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Individual {
+    pub genome: Vec<f64>,
+    pub fitness: f64,
+}
+
+/// State of a run between generations
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// Generation the population is about to play
+    pub generation: u32,
+    /// Names of the genes, in genome order
+    pub parameters: Vec<String>,
+    pub population: Vec<Vec<f64>>,
+    /// Fittest individual so far
+    pub best: Option<Individual>,
+}
+
+impl Checkpoint {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        serde_json::from_str(&text).map_err(|e| format!("Failed to parse {}: {}", path, e))
+    }
+
+    /// Writes through a temporary file, so an interrupted save keeps the previous checkpoint
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize checkpoint: {}", e))?;
+        let tmp = format!("{}.tmp", path);
+        fs::write(&tmp, json).map_err(|e| format!("Failed to write {}: {}", tmp, e))?;
+        fs::rename(&tmp, path).map_err(|e| format!("Failed to rename {} to {}: {}", tmp, path, e))
+    }
+}
+
+pub struct Evolution {
+    base: Config,
+    settings: EvolutionConfig,
+    parameters: Vec<Parameter>,
+    generation: u32,
+    population: Vec<Vec<f64>>,
+    best: Option<Individual>,
+}
+
+impl Evolution {
+    /// First generation: the base config's own values plus random genomes
+    ///
+    /// # Returns
+    /// * `Err` if [tuning.parameters] does not describe numeric [scores] fields
+    pub fn new(base: &Config) -> Result<Self, String> {
+        let (parameters, theta) = tuning::tuned_parameters(base)?;
+        let settings = base.evolution.clone();
+        let mut rng = StdRng::seed_from_u64(settings.seed);
+        let mut population = vec![theta];
+        while population.len() < settings.population_size {
+            population.push(parameters.iter().map(|_| rng.random::<f64>()).collect());
+        }
+        Ok(Evolution { base: base.clone(), settings, parameters, generation: 0, population, best: None })
+    }
+
+    /// Continues a run from `checkpoint`
+    ///
+    /// # Returns
+    /// * `Err` if the checkpoint was written for other parameters or another population size
+    pub fn resume(base: &Config, checkpoint: Checkpoint) -> Result<Self, String> {
+        let mut evolution = Evolution::new(base)?;
+        let names: Vec<String> = evolution.parameters.iter().map(|p| p.name.clone()).collect();
+        if checkpoint.parameters != names {
+            return Err(format!(
+                "Checkpoint evolves [{}] but [tuning.parameters] lists [{}]",
+                checkpoint.parameters.join(", "),
+                names.join(", ")
+            ));
+        }
+        if checkpoint.population.len() != evolution.settings.population_size {
+            return Err(format!(
+                "Checkpoint population has {} individuals, evolution.population_size is {}",
+                checkpoint.population.len(),
+                evolution.settings.population_size
+            ));
+        }
+        if checkpoint.population.iter().any(|genome| genome.len() != names.len()) {
+            return Err("Checkpoint genome length does not match [tuning.parameters]".to_string());
+        }
+        evolution.generation = checkpoint.generation;
+        evolution.population = checkpoint.population;
+        evolution.best = checkpoint.best;
+        Ok(evolution)
+    }
+
+    pub fn parameters(&self) -> &[Parameter] {
+        &self.parameters
+    }
+
+    /// Generation about to be evaluated (0-based)
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    pub fn best(&self) -> Option<&Individual> {
+        self.best.as_ref()
+    }
+
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            generation: self.generation,
+            parameters: self.parameters.iter().map(|p| p.name.clone()).collect(),
+            population: self.population.clone(),
+            best: self.best.clone(),
+        }
+    }
+
+    /// Match score of every individual against the base config
+    pub fn evaluate(&self) -> Result<Vec<f64>, String> {
+        let games = self.settings.games_per_individual;
+        let seed = self.settings.seed.wrapping_add(u64::from(self.generation) * u64::from(games));
+        self.population
+            .iter()
+            .map(|genome| {
+                let config = tuning::config_at(&self.base, &self.parameters, genome)?;
+                Ok(arena::play_match(&config, &self.base, &self.base.arena, games, seed)?.score())
+            })
+            .collect()
+    }
+
+    /// Records the fittest individual and breeds the next generation
+    /// `fitness` holds one score per individual, as returned by evaluate
+    pub fn advance(&mut self, fitness: &[f64]) {
+        let fittest = (0..self.population.len()).max_by(|&a, &b| fitness[a].total_cmp(&fitness[b]).then(b.cmp(&a)));
+        if let Some(idx) = fittest {
+            if self.best.as_ref().is_none_or(|best| fitness[idx] > best.fitness) {
+                self.best = Some(Individual { genome: self.population[idx].clone(), fitness: fitness[idx] });
+            }
+        }
+        let mut rng = StdRng::seed_from_u64(self.settings.seed.wrapping_add(u64::from(self.generation) + 1));
+        self.population = next_population(&self.population, fitness, &self.settings, &mut rng);
+        self.generation += 1;
+    }
+
+    /// Values of the fittest individual so far in [scores] units (the base values before any
+    /// generation was evaluated)
+    pub fn best_values(&self) -> Vec<(String, f64)> {
+        let genome = self.best.as_ref().map_or(&self.population[0], |best| &best.genome);
+        tuning::values_at(&self.parameters, genome)
+    }
+}
+
+/// Standard normal sample (Box-Muller)
+fn gaussian(rng: &mut StdRng) -> f64 {
+    let u1 = 1.0 - rng.random::<f64>();
+    let u2 = rng.random::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+/// Index of the fittest of `size` individuals drawn at random
+fn tournament(fitness: &[f64], size: usize, rng: &mut StdRng) -> usize {
+    (0..size.max(1))
+        .map(|_| rng.random_range(0..fitness.len()))
+        .max_by(|&a, &b| fitness[a].total_cmp(&fitness[b]))
+        .unwrap_or(0)
+}
+
+/// Next generation of `population`: the elites, then children of tournament-selected parents
+fn next_population(population: &[Vec<f64>], fitness: &[f64], settings: &EvolutionConfig, rng: &mut StdRng) -> Vec<Vec<f64>> {
+    let mut ranked: Vec<usize> = (0..population.len()).collect();
+    ranked.sort_by(|&a, &b| fitness[b].total_cmp(&fitness[a]));
+    let mut next: Vec<Vec<f64>> = ranked.iter().take(settings.elite).map(|&idx| population[idx].clone()).collect();
+
+    while next.len() < settings.population_size {
+        let mother = &population[tournament(fitness, settings.tournament_size, rng)];
+        let mut child = mother.clone();
+        if rng.random_bool(settings.crossover_rate) {
+            let father = &population[tournament(fitness, settings.tournament_size, rng)];
+            for (gene, &other) in child.iter_mut().zip(father) {
+                if rng.random_bool(0.5) {
+                    *gene = other;
+                }
+            }
+        }
+        for gene in child.iter_mut() {
+            if rng.random_bool(settings.mutation_rate) {
+                *gene = (*gene + settings.mutation_sigma * gaussian(rng)).clamp(0.0, 1.0);
+            }
+        }
+        next.push(child);
+    }
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_population_keeps_elites_and_bounds() {
+        let settings = EvolutionConfig { population_size: 6, elite: 2, mutation_rate: 1.0, mutation_sigma: 0.5, ..Config::default_hardcoded().evolution };
+        let population: Vec<Vec<f64>> = (0..6).map(|i| vec![i as f64 / 5.0, 1.0 - i as f64 / 5.0]).collect();
+        let fitness = [0.1, 0.9, 0.3, 0.8, 0.0, 0.5];
+        let next = next_population(&population, &fitness, &settings, &mut StdRng::seed_from_u64(7));
+
+        assert_eq!(next.len(), 6);
+        assert_eq!(&next[..2], &[population[1].clone(), population[3].clone()]);
+        assert!(next.iter().flatten().all(|gene| (0.0..=1.0).contains(gene)));
+        let again = next_population(&population, &fitness, &settings, &mut StdRng::seed_from_u64(7));
+        assert_eq!(next, again, "the same seed breeds the same generation");
+    }
+
+    #[test]
+    fn test_checkpoint_round_trip_and_resume_checks() {
+        let config = Config::default_hardcoded();
+        let mut evolution = Evolution::new(&config).unwrap();
+        assert_eq!(evolution.checkpoint().population[0], tuning::tuned_parameters(&config).unwrap().1);
+        let fitness: Vec<f64> = (0..config.evolution.population_size).map(|i| i as f64 / 100.0).collect();
+        evolution.advance(&fitness);
+
+        let path = std::env::temp_dir().join(format!("evolution_checkpoint_{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+        evolution.checkpoint().save(path).unwrap();
+        let loaded = Checkpoint::load(path).unwrap();
+        let _ = fs::remove_file(path);
+        let saved = evolution.checkpoint();
+        assert_eq!((loaded.generation, &loaded.parameters), (saved.generation, &saved.parameters));
+        let genes = |c: &Checkpoint| c.population.iter().flatten().copied().collect::<Vec<f64>>();
+        assert!(genes(&loaded).iter().zip(genes(&saved)).all(|(a, b)| (a - b).abs() < 1e-12));
+        assert_eq!(loaded.best.as_ref().map(|best| best.fitness), fitness.last().copied());
+
+        let resumed = Evolution::resume(&config, loaded.clone()).unwrap();
+        assert_eq!(resumed.generation(), 1);
+        assert_eq!(resumed.best().map(|best| best.genome.len()), Some(evolution.parameters().len()));
+
+        let mut other = loaded;
+        other.parameters[0] = "not_a_weight".to_string();
+        assert!(Evolution::resume(&config, other).is_err());
+    }
+}
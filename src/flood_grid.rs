@@ -0,0 +1,207 @@
+// Flat, reusable grid for flood fills
+//
+// Flood fills run thousands of times per search. Instead of allocating a HashMap of
+// obstacles and a HashSet of visited cells per call, every thread keeps one FloodGrid.
+// Cells are "set" only if their stamp equals the current generation, so starting a new
+// fill is a single increment instead of clearing the arrays.
+
+use std::cell::RefCell;
+
+use crate::types::Coord;
+
+/// Reusable flood-fill buffers for one board size
+pub struct FloodGrid {
+    width: i32,
+    height: i32,
+    generation: u16,
+    /// Stamp == generation: cell reached by the current fill
+    reached: Vec<u16>,
+    /// Turns since the fill started at which the cell was reached (valid if reached)
+    distance: Vec<u16>,
+    /// Stamp == generation: cell holds an obstacle
+    obstacle: Vec<u16>,
+    /// Turns until the obstacle clears (valid if obstacle)
+    blocked_for: Vec<u16>,
+    /// Stamp == generation: secondary marks (e.g. articulation point check)
+    marked: Vec<u16>,
+    /// BFS queue, reused between fills
+    queue: Vec<(Coord, u16)>,
+}
+
+thread_local! {
+    static FLOOD_GRID: RefCell<FloodGrid> = RefCell::new(FloodGrid::new(0, 0));
+}
+
+/// Runs `f` with this thread's flood grid, reset for a `width` x `height` board
+pub fn with_flood_grid<R>(width: i32, height: i32, f: impl FnOnce(&mut FloodGrid) -> R) -> R {
+    FLOOD_GRID.with(|grid| {
+        let mut grid = grid.borrow_mut();
+        grid.begin(width, height);
+        f(&mut grid)
+    })
+}
+
+impl FloodGrid {
+    pub fn new(width: i32, height: i32) -> Self {
+        let cells = (width.max(0) * height.max(0)) as usize;
+        FloodGrid {
+            width,
+            height,
+            generation: 0,
+            reached: vec![0; cells],
+            distance: vec![0; cells],
+            obstacle: vec![0; cells],
+            blocked_for: vec![0; cells],
+            marked: vec![0; cells],
+            queue: Vec::with_capacity(cells),
+        }
+    }
+
+    /// Starts a new fill: resizes for the board and invalidates all previous marks
+    pub fn begin(&mut self, width: i32, height: i32) {
+        if width != self.width || height != self.height {
+            *self = FloodGrid::new(width, height);
+        }
+
+        self.generation = self.generation.wrapping_add(1);
+        if self.generation == 0 {
+            // Stamps wrapped around: clear once so stale stamps cannot match
+            self.reached.iter_mut().for_each(|s| *s = 0);
+            self.obstacle.iter_mut().for_each(|s| *s = 0);
+            self.marked.iter_mut().for_each(|s| *s = 0);
+            self.generation = 1;
+        }
+        self.queue.clear();
+    }
+
+    #[inline]
+    fn index(&self, c: Coord) -> Option<usize> {
+        if c.x < 0 || c.y < 0 || c.x >= self.width || c.y >= self.height {
+            None
+        } else {
+            Some((c.y * self.width + c.x) as usize)
+        }
+    }
+
+    #[inline]
+    pub fn in_bounds(&self, c: Coord) -> bool {
+        self.index(c).is_some()
+    }
+
+    /// Marks an obstacle that clears after `turns` turns (later calls overwrite earlier ones)
+    #[inline]
+    pub fn block(&mut self, c: Coord, turns: u16) {
+        if let Some(i) = self.index(c) {
+            self.obstacle[i] = self.generation;
+            self.blocked_for[i] = turns;
+        }
+    }
+
+    /// True if the cell is out of bounds or still occupied `turns` turns into the fill
+    #[inline]
+    pub fn blocked_at(&self, c: Coord, turns: u16) -> bool {
+        match self.index(c) {
+            Some(i) => self.obstacle[i] == self.generation && self.blocked_for[i] > turns,
+            None => true,
+        }
+    }
+
+    /// Marks a cell reached at `distance`; returns false if it was already reached
+    #[inline]
+    pub fn reach(&mut self, c: Coord, distance: u16) -> bool {
+        match self.index(c) {
+            Some(i) if self.reached[i] != self.generation => {
+                self.reached[i] = self.generation;
+                self.distance[i] = distance;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    #[inline]
+    pub fn is_reached(&self, c: Coord) -> bool {
+        self.index(c).is_some_and(|i| self.reached[i] == self.generation)
+    }
+
+    /// Distance at which the cell was reached in the current fill
+    #[inline]
+    pub fn distance(&self, c: Coord) -> Option<u16> {
+        self.index(c)
+            .filter(|&i| self.reached[i] == self.generation)
+            .map(|i| self.distance[i])
+    }
+
+    /// Sets a secondary mark; returns false if the cell was already marked
+    #[inline]
+    pub fn mark(&mut self, c: Coord) -> bool {
+        match self.index(c) {
+            Some(i) if self.marked[i] != self.generation => {
+                self.marked[i] = self.generation;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    #[inline]
+    pub fn is_marked(&self, c: Coord) -> bool {
+        self.index(c).is_some_and(|i| self.marked[i] == self.generation)
+    }
+
+    /// Takes the reusable BFS queue (hand it back with `restore_queue` to keep its capacity)
+    pub fn take_queue(&mut self) -> Vec<(Coord, u16)> {
+        let mut queue = std::mem::take(&mut self.queue);
+        queue.clear();
+        queue
+    }
+
+    pub fn restore_queue(&mut self, queue: Vec<(Coord, u16)>) {
+        self.queue = queue;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_generation_forgets_previous_fill() {
+        let mut grid = FloodGrid::new(5, 5);
+        let c = Coord { x: 2, y: 3 };
+
+        grid.begin(5, 5);
+        grid.block(c, 3);
+        assert!(grid.reach(c, 1));
+        assert!(!grid.reach(c, 2), "second reach of the same cell must be rejected");
+
+        grid.begin(5, 5);
+        assert!(!grid.is_reached(c));
+        assert!(!grid.blocked_at(c, 0));
+    }
+
+    #[test]
+    fn test_obstacles_clear_after_their_turns() {
+        let mut grid = FloodGrid::new(3, 3);
+        grid.begin(3, 3);
+        grid.block(Coord { x: 1, y: 1 }, 2);
+
+        assert!(grid.blocked_at(Coord { x: 1, y: 1 }, 1));
+        assert!(!grid.blocked_at(Coord { x: 1, y: 1 }, 2));
+        assert!(grid.blocked_at(Coord { x: -1, y: 0 }, 0), "out of bounds is always blocked");
+    }
+
+    #[test]
+    fn test_generation_wraparound_clears_stale_stamps() {
+        let mut grid = FloodGrid::new(2, 2);
+        let c = Coord { x: 0, y: 0 };
+        grid.begin(2, 2);
+        grid.reach(c, 0);
+
+        // Cycle through every generation back to the one that reached `c`
+        for _ in 0..u16::MAX {
+            grid.begin(2, 2);
+        }
+        assert!(!grid.is_reached(c));
+    }
+}
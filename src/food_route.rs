@@ -0,0 +1,122 @@
+// Starvation-proof food routing
+//
+// The health score only pulls towards the nearest food. Below food_routing.health_threshold
+// that is not enough: the nearest food may be raced by a longer snake or sit in a pocket we
+// cannot leave after eating. The planner walks our time-aware BFS (distance_field.rs) back
+// from each food, nearest first, and keeps the first route where
+// - we arrive before starving,
+// - no cell on the way is reached as early by an opponent at least as long as we are (equal
+//   heads die together), and
+// - after eating at the food, the space left from it holds our longer body.
+// The search then gets first_step_bonus on the route's first move as a root bias.
+
+use crate::bot::Bot;
+use crate::config::Config;
+use crate::distance_field::DistanceField;
+use crate::types::{Board, Coord, Direction};
+
+/// A safe route to a food: the cells from our first step to the food
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoodRoute {
+    pub food: Coord,
+    pub path: Vec<Coord>,
+}
+
+impl FoodRoute {
+    /// Direction of the first step from `head`
+    pub fn first_move(&self, head: Coord) -> Option<Direction> {
+        Direction::from_delta(self.path[0] - head)
+    }
+}
+
+/// Safe route to the nearest food for snake `idx`, None if no food has one
+pub fn plan(board: &Board, idx: usize, config: &Config) -> Option<FoodRoute> {
+    let snake = board.snakes.get(idx).filter(|s| s.health > 0 && !s.body.is_empty())?;
+    let sources: Vec<usize> = (0..board.snakes.len()).filter(|&i| board.snakes[i].health > 0).collect();
+    let field = DistanceField::build(board, &sources, config.hazard_walls.as_deref());
+    let rivals: Vec<usize> = sources.iter().copied().filter(|&i| i != idx && board.snakes[i].length >= snake.length).collect();
+
+    let mut foods: Vec<(u16, Coord)> = board.food.iter().filter_map(|&food| Some((field.distance(idx, food)?, food))).collect();
+    foods.sort_by_key(|&(dist, food)| (dist, food.x, food.y));
+    foods
+        .into_iter()
+        .filter(|&(dist, _)| dist > 0 && (dist as i32) < snake.health)
+        .find_map(|(_, food)| {
+            let path = path_to(&field, idx, food)?;
+            let contested = path.iter().enumerate().any(|(step, &cell)| {
+                rivals.iter().any(|&rival| field.distance(rival, cell).is_some_and(|d| d as usize <= step + 1))
+            });
+            (!contested && escapes_after_eating(board, idx, &path, config)).then_some(FoodRoute { food, path })
+        })
+}
+
+/// Cells from the first step to `target` along snake `idx`'s BFS layers
+fn path_to(field: &DistanceField, idx: usize, target: Coord) -> Option<Vec<Coord>> {
+    let mut path = vec![target];
+    let mut dist = field.distance(idx, target)?;
+    while dist > 1 {
+        let prev = path[path.len() - 1].neighbors().iter().copied().find(|&c| field.distance(idx, c) == Some(dist - 1))?;
+        path.push(prev);
+        dist -= 1;
+    }
+    path.reverse();
+    Some(path)
+}
+
+/// Whether the space reachable from the food, once we have walked `path` and eaten (other
+/// snakes left in place), holds our body
+fn escapes_after_eating(board: &Board, idx: usize, path: &[Coord], config: &Config) -> bool {
+    let mut child = board.clone();
+    let mut head = board.snakes[idx].body[0];
+    for &cell in path {
+        let Some(dir) = Direction::from_delta(cell - head) else { return false };
+        Bot::apply_move(&mut child, idx, dir, config);
+        head = cell;
+    }
+    let length = child.snakes[idx].length.max(0) as usize;
+    child.snakes[idx].health > 0 && Bot::flood_fill_bfs(&child, head, idx, Some(length), config.hazard_walls.as_deref()) >= length
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board_builder::BoardBuilder;
+
+    #[test]
+    fn test_route_skips_food_raced_by_a_longer_snake() {
+        let config = Config::default_hardcoded();
+        // Food at (5, 5) is 3 moves away for us but 2 for the longer opponent; (9, 2) is safe
+        let board = BoardBuilder::new(11, 11)
+            .snake("us", &[(5, 2), (4, 2), (3, 2)])
+            .snake("them", &[(5, 7), (5, 8), (5, 9), (5, 10)])
+            .health("us", 20)
+            .food(&[(5, 5), (9, 2)])
+            .build();
+        let route = plan(&board, 0, &config).expect("a safe food");
+        assert_eq!(route.food, Coord { x: 9, y: 2 });
+        assert_eq!(route.path, (6..=9).map(|x| Coord { x, y: 2 }).collect::<Vec<_>>());
+        assert_eq!(route.first_move(Coord { x: 5, y: 2 }), Some(Direction::Right));
+
+        // A shorter opponent does not contest the food
+        let mut short = board.clone();
+        short.snakes[1].body.truncate(2);
+        short.snakes[1].length = 2;
+        assert_eq!(plan(&short, 0, &config).map(|r| r.food), Some(Coord { x: 5, y: 5 }));
+    }
+
+    #[test]
+    fn test_no_route_into_a_dead_end_or_beyond_our_health() {
+        let config = Config::default_hardcoded();
+        // The only food is at the end of a corridor along the bottom wall, between the shorter
+        // opponent and our own body: after eating there is nowhere to go
+        let board = BoardBuilder::new(5, 5)
+            .snake("us", &[(2, 0), (3, 0), (4, 0), (4, 1), (4, 2), (4, 3)])
+            .snake("them", &[(0, 1), (1, 1), (2, 1), (3, 1)])
+            .food(&[(0, 0)])
+            .build();
+        assert_eq!(plan(&board, 0, &config), None);
+
+        let open = BoardBuilder::new(11, 11).snake("us", &[(0, 0), (0, 1), (0, 2)]).health("us", 5).food(&[(10, 10)]).build();
+        assert_eq!(plan(&open, 0, &config), None, "20 moves away with 5 health");
+    }
+}
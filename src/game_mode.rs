@@ -0,0 +1,182 @@
+// Game mode detection and per-mode strategy
+//
+// Strategy used to be chosen from the number of alive snakes alone, and every other ruleset
+// feature was detected on its own, per move. GameMode is parsed once per game at /start from
+// game.ruleset (name, settings) and game.map, kept in the game session, and carried to the
+// search in Config::game_mode. Everything that depends on the rules dispatches on its
+// ModeStrategy: the search engine (paired alpha-beta duels vs MaxN), the evaluation profile,
+// how food is simulated and how hazards are treated.
+
+use crate::config::Config;
+use crate::snail;
+use crate::squad::SquadRules;
+use crate::types::Game;
+
+/// Map whose hazards are walls (any map with lethal hazard damage is treated the same way)
+pub const MAZE_MAP: &str = "arcade_maze";
+
+/// Rules family of a game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GameMode {
+    /// Standard rules (also the fallback for unknown rulesets)
+    #[default]
+    Standard,
+    Solo,
+    /// Standard rules with a shrinking ring of survivable hazards
+    Royale,
+    /// Snakes grow every turn and never starve; no food
+    Constrictor,
+    /// Board edges wrap around (the search still plays the board as bounded)
+    Wrapped,
+    /// Teams of snakes (see squad.rs)
+    Squad(SquadRules),
+    /// Hazards are lethal walls (arcade_maze, see hazard_walls.rs)
+    Maze,
+    /// Snakes leave hazard trails (snail_mode, see snail.rs)
+    Snail,
+}
+
+/// Search engine family
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchEngine {
+    /// Paired alpha-beta when exactly two snakes are alive, MaxN otherwise
+    DuelOrMaxN,
+    /// Always MaxN (allies are not opponents, so two alive snakes are not a duel)
+    MaxN,
+}
+
+/// How the search simulates eating
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FoodModel {
+    /// Food restores health and grows the snake; health drops every turn
+    Standard,
+    /// Every snake grows every turn and stays at full health
+    Constrictor,
+}
+
+/// How the search treats hazards
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HazardModel {
+    /// Not modelled (survivable damage)
+    Ignored,
+    /// Impassable cells
+    Walls,
+    /// Decaying trails laid behind every tail
+    Trails,
+}
+
+/// What the bot does differently in a game mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModeStrategy {
+    pub engine: SearchEngine,
+    /// Profile played when the request names none (used only if configured)
+    pub profile: Option<&'static str>,
+    pub food: FoodModel,
+    pub hazards: HazardModel,
+}
+
+impl GameMode {
+    /// Mode of a game from its ruleset name and settings and its map
+    ///
+    /// Lethal hazard damage (game_rules.hazard_wall_damage or more) makes any map a maze.
+    pub fn from_game(game: &Game, config: &Config) -> Self {
+        if let Some(rules) = SquadRules::from_ruleset(&game.ruleset) {
+            return GameMode::Squad(rules);
+        }
+        if game.map == snail::SNAIL_MAP {
+            return GameMode::Snail;
+        }
+        let hazard_damage = game.ruleset.settings.hazard_damage_per_turn.unwrap_or(config.game_rules.hazard_damage_per_turn);
+        if game.map == MAZE_MAP || hazard_damage >= config.game_rules.hazard_wall_damage {
+            return GameMode::Maze;
+        }
+        match game.ruleset.name.as_str() {
+            "solo" => GameMode::Solo,
+            "royale" => GameMode::Royale,
+            "constrictor" => GameMode::Constrictor,
+            "wrapped" => GameMode::Wrapped,
+            _ => GameMode::Standard,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::Standard => "standard",
+            GameMode::Solo => "solo",
+            GameMode::Royale => "royale",
+            GameMode::Constrictor => "constrictor",
+            GameMode::Wrapped => "wrapped",
+            GameMode::Squad(_) => "squad",
+            GameMode::Maze => "maze",
+            GameMode::Snail => "snail",
+        }
+    }
+
+    /// Squad settings (None outside squad games)
+    pub fn squad_rules(&self) -> Option<SquadRules> {
+        match self {
+            GameMode::Squad(rules) => Some(*rules),
+            _ => None,
+        }
+    }
+
+    pub fn strategy(&self) -> ModeStrategy {
+        let standard = ModeStrategy {
+            engine: SearchEngine::DuelOrMaxN,
+            profile: None,
+            food: FoodModel::Standard,
+            hazards: HazardModel::Ignored,
+        };
+        match self {
+            GameMode::Squad(_) => ModeStrategy { engine: SearchEngine::MaxN, ..standard },
+            GameMode::Constrictor => ModeStrategy { food: FoodModel::Constrictor, ..standard },
+            GameMode::Maze => ModeStrategy { hazards: HazardModel::Walls, ..standard },
+            GameMode::Snail => ModeStrategy {
+                profile: Some(snail::SNAIL_PROFILE),
+                hazards: HazardModel::Trails,
+                ..standard
+            },
+            GameMode::Standard | GameMode::Solo | GameMode::Royale | GameMode::Wrapped => standard,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn game(ruleset: Value, map: &str) -> Game {
+        Game {
+            id: "g".to_string(),
+            ruleset: serde_json::from_value(ruleset).unwrap(),
+            timeout: 500,
+            map: map.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_mode_comes_from_ruleset_and_map() {
+        let config = Config::default_hardcoded();
+        let mode = |ruleset: Value, map: &str| GameMode::from_game(&game(ruleset, map), &config);
+
+        assert_eq!(mode(json!({ "name": "standard", "version": "v1.2.3" }), "standard"), GameMode::Standard);
+        assert_eq!(mode(json!({ "name": "constrictor" }), ""), GameMode::Constrictor);
+        assert_eq!(mode(json!({ "name": "something_new" }), ""), GameMode::Standard);
+        assert_eq!(mode(json!({ "name": "squad" }), ""), GameMode::Squad(SquadRules::default()));
+        assert_eq!(mode(json!({ "name": "wrapped" }), "snail_mode"), GameMode::Snail);
+        // Lethal hazards make a maze whatever the map is called; royale's 14 damage does not
+        assert_eq!(mode(json!({ "name": "wrapped", "settings": { "hazardDamagePerTurn": 100 } }), "custom"), GameMode::Maze);
+        assert_eq!(mode(json!({ "name": "royale", "settings": { "hazardDamagePerTurn": 14 } }), ""), GameMode::Royale);
+    }
+
+    #[test]
+    fn test_strategy_dispatches_per_mode() {
+        assert_eq!(GameMode::Standard.strategy().engine, SearchEngine::DuelOrMaxN);
+        assert_eq!(GameMode::Squad(SquadRules::default()).strategy().engine, SearchEngine::MaxN);
+        assert_eq!(GameMode::Constrictor.strategy().food, FoodModel::Constrictor);
+        assert_eq!(GameMode::Maze.strategy().hazards, HazardModel::Walls);
+        let snail = GameMode::Snail.strategy();
+        assert_eq!((snail.hazards, snail.profile), (HazardModel::Trails, Some("snail")));
+    }
+}
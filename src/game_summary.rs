@@ -0,0 +1,244 @@
+// One summary line per finished game
+//
+// The debug log answers "why did we play that", but win rates and trends over weeks of games
+// only need one line per game. GameStats accumulates in the game's session on every /move;
+// at /end it becomes a GameSummary (outcome, turns, average depth and nodes, the smallest
+// margin to the move timeout, food eaten, kills and the cause of death) appended as a JSON
+// line to debug.summary_file_path, whether or not debug logging is enabled.
+//
+// Kills and food are read off consecutive boards: an opponent that is gone on the next board
+// counts as our kill when most of its fatal moves (not counting the reverse into its neck),
+// played with rules::resolve_turn, end on our body or in a head-to-head against us.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+use crate::analysis;
+use crate::config::Config;
+use crate::game_mode::GameMode;
+use crate::rules::{self, EliminationCause, RulesSettings};
+use crate::types::{Board, Direction};
+
+/// How the game ended for us
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    /// Last snake standing
+    Win,
+    Loss,
+    /// Nobody survived, or the game stopped with several snakes alive
+    Draw,
+}
+
+/// The search behind one /move answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveSearch {
+    /// Deepest completed iteration
+    pub depth: u8,
+    pub nodes: u64,
+    /// Move timeout minus handler time
+    pub margin_ms: i64,
+}
+
+/// Per-game counters, updated on every /move
+#[derive(Debug, Clone, Default)]
+pub struct GameStats {
+    pub moves: u32,
+    depth_sum: u64,
+    nodes_sum: u64,
+    /// Smallest move timeout minus handler time seen (None before the first move)
+    pub min_time_margin_ms: Option<i64>,
+    pub food_eaten: u32,
+    /// Ids of the opponents we eliminated
+    pub kills: Vec<String>,
+    /// Turn, board and our answer of the last /move; the next turn's board shows what came of it
+    last: Option<(i32, Board, Direction)>,
+}
+
+/// The line written at /end
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameSummary {
+    pub game_id: String,
+    pub timestamp: String,
+    pub mode: String,
+    pub outcome: Outcome,
+    /// Turn of the final board
+    pub turns: i32,
+    /// /move requests answered
+    pub moves: u32,
+    pub avg_depth: f64,
+    pub avg_nodes: f64,
+    pub min_time_margin_ms: Option<i64>,
+    pub food_eaten: u32,
+    pub kills: Vec<String>,
+    /// analysis::DeathCause of our last move, None unless we lost
+    pub death_cause: Option<String>,
+}
+
+impl GameStats {
+    /// Records the move answered for `board` on `turn` (`you` is our snake's id)
+    pub fn record_move(&mut self, turn: i32, board: &Board, you: &str, chosen: Direction, search: MoveSearch, config: &Config) {
+        self.observe(turn, board, you, config);
+        self.moves += 1;
+        self.depth_sum += search.depth as u64;
+        self.nodes_sum += search.nodes;
+        self.min_time_margin_ms = Some(self.min_time_margin_ms.map_or(search.margin_ms, |min| min.min(search.margin_ms)));
+        self.last = Some((turn, board.clone(), chosen));
+    }
+
+    /// Turn, board and our answer of the last recorded /move
+    pub fn last_move(&self) -> Option<(i32, &Board, Direction)> {
+        self.last.as_ref().map(|(turn, board, mv)| (*turn, board, *mv))
+    }
+
+    /// Credits food and kills of the turn that led from the last recorded board to `board`
+    fn observe(&mut self, turn: i32, board: &Board, you: &str, config: &Config) {
+        let Some((prev_turn, prev, our_move)) = &self.last else { return };
+        if turn == prev_turn + 1 {
+            if let (Some(before), Some(after)) = (prev.our_snake(you), board.our_snake(you)) {
+                self.food_eaten += (after.length - before.length).max(0) as u32;
+            }
+            let kills = kills(prev, board, you, *our_move, &RulesSettings::from_config(config));
+            self.kills.extend(kills);
+        }
+    }
+
+    /// Summary of the game that ended on `board` at `turn`
+    pub fn finish(mut self, game_id: &str, mode: GameMode, turn: i32, board: &Board, you: &str, config: &Config) -> GameSummary {
+        self.observe(turn, board, you, config);
+        let alive = board.our_snake(you).is_some();
+        let opponents = board.snakes.iter().filter(|s| s.id != you).count();
+        let outcome = match (alive, opponents) {
+            (true, 0) => Outcome::Win,
+            (false, n) if n > 0 => Outcome::Loss,
+            _ => Outcome::Draw,
+        };
+        let death_cause = match (&self.last, outcome) {
+            (Some((_, prev, our_move)), Outcome::Loss) => us_first(prev, you)
+                .and_then(|prev| analysis::analyze_death_observed(&prev, board, turn, *our_move, config))
+                .map(|death| death.cause.as_str().to_string()),
+            _ => None,
+        };
+        let average = |sum: u64| if self.moves == 0 { 0.0 } else { sum as f64 / self.moves as f64 };
+
+        GameSummary {
+            game_id: game_id.to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            mode: mode.name().to_string(),
+            outcome,
+            turns: turn,
+            moves: self.moves,
+            avg_depth: average(self.depth_sum),
+            avg_nodes: average(self.nodes_sum),
+            min_time_margin_ms: self.min_time_margin_ms,
+            food_eaten: self.food_eaten,
+            kills: self.kills,
+            death_cause,
+        }
+    }
+}
+
+impl GameSummary {
+    /// Appends the summary as one JSON line to `path`
+    pub fn append_to(&self, path: &Path) -> Result<(), String> {
+        let line = serde_json::to_string(self).map_err(|e| format!("Failed to serialize game summary: {}", e))?;
+        append_line(path, &line)
+    }
+}
+
+/// Appends `line` and a newline to `path`, creating the file
+pub(crate) fn append_line(path: &Path, line: &str) -> Result<(), String> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    writeln!(file, "{}", line).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// `board` with `you` moved to the front, as analysis expects
+pub(crate) fn us_first(board: &Board, you: &str) -> Option<Board> {
+    let idx = board.snake_index(you)?;
+    let mut board = board.clone();
+    let us = board.snakes.remove(idx);
+    board.snakes.insert(0, us);
+    board.occupancy = None;
+    Some(board)
+}
+
+/// Opponents on `prev` that are gone on `next` because of us
+fn kills(prev: &Board, next: &Board, you: &str, our_move: Direction, settings: &RulesSettings) -> Vec<String> {
+    // Survivors' moves from their head displacement; the eliminated continue straight
+    let moves: HashMap<String, Direction> = prev
+        .snakes
+        .iter()
+        .map(|snake| {
+            let mv = if snake.id == you {
+                our_move
+            } else {
+                next.our_snake(&snake.id)
+                    .and_then(|after| rules::direction_between(snake.head, after.head))
+                    .unwrap_or_else(|| rules::default_move(&snake.body))
+            };
+            (snake.id.clone(), mv)
+        })
+        .collect();
+
+    prev.snakes
+        .iter()
+        .filter(|snake| snake.id != you && next.our_snake(&snake.id).is_none())
+        .filter(|victim| {
+            let (mut fatal, mut ours) = (0, 0);
+            // Backing into its own neck is no move a snake plays
+            let neck = victim.body.get(1).filter(|&&neck| neck != victim.head);
+            for dir in Direction::ALL.iter().copied().filter(|dir| neck != Some(&dir.apply(&victim.head))) {
+                let mut moves = moves.clone();
+                moves.insert(victim.id.clone(), dir);
+                let mut board = prev.clone();
+                let eliminations = rules::resolve_turn(&mut board, &moves, settings);
+                if let Some(elimination) = eliminations.iter().find(|e| e.snake_id == victim.id) {
+                    fatal += 1;
+                    if matches!(&elimination.cause, EliminationCause::Collision(id) | EliminationCause::HeadToHead(id) if id == you) {
+                        ours += 1;
+                    }
+                }
+            }
+            ours * 2 > fatal
+        })
+        .map(|victim| victim.id.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board_builder::BoardBuilder;
+
+    #[test]
+    fn test_summary_counts_food_kills_and_outcome() {
+        let config = Config::default_hardcoded();
+
+        // We take the food and win the head-to-head on it
+        let mut stats = GameStats::default();
+        let before = BoardBuilder::new(7, 7).snake("us", &[(2, 1), (1, 1), (0, 1), (0, 0)]).snake("them", &[(4, 1), (5, 1), (6, 1)]).food(&[(3, 1)]).build();
+        stats.record_move(1, &before, "us", Direction::Right, MoveSearch { depth: 6, nodes: 1000, margin_ms: 300 }, &config);
+        let after = BoardBuilder::new(7, 7).snake("us", &[(3, 1), (2, 1), (1, 1), (0, 1), (0, 1)]).build();
+        let summary = stats.finish("g", GameMode::Standard, 2, &after, "us", &config);
+        assert_eq!((summary.outcome, summary.food_eaten, summary.kills.clone()), (Outcome::Win, 1, vec!["them".to_string()]));
+        assert_eq!((summary.moves, summary.avg_depth, summary.min_time_margin_ms, summary.death_cause), (1, 6.0, Some(300), None));
+
+        // We run into the wall
+        let mut stats = GameStats::default();
+        let edge = BoardBuilder::new(7, 7).snake("us", &[(0, 3), (1, 3), (2, 3)]).snake("them", &[(4, 3), (5, 3), (6, 3)]).build();
+        stats.record_move(7, &before, "us", Direction::Up, MoveSearch { depth: 4, nodes: 500, margin_ms: 410 }, &config);
+        stats.record_move(8, &edge, "us", Direction::Left, MoveSearch { depth: 8, nodes: 1500, margin_ms: 90 }, &config);
+        let summary = stats.finish("g", GameMode::Standard, 9, &BoardBuilder::new(7, 7).snake("them", &[(3, 3), (4, 3), (5, 3)]).build(), "us", &config);
+        assert_eq!(summary.outcome, Outcome::Loss);
+        assert_eq!(summary.death_cause.as_deref(), Some("Wall Collision"));
+        assert_eq!((summary.avg_depth, summary.avg_nodes, summary.min_time_margin_ms), (6.0, 1000.0, Some(90)));
+        assert!(summary.kills.is_empty());
+    }
+}
@@ -7,53 +7,286 @@
 // - Delegating to Bot methods
 // - Serializing responses
 
-use rocket::http::Status;
-use rocket::serde::json::Json;
+use rocket::data::{self, Data, FromData, Limits};
+use rocket::http::{ContentType, Status};
+use rocket::request::{FromRequest, Outcome, Request};
+use log::warn;
+use rocket::serde::json::{self, Json};
+use rocket_ws::{Channel, WebSocket};
 use serde_json::Value;
 
 use crate::bot::Bot;
+use crate::health;
+use crate::metrics::{LatencyMetrics, MoveContext};
+use crate::request_validation;
+use crate::stakes::Stakes;
 use crate::types::GameState;
 
+/// Unknown personality profiles answer 404
+fn profile_not_found(e: String) -> (Status, String) {
+    (Status::NotFound, e)
+}
+
+/// /move body kept verbatim next to its parsed game state, so debug.log_requests can log
+/// exactly what arrived on the wire (also tells the latency fairing which game the move is for)
+pub struct RawGameState {
+    raw: String,
+    state: Result<GameState, String>,
+}
+
+#[rocket::async_trait]
+impl<'r> FromData<'r> for RawGameState {
+    type Error = std::convert::Infallible;
+
+    async fn from_data(req: &'r Request<'_>, data: Data<'r>) -> data::Outcome<'r, Self> {
+        // Same size limit as the Json guard
+        let limit = req.limits().get("json").unwrap_or(Limits::JSON);
+        let (raw, state) = match data.open(limit).into_string().await {
+            Ok(body) if body.is_complete() => {
+                let raw = body.into_inner();
+                let state = serde_json::from_str::<GameState>(&raw).map_err(|e| format!("parse error: {}", e));
+                (raw, state)
+            }
+            Ok(_) => (String::new(), Err(format!("body exceeds the {} json limit", limit))),
+            Err(e) => (String::new(), Err(format!("i/o error: {}", e))),
+        };
+        if let Ok(state) = &state {
+            let (game_id, turn, timeout) = (state.game.id.clone(), state.turn, state.game.timeout);
+            req.local_cache(|| MoveContext(Some((game_id, turn, timeout))));
+        }
+        data::Outcome::Success(RawGameState { raw, state })
+    }
+}
+
+/// Parses and sanitizes a game state body
+/// Unparseable or unplayable states answer 400; repairs are logged and applied
+fn checked_state(bot: &Bot, body: Result<Json<GameState>, json::Error<'_>>) -> Result<GameState, (Status, String)> {
+    sanitized(bot, body.map(Json::into_inner).map_err(|e| e.to_string()))
+}
+
+fn sanitized(bot: &Bot, state: Result<GameState, String>) -> Result<GameState, (Status, String)> {
+    let mut state = state.map_err(|e| (Status::BadRequest, format!("Invalid game state: {}", e)))?;
+
+    let repairs = request_validation::sanitize(&mut state, &bot.config())
+        .map_err(|e| (Status::BadRequest, format!("Invalid game state: {}", e)))?;
+    if !repairs.is_empty() {
+        warn!("Turn {}: repaired request ({})", state.turn, repairs.join("; "));
+    }
+    Ok(state)
+}
+
+/// Parses the optional ?stakes= query of /start (400 for unknown stakes)
+fn parsed_stakes(stakes: Option<&str>) -> Result<Option<Stakes>, (Status, String)> {
+    stakes.map(Stakes::parse).transpose().map_err(|e| (Status::BadRequest, e))
+}
+
 /// GET / endpoint
 /// Returns bot metadata and appearance configuration
 #[get("/")]
-pub fn index(bot: &rocket::State<Bot>) -> Json<Value> {
-    Json(bot.info())
+pub fn index(bot: &rocket::State<Bot>) -> Result<Json<Value>, (Status, String)> {
+    bot.info(None).map(Json).map_err(profile_not_found)
 }
 
 /// POST /start endpoint
 /// Called when a game starts - allows initialization logic
-#[post("/start", format = "json", data = "<start_req>")]
-pub fn start(bot: &rocket::State<Bot>, start_req: Json<GameState>) -> Status {
-    bot.start(
+/// ?stakes=ladder|tournament sets the stakes of the game (default: stakes.default)
+#[post("/start?<stakes>", format = "json", data = "<start_req>")]
+pub fn start(
+    bot: &rocket::State<Bot>,
+    stakes: Option<&str>,
+    start_req: Result<Json<GameState>, json::Error<'_>>,
+) -> Result<Status, (Status, String)> {
+    let stakes = parsed_stakes(stakes)?;
+    let start_req = checked_state(bot, start_req)?;
+    bot.start_with_stakes(
+        None,
         &start_req.game,
         &start_req.turn,
         &start_req.board,
         &start_req.you,
-    );
-
-    Status::Ok
+        stakes,
+    )
+    .map(|_| Status::Ok)
+    .map_err(profile_not_found)
 }
 
 /// POST /move endpoint
-/// Called each turn to compute and return the next move
+/// Called each turn to compute and return the next move (400 for malformed game states)
 #[post("/move", format = "json", data = "<move_req>")]
-pub async fn get_move(bot: &rocket::State<Bot>, move_req: Json<GameState>) -> Json<Value> {
-    let response = bot.get_move(
+pub async fn get_move(bot: &rocket::State<Bot>, move_req: RawGameState) -> Result<Json<Value>, (Status, String)> {
+    let RawGameState { raw, state } = move_req;
+    let request = bot.config().debug.log_requests.then_some(raw);
+    let move_req = sanitized(bot, state)?;
+    bot.get_move_with_request(
+        None,
         &move_req.game,
         &move_req.turn,
         &move_req.board,
         &move_req.you,
-    ).await;
-
-    Json(response)
+        request,
+    )
+    .await
+    .map(Json)
+    .map_err(profile_not_found)
 }
 
 /// POST /end endpoint
 /// Called when a game ends - allows cleanup and logging
 #[post("/end", format = "json", data = "<end_req>")]
-pub fn end(bot: &rocket::State<Bot>, end_req: Json<GameState>) -> Status {
-    bot.end(&end_req.game, &end_req.turn, &end_req.board, &end_req.you);
+pub fn end(
+    bot: &rocket::State<Bot>,
+    metrics: &rocket::State<LatencyMetrics>,
+    end_req: Json<GameState>,
+) -> Result<Status, (Status, String)> {
+    metrics.log_game_end(&end_req.game.id);
+    bot.end(None, &end_req.game, &end_req.turn, &end_req.board, &end_req.you)
+        .map(|_| Status::Ok)
+        .map_err(profile_not_found)
+}
+
+/// GET /<profile> endpoint
+/// Metadata for a personality profile ([profiles.<name>] in Snake.toml)
+#[get("/<profile>")]
+pub fn profile_index(bot: &rocket::State<Bot>, profile: &str) -> Result<Json<Value>, (Status, String)> {
+    bot.info(Some(profile)).map(Json).map_err(profile_not_found)
+}
+
+/// POST /<profile>/start endpoint
+#[post("/<profile>/start?<stakes>", format = "json", data = "<start_req>")]
+pub fn profile_start(
+    bot: &rocket::State<Bot>,
+    profile: &str,
+    stakes: Option<&str>,
+    start_req: Result<Json<GameState>, json::Error<'_>>,
+) -> Result<Status, (Status, String)> {
+    let stakes = parsed_stakes(stakes)?;
+    let start_req = checked_state(bot, start_req)?;
+    bot.start_with_stakes(
+        Some(profile),
+        &start_req.game,
+        &start_req.turn,
+        &start_req.board,
+        &start_req.you,
+        stakes,
+    )
+    .map(|_| Status::Ok)
+    .map_err(profile_not_found)
+}
+
+/// POST /<profile>/move endpoint
+/// Same search as /move, evaluated with the profile's score weights
+#[post("/<profile>/move", format = "json", data = "<move_req>")]
+pub async fn profile_move(
+    bot: &rocket::State<Bot>,
+    profile: &str,
+    move_req: RawGameState,
+) -> Result<Json<Value>, (Status, String)> {
+    let RawGameState { raw, state } = move_req;
+    let request = bot.config().debug.log_requests.then_some(raw);
+    let move_req = sanitized(bot, state)?;
+    bot.get_move_with_request(
+        Some(profile),
+        &move_req.game,
+        &move_req.turn,
+        &move_req.board,
+        &move_req.you,
+        request,
+    )
+    .await
+    .map(Json)
+    .map_err(profile_not_found)
+}
+
+/// POST /<profile>/end endpoint
+#[post("/<profile>/end", format = "json", data = "<end_req>")]
+pub fn profile_end(
+    bot: &rocket::State<Bot>,
+    metrics: &rocket::State<LatencyMetrics>,
+    profile: &str,
+    end_req: Json<GameState>,
+) -> Result<Status, (Status, String)> {
+    metrics.log_game_end(&end_req.game.id);
+    bot.end(Some(profile), &end_req.game, &end_req.turn, &end_req.board, &end_req.you)
+        .map(|_| Status::Ok)
+        .map_err(profile_not_found)
+}
+
+/// GET /healthz endpoint
+/// Liveness: the server is up with a loaded config
+#[get("/healthz")]
+pub fn healthz(bot: &rocket::State<Bot>) -> Json<Value> {
+    Json(health::liveness(&bot.config()))
+}
+
+/// GET /metrics endpoint
+/// Handler latency histograms per endpoint (Prometheus text format)
+#[get("/metrics")]
+pub fn metrics(metrics: &rocket::State<LatencyMetrics>) -> (ContentType, String) {
+    (ContentType::Plain, metrics.render_prometheus())
+}
+
+/// GET /ws/games/<game_id> endpoint
+/// WebSocket stream of the game's per-turn search results (404 unless spectator.enabled)
+#[get("/ws/games/<game_id>")]
+pub fn spectate(bot: &rocket::State<Bot>, game_id: &str, ws: Option<WebSocket>) -> Result<Channel<'static>, (Status, String)> {
+    let subscription = bot
+        .spectate(game_id)
+        .ok_or_else(|| (Status::NotFound, "spectator stream is disabled".to_string()))?;
+    let ws = ws.ok_or_else(|| (Status::BadRequest, "expected a WebSocket upgrade request".to_string()))?;
+    Ok(subscription.stream(ws))
+}
+
+/// GET /readyz endpoint
+/// Readiness: config valid, rayon pool responsive, one-ply self-test within its limit (503 otherwise)
+#[get("/readyz")]
+pub async fn readyz(bot: &rocket::State<Bot>) -> (Status, Json<Value>) {
+    let (ready, body) = health::readiness(bot.config(), bot.capacity()).await;
+    let status = if ready { Status::Ok } else { Status::ServiceUnavailable };
+    (status, Json(body))
+}
+
+/// Request guard for the /admin endpoints
+/// Expects `Authorization: Bearer <token>` matching BATTLESNAKE_ADMIN_TOKEN.
+/// Without a configured token the endpoints answer 404, as if they did not exist.
+pub struct AdminAuth;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for AdminAuth {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let bot = match req.rocket().state::<Bot>() {
+            Some(bot) if bot.admin_enabled() => bot,
+            _ => return Outcome::Error((Status::NotFound, ())),
+        };
+
+        let presented = req
+            .headers()
+            .get_one("Authorization")
+            .and_then(|value| value.strip_prefix("Bearer "));
+        match presented {
+            Some(token) if bot.admin_token_matches(token) => Outcome::Success(AdminAuth),
+            _ => Outcome::Error((Status::Unauthorized, ())),
+        }
+    }
+}
+
+/// GET /admin/config endpoint
+/// Returns the score weights used for new moves
+#[get("/admin/config")]
+pub fn get_admin_config(bot: &rocket::State<Bot>, _auth: AdminAuth) -> Json<Value> {
+    Json(bot.scores_json())
+}
 
-    Status::Ok
+/// POST /admin/config endpoint
+/// Applies a partial ScoresConfig JSON object; subsequent moves use the new weights
+#[post("/admin/config", format = "json", data = "<overrides>")]
+pub fn update_admin_config(
+    bot: &rocket::State<Bot>,
+    _auth: AdminAuth,
+    overrides: Json<Value>,
+) -> Result<Json<Value>, (Status, String)> {
+    bot.update_scores(&overrides)
+        .map(Json)
+        .map_err(|e| (Status::BadRequest, e))
 }
@@ -0,0 +1,139 @@
+// Impassable hazard walls (arcade_maze and other fixed-layout maps)
+//
+// The search does not model hazard damage, which is right for the shrinking royale hazards
+// (14 damage a turn is survivable) but fatal on maps whose hazards are walls: in arcade_maze
+// a hazard cell takes a full 100 health, so entering one is as final as leaving the board.
+// In GameMode::Maze games (arcade_maze, or a ruleset hazardDamagePerTurn of at least
+// game_rules.hazard_wall_damage) HazardWalls is built once per move from the board's hazards
+// and carried to the search in Config::hazard_walls: move generation
+// never enters a wall, the flood fills and the distance field treat walls as permanent
+// obstacles, and food is measured by the shortest path through the corridors (a BFS from
+// every food at the root; food is not spawned during the search) instead of Manhattan
+// distance, which in a maze happily points through a wall.
+
+use crate::flood_grid::FloodGrid;
+use crate::types::{Board, Coord};
+
+/// Wall cells of the current map plus corridor distances to the root's food
+#[derive(Debug, Clone)]
+pub struct HazardWalls {
+    width: i32,
+    height: i32,
+    wall: Vec<bool>,
+    cells: Vec<Coord>,
+    /// BFS distance (around walls only) from each food cell, u16::MAX if unreachable
+    food_distances: Vec<(Coord, Vec<u16>)>,
+}
+
+impl HazardWalls {
+    /// Treats every hazard of `board` as a wall
+    pub fn new(board: &Board) -> Self {
+        let (width, height) = (board.width, board.height as i32);
+        let mut walls = HazardWalls {
+            width,
+            height,
+            wall: vec![false; (width.max(0) * height.max(0)) as usize],
+            cells: Vec::new(),
+            food_distances: Vec::new(),
+        };
+        for &hazard in &board.hazards {
+            if let Some(i) = walls.index(hazard) {
+                if !walls.wall[i] {
+                    walls.wall[i] = true;
+                    walls.cells.push(hazard);
+                }
+            }
+        }
+        walls.food_distances = board.food.iter().map(|&food| (food, walls.distances_from(food))).collect();
+        walls
+    }
+
+    fn index(&self, c: Coord) -> Option<usize> {
+        if c.x < 0 || c.y < 0 || c.x >= self.width || c.y >= self.height {
+            None
+        } else {
+            Some((c.y * self.width + c.x) as usize)
+        }
+    }
+
+    /// True if `c` is a wall cell
+    #[inline]
+    pub fn is_wall(&self, c: Coord) -> bool {
+        self.index(c).is_some_and(|i| self.wall[i])
+    }
+
+    /// Wall cells, in board order of the hazards
+    pub fn cells(&self) -> &[Coord] {
+        &self.cells
+    }
+
+    /// Marks every wall as a permanent obstacle of the current fill
+    pub fn block(&self, grid: &mut FloodGrid) {
+        for &cell in &self.cells {
+            grid.block(cell, u16::MAX);
+        }
+    }
+
+    /// BFS distances from `start` around the walls (snakes ignored)
+    fn distances_from(&self, start: Coord) -> Vec<u16> {
+        let mut distances = vec![u16::MAX; self.wall.len()];
+        let Some(start_i) = self.index(start) else { return distances };
+        distances[start_i] = 0;
+        let mut queue = vec![(start, 0u16)];
+        let mut next_idx = 0;
+        while next_idx < queue.len() {
+            let (pos, turns) = queue[next_idx];
+            next_idx += 1;
+            for next in pos.neighbors() {
+                match self.index(next) {
+                    Some(i) if !self.wall[i] && distances[i] == u16::MAX => {
+                        distances[i] = turns + 1;
+                        queue.push((next, turns + 1));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        distances
+    }
+
+    /// Corridor distance from `from` to the food at `food`; walled-off food counts as the
+    /// number of cells on the board (farther than any reachable food). None if `food` was not
+    /// on the board the walls were built from
+    pub fn food_distance(&self, from: Coord, food: Coord) -> Option<i32> {
+        let (_, distances) = self.food_distances.iter().find(|(f, _)| *f == food)?;
+        let distance = self.index(from).map_or(u16::MAX, |i| distances[i]);
+        Some(if distance == u16::MAX { self.wall.len() as i32 } else { distance as i32 })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board_builder::parse_board;
+
+    #[test]
+    fn test_food_distance_follows_the_corridor() {
+        let board = parse_board(
+            "
+            . . . . .
+            . # # # .
+            a> # * # .
+            . # . . .
+            . . . . .
+        ",
+        )
+        .unwrap();
+        let walls = HazardWalls::new(&board);
+        let food = Coord { x: 2, y: 2 };
+        assert!(walls.is_wall(Coord { x: 1, y: 2 }) && !walls.is_wall(Coord { x: 0, y: 2 }));
+        assert_eq!(walls.cells().len(), 6);
+
+        // Manhattan says 2; around the walls it is down, along the bottom and back up
+        assert_eq!(walls.food_distance(Coord { x: 0, y: 2 }, food), Some(6));
+        assert_eq!(walls.food_distance(Coord { x: 0, y: 2 }, Coord { x: 4, y: 4 }), None);
+
+        let sealed = HazardWalls::new(&parse_board("a> # *").unwrap());
+        assert_eq!(sealed.food_distance(Coord { x: 0, y: 0 }, Coord { x: 2, y: 0 }), Some(3));
+    }
+}
@@ -0,0 +1,235 @@
+// Liveness and readiness probes for hosting platforms
+//
+// /healthz answers as long as the server is up and a config is loaded. /readyz additionally
+// proves the bot can actually answer /move in time: the configuration validates, a search
+// slot (capacity::GameCapacity) frees up and its pool starts the self-test promptly, and a
+// one-ply search on a fixed position finishes within health.self_test_max_ms. Every slot
+// taken by runaway searches or a pathological slowdown shows up as 503 instead of as
+// timeouts in a live game.
+
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::bot::Bot;
+use crate::capacity::GameCapacity;
+use crate::config::Config;
+use crate::types::{Battlesnake, Board, Coord};
+
+/// Outcome of one readiness check
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub ok: bool,
+    pub detail: String,
+}
+
+impl CheckResult {
+    fn to_json(&self) -> Value {
+        json!({ "name": self.name, "ok": self.ok, "detail": self.detail })
+    }
+}
+
+/// Package name, version and build flavour
+pub fn build_info() -> Value {
+    json!({
+        "name": env!("CARGO_PKG_NAME"),
+        "version": env!("CARGO_PKG_VERSION"),
+        "profile": if cfg!(debug_assertions) { "debug" } else { "release" },
+        "target": format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS),
+    })
+}
+
+/// Liveness report: the process is up and serving with a loaded config
+/// Corresponds to GET /healthz
+pub fn liveness(config: &Config) -> Value {
+    json!({
+        "status": "ok",
+        "build": build_info(),
+        "config_loaded": true,
+        "profiles": config.profile_names().collect::<Vec<_>>(),
+    })
+}
+
+/// Readiness report: (ready, JSON body)
+/// Corresponds to GET /readyz
+pub async fn readiness(config: Arc<Config>, capacity: &Arc<GameCapacity>) -> (bool, Value) {
+    let mut checks = vec![check_config(&config)];
+    checks.extend(check_slot_and_search(config, capacity).await);
+
+    let ready = checks.iter().all(|check| check.ok);
+    let body = json!({
+        "status": if ready { "ready" } else { "unavailable" },
+        "build": build_info(),
+        "checks": checks.iter().map(CheckResult::to_json).collect::<Vec<_>>(),
+    });
+    (ready, body)
+}
+
+fn check_config(config: &Config) -> CheckResult {
+    let errors = config.validation_errors();
+    CheckResult {
+        name: "config",
+        ok: errors.is_empty(),
+        detail: match errors.first() {
+            None => "valid".to_string(),
+            Some(first) => format!("{} error(s), first: {}", errors.len(), first),
+        },
+    }
+}
+
+/// Runs the one-ply self-test in a search slot, like a /move would: one check for getting a
+/// slot and its pool picking the job up, one for the search finishing within the configured limit
+async fn check_slot_and_search(config: Arc<Config>, capacity: &Arc<GameCapacity>) -> Vec<CheckResult> {
+    let timeout = Duration::from_millis(config.health.pool_timeout_ms);
+    let max_search_ms = config.health.self_test_max_ms;
+    let requested = Instant::now();
+
+    let Some(slot) = capacity.acquire(timeout).await else {
+        return vec![
+            CheckResult {
+                name: "search_slot",
+                ok: false,
+                detail: format!("no free slot within {}ms ({} searches running)", timeout.as_millis(), capacity.active()),
+            },
+            CheckResult {
+                name: "one_ply_search",
+                ok: false,
+                detail: "not run (no search slot)".to_string(),
+            },
+        ];
+    };
+    let waited = requested.elapsed();
+    let threads = slot.threads();
+    let job = tokio::task::spawn_blocking(move || {
+        slot.install(|| {
+            let queued = requested.elapsed();
+            (queued, self_test_search(&config))
+        })
+    });
+
+    match tokio::time::timeout(timeout, job).await {
+        Ok(Ok((queued, search_time))) => vec![
+            CheckResult {
+                name: "search_slot",
+                ok: true,
+                detail: format!(
+                    "slot after {:.2}ms, job started after {:.2}ms on {} threads",
+                    ms(waited),
+                    ms(queued),
+                    threads
+                ),
+            },
+            CheckResult {
+                name: "one_ply_search",
+                ok: search_time <= Duration::from_millis(max_search_ms),
+                detail: format!("{:.2}ms (limit {}ms)", ms(search_time), max_search_ms),
+            },
+        ],
+        _ => vec![
+            CheckResult {
+                name: "search_slot",
+                ok: false,
+                detail: format!("slot pool gave no response within {}ms", timeout.as_millis()),
+            },
+            CheckResult {
+                name: "one_ply_search",
+                ok: false,
+                detail: "not run (pool unresponsive)".to_string(),
+            },
+        ],
+    }
+}
+
+fn ms(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Times a one-ply search (the search fallback ranking) on a fixed mid-game duel
+pub fn self_test_search(config: &Config) -> Duration {
+    let (board, you) = self_test_position();
+    let started = Instant::now();
+    let legal = Bot::generate_legal_moves(&board, &you, config);
+    Bot::single_ply_fallback(&board, &you, &legal, config);
+    started.elapsed()
+}
+
+fn self_test_position() -> (Board, Battlesnake) {
+    let snake = |id: &str, body: Vec<(i32, i32)>| {
+        let body: Vec<Coord> = body.into_iter().map(|(x, y)| Coord { x, y }).collect();
+        Battlesnake {
+            id: id.to_string(),
+            name: id.to_string(),
+            health: 80,
+            head: body[0],
+            length: body.len() as i32,
+            body,
+            latency: "0".to_string(),
+            shout: None,
+            squad: None,
+        }
+    };
+    let you = snake("self-test-us", vec![(5, 5), (5, 4), (5, 3), (4, 3), (3, 3)]);
+    let opponent = snake("self-test-them", vec![(7, 7), (7, 8), (8, 8), (9, 8)]);
+    let board = Board {
+        height: 11,
+        width: 11,
+        food: vec![Coord { x: 2, y: 8 }, Coord { x: 9, y: 2 }],
+        snakes: vec![you.clone(), opponent],
+        hazards: vec![],
+        occupancy: None,
+    };
+    (board, you)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_readiness_passes_with_default_config() {
+        // Generous search limit: tests share the machine with the rest of the suite
+        let mut config = Config::default_hardcoded();
+        config.health.self_test_max_ms = 1000;
+        let capacity = GameCapacity::new(&config);
+        let (ready, body) = readiness(Arc::new(config), &capacity).await;
+        let checks: Vec<&str> = body["checks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|check| check["name"].as_str().unwrap())
+            .collect();
+
+        assert_eq!(checks, vec!["config", "search_slot", "one_ply_search"]);
+        assert!(ready, "readiness failed: {}", body);
+        assert_eq!(body["build"]["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(capacity.active(), 0, "the probe returns its slot");
+    }
+
+    #[tokio::test]
+    async fn test_busy_slots_are_not_ready() {
+        let mut config = Config::default_hardcoded();
+        config.concurrency.max_concurrent_games = 1;
+        config.health.pool_timeout_ms = 20;
+        let capacity = GameCapacity::new(&config);
+        let _running = capacity.acquire(Duration::from_millis(20)).await.expect("free slot");
+        let (ready, body) = readiness(Arc::new(config), &capacity).await;
+
+        assert!(!ready);
+        assert_eq!(body["checks"][1]["name"], "search_slot");
+        assert_eq!(body["checks"][1]["ok"], false);
+        assert_eq!(body["checks"][2]["ok"], false);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_config_is_not_ready() {
+        let mut config = Config::default_hardcoded();
+        config.timing.network_overhead_ms = config.timing.response_time_budget_ms;
+        let capacity = GameCapacity::new(&config);
+        let (ready, body) = readiness(Arc::new(config), &capacity).await;
+
+        assert!(!ready);
+        assert_eq!(body["status"], "unavailable");
+        assert_eq!(body["checks"][0]["ok"], false);
+    }
+}
@@ -0,0 +1,317 @@
+// Self-contained HTML replay of a game log
+//
+// Sharing a lost game used to mean sending the JSONL plus instructions for the replay tool.
+// The report is a single HTML file: turn data is embedded as JSON and a small script draws
+// each board as SVG, with play/step controls, the logged and replayed move, the search score
+// and depth, the root move scores the live bot logged (when the log has them), and bars for
+// the evaluation components of the logged move (the same breakdown as
+// Bot::evaluate_move_detailed). Written by `replay --export-html`.
+
+use serde::Serialize;
+
+use crate::bot::Bot;
+use crate::config::Config;
+use crate::replay::ReplayResult;
+use crate::types::{Board, Coord};
+
+#[derive(Serialize)]
+struct SnakeFrame {
+    id: String,
+    health: i32,
+    length: i32,
+    body: Vec<[i32; 2]>,
+}
+
+/// One turn as the page script reads it
+#[derive(Serialize)]
+struct TurnFrame {
+    turn: i32,
+    width: i32,
+    height: u32,
+    food: Vec<[i32; 2]>,
+    hazards: Vec<[i32; 2]>,
+    snakes: Vec<SnakeFrame>,
+    logged: &'static str,
+    replayed: &'static str,
+    score: i32,
+    depth: u8,
+    /// (component, value) of the logged move's evaluation, total last
+    components: Vec<(&'static str, i32)>,
+    /// Logged root move scores of the deepest iteration, as (move, score)
+    candidates: Vec<(String, i32)>,
+    /// Best minus second best of those scores
+    margin: Option<i64>,
+}
+
+fn cells(coords: &[Coord]) -> Vec<[i32; 2]> {
+    coords.iter().map(|c| [c.x, c.y]).collect()
+}
+
+fn frame(result: &ReplayResult, config: &Config) -> TurnFrame {
+    let board: &Board = &result.board;
+    let components = match Bot::evaluate_move_detailed(board, &result.our_id, result.original_move, config) {
+        Ok(d) => {
+            let mut components = d.components().to_vec();
+            components.push(("total", d.total));
+            components
+        }
+        Err(_) => Vec::new(),
+    };
+    let deepest = result.logged.as_ref().and_then(|logged| logged.candidates.as_ref()?.last());
+
+    TurnFrame {
+        turn: result.turn,
+        width: board.width,
+        height: board.height,
+        food: cells(&board.food),
+        hazards: cells(&board.hazards),
+        snakes: board
+            .snakes
+            .iter()
+            .map(|s| SnakeFrame { id: s.id.clone(), health: s.health, length: s.length, body: cells(&s.body) })
+            .collect(),
+        logged: result.original_move.as_str(),
+        replayed: result.replayed_move.as_str(),
+        score: result.replayed_score,
+        depth: result.search_depth,
+        components,
+        candidates: deepest.map_or_else(Vec::new, |iteration| {
+            iteration.scores.iter().map(|c| (c.candidate_move.clone(), c.score)).collect()
+        }),
+        margin: deepest.and_then(|iteration| iteration.margin()),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// HTML page replaying `results` (in the given order) under `title`
+pub fn render(title: &str, results: &[ReplayResult], config: &Config) -> Result<String, String> {
+    let frames: Vec<TurnFrame> = results.iter().map(|r| frame(r, config)).collect();
+    let json = serde_json::to_string(&frames).map_err(|e| format!("Failed to serialize turns: {}", e))?;
+    // "</script>" inside the data would end the script element early
+    let json = json.replace("</", "<\\/");
+    Ok(TEMPLATE.replace("__TITLE__", &escape_html(title)).replace("__TURNS__", &json))
+}
+
+const TEMPLATE: &str = r##"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>__TITLE__</title>
+<style>
+  body { font-family: sans-serif; background: #1e1e24; color: #ddd; margin: 20px; }
+  h1 { font-size: 18px; }
+  #main { display: flex; gap: 24px; align-items: flex-start; }
+  #controls { margin: 12px 0; display: flex; gap: 8px; align-items: center; }
+  #slider { width: 400px; }
+  button { background: #333; color: #ddd; border: 1px solid #555; padding: 4px 10px; cursor: pointer; }
+  table { border-collapse: collapse; }
+  td { padding: 2px 8px; }
+  .mismatch { color: #ff6b6b; font-weight: bold; }
+  .chosen { font-weight: bold; }
+  .bar { height: 12px; display: inline-block; }
+  .pos { background: #4caf50; }
+  .neg { background: #e53935; }
+  .barcell { width: 220px; position: relative; }
+  .axis { position: absolute; left: 110px; top: 0; bottom: 0; border-left: 1px solid #777; }
+</style>
+</head>
+<body>
+<h1>__TITLE__</h1>
+<div id="controls">
+  <button id="prev">&#9664;</button>
+  <button id="play">Play</button>
+  <button id="next">&#9654;</button>
+  <input id="slider" type="range" min="0" value="0">
+  <span id="turnLabel"></span>
+</div>
+<div id="main">
+  <svg id="board"></svg>
+  <div>
+    <table id="info"></table>
+    <h3>Logged candidates</h3>
+    <table id="candidates"></table>
+    <h3>Evaluation of the logged move</h3>
+    <table id="components"></table>
+    <h3>Snakes</h3>
+    <table id="snakes"></table>
+  </div>
+</div>
+<script>
+const TURNS = __TURNS__;
+const COLORS = ["#4caf50", "#ffb300", "#42a5f5", "#ab47bc", "#26c6da", "#eeeeee"];
+const CELL = 40;
+let index = 0;
+let timer = null;
+
+const slider = document.getElementById("slider");
+slider.max = Math.max(TURNS.length - 1, 0);
+
+function el(name, attrs) {
+  const node = document.createElementNS("http://www.w3.org/2000/svg", name);
+  for (const key in attrs) node.setAttribute(key, attrs[key]);
+  return node;
+}
+
+function center(cell, height) {
+  return [cell[0] * CELL + CELL / 2, (height - 1 - cell[1]) * CELL + CELL / 2];
+}
+
+function drawBoard(t) {
+  const svg = document.getElementById("board");
+  svg.innerHTML = "";
+  svg.setAttribute("width", t.width * CELL);
+  svg.setAttribute("height", t.height * CELL);
+  for (let x = 0; x < t.width; x++) {
+    for (let y = 0; y < t.height; y++) {
+      svg.appendChild(el("rect", { x: x * CELL + 1, y: (t.height - 1 - y) * CELL + 1, width: CELL - 2, height: CELL - 2, fill: "#2a2a33" }));
+    }
+  }
+  for (const h of t.hazards) {
+    svg.appendChild(el("rect", { x: h[0] * CELL + 1, y: (t.height - 1 - h[1]) * CELL + 1, width: CELL - 2, height: CELL - 2, fill: "#55556a" }));
+  }
+  for (const f of t.food) {
+    const [cx, cy] = center(f, t.height);
+    svg.appendChild(el("circle", { cx: cx, cy: cy, r: CELL / 5, fill: "#ff5252" }));
+  }
+  t.snakes.forEach((s, i) => {
+    const color = COLORS[i % COLORS.length];
+    const points = s.body.map(c => center(c, t.height).join(",")).join(" ");
+    svg.appendChild(el("polyline", { points: points, fill: "none", stroke: color, "stroke-width": CELL * 0.6, "stroke-linecap": "round", "stroke-linejoin": "round", opacity: s.health > 0 ? 1 : 0.3 }));
+    const [hx, hy] = center(s.body[0], t.height);
+    svg.appendChild(el("circle", { cx: hx, cy: hy, r: CELL * 0.38, fill: color, stroke: "#000", "stroke-width": 2 }));
+  });
+}
+
+function row(cells, cls) {
+  const tr = document.createElement("tr");
+  for (const c of cells) {
+    const td = document.createElement("td");
+    if (c instanceof Node) td.appendChild(c); else td.textContent = c;
+    if (cls) td.className = cls;
+    tr.appendChild(td);
+  }
+  return tr;
+}
+
+function drawInfo(t) {
+  const info = document.getElementById("info");
+  info.innerHTML = "";
+  info.appendChild(row(["Turn", t.turn]));
+  info.appendChild(row(["Logged move", t.logged]));
+  info.appendChild(row(["Replayed move", t.replayed], t.logged === t.replayed ? "" : "mismatch"));
+  info.appendChild(row(["Score", t.score]));
+  info.appendChild(row(["Depth", t.depth]));
+  if (t.margin !== null) info.appendChild(row(["Margin", t.margin]));
+
+  const candidates = document.getElementById("candidates");
+  candidates.innerHTML = "";
+  if (t.candidates.length === 0) candidates.appendChild(row(["(not logged)"]));
+  for (const [name, score] of t.candidates) {
+    candidates.appendChild(row([name, score], name === t.logged ? "chosen" : ""));
+  }
+
+  const table = document.getElementById("components");
+  table.innerHTML = "";
+  const scale = Math.max(1, ...t.components.filter(c => c[0] !== "total").map(c => Math.abs(c[1])));
+  for (const [name, value] of t.components) {
+    const cell = document.createElement("div");
+    cell.className = "barcell";
+    cell.appendChild(Object.assign(document.createElement("div"), { className: "axis" }));
+    if (name !== "total") {
+      const bar = document.createElement("div");
+      const width = Math.round(Math.abs(value) / scale * 110);
+      bar.className = "bar " + (value >= 0 ? "pos" : "neg");
+      bar.style.width = width + "px";
+      bar.style.marginLeft = (value >= 0 ? 110 : 110 - width) + "px";
+      cell.appendChild(bar);
+    }
+    table.appendChild(row([name, value, cell]));
+  }
+
+  const snakes = document.getElementById("snakes");
+  snakes.innerHTML = "";
+  t.snakes.forEach((s, i) => {
+    const swatch = Object.assign(document.createElement("span"), { textContent: "■" });
+    swatch.style.color = COLORS[i % COLORS.length];
+    snakes.appendChild(row([swatch, s.id, "health " + s.health, "length " + s.length]));
+  });
+}
+
+function show(i) {
+  if (TURNS.length === 0) return;
+  index = Math.min(Math.max(i, 0), TURNS.length - 1);
+  slider.value = index;
+  const t = TURNS[index];
+  document.getElementById("turnLabel").textContent = "Turn " + t.turn + " (" + (index + 1) + "/" + TURNS.length + ")";
+  drawBoard(t);
+  drawInfo(t);
+}
+
+function togglePlay() {
+  const button = document.getElementById("play");
+  if (timer) {
+    clearInterval(timer);
+    timer = null;
+    button.textContent = "Play";
+  } else {
+    timer = setInterval(() => { if (index + 1 >= TURNS.length) togglePlay(); else show(index + 1); }, 400);
+    button.textContent = "Pause";
+  }
+}
+
+document.getElementById("prev").onclick = () => show(index - 1);
+document.getElementById("next").onclick = () => show(index + 1);
+document.getElementById("play").onclick = togglePlay;
+slider.oninput = () => show(Number(slider.value));
+document.addEventListener("keydown", e => {
+  if (e.key === "ArrowLeft") show(index - 1);
+  if (e.key === "ArrowRight") show(index + 1);
+  if (e.key === " ") { e.preventDefault(); togglePlay(); }
+});
+show(0);
+</script>
+</body>
+</html>
+"##;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board_builder::BoardBuilder;
+    use crate::schema::SearchResultV1;
+    use crate::types::Direction;
+
+    #[test]
+    fn test_report_embeds_turns_and_escapes_the_title() {
+        let board = BoardBuilder::new(5, 5).snake("us</script>", &[(2, 2), (2, 1)]).food(&[(0, 0)]).build();
+        let result = ReplayResult {
+            turn: 7,
+            original_move: Direction::Up,
+            replayed_move: Direction::Left,
+            matches: false,
+            original_score: 0,
+            replayed_score: 1234,
+            search_depth: 3,
+            computation_time_ms: 5,
+            board,
+            our_id: "us</script>".to_string(),
+            logged: Some(
+                SearchResultV1::new(7, Direction::Up, 0, 3, 5)
+                    .with_candidates(&[(3, Direction::Up, 40), (3, Direction::Left, 15), (2, Direction::Left, 90)]),
+            ),
+            replayed_leaf: None,
+            original_leaf: None,
+        };
+
+        let html = render("game <1>", &[result], &Config::default_hardcoded()).unwrap();
+        assert!(html.contains("<title>game &lt;1&gt;</title>"));
+        assert!(html.contains("\"turn\":7"));
+        assert!(html.contains("\"logged\":\"up\",\"replayed\":\"left\",\"score\":1234,\"depth\":3"));
+        assert!(html.contains("[\"total\","));
+        assert!(html.contains("\"candidates\":[[\"up\",40],[\"left\",15]],\"margin\":25"));
+        assert!(!html.contains("us</script>"), "snake ids must not close the script element");
+    }
+}
@@ -0,0 +1,129 @@
+// Measured network latency per game
+//
+// The engine reports the round trip it observed for our previous move in `you.latency`.
+// Subtracting our own handler time for that turn leaves the network + framework overhead,
+// which replaces the static timing.network_overhead_ms once samples are available.
+
+use crate::config::Config;
+
+/// Smoothed estimate of the non-compute part of a /move round trip for one game
+#[derive(Debug, Clone, Default)]
+pub struct LatencyTracker {
+    /// EWMA of (engine-reported latency - our handler time), None until the first sample
+    overhead_ms: Option<f64>,
+    /// Handler time of the last served /move, waiting for the engine's latency report
+    last_handler_ms: Option<u64>,
+    samples: u32,
+}
+
+impl LatencyTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the latency the engine reported for our previous move
+    /// Ignored on the first turn (no previous move) or if the value is missing/unparseable
+    pub fn observe(&mut self, reported_latency: &str, config: &Config) {
+        let handler_ms = match self.last_handler_ms.take() {
+            Some(ms) => ms,
+            None => return,
+        };
+        let reported_ms = match reported_latency.trim().parse::<u64>() {
+            Ok(ms) if ms > 0 => ms,
+            _ => return,
+        };
+
+        let overhead = reported_ms.saturating_sub(handler_ms) as f64;
+        let smoothing = config.timing.latency.smoothing.clamp(0.0, 1.0);
+        self.overhead_ms = Some(match self.overhead_ms {
+            Some(previous) => smoothing * overhead + (1.0 - smoothing) * previous,
+            None => overhead,
+        });
+        self.samples += 1;
+    }
+
+    /// Records how long our handler took for the move just served
+    pub fn record_handler_time(&mut self, handler_ms: u64) {
+        self.last_handler_ms = Some(handler_ms);
+    }
+
+    /// Overhead to reserve this turn: measured estimate plus safety margin, or the static config value
+    pub fn overhead_estimate_ms(&self, config: &Config) -> u64 {
+        let latency = &config.timing.latency;
+        match self.overhead_ms {
+            Some(measured) if latency.enabled => {
+                (measured.ceil() as u64 + latency.safety_margin_ms).clamp(latency.min_overhead_ms, latency.max_overhead_ms)
+            }
+            _ => config.timing.network_overhead_ms,
+        }
+    }
+
+    /// Computation budget for this turn: the game's own timeout (0 = not announced) minus the
+    /// overhead estimate, or timing.response_time_budget_ms without a timeout
+    /// With timing.use_game_timeout off the game's timeout only caps the configured budget
+    pub fn budget_ms(&self, game_timeout_ms: u32, config: &Config) -> u64 {
+        let response_budget = match game_timeout_ms as u64 {
+            0 => config.timing.response_time_budget_ms,
+            timeout if config.timing.use_game_timeout => timeout,
+            timeout => config.timing.response_time_budget_ms.min(timeout),
+        };
+        response_budget.saturating_sub(self.overhead_estimate_ms(config))
+    }
+
+    /// Number of latency samples folded into the estimate
+    pub fn samples(&self) -> u32 {
+        self.samples
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_static_overhead_until_first_sample() {
+        let config = Config::default_hardcoded();
+        let mut tracker = LatencyTracker::new();
+
+        // First turn: nothing served yet, the report is ignored
+        tracker.observe("0", &config);
+        assert_eq!(tracker.samples(), 0);
+        assert_eq!(tracker.budget_ms(400, &config), config.timing.effective_budget_ms());
+    }
+
+    #[test]
+    fn test_measured_overhead_adapts_budget() {
+        let config = Config::default_hardcoded();
+        let mut tracker = LatencyTracker::new();
+
+        // Handler took 340ms and the engine saw 360ms: 20ms of network overhead
+        tracker.record_handler_time(340);
+        tracker.observe("360", &config);
+
+        let expected_overhead = (20 + config.timing.latency.safety_margin_ms)
+            .clamp(config.timing.latency.min_overhead_ms, config.timing.latency.max_overhead_ms);
+        assert_eq!(tracker.overhead_estimate_ms(&config), expected_overhead);
+        assert_eq!(tracker.budget_ms(500, &config), 500 - expected_overhead);
+    }
+
+    #[test]
+    fn test_budget_respects_shorter_game_timeout() {
+        let config = Config::default_hardcoded();
+        let tracker = LatencyTracker::new();
+
+        assert_eq!(tracker.budget_ms(300, &config), 300 - config.timing.network_overhead_ms);
+    }
+
+    #[test]
+    fn test_budget_follows_game_timeout() {
+        let mut config = Config::default_hardcoded();
+        let tracker = LatencyTracker::new();
+        let overhead = config.timing.network_overhead_ms;
+
+        assert_eq!(tracker.budget_ms(500, &config), 500 - overhead, "500ms ladder");
+        assert_eq!(tracker.budget_ms(0, &config), config.timing.effective_budget_ms(), "no timeout announced");
+
+        config.timing.use_game_timeout = false;
+        assert_eq!(tracker.budget_ms(500, &config), config.timing.effective_budget_ms());
+    }
+}
@@ -1,10 +1,59 @@
 // Library exports for Battlesnake bot
 // This allows the replay tool and other utilities to use the core bot logic
 
+pub mod analysis;
+pub mod arena;
+pub mod board_builder;
 pub mod bot;
+pub mod capacity;
 pub mod config;
+pub mod config_validation;
+pub mod contempt;
+pub mod dataset;
 pub mod debug_logger;
+pub mod distance_field;
+pub mod endgame;
+pub mod error;
+pub mod eval_cache;
+pub mod evaluator;
+pub mod evolution;
+pub mod flood_grid;
+pub mod food_route;
+pub mod game_mode;
+pub mod game_summary;
+pub mod hazard_walls;
+pub mod health;
+pub mod html_report;
+pub mod latency;
+pub mod metrics;
+pub mod move_list;
+#[cfg(feature = "nn")]
+pub mod nn;
+pub mod occupancy;
+pub mod opening;
+pub mod opponents;
+pub mod post_mortem;
 pub mod profiler;
+pub mod puzzles;
+pub mod rating;
+pub mod render;
 pub mod replay;
+pub mod request_validation;
+pub mod rules;
+pub mod schema;
+pub mod score_trend;
+pub mod search_scratch;
+pub mod session;
 pub mod simple_profiler;
+pub mod snail;
+pub mod spectator;
+pub mod squad;
+pub mod stakes;
+pub mod stall;
+pub mod time_manager;
+pub mod timing_history;
+pub mod trap;
+pub mod tuning;
+pub mod turn_profile;
 pub mod types;
+pub mod win_probability;
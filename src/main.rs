@@ -1,20 +1,97 @@
 #[macro_use]
 extern crate rocket;
 
-use log::info;
+use log::{error, info, warn};
 use rocket::fairing::AdHoc;
 use std::env;
 
+mod analysis;
+#[cfg(test)]
+mod board_builder;
 mod bot;
+mod capacity;
 mod config;
+mod config_validation;
+mod contempt;
+#[cfg(feature = "nn")]
+mod dataset;
 mod debug_logger;
+mod distance_field;
+mod endgame;
+mod error;
+mod eval_cache;
+mod evaluator;
+mod flood_grid;
+mod food_route;
+mod game_mode;
+mod game_summary;
 mod handler;
+mod hazard_walls;
+mod health;
+mod latency;
+mod metrics;
+mod move_list;
+#[cfg(feature = "nn")]
+mod nn;
+mod occupancy;
+mod opening;
+mod post_mortem;
 mod replay;
+mod request_validation;
+mod rules;
+mod schema;
+mod score_trend;
+mod search_scratch;
+mod session;
 mod simple_profiler;
+mod snail;
+mod spectator;
+mod squad;
+mod stakes;
+mod stall;
+mod time_manager;
+mod timing_history;
+mod trap;
+mod turn_profile;
 mod types;
+mod win_probability;
+
+/// Validates a config file, prints every issue and returns the process exit code
+fn check_config(path: &str) -> i32 {
+    let config = match config::Config::from_file(path) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            return 1;
+        }
+    };
+
+    let issues = config.validate();
+    for issue in &issues {
+        println!("{}", issue);
+    }
+    let errors = issues
+        .iter()
+        .filter(|issue| issue.severity == config_validation::Severity::Error)
+        .count();
+    println!("{}: {} error(s), {} warning(s)", path, errors, issues.len() - errors);
+
+    if errors > 0 {
+        1
+    } else {
+        0
+    }
+}
 
 #[launch]
 fn rocket() -> _ {
+    // `--check-config [path]` validates Snake.toml (or the given file) and exits
+    let args: Vec<String> = env::args().collect();
+    if let Some(flag) = args.iter().position(|arg| arg == "--check-config") {
+        let path = args.get(flag + 1).map(String::as_str).unwrap_or("Snake.toml");
+        std::process::exit(check_config(path));
+    }
+
     // Lots of web hosting services expect you to bind to the port specified by the `PORT`
     // environment variable. However, Rocket looks at the `ROCKET_PORT` environment variable.
     // If we find a value for `PORT`, we set `ROCKET_PORT` to that value.
@@ -34,10 +111,35 @@ fn rocket() -> _ {
 
     // Load configuration once at startup
     let config = config::Config::load_or_default();
-    let bot = bot::Bot::new(config);
+    let issues = config.validate();
+    for issue in &issues {
+        match issue.severity {
+            config_validation::Severity::Error => error!("Config {}", issue),
+            config_validation::Severity::Warning => warn!("Config {}", issue),
+        }
+    }
+    if issues.iter().any(|issue| issue.severity == config_validation::Severity::Error) {
+        error!("Refusing to start with an invalid configuration (run with --check-config for details)");
+        std::process::exit(1);
+    }
 
-    rocket::build()
-        .manage(bot)
+    // Weight hot-reload (/admin/config) answers 404 unless a token is configured
+    let admin_token = env::var("BATTLESNAKE_ADMIN_TOKEN").ok();
+    if admin_token.as_deref().is_some_and(|t| !t.is_empty()) {
+        info!("Admin endpoints enabled (/admin/config)");
+    }
+    for profile in config.profile_names() {
+        info!("Profile available: /{}/move", profile);
+    }
+    let latency_metrics = metrics::LatencyMetrics::new(&config.metrics);
+    let metrics_enabled = config.metrics.enabled;
+    let bot = bot::Bot::new(config).with_admin_token(admin_token);
+
+    let mut server = rocket::build().manage(bot).manage(latency_metrics);
+    if metrics_enabled {
+        server = server.attach(metrics::LatencyFairing);
+    }
+    server
         .attach(AdHoc::on_response("Server ID Middleware", |_, res| {
             Box::pin(async move {
                 res.set_raw_header("Server", "battlesnake/github/starter-snake-rust");
@@ -45,6 +147,21 @@ fn rocket() -> _ {
         }))
         .mount(
             "/",
-            routes![handler::index, handler::start, handler::get_move, handler::end],
+            routes![
+                handler::index,
+                handler::start,
+                handler::get_move,
+                handler::end,
+                handler::profile_index,
+                handler::profile_start,
+                handler::profile_move,
+                handler::profile_end,
+                handler::healthz,
+                handler::readyz,
+                handler::metrics,
+                handler::spectate,
+                handler::get_admin_config,
+                handler::update_admin_config
+            ],
         )
 }
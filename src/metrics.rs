@@ -0,0 +1,267 @@
+// Handler latency metrics for the web layer
+//
+// A Rocket fairing times every request from arrival to response and adds it to a per-endpoint
+// histogram (keyed by the route template, so /<profile>/move is one series), served by GET
+// /metrics in the Prometheus text format. /move responses are also tracked per game: a turn
+// whose handler time exceeds metrics.slow_turn_fraction of the game's timeout is flagged as it
+// happens (it is one network hiccup away from a timeout loss), and /end logs the game's
+// latency summary. The game of a /move is known once its body is parsed: the RawGameState
+// guard leaves a MoveContext in the request-local cache for the fairing to pick up.
+
+use log::{info, warn};
+use parking_lot::Mutex;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::{Data, Request, Response};
+use std::collections::{BTreeMap, HashMap};
+use std::time::Instant;
+
+use crate::config::MetricsConfig;
+
+/// Histogram bucket upper bounds in milliseconds (plus +Inf)
+pub const BUCKETS_MS: [u64; 11] = [5, 10, 25, 50, 100, 150, 200, 300, 400, 500, 1000];
+
+/// Games tracked at once; the least recently seen is dropped when a game's /end never arrives
+const MAX_TRACKED_GAMES: usize = 256;
+
+/// Latency histogram of one endpoint
+#[derive(Debug, Clone, Default)]
+pub struct Histogram {
+    /// Observations per bucket of BUCKETS_MS, the last one for +Inf (not cumulative)
+    counts: [u64; BUCKETS_MS.len() + 1],
+    sum_ms: f64,
+    count: u64,
+}
+
+impl Histogram {
+    pub fn observe(&mut self, elapsed_ms: f64) {
+        let bucket = BUCKETS_MS.iter().position(|&bound| elapsed_ms <= bound as f64).unwrap_or(BUCKETS_MS.len());
+        self.counts[bucket] += 1;
+        self.sum_ms += elapsed_ms;
+        self.count += 1;
+    }
+}
+
+/// Identifies the game of a parsed /move request (see RawGameState)
+#[derive(Debug, Clone, Default)]
+pub struct MoveContext(pub Option<(String, i32, u32)>);
+
+/// When the fairing first saw the request
+struct RequestStart(Instant);
+
+/// /move handler times of one game
+#[derive(Debug, Clone)]
+struct GameLatency {
+    timeout_ms: u32,
+    handler_ms: Vec<u64>,
+    /// (turn, handler ms) over the slow-turn threshold
+    slow_turns: Vec<(i32, u64)>,
+    last_seen: Instant,
+}
+
+/// Latency summary of a finished game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameLatencySummary {
+    pub moves: usize,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub max_ms: u64,
+    pub timeout_ms: u32,
+    pub slow_turns: Vec<(i32, u64)>,
+}
+
+impl GameLatencySummary {
+    fn from_game(game: &GameLatency) -> Self {
+        let mut sorted = game.handler_ms.clone();
+        sorted.sort_unstable();
+        let percentile = |p: usize| sorted.get((sorted.len() * p / 100).min(sorted.len().saturating_sub(1))).copied().unwrap_or(0);
+        GameLatencySummary {
+            moves: sorted.len(),
+            p50_ms: percentile(50),
+            p95_ms: percentile(95),
+            max_ms: sorted.last().copied().unwrap_or(0),
+            timeout_ms: game.timeout_ms,
+            slow_turns: game.slow_turns.clone(),
+        }
+    }
+
+    /// One-line summary, e.g. "143 moves, handler p50 312ms, p95 389ms, max 402ms, 0 slow
+    /// turn(s) of the 500ms timeout"
+    pub fn describe(&self) -> String {
+        let slow: Vec<String> = self.slow_turns.iter().map(|(turn, ms)| format!("{} ({}ms)", turn, ms)).collect();
+        format!(
+            "{} moves, handler p50 {}ms, p95 {}ms, max {}ms, {} slow turn(s) of the {}ms timeout{}",
+            self.moves,
+            self.p50_ms,
+            self.p95_ms,
+            self.max_ms,
+            self.slow_turns.len(),
+            self.timeout_ms,
+            if slow.is_empty() { String::new() } else { format!(": {}", slow.join(", ")) }
+        )
+    }
+}
+
+/// Per-endpoint histograms and per-game /move latencies (Rocket managed state)
+pub struct LatencyMetrics {
+    slow_turn_fraction: f64,
+    endpoints: Mutex<BTreeMap<String, Histogram>>,
+    games: Mutex<HashMap<String, GameLatency>>,
+    slow_turns_total: Mutex<u64>,
+}
+
+impl LatencyMetrics {
+    pub fn new(config: &MetricsConfig) -> Self {
+        LatencyMetrics {
+            slow_turn_fraction: config.slow_turn_fraction,
+            endpoints: Mutex::new(BTreeMap::new()),
+            games: Mutex::new(HashMap::new()),
+            slow_turns_total: Mutex::new(0),
+        }
+    }
+
+    /// Adds one handler time to the endpoint's histogram
+    pub fn record(&self, endpoint: &str, elapsed_ms: f64) {
+        self.endpoints.lock().entry(endpoint.to_string()).or_default().observe(elapsed_ms);
+    }
+
+    /// Tracks the handler time of a /move; returns true if it exceeded the slow-turn threshold
+    pub fn record_move(&self, game_id: &str, turn: i32, timeout_ms: u32, handler_ms: u64) -> bool {
+        let mut games = self.games.lock();
+        if !games.contains_key(game_id) && games.len() >= MAX_TRACKED_GAMES {
+            let stalest = games.iter().min_by_key(|(_, game)| game.last_seen).map(|(id, _)| id.clone());
+            if let Some(id) = stalest {
+                games.remove(&id);
+            }
+        }
+        let game = games.entry(game_id.to_string()).or_insert_with(|| GameLatency {
+            timeout_ms,
+            handler_ms: Vec::new(),
+            slow_turns: Vec::new(),
+            last_seen: Instant::now(),
+        });
+        game.timeout_ms = timeout_ms;
+        game.last_seen = Instant::now();
+        game.handler_ms.push(handler_ms);
+
+        let slow = timeout_ms > 0 && handler_ms as f64 > timeout_ms as f64 * self.slow_turn_fraction;
+        if slow {
+            game.slow_turns.push((turn, handler_ms));
+            *self.slow_turns_total.lock() += 1;
+        }
+        slow
+    }
+
+    /// Stops tracking a game and returns its latency summary (None if it served no /move)
+    pub fn finish_game(&self, game_id: &str) -> Option<GameLatencySummary> {
+        self.games.lock().remove(game_id).map(|game| GameLatencySummary::from_game(&game))
+    }
+
+    /// Logs the summary of a finished game (a warning if any turn was slow)
+    pub fn log_game_end(&self, game_id: &str) {
+        if let Some(summary) = self.finish_game(game_id) {
+            if summary.slow_turns.is_empty() {
+                info!("Game {} latency: {}", game_id, summary.describe());
+            } else {
+                warn!("Game {} latency: {}", game_id, summary.describe());
+            }
+        }
+    }
+
+    /// Histograms and slow-turn counter in the Prometheus text exposition format
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+        out.push_str("# HELP battlesnake_handler_latency_ms Handler latency per endpoint in milliseconds\n");
+        out.push_str("# TYPE battlesnake_handler_latency_ms histogram\n");
+        for (endpoint, histogram) in self.endpoints.lock().iter() {
+            let mut cumulative = 0;
+            for (idx, count) in histogram.counts.iter().enumerate() {
+                cumulative += count;
+                let bound = BUCKETS_MS.get(idx).map_or("+Inf".to_string(), |bound| bound.to_string());
+                out.push_str(&format!(
+                    "battlesnake_handler_latency_ms_bucket{{endpoint=\"{}\",le=\"{}\"}} {}\n",
+                    endpoint, bound, cumulative
+                ));
+            }
+            out.push_str(&format!("battlesnake_handler_latency_ms_sum{{endpoint=\"{}\"}} {:.3}\n", endpoint, histogram.sum_ms));
+            out.push_str(&format!("battlesnake_handler_latency_ms_count{{endpoint=\"{}\"}} {}\n", endpoint, histogram.count));
+        }
+        out.push_str("# HELP battlesnake_slow_turns_total /move responses over metrics.slow_turn_fraction of the game timeout\n");
+        out.push_str("# TYPE battlesnake_slow_turns_total counter\n");
+        out.push_str(&format!("battlesnake_slow_turns_total {}\n", *self.slow_turns_total.lock()));
+        out
+    }
+}
+
+/// Fairing timing every request into the managed LatencyMetrics
+pub struct LatencyFairing;
+
+#[rocket::async_trait]
+impl Fairing for LatencyFairing {
+    fn info(&self) -> Info {
+        Info { name: "Handler Latency Metrics", kind: Kind::Request | Kind::Response }
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _data: &mut Data<'_>) {
+        req.local_cache(|| RequestStart(Instant::now()));
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, _res: &mut Response<'r>) {
+        let metrics = match req.rocket().state::<LatencyMetrics>() {
+            Some(metrics) => metrics,
+            None => return,
+        };
+        let elapsed = req.local_cache(|| RequestStart(Instant::now())).0.elapsed();
+        let endpoint = req.route().map_or_else(|| "unmatched".to_string(), |route| route.uri.to_string());
+        metrics.record(&endpoint, elapsed.as_secs_f64() * 1000.0);
+
+        if let MoveContext(Some((game_id, turn, timeout_ms))) = req.local_cache(MoveContext::default) {
+            let handler_ms = elapsed.as_millis() as u64;
+            if metrics.record_move(game_id, *turn, *timeout_ms, handler_ms) {
+                warn!(
+                    "Turn {}: SLOW RESPONSE {}ms for a {}ms timeout (game {})",
+                    turn, handler_ms, timeout_ms, game_id
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics() -> LatencyMetrics {
+        LatencyMetrics::new(&MetricsConfig { enabled: true, slow_turn_fraction: 0.9 })
+    }
+
+    #[test]
+    fn test_histogram_is_cumulative_per_endpoint() {
+        let metrics = metrics();
+        metrics.record("/move", 3.0);
+        metrics.record("/move", 120.0);
+        metrics.record("/move", 2000.0);
+        metrics.record("/start", 1.0);
+
+        let text = metrics.render_prometheus();
+        assert!(text.contains("battlesnake_handler_latency_ms_bucket{endpoint=\"/move\",le=\"5\"} 1\n"));
+        assert!(text.contains("battlesnake_handler_latency_ms_bucket{endpoint=\"/move\",le=\"150\"} 2\n"));
+        assert!(text.contains("battlesnake_handler_latency_ms_bucket{endpoint=\"/move\",le=\"+Inf\"} 3\n"));
+        assert!(text.contains("battlesnake_handler_latency_ms_count{endpoint=\"/start\"} 1\n"));
+    }
+
+    #[test]
+    fn test_slow_turns_are_flagged_and_summarized_at_game_end() {
+        let metrics = metrics();
+        assert!(!metrics.record_move("g", 0, 500, 300));
+        assert!(!metrics.record_move("g", 1, 500, 450), "exactly 90% is still in time");
+        assert!(metrics.record_move("g", 2, 500, 470));
+        assert!(!metrics.record_move("other", 0, 0, 900), "no timeout announced");
+
+        let summary = metrics.finish_game("g").unwrap();
+        assert_eq!((summary.moves, summary.p50_ms, summary.max_ms), (3, 450, 470));
+        assert_eq!(summary.slow_turns, vec![(2, 470)]);
+        assert!(summary.describe().ends_with("1 slow turn(s) of the 500ms timeout: 2 (470ms)"));
+        assert!(metrics.finish_game("g").is_none());
+        assert!(metrics.render_prometheus().contains("battlesnake_slow_turns_total 1\n"));
+    }
+}
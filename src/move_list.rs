@@ -0,0 +1,136 @@
+// Fixed-capacity move list
+//
+// A snake never has more than four moves, yet move generation and ordering used to hand out
+// a Vec<Direction> per search node (pooled in SearchScratch to dodge the allocator). MoveList
+// keeps up to four directions inline: it is Copy, lives on the stack and derefs to a slice,
+// so call sites index, iterate, search and sort it in place as they did the Vec.
+
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+use crate::types::Direction;
+
+/// Up to four moves, in insertion order
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct MoveList {
+    moves: [Direction; MoveList::CAPACITY],
+    len: u8,
+}
+
+impl MoveList {
+    /// One move per direction
+    pub const CAPACITY: usize = 4;
+
+    pub const fn new() -> Self {
+        MoveList { moves: [Direction::Up; MoveList::CAPACITY], len: 0 }
+    }
+
+    /// Appends `mv`; panics if the list already holds CAPACITY moves
+    pub fn push(&mut self, mv: Direction) {
+        assert!((self.len as usize) < Self::CAPACITY, "MoveList holds at most {} moves", Self::CAPACITY);
+        self.moves[self.len as usize] = mv;
+        self.len += 1;
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Keeps the first `len` moves
+    pub fn truncate(&mut self, len: usize) {
+        self.len = self.len.min(len as u8);
+    }
+
+    /// Keeps the moves for which `keep` returns true, in order
+    pub fn retain(&mut self, mut keep: impl FnMut(&Direction) -> bool) {
+        let mut kept = 0;
+        for idx in 0..self.len as usize {
+            let mv = self.moves[idx];
+            if keep(&mv) {
+                self.moves[kept] = mv;
+                kept += 1;
+            }
+        }
+        self.len = kept as u8;
+    }
+}
+
+impl Default for MoveList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for MoveList {
+    type Target = [Direction];
+
+    fn deref(&self) -> &[Direction] {
+        &self.moves[..self.len as usize]
+    }
+}
+
+impl DerefMut for MoveList {
+    fn deref_mut(&mut self) -> &mut [Direction] {
+        &mut self.moves[..self.len as usize]
+    }
+}
+
+impl fmt::Debug for MoveList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Extend<Direction> for MoveList {
+    fn extend<I: IntoIterator<Item = Direction>>(&mut self, moves: I) {
+        for mv in moves {
+            self.push(mv);
+        }
+    }
+}
+
+impl std::iter::FromIterator<Direction> for MoveList {
+    fn from_iter<I: IntoIterator<Item = Direction>>(moves: I) -> Self {
+        let mut list = MoveList::new();
+        list.extend(moves);
+        list
+    }
+}
+
+impl IntoIterator for MoveList {
+    type Item = Direction;
+    type IntoIter = std::iter::Take<std::array::IntoIter<Direction, { MoveList::CAPACITY }>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIterator::into_iter(self.moves).take(self.len as usize)
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a Direction;
+    type IntoIter = std::slice::Iter<'a, Direction>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_move_list_behaves_like_a_short_vec() {
+        let mut moves: MoveList = [Direction::Left, Direction::Up, Direction::Right].iter().copied().collect();
+        assert_eq!((moves.len(), moves[1], moves.first()), (3, Direction::Up, Some(&Direction::Left)));
+
+        moves.retain(|&mv| mv != Direction::Up);
+        moves.push(Direction::Down);
+        assert_eq!(&moves[..], &[Direction::Left, Direction::Right, Direction::Down]);
+
+        // Sorted in place through the slice
+        moves.sort_by_key(|mv| mv.as_str());
+        assert_eq!(moves.into_iter().collect::<Vec<_>>(), vec![Direction::Down, Direction::Left, Direction::Right]);
+        assert_eq!(format!("{:?}", MoveList::new()), "[]");
+    }
+}
@@ -0,0 +1,148 @@
+// Value network backend (cargo feature "nn")
+//
+// A small fully connected network over the state tensor of dataset.rs, loaded from a JSON
+// weights file so no inference runtime is needed. The file lists the input shape and the
+// layers in order; each layer maps its inputs to `bias.len()` outputs with `weights` given
+// row by row (one row per output) and an activation. The last layer has a single output,
+// squashed by tanh into a value in [-1, 1]:
+//
+//   { "size": 11, "channels": 9,
+//     "layers": [ { "weights": [[...], ...], "bias": [...], "activation": "relu" },
+//                 { "weights": [[...]], "bias": [0.0], "activation": "linear" } ] }
+//
+// A batch is evaluated layer by layer, so every weight row is read once per batch rather
+// than once per position.
+
+use serde::Deserialize;
+use std::fs;
+
+use crate::dataset;
+use crate::evaluator::ValueModel;
+use crate::types::Board;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Activation {
+    Relu,
+    Tanh,
+    Linear,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Layer {
+    pub weights: Vec<Vec<f32>>,
+    pub bias: Vec<f32>,
+    pub activation: Activation,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ModelFile {
+    size: usize,
+    channels: usize,
+    layers: Vec<Layer>,
+}
+
+/// Value network loaded from a JSON weights file
+#[derive(Debug)]
+pub struct ValueNetwork {
+    size: usize,
+    layers: Vec<Layer>,
+}
+
+impl ValueNetwork {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| format!("Failed to read model {}: {}", path, e))?;
+        Self::from_json(&text).map_err(|e| format!("Model {}: {}", path, e))
+    }
+
+    /// Parses a model and checks that its layers chain from the state tensor to one value
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        let model: ModelFile = serde_json::from_str(text).map_err(|e| format!("invalid JSON: {}", e))?;
+        if model.channels != dataset::CHANNELS {
+            return Err(format!("expects {} input channels, the state tensor has {}", model.channels, dataset::CHANNELS));
+        }
+        let mut inputs = model.channels * model.size * model.size;
+        for (i, layer) in model.layers.iter().enumerate() {
+            if layer.weights.len() != layer.bias.len() || layer.weights.iter().any(|row| row.len() != inputs) {
+                return Err(format!("layer {} must have {} bias values and rows of {} weights", i, layer.weights.len(), inputs));
+            }
+            inputs = layer.bias.len();
+        }
+        if model.layers.is_empty() || inputs != 1 {
+            return Err(format!("the last layer must have 1 output, not {}", inputs));
+        }
+        Ok(ValueNetwork { size: model.size, layers: model.layers })
+    }
+
+    /// Forward pass of a batch of input vectors
+    fn forward(&self, mut batch: Vec<Vec<f32>>) -> Vec<f32> {
+        for layer in &self.layers {
+            let mut outputs = vec![Vec::with_capacity(layer.bias.len()); batch.len()];
+            for (row, &bias) in layer.weights.iter().zip(&layer.bias) {
+                for (input, output) in batch.iter().zip(outputs.iter_mut()) {
+                    let sum = bias + row.iter().zip(input).map(|(w, x)| w * x).sum::<f32>();
+                    output.push(match layer.activation {
+                        Activation::Relu => sum.max(0.0),
+                        Activation::Tanh => sum.tanh(),
+                        Activation::Linear => sum,
+                    });
+                }
+            }
+            batch = outputs;
+        }
+        batch.into_iter().map(|output| output[0].tanh()).collect()
+    }
+}
+
+impl ValueModel for ValueNetwork {
+    fn values(&self, positions: &[Board]) -> Vec<Option<f32>> {
+        let encoded: Vec<Option<Vec<f32>>> = positions.iter().map(|board| dataset::encode(board, self.size)).collect();
+        let mut values = self.forward(encoded.iter().flatten().cloned().collect()).into_iter();
+        encoded.iter().map(|input| input.as_ref().and_then(|_| values.next())).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board_builder::BoardBuilder;
+
+    /// 1x1 board model: one hidden unit reading our head, then the output
+    fn tiny_model(hidden_activation: &str) -> String {
+        let mut weights = vec![0.0; dataset::CHANNELS];
+        weights[dataset::CH_OUR_HEAD] = 2.0;
+        serde_json::json!({
+            "size": 1,
+            "channels": dataset::CHANNELS,
+            "layers": [
+                { "weights": [weights], "bias": [-1.0], "activation": hidden_activation },
+                { "weights": [[0.5]], "bias": [0.0], "activation": "linear" },
+            ]
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_forward_pass_and_unreadable_boards() {
+        let model = ValueNetwork::from_json(&tiny_model("relu")).unwrap();
+        let fits = BoardBuilder::new(1, 1).snake("us", &[(0, 0)]).build();
+        let too_large = BoardBuilder::new(2, 1).snake("us", &[(0, 0)]).build();
+        let values = model.values(&[fits.clone(), too_large, fits]);
+        // relu(2 * 1 - 1) = 1, then tanh(0.5 * 1)
+        assert_eq!(values, vec![Some(0.5f32.tanh()), None, Some(0.5f32.tanh())]);
+    }
+
+    #[test]
+    fn test_bad_models_are_rejected() {
+        assert!(ValueNetwork::from_json(&tiny_model("sigmoid")).is_err());
+        let two_outputs = tiny_model("relu").replace("[[0.5]]", "[[0.5], [0.5]]").replace("\"bias\":[0.0]", "\"bias\":[0.0,0.0]");
+        assert!(ValueNetwork::from_json(&two_outputs).unwrap_err().contains("1 output"));
+        assert!(ValueNetwork::load("no/such/model.json").is_err());
+
+        let mut config = crate::config::Config::default_hardcoded();
+        config.strategy.evaluator = "nn".to_string();
+        config.nn.model_path = "no/such/model.json".to_string();
+        crate::evaluator::attach(&mut config);
+        assert!(config.evaluator.is_none(), "a model that fails to load leaves the handcrafted evaluation");
+    }
+}
@@ -0,0 +1,181 @@
+// Board queries and the cached occupancy grid
+//
+// "Is anything on this cell" used to be answered by looping over every snake body (and the
+// food and hazard lists) at each call site. The Board methods here answer it in one place. By
+// default they scan, which is the right cost for the search's short-lived child boards; a
+// board that is queried many times without changing (the root of a search) calls
+// Board::cache_occupancy once and every query becomes a grid lookup. The cache is a snapshot:
+// clones start without it, and code that mutates a cached board must refresh or clear it.
+
+use crate::types::{Battlesnake, Board, Coord};
+
+/// What the occupancy grid knows about one cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Empty,
+    /// One alive snake's body; `from_tail` counts its segments behind the cell's head-most
+    /// segment (0 for the tail), i.e. the turns until the cell frees up
+    Snake { idx: u16, from_tail: u16 },
+    /// Bodies of several snakes overlap (a collision awaiting elimination); queries scan
+    Shared,
+}
+
+/// Per-cell snapshot of a board (see Board::cache_occupancy)
+#[derive(Debug, Clone)]
+pub struct Occupancy {
+    cells: Vec<Cell>,
+    food: Vec<bool>,
+    hazard: Vec<bool>,
+}
+
+fn is_alive(snake: &Battlesnake) -> bool {
+    snake.health > 0 && !snake.body.is_empty()
+}
+
+impl Board {
+    /// Row-major index of `c` (None if off the board)
+    #[inline]
+    pub fn cell_index(&self, c: Coord) -> Option<usize> {
+        if c.x < 0 || c.y < 0 || c.x >= self.width || c.y >= self.height as i32 {
+            None
+        } else {
+            Some((c.y * self.width + c.x) as usize)
+        }
+    }
+
+    /// Builds the occupancy grid; queries use it until the board is cloned or cleared
+    pub fn cache_occupancy(&mut self) {
+        let len = (self.width.max(0) as usize) * self.height as usize;
+        let mut occupancy = Occupancy { cells: vec![Cell::Empty; len], food: vec![false; len], hazard: vec![false; len] };
+        for (idx, snake) in self.snakes.iter().enumerate().filter(|(_, s)| is_alive(s)) {
+            // Tail first, so a stacked segment ends up with its head-most distance
+            for (from_tail, &segment) in snake.body.iter().rev().enumerate() {
+                let Some(i) = self.cell_index(segment) else { continue };
+                occupancy.cells[i] = match occupancy.cells[i] {
+                    Cell::Snake { idx: other, .. } if other as usize != idx => Cell::Shared,
+                    Cell::Shared => Cell::Shared,
+                    _ => Cell::Snake { idx: idx as u16, from_tail: from_tail as u16 },
+                };
+            }
+        }
+        for (flags, cells) in [(&mut occupancy.food, &self.food), (&mut occupancy.hazard, &self.hazards)] {
+            for &c in cells {
+                if let Some(i) = self.cell_index(c) {
+                    flags[i] = true;
+                }
+            }
+        }
+        self.occupancy = Some(occupancy);
+    }
+
+    /// Drops the occupancy grid (required after mutating a cached board)
+    pub fn clear_occupancy(&mut self) {
+        self.occupancy = None;
+    }
+
+    /// Alive snakes (positive health and a body)
+    pub fn alive_snakes(&self) -> impl Iterator<Item = &Battlesnake> + '_ {
+        self.snakes.iter().filter(|s| is_alive(s))
+    }
+
+    /// Index of the snake with this id
+    pub fn snake_index(&self, id: &str) -> Option<usize> {
+        self.snakes.iter().position(|s| s.id == id)
+    }
+
+    /// The snake with this id
+    pub fn our_snake(&self, id: &str) -> Option<&Battlesnake> {
+        self.snakes.iter().find(|s| s.id == id)
+    }
+
+    /// True if food lies on `c`
+    pub fn is_food(&self, c: Coord) -> bool {
+        match &self.occupancy {
+            Some(occupancy) => self.cell_index(c).is_some_and(|i| occupancy.food[i]),
+            None => self.food.contains(&c),
+        }
+    }
+
+    /// True if at least one hazard lies on `c`
+    pub fn is_hazard(&self, c: Coord) -> bool {
+        match &self.occupancy {
+            Some(occupancy) => self.cell_index(c).is_some_and(|i| occupancy.hazard[i]),
+            None => self.hazards.contains(&c),
+        }
+    }
+
+    /// Index of an alive snake whose body covers `c` (the first in board order if several do)
+    pub fn snake_at(&self, c: Coord) -> Option<usize> {
+        if let Some(occupancy) = &self.occupancy {
+            match self.cell_index(c).map(|i| occupancy.cells[i]) {
+                None | Some(Cell::Empty) => return None,
+                Some(Cell::Snake { idx, .. }) => return Some(idx as usize),
+                Some(Cell::Shared) => {}
+            }
+        }
+        self.snakes.iter().position(|s| is_alive(s) && s.body.contains(&c))
+    }
+
+    /// True if an alive snake's body covers `c`
+    pub fn occupied(&self, c: Coord) -> bool {
+        self.snake_at(c).is_some()
+    }
+
+    /// True if `c` is still covered after `tail_offset` turns (each snake's last `tail_offset`
+    /// segments have moved away by then), ignoring the snakes for which `ignore(idx)` is true
+    pub fn blocked(&self, c: Coord, tail_offset: usize, ignore: impl Fn(usize) -> bool) -> bool {
+        if let Some(occupancy) = &self.occupancy {
+            match self.cell_index(c).map(|i| occupancy.cells[i]) {
+                None | Some(Cell::Empty) => return false,
+                Some(Cell::Snake { idx, from_tail }) => return from_tail as usize >= tail_offset && !ignore(idx as usize),
+                Some(Cell::Shared) => {}
+            }
+        }
+        self.snakes.iter().enumerate().any(|(idx, snake)| {
+            snake.health > 0
+                && !ignore(idx)
+                && snake.body[..snake.body.len().saturating_sub(tail_offset)].contains(&c)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board_builder::parse_board;
+    use crate::types::Coord;
+
+    #[test]
+    fn test_cached_and_scanned_queries_agree() {
+        let mut board = parse_board(
+            "
+            . . * . .
+            . a a a> .
+            . a # . b>
+            . . . b b
+            . . . . .
+        ",
+        )
+        .unwrap();
+        board.snakes[1].health = 0;
+        let scanned = board.clone();
+        board.cache_occupancy();
+
+        for y in -1..6 {
+            for x in -1..6 {
+                let c = Coord { x, y };
+                assert_eq!(board.snake_at(c), scanned.snake_at(c), "{:?}", c);
+                assert_eq!((board.is_food(c), board.is_hazard(c)), (scanned.is_food(c), scanned.is_hazard(c)));
+                for offset in 0..4 {
+                    assert_eq!(board.blocked(c, offset, |_| false), scanned.blocked(c, offset, |_| false));
+                }
+            }
+        }
+
+        let tail = Coord { x: 1, y: 2 };
+        assert!(board.occupied(tail) && !board.blocked(tail, 1, |_| false), "the tail moves away");
+        assert!(!board.occupied(Coord { x: 4, y: 2 }), "dead snakes are not on the board");
+        assert_eq!(board.alive_snakes().count(), 1);
+        assert_eq!(board.cell_index(Coord { x: 2, y: 1 }), Some(7));
+        assert!(board.clone().occupancy.is_none(), "clones start without the cache");
+    }
+}
@@ -0,0 +1,240 @@
+// Duel opening repertoire
+//
+// Standard duel spawns are symmetric and the first ~10 turns decide who controls the center.
+// At /start we classify our spawn quadrant and the food layout, then pick a short waypoint
+// plan from a small library. The search is nudged toward the next waypoint with a light root
+// bonus; once following the plan becomes unsafe it is dropped and normal search takes over.
+
+use crate::config::Config;
+use crate::types::{Battlesnake, Board, Coord};
+
+/// Which part of the board we spawned in, relative to the center
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpawnQuadrant {
+    BottomLeft,
+    BottomRight,
+    TopLeft,
+    TopRight,
+    /// On a center line (mid-edge spawn or exact center)
+    Axis,
+}
+
+/// Coarse classification of the starting food around us
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FoodLayout {
+    /// Center food plus a food item close to our spawn (standard ruleset)
+    CenterAndNear,
+    CenterOnly,
+    NearOnly,
+    /// No food worth planning around
+    Sparse,
+}
+
+/// A short waypoint plan for the opening of a duel
+#[derive(Debug, Clone)]
+pub struct OpeningPlan {
+    pub quadrant: SpawnQuadrant,
+    pub layout: FoodLayout,
+    pub waypoints: Vec<Coord>,
+    next_waypoint: usize,
+    abandoned: bool,
+}
+
+impl OpeningPlan {
+    /// Builds the opening plan for a duel, or None if this is not a duel or openings are disabled
+    pub fn for_duel(board: &Board, you: &Battlesnake, config: &Config) -> Option<Self> {
+        if !config.opening.enabled || board.snakes.len() != 2 || you.body.is_empty() {
+            return None;
+        }
+
+        let head = you.body[0];
+        let quadrant = classify_quadrant(head, board.width, board.height as i32);
+        let layout = classify_food(board, head, config);
+        let waypoints = plan_waypoints(quadrant, layout, board, head, config);
+
+        if waypoints.is_empty() {
+            return None;
+        }
+
+        Some(OpeningPlan {
+            quadrant,
+            layout,
+            waypoints,
+            next_waypoint: 0,
+            abandoned: false,
+        })
+    }
+
+    /// Returns the waypoint we should currently be heading for
+    /// Skips waypoints already reached; None once the plan is finished, expired or abandoned
+    pub fn current_target(&mut self, head: Coord, turn: i32, config: &Config) -> Option<Coord> {
+        if self.abandoned || turn > config.opening.max_turn {
+            return None;
+        }
+
+        while self.next_waypoint < self.waypoints.len() && self.waypoints[self.next_waypoint] == head {
+            self.next_waypoint += 1;
+        }
+
+        self.waypoints.get(self.next_waypoint).copied()
+    }
+
+    /// Drops the plan for the rest of the game (following it became unsafe)
+    pub fn abandon(&mut self) {
+        self.abandoned = true;
+    }
+}
+
+/// Classifies a spawn position into a quadrant relative to the board center
+pub fn classify_quadrant(head: Coord, width: i32, height: i32) -> SpawnQuadrant {
+    let center_x = width / 2;
+    let center_y = height / 2;
+
+    match (head.x.cmp(&center_x), head.y.cmp(&center_y)) {
+        (std::cmp::Ordering::Less, std::cmp::Ordering::Less) => SpawnQuadrant::BottomLeft,
+        (std::cmp::Ordering::Greater, std::cmp::Ordering::Less) => SpawnQuadrant::BottomRight,
+        (std::cmp::Ordering::Less, std::cmp::Ordering::Greater) => SpawnQuadrant::TopLeft,
+        (std::cmp::Ordering::Greater, std::cmp::Ordering::Greater) => SpawnQuadrant::TopRight,
+        _ => SpawnQuadrant::Axis,
+    }
+}
+
+/// Classifies the food layout around our spawn
+pub fn classify_food(board: &Board, head: Coord, config: &Config) -> FoodLayout {
+    let center = board_center(board);
+    let has_center = board.food.contains(&center);
+    let has_near = nearest_food(board, head, config).is_some();
+
+    match (has_center, has_near) {
+        (true, true) => FoodLayout::CenterAndNear,
+        (true, false) => FoodLayout::CenterOnly,
+        (false, true) => FoodLayout::NearOnly,
+        (false, false) => FoodLayout::Sparse,
+    }
+}
+
+/// Plan library: waypoints keyed by (spawn quadrant, food layout)
+/// The staging square sits one step off the center on our side, so we contest the center
+/// without running head-first into the opponent doing the same from the mirrored spawn
+fn plan_waypoints(
+    quadrant: SpawnQuadrant,
+    layout: FoodLayout,
+    board: &Board,
+    head: Coord,
+    config: &Config,
+) -> Vec<Coord> {
+    let center = board_center(board);
+    let staging = staging_square(quadrant, center, head);
+    let near = nearest_food(board, head, config);
+
+    let mut waypoints = match layout {
+        FoodLayout::CenterAndNear => vec![near.unwrap_or(staging), staging, center],
+        FoodLayout::CenterOnly => vec![staging, center],
+        FoodLayout::NearOnly => vec![near.unwrap_or(staging), staging],
+        FoodLayout::Sparse => vec![staging],
+    };
+
+    waypoints.dedup();
+    waypoints.retain(|&wp| wp != head);
+    waypoints
+}
+
+/// Square adjacent to the center on our side of the board
+fn staging_square(quadrant: SpawnQuadrant, center: Coord, head: Coord) -> Coord {
+    match quadrant {
+        SpawnQuadrant::BottomLeft => Coord { x: center.x - 1, y: center.y - 1 },
+        SpawnQuadrant::BottomRight => Coord { x: center.x + 1, y: center.y - 1 },
+        SpawnQuadrant::TopLeft => Coord { x: center.x - 1, y: center.y + 1 },
+        SpawnQuadrant::TopRight => Coord { x: center.x + 1, y: center.y + 1 },
+        SpawnQuadrant::Axis => Coord {
+            x: center.x + (head.x - center.x).signum(),
+            y: center.y + (head.y - center.y).signum(),
+        },
+    }
+}
+
+fn board_center(board: &Board) -> Coord {
+    Coord {
+        x: board.width / 2,
+        y: board.height as i32 / 2,
+    }
+}
+
+/// Closest non-center food within opening.near_food_distance of our head
+fn nearest_food(board: &Board, head: Coord, config: &Config) -> Option<Coord> {
+    let center = board_center(board);
+    board
+        .food
+        .iter()
+        .filter(|&&food| food != center)
+        .map(|&food| (food, food.manhattan(head)))
+        .filter(|&(_, dist)| dist <= config.opening.near_food_distance)
+        .min_by_key(|&(_, dist)| dist)
+        .map(|(food, _)| food)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snake(id: &str, body: Vec<Coord>) -> Battlesnake {
+        Battlesnake {
+            id: id.to_string(),
+            name: id.to_string(),
+            health: 100,
+            head: body[0],
+            length: body.len() as i32,
+            body,
+            latency: "0".to_string(),
+            shout: None,
+            squad: None,
+        }
+    }
+
+    fn standard_duel() -> Board {
+        Board {
+            height: 11,
+            width: 11,
+            food: vec![Coord { x: 0, y: 2 }, Coord { x: 10, y: 8 }, Coord { x: 5, y: 5 }],
+            snakes: vec![
+                snake("us", vec![Coord { x: 1, y: 1 }; 3]),
+                snake("them", vec![Coord { x: 9, y: 9 }; 3]),
+            ],
+            hazards: vec![],
+            occupancy: None,
+        }
+    }
+
+    #[test]
+    fn test_standard_spawn_plans_near_food_then_center() {
+        let config = Config::default_hardcoded();
+        let board = standard_duel();
+        let plan = OpeningPlan::for_duel(&board, &board.snakes[0], &config).expect("duel should get a plan");
+
+        assert_eq!(plan.quadrant, SpawnQuadrant::BottomLeft);
+        assert_eq!(plan.layout, FoodLayout::CenterAndNear);
+        assert_eq!(
+            plan.waypoints,
+            vec![Coord { x: 0, y: 2 }, Coord { x: 4, y: 4 }, Coord { x: 5, y: 5 }]
+        );
+    }
+
+    #[test]
+    fn test_plan_advances_and_expires() {
+        let config = Config::default_hardcoded();
+        let board = standard_duel();
+        let mut plan = OpeningPlan::for_duel(&board, &board.snakes[0], &config).unwrap();
+
+        assert_eq!(plan.current_target(Coord { x: 0, y: 2 }, 2, &config), Some(Coord { x: 4, y: 4 }));
+        assert_eq!(plan.current_target(Coord { x: 3, y: 4 }, config.opening.max_turn + 1, &config), None);
+    }
+
+    #[test]
+    fn test_no_plan_outside_duels() {
+        let config = Config::default_hardcoded();
+        let mut board = standard_duel();
+        board.snakes.push(snake("third", vec![Coord { x: 1, y: 9 }; 3]));
+
+        assert!(OpeningPlan::for_duel(&board, &board.snakes[0], &config).is_none());
+    }
+}
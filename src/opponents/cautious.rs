@@ -0,0 +1,28 @@
+// Cautious opponent: keeps out of head-to-head reach and away from small pockets
+
+use std::cmp::Reverse;
+
+use super::{cheapest_move, reachable_cells, Opponent};
+use crate::types::{Battlesnake, Board, Coord, Direction};
+
+/// Never steps next to the head of a snake at least as long (unless every move does), then
+/// prefers the move with the most reachable cells
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Cautious;
+
+/// True if a snake other than `you`, at least as long, could move its head onto `c`
+fn contested(board: &Board, you: &Battlesnake, c: Coord) -> bool {
+    board
+        .alive_snakes()
+        .any(|snake| snake.id != you.id && snake.length >= you.length && snake.head.manhattan(c) == 1)
+}
+
+impl Opponent for Cautious {
+    fn name(&self) -> &'static str {
+        "cautious"
+    }
+
+    fn choose(&mut self, board: &Board, you: &Battlesnake) -> Direction {
+        cheapest_move(board, you, |next| (contested(board, you, next), Reverse(reachable_cells(board, next))))
+    }
+}
@@ -0,0 +1,18 @@
+// Greedy food opponent: always heads for the nearest food, whatever is in the way
+
+use super::{cheapest_move, nearest_food_distance, Opponent};
+use crate::types::{Battlesnake, Board, Direction};
+
+/// Shortest Manhattan distance to food, ignoring every danger but walls and bodies
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GreedyFood;
+
+impl Opponent for GreedyFood {
+    fn name(&self) -> &'static str {
+        "greedy"
+    }
+
+    fn choose(&mut self, board: &Board, you: &Battlesnake) -> Direction {
+        cheapest_move(board, you, |next| nearest_food_distance(board, next))
+    }
+}
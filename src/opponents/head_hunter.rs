@@ -0,0 +1,26 @@
+// Aggressive head-hunter opponent: chases the head of the nearest shorter snake
+
+use super::{cheapest_move, nearest_food_distance, Opponent};
+use crate::types::{Battlesnake, Board, Direction};
+
+/// Closes in on the head of the nearest shorter snake (a head-to-head it would win); with
+/// no shorter snake on the board it eats to outgrow one
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeadHunter;
+
+impl Opponent for HeadHunter {
+    fn name(&self) -> &'static str {
+        "head-hunter"
+    }
+
+    fn choose(&mut self, board: &Board, you: &Battlesnake) -> Direction {
+        let prey = board
+            .alive_snakes()
+            .filter(|snake| snake.id != you.id && snake.length < you.length)
+            .min_by_key(|snake| snake.head.manhattan(you.head));
+        match prey {
+            Some(prey) => cheapest_move(board, you, |next| next.manhattan(prey.head)),
+            None => cheapest_move(board, you, |next| nearest_food_distance(board, next)),
+        }
+    }
+}
@@ -0,0 +1,223 @@
+// Scripted opponents
+//
+// Simple, predictable snakes to play our Bot against: the arena (arena::play_vs_opponents),
+// the local game runner (bin/play_local) and tests that need an opponent with a known
+// habit. An Opponent looks only at the board it is given and picks one move; it does not
+// search and does not use our Config, so changing our weights never changes how it plays.
+// Every implementation chooses among safe_moves (no wall, no body, tails that move away are
+// free) and falls back to rules::default_move when it has none.
+
+mod cautious;
+mod greedy_food;
+mod head_hunter;
+mod random_legal;
+mod wall_hugger;
+
+pub use cautious::Cautious;
+pub use greedy_food::GreedyFood;
+pub use head_hunter::HeadHunter;
+pub use random_legal::RandomLegal;
+pub use wall_hugger::WallHugger;
+
+use crate::rules;
+use crate::types::{Battlesnake, Board, Coord, Direction};
+
+/// A scripted snake
+pub trait Opponent: Send {
+    /// Short name, as accepted by by_name
+    fn name(&self) -> &'static str;
+
+    /// Move of `you` on `board`
+    fn choose(&mut self, board: &Board, you: &Battlesnake) -> Direction;
+}
+
+/// Names accepted by by_name
+pub const NAMES: [&str; 5] = ["greedy", "cautious", "head-hunter", "random", "wall"];
+
+/// Opponent by name; `seed` drives the random ones
+pub fn by_name(name: &str, seed: u64) -> Result<Box<dyn Opponent>, String> {
+    match name {
+        "greedy" => Ok(Box::new(GreedyFood)),
+        "cautious" => Ok(Box::new(Cautious)),
+        "head-hunter" => Ok(Box::new(HeadHunter)),
+        "random" => Ok(Box::new(RandomLegal::new(seed))),
+        "wall" => Ok(Box::new(WallHugger)),
+        other => Err(format!("Unknown opponent '{}' (expected one of {})", other, NAMES.join(", "))),
+    }
+}
+
+/// True if an alive snake's body still covers `c` after this turn's moves (tails move away
+/// unless the snake just ate, which stacks its last two segments)
+fn blocked_next_turn(board: &Board, c: Coord) -> bool {
+    board.alive_snakes().any(|snake| {
+        let len = snake.body.len();
+        let tail_stays = len >= 2 && snake.body[len - 1] == snake.body[len - 2];
+        let covered = if tail_stays { len } else { len - 1 };
+        snake.body[..covered].contains(&c)
+    })
+}
+
+fn on_board(board: &Board, c: Coord) -> bool {
+    board.cell_index(c).is_some()
+}
+
+/// Moves of `you` that stay on the board, off its neck and out of every body, in
+/// Direction::ALL order
+pub fn safe_moves(board: &Board, you: &Battlesnake) -> Vec<Direction> {
+    let Some(&head) = you.body.first() else { return Vec::new() };
+    let neck = you.body.get(1).copied().filter(|&neck| neck != head);
+    Direction::ALL
+        .iter()
+        .copied()
+        .filter(|dir| {
+            let next = dir.apply(&head);
+            Some(next) != neck && on_board(board, next) && !blocked_next_turn(board, next)
+        })
+        .collect()
+}
+
+/// The safe move with the lowest `cost` (the first in Direction::ALL order on ties), or the
+/// default move if there is no safe move
+fn cheapest_move<K: Ord>(board: &Board, you: &Battlesnake, cost: impl Fn(Coord) -> K) -> Direction {
+    safe_moves(board, you)
+        .into_iter()
+        .min_by_key(|dir| cost(dir.apply(&you.head)))
+        .unwrap_or_else(|| rules::default_move(&you.body))
+}
+
+/// Manhattan distance from `c` to the nearest food (0 without food)
+fn nearest_food_distance(board: &Board, c: Coord) -> i32 {
+    board.food.iter().map(|&food| c.manhattan(food)).min().unwrap_or(0)
+}
+
+/// Cells reachable from `start` through cells free next turn (start included, if free)
+fn reachable_cells(board: &Board, start: Coord) -> usize {
+    if !on_board(board, start) || blocked_next_turn(board, start) {
+        return 0;
+    }
+    let mut seen = vec![false; (board.width.max(0) as usize) * board.height as usize];
+    let mut queue = vec![start];
+    if let Some(i) = board.cell_index(start) {
+        seen[i] = true;
+    }
+    let mut next_idx = 0;
+    while next_idx < queue.len() {
+        let pos = queue[next_idx];
+        next_idx += 1;
+        for next in pos.neighbors() {
+            match board.cell_index(next) {
+                Some(i) if !seen[i] && !blocked_next_turn(board, next) => {
+                    seen[i] = true;
+                    queue.push(next);
+                }
+                _ => {}
+            }
+        }
+    }
+    queue.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board_builder::{parse_board, BoardBuilder};
+
+    fn snake<'a>(board: &'a Board, id: &str) -> &'a Battlesnake {
+        board.our_snake(id).unwrap()
+    }
+
+    #[test]
+    fn test_safe_moves_free_moving_tails_only() {
+        let board = BoardBuilder::new(5, 4).snake("a", &[(1, 1), (1, 2), (2, 2), (2, 1)]).build();
+        // Up is the neck, right is the tail (it moves away)
+        assert_eq!(safe_moves(&board, snake(&board, "a")), vec![Direction::Down, Direction::Left, Direction::Right]);
+        assert_eq!(reachable_cells(&board, Coord { x: 0, y: 1 }), 17);
+        assert!(by_name("nobody", 0).is_err());
+        for name in NAMES {
+            assert_eq!(by_name(name, 0).unwrap().name(), name);
+        }
+    }
+
+    #[test]
+    fn test_every_opponent_plays_its_habit() {
+        let board = parse_board(
+            "
+            . . . . . . .
+            . . . . . . .
+            * . . a> . . .
+            . . . a . . .
+            . . . a b b b>
+            . . . . . . .
+            . . . . . . .
+        ",
+        )
+        .unwrap();
+        let a = snake(&board, "a");
+        assert_eq!(GreedyFood.choose(&board, a), Direction::Left);
+        // b is as long as a, so there is nothing to hunt: the hunter eats instead
+        assert_eq!(HeadHunter.choose(&board, a), Direction::Left);
+        assert_eq!(WallHugger.choose(&board, a), Direction::Up);
+        let mut random = RandomLegal::new(3);
+        for _ in 0..20 {
+            assert!(safe_moves(&board, a).contains(&random.choose(&board, a)));
+        }
+
+        // The shorter b is in reach: the hunter steps next to its head (onto its tail)
+        let board = parse_board(
+            "
+            . . . . .
+            a a a a> .
+            . . . b b>
+            . . . . .
+        ",
+        )
+        .unwrap();
+        assert_eq!(HeadHunter.choose(&board, snake(&board, "a")), Direction::Down);
+
+        // Up and right are next to the longer b's head; the greedy snake does not care
+        let board = parse_board(
+            "
+            . . . . b
+            . . . b> b
+            . . a> . .
+            . . a . .
+            . . a . .
+        ",
+        )
+        .unwrap();
+        let a = snake(&board, "a");
+        assert_eq!(Cautious.choose(&board, a), Direction::Left);
+        assert_eq!(GreedyFood.choose(&board, a), Direction::Up);
+    }
+
+    #[test]
+    fn test_search_outruns_a_longer_head_hunter() {
+        use crate::bot::{Bot, HistoryTable, SearchLimits, SharedSearchState};
+        use crate::config::Config;
+        use std::collections::HashMap;
+        use std::sync::Arc;
+
+        let config = Config::default_hardcoded();
+        let settings = rules::RulesSettings::from_config(&config);
+        let mut board = BoardBuilder::new(11, 11)
+            .snake("us", &[(5, 5), (5, 4), (5, 3)])
+            .snake("hunter", &[(7, 5), (8, 5), (9, 5), (10, 5), (10, 4)])
+            .build();
+        let mut hunter = HeadHunter;
+
+        // Right would meet the hunter head-on next turn; it keeps closing in after that
+        for turn in 0..8 {
+            let you = snake(&board, "us").clone();
+            let shared = Arc::new(SharedSearchState::new());
+            let mut history = HistoryTable::new(11, 11);
+            let limits = SearchLimits::default().with_fixed_depth(Some(3));
+            Bot::compute_best_move_internal(&board, &you, turn, shared.clone(), &config, &mut history, limits);
+            let ours = Bot::index_to_direction(shared.get_best().0, &config);
+            let theirs = hunter.choose(&board, snake(&board, "hunter"));
+
+            let moves = HashMap::from([("us".to_string(), ours), ("hunter".to_string(), theirs)]);
+            rules::resolve_turn(&mut board, &moves, &settings);
+            assert!(board.our_snake("us").is_some(), "caught on turn {} playing {}", turn, ours.as_str());
+        }
+    }
+}
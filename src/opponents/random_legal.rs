@@ -0,0 +1,34 @@
+// Random opponent: any safe move
+
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::SeedableRng;
+
+use super::{safe_moves, Opponent};
+use crate::rules;
+use crate::types::{Battlesnake, Board, Direction};
+
+/// A uniformly random safe move (reproducible from the seed)
+#[derive(Debug, Clone)]
+pub struct RandomLegal {
+    rng: StdRng,
+}
+
+impl RandomLegal {
+    pub fn new(seed: u64) -> Self {
+        RandomLegal { rng: StdRng::seed_from_u64(seed) }
+    }
+}
+
+impl Opponent for RandomLegal {
+    fn name(&self) -> &'static str {
+        "random"
+    }
+
+    fn choose(&mut self, board: &Board, you: &Battlesnake) -> Direction {
+        safe_moves(board, you)
+            .choose(&mut self.rng)
+            .copied()
+            .unwrap_or_else(|| rules::default_move(&you.body))
+    }
+}
@@ -0,0 +1,19 @@
+// Wall-hugger opponent: stays as close to the edge of the board as it can
+
+use super::{cheapest_move, Opponent};
+use crate::types::{Battlesnake, Board, Direction};
+
+/// The safe move ending nearest to an edge of the board
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WallHugger;
+
+impl Opponent for WallHugger {
+    fn name(&self) -> &'static str {
+        "wall"
+    }
+
+    fn choose(&mut self, board: &Board, you: &Battlesnake) -> Direction {
+        let (width, height) = (board.width, board.height as i32);
+        cheapest_move(board, you, |next| next.x.min(next.y).min(width - 1 - next.x).min(height - 1 - next.y))
+    }
+}
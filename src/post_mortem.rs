@@ -0,0 +1,154 @@
+// Cause-of-death record written at /end
+//
+// analyze_deaths reconstructs a death from the debug log: the last logged board and move,
+// played against every opponent reply because the log never shows what came next. At /end
+// the bot holds what the log lacks: the final board, on which the surviving opponents'
+// heads show the moves they played. When we are gone from it, /end classifies the death
+// with analysis::analyze_death_observed and appends a PostMortem JSON line to
+// debug.post_mortem_file_path: the cause, the position we died from and the search results
+// of our last debug.post_mortem_turns moves, whose candidate tables show what else the
+// search considered. analyze_deaths --post-mortems reports these records as they are.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::Path;
+
+use crate::analysis::{self, DeathAnalysis, DeathCause};
+use crate::config::Config;
+use crate::game_summary::{self, GameStats};
+use crate::replay::ReplayEngine;
+use crate::schema::SearchResultV1;
+use crate::types::Board;
+
+/// How we died, appended as one JSON line at /end
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostMortem {
+    pub game_id: String,
+    pub timestamp: String,
+    /// Our snake's id
+    pub you: String,
+    /// Turn of the fatal move
+    pub turn: i32,
+    /// The fatal move, lowercase
+    #[serde(rename = "move")]
+    pub final_move: String,
+    pub cause: DeathCause,
+    /// DeathAnalysis::describe of the death
+    pub description: String,
+    pub fatal_replies: f32,
+    pub health: i32,
+    pub length: i32,
+    pub space: usize,
+    pub escape_routes: usize,
+    pub food_count: usize,
+    pub longest_opponent: i32,
+    /// The board we played the fatal move on
+    pub board: Board,
+    /// Search results of our last moves, oldest first, with their candidate tables
+    pub last_turns: Vec<SearchResultV1>,
+}
+
+impl PostMortem {
+    /// Post-mortem of the game that ended on `board`; None if `you` survived or never moved
+    pub fn build(game_id: &str, board: &Board, you: &str, stats: &GameStats, recent: &VecDeque<SearchResultV1>, config: &Config) -> Option<PostMortem> {
+        if board.our_snake(you).is_some() {
+            return None;
+        }
+        let (turn, prev, final_move) = stats.last_move()?;
+        let prev = game_summary::us_first(prev, you)?;
+        let death = analysis::analyze_death_observed(&prev, board, turn, final_move, config)?;
+
+        Some(PostMortem {
+            game_id: game_id.to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            you: you.to_string(),
+            turn,
+            final_move: final_move.as_str().to_string(),
+            description: death.describe(),
+            cause: death.cause,
+            fatal_replies: death.fatal_replies,
+            health: death.health,
+            length: death.length,
+            space: death.space,
+            escape_routes: death.escape_routes,
+            food_count: death.food_count,
+            longest_opponent: death.longest_opponent,
+            board: prev,
+            last_turns: recent.iter().cloned().collect(),
+        })
+    }
+
+    /// The recorded death as analysis reports it
+    pub fn death(&self) -> Result<DeathAnalysis, String> {
+        Ok(DeathAnalysis {
+            turn: self.turn,
+            final_move: ReplayEngine::parse_direction(&self.final_move)?,
+            cause: self.cause.clone(),
+            fatal_replies: self.fatal_replies,
+            health: self.health,
+            length: self.length,
+            space: self.space,
+            escape_routes: self.escape_routes,
+            food_count: self.food_count,
+            longest_opponent: self.longest_opponent,
+        })
+    }
+
+    /// Appends the post-mortem as one JSON line to `path`
+    pub fn append_to(&self, path: &Path) -> Result<(), String> {
+        let line = serde_json::to_string(self).map_err(|e| format!("Failed to serialize post-mortem: {}", e))?;
+        game_summary::append_line(path, &line)
+    }
+
+    /// Reads every post-mortem line of `path`
+    pub fn load(path: &Path) -> Result<Vec<PostMortem>, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(idx, line)| serde_json::from_str(line).map_err(|e| format!("{} line {}: {}", path.display(), idx + 1, e)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_summary::MoveSearch;
+    use crate::board_builder::BoardBuilder;
+    use crate::types::Direction;
+
+    #[test]
+    fn test_post_mortem_uses_the_final_board() {
+        let config = Config::default_hardcoded();
+        let mut stats = GameStats::default();
+        let mut recent = VecDeque::new();
+
+        // Moving up meets "big" head-to-head unless it turns away; "small" dodges either way
+        let before = BoardBuilder::new(7, 7)
+            .snake("us", &[(3, 2), (3, 1), (3, 0)])
+            .snake("big", &[(3, 4), (3, 5), (3, 6), (2, 6)])
+            .snake("small", &[(5, 3), (6, 3), (6, 2)])
+            .build();
+        stats.record_move(20, &before, "us", Direction::Up, MoveSearch { depth: 7, nodes: 900, margin_ms: 200 }, &config);
+        recent.push_back(SearchResultV1::new(20, Direction::Up, -40, 7, 180).with_candidates(&[(7, Direction::Up, -40), (7, Direction::Left, -90)]));
+
+        // The final board shows "big" came down onto our cell
+        let after = BoardBuilder::new(7, 7).snake("big", &[(3, 3), (3, 4), (3, 5), (3, 6)]).snake("small", &[(5, 4), (5, 3), (6, 3)]).build();
+        let post_mortem = PostMortem::build("g", &after, "us", &stats, &recent, &config).unwrap();
+        assert_eq!(post_mortem.cause, DeathCause::HeadToHead("big".to_string()));
+        assert_eq!(post_mortem.fatal_replies, 1.0, "only the eliminated snakes' replies are enumerated");
+        assert_eq!((post_mortem.turn, post_mortem.final_move.as_str(), post_mortem.board.snakes[0].id.as_str()), (20, "up", "us"));
+        assert_eq!(post_mortem.last_turns[0].candidates.as_ref().unwrap()[0].scores.len(), 2);
+
+        let line = serde_json::to_string(&post_mortem).unwrap();
+        let parsed: PostMortem = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed.death().unwrap().cause, post_mortem.cause);
+
+        // Nothing to report when we are still on the board
+        let alive = BoardBuilder::new(7, 7).snake("us", &[(3, 3), (3, 2), (3, 1)]).build();
+        assert!(PostMortem::build("g", &alive, "us", &stats, &recent, &config).is_none());
+    }
+}
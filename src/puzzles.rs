@@ -0,0 +1,211 @@
+// Tactical puzzles: positions with a known set of acceptable moves
+//
+// Death analyses used to end in a one-off fix. A puzzle captures the position instead: the
+// board, which snake we are, the moves that are acceptable and a tag naming the tactic
+// ("trap-escape", "forced-win", ...). The position is either API board JSON or a drawn grid
+// (board_builder.rs) with a health map. Puzzles live as one JSON file each in tests/puzzles,
+// are solved with a fixed-depth search (the same path as replay --depth), and run both as
+// a regression test (tests/puzzle_tests.rs) and through the puzzles binary, which reports
+// the pass rate per tag.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::board_builder::BoardBuilder;
+use crate::config::Config;
+use crate::replay::{ReplayEngine, ReplayResult};
+use crate::types::{Board, Direction};
+
+/// One puzzle file
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Puzzle {
+    pub name: String,
+    /// Category the pass rate is reported under
+    pub tag: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    /// Id of the snake to move
+    pub you: String,
+    #[serde(default)]
+    pub turn: i32,
+    /// Search depth for this puzzle (None = the runner's default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth: Option<u8>,
+    /// Moves that solve the puzzle ("up", "down", "left", "right")
+    pub acceptable: Vec<String>,
+    /// Why the bot currently fails this puzzle; the regression test tolerates the failure
+    /// and asks for the marker to be removed once the puzzle passes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub known_failure: Option<String>,
+    #[serde(flatten)]
+    pub position: Position,
+}
+
+/// Puzzle position: API board JSON, or grid rows in the board_builder notation plus the
+/// health of each snake (100 if not listed)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Position {
+    Board { board: Board },
+    Ascii {
+        ascii: Vec<String>,
+        #[serde(default)]
+        health: BTreeMap<String, i32>,
+    },
+}
+
+impl Position {
+    pub fn to_board(&self) -> Result<Board, String> {
+        match self {
+            Position::Board { board } => Ok(board.clone()),
+            Position::Ascii { ascii, health } => {
+                let mut builder = BoardBuilder::from_ascii(&ascii.join("\n"))?;
+                let board = builder.clone().build();
+                for (id, &value) in health {
+                    if !board.snakes.iter().any(|s| &s.id == id) {
+                        return Err(format!("health given for snake '{}', which is not on the board", id));
+                    }
+                    builder = builder.health(id, value);
+                }
+                Ok(builder.build())
+            }
+        }
+    }
+}
+
+/// Result of solving one puzzle
+#[derive(Debug, Clone)]
+pub struct PuzzleOutcome {
+    pub name: String,
+    pub tag: String,
+    pub chosen: Direction,
+    pub acceptable: Vec<Direction>,
+    pub depth: u8,
+    pub passed: bool,
+    pub known_failure: bool,
+}
+
+/// Pass count per tag
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TagSummary {
+    pub passed: usize,
+    pub total: usize,
+}
+
+impl TagSummary {
+    pub fn pass_rate(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.passed as f64 / self.total as f64 * 100.0
+    }
+}
+
+fn parse_direction(s: &str) -> Result<Direction, String> {
+    match s.to_lowercase().as_str() {
+        "up" => Ok(Direction::Up),
+        "down" => Ok(Direction::Down),
+        "left" => Ok(Direction::Left),
+        "right" => Ok(Direction::Right),
+        _ => Err(format!("Invalid direction '{}'", s)),
+    }
+}
+
+impl Puzzle {
+    /// Reads one puzzle file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let puzzle: Puzzle =
+            serde_json::from_str(&contents).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+        if puzzle.acceptable.is_empty() {
+            return Err(format!("{}: no acceptable moves listed", path.display()));
+        }
+        puzzle.acceptable_moves().map_err(|e| format!("{}: {}", path.display(), e))?;
+        let board = puzzle.position.to_board().map_err(|e| format!("{}: {}", path.display(), e))?;
+        if !board.snakes.iter().any(|s| s.id == puzzle.you) {
+            return Err(format!("{}: snake '{}' is not on the board", path.display(), puzzle.you));
+        }
+        Ok(puzzle)
+    }
+
+    /// Puzzle candidate from a replayed turn on which the search disagrees with the move
+    /// played: the replayed snake's position, with the search's move as the answer
+    /// Review candidates before adding them to tests/puzzles; the move the search prefers
+    /// is not necessarily the better one
+    pub fn from_replay(result: &ReplayResult, name: String, tag: &str) -> Puzzle {
+        Puzzle {
+            name,
+            tag: tag.to_string(),
+            description: format!(
+                "{} played {} on turn {}; the search prefers {} at depth {}",
+                result.our_id,
+                result.original_move.as_str(),
+                result.turn,
+                result.replayed_move.as_str(),
+                result.search_depth
+            ),
+            you: result.our_id.clone(),
+            turn: result.turn,
+            depth: Some(result.search_depth),
+            acceptable: vec![result.replayed_move.as_str().to_string()],
+            known_failure: None,
+            position: Position::Board { board: result.board.clone() },
+        }
+    }
+
+    pub fn acceptable_moves(&self) -> Result<Vec<Direction>, String> {
+        self.acceptable.iter().map(|s| parse_direction(s)).collect()
+    }
+
+    /// Runs the fixed-depth search and checks its move
+    pub fn solve(&self, config: &Config, default_depth: u8) -> Result<PuzzleOutcome, String> {
+        let depth = self.depth.unwrap_or(default_depth);
+        let board = self.position.to_board()?;
+        let engine = ReplayEngine::new(config.clone(), false).with_fixed_depth(Some(depth));
+        let (chosen, _, _, _) = engine.replay_turn(&board, &self.you, self.turn)?;
+        let acceptable = self.acceptable_moves()?;
+
+        Ok(PuzzleOutcome {
+            name: self.name.clone(),
+            tag: self.tag.clone(),
+            passed: acceptable.contains(&chosen),
+            chosen,
+            acceptable,
+            depth,
+            known_failure: self.known_failure.is_some(),
+        })
+    }
+}
+
+/// All puzzles (*.json) in `dir`, sorted by file name
+pub fn load_dir<P: AsRef<Path>>(dir: P) -> Result<Vec<(PathBuf, Puzzle)>, String> {
+    let dir = dir.as_ref();
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| Puzzle::from_file(&path).map(|puzzle| (path, puzzle)))
+        .collect()
+}
+
+/// Pass counts per tag
+pub fn summarize(outcomes: &[PuzzleOutcome]) -> BTreeMap<String, TagSummary> {
+    let mut summary: BTreeMap<String, TagSummary> = BTreeMap::new();
+    for outcome in outcomes {
+        let entry = summary.entry(outcome.tag.clone()).or_default();
+        entry.total += 1;
+        if outcome.passed {
+            entry.passed += 1;
+        }
+    }
+    summary
+}
@@ -0,0 +1,193 @@
+// Elo estimates and sequential probability ratio tests for arena matches
+//
+// A fixed number of self-play games either wastes time on obvious results or is too small
+// to separate a +10 Elo change from noise. The SPRT keeps playing batches until the
+// log-likelihood ratio of H1 (candidate is elo1 stronger) against H0 (elo0) leaves the
+// bounds set by the error rates alpha/beta, using the normal approximation of the game
+// score distribution (wins, draws, losses) as fishtest does.
+
+use crate::config::RatingConfig;
+
+/// Win/draw/loss tally of a match from the first config's point of view
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MatchResult {
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+impl MatchResult {
+    pub fn games(&self) -> u32 {
+        self.wins + self.draws + self.losses
+    }
+
+    /// Points per game in [0, 1] (draw = half a point)
+    pub fn score(&self) -> f64 {
+        if self.games() == 0 {
+            return 0.5;
+        }
+        (self.wins as f64 + 0.5 * self.draws as f64) / self.games() as f64
+    }
+}
+
+impl std::ops::AddAssign for MatchResult {
+    fn add_assign(&mut self, other: MatchResult) {
+        self.wins += other.wins;
+        self.draws += other.draws;
+        self.losses += other.losses;
+    }
+}
+
+/// 95% two-sided normal quantile
+const Z_95: f64 = 1.959964;
+
+/// Expected score of a player `elo` points stronger (logistic model)
+pub fn expected_score(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+/// Elo difference that yields the expected `score` (clamped away from 0 and 1)
+pub fn elo_from_score(score: f64) -> f64 {
+    let score = score.clamp(1e-6, 1.0 - 1e-6);
+    -400.0 * (1.0 / score - 1.0).log10()
+}
+
+/// Elo difference with a 95% confidence interval
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EloEstimate {
+    pub elo: f64,
+    pub lower: f64,
+    pub upper: f64,
+}
+
+impl EloEstimate {
+    /// +/- half-width of the interval (the interval is asymmetric; this is its average)
+    pub fn error(&self) -> f64 {
+        (self.upper - self.lower) / 2.0
+    }
+}
+
+/// Mean and per-game variance of the score (win 1, draw 0.5, loss 0), with `prior` virtual
+/// games added to each outcome
+fn score_stats(result: &MatchResult, prior: f64) -> (f64, f64) {
+    let (wins, draws, losses) = (result.wins as f64 + prior, result.draws as f64 + prior, result.losses as f64 + prior);
+    let n = wins + draws + losses;
+    let mean = (wins + 0.5 * draws) / n;
+    let variance = (wins * (1.0 - mean).powi(2) + draws * (0.5 - mean).powi(2) + losses * mean.powi(2)) / n;
+    (mean, variance)
+}
+
+/// Elo of the first player of `result`, None before any game was played
+pub fn elo(result: &MatchResult) -> Option<EloEstimate> {
+    if result.games() == 0 {
+        return None;
+    }
+    let (mean, variance) = score_stats(result, 0.0);
+    let margin = Z_95 * (variance / result.games() as f64).sqrt();
+    Some(EloEstimate {
+        elo: elo_from_score(mean),
+        lower: elo_from_score(mean - margin),
+        upper: elo_from_score(mean + margin),
+    })
+}
+
+/// State of a sequential test
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SprtDecision {
+    /// H1 accepted: the candidate is at least elo1 stronger
+    Accept,
+    /// H0 accepted: the candidate is no better than elo0
+    Reject,
+    /// Not enough evidence yet
+    Continue,
+}
+
+/// Log-likelihood ratio and its decision bounds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SprtStatus {
+    pub llr: f64,
+    pub lower_bound: f64,
+    pub upper_bound: f64,
+    pub decision: SprtDecision,
+}
+
+/// Sequential probability ratio test of elo1 against elo0
+pub struct Sprt {
+    elo0: f64,
+    elo1: f64,
+    lower_bound: f64,
+    upper_bound: f64,
+}
+
+impl Sprt {
+    pub fn new(settings: &RatingConfig) -> Self {
+        Sprt {
+            elo0: settings.elo0,
+            elo1: settings.elo1,
+            lower_bound: (settings.beta / (1.0 - settings.alpha)).ln(),
+            upper_bound: ((1.0 - settings.beta) / settings.alpha).ln(),
+        }
+    }
+
+    /// Log-likelihood ratio of H1 over H0 for the games so far
+    pub fn llr(&self, result: &MatchResult) -> f64 {
+        if result.games() == 0 {
+            return 0.0;
+        }
+        // Half a virtual game per outcome keeps the variance positive when every game so far
+        // ended the same way (a clean sweep must still be able to pass, just not after 3 games)
+        let (mean, variance) = score_stats(result, 0.5);
+        let (s0, s1) = (expected_score(self.elo0), expected_score(self.elo1));
+        result.games() as f64 * (s1 - s0) * (2.0 * mean - s0 - s1) / (2.0 * variance)
+    }
+
+    pub fn status(&self, result: &MatchResult) -> SprtStatus {
+        let llr = self.llr(result);
+        let decision = if llr >= self.upper_bound {
+            SprtDecision::Accept
+        } else if llr <= self.lower_bound {
+            SprtDecision::Reject
+        } else {
+            SprtDecision::Continue
+        };
+        SprtStatus { llr, lower_bound: self.lower_bound, upper_bound: self.upper_bound, decision }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn result(wins: u32, draws: u32, losses: u32) -> MatchResult {
+        MatchResult { wins, draws, losses }
+    }
+
+    #[test]
+    fn test_elo_is_symmetric_and_brackets_the_estimate() {
+        assert!(elo(&result(0, 0, 0)).is_none());
+        assert_eq!(elo(&result(10, 0, 10)).unwrap().elo, 0.0);
+
+        let ahead = elo(&result(60, 10, 30)).unwrap();
+        let behind = elo(&result(30, 10, 60)).unwrap();
+        assert!((ahead.elo + behind.elo).abs() < 1e-9);
+        assert!(ahead.lower < ahead.elo && ahead.elo < ahead.upper);
+        // 65% score is about +108 Elo
+        assert!((ahead.elo - 107.5).abs() < 0.5, "{}", ahead.elo);
+
+        let more_games = elo(&result(600, 100, 300)).unwrap();
+        assert!(more_games.error() < ahead.error());
+    }
+
+    #[test]
+    fn test_sprt_accepts_clear_gains_and_rejects_clear_losses() {
+        let sprt = Sprt::new(&Config::default_hardcoded().rating);
+
+        assert_eq!(sprt.status(&result(0, 0, 0)).decision, SprtDecision::Continue);
+        assert_eq!(sprt.status(&result(5, 0, 3)).decision, SprtDecision::Continue);
+        assert_eq!(sprt.status(&result(700, 100, 500)).decision, SprtDecision::Accept);
+        assert_eq!(sprt.status(&result(500, 100, 700)).decision, SprtDecision::Reject);
+        assert_eq!(sprt.status(&result(4, 0, 0)).decision, SprtDecision::Continue);
+        assert_eq!(sprt.status(&result(80, 0, 0)).decision, SprtDecision::Accept);
+    }
+}
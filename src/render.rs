@@ -0,0 +1,98 @@
+// Terminal rendering of boards
+//
+// Reading a position out of log JSON means plotting coordinates by hand. Board::render draws
+// it instead: a colored Unicode grid with axis labels and a legend, for the replay tool's
+// mismatch report on a terminal. Its plain-text counterpart for log files, Board::render_ascii
+// (the board_builder notation), lives in types.rs since the server logs with it. Snakes are
+// lettered a, b, c, ... in board order.
+
+use crate::types::{snake_letter, Board, BoardCell, Coord};
+
+const RESET: &str = "\x1b[0m";
+/// Snake colors in board order (red is kept for food)
+const SNAKE_COLORS: [&str; 6] = ["\x1b[32m", "\x1b[33m", "\x1b[34m", "\x1b[35m", "\x1b[36m", "\x1b[37m"];
+const FOOD_COLOR: &str = "\x1b[91m";
+const HAZARD_BACKGROUND: &str = "\x1b[48;5;238m";
+const EMPTY_COLOR: &str = "\x1b[90m";
+
+impl Board {
+    /// Colored Unicode grid (top row = highest y) with axis labels and a snake legend
+    pub fn render(&self) -> String {
+        let grid = self.cells();
+        let mut out = String::new();
+        for (y, row) in grid.iter().enumerate().rev() {
+            out.push_str(&format!("{:>2} ", y));
+            for (x, cell) in row.iter().enumerate() {
+                let hazard = self.hazards.contains(&Coord { x: x as i32, y: y as i32 });
+                if hazard {
+                    out.push_str(HAZARD_BACKGROUND);
+                }
+                let (color, symbol) = match *cell {
+                    BoardCell::Empty => (EMPTY_COLOR, "·"),
+                    BoardCell::Food => (FOOD_COLOR, "●"),
+                    BoardCell::Head(idx) => (SNAKE_COLORS[idx % SNAKE_COLORS.len()], "◆"),
+                    BoardCell::Body(idx) => (SNAKE_COLORS[idx % SNAKE_COLORS.len()], "■"),
+                };
+                out.push_str(&format!("{}{} {}", color, symbol, RESET));
+            }
+            out.push('\n');
+        }
+        out.push_str("   ");
+        for x in 0..self.width {
+            out.push_str(&format!("{:<2}", x % 10));
+        }
+        out.push('\n');
+
+        for (idx, snake) in self.snakes.iter().enumerate() {
+            out.push_str(&format!(
+                "{}◆ {}{} {} (health {}, length {})\n",
+                SNAKE_COLORS[idx % SNAKE_COLORS.len()],
+                snake_letter(idx),
+                RESET,
+                snake.id,
+                snake.health,
+                snake.length
+            ));
+        }
+        out
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::board_builder::{parse_board, BoardBuilder};
+
+    #[test]
+    fn test_ascii_render_round_trips_through_the_parser() {
+        let board = BoardBuilder::new(5, 4)
+            .snake("us", &[(3, 2), (2, 2), (1, 2), (1, 1)])
+            .snake("them", &[(3, 1), (3, 0)])
+            .food(&[(2, 3), (4, 0)])
+            .hazards(&[(2, 0), (4, 0)])
+            .health("them", 42)
+            .build();
+
+        let rendered = board.render_ascii();
+        let grid: String = rendered.lines().take(4).collect::<Vec<_>>().join("\n");
+        assert_eq!(grid, ".  .  *  .  .\n.  a  a  a> .\n.  a  .  b> .\n.  .  #  b  *#");
+        assert!(rendered.contains("b = them (health 42, length 2)"));
+
+        let parsed = parse_board(&grid).unwrap();
+        assert_eq!(parsed.snakes[0].body, board.snakes[0].body);
+        assert_eq!(parsed.snakes[1].body, board.snakes[1].body);
+        assert_eq!((parsed.food, parsed.hazards), (board.food, board.hazards));
+    }
+
+    #[test]
+    fn test_colored_render_has_a_row_per_y_and_a_legend() {
+        let board = BoardBuilder::new(3, 3).snake("us", &[(1, 1), (1, 0)]).food(&[(0, 2)]).build();
+        let rendered = board.render();
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(lines.len(), 3 + 1 + 1);
+        assert!(lines[0].starts_with(" 2 ") && lines[0].contains('●'));
+        assert!(lines[1].contains('◆') && lines[2].contains('■'));
+        assert!(lines[4].contains("us (health 100, length 2)"));
+    }
+}
@@ -6,18 +6,29 @@
 // 3. Compare expected vs actual moves
 // 4. Generate detailed analysis reports
 
+use flate2::read::GzDecoder;
 use log::{info, warn};
 use serde::{Deserialize, Serialize};
+use rand::SeedableRng;
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
-use std::path::Path;
+use rayon::prelude::*;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::Instant;
 
-use crate::bot::Bot;
+use crate::analysis;
+use crate::bot::{self, Bot, DetailedScore, HistoryTable, SearchLimits, SharedSearchState};
 use crate::config::Config;
-use crate::types::{Board, Direction};
+use crate::debug_logger::TurnNotes;
+use crate::error::BotError;
+use crate::evaluator;
+use crate::request_validation;
+use crate::rules::{self, Elimination, RulesSettings};
+use crate::schema::{ScoreBreakdownV1, SearchResultV1};
+use crate::types::{Battlesnake, Board, Coord, Direction, GameState};
 
 /// Represents a single log entry from the debug JSONL file
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -26,6 +37,70 @@ pub struct LogEntry {
     pub chosen_move: String,
     pub board: Board,
     pub timestamp: String,
+    /// Versioned search result written by the live bot (absent in older logs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<SearchResultV1>,
+    /// Panic and final-move override notes recorded by the live bot
+    #[serde(flatten)]
+    pub notes: TurnNotes,
+}
+
+impl LogEntry {
+    /// The /move request logged with debug.log_requests, parsed and sanitized as the server
+    /// does (None for entries without one)
+    pub fn request_state(&self, config: &Config) -> Option<Result<GameState, BotError>> {
+        let raw = self.notes.request.as_ref()?;
+        let logged = |reason: String| BotError::LoggedRequest { turn: self.turn, reason };
+        let state = serde_json::from_str::<GameState>(raw)
+            .map_err(|e| logged(format!("unreadable logged request: {}", e)))
+            .and_then(|mut state| {
+                request_validation::sanitize(&mut state, config).map_err(|e| logged(format!("invalid logged request: {}", e)))?;
+                Ok(state)
+            });
+        Some(state)
+    }
+
+    /// The snake that made the logged move: the one the live bot recorded (notes.you), else
+    /// the first snake on the board as older logs assume
+    pub fn our_snake(&self) -> Result<&Battlesnake, BotError> {
+        SnakeSelector::default().find(self)
+    }
+}
+
+/// Move `snake_id` made from `board` to `next` (wrapped boards included); None unless it
+/// is on both
+fn move_played(board: &Board, next: &Board, snake_id: &str) -> Option<Direction> {
+    let from = *board.our_snake(snake_id)?.body.first()?;
+    let to = *next.our_snake(snake_id)?.body.first()?;
+    let wrap = |c: Coord| Coord { x: c.x.rem_euclid(board.width), y: c.y.rem_euclid(board.height as i32) };
+    Direction::ALL.iter().copied().find(|dir| wrap(dir.apply(&from)) == to)
+}
+
+/// Which snake on a logged board is ours (replay --snake-id / --snake-name)
+///
+/// The id (given, else the one the live bot logged) is matched first, then the name: imported
+/// official games give our snake a new id every game but keep its name. Logs with neither
+/// take the first snake.
+#[derive(Debug, Clone, Default)]
+pub struct SnakeSelector {
+    pub id: Option<String>,
+    pub name: Option<String>,
+}
+
+impl SnakeSelector {
+    pub fn find<'a>(&self, entry: &'a LogEntry) -> Result<&'a Battlesnake, BotError> {
+        let id = self.id.as_ref().or(entry.notes.you.as_ref());
+        if let Some(snake) = id.and_then(|id| entry.board.our_snake(id)) {
+            return Ok(snake);
+        }
+        if let Some(snake) = self.name.as_ref().and_then(|name| entry.board.snakes.iter().find(|s| s.name == *name)) {
+            return Ok(snake);
+        }
+        match id.or(self.name.as_ref()) {
+            Some(wanted) => Err(BotError::SnakeNotFound(wanted.clone())),
+            None => entry.board.snakes.first().ok_or(BotError::NoSnakes),
+        }
+    }
 }
 
 /// Result of replaying a single turn
@@ -39,6 +114,33 @@ pub struct ReplayResult {
     pub replayed_score: i32,
     pub search_depth: u8,
     pub computation_time_ms: u128,
+    /// Position the turn was replayed from (drawn in the mismatch report)
+    pub board: Board,
+    /// Id of the snake replayed on `board`
+    pub our_id: String,
+    /// Search result the live bot logged for this turn (absent in older logs)
+    pub logged: Option<SearchResultV1>,
+    /// Explain mode (debug.explain): evaluation at the end of the replayed search's winning
+    /// line under the replayed and under the original move (None when it scored no such line)
+    pub replayed_leaf: Option<DetailedScore>,
+    pub original_leaf: Option<DetailedScore>,
+}
+
+impl ReplayResult {
+    /// Replayed decision in the stable versioned schema
+    pub fn to_schema(&self) -> SearchResultV1 {
+        let result = SearchResultV1::new(
+            self.turn,
+            self.replayed_move,
+            self.replayed_score,
+            self.search_depth,
+            self.computation_time_ms as u64,
+        );
+        match &self.replayed_leaf {
+            Some(leaf) => result.with_leaf_breakdown(ScoreBreakdownV1::from(leaf)),
+            None => result,
+        }
+    }
 }
 
 /// Statistics for a complete replay session
@@ -50,43 +152,221 @@ pub struct ReplayStats {
     pub match_rate: f64,
 }
 
+/// Selects the log entries worth replaying; every set condition must hold
+///
+/// Long games are mostly uneventful, so a replay can focus on a turn window, crowded
+/// boards, the approach to our death, or positions where we are starving.
+#[derive(Debug, Clone, Default)]
+pub struct EntryFilter {
+    /// Inclusive turn ranges (empty = every turn)
+    pub turns: Vec<(i32, i32)>,
+    /// Minimum number of snakes on the board
+    pub min_snakes: Option<usize>,
+    /// Only the last K turns before our death
+    pub near_death: Option<i32>,
+    /// Maximum health of our snake
+    pub low_health: Option<i32>,
+    /// Our snake, for low_health
+    pub snake: SnakeSelector,
+}
+
+impl EntryFilter {
+    /// Parses a turn list such as "5,10,30..60" (ranges are inclusive)
+    pub fn parse_turns(s: &str) -> Result<Vec<(i32, i32)>, String> {
+        let turn = |t: &str| t.trim().parse::<i32>().map_err(|e| format!("Invalid turn number '{}': {}", t, e));
+        s.split(',')
+            .map(|part| match part.split_once("..") {
+                Some((from, to)) => {
+                    let (from, to) = (turn(from)?, turn(to)?);
+                    if from > to {
+                        return Err(format!("Empty turn range '{}'", part));
+                    }
+                    Ok((from, to))
+                }
+                None => turn(part).map(|t| (t, t)),
+            })
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.turns.is_empty() && self.min_snakes.is_none() && self.near_death.is_none() && self.low_health.is_none()
+    }
+
+    /// Turn on which we died: the last logged turn, unless we were the only snake left
+    /// (then we won and there is no death to approach)
+    pub fn death_turn(entries: &[LogEntry]) -> Option<i32> {
+        let last = entries.iter().max_by_key(|e| e.turn)?;
+        if last.board.snakes.len() > 1 {
+            Some(last.turn)
+        } else {
+            None
+        }
+    }
+
+    /// Entries of one game passing the filter, in log order
+    pub fn select(&self, entries: &[LogEntry]) -> Vec<LogEntry> {
+        let death = Self::death_turn(entries);
+        entries
+            .iter()
+            .filter(|e| self.turns.is_empty() || self.turns.iter().any(|&(from, to)| (from..=to).contains(&e.turn)))
+            .filter(|e| self.min_snakes.is_none_or(|min| e.board.snakes.len() >= min))
+            .filter(|e| match (self.near_death, death) {
+                (None, _) => true,
+                (Some(k), Some(death)) => death - e.turn < k,
+                (Some(_), None) => false,
+            })
+            .filter(|e| self.low_health.is_none_or(|max| self.snake.find(e).is_ok_and(|us| us.health <= max)))
+            .cloned()
+            .collect()
+    }
+}
+
+/// One turn replayed under two configurations
+#[derive(Debug, Clone)]
+pub struct ComparedTurn {
+    pub turn: i32,
+    pub logged_move: Direction,
+    pub move_a: Direction,
+    pub move_b: Direction,
+    pub score_a: i32,
+    pub score_b: i32,
+    pub depth_a: u8,
+    pub depth_b: u8,
+    /// For divergent turns: each config's move evaluated under that config
+    pub breakdown: Option<(DetailedScore, DetailedScore)>,
+}
+
+impl ComparedTurn {
+    pub fn diverges(&self) -> bool {
+        self.move_a != self.move_b
+    }
+}
+
+/// Result of replaying a log under two configurations
+#[derive(Debug, Clone, Default)]
+pub struct ConfigComparison {
+    pub turns: Vec<ComparedTurn>,
+}
+
+impl ConfigComparison {
+    pub fn divergent(&self) -> usize {
+        self.turns.iter().filter(|t| t.diverges()).count()
+    }
+
+    /// Percentage of turns where the two configs chose different moves
+    pub fn divergence_rate(&self) -> f64 {
+        if self.turns.is_empty() {
+            return 0.0;
+        }
+        self.divergent() as f64 / self.turns.len() as f64 * 100.0
+    }
+
+    /// Turns on which config A and config B agree with the logged move
+    pub fn log_agreement(&self) -> (usize, usize) {
+        let a = self.turns.iter().filter(|t| t.move_a == t.logged_move).count();
+        let b = self.turns.iter().filter(|t| t.move_b == t.logged_move).count();
+        (a, b)
+    }
+}
+
+/// One line of a what-if rollout
+#[derive(Debug, Clone)]
+pub struct WhatIfLine {
+    /// Our moves, starting with the move under test
+    pub moves: Vec<Direction>,
+    /// Turns we were still alive after (at most the horizon)
+    pub turns_survived: i32,
+    /// How we died, if we did within the horizon
+    pub death: Option<Elimination>,
+    /// We were the last snake standing
+    pub won: bool,
+}
+
+impl WhatIfLine {
+    pub fn first_move(&self) -> Option<Direction> {
+        self.moves.first().copied()
+    }
+
+    pub fn describe(&self) -> String {
+        match (&self.death, self.won) {
+            (Some(death), _) => format!("died after {} turn(s) ({:?})", self.turns_survived, death.cause),
+            (None, true) => format!("won after {} turn(s)", self.turns_survived),
+            (None, false) => format!("alive after {} turn(s)", self.turns_survived),
+        }
+    }
+}
+
+/// Forced move against the bot's own choice, both played forward from the same turn
+#[derive(Debug, Clone)]
+pub struct WhatIfReport {
+    pub turn: i32,
+    pub forced: WhatIfLine,
+    pub baseline: WhatIfLine,
+}
+
+impl WhatIfReport {
+    /// Turns the forced line survives longer than the bot's line (negative: shorter)
+    /// A win counts as surviving the whole horizon
+    pub fn survival_gain(&self, horizon: i32) -> i32 {
+        let effective = |line: &WhatIfLine| if line.won { horizon } else { line.turns_survived };
+        effective(&self.forced) - effective(&self.baseline)
+    }
+}
+
 /// Replay engine for analyzing debug logs
 pub struct ReplayEngine {
     config: Config,
     verbose: bool,
+    limits: SearchLimits,
+    snake: SnakeSelector,
 }
 
 impl ReplayEngine {
     /// Creates a new replay engine with the given configuration
-    pub fn new(config: Config, verbose: bool) -> Self {
-        ReplayEngine { config, verbose }
+    pub fn new(mut config: Config, verbose: bool) -> Self {
+        evaluator::attach(&mut config);
+        bot::init_global_search_pool(&config);
+        let limits = SearchLimits::from_config(&config);
+        ReplayEngine { config, verbose, limits, snake: SnakeSelector::default() }
+    }
+
+    /// Replays the snake `snake` selects instead of the logged or first one
+    pub fn with_snake(mut self, snake: SnakeSelector) -> Self {
+        self.snake = snake;
+        self
+    }
+
+    /// Deterministic mode: search every turn to exactly `depth` with no clock
+    /// Makes replay results reproducible across runs and machines
+    pub fn with_fixed_depth(mut self, depth: Option<u8>) -> Self {
+        self.limits = self.limits.with_fixed_depth(depth);
+        self
     }
 
-    /// Loads all log entries from a JSONL file
+    /// Loads all log entries from a JSONL file (gzipped if the name ends in .gz)
     pub fn load_log_file<P: AsRef<Path>>(
         &self,
         log_path: P,
-    ) -> Result<Vec<LogEntry>, String> {
-        let file = File::open(log_path.as_ref())
-            .map_err(|e| format!("Failed to open log file: {}", e))?;
+    ) -> Result<Vec<LogEntry>, BotError> {
+        let file = File::open(log_path.as_ref()).map_err(BotError::LogOpen)?;
 
-        let reader = BufReader::new(file);
+        // Per-game logs are gzipped when debug.compress is set
+        let reader: Box<dyn BufRead> = if log_path.as_ref().extension().is_some_and(|ext| ext == "gz") {
+            Box::new(BufReader::new(GzDecoder::new(file)))
+        } else {
+            Box::new(BufReader::new(file))
+        };
         let mut entries = Vec::new();
 
         for (line_num, line) in reader.lines().enumerate() {
-            let line = line.map_err(|e| format!("Failed to read line {}: {}", line_num + 1, e))?;
+            let line = line.map_err(|source| BotError::LogRead { line: line_num + 1, source })?;
 
             if line.trim().is_empty() {
                 continue;
             }
 
-            let entry: LogEntry = serde_json::from_str(&line).map_err(|e| {
-                format!(
-                    "Failed to parse JSON on line {}: {}",
-                    line_num + 1,
-                    e
-                )
-            })?;
+            let entry: LogEntry =
+                serde_json::from_str(&line).map_err(|source| BotError::LogParse { line: line_num + 1, source })?;
 
             entries.push(entry);
         }
@@ -102,18 +382,28 @@ impl ReplayEngine {
         board: &Board,
         our_snake_id: &str,
         turn: i32,
-    ) -> Result<(Direction, i32, u8, u128), String> {
+    ) -> Result<(Direction, i32, u8, u128), BotError> {
+        self.search_turn(board, our_snake_id, turn).map(|(direction, score, depth, time, _)| (direction, score, depth, time))
+    }
+
+    /// replay_turn, also returning the finished search's shared state
+    fn search_turn(
+        &self,
+        board: &Board,
+        our_snake_id: &str,
+        turn: i32,
+    ) -> Result<(Direction, i32, u8, u128, Arc<SharedSearchState>), BotError> {
         // Find our snake in the board
-        let our_snake = board
-            .snakes
-            .iter()
-            .find(|s| s.id == our_snake_id)
-            .ok_or_else(|| format!("Snake with id '{}' not found in board state", our_snake_id))?;
+        let our_snake = board.our_snake(our_snake_id).ok_or_else(|| BotError::SnakeNotFound(our_snake_id.to_string()))?;
+        if our_snake.body.is_empty() {
+            return Err(BotError::EmptyBody(our_snake_id.to_string()));
+        }
 
         let start_time = Instant::now();
 
         // Use Bot's internal computation logic
-        let shared = Arc::new(crate::bot::SharedSearchState::new());
+        let shared = Arc::new(SharedSearchState::new());
+        let limits = self.limits.started_at(start_time);
 
         // CRITICAL: Initialize shared state with first legal move to ensure we never
         // return an illegal move if search times out before completing any iterations
@@ -134,75 +424,128 @@ impl ReplayEngine {
 
         // Run computation synchronously (we're already in a non-async context)
         let turn_clone = turn;
-        std::thread::spawn(move || {
+        let search = std::thread::Builder::new().stack_size(bot::search_stack_bytes(&self.config)).spawn(move || {
+            // Each replayed turn starts from an empty history table (no game session)
+            let mut history = HistoryTable::new(board_clone.width as u32, board_clone.height);
             Bot::compute_best_move_internal(
                 &board_clone,
                 &our_snake_clone,
                 turn_clone,
                 shared_clone,
-                start_time,
                 &config_clone,
+                &mut history,
+                limits,
             )
-        });
+        }).map_err(BotError::SearchThread)?;
 
-        // Wait for completion or timeout
-        let effective_budget = self.config.timing.effective_budget_ms();
-        let poll_interval = std::time::Duration::from_millis(10);
+        if limits.fixed_depth.is_some() {
+            // Fixed depth: no clock, wait for the search to finish
+            search
+                .join()
+                .map_err(|_| BotError::SearchPanicked(turn))?;
+        } else {
+            // Wait for completion or timeout
+            let effective_budget = self.config.timing.effective_budget_ms();
+            let poll_interval = std::time::Duration::from_millis(10);
+
+            loop {
+                std::thread::sleep(poll_interval);
+                let elapsed = start_time.elapsed().as_millis() as u64;
 
-        loop {
-            std::thread::sleep(poll_interval);
-            let elapsed = start_time.elapsed().as_millis() as u64;
+                if elapsed >= effective_budget || shared.search_complete.load(Ordering::Acquire) {
+                    break;
+                }
+            }
 
-            if elapsed >= effective_budget || shared.search_complete.load(Ordering::Acquire) {
-                break;
+            // Same as Bot::get_move: abort the search once the budget expires
+            shared.request_stop();
+            if search.is_finished() && search.join().is_err() {
+                return Err(BotError::SearchPanicked(turn));
             }
         }
 
         let computation_time = start_time.elapsed().as_millis();
-        let (move_idx, score) = shared.get_best();
-        let depth = shared.current_depth.load(Ordering::Acquire);
+        let (move_idx, score, depth) = shared.answer();
 
         let direction = Bot::index_to_direction(move_idx, &self.config);
 
-        Ok((direction, score, depth, computation_time))
+        // Apply the same veto and verification layers as Bot::get_move so replays match production
+        let direction = Bot::apply_ban_list(board, our_snake, direction, &legal_moves, turn, &self.config);
+        let direction = match Bot::verify_one_ply_safety(board, our_snake, direction, &legal_moves, &self.config) {
+            Some(safety_override) => {
+                warn!("Turn {}: SAFETY OVERRIDE {}", turn, safety_override.describe());
+                safety_override.replacement
+            }
+            None => direction,
+        };
+
+        Ok((direction, score, depth, computation_time, shared))
     }
 
     /// Replays a single log entry and compares the result
-    pub fn replay_entry(&self, entry: &LogEntry) -> Result<ReplayResult, String> {
+    pub fn replay_entry(&self, entry: &LogEntry) -> Result<ReplayResult, BotError> {
         if self.verbose {
             info!("Replaying turn {}...", entry.turn);
         }
 
-        // Assume the first snake in the log is our snake (the one that made the logged move)
-        let our_snake = entry
-            .board
-            .snakes
-            .first()
-            .ok_or("No snakes found in board state")?;
+        // A logged request (debug.log_requests) is searched exactly as the server parsed it;
+        // otherwise the logged board is searched for the snake that made the logged move
+        let request = entry.request_state(&self.config).transpose()?;
+        let (board, our_id) = match &request {
+            Some(state) => (&state.board, state.you.id.clone()),
+            None => (&entry.board, self.snake.find(entry)?.id.clone()),
+        };
 
         let original_move = Self::parse_direction(&entry.chosen_move)?;
+        self.replay_position(board, our_id, entry.turn, original_move, entry.result.clone())
+    }
 
-        let (replayed_move, replayed_score, search_depth, computation_time) =
-            self.replay_turn(&entry.board, &our_snake.id, entry.turn)?;
+    /// Searches `our_id` on `board` and compares with the move it played, `original_move`
+    fn replay_position(
+        &self,
+        board: &Board,
+        our_id: String,
+        turn: i32,
+        original_move: Direction,
+        logged: Option<SearchResultV1>,
+    ) -> Result<ReplayResult, BotError> {
+        let (replayed_move, replayed_score, search_depth, computation_time, shared) = self.search_turn(board, &our_id, turn)?;
 
         let matches = original_move == replayed_move;
 
         let result = ReplayResult {
-            turn: entry.turn,
+            turn,
             original_move,
             replayed_move,
             matches,
-            original_score: 0, // We don't log scores in the original debug output
+            // Older logs carry no search result
+            original_score: logged.as_ref().map_or(0, |logged| logged.score),
             replayed_score,
             search_depth,
             computation_time_ms: computation_time,
+            board: board.clone(),
+            our_id,
+            logged,
+            replayed_leaf: shared.leaf_breakdown(search_depth, replayed_move),
+            original_leaf: shared.leaf_breakdown(search_depth, original_move),
         };
 
         if self.verbose {
+            if let Some(logged) = &result.logged {
+                info!(
+                    "Turn {}: logged depth {}, score {}, {} nodes, TT hit rate {}, PV {}",
+                    turn,
+                    logged.depth,
+                    logged.score,
+                    logged.nodes.map_or("?".to_string(), |n| n.to_string()),
+                    logged.tt_hit_rate().map_or("?".to_string(), |rate| format!("{:.1}%", rate * 100.0)),
+                    logged.pv.as_ref().map_or("?".to_string(), |pv| pv.join(" "))
+                );
+            }
             if matches {
                 info!(
                     "Turn {}: ✓ MATCH - {} (score: {}, depth: {}, time: {}ms)",
-                    entry.turn,
+                    turn,
                     replayed_move.as_str(),
                     replayed_score,
                     search_depth,
@@ -211,7 +554,7 @@ impl ReplayEngine {
             } else {
                 warn!(
                     "Turn {}: ✗ MISMATCH - Original: {}, Replayed: {} (score: {}, depth: {}, time: {}ms)",
-                    entry.turn,
+                    turn,
                     original_move.as_str(),
                     replayed_move.as_str(),
                     replayed_score,
@@ -225,7 +568,7 @@ impl ReplayEngine {
     }
 
     /// Replays all entries in a log file
-    pub fn replay_all(&self, entries: &[LogEntry]) -> Result<Vec<ReplayResult>, String> {
+    pub fn replay_all(&self, entries: &[LogEntry]) -> Result<Vec<ReplayResult>, BotError> {
         let mut results = Vec::new();
 
         for entry in entries {
@@ -240,19 +583,76 @@ impl ReplayEngine {
         Ok(results)
     }
 
+    /// Replays the decisions of `snake_id` (usually an opponent) instead of ours: each logged
+    /// board on which it moves on to the next turn's board is searched from its perspective
+    /// and compared with the move it made, recovered from its head displacement
+    pub fn replay_as(&self, entries: &[LogEntry], snake_id: &str) -> Result<Vec<ReplayResult>, BotError> {
+        if !entries.iter().any(|e| e.board.our_snake(snake_id).is_some()) {
+            return Err(BotError::SnakeNotFound(snake_id.to_string()));
+        }
+        let mut results: Vec<ReplayResult> = Vec::new();
+        for entry in entries {
+            // Self-play logs hold every turn twice; replay it once
+            if results.iter().any(|r| r.turn == entry.turn) {
+                continue;
+            }
+            let played = entries
+                .iter()
+                .filter(|next| next.turn == entry.turn + 1)
+                .find_map(|next| move_played(&entry.board, &next.board, snake_id));
+            let Some(played) = played else { continue };
+            match self.replay_position(&entry.board, snake_id.to_string(), entry.turn, played, None) {
+                Ok(result) => results.push(result),
+                Err(e) => warn!("Failed to replay turn {} as {}: {}", entry.turn, snake_id, e),
+            }
+        }
+        Ok(results)
+    }
+
+    /// Same as replay_all, with turns replayed in parallel on the current rayon pool
+    /// Results keep the order of `entries`. Use a fixed depth: timed searches running side
+    /// by side get less CPU each and reach shallower depths than in a serial replay.
+    pub fn replay_all_parallel(&self, entries: &[LogEntry]) -> Vec<ReplayResult> {
+        entries
+            .par_iter()
+            .filter_map(|entry| match self.replay_entry(entry) {
+                Ok(result) => Some(result),
+                Err(e) => {
+                    warn!("Failed to replay turn {}: {}", entry.turn, e);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Replays the entries of every game in `paths` selected by `filter`, in parallel,
+    /// games and turns alike. Returns per file either its results or the error that
+    /// prevented loading it
+    pub fn replay_files(
+        &self,
+        paths: &[PathBuf],
+        filter: &EntryFilter,
+    ) -> Vec<(PathBuf, Result<Vec<ReplayResult>, BotError>)> {
+        paths
+            .par_iter()
+            .map(|path| {
+                let results =
+                    self.load_log_file(path).map(|entries| self.replay_all_parallel(&filter.select(&entries)));
+                (path.clone(), results)
+            })
+            .collect()
+    }
+
     /// Replays specific turns from a log file
     pub fn replay_turns(
         &self,
         entries: &[LogEntry],
         turn_numbers: &[i32],
-    ) -> Result<Vec<ReplayResult>, String> {
+    ) -> Result<Vec<ReplayResult>, BotError> {
         let mut results = Vec::new();
 
         for turn_num in turn_numbers {
-            let entry = entries
-                .iter()
-                .find(|e| e.turn == *turn_num)
-                .ok_or_else(|| format!("Turn {} not found in log file", turn_num))?;
+            let entry = entries.iter().find(|e| e.turn == *turn_num).ok_or(BotError::TurnNotFound(*turn_num))?;
 
             match self.replay_entry(entry) {
                 Ok(result) => results.push(result),
@@ -284,8 +684,43 @@ impl ReplayEngine {
         }
     }
 
-    /// Prints a detailed report of replay results
-    pub fn print_report(&self, results: &[ReplayResult]) {
+    /// Per-game and overall match statistics of a batch replay (see replay_files)
+    pub fn print_batch_report(&self, games: &[(PathBuf, Result<Vec<ReplayResult>, BotError>)]) {
+        println!("\n═══════════════════════════════════════════════════════════");
+        println!("                 BATCH REPLAY REPORT");
+        println!("═══════════════════════════════════════════════════════════");
+        println!("{:<48} {:>6} {:>8} {:>8}", "Game", "Turns", "Matches", "Rate");
+
+        let mut all = Vec::new();
+        for (path, results) in games {
+            let name = path.display().to_string();
+            match results {
+                Ok(results) => {
+                    let stats = self.generate_stats(results);
+                    println!("{:<48} {:>6} {:>8} {:>7.1}%", name, stats.total_turns, stats.matches, stats.match_rate);
+                    all.extend(results.iter().cloned());
+                }
+                Err(e) => println!("{:<48} error: {}", name, e),
+            }
+        }
+
+        let total = self.generate_stats(&all);
+        println!("───────────────────────────────────────────────────────────");
+        println!("{:<48} {:>6} {:>8} {:>7.1}%", format!("All ({} games)", games.len()), total.total_turns, total.matches, total.match_rate);
+        println!("═══════════════════════════════════════════════════════════\n");
+    }
+
+    /// Prints how the logged game ended for us (see analysis::analyze_game)
+    pub fn print_death_summary(&self, entries: &[LogEntry]) {
+        match analysis::analyze_game(entries, &self.config) {
+            Ok(Some(death)) => println!("Death: {}\n", death.describe()),
+            Ok(None) => println!("Death: none, we were the last snake standing\n"),
+            Err(e) => warn!("Death analysis failed: {}", e),
+        }
+    }
+
+    /// Prints a detailed report of replay results, drawing mismatched boards with `render_board`
+    pub fn print_report(&self, results: &[ReplayResult], render_board: impl Fn(&Board) -> String) {
         let stats = self.generate_stats(results);
 
         println!("\n═══════════════════════════════════════════════════════════");
@@ -323,32 +758,214 @@ impl ReplayEngine {
                     result.search_depth,
                     result.computation_time_ms
                 );
+                if let Some(cause) = self.divergence_cause(result) {
+                    println!("  {}", cause);
+                }
+                if let Some(cause) = Self::leaf_divergence_cause(result) {
+                    println!("  {}", cause);
+                }
+                if let Some(table) = result.logged.as_ref().and_then(|logged| logged.candidates.as_ref()) {
+                    println!("  logged candidates:");
+                    for iteration in table {
+                        let scores: Vec<String> =
+                            iteration.scores.iter().map(|c| format!("{} {}", c.candidate_move, c.score)).collect();
+                        let margin = iteration.margin().map_or(String::new(), |m| format!(" (margin {})", m));
+                        println!("    depth {:>2}: {}{}", iteration.depth, scores.join(", "), margin);
+                    }
+                }
+                println!("{}", render_board(&result.board));
+            }
+            println!();
+        }
+    }
+
+    /// Replays `entries` under `config_a` and `config_b` with this engine's search limits
+    /// (e.g. --depth) and records, per turn, where the two choose different moves
+    pub fn compare(&self, entries: &[LogEntry], config_a: &Config, config_b: &Config) -> Result<ConfigComparison, BotError> {
+        let engine = |config: &Config| ReplayEngine { config: config.clone(), verbose: self.verbose, limits: self.limits, snake: self.snake.clone() };
+        let (engine_a, engine_b) = (engine(config_a), engine(config_b));
+        let mut comparison = ConfigComparison::default();
+
+        for entry in entries {
+            let our_snake = self.snake.find(entry)?;
+            let logged_move = Self::parse_direction(&entry.chosen_move)?;
+            let (move_a, score_a, depth_a, _) = engine_a.replay_turn(&entry.board, &our_snake.id, entry.turn)?;
+            let (move_b, score_b, depth_b, _) = engine_b.replay_turn(&entry.board, &our_snake.id, entry.turn)?;
+
+            let breakdown = if move_a != move_b {
+                Some((
+                    Bot::evaluate_move_detailed(&entry.board, &our_snake.id, move_a, config_a)?,
+                    Bot::evaluate_move_detailed(&entry.board, &our_snake.id, move_b, config_b)?,
+                ))
+            } else {
+                None
+            };
+            comparison.turns.push(ComparedTurn {
+                turn: entry.turn,
+                logged_move,
+                move_a,
+                move_b,
+                score_a,
+                score_b,
+                depth_a,
+                depth_b,
+                breakdown,
+            });
+        }
+        Ok(comparison)
+    }
+
+    /// Prints per-turn divergences and aggregate statistics of a comparison
+    pub fn print_comparison(&self, comparison: &ConfigComparison, name_a: &str, name_b: &str) {
+        let total = comparison.turns.len();
+        let (a_log, b_log) = comparison.log_agreement();
+        let pct = |n: usize| if total == 0 { 0.0 } else { n as f64 / total as f64 * 100.0 };
+
+        println!("\n═══════════════════════════════════════════════════════════");
+        println!("                  CONFIG COMPARISON");
+        println!("═══════════════════════════════════════════════════════════");
+        println!("A: {}", name_a);
+        println!("B: {}", name_b);
+        println!("Total Turns:      {}", total);
+        println!("Divergent Turns:  {} ({:.1}%)", comparison.divergent(), comparison.divergence_rate());
+        println!("A matches log:    {} ({:.1}%)", a_log, pct(a_log));
+        println!("B matches log:    {} ({:.1}%)", b_log, pct(b_log));
+        println!("═══════════════════════════════════════════════════════════\n");
+
+        for t in comparison.turns.iter().filter(|t| t.diverges()) {
+            println!(
+                "Turn {}: A {} (score {}, depth {}) vs B {} (score {}, depth {}), logged {}",
+                t.turn,
+                t.move_a.as_str(),
+                t.score_a,
+                t.depth_a,
+                t.move_b.as_str(),
+                t.score_b,
+                t.depth_b,
+                t.logged_move.as_str()
+            );
+            if let Some((a, b)) = &t.breakdown {
+                println!("  {:<16} {:>12} {:>12}", "component", format!("A:{}", t.move_a.as_str()), format!("B:{}", t.move_b.as_str()));
+                for ((name, va), (_, vb)) in a.components().iter().zip(b.components().iter()) {
+                    println!("  {:<16} {:>12} {:>12}", name, va, vb);
+                }
+                println!("  {:<16} {:>12} {:>12}", "total", a.total, b.total);
             }
             println!();
         }
     }
 
+    /// Plays `entry` forward twice, once with our first move forced to `forced` and once with
+    /// the bot's own choice; the bot moves every snake after that, turns are resolved by the
+    /// official rules and food spawns per [arena], seeded from what_if.seed
+    pub fn what_if(&self, entry: &LogEntry, forced: Direction) -> Result<WhatIfReport, BotError> {
+        let us = self.snake.find(entry)?.id.clone();
+        let (bot_move, _, _, _) = self.replay_turn(&entry.board, &us, entry.turn)?;
+        // Roll forward under the game's own ruleset settings when the request was logged
+        let ruled = match entry.request_state(&self.config) {
+            Some(Ok(state)) => self.config.with_ruleset(&state.game.ruleset.settings),
+            _ => self.config.clone(),
+        };
+        Ok(WhatIfReport {
+            turn: entry.turn,
+            forced: self.roll_forward(&entry.board, &us, entry.turn, forced, &ruled)?,
+            baseline: self.roll_forward(&entry.board, &us, entry.turn, bot_move, &ruled)?,
+        })
+    }
+
+    fn roll_forward(&self, start: &Board, us: &str, turn: i32, first_move: Direction, rules: &Config) -> Result<WhatIfLine, BotError> {
+        let settings = RulesSettings::from_config(rules);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(self.config.what_if.seed);
+        let multiplayer = start.snakes.len() > 1;
+        let mut board = start.clone();
+        let mut line = WhatIfLine { moves: Vec::new(), turns_survived: 0, death: None, won: false };
+
+        for t in 0..self.config.what_if.horizon {
+            let mut moves: HashMap<String, Direction> = HashMap::new();
+            for snake in &board.snakes {
+                let dir = if t == 0 && snake.id == us {
+                    first_move
+                } else {
+                    self.replay_turn(&board, &snake.id, turn + t)?.0
+                };
+                moves.insert(snake.id.clone(), dir);
+            }
+            line.moves.push(moves[us]);
+
+            let eliminations = rules::resolve_turn(&mut board, &moves, &settings);
+            if let Some(death) = eliminations.into_iter().find(|e| e.snake_id == us) {
+                line.death = Some(death);
+                return Ok(line);
+            }
+            line.turns_survived = t + 1;
+            if multiplayer && board.snakes.len() == 1 {
+                line.won = true;
+                return Ok(line);
+            }
+            rules::spawn_food(&mut board, &rules.arena, &mut rng);
+        }
+        Ok(line)
+    }
+
+    /// Why a mismatched turn diverged: the evaluation components (one-ply DetailedScore)
+    /// that differ most between the replayed and the original move, e.g.
+    /// "diverged due to space (+1200) vs health (-900)" (replayed minus original)
+    /// None for matching turns or when the moves evaluate identically
+    pub fn divergence_cause(&self, result: &ReplayResult) -> Option<String> {
+        if result.matches {
+            return None;
+        }
+        let original = Bot::evaluate_move_detailed(&result.board, &result.our_id, result.original_move, &self.config).ok()?;
+        let replayed = Bot::evaluate_move_detailed(&result.board, &result.our_id, result.replayed_move, &self.config).ok()?;
+        Self::describe_divergence("diverged due to", &replayed, &original, result)
+    }
+
+    /// Same as divergence_cause for the ends of the two moves' winning lines (explain mode),
+    /// e.g. "winning lines end apart in space (+3400) vs length (-100)"
+    pub fn leaf_divergence_cause(result: &ReplayResult) -> Option<String> {
+        if result.matches {
+            return None;
+        }
+        let (replayed, original) = (result.replayed_leaf.as_ref()?, result.original_leaf.as_ref()?);
+        Self::describe_divergence("winning lines end apart in", replayed, original, result)
+    }
+
+    /// "<prefix> <two largest component differences> [replayed minus original, eval total]"
+    fn describe_divergence(prefix: &str, replayed: &DetailedScore, original: &DetailedScore, result: &ReplayResult) -> Option<String> {
+        let diffs = replayed.differences(original);
+        if diffs.is_empty() {
+            return None;
+        }
+
+        let top: Vec<String> = diffs.iter().take(2).map(|(name, diff)| format!("{} ({:+})", name, diff)).collect();
+        Some(format!(
+            "{} {} [{} minus {}, eval total {:+}]",
+            prefix,
+            top.join(" vs "),
+            result.replayed_move.as_str(),
+            result.original_move.as_str(),
+            i64::from(replayed.total) - i64::from(original.total)
+        ))
+    }
+
     /// Validates that specific expected moves were made
     pub fn validate_expected_moves(
         &self,
         entries: &[LogEntry],
         expected_moves: &[(i32, Vec<Direction>)], // (turn, acceptable_moves)
-    ) -> Result<(), String> {
+    ) -> Result<(), BotError> {
         for (turn, acceptable) in expected_moves {
-            let entry = entries
-                .iter()
-                .find(|e| e.turn == *turn)
-                .ok_or_else(|| format!("Turn {} not found in log", turn))?;
+            let entry = entries.iter().find(|e| e.turn == *turn).ok_or(BotError::TurnNotFound(*turn))?;
 
             let actual_move = Self::parse_direction(&entry.chosen_move)?;
 
             if !acceptable.contains(&actual_move) {
-                return Err(format!(
+                return Err(BotError::UnexpectedMoves(format!(
                     "Turn {}: Expected one of {:?}, but got {}",
                     turn,
                     acceptable.iter().map(|d| d.as_str()).collect::<Vec<_>>(),
                     actual_move.as_str()
-                ));
+                )));
             }
         }
 
@@ -356,13 +973,13 @@ impl ReplayEngine {
     }
 
     /// Helper to parse direction string
-    fn parse_direction(s: &str) -> Result<Direction, String> {
+    pub(crate) fn parse_direction(s: &str) -> Result<Direction, BotError> {
         match s.to_lowercase().as_str() {
             "up" => Ok(Direction::Up),
             "down" => Ok(Direction::Down),
             "left" => Ok(Direction::Left),
             "right" => Ok(Direction::Right),
-            _ => Err(format!("Invalid direction: {}", s)),
+            _ => Err(BotError::InvalidDirection(s.to_string())),
         }
     }
 
@@ -405,4 +1022,80 @@ mod tests {
         // Test invalid direction
         assert!(ReplayEngine::parse_direction("invalid").is_err());
     }
+
+    fn entry(turn: i32, board: Board) -> LogEntry {
+        LogEntry {
+            turn,
+            chosen_move: "up".to_string(),
+            board,
+            timestamp: String::new(),
+            result: None,
+            notes: TurnNotes::default(),
+        }
+    }
+
+    #[test]
+    fn test_entry_filter_combines_conditions() {
+        use crate::board_builder::BoardBuilder;
+        let duel = |health| BoardBuilder::new(7, 7).snake("us", &[(1, 1)]).snake("them", &[(5, 5)]).health("us", health).build();
+        let crowded = BoardBuilder::new(7, 7).snake("us", &[(1, 1)]).snake("b", &[(5, 5)]).snake("c", &[(3, 3)]).build();
+        let entries = vec![entry(0, crowded.clone()), entry(1, crowded), entry(2, duel(40)), entry(3, duel(90)), entry(4, duel(20))];
+        let turns = |filter: &EntryFilter| filter.select(&entries).iter().map(|e| e.turn).collect::<Vec<_>>();
+
+        assert_eq!(EntryFilter::parse_turns("5, 1..3").unwrap(), vec![(5, 5), (1, 3)]);
+        assert!(EntryFilter::parse_turns("4..2").is_err());
+        assert_eq!(turns(&EntryFilter::default()), vec![0, 1, 2, 3, 4]);
+        assert_eq!(turns(&EntryFilter { turns: vec![(1, 3)], ..Default::default() }), vec![1, 2, 3]);
+        assert_eq!(turns(&EntryFilter { min_snakes: Some(3), ..Default::default() }), vec![0, 1]);
+        assert_eq!(turns(&EntryFilter { near_death: Some(2), ..Default::default() }), vec![3, 4]);
+        assert_eq!(
+            turns(&EntryFilter { near_death: Some(3), low_health: Some(50), ..Default::default() }),
+            vec![2, 4]
+        );
+
+        // We were the last snake standing: no death to approach
+        let won = vec![entry(0, duel(90)), entry(1, BoardBuilder::new(7, 7).snake("us", &[(1, 1)]).build())];
+        assert!(EntryFilter { near_death: Some(5), ..Default::default() }.select(&won).is_empty());
+    }
+
+    #[test]
+    fn test_replay_searches_the_logged_snake() {
+        use crate::board_builder::BoardBuilder;
+        let board = BoardBuilder::new(7, 7).snake("them", &[(5, 5), (5, 4), (5, 3)]).snake("us", &[(0, 3), (0, 2), (0, 1)]).build();
+        let mut logged = entry(3, board.clone());
+        assert_eq!(logged.our_snake().unwrap().id, "them", "older logs: first snake");
+        logged.notes.you = Some("us".to_string());
+        assert_eq!(logged.our_snake().unwrap().id, "us");
+
+        let engine = ReplayEngine::new(Config::default_hardcoded(), false).with_fixed_depth(Some(2));
+        let result = engine.replay_entry(&logged).unwrap();
+        assert_eq!(result.our_id, "us");
+        assert!(![Direction::Left, Direction::Down].contains(&result.replayed_move), "{:?} is illegal for us", result.replayed_move);
+
+        logged.notes.you = Some("gone".to_string());
+        assert!(matches!(engine.replay_entry(&logged), Err(BotError::SnakeNotFound(id)) if id == "gone"));
+        assert!(matches!(engine.replay_entry(&entry(3, BoardBuilder::new(7, 7).build())), Err(BotError::NoSnakes)));
+    }
+
+    #[test]
+    fn test_snake_selector_matches_id_then_name() {
+        use crate::board_builder::BoardBuilder;
+        let mut board = BoardBuilder::new(7, 7).snake("gs_1", &[(1, 1)]).snake("gs_2", &[(5, 5)]).build();
+        board.snakes[1].name = "our-snake".to_string();
+        let mut imported = entry(0, board);
+        let find = |id: Option<&str>, name: Option<&str>, e: &LogEntry| {
+            let selector = SnakeSelector { id: id.map(String::from), name: name.map(String::from) };
+            selector.find(e).map(|s| s.id.clone())
+        };
+
+        assert_eq!(find(None, None, &imported).unwrap(), "gs_1");
+        assert_eq!(find(None, Some("our-snake"), &imported).unwrap(), "gs_2");
+        assert_eq!(find(Some("gs_1"), Some("our-snake"), &imported).unwrap(), "gs_1", "the id wins");
+        assert_eq!(find(Some("old-id"), Some("our-snake"), &imported).unwrap(), "gs_2", "then the name");
+        assert!(matches!(find(None, Some("nobody"), &imported), Err(BotError::SnakeNotFound(name)) if name == "nobody"));
+
+        imported.notes.you = Some("gs_2".to_string());
+        assert_eq!(find(None, None, &imported).unwrap(), "gs_2");
+        assert_eq!(find(Some("gs_1"), None, &imported).unwrap(), "gs_1", "given ids override the logged one");
+    }
 }
@@ -0,0 +1,243 @@
+// Defensive checks on incoming game states
+//
+// The engine is trusted to send well-formed boards, but a malformed payload (zero-size
+// board, a snake without a body, coordinates off the board) used to flow straight into
+// move generation and flood fills, where it could panic or allocate absurd grids. Requests
+// are checked before they reach the Bot: problems that leave no sensible move to compute
+// are rejected (400), recoverable inconsistencies are repaired and logged.
+
+use crate::config::Config;
+use crate::types::{Coord, GameState};
+
+/// Repairs and rejections for one request
+pub type Repairs = Vec<String>;
+
+/// Validates `state` in place
+///
+/// # Returns
+/// * `Ok(repairs)` - the state is usable; `repairs` describes what was changed (often empty)
+/// * `Err(reason)` - the state cannot be played and the request should be rejected
+pub fn sanitize(state: &mut GameState, config: &Config) -> Result<Repairs, String> {
+    let mut repairs = Repairs::new();
+    let max = config.request_validation.max_board_dimension;
+    let (width, height) = (state.board.width, state.board.height as i32);
+
+    if width <= 0 || height <= 0 {
+        return Err(format!("board must not be empty (got {}x{})", width, height));
+    }
+    if width > max || height > max {
+        return Err(format!("board {}x{} exceeds the {}x{} limit", width, height, max, max));
+    }
+    let in_bounds = |c: &Coord| c.x >= 0 && c.y >= 0 && c.x < width && c.y < height;
+
+    if state.you.body.is_empty() {
+        return Err("'you' has an empty body".to_string());
+    }
+    if !state.you.body.iter().all(in_bounds) {
+        return Err("'you' has body segments outside the board".to_string());
+    }
+
+    if state.turn < 0 {
+        repairs.push(format!("turn {} clamped to 0", state.turn));
+        state.turn = 0;
+    }
+
+    let before = state.board.food.len();
+    state.board.food.retain(in_bounds);
+    if state.board.food.len() < before {
+        repairs.push(format!("dropped {} food outside the board", before - state.board.food.len()));
+    }
+    let before = state.board.hazards.len();
+    state.board.hazards.retain(in_bounds);
+    if state.board.hazards.len() < before {
+        repairs.push(format!("dropped {} hazards outside the board", before - state.board.hazards.len()));
+    }
+
+    // Opponents that cannot be placed on the board are dropped, as are duplicate ids
+    let you_id = state.you.id.clone();
+    let mut seen: Vec<String> = Vec::with_capacity(state.board.snakes.len());
+    state.board.snakes.retain(|snake| {
+        let keep = if seen.contains(&snake.id) {
+            repairs.push(format!("dropped duplicate snake '{}'", snake.id));
+            false
+        } else if snake.body.is_empty() {
+            repairs.push(format!("dropped snake '{}' with an empty body", snake.id));
+            false
+        } else if snake.id != you_id && !snake.body.iter().all(in_bounds) {
+            repairs.push(format!("dropped snake '{}' with segments outside the board", snake.id));
+            false
+        } else {
+            true
+        };
+        seen.push(snake.id.clone());
+        keep
+    });
+
+    // The board is the source of truth for us; add 'you' if the engine left it out
+    match state.board.snakes.iter().position(|s| s.id == you_id) {
+        Some(idx) if !state.board.snakes[idx].body.iter().all(in_bounds) => {
+            return Err("'you' has body segments outside the board".to_string());
+        }
+        Some(_) => {}
+        None => {
+            repairs.push("'you' missing from board.snakes, added".to_string());
+            state.board.snakes.insert(0, state.you.clone());
+        }
+    }
+
+    let max_snakes = config.request_validation.max_snakes;
+    if state.board.snakes.len() > max_snakes {
+        return Err(format!("{} snakes exceed the limit of {}", state.board.snakes.len(), max_snakes));
+    }
+
+    let health_max = config.scores.health_max as i32;
+    for snake in state.board.snakes.iter_mut() {
+        if snake.head != snake.body[0] {
+            repairs.push(format!("snake '{}' head moved to body[0]", snake.id));
+            snake.head = snake.body[0];
+        }
+        if snake.length != snake.body.len() as i32 {
+            repairs.push(format!("snake '{}' length {} set to {}", snake.id, snake.length, snake.body.len()));
+            snake.length = snake.body.len() as i32;
+        }
+        if !(0..=health_max).contains(&snake.health) {
+            let clamped = snake.health.clamp(0, health_max);
+            repairs.push(format!("snake '{}' health {} clamped to {}", snake.id, snake.health, clamped));
+            snake.health = clamped;
+        }
+    }
+
+    if let Some(board_you) = state.board.snakes.iter().find(|s| s.id == you_id) {
+        if board_you.body != state.you.body || board_you.health != state.you.health {
+            repairs.push("'you' replaced by its board.snakes entry".to_string());
+        }
+        state.you = board_you.clone();
+    }
+
+    Ok(repairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Battlesnake, Board, Game, Ruleset};
+
+    fn snake(id: &str, body: Vec<(i32, i32)>) -> Battlesnake {
+        let body: Vec<Coord> = body.into_iter().map(|(x, y)| Coord { x, y }).collect();
+        Battlesnake {
+            id: id.to_string(),
+            name: id.to_string(),
+            health: 90,
+            head: body.first().copied().unwrap_or(Coord { x: 0, y: 0 }),
+            length: body.len() as i32,
+            body,
+            latency: "0".to_string(),
+            shout: None,
+            squad: None,
+        }
+    }
+
+    fn state() -> GameState {
+        let you = snake("us", vec![(1, 1), (1, 0)]);
+        GameState {
+            game: Game { id: "g".to_string(), ruleset: Ruleset::default(), timeout: 500, map: String::new() },
+            turn: 4,
+            board: Board {
+                height: 7,
+                width: 7,
+                food: vec![Coord { x: 3, y: 3 }],
+                snakes: vec![you.clone(), snake("them", vec![(5, 5), (5, 6)])],
+                hazards: vec![],
+                occupancy: None,
+            },
+            you,
+        }
+    }
+
+    fn sanitize_default(state: &mut GameState) -> Result<Repairs, String> {
+        sanitize(state, &Config::default_hardcoded())
+    }
+
+    #[test]
+    fn test_well_formed_state_is_untouched() {
+        let mut s = state();
+        assert_eq!(sanitize_default(&mut s), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_zero_size_and_oversized_boards_are_rejected() {
+        let mut s = state();
+        s.board.width = 0;
+        assert!(sanitize_default(&mut s).is_err());
+
+        let mut s = state();
+        s.board.height = 10_000;
+        assert!(sanitize_default(&mut s).is_err());
+
+        let mut config = Config::default_hardcoded();
+        config.request_validation.max_snakes = 1;
+        assert!(sanitize(&mut state(), &config).unwrap_err().contains("2 snakes"));
+    }
+
+    #[test]
+    fn test_you_without_body_or_off_board_is_rejected() {
+        let mut s = state();
+        s.you.body.clear();
+        assert!(sanitize_default(&mut s).is_err());
+
+        let mut s = state();
+        s.you.body[0] = Coord { x: 7, y: 1 };
+        assert!(sanitize_default(&mut s).is_err());
+    }
+
+    #[test]
+    fn test_you_missing_from_board_is_added() {
+        let mut s = state();
+        s.board.snakes.remove(0);
+        let repairs = sanitize_default(&mut s).unwrap();
+
+        assert_eq!(repairs.len(), 1);
+        assert_eq!(s.board.snakes[0].id, "us");
+    }
+
+    #[test]
+    fn test_broken_opponents_are_dropped() {
+        let mut s = state();
+        s.board.snakes.push(snake("empty", vec![]));
+        s.board.snakes.push(snake("outside", vec![(9, 9)]));
+        s.board.snakes.push(snake("them", vec![(0, 6)]));
+        let repairs = sanitize_default(&mut s).unwrap();
+
+        assert_eq!(repairs.len(), 3);
+        let ids: Vec<&str> = s.board.snakes.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["us", "them"]);
+        assert_eq!(s.board.snakes[1].body[0], Coord { x: 5, y: 5 });
+    }
+
+    #[test]
+    fn test_inconsistent_fields_are_repaired() {
+        let mut s = state();
+        s.turn = -3;
+        s.board.food.push(Coord { x: -1, y: 2 });
+        s.board.hazards.push(Coord { x: 2, y: 70 });
+        s.board.snakes[1].head = Coord { x: 0, y: 0 };
+        s.board.snakes[1].length = 12;
+        s.board.snakes[1].health = 250;
+        let repairs = sanitize_default(&mut s).unwrap();
+
+        assert_eq!(repairs.len(), 6);
+        assert_eq!(s.turn, 0);
+        assert_eq!(s.board.food, vec![Coord { x: 3, y: 3 }]);
+        assert!(s.board.hazards.is_empty());
+        let them = &s.board.snakes[1];
+        assert_eq!((them.head, them.length, them.health), (Coord { x: 5, y: 5 }, 2, 100));
+    }
+
+    #[test]
+    fn test_you_follows_board_entry() {
+        let mut s = state();
+        s.you.health = 12;
+        sanitize_default(&mut s).unwrap();
+        assert_eq!(s.you.health, 90);
+    }
+}
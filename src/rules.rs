@@ -0,0 +1,341 @@
+// Reference implementation of the official Battlesnake standard rules
+//
+// The search simulates turns with Bot::apply_move / Bot::advance_game_state, which are tuned
+// for speed and apply snakes one at a time. This module resolves a whole turn the way the
+// official rules engine (BattlesnakeOfficial/rules, standard pipeline) does, stage by stage:
+//   1. movement     every snake moves (snakes without a move continue straight)
+//   2. starvation   every snake loses one health
+//   3. hazards      snakes whose head is in a hazard (and not on food) take hazard damage
+//   4. feeding      snakes whose head is on food eat: full health, tail segment duplicated
+//   5. elimination  out of health / out of bounds first, then self, body and head-to-head
+//                   collisions, all decided against the same post-move board
+// Food spawning is random and left to the caller (spawn_food). Eliminated snakes are removed from the board,
+// as in the next request the engine sends. It is the oracle for rules_oracle_tests.rs.
+// With squad settings (squad ruleset) collisions between squad-mates are forgiven when
+// allowBodyCollisions is set, a squad falls together under sharedElimination, and the
+// survivors share health and length (squad::share_attributes) after elimination.
+
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::Rng;
+use std::collections::HashMap;
+
+use crate::config::{ArenaConfig, Config};
+use crate::squad::{self, SquadRules};
+use crate::types::{Board, Coord, Direction};
+
+/// Rule parameters that vary between rulesets
+#[derive(Debug, Clone, PartialEq)]
+pub struct RulesSettings {
+    pub health_on_food: i32,
+    pub health_loss_per_turn: i32,
+    pub hazard_damage_per_turn: i32,
+    /// Squad settings (None outside the squad ruleset)
+    pub squad: Option<SquadRules>,
+}
+
+impl RulesSettings {
+    /// Standard rules with the values from [game_rules]
+    pub fn from_config(config: &Config) -> Self {
+        RulesSettings {
+            health_on_food: config.game_rules.health_on_food as i32,
+            health_loss_per_turn: config.game_rules.health_loss_per_turn as i32,
+            hazard_damage_per_turn: config.game_rules.hazard_damage_per_turn,
+            squad: config.game_mode.squad_rules(),
+        }
+    }
+}
+
+/// Why a snake was eliminated (official EliminatedCause values)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EliminationCause {
+    OutOfHealth,
+    OutOfBounds,
+    SelfCollision,
+    /// Head ran into the body of the snake with this id
+    Collision(String),
+    /// Lost (or tied) a head-to-head against the snake with this id
+    HeadToHead(String),
+    /// Eliminated along with the squad-mate with this id (squad sharedElimination)
+    BySquad(String),
+}
+
+/// A snake removed from the board this turn
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elimination {
+    pub snake_id: String,
+    pub cause: EliminationCause,
+}
+
+/// Direction of a one-step move from `from` to `to` (None if not adjacent)
+pub fn direction_between(from: Coord, to: Coord) -> Option<Direction> {
+    Direction::from_delta(to - from)
+}
+
+/// Move the engine uses when a snake sent none: continue from neck to head, else up
+pub fn default_move(body: &[Coord]) -> Direction {
+    match body {
+        [head, neck, ..] if head != neck => direction_between(*neck, *head).unwrap_or(Direction::Up),
+        _ => Direction::Up,
+    }
+}
+
+/// Resolves one turn in place and returns the eliminations
+///
+/// # Arguments
+/// * `board` - Board at the start of the turn; becomes the board of the next turn
+/// * `moves` - Move per snake id (missing snakes use `default_move`)
+/// * `settings` - Ruleset parameters
+pub fn resolve_turn(board: &mut Board, moves: &HashMap<String, Direction>, settings: &RulesSettings) -> Vec<Elimination> {
+    board.clear_occupancy();
+    // 1. Movement
+    for snake in board.snakes.iter_mut().filter(|s| !s.body.is_empty()) {
+        let dir = moves.get(&snake.id).copied().unwrap_or_else(|| default_move(&snake.body));
+        let new_head = dir.apply(&snake.body[0]);
+        snake.body.insert(0, new_head);
+        snake.body.pop();
+        snake.head = new_head;
+    }
+
+    // 2. Starvation
+    for snake in board.snakes.iter_mut() {
+        snake.health -= settings.health_loss_per_turn;
+    }
+
+    // 3. Hazard damage (not on the turn a snake eats)
+    for snake in board.snakes.iter_mut().filter(|s| !s.body.is_empty()) {
+        let head = snake.body[0];
+        if board.hazards.contains(&head) && !board.food.contains(&head) {
+            snake.health = (snake.health - settings.hazard_damage_per_turn).max(0);
+        }
+    }
+
+    // 4. Feeding: every snake whose head is on a food eats it
+    let mut eaten: Vec<Coord> = Vec::new();
+    for snake in board.snakes.iter_mut().filter(|s| !s.body.is_empty()) {
+        let head = snake.body[0];
+        if board.food.contains(&head) {
+            snake.health = settings.health_on_food;
+            let tail = *snake.body.last().unwrap_or(&head);
+            snake.body.push(tail);
+            if !eaten.contains(&head) {
+                eaten.push(head);
+            }
+        }
+    }
+    board.food.retain(|f| !eaten.contains(f));
+    for snake in board.snakes.iter_mut() {
+        snake.length = snake.body.len() as i32;
+    }
+
+    // 5. Elimination
+    let eliminations = eliminate(board, settings.squad.as_ref());
+    board.snakes.retain(|s| !eliminations.iter().any(|e| e.snake_id == s.id));
+    if let Some(rules) = &settings.squad {
+        squad::share_attributes(board, rules);
+    }
+    eliminations
+}
+
+fn eliminate(board: &Board, squad_rules: Option<&SquadRules>) -> Vec<Elimination> {
+    let mut eliminations = Vec::new();
+    let in_bounds = |c: &Coord| c.x >= 0 && c.y >= 0 && c.x < board.width && c.y < board.height as i32;
+
+    // First order: health and bounds, decided per snake
+    for snake in &board.snakes {
+        let cause = if snake.health <= 0 {
+            Some(EliminationCause::OutOfHealth)
+        } else if !snake.body.iter().all(in_bounds) {
+            Some(EliminationCause::OutOfBounds)
+        } else {
+            None
+        };
+        if let Some(cause) = cause {
+            eliminations.push(Elimination { snake_id: snake.id.clone(), cause });
+        }
+    }
+
+    // Collisions are checked among the survivors of the first order and applied together
+    let alive: Vec<_> = board
+        .snakes
+        .iter()
+        .filter(|s| !s.body.is_empty() && !eliminations.iter().any(|e| e.snake_id == s.id))
+        .collect();
+    let passes_allies = squad_rules.is_some_and(|rules| rules.allow_body_collisions);
+    let mut collisions = Vec::new();
+    for snake in &alive {
+        let head = snake.body[0];
+        let rivals = || alive.iter().filter(|o| o.id != snake.id && !(passes_allies && snake.is_ally_of(o)));
+        let cause = if snake.body[1..].contains(&head) {
+            Some(EliminationCause::SelfCollision)
+        } else if let Some(other) = rivals().find(|o| o.body[1..].contains(&head)) {
+            Some(EliminationCause::Collision(other.id.clone()))
+        } else {
+            rivals()
+                .find(|o| o.body[0] == head && snake.body.len() <= o.body.len())
+                .map(|other| EliminationCause::HeadToHead(other.id.clone()))
+        };
+        if let Some(cause) = cause {
+            collisions.push(Elimination { snake_id: snake.id.clone(), cause });
+        }
+    }
+
+    eliminations.extend(collisions);
+
+    // A squad falls with any of its members
+    if squad_rules.is_some_and(|rules| rules.shared_elimination) {
+        let fallen: Vec<usize> = (0..board.snakes.len())
+            .filter(|&idx| eliminations.iter().any(|e| e.snake_id == board.snakes[idx].id))
+            .collect();
+        for (idx, mate) in squad::shared_eliminations(board, &fallen) {
+            eliminations.push(Elimination { snake_id: board.snakes[idx].id.clone(), cause: EliminationCause::BySquad(mate) });
+        }
+    }
+    eliminations
+}
+
+/// Standard food spawning (arena.minimum_food, arena.food_spawn_chance): top up to minimum_food, otherwise a chance of one new food
+pub fn spawn_food(board: &mut Board, arena: &ArenaConfig, rng: &mut StdRng) {
+    let wanted = if board.food.len() < arena.minimum_food {
+        arena.minimum_food - board.food.len()
+    } else if rng.random_range(0..100) < arena.food_spawn_chance {
+        1
+    } else {
+        0
+    };
+    if wanted == 0 {
+        return;
+    }
+
+    let free: Vec<Coord> = (0..board.width)
+        .flat_map(|x| (0..board.height as i32).map(move |y| Coord { x, y }))
+        .filter(|c| !board.food.contains(c) && !board.snakes.iter().any(|s| s.body.contains(c)))
+        .collect();
+    board.food.extend(free.choose_multiple(rng, wanted));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Battlesnake;
+
+    fn snake(id: &str, body: &[(i32, i32)]) -> Battlesnake {
+        let body: Vec<Coord> = body.iter().map(|&(x, y)| Coord { x, y }).collect();
+        Battlesnake {
+            id: id.to_string(),
+            name: id.to_string(),
+            health: 50,
+            head: body[0],
+            length: body.len() as i32,
+            body,
+            latency: "0".to_string(),
+            shout: None,
+            squad: None,
+        }
+    }
+
+    fn board(snakes: Vec<Battlesnake>, food: &[(i32, i32)]) -> Board {
+        Board {
+            height: 7,
+            width: 7,
+            food: food.iter().map(|&(x, y)| Coord { x, y }).collect(),
+            snakes,
+            hazards: vec![],
+            occupancy: None,
+        }
+    }
+
+    fn settings() -> RulesSettings {
+        RulesSettings::from_config(&Config::default_hardcoded())
+    }
+
+    fn moves(list: &[(&str, Direction)]) -> HashMap<String, Direction> {
+        list.iter().map(|&(id, dir)| (id.to_string(), dir)).collect()
+    }
+
+    #[test]
+    fn test_eating_duplicates_the_tail() {
+        let mut b = board(vec![snake("a", &[(1, 1), (1, 0), (0, 0)])], &[(1, 2)]);
+        let out = resolve_turn(&mut b, &moves(&[("a", Direction::Up)]), &settings());
+
+        assert!(out.is_empty());
+        let a = &b.snakes[0];
+        assert_eq!(a.body, vec![Coord { x: 1, y: 2 }, Coord { x: 1, y: 1 }, Coord { x: 1, y: 0 }, Coord { x: 1, y: 0 }]);
+        assert_eq!((a.health, a.length), (100, 4));
+        assert!(b.food.is_empty());
+    }
+
+    #[test]
+    fn test_head_to_head_and_body_collision_use_the_same_board() {
+        // b and c meet head-on at (3,3) with equal length: both die.
+        // a runs into c's neck; c's body still counts although c dies the same turn.
+        let mut b = board(
+            vec![
+                snake("a", &[(4, 4), (5, 4), (6, 4)]),
+                snake("b", &[(2, 3), (1, 3), (0, 3)]),
+                snake("c", &[(4, 3), (5, 3), (6, 3)]),
+            ],
+            &[],
+        );
+        let out = resolve_turn(
+            &mut b,
+            &moves(&[("a", Direction::Down), ("b", Direction::Right), ("c", Direction::Left)]),
+            &settings(),
+        );
+
+        assert_eq!(out.len(), 3);
+        assert!(out.contains(&Elimination { snake_id: "a".into(), cause: EliminationCause::Collision("c".into()) }));
+        assert!(out.contains(&Elimination { snake_id: "b".into(), cause: EliminationCause::HeadToHead("c".into()) }));
+        assert!(out.contains(&Elimination { snake_id: "c".into(), cause: EliminationCause::HeadToHead("b".into()) }));
+        assert!(b.snakes.is_empty());
+    }
+
+    #[test]
+    fn test_stacked_tail_blocks_and_missing_move_goes_straight() {
+        // a just ate (stacked tail at (0,0)); b has no move and continues left into it
+        let mut b = board(
+            vec![snake("a", &[(0, 2), (0, 1), (0, 0), (0, 0)]), snake("b", &[(1, 0), (2, 0), (3, 0)])],
+            &[],
+        );
+        let out = resolve_turn(&mut b, &moves(&[("a", Direction::Right)]), &settings());
+
+        assert_eq!(out, vec![Elimination { snake_id: "b".into(), cause: EliminationCause::Collision("a".into()) }]);
+    }
+
+    #[test]
+    fn test_squad_mates_pass_through_each_other_and_fall_together() {
+        let mut a = snake("a", &[(3, 3), (3, 2), (3, 1)]);
+        let mut b = snake("b", &[(2, 4), (3, 4), (4, 4), (5, 4)]);
+        let mut c = snake("c", &[(0, 6), (1, 6)]);
+        for (s, squad) in [(&mut a, "red"), (&mut b, "red"), (&mut c, "blue")] {
+            s.squad = Some(squad.to_string());
+        }
+        a.health = 80;
+        let squad = RulesSettings { squad: Some(SquadRules::default()), ..settings() };
+        let turn = moves(&[("a", Direction::Up), ("b", Direction::Left), ("c", Direction::Down)]);
+
+        // a runs into its squad-mate's body: forgiven, and a takes b's length, b takes a's health
+        let mut shared = board(vec![a.clone(), b.clone(), c.clone()], &[]);
+        assert!(resolve_turn(&mut shared, &turn, &squad).is_empty());
+        assert_eq!((shared.snakes[0].length, shared.snakes[1].health), (4, 79));
+
+        // Without passing, a crashes into b and takes its squad-mate down too
+        let no_passing = RulesSettings { squad: Some(SquadRules { allow_body_collisions: false, ..SquadRules::default() }), ..settings() };
+        let mut fatal = board(vec![a, b, c], &[]);
+        let out = resolve_turn(&mut fatal, &turn, &no_passing);
+        assert!(out.contains(&Elimination { snake_id: "a".into(), cause: EliminationCause::Collision("b".into()) }));
+        assert!(out.contains(&Elimination { snake_id: "b".into(), cause: EliminationCause::BySquad("a".into()) }));
+        assert_eq!(out.len(), 2, "the other squad is unaffected");
+    }
+
+    #[test]
+    fn test_out_of_bounds_and_starvation() {
+        let mut starving = snake("s", &[(3, 3), (3, 2), (3, 1)]);
+        starving.health = 1;
+        let mut b = board(vec![snake("w", &[(0, 3), (1, 3), (2, 3)]), starving], &[]);
+        let out = resolve_turn(&mut b, &moves(&[("w", Direction::Left), ("s", Direction::Up)]), &settings());
+
+        assert_eq!(out[0].cause, EliminationCause::OutOfBounds);
+        assert_eq!(out[1].cause, EliminationCause::OutOfHealth);
+    }
+}
@@ -0,0 +1,271 @@
+// Stable, versioned result schema for external consumers
+//
+// Dashboards, notebooks and the web UI parse search results and score breakdowns.
+// These types are the contract: fields may be added (as Option with #[serde(default)])
+// but never renamed, retyped or removed. A breaking change gets a new V2 type and a
+// new schema_version, and the V1 types stay as they are.
+
+use serde::{Deserialize, Serialize};
+
+use crate::bot::{effective_branching_factor, DetailedScore, IterationStats};
+use crate::types::Direction;
+
+/// Schema version emitted in every SearchResultV1
+pub const SEARCH_RESULT_SCHEMA_VERSION: u32 = 1;
+
+/// Per-component evaluation of a single move (mirrors DetailedScore)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoreBreakdownV1 {
+    pub total: i32,
+    pub survival: i32,
+    pub health: i32,
+    pub space: i32,
+    pub control: i32,
+    pub attack: i32,
+    pub length: i32,
+    pub head_collision: i32,
+    pub wall_penalty: i32,
+    pub center_bias: i32,
+    /// Terms added after the first V1 logs (absent there)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trap_setting: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub endgame_parity: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub corner_danger: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length_advantage: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub growth_urgency: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tail_chasing: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub articulation: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trail_penalty: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temporal_discount: Option<i32>,
+}
+
+impl From<&DetailedScore> for ScoreBreakdownV1 {
+    fn from(score: &DetailedScore) -> Self {
+        ScoreBreakdownV1 {
+            total: score.total,
+            survival: score.survival,
+            health: score.health,
+            space: score.space,
+            control: score.control,
+            attack: score.attack,
+            length: score.length,
+            head_collision: score.head_collision,
+            wall_penalty: score.wall_penalty,
+            center_bias: score.center_bias,
+            trap_setting: Some(score.trap_setting),
+            endgame_parity: Some(score.endgame_parity),
+            corner_danger: Some(score.corner_danger),
+            length_advantage: Some(score.length_advantage),
+            growth_urgency: Some(score.growth_urgency),
+            tail_chasing: Some(score.tail_chasing),
+            articulation: Some(score.articulation),
+            trail_penalty: Some(score.trail_penalty),
+            temporal_discount: Some(score.temporal_discount),
+        }
+    }
+}
+
+/// Outcome of one move decision (live /move, replay, or analysis)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchResultV1 {
+    pub schema_version: u32,
+    pub turn: i32,
+    /// Lowercase move name as sent to the engine ("up", "down", "left", "right")
+    #[serde(rename = "move")]
+    pub chosen_move: String,
+    pub score: i32,
+    pub depth: u8,
+    pub time_ms: u64,
+    /// Search nodes visited (absent when the producer does not count nodes)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nodes: Option<u64>,
+    /// Evaluation breakdown of the chosen move (absent unless requested)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub breakdown: Option<ScoreBreakdownV1>,
+    /// Transposition table score probes during the search (absent in older logs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tt_probes: Option<u64>,
+    /// Probes that returned a cached score
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tt_hits: Option<u64>,
+    /// Probes that found another position in the slot (hash collisions; absent when none)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tt_collisions: Option<u64>,
+    /// Principal variation from the root: lowercase moves, snakes taking turns in search
+    /// order starting with the chosen move (absent in older logs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pv: Option<Vec<String>>,
+    /// Root move scores per iterative-deepening iteration, shallowest first (absent in older
+    /// logs). An iteration cut short by the clock lists only the moves it finished.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub candidates: Option<Vec<CandidateIterationV1>>,
+    /// Completed iterations with their own node counts, shallowest first (absent in older logs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iterations: Option<Vec<IterationV1>>,
+    /// Evaluation breakdown at the end of the winning line under the chosen move, i.e. why
+    /// the search scored it as it did (present only with debug.explain)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leaf_breakdown: Option<ScoreBreakdownV1>,
+}
+
+/// Cost of one completed iteration (mirrors IterationStats)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IterationV1 {
+    pub depth: u8,
+    pub time_ms: u64,
+    pub nodes: u64,
+}
+
+/// Final score of one root move in one iteration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CandidateScoreV1 {
+    #[serde(rename = "move")]
+    pub candidate_move: String,
+    pub score: i32,
+}
+
+/// Root move scores of one iteration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CandidateIterationV1 {
+    pub depth: u8,
+    /// In up, down, left, right order
+    pub scores: Vec<CandidateScoreV1>,
+}
+
+impl CandidateIterationV1 {
+    /// Best score minus second best (None with fewer than two moves)
+    pub fn margin(&self) -> Option<i64> {
+        let mut scores: Vec<i32> = self.scores.iter().map(|c| c.score).collect();
+        scores.sort_unstable_by(|a, b| b.cmp(a));
+        match scores.as_slice() {
+            [best, second, ..] => Some(*best as i64 - *second as i64),
+            _ => None,
+        }
+    }
+}
+
+impl SearchResultV1 {
+    pub fn new(turn: i32, chosen_move: Direction, score: i32, depth: u8, time_ms: u64) -> Self {
+        SearchResultV1 {
+            schema_version: SEARCH_RESULT_SCHEMA_VERSION,
+            turn,
+            chosen_move: chosen_move.as_str().to_string(),
+            score,
+            depth,
+            time_ms,
+            nodes: None,
+            breakdown: None,
+            tt_probes: None,
+            tt_hits: None,
+            tt_collisions: None,
+            pv: None,
+            candidates: None,
+            iterations: None,
+            leaf_breakdown: None,
+        }
+    }
+
+    pub fn with_nodes(mut self, nodes: u64) -> Self {
+        self.nodes = Some(nodes);
+        self
+    }
+
+    pub fn with_breakdown(mut self, breakdown: ScoreBreakdownV1) -> Self {
+        self.breakdown = Some(breakdown);
+        self
+    }
+
+    pub fn with_leaf_breakdown(mut self, breakdown: ScoreBreakdownV1) -> Self {
+        self.leaf_breakdown = Some(breakdown);
+        self
+    }
+
+    pub fn with_tt_stats(mut self, probes: u64, hits: u64) -> Self {
+        self.tt_probes = Some(probes);
+        self.tt_hits = Some(hits);
+        self
+    }
+
+    /// Records transposition hash collisions (left absent when there were none)
+    pub fn with_tt_collisions(mut self, collisions: u64) -> Self {
+        self.tt_collisions = Some(collisions).filter(|&n| n > 0);
+        self
+    }
+
+    /// Sets the principal variation (left absent when the line is empty)
+    pub fn with_pv(mut self, line: &[Direction]) -> Self {
+        if !line.is_empty() {
+            self.pv = Some(line.iter().map(|dir| dir.as_str().to_string()).collect());
+        }
+        self
+    }
+
+    /// Sets the candidate table from (depth, move, score) records (left absent when empty);
+    /// a move recorded twice at one depth keeps its last score
+    pub fn with_candidates(mut self, root_scores: &[(u8, Direction, i32)]) -> Self {
+        let mut depths: Vec<u8> = root_scores.iter().map(|&(depth, _, _)| depth).collect();
+        depths.sort_unstable();
+        depths.dedup();
+        let table: Vec<CandidateIterationV1> = depths
+            .into_iter()
+            .map(|depth| CandidateIterationV1 {
+                depth,
+                scores: Direction::ALL
+                    .iter()
+                    .filter_map(|&dir| {
+                        let score = root_scores.iter().rev().find(|&&(d, mv, _)| d == depth && mv == dir)?.2;
+                        Some(CandidateScoreV1 { candidate_move: dir.as_str().to_string(), score })
+                    })
+                    .collect(),
+            })
+            .collect();
+        if !table.is_empty() {
+            self.candidates = Some(table);
+        }
+        self
+    }
+
+    /// Sets the completed iterations (left absent when empty)
+    pub fn with_iterations(mut self, iterations: &[IterationStats]) -> Self {
+        if !iterations.is_empty() {
+            self.iterations = Some(
+                iterations.iter().map(|it| IterationV1 { depth: it.depth, time_ms: it.time_ms, nodes: it.nodes }).collect(),
+            );
+        }
+        self
+    }
+
+    /// Effective branching factor after the last logged iteration (see
+    /// bot::effective_branching_factor)
+    pub fn effective_branching_factor(&self) -> Option<f64> {
+        let iterations: Vec<IterationStats> = self
+            .iterations
+            .as_ref()?
+            .iter()
+            .map(|it| IterationStats { depth: it.depth, time_ms: it.time_ms, nodes: it.nodes })
+            .collect();
+        effective_branching_factor(&iterations)
+    }
+
+    /// Best-minus-second margin of the deepest iteration that scored two or more moves
+    /// (how close the decision was)
+    pub fn decision_margin(&self) -> Option<i64> {
+        self.candidates.as_ref()?.iter().rev().find_map(CandidateIterationV1::margin)
+    }
+
+    /// Share of transposition table probes that hit (None without telemetry or probes)
+    pub fn tt_hit_rate(&self) -> Option<f64> {
+        match (self.tt_probes, self.tt_hits) {
+            (Some(probes), Some(hits)) if probes > 0 => Some(hits as f64 / probes as f64),
+            _ => None,
+        }
+    }
+}
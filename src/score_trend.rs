@@ -0,0 +1,102 @@
+// Root score trend across turns
+//
+// A lost game rarely ends on the turn the search first saw it coming: the root score
+// collapses a few turns earlier, when the search reaches the trap or the lost race for
+// food. ScoreTrend lives in the game session and keeps the root scores of the last
+// score_trend.window searched turns. A score at least score_trend.drop_threshold below their
+// mean is a drop: /move logs a SCORE DROP warning with the board and the candidate scores
+// and tags the debug log entry ("score_drop"), so the turn the bot realized it was losing
+// can be found again. The window restarts after a drop, so a collapse is reported once
+// rather than on every turn that follows it.
+
+use std::collections::VecDeque;
+
+use crate::config::ScoreTrendConfig;
+use crate::types::Direction;
+
+/// Root scores of the recent searched turns of one game
+#[derive(Debug, Clone, Default)]
+pub struct ScoreTrend {
+    recent: VecDeque<i32>,
+}
+
+/// A turn whose root score fell well below the recent mean
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoreDrop {
+    pub turn: i32,
+    pub score: i32,
+    /// Mean root score of the turns before
+    pub baseline: i64,
+    /// Turns in the baseline
+    pub turns: usize,
+}
+
+impl ScoreDrop {
+    pub fn describe(&self) -> String {
+        format!(
+            "root score {} is {} below the mean {} of the previous {} turns",
+            self.score,
+            self.baseline - self.score as i64,
+            self.baseline,
+            self.turns
+        )
+    }
+}
+
+impl ScoreTrend {
+    /// Records the root score of `turn`; Some if it dropped below the recent mean by at
+    /// least drop_threshold (needs two earlier scores)
+    pub fn observe(&mut self, turn: i32, score: i32, config: &ScoreTrendConfig) -> Option<ScoreDrop> {
+        let drop = if self.recent.len() >= 2 {
+            let baseline = self.recent.iter().map(|&s| s as i64).sum::<i64>() / self.recent.len() as i64;
+            (baseline - score as i64 >= config.drop_threshold).then_some(ScoreDrop { turn, score, baseline, turns: self.recent.len() })
+        } else {
+            None
+        };
+        if drop.is_some() {
+            self.recent.clear();
+        }
+        self.recent.push_back(score);
+        while self.recent.len() > config.window {
+            self.recent.pop_front();
+        }
+        drop
+    }
+}
+
+/// Root move scores of the deepest iteration, e.g. "up -40, left -90"
+pub fn format_candidates(root_scores: &[(u8, Direction, i32)]) -> String {
+    let deepest = root_scores.iter().map(|&(depth, _, _)| depth).max().unwrap_or(0);
+    root_scores
+        .iter()
+        .filter(|&&(depth, _, _)| depth == deepest)
+        .map(|&(_, mv, score)| format!("{} {}", mv.as_str(), score))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drop_is_reported_once() {
+        let config = ScoreTrendConfig { enabled: true, window: 3, drop_threshold: 1000 };
+        let mut trend = ScoreTrend::default();
+        let drops: Vec<Option<ScoreDrop>> = [(1, 500), (2, 700), (3, 300), (4, -800), (5, -900), (6, -950), (7, -2500)]
+            .iter()
+            .map(|&(turn, score)| trend.observe(turn, score, &config))
+            .collect();
+
+        // Turn 4 is 1300 below the mean of 500, 700 and 300; turns 5 and 6 start a new window
+        assert_eq!(drops[3], Some(ScoreDrop { turn: 4, score: -800, baseline: 500, turns: 3 }));
+        assert_eq!(drops.iter().filter(|d| d.is_some()).count(), 2);
+        assert_eq!(drops[6].as_ref().map(|d| d.baseline), Some(-883));
+    }
+
+    #[test]
+    fn test_candidates_of_the_deepest_iteration() {
+        let scores = [(3, Direction::Up, 10), (4, Direction::Up, -40), (4, Direction::Left, -90)];
+        assert_eq!(format_candidates(&scores), "up -40, left -90");
+    }
+}
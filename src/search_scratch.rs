@@ -0,0 +1,113 @@
+// Per-thread scratch buffers for the search
+//
+// Every search node used to clone the board for each child. The recursion now borrows this
+// thread's SearchScratch once at the root and takes child boards from its pool, handing them
+// back when the node is done. Recycled boards are overwritten with clone_from, which keeps
+// their body/food/hazard allocations. Move lists need no pool: a MoveList lives on the stack
+// (move_list.rs).
+// Flood-fill grids have their own per-thread pool (flood_grid.rs) because they are used by
+// the evaluation, below the frames holding the scratch borrow.
+
+use std::cell::RefCell;
+
+use crate::types::Board;
+
+/// Pool of boards reused across search nodes on one thread
+pub struct SearchScratch {
+    boards: Vec<Board>,
+    /// Boards that had to be cloned from scratch because the pool was empty
+    board_allocations: usize,
+}
+
+thread_local! {
+    static SEARCH_SCRATCH: RefCell<SearchScratch> = RefCell::new(SearchScratch::new());
+}
+
+/// Runs `f` with this thread's scratch pools
+/// Must not be nested: call it at the search root and pass the &mut down the recursion
+pub fn with_search_scratch<R>(f: impl FnOnce(&mut SearchScratch) -> R) -> R {
+    SEARCH_SCRATCH.with(|scratch| f(&mut scratch.borrow_mut()))
+}
+
+impl Default for SearchScratch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SearchScratch {
+    pub fn new() -> Self {
+        SearchScratch {
+            boards: Vec::new(),
+            board_allocations: 0,
+        }
+    }
+
+    /// Copy of `parent`, backed by a recycled board when one is available
+    pub fn child_board(&mut self, parent: &Board) -> Board {
+        match self.boards.pop() {
+            Some(mut board) => {
+                board.clone_from(parent);
+                board
+            }
+            None => {
+                self.board_allocations += 1;
+                parent.clone()
+            }
+        }
+    }
+
+    /// Returns a board obtained from child_board to the pool
+    pub fn recycle_board(&mut self, board: Board) {
+        self.boards.push(board);
+    }
+
+    /// Number of boards allocated because the pool was empty (peak search depth, roughly)
+    pub fn board_allocations(&self) -> usize {
+        self.board_allocations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Battlesnake, Coord};
+
+    fn board() -> Board {
+        let body = vec![Coord { x: 1, y: 1 }, Coord { x: 1, y: 0 }, Coord { x: 0, y: 0 }];
+        Board {
+            height: 7,
+            width: 7,
+            food: vec![Coord { x: 3, y: 3 }],
+            snakes: vec![Battlesnake {
+                id: "a".to_string(),
+                name: "a".to_string(),
+                health: 90,
+                head: body[0],
+                length: body.len() as i32,
+                body,
+                latency: "0".to_string(),
+                shout: None,
+                squad: None,
+            }],
+            hazards: vec![],
+            occupancy: None,
+        }
+    }
+
+    #[test]
+    fn test_recycled_board_is_reused_and_overwritten() {
+        let mut scratch = SearchScratch::new();
+        let parent = board();
+
+        let mut child = scratch.child_board(&parent);
+        child.snakes[0].body.push(Coord { x: 0, y: 1 });
+        child.food.clear();
+        scratch.recycle_board(child);
+
+        let child = scratch.child_board(&parent);
+        assert_eq!(scratch.board_allocations(), 1, "second child must come from the pool");
+        assert_eq!(child.snakes[0].body, parent.snakes[0].body);
+        assert_eq!(child.food, parent.food);
+    }
+}
@@ -0,0 +1,228 @@
+// Per-game session state
+// Holds search knowledge that should survive between /move requests of the same game
+
+use parking_lot::{Mutex, RwLock};
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::Receiver;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::bot::HistoryTable;
+use crate::config::Config;
+use crate::game_mode::GameMode;
+use crate::game_summary::GameStats;
+use crate::latency::LatencyTracker;
+use crate::opening::OpeningPlan;
+use crate::schema::SearchResultV1;
+use crate::score_trend::ScoreTrend;
+use crate::stakes::Stakes;
+use crate::time_manager::TimeBank;
+use crate::timing_history::TimingHistory;
+use crate::types::Board;
+
+/// State carried across turns for a single game
+/// Created at /start (or lazily on the first /move) and dropped at /end, or once idle for
+/// concurrency.session_idle_timeout_ms (a game whose /end never came)
+pub struct GameSession {
+    /// Butterfly history table, aged between turns instead of rebuilt from scratch
+    pub history: HistoryTable,
+    /// Number of /move requests served for this game
+    pub turns_played: u32,
+    /// Duel opening plan chosen at /start (None outside duels or once finished)
+    pub opening: Option<OpeningPlan>,
+    /// Measured network overhead, adapts the per-turn budget
+    pub latency: LatencyTracker,
+    /// Move timeout of this game from /start (refreshed by every /move, 0 = not announced)
+    pub game_timeout_ms: u32,
+    /// Score swing between iterations of the previous search (time allocation input)
+    pub last_score_swing: Option<u32>,
+    /// Root score of the previous searched turn (time allocation input, see win_probability)
+    pub last_score: Option<i32>,
+    /// Time saved by earlier turns, spent on critical ones
+    pub time_bank: TimeBank,
+    /// Iteration times seen so far (seeded from the bot-wide history on the first move)
+    pub timings: TimingHistory,
+    /// Mode parsed at /start (or on the first /move if /start was missed)
+    pub mode: Option<GameMode>,
+    /// Stakes chosen at /start (stakes.default on the first /move if /start was missed)
+    pub stakes: Option<Stakes>,
+    /// Counters for the summary written at /end
+    pub stats: GameStats,
+    /// Search results of the last debug.post_mortem_turns moves, oldest first
+    pub recent_searches: VecDeque<SearchResultV1>,
+    /// Root scores of the recent turns, for score drop warnings
+    pub score_trend: ScoreTrend,
+    /// Disconnects once the last move's report (debug log entry, post-mortem record), built
+    /// after its response, is done; /end waits for it
+    pub pending_report: Option<Receiver<()>>,
+}
+
+impl GameSession {
+    /// Creates an empty session sized for the given board
+    pub fn new(board: &Board) -> Self {
+        GameSession {
+            history: HistoryTable::new(board.width as u32, board.height),
+            turns_played: 0,
+            opening: None,
+            latency: LatencyTracker::new(),
+            game_timeout_ms: 0,
+            last_score_swing: None,
+            last_score: None,
+            time_bank: TimeBank::default(),
+            timings: TimingHistory::new(),
+            mode: None,
+            stakes: None,
+            stats: GameStats::default(),
+            recent_searches: VecDeque::new(),
+            score_trend: ScoreTrend::default(),
+            pending_report: None,
+        }
+    }
+
+    /// Hands out a copy of the history table for the next search
+    /// Knowledge from the previous turn is halved so the new position can reshape it quickly
+    pub fn history_for_next_turn(&mut self, board: &Board, config: &Config) -> HistoryTable {
+        if !self.history.matches_dimensions(board.width as u32, board.height) {
+            self.history = HistoryTable::new(board.width as u32, board.height);
+        } else if self.turns_played > 0 && config.move_ordering.history_persist_across_turns {
+            self.history.age();
+        } else {
+            self.history.clear();
+        }
+        self.turns_played += 1;
+        self.history.clone()
+    }
+
+    /// Stores the history table produced by a finished search
+    /// Ignored if a newer turn has already been handed out (the late search overran its budget)
+    pub fn store_history(&mut self, history: HistoryTable, served_turn: u32) {
+        if served_turn == self.turns_played {
+            self.history = history;
+        }
+    }
+}
+
+/// Thread-safe map of active game sessions keyed by game id
+/// Sessions are individually locked so concurrent games never contend with each other
+pub struct SessionStore {
+    sessions: RwLock<HashMap<String, StoredSession>>,
+    /// Sessions untouched for longer are dropped when another game's session is created
+    idle_timeout: Duration,
+}
+
+struct StoredSession {
+    session: Arc<Mutex<GameSession>>,
+    last_touched: Mutex<Instant>,
+}
+
+impl SessionStore {
+    pub fn new(idle_timeout: Duration) -> Self {
+        SessionStore {
+            sessions: RwLock::new(HashMap::new()),
+            idle_timeout,
+        }
+    }
+
+    /// Returns the session for a game, creating it if /start was missed
+    /// Creating one first drops the sessions of games idle for longer than the idle timeout
+    pub fn get_or_create(&self, game_id: &str, board: &Board) -> Arc<Mutex<GameSession>> {
+        self.get_or_create_at(game_id, board, Instant::now())
+    }
+
+    fn get_or_create_at(&self, game_id: &str, board: &Board, now: Instant) -> Arc<Mutex<GameSession>> {
+        if let Some(stored) = self.sessions.read().get(game_id) {
+            *stored.last_touched.lock() = now;
+            return stored.session.clone();
+        }
+
+        let mut sessions = self.sessions.write();
+        sessions.retain(|_, stored| now.saturating_duration_since(*stored.last_touched.lock()) <= self.idle_timeout);
+        let stored = sessions.entry(game_id.to_string()).or_insert_with(|| StoredSession {
+            session: Arc::new(Mutex::new(GameSession::new(board))),
+            last_touched: Mutex::new(now),
+        });
+        *stored.last_touched.lock() = now;
+        stored.session.clone()
+    }
+
+    /// Removes a finished game's session
+    pub fn remove(&self, game_id: &str) -> Option<Arc<Mutex<GameSession>>> {
+        self.sessions.write().remove(game_id).map(|stored| stored.session)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Coord, Direction};
+
+    fn empty_board() -> Board {
+        Board { height: 11, width: 11, food: vec![], snakes: vec![], hazards: vec![], occupancy: None }
+    }
+
+    #[test]
+    fn test_history_persists_and_halves_between_turns() {
+        let config = Config::default_hardcoded();
+        let board = empty_board();
+        let mut session = GameSession::new(&board);
+        let pos = Coord { x: 3, y: 3 };
+
+        let history = session.history_for_next_turn(&board, &config);
+        history.update(true, &pos, Direction::Up, 4, true);
+        session.store_history(history, session.turns_played);
+
+        let next = session.history_for_next_turn(&board, &config);
+        assert_eq!(next.get_score(true, &pos, Direction::Up), 8, "2^4 cutoff bonus should be halved once");
+        assert_eq!(next.get_score(false, &pos, Direction::Up), 0, "opponent side must stay independent");
+    }
+
+    #[test]
+    fn test_stale_search_does_not_overwrite_newer_history() {
+        let config = Config::default_hardcoded();
+        let board = empty_board();
+        let mut session = GameSession::new(&board);
+        let pos = Coord { x: 1, y: 1 };
+
+        let stale = session.history_for_next_turn(&board, &config);
+        let stale_turn = session.turns_played;
+        let _current = session.history_for_next_turn(&board, &config);
+
+        stale.update(true, &pos, Direction::Left, 6, true);
+        session.store_history(stale, stale_turn);
+
+        assert_eq!(session.history.get_score(true, &pos, Direction::Left), 0);
+    }
+
+    #[test]
+    fn test_store_creates_and_removes_sessions() {
+        let store = SessionStore::new(Duration::from_secs(60));
+        let board = empty_board();
+
+        let first = store.get_or_create("a", &board);
+        let again = store.get_or_create("a", &board);
+        assert!(Arc::ptr_eq(&first, &again));
+
+        assert!(store.remove("a").is_some());
+        assert!(store.remove("a").is_none());
+    }
+
+    #[test]
+    fn test_idle_sessions_are_evicted_when_a_game_starts() {
+        let store = SessionStore::new(Duration::from_secs(60));
+        let board = empty_board();
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+
+        let abandoned = store.get_or_create_at("abandoned", &board, at(0));
+        let active = store.get_or_create_at("active", &board, at(0));
+        store.get_or_create_at("active", &board, at(50));
+        // Touching an existing session never evicts
+        store.get_or_create_at("active", &board, at(100));
+        assert_eq!(store.sessions.read().len(), 2);
+
+        store.get_or_create_at("new", &board, at(100));
+        assert_eq!(store.sessions.read().len(), 2, "only the session idle for 100s goes");
+        assert!(!Arc::ptr_eq(&abandoned, &store.get_or_create_at("abandoned", &board, at(100))));
+        assert!(Arc::ptr_eq(&active, &store.get_or_create_at("active", &board, at(100))));
+    }
+}
@@ -1,37 +1,56 @@
 //! Simple profiling macros using thread-local storage and conditional compilation
 //!
 //! This module provides lightweight profiling without changing function signatures.
-//! Enable with environment variable: BATTLESNAKE_PROFILE=1
+//! Enable with environment variable: BATTLESNAKE_PROFILE=1, or [profiling] enabled = true
 
+use serde::{Serialize, Serializer};
 use std::cell::RefCell;
-use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::time::Instant;
 
 thread_local! {
-    static MOVE_GEN_TIME: RefCell<u64> = RefCell::new(0);
-    static MOVE_GEN_COUNT: RefCell<usize> = RefCell::new(0);
+    static MOVE_GEN_TIME: RefCell<u64> = const { RefCell::new(0) };
+    static MOVE_GEN_COUNT: RefCell<usize> = const { RefCell::new(0) };
 
-    static EVAL_TIME: RefCell<u64> = RefCell::new(0);
-    static EVAL_COUNT: RefCell<usize> = RefCell::new(0);
+    static EVAL_TIME: RefCell<u64> = const { RefCell::new(0) };
+    static EVAL_COUNT: RefCell<usize> = const { RefCell::new(0) };
 
-    static FLOOD_FILL_TIME: RefCell<u64> = RefCell::new(0);
-    static FLOOD_FILL_COUNT: RefCell<usize> = RefCell::new(0);
+    static FLOOD_FILL_TIME: RefCell<u64> = const { RefCell::new(0) };
+    static FLOOD_FILL_COUNT: RefCell<usize> = const { RefCell::new(0) };
 
-    static ADVERSARIAL_FLOOD_FILL_TIME: RefCell<u64> = RefCell::new(0);
-    static ADVERSARIAL_FLOOD_FILL_COUNT: RefCell<usize> = RefCell::new(0);
+    static DISTANCE_FIELD_TIME: RefCell<u64> = const { RefCell::new(0) };
+    static DISTANCE_FIELD_COUNT: RefCell<usize> = const { RefCell::new(0) };
 
-    static APPLY_MOVE_TIME: RefCell<u64> = RefCell::new(0);
-    static APPLY_MOVE_COUNT: RefCell<usize> = RefCell::new(0);
+    static APPLY_MOVE_TIME: RefCell<u64> = const { RefCell::new(0) };
+    static APPLY_MOVE_COUNT: RefCell<usize> = const { RefCell::new(0) };
 
-    static ALPHA_BETA_TIME: RefCell<u64> = RefCell::new(0);
-    static ALPHA_BETA_COUNT: RefCell<usize> = RefCell::new(0);
-    static ALPHA_BETA_CUTOFFS: RefCell<usize> = RefCell::new(0);
+    static ALPHA_BETA_TIME: RefCell<u64> = const { RefCell::new(0) };
+    static ALPHA_BETA_COUNT: RefCell<usize> = const { RefCell::new(0) };
+    static ALPHA_BETA_CUTOFFS: RefCell<usize> = const { RefCell::new(0) };
 
-    static MAXN_TIME: RefCell<u64> = RefCell::new(0);
-    static MAXN_COUNT: RefCell<usize> = RefCell::new(0);
+    static MAXN_TIME: RefCell<u64> = const { RefCell::new(0) };
+    static MAXN_COUNT: RefCell<usize> = const { RefCell::new(0) };
 
-    static TT_LOOKUPS: RefCell<usize> = RefCell::new(0);
-    static TT_HITS: RefCell<usize> = RefCell::new(0);
+    static TT_LOOKUPS: RefCell<usize> = const { RefCell::new(0) };
+    static TT_HITS: RefCell<usize> = const { RefCell::new(0) };
+
+    static EVAL_CACHE_LOOKUPS: RefCell<usize> = const { RefCell::new(0) };
+    static EVAL_CACHE_HITS: RefCell<usize> = const { RefCell::new(0) };
+
+    static IDAPOS_TIME: RefCell<u64> = const { RefCell::new(0) };
+    static IDAPOS_COUNT: RefCell<usize> = const { RefCell::new(0) };
+    static IDAPOS_CACHE_LOOKUPS: RefCell<usize> = const { RefCell::new(0) };
+    static IDAPOS_CACHE_HITS: RefCell<usize> = const { RefCell::new(0) };
+
+    static QUIESCENCE_EXTENSIONS: RefCell<usize> = const { RefCell::new(0) };
+    static QUIESCENCE_CAPPED: RefCell<usize> = const { RefCell::new(0) };
+
+    static BOARD_CLONES: RefCell<usize> = const { RefCell::new(0) };
+    static BOARD_CLONE_BYTES: RefCell<u64> = const { RefCell::new(0) };
+    static HASH_ALLOCATIONS: RefCell<usize> = const { RefCell::new(0) };
+
+    static EVAL_COMPONENT_TIME: RefCell<[u64; EvalComponent::COUNT]> = const { RefCell::new([0; EvalComponent::COUNT]) };
+    static EVAL_COMPONENT_COUNT: RefCell<[usize; EvalComponent::COUNT]> = const { RefCell::new([0; EvalComponent::COUNT]) };
 }
 
 // Global aggregators
@@ -41,8 +60,8 @@ static GLOBAL_EVAL_TIME: AtomicU64 = AtomicU64::new(0);
 static GLOBAL_EVAL_COUNT: AtomicUsize = AtomicUsize::new(0);
 static GLOBAL_FLOOD_FILL_TIME: AtomicU64 = AtomicU64::new(0);
 static GLOBAL_FLOOD_FILL_COUNT: AtomicUsize = AtomicUsize::new(0);
-static GLOBAL_ADVERSARIAL_FLOOD_FILL_TIME: AtomicU64 = AtomicU64::new(0);
-static GLOBAL_ADVERSARIAL_FLOOD_FILL_COUNT: AtomicUsize = AtomicUsize::new(0);
+static GLOBAL_DISTANCE_FIELD_TIME: AtomicU64 = AtomicU64::new(0);
+static GLOBAL_DISTANCE_FIELD_COUNT: AtomicUsize = AtomicUsize::new(0);
 static GLOBAL_APPLY_MOVE_TIME: AtomicU64 = AtomicU64::new(0);
 static GLOBAL_APPLY_MOVE_COUNT: AtomicUsize = AtomicUsize::new(0);
 static GLOBAL_ALPHA_BETA_TIME: AtomicU64 = AtomicU64::new(0);
@@ -52,10 +71,256 @@ static GLOBAL_MAXN_TIME: AtomicU64 = AtomicU64::new(0);
 static GLOBAL_MAXN_COUNT: AtomicUsize = AtomicUsize::new(0);
 static GLOBAL_TT_LOOKUPS: AtomicUsize = AtomicUsize::new(0);
 static GLOBAL_TT_HITS: AtomicUsize = AtomicUsize::new(0);
+static GLOBAL_EVAL_CACHE_LOOKUPS: AtomicUsize = AtomicUsize::new(0);
+static GLOBAL_EVAL_CACHE_HITS: AtomicUsize = AtomicUsize::new(0);
+static GLOBAL_IDAPOS_TIME: AtomicU64 = AtomicU64::new(0);
+static GLOBAL_IDAPOS_COUNT: AtomicUsize = AtomicUsize::new(0);
+static GLOBAL_IDAPOS_CACHE_LOOKUPS: AtomicUsize = AtomicUsize::new(0);
+static GLOBAL_IDAPOS_CACHE_HITS: AtomicUsize = AtomicUsize::new(0);
+static GLOBAL_QUIESCENCE_EXTENSIONS: AtomicUsize = AtomicUsize::new(0);
+static GLOBAL_QUIESCENCE_CAPPED: AtomicUsize = AtomicUsize::new(0);
+static GLOBAL_BOARD_CLONES: AtomicUsize = AtomicUsize::new(0);
+static GLOBAL_BOARD_CLONE_BYTES: AtomicU64 = AtomicU64::new(0);
+static GLOBAL_HASH_ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static GLOBAL_EVAL_COMPONENT_TIME: [AtomicU64; EvalComponent::COUNT] = [const { AtomicU64::new(0) }; EvalComponent::COUNT];
+static GLOBAL_EVAL_COMPONENT_COUNT: [AtomicUsize; EvalComponent::COUNT] = [const { AtomicUsize::new(0) }; EvalComponent::COUNT];
+
+/// Set from [profiling] enabled at startup (the environment variable works either way)
+static ENABLED_BY_CONFIG: AtomicBool = AtomicBool::new(false);
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED_BY_CONFIG.store(enabled, Ordering::Relaxed);
+}
 
 #[inline]
 pub fn is_profiling_enabled() -> bool {
-    std::env::var("BATTLESNAKE_PROFILE").is_ok()
+    ENABLED_BY_CONFIG.load(Ordering::Relaxed) || std::env::var("BATTLESNAKE_PROFILE").is_ok()
+}
+
+/// Terms of the heuristic evaluation timed one by one (see evaluator::components)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvalComponent {
+    Health,
+    Space,
+    Control,
+    Attack,
+    TrapSetting,
+    EndgameParity,
+    HeadCollision,
+    /// Wall penalty, center bias and corner danger
+    Positional,
+    LengthAdvantage,
+    TrailPenalty,
+    GrowthUrgency,
+    TailChasing,
+    Articulation,
+}
+
+impl EvalComponent {
+    pub const COUNT: usize = 13;
+    pub const ALL: [EvalComponent; EvalComponent::COUNT] = [
+        EvalComponent::Health,
+        EvalComponent::Space,
+        EvalComponent::Control,
+        EvalComponent::Attack,
+        EvalComponent::TrapSetting,
+        EvalComponent::EndgameParity,
+        EvalComponent::HeadCollision,
+        EvalComponent::Positional,
+        EvalComponent::LengthAdvantage,
+        EvalComponent::TrailPenalty,
+        EvalComponent::GrowthUrgency,
+        EvalComponent::TailChasing,
+        EvalComponent::Articulation,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EvalComponent::Health => "health",
+            EvalComponent::Space => "space",
+            EvalComponent::Control => "control",
+            EvalComponent::Attack => "attack",
+            EvalComponent::TrapSetting => "trap_setting",
+            EvalComponent::EndgameParity => "endgame_parity",
+            EvalComponent::HeadCollision => "head_collision",
+            EvalComponent::Positional => "positional",
+            EvalComponent::LengthAdvantage => "length_advantage",
+            EvalComponent::TrailPenalty => "trail_penalty",
+            EvalComponent::GrowthUrgency => "growth_urgency",
+            EvalComponent::TailChasing => "tail_chasing",
+            EvalComponent::Articulation => "articulation",
+        }
+    }
+}
+
+/// Times the terms of one evaluation and adds them to this thread's counters when dropped
+/// (one thread-local update per evaluation instead of one per term); inert when profiling
+/// is off
+pub struct ComponentTimer {
+    enabled: bool,
+    times: [u64; EvalComponent::COUNT],
+    calls: [usize; EvalComponent::COUNT],
+}
+
+impl ComponentTimer {
+    pub fn start() -> Self {
+        ComponentTimer { enabled: is_profiling_enabled(), times: [0; EvalComponent::COUNT], calls: [0; EvalComponent::COUNT] }
+    }
+
+    /// Runs `compute`, timing it as `component`
+    #[inline]
+    pub fn time<R>(&mut self, component: EvalComponent, compute: impl FnOnce() -> R) -> R {
+        if !self.enabled {
+            return compute();
+        }
+        let start = Instant::now();
+        let result = compute();
+        self.times[component as usize] += start.elapsed().as_nanos() as u64;
+        self.calls[component as usize] += 1;
+        result
+    }
+}
+
+impl Drop for ComponentTimer {
+    fn drop(&mut self) {
+        if self.enabled {
+            EVAL_COMPONENT_TIME.with(|t| t.borrow_mut().iter_mut().zip(self.times).for_each(|(total, ns)| *total += ns));
+            EVAL_COMPONENT_COUNT.with(|c| c.borrow_mut().iter_mut().zip(self.calls).for_each(|(total, n)| *total += n));
+        }
+    }
+}
+
+/// Calls and total time of one profiled category
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CategoryStats {
+    pub calls: usize,
+    pub time_ns: u64,
+}
+
+/// Calls and time of every evaluation term, serialized as a map keyed by EvalComponent::name
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EvalComponentStats(pub [CategoryStats; EvalComponent::COUNT]);
+
+impl EvalComponentStats {
+    pub fn get(&self, component: EvalComponent) -> CategoryStats {
+        self.0[component as usize]
+    }
+
+    fn load() -> Self {
+        EvalComponentStats(std::array::from_fn(|idx| CategoryStats::load(&GLOBAL_EVAL_COMPONENT_TIME[idx], &GLOBAL_EVAL_COMPONENT_COUNT[idx])))
+    }
+
+    fn since(&self, earlier: &EvalComponentStats) -> Self {
+        EvalComponentStats(std::array::from_fn(|idx| self.0[idx].since(&earlier.0[idx])))
+    }
+}
+
+impl Serialize for EvalComponentStats {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(EvalComponent::ALL.iter().map(|&component| (component.name(), self.get(component))))
+    }
+}
+
+impl CategoryStats {
+    fn load(time: &AtomicU64, count: &AtomicUsize) -> Self {
+        CategoryStats { calls: count.load(Ordering::Relaxed), time_ns: time.load(Ordering::Relaxed) }
+    }
+
+    fn since(&self, earlier: &CategoryStats) -> Self {
+        CategoryStats {
+            calls: self.calls.saturating_sub(earlier.calls),
+            time_ns: self.time_ns.saturating_sub(earlier.time_ns),
+        }
+    }
+}
+
+/// Merged global counters at one point in time; the difference of two snapshots is the
+/// work done in between (by every search running meanwhile)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ProfileSnapshot {
+    pub move_gen: CategoryStats,
+    pub eval: CategoryStats,
+    pub flood_fill: CategoryStats,
+    pub distance_field: CategoryStats,
+    pub apply_move: CategoryStats,
+    pub alpha_beta: CategoryStats,
+    pub maxn: CategoryStats,
+    pub alpha_beta_cutoffs: usize,
+    pub tt_lookups: usize,
+    pub tt_hits: usize,
+    pub eval_cache_lookups: usize,
+    pub eval_cache_hits: usize,
+    /// Active-set computations (cache misses and uncached calls)
+    pub idapos: CategoryStats,
+    pub idapos_cache_lookups: usize,
+    pub idapos_cache_hits: usize,
+    /// Quiescence extensions taken
+    pub quiescence_extensions: usize,
+    /// Unstable leaves evaluated as they stand because a quiescence cap was reached
+    pub quiescence_capped: usize,
+    /// Time of each evaluation term (part of `eval`)
+    pub eval_components: EvalComponentStats,
+    /// Board clones (Board::clone and Board::clone_from) and the heap bytes they copied
+    pub board_clones: usize,
+    pub board_clone_bytes: u64,
+    /// Allocations and regrowths of the hash tables filled during the search
+    pub hash_allocations: usize,
+}
+
+impl ProfileSnapshot {
+    /// Counters accumulated since `earlier`
+    pub fn since(&self, earlier: &ProfileSnapshot) -> ProfileSnapshot {
+        ProfileSnapshot {
+            move_gen: self.move_gen.since(&earlier.move_gen),
+            eval: self.eval.since(&earlier.eval),
+            flood_fill: self.flood_fill.since(&earlier.flood_fill),
+            distance_field: self.distance_field.since(&earlier.distance_field),
+            apply_move: self.apply_move.since(&earlier.apply_move),
+            alpha_beta: self.alpha_beta.since(&earlier.alpha_beta),
+            maxn: self.maxn.since(&earlier.maxn),
+            alpha_beta_cutoffs: self.alpha_beta_cutoffs.saturating_sub(earlier.alpha_beta_cutoffs),
+            tt_lookups: self.tt_lookups.saturating_sub(earlier.tt_lookups),
+            tt_hits: self.tt_hits.saturating_sub(earlier.tt_hits),
+            eval_cache_lookups: self.eval_cache_lookups.saturating_sub(earlier.eval_cache_lookups),
+            eval_cache_hits: self.eval_cache_hits.saturating_sub(earlier.eval_cache_hits),
+            idapos: self.idapos.since(&earlier.idapos),
+            idapos_cache_lookups: self.idapos_cache_lookups.saturating_sub(earlier.idapos_cache_lookups),
+            idapos_cache_hits: self.idapos_cache_hits.saturating_sub(earlier.idapos_cache_hits),
+            quiescence_extensions: self.quiescence_extensions.saturating_sub(earlier.quiescence_extensions),
+            quiescence_capped: self.quiescence_capped.saturating_sub(earlier.quiescence_capped),
+            eval_components: self.eval_components.since(&earlier.eval_components),
+            board_clones: self.board_clones.saturating_sub(earlier.board_clones),
+            board_clone_bytes: self.board_clone_bytes.saturating_sub(earlier.board_clone_bytes),
+            hash_allocations: self.hash_allocations.saturating_sub(earlier.hash_allocations),
+        }
+    }
+}
+
+/// Current merged counters (see merge_thread_local)
+pub fn snapshot() -> ProfileSnapshot {
+    ProfileSnapshot {
+        move_gen: CategoryStats::load(&GLOBAL_MOVE_GEN_TIME, &GLOBAL_MOVE_GEN_COUNT),
+        eval: CategoryStats::load(&GLOBAL_EVAL_TIME, &GLOBAL_EVAL_COUNT),
+        flood_fill: CategoryStats::load(&GLOBAL_FLOOD_FILL_TIME, &GLOBAL_FLOOD_FILL_COUNT),
+        distance_field: CategoryStats::load(&GLOBAL_DISTANCE_FIELD_TIME, &GLOBAL_DISTANCE_FIELD_COUNT),
+        apply_move: CategoryStats::load(&GLOBAL_APPLY_MOVE_TIME, &GLOBAL_APPLY_MOVE_COUNT),
+        alpha_beta: CategoryStats::load(&GLOBAL_ALPHA_BETA_TIME, &GLOBAL_ALPHA_BETA_COUNT),
+        maxn: CategoryStats::load(&GLOBAL_MAXN_TIME, &GLOBAL_MAXN_COUNT),
+        alpha_beta_cutoffs: GLOBAL_ALPHA_BETA_CUTOFFS.load(Ordering::Relaxed),
+        tt_lookups: GLOBAL_TT_LOOKUPS.load(Ordering::Relaxed),
+        tt_hits: GLOBAL_TT_HITS.load(Ordering::Relaxed),
+        eval_cache_lookups: GLOBAL_EVAL_CACHE_LOOKUPS.load(Ordering::Relaxed),
+        eval_cache_hits: GLOBAL_EVAL_CACHE_HITS.load(Ordering::Relaxed),
+        idapos: CategoryStats::load(&GLOBAL_IDAPOS_TIME, &GLOBAL_IDAPOS_COUNT),
+        idapos_cache_lookups: GLOBAL_IDAPOS_CACHE_LOOKUPS.load(Ordering::Relaxed),
+        idapos_cache_hits: GLOBAL_IDAPOS_CACHE_HITS.load(Ordering::Relaxed),
+        quiescence_extensions: GLOBAL_QUIESCENCE_EXTENSIONS.load(Ordering::Relaxed),
+        quiescence_capped: GLOBAL_QUIESCENCE_CAPPED.load(Ordering::Relaxed),
+        eval_components: EvalComponentStats::load(),
+        board_clones: GLOBAL_BOARD_CLONES.load(Ordering::Relaxed),
+        board_clone_bytes: GLOBAL_BOARD_CLONE_BYTES.load(Ordering::Relaxed),
+        hash_allocations: GLOBAL_HASH_ALLOCATIONS.load(Ordering::Relaxed),
+    }
 }
 
 pub struct ProfileGuard {
@@ -93,9 +358,9 @@ impl Drop for ProfileGuard {
                 FLOOD_FILL_TIME.with(|t| *t.borrow_mut() += elapsed_ns);
                 FLOOD_FILL_COUNT.with(|c| *c.borrow_mut() += 1);
             }
-            "adversarial_flood_fill" => {
-                ADVERSARIAL_FLOOD_FILL_TIME.with(|t| *t.borrow_mut() += elapsed_ns);
-                ADVERSARIAL_FLOOD_FILL_COUNT.with(|c| *c.borrow_mut() += 1);
+            "distance_field" => {
+                DISTANCE_FIELD_TIME.with(|t| *t.borrow_mut() += elapsed_ns);
+                DISTANCE_FIELD_COUNT.with(|c| *c.borrow_mut() += 1);
             }
             "apply_move" => {
                 APPLY_MOVE_TIME.with(|t| *t.borrow_mut() += elapsed_ns);
@@ -109,6 +374,10 @@ impl Drop for ProfileGuard {
                 MAXN_TIME.with(|t| *t.borrow_mut() += elapsed_ns);
                 MAXN_COUNT.with(|c| *c.borrow_mut() += 1);
             }
+            "idapos" => {
+                IDAPOS_TIME.with(|t| *t.borrow_mut() += elapsed_ns);
+                IDAPOS_COUNT.with(|c| *c.borrow_mut() += 1);
+            }
             _ => {}
         }
     }
@@ -131,6 +400,56 @@ pub fn record_tt_lookup(hit: bool) {
     }
 }
 
+#[inline]
+pub fn record_eval_cache_lookup(hit: bool) {
+    if is_profiling_enabled() {
+        EVAL_CACHE_LOOKUPS.with(|c| *c.borrow_mut() += 1);
+        if hit {
+            EVAL_CACHE_HITS.with(|c| *c.borrow_mut() += 1);
+        }
+    }
+}
+
+#[inline]
+pub fn record_idapos_cache_lookup(hit: bool) {
+    if is_profiling_enabled() {
+        IDAPOS_CACHE_LOOKUPS.with(|c| *c.borrow_mut() += 1);
+        if hit {
+            IDAPOS_CACHE_HITS.with(|c| *c.borrow_mut() += 1);
+        }
+    }
+}
+
+/// A quiescence extension taken, or refused at a cap (timing.max_quiescence_ply /
+/// timing.max_quiescence_extensions)
+#[inline]
+pub fn record_quiescence_extension(taken: bool) {
+    if is_profiling_enabled() {
+        if taken {
+            QUIESCENCE_EXTENSIONS.with(|c| *c.borrow_mut() += 1);
+        } else {
+            QUIESCENCE_CAPPED.with(|c| *c.borrow_mut() += 1);
+        }
+    }
+}
+
+/// A board clone copying `bytes` of heap data (see Board::heap_bytes)
+#[inline]
+pub fn record_board_clone(bytes: impl FnOnce() -> usize) {
+    if is_profiling_enabled() {
+        BOARD_CLONES.with(|c| *c.borrow_mut() += 1);
+        BOARD_CLONE_BYTES.with(|c| *c.borrow_mut() += bytes() as u64);
+    }
+}
+
+/// Records a hash table allocation when an insert changed its capacity
+#[inline]
+pub fn record_hash_growth(capacity_before: usize, capacity_after: usize) {
+    if capacity_after != capacity_before && is_profiling_enabled() {
+        HASH_ALLOCATIONS.with(|c| *c.borrow_mut() += 1);
+    }
+}
+
 pub fn merge_thread_local() {
     if !is_profiling_enabled() {
         return;
@@ -163,12 +482,12 @@ pub fn merge_thread_local() {
         *c.borrow_mut() = 0;
     });
 
-    ADVERSARIAL_FLOOD_FILL_TIME.with(|t| {
-        GLOBAL_ADVERSARIAL_FLOOD_FILL_TIME.fetch_add(*t.borrow(), Ordering::Relaxed);
+    DISTANCE_FIELD_TIME.with(|t| {
+        GLOBAL_DISTANCE_FIELD_TIME.fetch_add(*t.borrow(), Ordering::Relaxed);
         *t.borrow_mut() = 0;
     });
-    ADVERSARIAL_FLOOD_FILL_COUNT.with(|c| {
-        GLOBAL_ADVERSARIAL_FLOOD_FILL_COUNT.fetch_add(*c.borrow(), Ordering::Relaxed);
+    DISTANCE_FIELD_COUNT.with(|c| {
+        GLOBAL_DISTANCE_FIELD_COUNT.fetch_add(*c.borrow(), Ordering::Relaxed);
         *c.borrow_mut() = 0;
     });
 
@@ -211,6 +530,65 @@ pub fn merge_thread_local() {
         GLOBAL_TT_HITS.fetch_add(*c.borrow(), Ordering::Relaxed);
         *c.borrow_mut() = 0;
     });
+
+    EVAL_CACHE_LOOKUPS.with(|c| {
+        GLOBAL_EVAL_CACHE_LOOKUPS.fetch_add(*c.borrow(), Ordering::Relaxed);
+        *c.borrow_mut() = 0;
+    });
+    EVAL_CACHE_HITS.with(|c| {
+        GLOBAL_EVAL_CACHE_HITS.fetch_add(*c.borrow(), Ordering::Relaxed);
+        *c.borrow_mut() = 0;
+    });
+
+    IDAPOS_TIME.with(|t| {
+        GLOBAL_IDAPOS_TIME.fetch_add(*t.borrow(), Ordering::Relaxed);
+        *t.borrow_mut() = 0;
+    });
+    IDAPOS_COUNT.with(|c| {
+        GLOBAL_IDAPOS_COUNT.fetch_add(*c.borrow(), Ordering::Relaxed);
+        *c.borrow_mut() = 0;
+    });
+    IDAPOS_CACHE_LOOKUPS.with(|c| {
+        GLOBAL_IDAPOS_CACHE_LOOKUPS.fetch_add(*c.borrow(), Ordering::Relaxed);
+        *c.borrow_mut() = 0;
+    });
+    IDAPOS_CACHE_HITS.with(|c| {
+        GLOBAL_IDAPOS_CACHE_HITS.fetch_add(*c.borrow(), Ordering::Relaxed);
+        *c.borrow_mut() = 0;
+    });
+
+    QUIESCENCE_EXTENSIONS.with(|c| {
+        GLOBAL_QUIESCENCE_EXTENSIONS.fetch_add(*c.borrow(), Ordering::Relaxed);
+        *c.borrow_mut() = 0;
+    });
+    QUIESCENCE_CAPPED.with(|c| {
+        GLOBAL_QUIESCENCE_CAPPED.fetch_add(*c.borrow(), Ordering::Relaxed);
+        *c.borrow_mut() = 0;
+    });
+
+    BOARD_CLONES.with(|c| {
+        GLOBAL_BOARD_CLONES.fetch_add(*c.borrow(), Ordering::Relaxed);
+        *c.borrow_mut() = 0;
+    });
+    BOARD_CLONE_BYTES.with(|c| {
+        GLOBAL_BOARD_CLONE_BYTES.fetch_add(*c.borrow(), Ordering::Relaxed);
+        *c.borrow_mut() = 0;
+    });
+    HASH_ALLOCATIONS.with(|c| {
+        GLOBAL_HASH_ALLOCATIONS.fetch_add(*c.borrow(), Ordering::Relaxed);
+        *c.borrow_mut() = 0;
+    });
+
+    EVAL_COMPONENT_TIME.with(|t| {
+        for (global, local) in GLOBAL_EVAL_COMPONENT_TIME.iter().zip(t.borrow_mut().iter_mut()) {
+            global.fetch_add(std::mem::take(local), Ordering::Relaxed);
+        }
+    });
+    EVAL_COMPONENT_COUNT.with(|c| {
+        for (global, local) in GLOBAL_EVAL_COMPONENT_COUNT.iter().zip(c.borrow_mut().iter_mut()) {
+            global.fetch_add(std::mem::take(local), Ordering::Relaxed);
+        }
+    });
 }
 
 pub fn print_report(total_time_ms: u64) {
@@ -248,8 +626,8 @@ pub fn print_report(total_time_ms: u64) {
     let ff_pct = if eval_time > 0 { 100.0 * ff_time as f64 / eval_time as f64 } else { 0.0 };
     let ff_avg_us = if ff_count > 0 { ff_time as f64 / (ff_count * 1000) as f64 } else { 0.0 };
 
-    let aff_time = GLOBAL_ADVERSARIAL_FLOOD_FILL_TIME.load(Ordering::Relaxed);
-    let aff_count = GLOBAL_ADVERSARIAL_FLOOD_FILL_COUNT.load(Ordering::Relaxed);
+    let aff_time = GLOBAL_DISTANCE_FIELD_TIME.load(Ordering::Relaxed);
+    let aff_count = GLOBAL_DISTANCE_FIELD_COUNT.load(Ordering::Relaxed);
     let aff_ms = aff_time as f64 / 1_000_000.0;
     let aff_pct = if eval_time > 0 { 100.0 * aff_time as f64 / eval_time as f64 } else { 0.0 };
     let aff_avg_us = if aff_count > 0 { aff_time as f64 / (aff_count * 1000) as f64 } else { 0.0 };
@@ -260,9 +638,22 @@ pub fn print_report(total_time_ms: u64) {
     eprintln!("  Avg:                   {:.2}µs/call", eval_avg_us);
     eprintln!("  Flood Fill (Space):    {:.2}ms ({:.1}%) - {} calls, {:.2}µs avg",
         ff_ms, ff_pct, ff_count, ff_avg_us);
-    eprintln!("  Territory Control:     {:.2}ms ({:.1}%) - {} calls, {:.2}µs avg\n",
+    eprintln!("  Distance Field:        {:.2}ms ({:.1}%) - {} calls, {:.2}µs avg",
         aff_ms, aff_pct, aff_count, aff_avg_us);
 
+    // Evaluation terms, most expensive first
+    let mut terms: Vec<(EvalComponent, CategoryStats)> =
+        EvalComponent::ALL.iter().map(|&component| (component, EvalComponentStats::load().get(component))).collect();
+    terms.sort_by_key(|&(_, stats)| std::cmp::Reverse(stats.time_ns));
+    eprintln!("  Terms:");
+    for (component, stats) in terms.iter().filter(|(_, stats)| stats.calls > 0) {
+        let pct = if eval_time > 0 { 100.0 * stats.time_ns as f64 / eval_time as f64 } else { 0.0 };
+        let avg_us = stats.time_ns as f64 / (stats.calls * 1000) as f64;
+        eprintln!("    {:<19}{:.2}ms ({:.1}%) - {} calls, {:.2}µs avg",
+            format!("{}:", component.name()), stats.time_ns as f64 / 1_000_000.0, pct, stats.calls, avg_us);
+    }
+    eprintln!();
+
     let ab_time = GLOBAL_ALPHA_BETA_TIME.load(Ordering::Relaxed);
     let ab_count = GLOBAL_ALPHA_BETA_COUNT.load(Ordering::Relaxed);
     let ab_cutoffs = GLOBAL_ALPHA_BETA_CUTOFFS.load(Ordering::Relaxed);
@@ -297,6 +688,40 @@ pub fn print_report(total_time_ms: u64) {
     eprintln!("  Lookups:    {}", tt_lookups);
     eprintln!("  Hits:       {} ({:.1}%)\n", tt_hits, hit_rate);
 
+    let ec_lookups = GLOBAL_EVAL_CACHE_LOOKUPS.load(Ordering::Relaxed);
+    let ec_hits = GLOBAL_EVAL_CACHE_HITS.load(Ordering::Relaxed);
+    let ec_hit_rate = if ec_lookups > 0 { 100.0 * ec_hits as f64 / ec_lookups as f64 } else { 0.0 };
+
+    eprintln!("Evaluation Cache (space / territory):");
+    eprintln!("  Lookups:    {}", ec_lookups);
+    eprintln!("  Hits:       {} ({:.1}%)\n", ec_hits, ec_hit_rate);
+
+    // Time saved is estimated as cache hits times the average cost of a computed active set
+    let id_time = GLOBAL_IDAPOS_TIME.load(Ordering::Relaxed);
+    let id_count = GLOBAL_IDAPOS_COUNT.load(Ordering::Relaxed);
+    let id_lookups = GLOBAL_IDAPOS_CACHE_LOOKUPS.load(Ordering::Relaxed);
+    let id_hits = GLOBAL_IDAPOS_CACHE_HITS.load(Ordering::Relaxed);
+    let id_avg_ns = if id_count > 0 { id_time as f64 / id_count as f64 } else { 0.0 };
+    let id_hit_rate = if id_lookups > 0 { 100.0 * id_hits as f64 / id_lookups as f64 } else { 0.0 };
+
+    eprintln!("IDAPOS Active Sets:");
+    eprintln!("  Computed:   {} ({:.2}ms, {:.2}µs avg)", id_count, id_time as f64 / 1_000_000.0, id_avg_ns / 1000.0);
+    eprintln!("  Cache Hits: {} of {} ({:.1}%)", id_hits, id_lookups, id_hit_rate);
+    eprintln!("  Saved:      ~{:.2}ms\n", id_hits as f64 * id_avg_ns / 1_000_000.0);
+
+    let q_taken = GLOBAL_QUIESCENCE_EXTENSIONS.load(Ordering::Relaxed);
+    let q_capped = GLOBAL_QUIESCENCE_CAPPED.load(Ordering::Relaxed);
+    eprintln!("Quiescence:");
+    eprintln!("  Extensions: {}", q_taken);
+    eprintln!("  Capped:     {} unstable leaves\n", q_capped);
+
+    let clones = GLOBAL_BOARD_CLONES.load(Ordering::Relaxed);
+    let clone_bytes = GLOBAL_BOARD_CLONE_BYTES.load(Ordering::Relaxed);
+    let avg_bytes = if clones > 0 { clone_bytes / clones as u64 } else { 0 };
+    eprintln!("Allocations:");
+    eprintln!("  Board Clones: {} ({:.2}MB, {}B avg)", clones, clone_bytes as f64 / 1_048_576.0, avg_bytes);
+    eprintln!("  Hash Tables:  {} allocations\n", GLOBAL_HASH_ALLOCATIONS.load(Ordering::Relaxed));
+
     eprintln!("═══════════════════════════════════════════════════════════\n");
 }
 
@@ -307,8 +732,8 @@ pub fn reset() {
     GLOBAL_EVAL_COUNT.store(0, Ordering::Relaxed);
     GLOBAL_FLOOD_FILL_TIME.store(0, Ordering::Relaxed);
     GLOBAL_FLOOD_FILL_COUNT.store(0, Ordering::Relaxed);
-    GLOBAL_ADVERSARIAL_FLOOD_FILL_TIME.store(0, Ordering::Relaxed);
-    GLOBAL_ADVERSARIAL_FLOOD_FILL_COUNT.store(0, Ordering::Relaxed);
+    GLOBAL_DISTANCE_FIELD_TIME.store(0, Ordering::Relaxed);
+    GLOBAL_DISTANCE_FIELD_COUNT.store(0, Ordering::Relaxed);
     GLOBAL_APPLY_MOVE_TIME.store(0, Ordering::Relaxed);
     GLOBAL_APPLY_MOVE_COUNT.store(0, Ordering::Relaxed);
     GLOBAL_ALPHA_BETA_TIME.store(0, Ordering::Relaxed);
@@ -318,6 +743,21 @@ pub fn reset() {
     GLOBAL_MAXN_COUNT.store(0, Ordering::Relaxed);
     GLOBAL_TT_LOOKUPS.store(0, Ordering::Relaxed);
     GLOBAL_TT_HITS.store(0, Ordering::Relaxed);
+    GLOBAL_EVAL_CACHE_LOOKUPS.store(0, Ordering::Relaxed);
+    GLOBAL_EVAL_CACHE_HITS.store(0, Ordering::Relaxed);
+    GLOBAL_IDAPOS_TIME.store(0, Ordering::Relaxed);
+    GLOBAL_IDAPOS_COUNT.store(0, Ordering::Relaxed);
+    GLOBAL_IDAPOS_CACHE_LOOKUPS.store(0, Ordering::Relaxed);
+    GLOBAL_IDAPOS_CACHE_HITS.store(0, Ordering::Relaxed);
+    GLOBAL_QUIESCENCE_EXTENSIONS.store(0, Ordering::Relaxed);
+    GLOBAL_QUIESCENCE_CAPPED.store(0, Ordering::Relaxed);
+    GLOBAL_BOARD_CLONES.store(0, Ordering::Relaxed);
+    GLOBAL_BOARD_CLONE_BYTES.store(0, Ordering::Relaxed);
+    GLOBAL_HASH_ALLOCATIONS.store(0, Ordering::Relaxed);
+    for (time, count) in GLOBAL_EVAL_COMPONENT_TIME.iter().zip(GLOBAL_EVAL_COMPONENT_COUNT.iter()) {
+        time.store(0, Ordering::Relaxed);
+        count.store(0, Ordering::Relaxed);
+    }
 }
 
 #[macro_export]
@@ -327,3 +767,26 @@ macro_rules! profile {
         $code
     }};
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_component_timer_flushes_on_drop_and_serializes_by_name() {
+        let mut timer = ComponentTimer { enabled: true, times: [0; EvalComponent::COUNT], calls: [0; EvalComponent::COUNT] };
+        assert_eq!(timer.time(EvalComponent::Space, || 42), 42);
+        timer.time(EvalComponent::Space, || ());
+        drop(timer);
+        let calls = EVAL_COMPONENT_COUNT.with(|c| *c.borrow());
+        assert_eq!(calls[EvalComponent::Space as usize], 2);
+        assert_eq!(calls.iter().sum::<usize>(), 2);
+
+        let mut stats = EvalComponentStats::default();
+        stats.0[EvalComponent::Space as usize] = CategoryStats { calls: 2, time_ns: 10 };
+        let json = serde_json::to_value(stats).unwrap();
+        assert_eq!(json.as_object().unwrap().len(), EvalComponent::COUNT);
+        assert_eq!(json["space"]["calls"], 2);
+        assert_eq!(json["articulation"]["time_ns"], 0);
+    }
+}
@@ -0,0 +1,59 @@
+// Snail mode (hazard trail) support
+//
+// On the snail_mode map every snake leaves a trail: each turn the cell its tail vacated gets
+// a stack of hazards as high as the snake is long (none on the turn after eating), and every
+// stack loses one hazard per turn. The engine sends stacks as repeated entries of
+// board.hazards and applies hazard damage once per entry, so a fresh trail is lethal and an
+// old one merely hurts. The search models this in GameMode::Snail games: apply_move lays
+// the trail of each moving snake, advance_game_state decays all stacks, and the evaluation
+// charges scores.trail_reentry_penalty per stack under a snake's head. Games without an
+// explicit profile play with [profiles.snail] when it is configured.
+
+use crate::types::Coord;
+
+/// Map name the engine sends for snail mode
+pub const SNAIL_MAP: &str = "snail_mode";
+
+/// Profile used for snail mode games requested without a profile
+pub const SNAIL_PROFILE: &str = "snail";
+
+/// Lays a stack of `stacks` hazards on the vacated tail cell
+pub fn lay_trail(hazards: &mut Vec<Coord>, tail: Coord, stacks: usize) {
+    hazards.extend(std::iter::repeat_n(tail, stacks));
+}
+
+/// Removes one hazard from every stack (order of the remaining entries is not kept)
+pub fn decay_trails(hazards: &mut Vec<Coord>) {
+    hazards.sort_unstable_by_key(|c| (c.x, c.y));
+    let mut previous: Option<Coord> = None;
+    hazards.retain(|&c| {
+        let keep = previous == Some(c);
+        previous = Some(c);
+        keep
+    });
+}
+
+/// Height of the hazard stack on `c`
+pub fn trail_depth(hazards: &[Coord], c: Coord) -> usize {
+    hazards.iter().filter(|&&h| h == c).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trails_stack_and_decay_one_per_turn() {
+        let (a, b) = (Coord { x: 1, y: 1 }, Coord { x: 2, y: 1 });
+        let mut hazards = Vec::new();
+        lay_trail(&mut hazards, a, 3);
+        lay_trail(&mut hazards, b, 1);
+        assert_eq!((trail_depth(&hazards, a), trail_depth(&hazards, b)), (3, 1));
+
+        decay_trails(&mut hazards);
+        assert_eq!((trail_depth(&hazards, a), trail_depth(&hazards, b)), (2, 0));
+        decay_trails(&mut hazards);
+        decay_trails(&mut hazards);
+        assert!(hazards.is_empty());
+    }
+}
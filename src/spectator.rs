@@ -0,0 +1,211 @@
+// Live spectator stream (GET /ws/games/<game_id>)
+//
+// Dashboards used to follow a game by tailing the debug log. With spectator.enabled, a
+// browser can open a WebSocket on /ws/games/<game_id> and receive one JSON text frame per
+// turn the bot plays in that game: the board it was given, the SearchResultV1 of its decision
+// (move, score, depth, PV, candidates, iterations, nodes) and its time budget. Every game
+// has a tokio broadcast channel, created by the first viewer and dropped when its last viewer
+// leaves or at /end, which closes the viewers' sockets, so made-up game ids hold no channel
+// beyond their connections. A viewer that falls more than spectator.channel_capacity frames
+// behind skips the oldest ones. Nothing is serialized for games nobody watches.
+//
+// rocket_ws performs the handshake and the framing (pings are answered there); messages from
+// the viewer are otherwise ignored.
+
+use parking_lot::Mutex;
+use rocket::futures::{SinkExt, StreamExt};
+use rocket_ws::result::Error;
+use rocket_ws::{Channel, Message, WebSocket};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+use crate::schema::SearchResultV1;
+use crate::types::Board;
+
+/// One turn of a watched game, as sent to its viewers
+#[derive(Debug, Serialize)]
+pub struct SpectatorFrame<'a> {
+    pub game_id: &'a str,
+    /// Id of our snake (several profiles of this server may play the same game)
+    pub you: &'a str,
+    pub turn: i32,
+    pub board: &'a Board,
+    pub budget_ms: u64,
+    pub result: &'a SearchResultV1,
+}
+
+/// Broadcast channel of one watched game
+struct GameChannel {
+    /// Tells a channel apart from a later one for the same game id
+    id: u64,
+    sender: broadcast::Sender<String>,
+}
+
+#[derive(Default)]
+struct Channels {
+    games: HashMap<String, GameChannel>,
+    next_id: u64,
+}
+
+/// Broadcast channels of the watched games
+#[derive(Clone)]
+pub struct SpectatorHub {
+    channels: Arc<Mutex<Channels>>,
+    capacity: usize,
+}
+
+impl SpectatorHub {
+    /// `capacity`: frames buffered per game (spectator.channel_capacity)
+    pub fn new(capacity: usize) -> Self {
+        SpectatorHub { channels: Arc::default(), capacity: capacity.max(1) }
+    }
+
+    /// New viewer of `game_id` (the game need not have started yet)
+    pub fn subscribe(&self, game_id: &str) -> Subscription {
+        let mut channels = self.channels.lock();
+        let Channels { games, next_id } = &mut *channels;
+        let channel = games.entry(game_id.to_string()).or_insert_with(|| {
+            *next_id += 1;
+            GameChannel { id: *next_id, sender: broadcast::channel(self.capacity).0 }
+        });
+        Subscription {
+            game_id: game_id.to_string(),
+            channel_id: channel.id,
+            frames: channel.sender.subscribe(),
+            channels: Arc::clone(&self.channels),
+        }
+    }
+
+    /// True if someone is watching `game_id` (frames for other games are not built)
+    pub fn is_watched(&self, game_id: &str) -> bool {
+        self.channels.lock().games.get(game_id).is_some_and(|channel| channel.sender.receiver_count() > 0)
+    }
+
+    /// Sends `frame` to the viewers of its game; a channel whose viewers all left is dropped
+    pub fn publish(&self, frame: &SpectatorFrame) {
+        let mut channels = self.channels.lock();
+        let Some(channel) = channels.games.get(frame.game_id) else { return };
+        let text = match serde_json::to_string(frame) {
+            Ok(text) => text,
+            Err(e) => {
+                log::warn!("Spectator frame for game {}: {}", frame.game_id, e);
+                return;
+            }
+        };
+        if channel.sender.send(text).is_err() {
+            channels.games.remove(frame.game_id);
+        }
+    }
+
+    /// Ends the stream of `game_id`: viewers get the frames already sent, then a close frame
+    pub fn close(&self, game_id: &str) {
+        self.channels.lock().games.remove(game_id);
+    }
+}
+
+/// One viewer's feed of a game; the game's channel goes away with its last subscription
+pub struct Subscription {
+    game_id: String,
+    channel_id: u64,
+    frames: broadcast::Receiver<String>,
+    channels: Arc<Mutex<Channels>>,
+}
+
+impl Subscription {
+    /// Next frame of the game (Closed once the game ended)
+    pub async fn recv(&mut self) -> Result<String, broadcast::error::RecvError> {
+        self.frames.recv().await
+    }
+
+    /// Streams the game to the viewer behind `ws` until the game ends or the viewer leaves
+    pub fn stream(mut self, ws: WebSocket) -> Channel<'static> {
+        ws.channel(move |mut socket| {
+            Box::pin(async move {
+                loop {
+                    tokio::select! {
+                        frame = self.recv() => match frame {
+                            Ok(text) => socket.send(Message::Text(text)).await?,
+                            Err(broadcast::error::RecvError::Lagged(_)) => {}
+                            Err(broadcast::error::RecvError::Closed) => break socket.close(None).await,
+                        },
+                        message = socket.next() => match message {
+                            // A close is answered while the stream is polled, which then ends
+                            Some(Ok(_)) => {}
+                            // A viewer that drops the connection (or breaks the protocol) just leaves
+                            Some(Err(Error::Protocol(_))) | None => break Ok(()),
+                            Some(Err(e)) => break Err(e),
+                        },
+                    }
+                }
+            })
+        })
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let mut channels = self.channels.lock();
+        // Our receiver still counts, so the last viewer sees a count of 1
+        let last = channels
+            .games
+            .get(&self.game_id)
+            .is_some_and(|channel| channel.id == self.channel_id && channel.sender.receiver_count() <= 1);
+        if last {
+            channels.games.remove(&self.game_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Direction;
+
+    #[tokio::test]
+    async fn test_frames_reach_viewers_until_the_game_ends() {
+        let hub = SpectatorHub::new(4);
+        let board = Board { height: 3, width: 3, food: vec![], snakes: vec![], hazards: vec![], occupancy: None };
+        let result = SearchResultV1::new(5, Direction::Up, 120, 4, 30);
+        let frame = SpectatorFrame { game_id: "g", you: "me", turn: 5, board: &board, budget_ms: 400, result: &result };
+
+        hub.publish(&frame);
+        assert!(!hub.is_watched("g"), "unwatched games have no channel");
+        let mut viewer = hub.subscribe("g");
+        assert!(hub.is_watched("g") && !hub.is_watched("other"));
+        hub.publish(&frame);
+        hub.close("g");
+
+        let text = viewer.recv().await.unwrap();
+        let json: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!((json["turn"].as_i64(), json["result"]["move"].as_str()), (Some(5), Some("up")));
+        assert!(matches!(viewer.recv().await, Err(broadcast::error::RecvError::Closed)));
+    }
+
+    #[test]
+    fn test_channels_go_away_with_their_last_viewer() {
+        let hub = SpectatorHub::new(4);
+        let first = hub.subscribe("g");
+        let second = hub.subscribe("g");
+        drop(first);
+        assert!(hub.is_watched("g"));
+        drop(second);
+        assert_eq!(hub.channels.lock().games.len(), 0);
+
+        // Viewers of made-up ids hold no channel once they leave
+        for i in 0..100 {
+            drop(hub.subscribe(&format!("fake-{}", i)));
+        }
+        assert_eq!(hub.channels.lock().games.len(), 0);
+
+        // A viewer of a closed game does not drop the channel of a new viewer of the same id
+        let old = hub.subscribe("g");
+        hub.close("g");
+        let new = hub.subscribe("g");
+        drop(old);
+        assert!(hub.is_watched("g"));
+        drop(new);
+        assert_eq!(hub.channels.lock().games.len(), 0);
+    }
+}
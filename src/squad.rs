@@ -0,0 +1,164 @@
+// Squad ruleset support
+//
+// In squad games snakes play in teams (Battlesnake.squad). The ruleset settings decide how much
+// a squad shares: squad-mates may pass through each other's bodies, die together, and share
+// the best health and length of the squad after every turn. SquadRules is read from the
+// ruleset as part of GameMode::Squad and reaches the search in Config::game_mode; move
+// generation, the simulation (Bot::advance_game_state) and rules::resolve_turn apply it, and
+// the evaluation treats squad-mates as allies: no head-to-head danger between them, their
+// territory counts for us (squad.ally_territory_weight), and MaxN compares team utilities
+// (the mean score of each squad) instead of individual scores.
+
+use crate::types::{Board, Ruleset};
+
+/// Squad settings of the ruleset (official defaults: everything shared)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SquadRules {
+    pub allow_body_collisions: bool,
+    pub shared_elimination: bool,
+    pub shared_health: bool,
+    pub shared_length: bool,
+}
+
+impl Default for SquadRules {
+    fn default() -> Self {
+        SquadRules { allow_body_collisions: true, shared_elimination: true, shared_health: true, shared_length: true }
+    }
+}
+
+impl SquadRules {
+    /// Squad settings of a game's ruleset; None unless the ruleset is "squad"
+    pub fn from_ruleset(ruleset: &Ruleset) -> Option<Self> {
+        if ruleset.name != "squad" {
+            return None;
+        }
+        let settings = ruleset.settings.squad.clone().unwrap_or_default();
+        let defaults = SquadRules::default();
+        Some(SquadRules {
+            allow_body_collisions: settings.allow_body_collisions.unwrap_or(defaults.allow_body_collisions),
+            shared_elimination: settings.shared_elimination.unwrap_or(defaults.shared_elimination),
+            shared_health: settings.shared_health.unwrap_or(defaults.shared_health),
+            shared_length: settings.shared_length.unwrap_or(defaults.shared_length),
+        })
+    }
+}
+
+/// Indices of the squad-mates of every snake (empty outside squads)
+fn squad_mates(board: &Board) -> Vec<Vec<usize>> {
+    board
+        .snakes
+        .iter()
+        .map(|snake| (0..board.snakes.len()).filter(|&other| snake.is_ally_of(&board.snakes[other])).collect())
+        .collect()
+}
+
+/// Applies shared health and length after a turn: every alive squad member takes the best
+/// health and the greatest length of its alive squad (growing at the tail)
+pub fn share_attributes(board: &mut Board, rules: &SquadRules) {
+    if !rules.shared_health && !rules.shared_length {
+        return;
+    }
+    let mates = squad_mates(board);
+    let alive = |board: &Board, idx: usize| board.snakes[idx].health > 0 && !board.snakes[idx].body.is_empty();
+    let targets: Vec<(i32, i32)> = (0..board.snakes.len())
+        .map(|idx| {
+            let squad = mates[idx].iter().copied().chain(std::iter::once(idx)).filter(|&i| alive(board, i));
+            squad.fold((0, 0), |(health, length), i| {
+                (health.max(board.snakes[i].health), length.max(board.snakes[i].length))
+            })
+        })
+        .collect();
+    for (idx, (health, length)) in targets.into_iter().enumerate() {
+        if mates[idx].is_empty() || !alive(board, idx) {
+            continue;
+        }
+        let snake = &mut board.snakes[idx];
+        if rules.shared_health {
+            snake.health = health;
+        }
+        if rules.shared_length {
+            while snake.length < length {
+                let tail = snake.body[snake.body.len() - 1];
+                snake.body.push(tail);
+                snake.length += 1;
+            }
+        }
+    }
+}
+
+/// Alive snakes that fall with an eliminated squad-mate: (index, id of the fallen mate)
+/// `eliminated` holds the indices eliminated this turn
+pub fn shared_eliminations(board: &Board, eliminated: &[usize]) -> Vec<(usize, String)> {
+    let mates = squad_mates(board);
+    (0..board.snakes.len())
+        .filter(|idx| !eliminated.contains(idx))
+        .filter_map(|idx| {
+            let fallen = mates[idx].iter().find(|mate| eliminated.contains(mate))?;
+            Some((idx, board.snakes[*fallen].id.clone()))
+        })
+        .collect()
+}
+
+/// Replaces every squad member's score by the mean score of its squad (fallen members
+/// included, so losing a squad-mate costs the whole team)
+pub fn team_utilities(board: &Board, scores: &mut [i32]) {
+    let mates = squad_mates(board);
+    let team: Vec<i32> = (0..scores.len().min(board.snakes.len()))
+        .map(|idx| {
+            let total: i64 = mates[idx].iter().map(|&mate| scores[mate] as i64).sum::<i64>() + scores[idx] as i64;
+            (total / (mates[idx].len() as i64 + 1)) as i32
+        })
+        .collect();
+    scores[..team.len()].copy_from_slice(&team);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board_builder::BoardBuilder;
+    use serde_json::json;
+
+    fn squad_board() -> Board {
+        let mut board = BoardBuilder::new(7, 7)
+            .snake("a1", &[(1, 1), (1, 2), (1, 3)])
+            .snake("a2", &[(3, 3), (3, 4)])
+            .snake("b1", &[(5, 5), (5, 4), (5, 3)])
+            .health("a2", 40)
+            .build();
+        for (snake, squad) in board.snakes.iter_mut().zip(["red", "red", "blue"]) {
+            snake.squad = Some(squad.to_string());
+        }
+        board
+    }
+
+    #[test]
+    fn test_squad_rules_come_from_the_ruleset() {
+        let ruleset: Ruleset = serde_json::from_value(json!({
+            "name": "squad",
+            "settings": { "squad": { "allowBodyCollisions": false, "sharedHealth": true } }
+        }))
+        .unwrap();
+        let rules = SquadRules::from_ruleset(&ruleset).unwrap();
+        assert!(!rules.allow_body_collisions);
+        assert!(rules.shared_elimination, "missing settings keep the official default");
+
+        let standard: Ruleset = serde_json::from_value(json!({ "name": "standard" })).unwrap();
+        assert_eq!(SquadRules::from_ruleset(&standard), None);
+    }
+
+    #[test]
+    fn test_squads_share_health_length_fate_and_utility() {
+        let mut board = squad_board();
+        share_attributes(&mut board, &SquadRules::default());
+        assert_eq!((board.snakes[1].health, board.snakes[1].length), (100, 3));
+        assert_eq!(board.snakes[1].body.len(), 3);
+        assert_eq!(board.snakes[2].length, 3, "other squads are untouched");
+
+        assert_eq!(shared_eliminations(&board, &[0]), vec![(1, "a1".to_string())]);
+        assert!(shared_eliminations(&board, &[2]).is_empty());
+
+        let mut scores = vec![100, 300, 50];
+        team_utilities(&board, &mut scores);
+        assert_eq!(scores, vec![200, 200, 50]);
+    }
+}
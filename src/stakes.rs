@@ -0,0 +1,92 @@
+// Game stakes: ladder or tournament
+//
+// Ladder games are cheap to lose, so the configured settings play for the best average.
+// Tournament games are single elimination: a timeout or a gamble that fails ends the run.
+// Tournament stakes keep the same config but play it conservatively: a larger space margin
+// for every reachability check, a larger network overhead allowance (the static overhead
+// and the measured estimate alike) and best-reply search, which lets all opponents answer
+// against us, on every board that is not a duel. Stakes are chosen per game at /start
+// (POST /start?stakes=tournament), falling back to stakes.default, and kept in the session.
+
+use crate::config::Config;
+
+/// How much a game is worth losing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stakes {
+    Ladder,
+    Tournament,
+}
+
+impl Stakes {
+    pub fn parse(name: &str) -> Result<Stakes, String> {
+        match name {
+            "ladder" => Ok(Stakes::Ladder),
+            "tournament" => Ok(Stakes::Tournament),
+            other => Err(format!("Unknown stakes '{}' (expected \"ladder\" or \"tournament\")", other)),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Stakes::Ladder => "ladder",
+            Stakes::Tournament => "tournament",
+        }
+    }
+
+    /// Stakes of a game whose /start named none (stakes.default; ladder if unreadable)
+    pub fn default_for(config: &Config) -> Stakes {
+        Stakes::parse(&config.stakes.default).unwrap_or(Stakes::Ladder)
+    }
+
+    /// `config` as played at these stakes (None: unchanged)
+    pub fn apply(&self, config: &Config) -> Option<Config> {
+        match self {
+            Stakes::Ladder => None,
+            Stakes::Tournament => Some(tournament(config)),
+        }
+    }
+}
+
+/// The conservative variant of `config` (see stakes.tournament_*)
+fn tournament(config: &Config) -> Config {
+    let stakes = &config.stakes;
+    let mut conservative = config.clone();
+    conservative.scores.space_safety_margin += stakes.tournament_extra_space_margin;
+
+    let extra_ms = stakes.tournament_extra_overhead_ms;
+    let timing = &mut conservative.timing;
+    timing.network_overhead_ms = (timing.network_overhead_ms + extra_ms).min(timing.response_time_budget_ms.saturating_sub(1));
+    timing.latency.safety_margin_ms += extra_ms;
+    timing.latency.min_overhead_ms += extra_ms;
+    timing.latency.max_overhead_ms += extra_ms;
+
+    if stakes.tournament_paranoid_search {
+        conservative.strategy.multiplayer_search = "brs".to_string();
+    }
+    conservative
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tournament_stakes_play_the_config_conservatively() {
+        let config = Config::default_hardcoded();
+        assert_eq!(Stakes::default_for(&config), Stakes::Ladder);
+        assert!(Stakes::Ladder.apply(&config).is_none());
+        assert_eq!(Stakes::parse("tournament"), Ok(Stakes::Tournament));
+        assert!(Stakes::parse("finals").is_err());
+
+        let conservative = Stakes::Tournament.apply(&config).unwrap();
+        let extra = config.stakes.tournament_extra_overhead_ms;
+        assert_eq!(
+            conservative.scores.space_safety_margin,
+            config.scores.space_safety_margin + config.stakes.tournament_extra_space_margin
+        );
+        assert_eq!(conservative.timing.network_overhead_ms, config.timing.network_overhead_ms + extra);
+        assert_eq!(conservative.timing.latency.max_overhead_ms, config.timing.latency.max_overhead_ms + extra);
+        assert_eq!(conservative.strategy.multiplayer_search, "brs");
+        assert!(conservative.validation_errors().is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+// Late-game stall mode
+//
+// Once we are longer than every opponent, at least as healthy and clearly ahead on the
+// evaluation, the safest plan is to give nothing away: patrol a Hamiltonian cycle of the
+// board, which visits every cell and never traps our own body. A board has one when a side
+// is even (a boustrophedon over all but the first column, returning up that column); boards
+// with two odd sides, and hazard-wall maps, get no stall mode. The patrol is perturbed to eat
+// adjacent food that is_food_actually_safe accepts. The chosen move gets stall.follow_bonus as
+// a root bias, so the search still overrules it when the patrol walks into danger.
+
+use crate::bot::Bot;
+use crate::config::Config;
+use crate::types::{Board, Coord, Direction};
+use crate::win_probability;
+
+/// Why stall mode picked its move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StallStep {
+    /// Next cell of the cycle (either direction)
+    Cycle,
+    /// Detour onto safe adjacent food
+    Food(Coord),
+}
+
+/// A Hamiltonian cycle of a width x height board, None if there is none
+pub fn cycle(width: i32, height: i32) -> Option<Vec<Coord>> {
+    if width < 2 || height < 2 || (width % 2 != 0 && height % 2 != 0) {
+        return None;
+    }
+    if height % 2 != 0 {
+        // Build it on the transposed board
+        let cells = cycle(height, width)?;
+        return Some(cells.into_iter().map(|c| Coord { x: c.y, y: c.x }).collect());
+    }
+    let mut cells = Vec::with_capacity((width * height) as usize);
+    for y in 0..height {
+        if y % 2 == 0 {
+            cells.extend((1..width).map(|x| Coord { x, y }));
+        } else {
+            cells.extend((1..width).rev().map(|x| Coord { x, y }));
+        }
+    }
+    // Back down the first column
+    cells.extend((0..height).rev().map(|y| Coord { x: 0, y }));
+    Some(cells)
+}
+
+/// Whether snake `idx` should stall: longer than every opponent by stall.min_length_lead, at
+/// least as healthy as each, and likely to win: a calibrated win probability of at least
+/// stall.min_win_probability (stall.min_eval_lead ahead of the best opponent's evaluation
+/// with [win_probability] disabled)
+pub fn is_ahead(board: &Board, idx: usize, config: &Config) -> bool {
+    let Some(us) = board.snakes.get(idx).filter(|s| s.health > 0) else { return false };
+    let opponents: Vec<usize> = (0..board.snakes.len()).filter(|&i| i != idx && board.snakes[i].health > 0).collect();
+    if opponents.is_empty() {
+        return false;
+    }
+    let dominates = opponents.iter().all(|&i| {
+        let opp = &board.snakes[i];
+        us.length - opp.length >= config.stall.min_length_lead && us.health >= opp.health
+    });
+    dominates && {
+        let scores = Bot::evaluate_all(board, &us.id, config);
+        if config.win_probability.enabled {
+            return win_probability::probability(scores[idx], config) >= config.stall.min_win_probability;
+        }
+        let best_opponent = opponents.iter().map(|&i| scores[i]).max().unwrap_or(i32::MIN);
+        scores[idx].saturating_sub(best_opponent) >= config.stall.min_eval_lead
+    }
+}
+
+/// The patrol move for snake `idx`, None if the board has no cycle or no patrol move is legal
+pub fn stall_move(board: &Board, idx: usize, config: &Config) -> Option<(Direction, StallStep)> {
+    if config.hazard_walls.is_some() {
+        return None;
+    }
+    let cells = cycle(board.width, board.height as i32)?;
+    let us = &board.snakes[idx];
+    let head = us.body[0];
+    let legal = Bot::generate_legal_moves(board, us, config);
+    let sources: Vec<usize> = (0..board.snakes.len()).filter(|&i| board.snakes[i].health > 0).collect();
+
+    let food = legal.iter().find_map(|&mv| {
+        let next = mv.apply(&head);
+        (board.is_food(next) && Bot::is_food_actually_safe(board, next, idx, &sources, None, config)).then_some((mv, StallStep::Food(next)))
+    });
+    if food.is_some() {
+        return food;
+    }
+
+    // Successor first: once the body lies on the cycle it is the only way forward
+    let at = cells.iter().position(|&c| c == head)?;
+    let n = cells.len();
+    [cells[(at + 1) % n], cells[(at + n - 1) % n]]
+        .iter()
+        .filter(|&&next| !board.is_hazard(next))
+        .filter_map(|&next| Direction::from_delta(next - head).filter(|mv| legal.contains(mv)))
+        .max_by_key(|&mv| {
+            let mut child = board.clone();
+            Bot::apply_move(&mut child, idx, mv, config);
+            // Ties go to the successor (max_by_key keeps the last maximum)
+            (Bot::flood_fill_bfs(&child, mv.apply(&head), idx, Some(us.body.len()), None), mv.apply(&head) == cells[(at + 1) % n])
+        })
+        .map(|mv| (mv, StallStep::Cycle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board_builder::BoardBuilder;
+
+    #[test]
+    fn test_cycles_visit_every_cell_once() {
+        for &(w, h) in &[(11, 10), (7, 6), (6, 7), (2, 2), (19, 18)] {
+            let cells = cycle(w, h).unwrap();
+            let mut sorted: Vec<(i32, i32)> = cells.iter().map(|c| (c.x, c.y)).collect();
+            sorted.sort_unstable();
+            sorted.dedup();
+            assert_eq!(sorted.len(), (w * h) as usize, "{}x{}", w, h);
+            for (i, c) in cells.iter().enumerate() {
+                assert_eq!(c.manhattan(cells[(i + 1) % cells.len()]), 1, "{}x{} step {}", w, h, i);
+            }
+        }
+        assert_eq!(cycle(11, 11), None, "odd by odd");
+    }
+
+    #[test]
+    fn test_stall_follows_the_cycle_and_takes_safe_food() {
+        let config = Config::default_hardcoded();
+        // 10x10: our body lies on the cycle's bottom row, heading right
+        let board = BoardBuilder::new(10, 10)
+            .snake("us", &[(5, 0), (4, 0), (3, 0), (2, 0), (1, 0), (0, 0), (0, 1)])
+            .snake("them", &[(5, 8), (5, 9), (4, 9)])
+            .health("them", 50)
+            .food(&[(9, 5)])
+            .build();
+        assert!(is_ahead(&board, 0, &config));
+        assert!(!is_ahead(&board, 1, &config));
+        assert_eq!(stall_move(&board, 0, &config), Some((Direction::Right, StallStep::Cycle)));
+
+        let mut hungry = board.clone();
+        hungry.food.push(Coord { x: 5, y: 1 });
+        assert_eq!(stall_move(&hungry, 0, &config), Some((Direction::Up, StallStep::Food(Coord { x: 5, y: 1 }))));
+    }
+}
@@ -0,0 +1,182 @@
+// Game-phase-aware time allocation
+//
+// The latency-adjusted budget is the most we may spend on a turn. Average positions use a
+// nominal share of it; forced moves use a small fraction, while positions with a large
+// joint branching factor or a volatile previous search get up to the full budget, and
+// positions the previous root score already decides (see win_probability) get less.
+// Turns that finish before their budget (stable or decided searches) deposit the rest in
+// the game's TimeBank, which tops up critical turns, still within the full budget.
+
+use crate::bot::Bot;
+use crate::config::Config;
+use crate::types::{Battlesnake, Board};
+use crate::win_probability;
+
+/// Budget chosen for one turn and the factors behind it (for the /move log line)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeAllocation {
+    pub budget_ms: u64,
+    pub scale: f64,
+    pub branching: usize,
+    pub forced: bool,
+}
+
+/// Allocates this turn's computation budget
+/// `max_budget_ms` is the hard per-turn cap; `previous_swing` is the largest score change
+/// between consecutive iterations of our previous search and `previous_score` its root score
+/// (None on the first turn)
+pub fn allocate(
+    max_budget_ms: u64,
+    board: &Board,
+    you: &Battlesnake,
+    previous_swing: Option<u32>,
+    previous_score: Option<i32>,
+    config: &Config,
+) -> TimeAllocation {
+    let allocation = &config.timing.allocation;
+    let our_moves = Bot::generate_legal_moves(board, you, config).len();
+    let branching = joint_branching(board, config);
+
+    if !allocation.enabled {
+        return TimeAllocation { budget_ms: max_budget_ms, scale: 1.0, branching, forced: false };
+    }
+
+    if our_moves <= 1 {
+        let budget_ms = (max_budget_ms as f64 * allocation.forced_move_fraction) as u64;
+        return TimeAllocation { budget_ms, scale: allocation.forced_move_fraction, branching, forced: true };
+    }
+
+    let branching_factor =
+        (branching as f64 / allocation.reference_branching.max(1.0)).powf(allocation.branching_exponent);
+    let volatility_factor = match previous_swing {
+        Some(swing) => {
+            let reference = allocation.volatility_reference.max(1) as f64;
+            ((swing as f64 + reference) / (2.0 * reference)).powf(allocation.volatility_exponent)
+        }
+        None => 1.0,
+    };
+
+    let decided_factor = win_probability::uncertainty_scale(previous_score, config);
+
+    let scale = (branching_factor * volatility_factor * decided_factor).clamp(allocation.min_scale, allocation.max_scale);
+    let budget_ms = ((max_budget_ms as f64 * allocation.nominal_fraction * scale) as u64).min(max_budget_ms);
+
+    TimeAllocation { budget_ms, scale, branching, forced: false }
+}
+
+/// Time saved by the turns of one game that finished early, kept in the game session
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimeBank {
+    balance_ms: u64,
+}
+
+impl TimeBank {
+    pub fn balance_ms(&self) -> u64 {
+        self.balance_ms
+    }
+
+    /// Banks the unspent part of a turn's budget (the balance is capped at
+    /// timing.bank.max_balance_ms)
+    pub fn deposit(&mut self, budget_ms: u64, elapsed_ms: u64, config: &Config) {
+        let bank = &config.timing.bank;
+        if bank.enabled {
+            self.balance_ms = (self.balance_ms + budget_ms.saturating_sub(elapsed_ms)).min(bank.max_balance_ms);
+        }
+    }
+
+    /// Extra milliseconds for this turn, taken from the balance
+    /// Only critical turns (previous search swung by at least timing.bank.critical_swing) are
+    /// topped up, and never beyond `max_budget_ms`, the turn's hard cap
+    pub fn withdraw(&mut self, budget_ms: u64, max_budget_ms: u64, previous_swing: Option<u32>, config: &Config) -> u64 {
+        let bank = &config.timing.bank;
+        if !bank.enabled || previous_swing.is_none_or(|swing| swing < bank.critical_swing) {
+            return 0;
+        }
+        let extra = self.balance_ms.min(max_budget_ms.saturating_sub(budget_ms));
+        self.balance_ms -= extra;
+        extra
+    }
+}
+
+/// Product of legal move counts of all alive snakes (size of the first joint ply)
+fn joint_branching(board: &Board, config: &Config) -> usize {
+    board
+        .snakes
+        .iter()
+        .filter(|s| s.health > 0)
+        .map(|s| Bot::generate_legal_moves(board, s, config).len().max(1))
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Coord;
+
+    fn snake(id: &str, body: Vec<Coord>) -> Battlesnake {
+        Battlesnake {
+            id: id.to_string(),
+            name: id.to_string(),
+            health: 100,
+            head: body[0],
+            length: body.len() as i32,
+            body,
+            latency: "0".to_string(),
+            shout: None,
+            squad: None,
+        }
+    }
+
+    fn board(snakes: Vec<Battlesnake>) -> Board {
+        Board { height: 11, width: 11, food: vec![], snakes, hazards: vec![], occupancy: None }
+    }
+
+    #[test]
+    fn test_forced_move_uses_small_fraction() {
+        let config = Config::default_hardcoded();
+        // Corner: only Up is free
+        let b = board(vec![
+            snake("us", vec![Coord { x: 0, y: 0 }, Coord { x: 1, y: 0 }, Coord { x: 2, y: 0 }]),
+            snake("them", vec![Coord { x: 8, y: 8 }, Coord { x: 8, y: 9 }, Coord { x: 8, y: 10 }]),
+        ]);
+
+        let allocation = allocate(350, &b, &b.snakes[0], None, None, &config);
+        assert!(allocation.forced);
+        assert!(allocation.budget_ms < 350 / 2);
+    }
+
+    #[test]
+    fn test_volatile_previous_search_gets_more_time() {
+        let config = Config::default_hardcoded();
+        let b = board(vec![
+            snake("us", vec![Coord { x: 5, y: 5 }, Coord { x: 5, y: 4 }, Coord { x: 5, y: 3 }]),
+            snake("them", vec![Coord { x: 8, y: 8 }, Coord { x: 8, y: 9 }, Coord { x: 8, y: 10 }]),
+        ]);
+
+        let stable = allocate(350, &b, &b.snakes[0], Some(0), None, &config);
+        let volatile = allocate(350, &b, &b.snakes[0], Some(100_000), None, &config);
+        assert!(volatile.budget_ms > stable.budget_ms);
+        assert!(volatile.budget_ms <= 350, "allocation must never exceed the hard budget");
+    }
+
+    #[test]
+    fn test_time_bank_tops_up_critical_turns_within_the_hard_budget() {
+        let config = Config::default_hardcoded();
+        let critical = Some(config.timing.bank.critical_swing);
+        let mut bank = TimeBank::default();
+
+        bank.deposit(280, 100, &config);
+        bank.deposit(280, 300, &config);
+        assert_eq!(bank.balance_ms(), 180, "overrunning turns deposit nothing");
+        assert_eq!(bank.withdraw(280, 350, Some(0), &config), 0, "calm turns do not spend");
+
+        assert_eq!(bank.withdraw(280, 350, critical, &config), 70, "capped by the hard budget");
+        assert_eq!(bank.withdraw(200, 350, critical, &config), 110);
+        assert_eq!(bank.balance_ms(), 0);
+
+        for _ in 0..10 {
+            bank.deposit(350, 0, &config);
+        }
+        assert_eq!(bank.balance_ms(), config.timing.bank.max_balance_ms);
+    }
+}
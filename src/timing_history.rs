@@ -0,0 +1,127 @@
+// Iteration timings remembered across turns and games
+//
+// AdaptiveTimeEstimator starts every search from the configured exponential model, so each
+// turn used to re-learn how long depth 2, 3, ... take. Finished searches now leave their
+// observed iteration times here, bucketed by board size and alive snakes (the two inputs that
+// change iteration cost the most), and the next search in the same bucket is seeded with them.
+// Every game session keeps its own history, seeded from the bot-wide one when the game starts;
+// time_estimation.history_file keeps the bot-wide history across restarts.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use crate::types::Board;
+
+/// Running mean of the observed time of one depth
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct DepthTiming {
+    /// Observations folded into the mean (capped, so old games fade out)
+    pub observations: u32,
+    pub mean_ms: f64,
+}
+
+/// Observed iteration times of one bucket, indexed by depth
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DepthTimings {
+    pub depths: Vec<DepthTiming>,
+}
+
+impl DepthTimings {
+    /// Mean observed time of `depth` (None if never observed)
+    pub fn mean_ms(&self, depth: u8) -> Option<f64> {
+        self.depths.get(depth as usize).filter(|t| t.observations > 0).map(|t| t.mean_ms)
+    }
+
+    /// Folds one observation into the mean of `depth`, weighting it at least 1/max_observations
+    pub fn observe(&mut self, depth: u8, elapsed_ms: f64, max_observations: u32) {
+        let idx = depth as usize;
+        if self.depths.len() <= idx {
+            self.depths.resize(idx + 1, DepthTiming::default());
+        }
+        let timing = &mut self.depths[idx];
+        let n = timing.observations.min(max_observations.saturating_sub(1));
+        timing.mean_ms = (timing.mean_ms * n as f64 + elapsed_ms) / (n as f64 + 1.0);
+        timing.observations = n + 1;
+    }
+}
+
+/// Observed iteration times per bucket (see TimingHistory::bucket)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TimingHistory {
+    buckets: BTreeMap<String, DepthTimings>,
+}
+
+impl TimingHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bucket of a position, e.g. "11x11/4" for four alive snakes on a standard board
+    pub fn bucket(board: &Board) -> String {
+        let alive = board.snakes.iter().filter(|s| s.health > 0).count();
+        format!("{}x{}/{}", board.width, board.height, alive)
+    }
+
+    /// Timings observed in the bucket of `board` (None if none yet)
+    pub fn prior(&self, board: &Board) -> Option<DepthTimings> {
+        self.buckets.get(&Self::bucket(board)).cloned()
+    }
+
+    /// Records the (depth, ms) observations of a search on `board`
+    pub fn record(&mut self, board: &Board, timings: &[(u8, f64)], max_observations: u32) {
+        if timings.is_empty() {
+            return;
+        }
+        let bucket = self.buckets.entry(Self::bucket(board)).or_default();
+        for &(depth, elapsed_ms) in timings {
+            bucket.observe(depth, elapsed_ms, max_observations);
+        }
+    }
+
+    /// Reads a history written by save
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let json = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&json).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+    }
+
+    /// Writes the history as JSON, creating the parent directory if needed
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize timing history: {}", e))?;
+        fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board_builder::BoardBuilder;
+
+    #[test]
+    fn test_history_is_bucketed_capped_and_persisted() {
+        let duel = BoardBuilder::new(11, 11).snake("a", &[(1, 1)]).snake("b", &[(9, 9)]).build();
+        let solo = BoardBuilder::new(11, 11).snake("a", &[(1, 1)]).build();
+        let mut history = TimingHistory::new();
+        history.record(&duel, &[(2, 1.0), (3, 4.0)], 2);
+        history.record(&duel, &[(3, 8.0)], 2);
+        history.record(&duel, &[(3, 10.0)], 2);
+
+        assert_eq!(TimingHistory::bucket(&duel), "11x11/2");
+        assert!(history.prior(&solo).is_none());
+        let prior = history.prior(&duel).unwrap();
+        assert_eq!(prior.mean_ms(2), Some(1.0));
+        // Capped at two observations: the third moves the mean halfway
+        assert_eq!(prior.mean_ms(3), Some(8.0));
+        assert_eq!(prior.depths[3].observations, 2);
+        assert_eq!(prior.mean_ms(4), None);
+
+        let path = std::env::temp_dir().join(format!("timing_history_test_{}.json", std::process::id()));
+        history.save(&path).unwrap();
+        assert_eq!(TimingHistory::load(&path).unwrap(), history);
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,136 @@
+// Trap-setting planner
+//
+// attack_trap_bonus only notices opponents that already lack space. This module rewards
+// taking it away: a shorter opponent whose head is within scores.trap_setting_radius of ours
+// is trapped when its Voronoi region (the free cells it reaches before every other snake, from
+// the evaluation's distance field, which also credits each snake with its own body) is
+// smaller than its body. The evaluation adds trap_setting_bonus per trapped opponent plus
+// trap_setting_deficit_bonus per missing cell, weighted by weight_trap_setting, so the search
+// steers towards cutting moves. trap_moves names those moves at the root.
+
+use crate::bot::Bot;
+use crate::config::Config;
+use crate::distance_field::{ControlSummary, DistanceField};
+use crate::types::{Board, Direction};
+
+/// Opponents of snake `idx` caught in a trap, with their Voronoi region
+/// Only snakes in `sources` (the field's snakes) have a region
+pub fn trapped_opponents(board: &Board, idx: usize, control: &ControlSummary, sources: &[usize], config: &Config) -> Vec<(usize, usize)> {
+    let Some(head) = board.snakes.get(idx).filter(|s| s.health > 0).and_then(|s| s.body.first().copied()) else {
+        return Vec::new();
+    };
+    let length = board.snakes[idx].length;
+    board
+        .snakes
+        .iter()
+        .enumerate()
+        .filter(|&(opp, snake)| {
+            opp != idx
+                && sources.contains(&opp)
+                && snake.health > 0
+                && snake.length < length
+                && snake.body.first().is_some_and(|h| h.manhattan(head) <= config.scores.trap_setting_radius)
+        })
+        .filter_map(|(opp, snake)| {
+            let mut body = snake.body.clone();
+            body.sort_unstable_by_key(|c| (c.x, c.y));
+            body.dedup();
+            let region = control.owned.get(opp).copied().unwrap_or(0).saturating_sub(body.len());
+            (region < snake.length as usize).then_some((opp, region))
+        })
+        .collect()
+}
+
+/// Trap-setting score of snake `idx` (before weight_trap_setting)
+pub fn trap_score(board: &Board, idx: usize, control: &ControlSummary, sources: &[usize], config: &Config) -> i32 {
+    trapped_opponents(board, idx, control, sources, config)
+        .into_iter()
+        .map(|(opp, region)| {
+            let deficit = board.snakes[opp].length - region as i32;
+            config.scores.trap_setting_bonus + deficit * config.scores.trap_setting_deficit_bonus
+        })
+        .sum()
+}
+
+/// Our moves that trap an opponent the current position does not, with the opponents they
+/// trap and their regions after the move (opponents have not replied yet)
+pub fn trap_moves(board: &Board, our_idx: usize, config: &Config) -> Vec<(Direction, Vec<(usize, usize)>)> {
+    let Some(you) = board.snakes.get(our_idx) else { return Vec::new() };
+    let sources: Vec<usize> = (0..board.snakes.len()).filter(|&idx| board.snakes[idx].health > 0).collect();
+    let trapped = |board: &Board| {
+        let control = DistanceField::build(board, &sources, config.hazard_walls.as_deref()).control_summary(board.snakes.len());
+        trapped_opponents(board, our_idx, &control, &sources, config)
+    };
+    let before: Vec<usize> = trapped(board).into_iter().map(|(opp, _)| opp).collect();
+
+    Bot::generate_legal_moves(board, you, config)
+        .into_iter()
+        .filter_map(|mv| {
+            let mut child = board.clone();
+            Bot::apply_move(&mut child, our_idx, mv, config);
+            let cut: Vec<(usize, usize)> = trapped(&child).into_iter().filter(|(opp, _)| !before.contains(opp)).collect();
+            (!cut.is_empty()).then_some((mv, cut))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board_builder::BoardBuilder;
+    use crate::bot::{HistoryTable, SearchLimits, SharedSearchState};
+    use std::sync::Arc;
+
+    /// tests/fixtures/1v1_self/game_19.jsonl, turn 144 (won on turn 146): eating at (9, 0)
+    /// makes us longer and walls the opponent into the right-hand column
+    fn game_19_turn_144() -> Board {
+        BoardBuilder::new(11, 11)
+            .snake(
+                "us",
+                &[(9, 1), (9, 2), (8, 2), (8, 3), (7, 3), (6, 3), (5, 3), (5, 4), (5, 5), (5, 6), (5, 7), (5, 8), (4, 8), (4, 7), (4, 7)],
+            )
+            .snake(
+                "them",
+                &[(10, 2), (10, 3), (10, 4), (9, 4), (9, 5), (8, 5), (7, 5), (7, 6), (7, 7), (7, 8), (7, 9), (6, 9), (6, 8), (6, 7), (6, 6)],
+            )
+            .health("them", 98)
+            .food(&[(9, 10), (9, 0), (10, 7), (3, 8), (0, 7), (8, 10), (4, 3)])
+            .build()
+    }
+
+    #[test]
+    fn test_trap_move_from_a_won_game() {
+        let config = Config::default_hardcoded();
+        let board = game_19_turn_144();
+        assert_eq!(trap_moves(&board, 0, &config), vec![(Direction::Down, vec![(1, 1)])]);
+
+        let you = board.snakes[0].clone();
+        let shared = Arc::new(SharedSearchState::new());
+        let limits = SearchLimits::default().with_fixed_depth(Some(3));
+        Bot::compute_best_move_internal(&board, &you, 144, shared.clone(), &config, &mut HistoryTable::new(11, 11), limits);
+        assert_eq!(shared.get_best().0, Bot::direction_to_index(Direction::Down, &config), "the search springs the trap");
+    }
+
+    #[test]
+    fn test_trap_score_needs_a_shorter_opponent_close_by() {
+        let config = Config::default_hardcoded();
+        // A 5-long opponent along the bottom wall, its head sealed into the corner by our body
+        let board = BoardBuilder::new(11, 11)
+            .snake("us", &[(2, 1), (2, 2), (1, 2), (0, 2), (0, 3), (0, 4), (0, 5)])
+            .snake("them", &[(0, 0), (1, 0), (2, 0), (3, 0), (4, 0)])
+            .build();
+        let sources = [0, 1];
+        let control = DistanceField::build(&board, &sources, None).control_summary(2);
+        let trapped = trapped_opponents(&board, 0, &control, &sources, &config);
+        assert_eq!(trapped.len(), 1);
+        let (_, region) = trapped[0];
+        let deficit = 5 - region as i32;
+        assert_eq!(trap_score(&board, 0, &control, &sources, &config), config.scores.trap_setting_bonus + deficit * config.scores.trap_setting_deficit_bonus);
+
+        assert!(trapped_opponents(&board, 0, &control, &[0], &config).is_empty(), "only field snakes have a region");
+        assert!(trapped_opponents(&board, 1, &control, &sources, &config).is_empty(), "the longer snake is never trapped");
+        let mut far = config.clone();
+        far.scores.trap_setting_radius = 2;
+        assert!(trapped_opponents(&board, 0, &control, &sources, &far).is_empty(), "heads three apart");
+    }
+}
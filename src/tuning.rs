@@ -0,0 +1,300 @@
+// SPSA tuning of [scores] parameters through self-play
+//
+// Simultaneous Perturbation Stochastic Approximation estimates the gradient of the win rate
+// from just two configs per iteration: every tuned parameter is shifted by +c_k or -c_k
+// (random sign per parameter), the "plus" and "minus" configs play an arena match, and each
+// parameter moves toward the side that scored better. Parameters live in [0, 1] over the
+// bounds declared in [tuning.parameters], so one step size fits weights and bonuses alike.
+// Driven by src/bin/tune_spsa.rs. The same normalized encoding is the genome of the genetic
+// search in evolution.rs.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::Value;
+
+use crate::config::{Config, ScoresConfig, TuningConfig};
+
+/// One tuned [scores] field
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parameter {
+    pub name: String,
+    pub min: f64,
+    pub max: f64,
+    /// Integer fields are rounded when written back
+    pub integer: bool,
+}
+
+impl Parameter {
+    /// Value in [scores] units of a normalized position in [0, 1]
+    pub fn value(&self, normalized: f64) -> f64 {
+        let value = self.min + normalized.clamp(0.0, 1.0) * (self.max - self.min);
+        if self.integer {
+            value.round()
+        } else {
+            value
+        }
+    }
+
+    fn normalize(&self, value: f64) -> f64 {
+        ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+    }
+
+    fn to_json(&self, value: f64) -> Value {
+        if self.integer {
+            Value::from(value as i64)
+        } else {
+            Value::from(value)
+        }
+    }
+}
+
+/// A +/- pair of configs to play against each other
+pub struct Perturbation {
+    /// +1 or -1 per parameter
+    pub delta: Vec<f64>,
+    pub plus: Config,
+    pub minus: Config,
+}
+
+/// SPSA optimizer state
+pub struct Spsa {
+    base: Config,
+    settings: TuningConfig,
+    parameters: Vec<Parameter>,
+    /// Current point, normalized to [0, 1] per parameter
+    theta: Vec<f64>,
+    iteration: u32,
+    rng: StdRng,
+}
+
+impl Spsa {
+    /// Starts from the current [scores] values of `base`
+    ///
+    /// # Returns
+    /// * `Err` if a tuned name is not a numeric [scores] field or its bounds are empty
+    pub fn new(base: &Config) -> Result<Self, String> {
+        let (parameters, theta) = tuned_parameters(base)?;
+        Ok(Spsa {
+            base: base.clone(),
+            settings: base.tuning.clone(),
+            parameters,
+            theta,
+            iteration: 0,
+            rng: StdRng::seed_from_u64(base.tuning.seed),
+        })
+    }
+
+    pub fn parameters(&self) -> &[Parameter] {
+        &self.parameters
+    }
+
+    pub fn iteration(&self) -> u32 {
+        self.iteration
+    }
+
+    /// Step size a_k and perturbation size c_k for the current iteration
+    pub fn gains(&self) -> (f64, f64) {
+        let s = &self.settings;
+        let k = self.iteration as f64;
+        let a_k = s.learning_rate / (k + 1.0 + s.stability_constant).powf(s.learning_rate_decay);
+        let c_k = s.perturbation / (k + 1.0).powf(s.perturbation_decay);
+        (a_k, c_k)
+    }
+
+    /// Current parameter values in [scores] units
+    pub fn values(&self) -> Vec<(String, f64)> {
+        values_at(&self.parameters, &self.theta)
+    }
+
+    /// Base config with the parameters set to `theta`
+    fn config_at(&self, theta: &[f64]) -> Result<Config, String> {
+        config_at(&self.base, &self.parameters, theta)
+    }
+
+    /// Config at the current point
+    pub fn current_config(&self) -> Result<Config, String> {
+        self.config_at(&self.theta)
+    }
+
+    /// Draws a random sign per parameter and builds the two configs at theta +/- c_k * delta
+    pub fn perturb(&mut self) -> Result<Perturbation, String> {
+        let (_, c_k) = self.gains();
+        let delta: Vec<f64> = (0..self.theta.len())
+            .map(|_| if self.rng.random_bool(0.5) { 1.0 } else { -1.0 })
+            .collect();
+        let shifted = |sign: f64| -> Vec<f64> {
+            self.theta.iter().zip(&delta).map(|(t, d)| t + sign * c_k * d).collect()
+        };
+        Ok(Perturbation {
+            plus: self.config_at(&shifted(1.0))?,
+            minus: self.config_at(&shifted(-1.0))?,
+            delta,
+        })
+    }
+
+    /// SPSA update from the plus config's match score in [0, 1] against the minus config
+    pub fn update(&mut self, perturbation: &Perturbation, plus_score: f64) {
+        let (a_k, c_k) = self.gains();
+        // Score difference (plus - minus) in [-1, 1]
+        let difference = 2.0 * plus_score - 1.0;
+        for (theta, delta) in self.theta.iter_mut().zip(&perturbation.delta) {
+            let gradient = difference / (2.0 * c_k * delta);
+            *theta = (*theta + a_k * gradient).clamp(0.0, 1.0);
+        }
+        self.iteration += 1;
+    }
+}
+
+/// The [scores] fields listed in [tuning.parameters] and their current values in `base`,
+/// normalized to [0, 1] over their bounds
+///
+/// # Returns
+/// * `Err` if a tuned name is not a numeric [scores] field or its bounds are empty
+pub fn tuned_parameters(base: &Config) -> Result<(Vec<Parameter>, Vec<f64>), String> {
+    let scores = serde_json::to_value(&base.scores).map_err(|e| format!("Failed to serialize scores: {}", e))?;
+    let mut parameters = Vec::new();
+    let mut theta = Vec::new();
+    for (name, &[min, max]) in &base.tuning.parameters {
+        let current = scores
+            .get(name)
+            .filter(|v| v.is_number())
+            .ok_or_else(|| format!("tuning.parameters.{} is not a numeric [scores] field", name))?;
+        if min >= max {
+            return Err(format!("tuning.parameters.{}: min {} must be below max {}", name, min, max));
+        }
+        let parameter = Parameter { name: name.clone(), min, max, integer: !current.is_f64() };
+        theta.push(parameter.normalize(current.as_f64().unwrap_or(min)));
+        parameters.push(parameter);
+    }
+    if parameters.is_empty() {
+        return Err("[tuning.parameters] is empty".to_string());
+    }
+    Ok((parameters, theta))
+}
+
+/// Parameter values in [scores] units at the normalized point `theta`
+pub fn values_at(parameters: &[Parameter], theta: &[f64]) -> Vec<(String, f64)> {
+    parameters.iter().zip(theta).map(|(p, &t)| (p.name.clone(), p.value(t))).collect()
+}
+
+/// `base` with the parameters set to the normalized point `theta`
+pub fn config_at(base: &Config, parameters: &[Parameter], theta: &[f64]) -> Result<Config, String> {
+    let overrides: serde_json::Map<String, Value> = parameters
+        .iter()
+        .zip(theta)
+        .map(|(p, &t)| (p.name.clone(), p.to_json(p.value(t))))
+        .collect();
+    let mut config = base.clone();
+    config.scores = base.scores.with_overrides(&Value::Object(overrides))?;
+    Ok(config)
+}
+
+/// Returns `toml` (the text of a Snake.toml) with `values` written into its [scores] table
+/// Other tables (including [profiles.*] with the same keys) and comments are left untouched
+pub fn rewrite_scores(toml: &str, scores: &ScoresConfig, values: &[(String, f64)]) -> Result<String, String> {
+    let current = serde_json::to_value(scores).map_err(|e| format!("Failed to serialize scores: {}", e))?;
+    let mut section = String::new();
+    let mut written = Vec::new();
+    let mut out = String::with_capacity(toml.len());
+
+    for line in toml.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            section = trimmed.trim_matches(|c| c == '[' || c == ']').trim().to_string();
+        }
+        let key = trimmed.split('=').next().unwrap_or("").trim();
+        let replacement = match values.iter().find(|(name, _)| section == "scores" && trimmed.contains('=') && name == key) {
+            Some((name, value)) => {
+                let comment = line.find(" #").map(|i| &line[i..]).unwrap_or("");
+                let formatted = if current.get(name).is_some_and(Value::is_f64) {
+                    format_float(*value)
+                } else {
+                    format!("{}", value.round() as i64)
+                };
+                written.push(name.as_str());
+                Some(format!("{} = {}{}", name, formatted, comment))
+            }
+            None => None,
+        };
+        out.push_str(replacement.as_deref().unwrap_or(line));
+        out.push('\n');
+    }
+
+    match values.iter().find(|(name, _)| !written.contains(&name.as_str())) {
+        Some((name, _)) => Err(format!("[scores] has no '{}' line to update", name)),
+        None => Ok(out),
+    }
+}
+
+/// Up to 3 decimals, always with a decimal point (20.0, 12.345)
+fn format_float(value: f64) -> String {
+    let mut text = format!("{:.3}", value);
+    while text.ends_with('0') && !text.ends_with(".0") {
+        text.pop();
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spsa() -> Spsa {
+        Spsa::new(&Config::default_hardcoded()).unwrap()
+    }
+
+    #[test]
+    fn test_gains_decay_and_start_from_current_values() {
+        let mut spsa = spsa();
+        let values = spsa.values();
+        let value = |name: &str| values.iter().find(|(n, _)| n == name).unwrap().1;
+        assert!((value("weight_space") - 20.0).abs() < 1e-6);
+        assert_eq!(value("attack_head_to_head_bonus"), 200.0);
+
+        let (a0, c0) = spsa.gains();
+        let perturbation = spsa.perturb().unwrap();
+        spsa.update(&perturbation, 0.5);
+        let (a1, c1) = spsa.gains();
+        assert!(a1 < a0 && c1 < c0);
+        assert_eq!(spsa.values(), values, "an even match must not move the parameters");
+    }
+
+    #[test]
+    fn test_update_moves_toward_the_winning_side_within_bounds() {
+        let mut spsa = spsa();
+        let before = spsa.values();
+        let perturbation = spsa.perturb().unwrap();
+        for ((name, value), delta) in before.iter().zip(&perturbation.delta) {
+            let plus: f64 = serde_json::to_value(&perturbation.plus.scores).unwrap()[name].as_f64().unwrap();
+            assert_eq!(plus > *value, *delta > 0.0, "{} plus side", name);
+        }
+
+        spsa.update(&perturbation, 1.0);
+        for (((name, old), (_, new)), delta) in before.iter().zip(spsa.values()).zip(&perturbation.delta) {
+            assert_eq!(new > *old, *delta > 0.0, "{} moves toward plus", name);
+            let p = spsa.parameters().iter().find(|p| &p.name == name).unwrap();
+            assert!((p.min..=p.max).contains(&new));
+        }
+    }
+
+    #[test]
+    fn test_unknown_parameter_is_rejected() {
+        let mut config = Config::default_hardcoded();
+        config.tuning.parameters.insert("weight_nonsense".to_string(), [0.0, 1.0]);
+        assert!(Spsa::new(&config).is_err());
+    }
+
+    #[test]
+    fn test_rewrite_scores_only_touches_the_scores_table() {
+        let toml = "[scores]\nweight_space = 20.0\nweight_length = 100 # per segment\n\n[profiles.defensive]\nweight_space = 30.0\n";
+        let scores = Config::default_hardcoded().scores;
+        let values = vec![("weight_space".to_string(), 24.5), ("weight_length".to_string(), 87.6)];
+
+        let rewritten = rewrite_scores(toml, &scores, &values).unwrap();
+        assert_eq!(
+            rewritten,
+            "[scores]\nweight_space = 24.5\nweight_length = 88 # per segment\n\n[profiles.defensive]\nweight_space = 30.0\n"
+        );
+        assert!(rewrite_scores(toml, &scores, &[("weight_attack".to_string(), 1.0)]).is_err());
+    }
+}
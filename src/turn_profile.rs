@@ -0,0 +1,134 @@
+// Per-turn profiling export
+//
+// The profiler report goes to stderr as one dump per search, which is hard to line up with
+// turns and impossible to graph. With [profiling] enabled and json_directory set, every
+// search writes one JSON document, <json_directory>/<game id>/turn_<turn>.json: budget and
+// search time, nodes and node rate, TT probes and hits, each completed iteration with its
+// nodes and branching factor (nodes over the previous iteration's nodes), and the profiler
+// categories (calls and time) accumulated during the search. The category counters are
+// process-wide, so concurrent games show up in each other's documents.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::bot::{IterationStats, SharedSearchState};
+use crate::debug_logger::file_safe_id;
+use crate::simple_profiler::ProfileSnapshot;
+
+/// One completed iteration
+#[derive(Debug, Clone, Serialize)]
+pub struct IterationProfile {
+    pub depth: u8,
+    pub time_ms: u64,
+    pub nodes: u64,
+    /// Nodes over the previous iteration's nodes (None for the first iteration)
+    pub branching_factor: Option<f64>,
+}
+
+/// Profile of the search behind one /move
+#[derive(Debug, Clone, Serialize)]
+pub struct TurnProfile {
+    pub game_id: String,
+    pub turn: i32,
+    pub timestamp: String,
+    pub budget_ms: u64,
+    /// Wall time from the start of the request until the search thread finished
+    pub search_ms: u64,
+    /// Deepest iteration started (the last may have been aborted)
+    pub depth: u8,
+    pub nodes: u64,
+    pub nodes_per_ms: f64,
+    pub tt_probes: u64,
+    pub tt_hits: u64,
+    /// Transposition lookups that found another position in the slot
+    pub tt_collisions: u64,
+    pub iterations: Vec<IterationProfile>,
+    pub components: ProfileSnapshot,
+}
+
+/// Iterations with the branching factor of each over the one before
+pub fn iteration_profiles(iterations: &[IterationStats]) -> Vec<IterationProfile> {
+    iterations
+        .iter()
+        .enumerate()
+        .map(|(idx, it)| IterationProfile {
+            depth: it.depth,
+            time_ms: it.time_ms,
+            nodes: it.nodes,
+            branching_factor: idx.checked_sub(1).and_then(|prev| it.branching_over(&iterations[prev])),
+        })
+        .collect()
+}
+
+impl TurnProfile {
+    /// Profile of a finished search; `components` is the profiler difference over the search
+    pub fn from_search(
+        game_id: &str,
+        turn: i32,
+        budget_ms: u64,
+        search_ms: u64,
+        shared: &SharedSearchState,
+        components: ProfileSnapshot,
+    ) -> Self {
+        let nodes = shared.nodes();
+        let (tt_probes, tt_hits) = shared.tt_stats();
+        TurnProfile {
+            game_id: game_id.to_string(),
+            turn,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            budget_ms,
+            search_ms,
+            depth: shared.current_depth.load(std::sync::atomic::Ordering::Acquire),
+            nodes,
+            nodes_per_ms: nodes as f64 / search_ms.max(1) as f64,
+            tt_probes,
+            tt_hits,
+            tt_collisions: shared.tt_collisions(),
+            iterations: iteration_profiles(&shared.iterations()),
+            components,
+        }
+    }
+
+    /// `<directory>/<game id>/turn_<turn>.json` (turn zero-padded to sort in order)
+    pub fn path(directory: &Path, game_id: &str, turn: i32) -> PathBuf {
+        directory.join(file_safe_id(game_id)).join(format!("turn_{:04}.json", turn))
+    }
+
+    /// Writes the document (creating the game's directory); returns its path
+    pub fn write(&self, directory: &Path) -> Result<PathBuf, String> {
+        let path = Self::path(directory, &self.game_id, self.turn);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize profile: {}", e))?;
+        std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_lists_iterations_with_branching_factors() {
+        let shared = SharedSearchState::new();
+        shared.nodes_searched.store(1300, std::sync::atomic::Ordering::Relaxed);
+        for (depth, nodes) in [(2u8, 100u64), (3, 400), (4, 800)] {
+            shared.record_iteration(IterationStats { depth, time_ms: nodes / 10, nodes });
+        }
+
+        let profile = TurnProfile::from_search("a/b", 7, 300, 130, &shared, ProfileSnapshot::default());
+        let factors: Vec<Option<f64>> = profile.iterations.iter().map(|it| it.branching_factor).collect();
+        assert_eq!(factors, vec![None, Some(4.0), Some(2.0)]);
+        assert_eq!(profile.nodes_per_ms, 10.0);
+
+        let dir = std::env::temp_dir().join(format!("turn_profile_{}", std::process::id()));
+        let path = profile.write(&dir).unwrap();
+        assert_eq!(path, dir.join("a_b").join("turn_0007.json"));
+        let written: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written["iterations"][1]["branching_factor"], 4.0);
+        assert_eq!(written["components"]["eval"]["calls"], 0);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -2,29 +2,251 @@
 // See https://docs.battlesnake.com/api
 
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
-use std::collections::HashMap;
+use std::ops::{Add, Sub};
+
+use crate::occupancy::Occupancy;
+use crate::simple_profiler;
 
 /// Game metadata including ID, ruleset, and timeout
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Game {
     pub id: String,
-    pub ruleset: HashMap<String, Value>,
+    pub ruleset: Ruleset,
     pub timeout: u32,
+    /// Map the game is played on (e.g. "snail_mode"); empty if the engine sent none
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub map: String,
+}
+
+/// Ruleset of a game (name, version and the settings the engine plays with)
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct Ruleset {
+    pub name: String,
+    pub version: String,
+    pub settings: RulesetSettings,
+}
+
+impl Ruleset {
+    /// Name, version and the settings that were sent, e.g. "royale v1.2.3 (hazard damage 14,
+    /// minimum food 1, food spawn 15%, shrink every 25 turns)"
+    pub fn describe(&self) -> String {
+        let s = &self.settings;
+        let mut parts = Vec::new();
+        if let Some(damage) = s.hazard_damage_per_turn {
+            parts.push(format!("hazard damage {}", damage));
+        }
+        if let Some(minimum) = s.minimum_food {
+            parts.push(format!("minimum food {}", minimum));
+        }
+        if let Some(chance) = s.food_spawn_chance {
+            parts.push(format!("food spawn {}%", chance));
+        }
+        if let Some(turns) = s.royale.as_ref().and_then(|royale| royale.shrink_every_n_turns) {
+            parts.push(format!("shrink every {} turns", turns));
+        }
+        let name = if self.name.is_empty() { "?" } else { &self.name };
+        let version = if self.version.is_empty() { "?" } else { &self.version };
+        if parts.is_empty() {
+            format!("{} {}", name, version)
+        } else {
+            format!("{} {} ({})", name, version, parts.join(", "))
+        }
+    }
+}
+
+/// Ruleset settings; a setting the engine did not send is None (the [game_rules] and
+/// [arena] values apply)
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RulesetSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub food_spawn_chance: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum_food: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hazard_damage_per_turn: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub royale: Option<RoyaleSettings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub squad: Option<SquadSettings>,
+}
+
+/// Royale settings (the hazard ring is not simulated; the value is reported at /start)
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct RoyaleSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shrink_every_n_turns: Option<u32>,
+}
+
+/// Squad settings as sent by the engine (see squad::SquadRules for the defaults)
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SquadSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_body_collisions: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shared_elimination: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shared_health: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shared_length: Option<bool>,
 }
 
 /// Board state including dimensions, food, snakes, and hazards
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct Board {
     pub height: u32,
     pub width: i32,
     pub food: Vec<Coord>,
     pub snakes: Vec<Battlesnake>,
     pub hazards: Vec<Coord>,
+    /// Cached occupancy grid (see occupancy.rs); None unless Board::cache_occupancy was called
+    #[serde(skip)]
+    pub occupancy: Option<Occupancy>,
+}
+
+impl Board {
+    /// Clears the bodies of eliminated snakes (health 0 or no body) in simulated boards
+    ///
+    /// Eliminated snakes keep their slot, so a snake's index (the player index of search score
+    /// tuples, killers and history) stays stable for the rest of the search; only their bodies
+    /// vanish, so nothing treats them as obstacles and clones stop copying them.
+    pub fn remove_eliminated(&mut self) {
+        for snake in self.snakes.iter_mut().filter(|s| s.health <= 0 || s.body.is_empty()) {
+            snake.health = 0;
+            snake.body.clear();
+        }
+    }
+
+    /// Heap bytes a clone copies (cell lists, snake records, bodies and strings)
+    pub fn heap_bytes(&self) -> usize {
+        let coord = std::mem::size_of::<Coord>();
+        let snakes: usize = self
+            .snakes
+            .iter()
+            .map(|s| {
+                std::mem::size_of::<Battlesnake>()
+                    + s.body.len() * coord
+                    + s.id.len()
+                    + s.name.len()
+                    + s.latency.len()
+                    + s.shout.as_ref().map_or(0, String::len)
+                    + s.squad.as_ref().map_or(0, String::len)
+            })
+            .sum();
+        (self.food.len() + self.hazards.len()) * coord + snakes
+    }
+}
+
+// Clone is implemented by hand so clone_from reuses the existing allocations
+// (the search overwrites pooled child boards instead of cloning fresh ones)
+impl Clone for Board {
+    fn clone(&self) -> Self {
+        simple_profiler::record_board_clone(|| self.heap_bytes());
+        Board {
+            height: self.height,
+            width: self.width,
+            food: self.food.clone(),
+            snakes: self.snakes.clone(),
+            hazards: self.hazards.clone(),
+            occupancy: None,
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        simple_profiler::record_board_clone(|| source.heap_bytes());
+        self.height = source.height;
+        self.width = source.width;
+        self.food.clone_from(&source.food);
+        self.snakes.clone_from(&source.snakes);
+        self.hazards.clone_from(&source.hazards);
+        self.occupancy = None;
+    }
+}
+
+/// What is drawn on one cell of a rendered board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoardCell {
+    Empty,
+    Food,
+    Head(usize),
+    Body(usize),
+}
+
+/// Letter of the snake at board index `idx` (a..z, then wraps)
+pub fn snake_letter(idx: usize) -> char {
+    (b'a' + (idx % 26) as u8) as char
+}
+
+impl Board {
+    /// Rendered cell contents in [y][x] order; heads win over bodies, snakes over food
+    pub fn cells(&self) -> Vec<Vec<BoardCell>> {
+        let (width, height) = (self.width.max(0) as usize, self.height as usize);
+        let mut grid = vec![vec![BoardCell::Empty; width]; height];
+        let mut put = |c: Coord, cell: BoardCell| {
+            if c.x >= 0 && c.y >= 0 && (c.x as usize) < width && (c.y as usize) < height {
+                let slot = &mut grid[c.y as usize][c.x as usize];
+                let rank = |cell: BoardCell| match cell {
+                    BoardCell::Empty => 0,
+                    BoardCell::Food => 1,
+                    BoardCell::Body(_) => 2,
+                    BoardCell::Head(_) => 3,
+                };
+                if rank(cell) > rank(*slot) {
+                    *slot = cell;
+                }
+            }
+        };
+        for &food in &self.food {
+            put(food, BoardCell::Food);
+        }
+        for (idx, snake) in self.snakes.iter().enumerate() {
+            for &segment in snake.body.iter().skip(1) {
+                put(segment, BoardCell::Body(idx));
+            }
+            if let Some(&head) = snake.body.first() {
+                put(head, BoardCell::Head(idx));
+            }
+        }
+        grid
+    }
+
+    /// Plain grid in the board_builder notation (parseable by BoardBuilder::from_ascii),
+    /// followed by one "a = id (health, length)" line per snake
+    pub fn render_ascii(&self) -> String {
+        let grid = self.cells();
+        let mut out = String::new();
+        for (y, row) in grid.iter().enumerate().rev() {
+            let tokens: Vec<String> = row
+                .iter()
+                .enumerate()
+                .map(|(x, cell)| {
+                    let mut token = match *cell {
+                        BoardCell::Empty => ".".to_string(),
+                        BoardCell::Food => "*".to_string(),
+                        BoardCell::Head(idx) => format!("{}>", snake_letter(idx)),
+                        BoardCell::Body(idx) => snake_letter(idx).to_string(),
+                    };
+                    if self.hazards.contains(&Coord { x: x as i32, y: y as i32 }) {
+                        token = if token == "." { "#".to_string() } else { token + "#" };
+                    }
+                    format!("{:<3}", token)
+                })
+                .collect();
+            out.push_str(tokens.join("").trim_end());
+            out.push('\n');
+        }
+        for (idx, snake) in self.snakes.iter().enumerate() {
+            out.push_str(&format!("{} = {} (health {}, length {})\n", snake_letter(idx), snake.id, snake.health, snake.length));
+        }
+        out
+    }
 }
 
 /// Snake representation with all state information
-#[derive(Deserialize, Serialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug)]
 pub struct Battlesnake {
     pub id: String,
     pub name: String,
@@ -34,6 +256,44 @@ pub struct Battlesnake {
     pub length: i32,
     pub latency: String,
     pub shout: Option<String>,
+    /// Squad name in squad games (snakes of the same squad are allies)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub squad: Option<String>,
+}
+
+impl Battlesnake {
+    /// True if `other` is another snake of the same (non-empty) squad
+    pub fn is_ally_of(&self, other: &Battlesnake) -> bool {
+        self.id != other.id && self.squad.as_deref().is_some_and(|squad| !squad.is_empty() && other.squad.as_deref() == Some(squad))
+    }
+}
+
+impl Clone for Battlesnake {
+    fn clone(&self) -> Self {
+        Battlesnake {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            health: self.health,
+            body: self.body.clone(),
+            head: self.head,
+            length: self.length,
+            latency: self.latency.clone(),
+            shout: self.shout.clone(),
+            squad: self.squad.clone(),
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.id.clone_from(&source.id);
+        self.name.clone_from(&source.name);
+        self.health = source.health;
+        self.body.clone_from(&source.body);
+        self.head = source.head;
+        self.length = source.length;
+        self.latency.clone_from(&source.latency);
+        self.shout.clone_from(&source.shout);
+        self.squad.clone_from(&source.squad);
+    }
 }
 
 /// 2D coordinate on the board
@@ -43,6 +303,46 @@ pub struct Coord {
     pub y: i32,
 }
 
+impl Add for Coord {
+    type Output = Coord;
+
+    fn add(self, other: Coord) -> Coord {
+        Coord { x: self.x + other.x, y: self.y + other.y }
+    }
+}
+
+impl Sub for Coord {
+    type Output = Coord;
+
+    fn sub(self, other: Coord) -> Coord {
+        Coord { x: self.x - other.x, y: self.y - other.y }
+    }
+}
+
+impl Coord {
+    /// The four adjacent cells in Direction::ALL order (may lie off the board)
+    pub fn neighbors(self) -> [Coord; 4] {
+        Direction::ALL.map(|dir| dir.apply(&self))
+    }
+
+    /// The four adjacent cells on a `width` x `height` board whose edges wrap around
+    pub fn wrapped_neighbors(self, width: i32, height: i32) -> [Coord; 4] {
+        self.neighbors().map(|c| Coord { x: c.x.rem_euclid(width), y: c.y.rem_euclid(height) })
+    }
+
+    /// Manhattan (taxicab) distance: turns needed to walk from one cell to the other
+    #[inline]
+    pub fn manhattan(self, other: Coord) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Chebyshev distance: the larger of the horizontal and vertical offsets
+    #[inline]
+    pub fn chebyshev(self, other: Coord) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+}
+
 /// Represents the four possible movement directions for a Battlesnake
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Direction {
@@ -53,10 +353,8 @@ pub enum Direction {
 }
 
 impl Direction {
-    /// Returns all possible directions
-    pub fn all() -> [Direction; 4] {
-        [Direction::Up, Direction::Down, Direction::Left, Direction::Right]
-    }
+    /// All four directions, in move-ordering index order
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
 
     /// Converts direction to string representation for API response
     pub fn as_str(&self) -> &'static str {
@@ -68,15 +366,42 @@ impl Direction {
         }
     }
 
-    /// Calculates the next coordinate when moving in this direction
-    pub fn apply(&self, coord: &Coord) -> Coord {
+    /// Offset of one step in this direction
+    pub fn delta(&self) -> Coord {
+        match self {
+            Direction::Up => Coord { x: 0, y: 1 },
+            Direction::Down => Coord { x: 0, y: -1 },
+            Direction::Left => Coord { x: -1, y: 0 },
+            Direction::Right => Coord { x: 1, y: 0 },
+        }
+    }
+
+    /// Direction of a one-step offset (None unless `delta` is a unit step)
+    pub fn from_delta(delta: Coord) -> Option<Direction> {
+        match (delta.x, delta.y) {
+            (0, 1) => Some(Direction::Up),
+            (0, -1) => Some(Direction::Down),
+            (-1, 0) => Some(Direction::Left),
+            (1, 0) => Some(Direction::Right),
+            _ => None,
+        }
+    }
+
+    /// The reverse direction (the move back onto the neck)
+    pub fn opposite(&self) -> Direction {
         match self {
-            Direction::Up => Coord { x: coord.x, y: coord.y + 1 },
-            Direction::Down => Coord { x: coord.x, y: coord.y - 1 },
-            Direction::Left => Coord { x: coord.x - 1, y: coord.y },
-            Direction::Right => Coord { x: coord.x + 1, y: coord.y },
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
         }
     }
+
+    /// Calculates the next coordinate when moving in this direction
+    #[inline]
+    pub fn apply(&self, coord: &Coord) -> Coord {
+        *coord + self.delta()
+    }
 }
 
 /// Complete game state received from the API
@@ -87,3 +412,42 @@ pub struct GameState {
     pub board: Board,
     pub you: Battlesnake,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coord_and_direction_geometry() {
+        let (a, b) = (Coord { x: 1, y: 2 }, Coord { x: 4, y: 0 });
+        assert_eq!(a + b, Coord { x: 5, y: 2 });
+        assert_eq!(b - a, Coord { x: 3, y: -2 });
+        assert_eq!((a.manhattan(b), a.chebyshev(b)), (5, 3));
+
+        for dir in Direction::ALL {
+            let next = dir.apply(&a);
+            assert_eq!(Direction::from_delta(next - a), Some(dir));
+            assert_eq!(dir.opposite().apply(&next), a);
+        }
+        assert_eq!(Direction::from_delta(b - a), None);
+
+        let corner = Coord { x: 0, y: 10 };
+        assert!(corner.neighbors().contains(&Coord { x: -1, y: 10 }));
+        let wrapped = corner.wrapped_neighbors(11, 11);
+        assert!(wrapped.contains(&Coord { x: 10, y: 10 }) && wrapped.contains(&Coord { x: 0, y: 0 }));
+    }
+
+    #[test]
+    fn test_heap_bytes_shrink_when_eliminated_bodies_are_cleared() {
+        let mut board = crate::board_builder::BoardBuilder::new(7, 7)
+            .snake("a", &[(1, 1), (1, 2), (1, 3)])
+            .snake("b", &[(5, 5), (5, 4)])
+            .build();
+        let before = board.heap_bytes();
+        assert_eq!(board.clone().heap_bytes(), before);
+
+        board.snakes[0].health = 0;
+        board.remove_eliminated();
+        assert_eq!(board.heap_bytes(), before - 3 * std::mem::size_of::<Coord>());
+    }
+}
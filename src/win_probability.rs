@@ -0,0 +1,168 @@
+// Win probability calibration
+//
+// Root scores are sums of weighted heuristic terms, so a raw threshold says little about how
+// decided a position is. A logistic curve P(win) = 1 / (1 + exp(-(slope * score + intercept)))
+// fit on self-play outcomes (see fit and the calibrate_win_probability binary) turns them into
+// probabilities. The search stops deepening once the best move is a near-certain win or loss,
+// time allocation spends less on decided positions, and stall mode starts once we are likely
+// to win. With [win_probability] disabled, timing.certain_win_threshold /
+// certain_loss_threshold and stall.min_eval_lead apply as raw score thresholds instead.
+
+use crate::config::Config;
+
+/// Ridge penalty keeping the fit finite when scores separate wins from losses perfectly
+const RIDGE: f64 = 1e-3;
+const NEWTON_STEPS: usize = 50;
+
+/// Search outcome the score already decides
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decided {
+    Win,
+    Loss,
+}
+
+/// Calibrated probability that a root score of `score` wins
+pub fn probability(score: i32, config: &Config) -> f64 {
+    let model = &config.win_probability;
+    logistic(model.slope * score as f64 + model.intercept)
+}
+
+/// Whether `score` is decided enough to stop deepening (None: keep searching)
+pub fn decided(score: i32, config: &Config) -> Option<Decided> {
+    if config.win_probability.enabled {
+        let p = probability(score, config);
+        if p >= config.win_probability.certain_win {
+            return Some(Decided::Win);
+        }
+        if p <= config.win_probability.certain_loss {
+            return Some(Decided::Loss);
+        }
+    } else {
+        if score >= config.timing.certain_win_threshold {
+            return Some(Decided::Win);
+        }
+        if score <= config.timing.certain_loss_threshold {
+            return Some(Decided::Loss);
+        }
+    }
+    None
+}
+
+/// Time allocation factor for a position whose previous root score was `score`: 1 at even
+/// odds, falling as 4p(1 - p) towards win_probability.decided_min_scale as the game is decided
+pub fn uncertainty_scale(score: Option<i32>, config: &Config) -> f64 {
+    match score {
+        Some(score) if config.win_probability.enabled => {
+            let p = probability(score, config);
+            (4.0 * p * (1.0 - p)).max(config.win_probability.decided_min_scale)
+        }
+        _ => 1.0,
+    }
+}
+
+fn logistic(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+/// Logistic fit of game outcomes on root scores
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Calibration {
+    pub slope: f64,
+    pub intercept: f64,
+    /// Samples the fit used
+    pub samples: usize,
+    /// Mean log loss of the fit over those samples
+    pub log_loss: f64,
+}
+
+/// Fits slope and intercept to (root score, won) samples by Newton's method
+/// Scores are standardized first, so the fit behaves the same at any score scale
+pub fn fit(samples: &[(i32, bool)]) -> Result<Calibration, String> {
+    let wins = samples.iter().filter(|(_, won)| *won).count();
+    if wins == 0 || wins == samples.len() {
+        return Err(format!("Need both wins and losses to calibrate ({} samples, {} wins)", samples.len(), wins));
+    }
+    let n = samples.len() as f64;
+    let mean = samples.iter().map(|&(score, _)| score as f64).sum::<f64>() / n;
+    let spread = (samples.iter().map(|&(score, _)| (score as f64 - mean).powi(2)).sum::<f64>() / n).sqrt().max(1.0);
+    let points: Vec<(f64, f64)> =
+        samples.iter().map(|&(score, won)| ((score as f64 - mean) / spread, if won { 1.0 } else { 0.0 })).collect();
+
+    let (mut w, mut b) = (0.0, 0.0);
+    for _ in 0..NEWTON_STEPS {
+        // Gradient and Hessian of the mean log loss plus RIDGE / 2 * w^2
+        let (mut gw, mut gb, mut hww, mut hwb, mut hbb) = (RIDGE * w, 0.0, RIDGE, 0.0, 0.0);
+        for &(x, y) in &points {
+            let p = logistic(w * x + b);
+            let weight = p * (1.0 - p);
+            gw += (p - y) * x / n;
+            gb += (p - y) / n;
+            hww += weight * x * x / n;
+            hwb += weight * x / n;
+            hbb += weight / n;
+        }
+        let det = hww * hbb - hwb * hwb;
+        if det.abs() < 1e-12 {
+            break;
+        }
+        let (dw, db) = ((hbb * gw - hwb * gb) / det, (hww * gb - hwb * gw) / det);
+        w -= dw;
+        b -= db;
+        if dw.abs() < 1e-9 && db.abs() < 1e-9 {
+            break;
+        }
+    }
+
+    let log_loss = points
+        .iter()
+        .map(|&(x, y)| {
+            let p = logistic(w * x + b).clamp(1e-12, 1.0 - 1e-12);
+            -(y * p.ln() + (1.0 - y) * (1.0 - p).ln())
+        })
+        .sum::<f64>()
+        / n;
+    Ok(Calibration { slope: w / spread, intercept: b - w * mean / spread, samples: samples.len(), log_loss })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_recovers_a_logistic_curve() {
+        // Outcome frequencies follow P(win) = logistic(score / 1000) exactly
+        let mut samples = Vec::new();
+        for score in (-4000..=4000).step_by(500) {
+            let wins = (logistic(score as f64 / 1000.0) * 100.0).round() as usize;
+            samples.extend(std::iter::repeat_n((score, true), wins));
+            samples.extend(std::iter::repeat_n((score, false), 100 - wins));
+        }
+        let calibration = fit(&samples).unwrap();
+        assert!((calibration.slope - 0.001).abs() < 0.00005, "{:?}", calibration);
+        assert!(calibration.intercept.abs() < 0.05, "{:?}", calibration);
+
+        assert!(fit(&[(10, true), (20, true)]).is_err());
+    }
+
+    #[test]
+    fn test_decided_positions_stop_and_get_less_time() {
+        let mut config = Config::default_hardcoded();
+        config.win_probability.enabled = true;
+        config.win_probability.slope = 0.001;
+        config.win_probability.intercept = 0.0;
+
+        assert_eq!(decided(0, &config), None);
+        assert_eq!(decided(8000, &config), Some(Decided::Win));
+        assert_eq!(decided(-8000, &config), Some(Decided::Loss));
+        assert_eq!(uncertainty_scale(Some(0), &config), 1.0);
+        assert!(uncertainty_scale(Some(2000), &config) < uncertainty_scale(Some(500), &config));
+        assert_eq!(uncertainty_scale(Some(9000), &config), config.win_probability.decided_min_scale);
+        assert_eq!(uncertainty_scale(None, &config), 1.0);
+
+        // Disabled, the raw thresholds decide
+        config.win_probability.enabled = false;
+        assert_eq!(decided(8000, &config), None);
+        assert_eq!(decided(config.timing.certain_win_threshold, &config), Some(Decided::Win));
+        assert_eq!(uncertainty_scale(Some(9000), &config), 1.0);
+    }
+}
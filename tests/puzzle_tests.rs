@@ -0,0 +1,47 @@
+//! Tactical puzzle regression tests
+//!
+//! Solves every puzzle under tests/puzzles at its fixed depth (see src/puzzles.rs) and fails
+//! if any puzzle picks a move outside its acceptable list. Puzzles marked "known_failure"
+//! document a weakness instead: they may fail, and once they pass the marker must be removed
+//! so they start guarding against regressions.
+
+use starter_snake_rust::config::Config;
+use starter_snake_rust::puzzles::{self, PuzzleOutcome};
+use std::path::PathBuf;
+
+fn solve_all() -> Vec<PuzzleOutcome> {
+    let config = Config::default_hardcoded();
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(&config.puzzles.directory);
+    puzzles::load_dir(&dir)
+        .expect("puzzles load")
+        .iter()
+        .map(|(path, puzzle)| {
+            puzzle
+                .solve(&config, config.puzzles.default_depth)
+                .unwrap_or_else(|e| panic!("{}: {}", path.display(), e))
+        })
+        .collect()
+}
+
+#[test]
+fn test_puzzles_pick_an_acceptable_move() {
+    let outcomes = solve_all();
+    assert!(!outcomes.is_empty(), "no puzzles found");
+
+    let failures: Vec<String> = outcomes
+        .iter()
+        .filter(|o| !o.passed && !o.known_failure)
+        .map(|o| format!("[{}] {}: chose {:?}, acceptable {:?}", o.tag, o.name, o.chosen, o.acceptable))
+        .collect();
+    assert!(failures.is_empty(), "puzzle regressions:\n{}", failures.join("\n"));
+
+    let fixed: Vec<&str> = outcomes
+        .iter()
+        .filter(|o| o.passed && o.known_failure)
+        .map(|o| o.name.as_str())
+        .collect();
+    assert!(fixed.is_empty(), "now passing, remove their known_failure marker: {:?}", fixed);
+
+    let summary = puzzles::summarize(&outcomes);
+    assert_eq!(summary.values().map(|s| s.total).sum::<usize>(), outcomes.len());
+}
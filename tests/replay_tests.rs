@@ -7,7 +7,8 @@
 // - Generating statistics
 
 use starter_snake_rust::config::Config;
-use starter_snake_rust::replay::ReplayEngine;
+use starter_snake_rust::puzzles::Puzzle;
+use starter_snake_rust::replay::{EntryFilter, ReplayEngine};
 use starter_snake_rust::types::Direction;
 use std::path::PathBuf;
 
@@ -294,3 +295,151 @@ fn test_replay_empty_entries() {
     let results = engine.replay_all(&[]).expect("Should handle empty entries");
     assert_eq!(results.len(), 0, "Replaying empty entries should return empty results");
 }
+
+#[test]
+fn test_fixed_depth_replay_is_reproducible() {
+    let config = Config::default_hardcoded();
+    let engine = ReplayEngine::new(config, false).with_fixed_depth(Some(3));
+
+    let entries = engine
+        .load_log_file(fixture_path("collision_avoidance.jsonl"))
+        .expect("Failed to load collision_avoidance.jsonl");
+
+    let first = engine.replay_all(&entries).expect("First replay failed");
+    let second = engine.replay_all(&entries).expect("Second replay failed");
+
+    assert_eq!(first.len(), second.len());
+    for (a, b) in first.iter().zip(second.iter()) {
+        assert_eq!(a.replayed_move, b.replayed_move, "Turn {}: move must be reproducible", a.turn);
+        assert_eq!(a.replayed_score, b.replayed_score, "Turn {}: score must be reproducible", a.turn);
+        assert_eq!(a.search_depth, b.search_depth, "Turn {}: depth must be reproducible", a.turn);
+        assert!(a.search_depth <= 3, "Turn {}: fixed depth must not be exceeded", a.turn);
+    }
+}
+
+#[test]
+fn test_parallel_replay_matches_serial_replay() {
+    let config = Config::default_hardcoded();
+    let engine = ReplayEngine::new(config, false).with_fixed_depth(Some(2));
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(3).build().unwrap();
+
+    let paths = vec![fixture_path("collision_avoidance.jsonl"), fixture_path("food_acquisition.jsonl")];
+    let games = pool.install(|| engine.replay_files(&paths, &EntryFilter::default()));
+
+    assert_eq!(games.len(), paths.len());
+    for ((path, results), expected_path) in games.iter().zip(&paths) {
+        assert_eq!(path, expected_path, "games keep the order of their paths");
+        let entries = engine.load_log_file(path).unwrap();
+        let serial = engine.replay_all(&entries).unwrap();
+        let parallel = results.as_ref().expect("replay failed");
+
+        assert_eq!(parallel.len(), serial.len());
+        for (p, s) in parallel.iter().zip(&serial) {
+            assert_eq!((p.turn, p.replayed_move, p.replayed_score), (s.turn, s.replayed_move, s.replayed_score));
+        }
+    }
+}
+
+#[test]
+fn test_compare_identical_configs_never_diverges() {
+    let config = Config::default_hardcoded();
+    let engine = ReplayEngine::new(config.clone(), false).with_fixed_depth(Some(2));
+
+    let entries = engine
+        .load_log_file(fixture_path("food_acquisition.jsonl"))
+        .expect("Failed to load food_acquisition.jsonl");
+
+    let comparison = engine.compare(&entries, &config, &config).expect("Comparison failed");
+    let replayed = engine.replay_all(&entries).expect("Replay failed");
+
+    assert_eq!(comparison.turns.len(), entries.len());
+    assert_eq!(comparison.divergent(), 0);
+    assert!(comparison.turns.iter().all(|t| t.breakdown.is_none()));
+    let matches = replayed.iter().filter(|r| r.matches).count();
+    assert_eq!(comparison.log_agreement(), (matches, matches));
+}
+
+#[test]
+fn test_what_if_forced_collision_dies_before_the_bot_line() {
+    let mut config = Config::default_hardcoded();
+    config.what_if.horizon = 10;
+    let engine = ReplayEngine::new(config, false).with_fixed_depth(Some(2));
+
+    let entries = engine
+        .load_log_file(fixture_path("1v1_self/game_01.jsonl"))
+        .expect("Failed to load 1v1_self/game_01.jsonl");
+    let entry = entries.iter().find(|e| e.turn == 30).expect("turn 30");
+
+    // Left runs into the opponent's body on turn 30
+    let report = engine.what_if(entry, Direction::Left).expect("What-if failed");
+    assert_eq!(report.forced.first_move(), Some(Direction::Left));
+    assert_eq!(report.forced.turns_survived, 0);
+    assert!(report.forced.death.is_some());
+
+    assert_ne!(report.baseline.first_move(), Some(Direction::Left));
+    assert!(report.baseline.turns_survived > 0);
+    assert!(report.survival_gain(10) < 0);
+    assert_eq!(engine.what_if(entry, Direction::Left).unwrap().baseline.moves, report.baseline.moves, "rollouts are reproducible");
+}
+
+#[test]
+fn test_logged_request_is_replayed_as_received() {
+    let config = Config::default_hardcoded();
+    let engine = ReplayEngine::new(config.clone(), false).with_fixed_depth(Some(2));
+    let mut entries = engine
+        .load_log_file(fixture_path("collision_avoidance.jsonl"))
+        .expect("Failed to load collision_avoidance.jsonl");
+
+    // The server listed the opponent first; `you` says which snake we are
+    let board = serde_json::to_value(&entries[0].board).unwrap();
+    let mut wire_board = board.clone();
+    wire_board["snakes"].as_array_mut().unwrap().reverse();
+    let request = serde_json::json!({
+        "game": { "id": "g", "ruleset": { "name": "standard" }, "timeout": 500, "source": "custom" },
+        "turn": 0,
+        "board": wire_board,
+        "you": board["snakes"][0],
+    });
+    entries[0].notes.request = Some(request.to_string());
+    entries[0].notes.response = Some("{\"move\":\"up\"}".to_string());
+
+    let state = entries[0].request_state(&config).unwrap().expect("request should parse");
+    assert_eq!(state.you.id, "snake-1");
+    let result = engine.replay_entry(&entries[0]).expect("replay from the request failed");
+    assert_eq!(result.board.snakes[0].id, "snake-2", "the request's board is searched as received");
+
+    assert!(entries[1].request_state(&config).is_none());
+    entries[1].notes.request = Some("{\"turn\":1}".to_string());
+    assert!(engine.replay_entry(&entries[1]).unwrap_err().to_string().contains("Turn 1: unreadable logged request"));
+}
+
+#[test]
+fn test_replay_as_an_opponent_and_export_its_mistakes_as_puzzles() {
+    let config = Config::default_hardcoded();
+    let engine = ReplayEngine::new(config.clone(), false).with_fixed_depth(Some(2));
+    let entries = engine
+        .load_log_file(fixture_path("1v1_loopy_bot/game_01.jsonl"))
+        .expect("Failed to load 1v1_loopy_bot/game_01.jsonl");
+    let entries = &entries[..12];
+    let opponent = entries[0].board.snakes[1].id.clone();
+
+    let results = engine.replay_as(entries, &opponent).expect("replay as the opponent failed");
+    assert_eq!(results.len(), entries.len() - 1, "every turn but the last has a next board");
+    for (result, next) in results.iter().zip(&entries[1..]) {
+        assert_eq!(result.our_id, opponent);
+        let head = |board: &starter_snake_rust::types::Board| board.snakes.iter().find(|s| s.id == opponent).unwrap().body[0];
+        assert_eq!(result.original_move.apply(&head(&result.board)), head(&next.board), "turn {}", result.turn);
+    }
+    assert!(engine.replay_as(entries, "nobody").is_err());
+
+    // A disagreement becomes a puzzle candidate that loads and solves like any other
+    let result = &results[0];
+    let puzzle = Puzzle::from_replay(result, "opponent turn 0".to_string(), "opponent-mistake");
+    let path = std::env::temp_dir().join(format!("replay_as_puzzle_{}.json", std::process::id()));
+    std::fs::write(&path, serde_json::to_string_pretty(&puzzle).unwrap()).unwrap();
+    let loaded = Puzzle::from_file(&path).expect("exported puzzle loads");
+    std::fs::remove_file(&path).ok();
+    assert_eq!((loaded.you.as_str(), loaded.turn), (opponent.as_str(), 0));
+    let outcome = loaded.solve(&config, 2).unwrap();
+    assert!(outcome.passed, "the replayed move solves its own puzzle");
+}
@@ -0,0 +1,272 @@
+//! Rules-compliance oracle tests
+//!
+//! Replays every recorded game under tests/fixtures and checks, for each pair of consecutive
+//! logged turns, that resolving the turn locally reproduces the board the engine sent next:
+//! - rules::resolve_turn must match exactly (bodies, health, lengths, eliminations, eaten food)
+//! - Bot::simulate_turn (the search's model) must agree on who survives, their bodies (stacked
+//!   tails included) and lengths
+//!
+//! Moves are recovered from head displacement. Snakes eliminated during the turn are absent
+//! from the next board, so their moves are unknown: every combination is tried and the turn
+//! passes if one of them reproduces the logged board. Food spawned by the engine after the
+//! turn is not modelled, so the logged food may contain extra items.
+//!
+//! Recorded games rarely show the collisions bots avoid, so random rollouts on a crowded
+//! board (seeded, every move except into the neck) check Bot::simulate_turn against
+//! rules::resolve_turn turn by turn: survivors, bodies, health, lengths and food.
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use starter_snake_rust::board_builder::BoardBuilder;
+use starter_snake_rust::bot::Bot;
+use starter_snake_rust::config::Config;
+use starter_snake_rust::replay::LogEntry;
+use starter_snake_rust::rules::{self, RulesSettings};
+use starter_snake_rust::types::{Board, Coord, Direction};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures")
+}
+
+/// All game logs (game_*.jsonl) below the fixtures directory
+fn game_logs(dir: &Path) -> Vec<PathBuf> {
+    let mut logs = Vec::new();
+    for entry in fs::read_dir(dir).expect("fixtures directory").flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            logs.extend(game_logs(&path));
+        } else if path.file_name().and_then(|n| n.to_str()).is_some_and(|n| n.starts_with("game_") && n.ends_with(".jsonl")) {
+            logs.push(path);
+        }
+    }
+    logs.sort();
+    logs
+}
+
+/// One board per turn (logs written by several of our snakes repeat each turn)
+fn boards_by_turn(path: &Path) -> BTreeMap<i32, Board> {
+    let mut boards = BTreeMap::new();
+    for line in fs::read_to_string(path).expect("readable log").lines().filter(|l| !l.trim().is_empty()) {
+        let entry: LogEntry = serde_json::from_str(line).expect("valid log entry");
+        boards.entry(entry.turn).or_insert(entry.board);
+    }
+    boards
+}
+
+/// Moves of the snakes that survived into `next`, from head displacement
+fn survivor_moves(board: &Board, next: &Board) -> HashMap<String, Direction> {
+    next.snakes
+        .iter()
+        .filter_map(|after| {
+            let before = board.snakes.iter().find(|s| s.id == after.id)?;
+            let dir = rules::direction_between(before.body[0], after.body[0])
+                .unwrap_or_else(|| panic!("snake {} did not move one step", after.id));
+            Some((after.id.clone(), dir))
+        })
+        .collect()
+}
+
+/// Every assignment of a direction to each snake in `ids`
+fn move_combinations(ids: &[String]) -> Vec<Vec<(String, Direction)>> {
+    let mut combos = vec![vec![]];
+    for id in ids {
+        let mut extended = Vec::with_capacity(combos.len() * 4);
+        for combo in &combos {
+            for dir in Direction::ALL {
+                let mut next: Vec<(String, Direction)> = combo.clone();
+                next.push((id.clone(), dir));
+                extended.push(next);
+            }
+        }
+        combos = extended;
+    }
+    combos
+}
+
+/// Why `actual` differs from the logged `expected` board (None when they agree)
+fn rules_mismatch(actual: &Board, expected: &Board) -> Option<String> {
+    let ids = |b: &Board| {
+        let mut ids: Vec<String> = b.snakes.iter().map(|s| s.id.clone()).collect();
+        ids.sort();
+        ids
+    };
+    if ids(actual) != ids(expected) {
+        return Some(format!("survivors {:?}, logged {:?}", ids(actual), ids(expected)));
+    }
+    for snake in &actual.snakes {
+        let logged = expected.snakes.iter().find(|s| s.id == snake.id).unwrap();
+        if (&snake.body, snake.health, snake.length) != (&logged.body, logged.health, logged.length) {
+            return Some(format!(
+                "snake {}: body {:?} health {} length {}, logged body {:?} health {} length {}",
+                snake.id, snake.body, snake.health, snake.length, logged.body, logged.health, logged.length
+            ));
+        }
+    }
+    if let Some(food) = actual.food.iter().find(|f| !expected.food.contains(f)) {
+        return Some(format!("food at {:?} should have been eaten", food));
+    }
+    None
+}
+
+/// Why the search model disagrees with the logged board on survivors or their bodies
+/// (stacked tails included) and lengths
+fn bot_mismatch(actual: &Board, expected: &Board) -> Option<String> {
+    let mut alive: Vec<_> = actual.snakes.iter().filter(|s| s.health > 0).map(|s| (s.id.clone(), s.body.clone(), s.length)).collect();
+    let mut logged: Vec<_> = expected.snakes.iter().map(|s| (s.id.clone(), s.body.clone(), s.length)).collect();
+    alive.sort_by(|a, b| a.0.cmp(&b.0));
+    logged.sort_by(|a, b| a.0.cmp(&b.0));
+    (alive != logged).then(|| format!("alive {:?}, logged {:?}", alive, logged))
+}
+
+/// Checks one turn and returns the mismatches of (rules oracle, search model)
+fn check_turn(board: &Board, next: &Board, settings: &RulesSettings, config: &Config) -> (Option<String>, Option<String>) {
+    let known = survivor_moves(board, next);
+    let vanished: Vec<String> = board.snakes.iter().filter(|s| !known.contains_key(&s.id)).map(|s| s.id.clone()).collect();
+
+    let mut rules_error = None;
+    let mut bot_error = None;
+    for combo in move_combinations(&vanished) {
+        let mut moves = known.clone();
+        moves.extend(combo);
+
+        let mut resolved = board.clone();
+        rules::resolve_turn(&mut resolved, &moves, settings);
+        if let Some(e) = rules_mismatch(&resolved, next) {
+            rules_error.get_or_insert(e);
+            continue;
+        }
+
+        // These moves explain the logged board; the search model must agree with them
+        let mut simulated = board.clone();
+        Bot::simulate_turn(&mut simulated, &moves, config);
+        match bot_mismatch(&simulated, next) {
+            None => return (None, None),
+            Some(e) => {
+                bot_error.get_or_insert(e);
+            }
+        }
+    }
+    match bot_error {
+        Some(e) => (None, Some(e)),
+        None => (rules_error, None),
+    }
+}
+
+#[test]
+fn test_resolve_turn_reproduces_recorded_games() {
+    let config = Config::default_hardcoded();
+    let settings = RulesSettings::from_config(&config);
+    let logs = game_logs(&fixtures_dir());
+    assert!(!logs.is_empty(), "no recorded games found");
+
+    let mut checked = 0;
+    let mut rules_failures = Vec::new();
+    let mut bot_failures = Vec::new();
+    for log in &logs {
+        let boards = boards_by_turn(log);
+        for (turn, board) in &boards {
+            let Some(next) = boards.get(&(turn + 1)) else { continue };
+            checked += 1;
+            let (rules_error, bot_error) = check_turn(board, next, &settings, &config);
+            if let Some(e) = rules_error {
+                rules_failures.push(format!("{} turn {}: {}", log.display(), turn, e));
+            }
+            if let Some(e) = bot_error {
+                bot_failures.push(format!("{} turn {}: {}", log.display(), turn, e));
+            }
+        }
+    }
+
+    assert!(checked > 0);
+    assert!(
+        rules_failures.is_empty(),
+        "rules::resolve_turn diverged on {} of {} turns, first: {}",
+        rules_failures.len(),
+        checked,
+        rules_failures[0]
+    );
+    assert!(
+        bot_failures.is_empty(),
+        "Bot::simulate_turn diverged on {} of {} turns, first: {}",
+        bot_failures.len(),
+        checked,
+        bot_failures[0]
+    );
+}
+
+/// Random 7x7 position: `players` snakes of three stacked segments (as at the start of a
+/// game) on distinct cells, with random health, and five food
+fn random_board(rng: &mut StdRng, players: usize) -> Board {
+    let mut cells: Vec<(i32, i32)> = (0..7).flat_map(|x| (0..7).map(move |y| (x, y))).collect();
+    cells.shuffle(rng);
+    let mut builder = BoardBuilder::new(7, 7);
+    for (i, &cell) in cells.iter().take(players).enumerate() {
+        let id = format!("s{}", i);
+        builder = builder.snake(&id, &[cell; 3]).health(&id, rng.random_range(1..=100));
+    }
+    builder.food(&cells[players..players + 5]).build()
+}
+
+/// A random move for every snake, never back into its own neck
+fn random_moves(board: &Board, rng: &mut StdRng) -> HashMap<String, Direction> {
+    board
+        .snakes
+        .iter()
+        .map(|snake| {
+            let options: Vec<Direction> = Direction::ALL.iter().copied().filter(|d| snake.body.get(1) != Some(&d.apply(&snake.body[0]))).collect();
+            (snake.id.clone(), options[rng.random_range(0..options.len())])
+        })
+        .collect()
+}
+
+/// Why the search model's turn differs from the oracle's, including health and food
+fn rollout_mismatch(actual: &Board, expected: &Board) -> Option<String> {
+    let mut alive: Vec<_> = actual.snakes.iter().filter(|s| s.health > 0).map(|s| (s.id.clone(), s.body.clone(), s.health, s.length)).collect();
+    let mut logged: Vec<_> = expected.snakes.iter().map(|s| (s.id.clone(), s.body.clone(), s.health, s.length)).collect();
+    alive.sort_by(|a, b| a.0.cmp(&b.0));
+    logged.sort_by(|a, b| a.0.cmp(&b.0));
+    if alive != logged {
+        return Some(format!("alive {:?}, oracle {:?}", alive, logged));
+    }
+    let same_food = actual.food.len() == expected.food.len() && actual.food.iter().all(|f| expected.food.contains(f));
+    (!same_food).then(|| format!("food {:?}, oracle {:?}", actual.food, expected.food))
+}
+
+#[test]
+fn test_simulate_turn_matches_the_oracle_on_random_rollouts() {
+    let config = Config::default_hardcoded();
+    let settings = RulesSettings::from_config(&config);
+    let mut turns = 0;
+    let mut eliminations = 0;
+    for seed in 0..400u64 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut board = random_board(&mut rng, 2 + (seed % 3) as usize);
+        for turn in 0..60 {
+            if board.snakes.len() < 2 {
+                break;
+            }
+            let moves = random_moves(&board, &mut rng);
+            let mut simulated = board.clone();
+            Bot::simulate_turn(&mut simulated, &moves, &config);
+            let before = board.clone();
+            eliminations += rules::resolve_turn(&mut board, &moves, &settings).len();
+            if let Some(e) = rollout_mismatch(&simulated, &board) {
+                panic!("seed {} turn {}: {}\nboard {:?}\nmoves {:?}", seed, turn, e, before, moves);
+            }
+            turns += 1;
+
+            // Keep some food around so snakes keep eating into each other
+            if rng.random_bool(0.3) {
+                let cell = Coord { x: rng.random_range(0..7), y: rng.random_range(0..7) };
+                if !board.food.contains(&cell) && !board.snakes.iter().any(|s| s.body.contains(&cell)) {
+                    board.food.push(cell);
+                }
+            }
+        }
+    }
+    assert!(turns > 1000 && eliminations > 500, "{} turns, {} eliminations", turns, eliminations);
+}
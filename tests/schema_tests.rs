@@ -0,0 +1,146 @@
+// Backward-compatibility tests for the versioned result schema
+//
+// The golden fixture under tests/fixtures/schema/ is what external consumers
+// (dashboards, notebooks, web UI) rely on. If one of these tests fails, the change
+// broke the V1 contract: add a new versioned type instead of editing V1.
+
+use serde_json::Value;
+use starter_snake_rust::bot::DetailedScore;
+use starter_snake_rust::replay::LogEntry;
+use starter_snake_rust::schema::{ScoreBreakdownV1, SearchResultV1, SEARCH_RESULT_SCHEMA_VERSION};
+use starter_snake_rust::types::Direction;
+use std::fs;
+use std::path::PathBuf;
+
+fn golden_v1() -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join("schema")
+        .join("search_result_v1.json");
+    fs::read_to_string(path).expect("Failed to read golden schema fixture")
+}
+
+fn sorted_keys(value: &Value) -> Vec<String> {
+    let mut keys: Vec<String> = value.as_object().expect("expected a JSON object").keys().cloned().collect();
+    keys.sort();
+    keys
+}
+
+#[test]
+fn test_golden_v1_document_still_parses() {
+    let result: SearchResultV1 = serde_json::from_str(&golden_v1()).expect("V1 golden document must parse");
+
+    assert_eq!(result.schema_version, 1);
+    assert_eq!(result.turn, 42);
+    assert_eq!(result.chosen_move, "left");
+    assert_eq!(result.score, -1250);
+    assert_eq!(result.depth, 7);
+    assert_eq!(result.time_ms, 318);
+    assert_eq!(result.nodes, Some(184233));
+    assert_eq!(result.breakdown.as_ref().map(|b| b.head_collision), Some(-2500));
+}
+
+#[test]
+fn test_v1_field_names_are_stable() {
+    let golden: Value = serde_json::from_str(&golden_v1()).unwrap();
+    let parsed: SearchResultV1 = serde_json::from_value(golden.clone()).unwrap();
+    let reserialized = serde_json::to_value(&parsed).unwrap();
+
+    assert_eq!(sorted_keys(&reserialized), sorted_keys(&golden));
+    assert_eq!(sorted_keys(&reserialized["breakdown"]), sorted_keys(&golden["breakdown"]));
+    assert_eq!(reserialized, golden, "round trip must reproduce the golden document exactly");
+}
+
+#[test]
+fn test_optional_fields_may_be_absent() {
+    let minimal = r#"{"schema_version":1,"turn":3,"move":"up","score":10,"depth":2,"time_ms":5}"#;
+    let result: SearchResultV1 = serde_json::from_str(minimal).expect("optional fields must default");
+
+    assert_eq!(result.nodes, None);
+    assert_eq!(result.breakdown, None);
+
+    let value = serde_json::to_value(&result).unwrap();
+    assert!(value.get("nodes").is_none(), "absent optionals are omitted, not null");
+}
+
+#[test]
+fn test_constructor_emits_current_version_and_lowercase_move() {
+    let result = SearchResultV1::new(7, Direction::Right, 99, 5, 120).with_nodes(1000);
+
+    assert_eq!(result.schema_version, SEARCH_RESULT_SCHEMA_VERSION);
+    assert_eq!(serde_json::to_value(&result).unwrap()["move"], "right");
+}
+
+#[test]
+fn test_breakdown_mirrors_detailed_score() {
+    let detailed = DetailedScore {
+        total: 1,
+        survival: 2,
+        health: 3,
+        space: 4,
+        control: 5,
+        attack: 6,
+        length: 7,
+        head_collision: 8,
+        wall_penalty: 9,
+        center_bias: 10,
+        articulation: 11,
+        ..DetailedScore::default()
+    };
+    let breakdown = ScoreBreakdownV1::from(&detailed);
+
+    assert_eq!(breakdown.total, 1);
+    assert_eq!(breakdown.center_bias, 10);
+    assert_eq!(breakdown.articulation, Some(11));
+}
+
+#[test]
+fn test_log_entries_with_search_telemetry_load() {
+    let line = r#"{"turn":12,"chosen_move":"left","board":{"height":11,"width":11,"food":[],"snakes":[],"hazards":[]},"timestamp":"2025-01-01T00:00:00Z","result":{"schema_version":1,"turn":12,"move":"left","score":340,"depth":6,"time_ms":210,"nodes":5120,"tt_probes":400,"tt_hits":100,"pv":["left","up","left"]}}"#;
+    let entry: LogEntry = serde_json::from_str(line).expect("telemetry log lines must load");
+    let result = entry.result.expect("result present");
+
+    assert_eq!(result.tt_hit_rate(), Some(0.25));
+    assert_eq!(result.pv.as_deref(), Some(&["left".to_string(), "up".to_string(), "left".to_string()][..]));
+
+    let built = SearchResultV1::new(12, Direction::Left, 340, 6, 210)
+        .with_nodes(5120)
+        .with_tt_stats(400, 100)
+        .with_pv(&[Direction::Left, Direction::Up, Direction::Left]);
+    assert_eq!(built, result);
+    assert_eq!(SearchResultV1::new(0, Direction::Up, 0, 1, 0).with_pv(&[]).pv, None);
+}
+
+#[test]
+fn test_candidate_table_groups_root_scores_by_depth() {
+    // A re-searched move keeps its last score; depth 3 was cut short after one move
+    let root_scores = [
+        (2, Direction::Left, 50),
+        (2, Direction::Up, 80),
+        (2, Direction::Up, 70),
+        (2, Direction::Right, -10),
+        (3, Direction::Up, 90),
+    ];
+    let result = SearchResultV1::new(4, Direction::Up, 90, 3, 120).with_candidates(&root_scores);
+    let json = serde_json::to_value(&result).unwrap();
+    assert_eq!(
+        json["candidates"],
+        serde_json::json!([
+            { "depth": 2, "scores": [{ "move": "up", "score": 70 }, { "move": "left", "score": 50 }, { "move": "right", "score": -10 }] },
+            { "depth": 3, "scores": [{ "move": "up", "score": 90 }] },
+        ])
+    );
+    assert_eq!(result.decision_margin(), Some(20), "depth 3 scored one move, so depth 2 decides");
+
+    let roundtrip: SearchResultV1 = serde_json::from_value(json).unwrap();
+    assert_eq!(roundtrip, result);
+    assert_eq!(SearchResultV1::new(0, Direction::Up, 0, 1, 0).with_candidates(&[]).candidates, None);
+}
+
+#[test]
+fn test_log_entries_without_result_still_load() {
+    let line = r#"{"turn":0,"chosen_move":"up","board":{"height":11,"width":11,"food":[],"snakes":[],"hazards":[]},"timestamp":"2025-01-01T00:00:00Z"}"#;
+    let entry: LogEntry = serde_json::from_str(line).expect("pre-schema log lines must keep loading");
+    assert!(entry.result.is_none());
+}